@@ -68,6 +68,14 @@
 //!
 //! This will select a field on a component and pass it to a [`Curve`] with a type that matches the field.
 //!
+//! ## Reflected Fields
+//!
+//! A [`ReflectedField`], in combination with [`AnimatableCurve`], animates a field of any reflected
+//! component given its [reflection path], such as `"color.alpha"` or `"intensity"`. Unlike
+//! [`animated_field`], the component and field can be chosen at runtime using the
+//! [`AppTypeRegistry`], which allows data-driven authoring of animations (e.g. from asset files
+//! or glTF extras).
+//!
 //! ## Animatable Properties
 //!
 //! Animation of arbitrary aspects of entities can be accomplished using [`AnimatableProperty`] in
@@ -82,6 +90,8 @@
 //! [`AnimationClip`]: crate::AnimationClip
 //! [there]: AnimatableProperty
 //! [`animated_field`]: crate::animated_field
+//! [reflection path]: bevy_reflect::GetPath
+//! [`AppTypeRegistry`]: bevy_ecs::reflect::AppTypeRegistry
 
 use core::{
     any::TypeId,
@@ -96,14 +106,22 @@ use crate::{
     prelude::{Animatable, BlendInput},
     AnimationEntityMut, AnimationEvaluationError,
 };
-use bevy_ecs::component::{Component, Mutable};
+use alloc::sync::Arc;
+use bevy_ecs::{
+    component::{Component, Mutable},
+    reflect::ReflectComponent,
+};
 use bevy_math::curve::{
     cores::{UnevenCore, UnevenCoreError},
     Curve, Interval,
 };
 use bevy_platform::hash::Hashed;
-use bevy_reflect::{FromReflect, Reflect, Reflectable, TypeInfo, Typed};
+use bevy_reflect::{
+    FromReflect, FromType, GetPath, ParsedPath, Reflect, Reflectable, TypeInfo, TypePath,
+    TypeRegistry, Typed,
+};
 use downcast_rs::{impl_downcast, Downcast};
+use thiserror::Error;
 
 /// A trait for exposing a value in an entity so that it can be animated.
 ///
@@ -272,6 +290,179 @@ impl<C: Typed, P, F: Fn(&mut C) -> &mut P + 'static> AnimatedField<C, P, F> {
     }
 }
 
+/// A field of a reflected [`Component`] that can be animated, given its [reflection path].
+///
+/// This makes it possible to animate any numeric or otherwise [`Animatable`] value nested inside a
+/// component, e.g. the alpha of a color or the intensity of a light, without writing an accessor
+/// function. The component can be given as a type with [`ReflectedField::new`], or looked up by name
+/// in a [`TypeRegistry`] with [`ReflectedField::from_registry`] when animations are authored as data.
+///
+/// `A` is the type of the [`Animatable`] field. Evaluating the animation fails with
+/// [`AnimationEvaluationError::PropertyNotPresent`] if the path doesn't lead to a value of type `A`.
+///
+/// ```
+/// # use bevy_animation::animation_curves::{AnimatableCurve, AnimatableKeyframeCurve, ReflectedField};
+/// # use bevy_ecs::{component::Component, reflect::ReflectComponent};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Glow {
+///     emissive: [f32; 3],
+///     intensity: f32,
+/// }
+///
+/// let property = ReflectedField::<f32>::new::<Glow>("intensity").unwrap();
+/// let curve = AnimatableKeyframeCurve::new([(0.0, 0.0), (1.0, 10.0)]).unwrap();
+/// let animation = AnimatableCurve::new(property, curve);
+///
+/// let red_channel = ReflectedField::<f32>::new::<Glow>("emissive[0]").unwrap();
+/// ```
+///
+/// Curves animating the same component field through an [`AnimatedField`] and a [`ReflectedField`]
+/// are evaluated separately, and aren't blended together.
+///
+/// [reflection path]: bevy_reflect::GetPath
+pub struct ReflectedField<A> {
+    reflect_component: ReflectComponent,
+    component_type_id: TypeId,
+    path: ParsedPath,
+    /// A pre-hashed (component-type-id, reflection-path) pair, uniquely identifying a component field
+    evaluator_id: Hashed<(TypeId, Arc<str>)>,
+    marker: PhantomData<fn() -> A>,
+}
+
+/// An error that occurs when creating a [`ReflectedField`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ReflectedFieldError {
+    /// The reflection path couldn't be parsed.
+    #[error("invalid reflection path `{path}`: {error}")]
+    InvalidPath {
+        /// The invalid path.
+        path: String,
+        /// The description of the parsing error.
+        error: String,
+    },
+    /// No type with this name was registered.
+    #[error("no type named `{0}` is registered")]
+    UnknownType(String),
+    /// The type was registered without `#[reflect(Component)]`.
+    #[error("the type `{0}` is not a reflected component")]
+    NotAComponent(String),
+}
+
+impl<A> ReflectedField<A> {
+    /// Creates a [`ReflectedField`] animating the value at `path` inside the component `C`.
+    ///
+    /// Returns an error if the path can't be parsed. Whether the path leads to a value of type `A`
+    /// is only checked when the animation is evaluated.
+    pub fn new<C>(path: &str) -> Result<Self, ReflectedFieldError>
+    where
+        C: Component<Mutability = Mutable> + Reflect + TypePath,
+    {
+        Self::from_reflect_component(
+            <ReflectComponent as FromType<C>>::from_type(),
+            TypeId::of::<C>(),
+            path,
+        )
+    }
+
+    /// Creates a [`ReflectedField`] animating the value at `path` inside the component with the
+    /// given `component` name, looked up in `registry`.
+    ///
+    /// The component can be named by its full [type path](TypePath::type_path), or by its
+    /// [short type path](TypePath::short_type_path) if it isn't ambiguous. It must be registered
+    /// with `#[reflect(Component)]`.
+    pub fn from_registry(
+        registry: &TypeRegistry,
+        component: &str,
+        path: &str,
+    ) -> Result<Self, ReflectedFieldError> {
+        let registration = registry
+            .get_with_type_path(component)
+            .or_else(|| registry.get_with_short_type_path(component))
+            .ok_or_else(|| ReflectedFieldError::UnknownType(component.into()))?;
+        let reflect_component = registration
+            .data::<ReflectComponent>()
+            .ok_or_else(|| ReflectedFieldError::NotAComponent(component.into()))?;
+        Self::from_reflect_component(reflect_component.clone(), registration.type_id(), path)
+    }
+
+    fn from_reflect_component(
+        reflect_component: ReflectComponent,
+        component_type_id: TypeId,
+        path: &str,
+    ) -> Result<Self, ReflectedFieldError> {
+        let path = ParsedPath::parse(path).map_err(|error| ReflectedFieldError::InvalidPath {
+            path: path.into(),
+            error: error.to_string(),
+        })?;
+        // Paths are compared in their canonical form, so that e.g. `field` and `.field` animate the
+        // same property.
+        let canonical_path: Arc<str> = path.to_string().into();
+        Ok(Self {
+            reflect_component,
+            component_type_id,
+            path,
+            evaluator_id: Hashed::new((component_type_id, canonical_path)),
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the reflection path of the animated field, inside the component.
+    pub fn path(&self) -> &ParsedPath {
+        &self.path
+    }
+}
+
+impl<A> Clone for ReflectedField<A> {
+    fn clone(&self) -> Self {
+        Self {
+            reflect_component: self.reflect_component.clone(),
+            component_type_id: self.component_type_id,
+            path: self.path.clone(),
+            evaluator_id: self.evaluator_id.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A> Debug for ReflectedField<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReflectedField")
+            .field("component_type_id", &self.component_type_id)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<A> AnimatableProperty for ReflectedField<A>
+where
+    A: Animatable + Reflect,
+{
+    type Property = A;
+
+    fn get_mut<'a>(
+        &self,
+        entity: &'a mut AnimationEntityMut,
+    ) -> Result<&'a mut A, AnimationEvaluationError> {
+        let component = self.reflect_component.reflect_mut(entity).ok_or(
+            AnimationEvaluationError::ComponentNotPresent(self.component_type_id),
+        )?;
+        component
+            .into_inner()
+            .reflect_path_mut(&self.path)
+            .ok()
+            .and_then(|field| field.try_downcast_mut::<A>())
+            .ok_or(AnimationEvaluationError::PropertyNotPresent(
+                TypeId::of::<A>(),
+            ))
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        EvaluatorId::ComponentPath(&self.evaluator_id)
+    }
+}
+
 /// This trait collects the additional requirements on top of [`Curve<T>`] needed for a
 /// curve to be used as an [`AnimationCurve`].
 pub trait AnimationCompatibleCurve<T>: Curve<T> + Debug + Clone + Reflectable {}
@@ -613,6 +804,10 @@ pub enum EvaluatorId<'a> {
     // IMPLEMENTATION NOTE: The Hashed<(TypeId, usize) is intentionally cheap to clone, as it will be cloned per frame by the evaluator
     // Switching the field index `usize` for something like a field name `String` would probably be too expensive to justify
     ComponentField(&'a Hashed<(TypeId, usize)>),
+    /// Corresponds to a value at a reflection path inside a specific component type.
+    /// The `TypeId` should correspond to the component type, and the string to the
+    /// canonical form of the [`ParsedPath`] of the value.
+    ComponentPath(&'a Hashed<(TypeId, Arc<str>)>),
     /// Corresponds to a custom property of a given type. This should be the [`TypeId`]
     /// of the custom [`AnimatableProperty`].
     Type(TypeId),
@@ -807,4 +1002,58 @@ mod tests {
         let _ = AnimatedField::new_unchecked("1", |b: &mut B| &mut b.1);
         let _ = AnimatedField::new_unchecked("2", |b: &mut B| &mut b.2);
     }
+
+    #[test]
+    fn test_reflected_field() {
+        use bevy_ecs::{reflect::AppTypeRegistry, world::World};
+
+        #[derive(Clone, Debug, Component, Reflect)]
+        #[reflect(Component)]
+        struct Light {
+            intensity: f32,
+            color: [f32; 3],
+        }
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Light>();
+        let entity = world
+            .spawn(Light {
+                intensity: 1.0,
+                color: [1.0, 1.0, 1.0],
+            })
+            .id();
+
+        let intensity = ReflectedField::<f32>::new::<Light>("intensity").unwrap();
+        let green =
+            ReflectedField::<f32>::from_registry(&registry.read(), "Light", "color[1]").unwrap();
+        let mut query = world.query::<AnimationEntityMut>();
+        let mut entity_mut = query.get_mut(&mut world, entity).unwrap();
+        *intensity.get_mut(&mut entity_mut).unwrap() = 2.0;
+        *green.get_mut(&mut entity_mut).unwrap() = 0.5;
+        let light = world.get::<Light>(entity).unwrap();
+        assert_eq!(light.intensity, 2.0);
+        assert_eq!(light.color, [1.0, 0.5, 1.0]);
+
+        // Mismatched types are reported when evaluating.
+        let mut entity_mut = query.get_mut(&mut world, entity).unwrap();
+        let wrong_type = ReflectedField::<f64>::new::<Light>("intensity").unwrap();
+        assert!(matches!(
+            wrong_type.get_mut(&mut entity_mut),
+            Err(AnimationEvaluationError::PropertyNotPresent(_))
+        ));
+
+        // Equivalent paths share an evaluator, so that they can be blended.
+        let dotted = ReflectedField::<f32>::new::<Light>(".intensity").unwrap();
+        assert!(matches!(
+            (intensity.evaluator_id(), dotted.evaluator_id()),
+            (EvaluatorId::ComponentPath(a), EvaluatorId::ComponentPath(b)) if a == b
+        ));
+
+        assert_eq!(
+            ReflectedField::<f32>::from_registry(&registry.read(), "Missing", "intensity")
+                .unwrap_err(),
+            ReflectedFieldError::UnknownType("Missing".into())
+        );
+    }
 }
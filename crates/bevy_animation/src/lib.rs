@@ -722,6 +722,8 @@ pub struct AnimationEvaluationState {
 struct AnimationCurveEvaluators {
     component_property_curve_evaluators:
         PreHashMap<(TypeId, usize), Box<dyn AnimationCurveEvaluator>>,
+    component_path_curve_evaluators:
+        PreHashMap<(TypeId, Arc<str>), Box<dyn AnimationCurveEvaluator>>,
     type_id_curve_evaluators: TypeIdMap<Box<dyn AnimationCurveEvaluator>>,
 }
 
@@ -732,6 +734,9 @@ impl AnimationCurveEvaluators {
             EvaluatorId::ComponentField(component_property) => self
                 .component_property_curve_evaluators
                 .get_mut(component_property),
+            EvaluatorId::ComponentPath(component_path) => {
+                self.component_path_curve_evaluators.get_mut(component_path)
+            }
             EvaluatorId::Type(type_id) => self.type_id_curve_evaluators.get_mut(&type_id),
         }
         .map(|e| &mut **e)
@@ -747,6 +752,9 @@ impl AnimationCurveEvaluators {
             EvaluatorId::ComponentField(component_property) => &mut **self
                 .component_property_curve_evaluators
                 .get_or_insert_with(component_property, func),
+            EvaluatorId::ComponentPath(component_path) => &mut **self
+                .component_path_curve_evaluators
+                .get_or_insert_with(component_path, func),
             EvaluatorId::Type(type_id) => match self.type_id_curve_evaluators.entry(type_id) {
                 bevy_platform::collections::hash_map::Entry::Occupied(occupied_entry) => {
                     &mut **occupied_entry.into_mut()
@@ -762,6 +770,7 @@ impl AnimationCurveEvaluators {
 #[derive(Default)]
 struct CurrentEvaluators {
     component_properties: PreHashMap<(TypeId, usize), ()>,
+    component_paths: PreHashMap<(TypeId, Arc<str>), ()>,
     type_ids: TypeIdMap<()>,
 }
 
@@ -770,6 +779,7 @@ impl CurrentEvaluators {
         self.component_properties
             .keys()
             .map(EvaluatorId::ComponentField)
+            .chain(self.component_paths.keys().map(EvaluatorId::ComponentPath))
             .chain(self.type_ids.keys().copied().map(EvaluatorId::Type))
     }
 
@@ -781,6 +791,10 @@ impl CurrentEvaluators {
             (visit)(EvaluatorId::ComponentField(&key))?;
         }
 
+        for (key, _) in self.component_paths.drain() {
+            (visit)(EvaluatorId::ComponentPath(&key))?;
+        }
+
         for (key, _) in self.type_ids.drain() {
             (visit)(EvaluatorId::Type(key))?;
         }
@@ -794,6 +808,9 @@ impl CurrentEvaluators {
             EvaluatorId::ComponentField(component_property) => {
                 self.component_properties.insert(*component_property, ());
             }
+            EvaluatorId::ComponentPath(component_path) => {
+                self.component_paths.insert(component_path.clone(), ());
+            }
             EvaluatorId::Type(type_id) => {
                 self.type_ids.insert(type_id, ());
             }
@@ -1,5 +1,7 @@
 use crate::{Asset, AssetId, AssetLoadError, AssetPath, UntypedAssetId};
+use alloc::vec::Vec;
 use bevy_ecs::message::Message;
+use bevy_platform::time::Instant;
 use bevy_reflect::Reflect;
 use core::fmt::Debug;
 
@@ -44,6 +46,89 @@ impl<A: Asset> From<&AssetLoadFailedEvent<A>> for UntypedAssetLoadFailedEvent {
     }
 }
 
+/// A [`Message`] emitted when a specific [`Asset`] has been hot-reloaded, because its file or the
+/// file of one of its loader dependencies changed.
+///
+/// This is emitted once the new version of the asset is loaded, alongside
+/// [`AssetEvent::Modified`], and lets apps react to specific changes, such as rebuilding colliders
+/// only when a given mesh is reloaded. Reloads requested with
+/// [`AssetServer::reload`](crate::AssetServer::reload) don't emit this message.
+///
+/// For an untyped equivalent, see [`UntypedAssetReloadedEvent`].
+#[derive(Message, Clone, Debug)]
+pub struct AssetReloadedEvent<A: Asset> {
+    /// The stable identifier of the reloaded asset.
+    pub id: AssetId<A>,
+    /// The path of the reloaded asset.
+    pub path: AssetPath<'static>,
+    /// The path whose change caused the reload. This is [`path`](Self::path) itself, unless the
+    /// asset was reloaded because one of its loader dependencies changed.
+    pub changed_path: AssetPath<'static>,
+    /// When the previous change to [`changed_path`](Self::changed_path) was detected, or [`None`]
+    /// if this is the first change since the app started.
+    ///
+    /// Asset sources don't expose file timestamps, so this is the time at which the asset server
+    /// was notified of the change.
+    pub previous_modified_at: Option<Instant>,
+    /// When the change to [`changed_path`](Self::changed_path) was detected.
+    pub modified_at: Instant,
+    /// The assets that were also reloaded because they depend on this asset in their loader.
+    ///
+    /// This is only filled for the asset whose file changed.
+    pub invalidated_dependents: Vec<AssetPath<'static>>,
+}
+
+impl<A: Asset> AssetReloadedEvent<A> {
+    /// Converts this to an "untyped" / "generic-less" asset reload event that stores the type information.
+    pub fn untyped(&self) -> UntypedAssetReloadedEvent {
+        self.into()
+    }
+
+    /// Returns `true` if the asset was reloaded because one of its loader dependencies changed,
+    /// rather than its own file.
+    pub fn is_dependency_change(&self) -> bool {
+        self.path != self.changed_path
+    }
+}
+
+/// An untyped version of [`AssetReloadedEvent`].
+#[derive(Message, Clone, Debug)]
+pub struct UntypedAssetReloadedEvent {
+    /// The stable identifier of the reloaded asset.
+    pub id: UntypedAssetId,
+    /// The path of the reloaded asset.
+    pub path: AssetPath<'static>,
+    /// The path whose change caused the reload.
+    pub changed_path: AssetPath<'static>,
+    /// When the previous change to [`changed_path`](Self::changed_path) was detected.
+    pub previous_modified_at: Option<Instant>,
+    /// When the change to [`changed_path`](Self::changed_path) was detected.
+    pub modified_at: Instant,
+    /// The assets that were also reloaded because they depend on this asset in their loader.
+    pub invalidated_dependents: Vec<AssetPath<'static>>,
+}
+
+impl UntypedAssetReloadedEvent {
+    /// Returns `true` if the asset was reloaded because one of its loader dependencies changed,
+    /// rather than its own file.
+    pub fn is_dependency_change(&self) -> bool {
+        self.path != self.changed_path
+    }
+}
+
+impl<A: Asset> From<&AssetReloadedEvent<A>> for UntypedAssetReloadedEvent {
+    fn from(value: &AssetReloadedEvent<A>) -> Self {
+        UntypedAssetReloadedEvent {
+            id: value.id.untyped(),
+            path: value.path.clone(),
+            changed_path: value.changed_path.clone(),
+            previous_modified_at: value.previous_modified_at,
+            modified_at: value.modified_at,
+            invalidated_dependents: value.invalidated_dependents.clone(),
+        }
+    }
+}
+
 /// [`Message`]s that occur for a specific loaded [`Asset`], such as "value changed" events and "dependency" events.
 #[expect(missing_docs, reason = "Documenting the id fields is unhelpful.")]
 #[derive(Message, Reflect)]
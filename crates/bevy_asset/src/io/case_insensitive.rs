@@ -0,0 +1,265 @@
+use crate::io::{
+    AssetReader, AssetReaderError, AssetSourceEvent, ErasedAssetReader, PathStream, Reader,
+    VecReader,
+};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use bevy_platform::{
+    collections::HashMap,
+    sync::{PoisonError, RwLock},
+};
+use futures_lite::StreamExt;
+use std::path::{Component, Path, PathBuf};
+use tracing::warn;
+
+/// The entries of a directory, by their lowercase file name.
+type DirectoryIndex = HashMap<String, PathBuf>;
+
+/// An index of the directories of an [`AssetReader`], used to resolve its paths
+/// case-insensitively.
+///
+/// Directories are indexed from their listings as they are needed. Their index is kept until
+/// [`invalidate`](Self::invalidate) is called with a watcher event changing their entries, so that
+/// paths that aren't found, like the meta files of most assets, don't list their directory again.
+pub(crate) struct CaseInsensitiveIndex {
+    reader: Box<dyn ErasedAssetReader>,
+    directories: RwLock<HashMap<PathBuf, Arc<DirectoryIndex>>>,
+}
+
+impl CaseInsensitiveIndex {
+    /// Returns the path of the reader matching `path` case-insensitively.
+    async fn resolve(&self, path: &Path) -> Result<PathBuf, AssetReaderError> {
+        let mut resolved = PathBuf::new();
+        for component in path.components() {
+            let Component::Normal(name) = component else {
+                resolved.push(component);
+                continue;
+            };
+            let name = name.to_string_lossy().to_lowercase();
+            resolved = self
+                .index(&resolved)
+                .await?
+                .get(&name)
+                .cloned()
+                .ok_or_else(|| AssetReaderError::NotFound(path.to_path_buf()))?;
+        }
+        if resolved != path {
+            warn!(
+                "Asset path {path:?} was resolved to {resolved:?}, which has a different case. \
+                Use the case of the file in the path to avoid resolving it."
+            );
+        }
+        Ok(resolved)
+    }
+
+    /// Returns the path of the reader matching `path` case-insensitively, or [`None`] if there is
+    /// none.
+    ///
+    /// This blocks on listing the directories of `path` that aren't indexed yet. On the web, where
+    /// blocking would never complete, this returns [`None`].
+    pub(crate) fn resolve_blocking(&self, path: &Path) -> Option<PathBuf> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return bevy_tasks::block_on(self.resolve(path)).ok();
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = path;
+            None
+        }
+    }
+
+    async fn index(&self, directory: &Path) -> Result<Arc<DirectoryIndex>, AssetReaderError> {
+        if let Some(index) = self
+            .directories
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(directory)
+        {
+            return Ok(index.clone());
+        }
+        let entries: Vec<PathBuf> = self.reader.read_directory(directory).await?.collect().await;
+        let mut index = DirectoryIndex::default();
+        for entry in entries {
+            let Some(name) = entry.file_name() else {
+                continue;
+            };
+            let name = name.to_string_lossy().to_lowercase();
+            if let Some(other) = index.get(&name) {
+                warn!("{entry:?} and {other:?} only differ by case, {other:?} is used when resolving paths case-insensitively");
+                continue;
+            }
+            index.insert(name, entry);
+        }
+        let index = Arc::new(index);
+        self.directories
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(directory.to_path_buf(), index.clone());
+        Ok(index)
+    }
+
+    /// Forgets the index of the directories whose entries were changed by `event`.
+    pub(crate) fn invalidate(&self, event: &AssetSourceEvent) {
+        let (path, other) = match event {
+            AssetSourceEvent::ModifiedAsset(_) | AssetSourceEvent::ModifiedMeta(_) => return,
+            AssetSourceEvent::AddedAsset(path)
+            | AssetSourceEvent::RemovedAsset(path)
+            | AssetSourceEvent::AddedMeta(path)
+            | AssetSourceEvent::RemovedMeta(path)
+            | AssetSourceEvent::AddedFolder(path)
+            | AssetSourceEvent::RemovedFolder(path)
+            | AssetSourceEvent::RemovedUnknown { path, .. } => (path, None),
+            AssetSourceEvent::RenamedAsset { old, new }
+            | AssetSourceEvent::RenamedMeta { old, new }
+            | AssetSourceEvent::RenamedFolder { old, new } => (old, Some(new)),
+        };
+        self.directories
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|directory, _| {
+                [Some(path), other].into_iter().flatten().all(|path| {
+                    path.parent() != Some(directory.as_path()) && !directory.starts_with(path)
+                })
+            });
+    }
+}
+
+/// An [`AssetReader`] resolving the paths of another reader case-insensitively, enabled with
+/// [`AssetSourceBuilder::with_case_insensitive_paths`](crate::io::AssetSourceBuilder::with_case_insensitive_paths).
+///
+/// Paths are first read as they are. When they are not found, they are looked up in the
+/// [`CaseInsensitiveIndex`] of the inner reader.
+pub(crate) struct CaseInsensitiveAssetReader {
+    index: Arc<CaseInsensitiveIndex>,
+}
+
+impl CaseInsensitiveAssetReader {
+    pub(crate) fn new(reader: Box<dyn ErasedAssetReader>) -> Self {
+        Self {
+            index: Arc::new(CaseInsensitiveIndex {
+                reader,
+                directories: RwLock::default(),
+            }),
+        }
+    }
+
+    /// Returns the index this reader resolves paths with.
+    pub(crate) fn index(&self) -> Arc<CaseInsensitiveIndex> {
+        self.index.clone()
+    }
+}
+
+impl AssetReader for CaseInsensitiveAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match self.index.reader.read(path).await {
+            Err(AssetReaderError::NotFound(_)) => {}
+            result => return result,
+        }
+        let resolved = self.index.resolve(path).await?;
+        // The returned reader may borrow the path it reads, so a reader of a path made here can't
+        // be returned, and the asset is read into memory instead. The `AssetServer` resolves the
+        // case of the paths it loads beforehand, so its loads are streamed.
+        let mut bytes = Vec::new();
+        self.index
+            .reader
+            .read(&resolved)
+            .await?
+            .read_to_end(&mut bytes)
+            .await?;
+        Ok(Box::new(VecReader::new(bytes)))
+    }
+
+    async fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match self.index.reader.read_meta(path).await {
+            Err(AssetReaderError::NotFound(_)) => {}
+            result => return result,
+        }
+        let resolved = self.index.resolve(path).await?;
+        let mut bytes = Vec::new();
+        self.index
+            .reader
+            .read_meta(&resolved)
+            .await?
+            .read_to_end(&mut bytes)
+            .await?;
+        Ok(Box::new(VecReader::new(bytes)))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        match self.index.reader.read_directory(path).await {
+            Err(AssetReaderError::NotFound(_)) => {
+                let resolved = self.index.resolve(path).await?;
+                self.index.reader.read_directory(&resolved).await
+            }
+            result => result,
+        }
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        // Some readers report missing paths as not being directories.
+        let result = self.index.reader.is_directory(path).await;
+        if !matches!(result, Ok(false) | Err(AssetReaderError::NotFound(_))) {
+            return result;
+        }
+        match self.index.resolve(path).await {
+            Ok(resolved) if resolved != path => self.index.reader.is_directory(&resolved).await,
+            _ => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn resolves_paths_case_insensitively() {
+        let root = Dir::default();
+        root.insert_asset_text(Path::new("Textures/Player.png"), "player");
+        let reader =
+            CaseInsensitiveAssetReader::new(Box::new(MemoryAssetReader { root: root.clone() }));
+        let read_text = |path: &str| {
+            block_on(async {
+                let mut bytes = Vec::new();
+                AssetReader::read(&reader, Path::new(path))
+                    .await?
+                    .read_to_end(&mut bytes)
+                    .await?;
+                Ok::<_, AssetReaderError>(String::from_utf8(bytes).unwrap())
+            })
+        };
+        assert_eq!(read_text("Textures/Player.png").unwrap(), "player");
+        assert_eq!(read_text("textures/player.PNG").unwrap(), "player");
+        assert!(matches!(
+            read_text("textures/enemy.png"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        // Files added after a directory was indexed are found once the watcher reports them.
+        root.insert_asset_text(Path::new("Textures/Enemy.png"), "enemy");
+        assert!(matches!(
+            read_text("textures/enemy.png"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        reader
+            .index()
+            .invalidate(&AssetSourceEvent::AddedAsset("Textures/Enemy.png".into()));
+        assert_eq!(read_text("textures/enemy.png").unwrap(), "enemy");
+
+        let entries: Vec<PathBuf> = block_on(async {
+            AssetReader::read_directory(&reader, Path::new("TEXTURES"))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        assert_eq!(entries.len(), 2);
+        assert!(block_on(AssetReader::is_directory(&reader, Path::new("textures"))).unwrap());
+    }
+}
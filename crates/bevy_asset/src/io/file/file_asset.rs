@@ -10,15 +10,13 @@ use async_lock::{Semaphore, SemaphoreGuard};
 use futures_lite::StreamExt;
 
 use alloc::{borrow::ToOwned, boxed::Box};
-#[cfg(target_os = "windows")]
-use core::marker::PhantomData;
 #[cfg(not(target_os = "windows"))]
 use core::time::Duration;
 #[cfg(not(target_os = "windows"))]
 use futures_util::{future, pin_mut};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use super::{FileAssetReader, FileAssetWriter};
+use super::{get_temp_write_path, AtomicFileWriter, FileAssetReader, FileAssetWriter};
 
 impl Reader for File {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
@@ -47,14 +45,13 @@ async fn maybe_get_semaphore<'a>() -> Option<SemaphoreGuard<'a>> {
     }
 }
 
+#[cfg(not(target_os = "windows"))]
 struct GuardedFile<'a> {
     file: File,
-    #[cfg(not(target_os = "windows"))]
     _guard: Option<SemaphoreGuard<'a>>,
-    #[cfg(target_os = "windows")]
-    _lifetime: PhantomData<&'a ()>,
 }
 
+#[cfg(not(target_os = "windows"))]
 impl<'a> futures_io::AsyncRead for GuardedFile<'a> {
     fn poll_read(
         mut self: core::pin::Pin<&mut Self>,
@@ -65,68 +62,70 @@ impl<'a> futures_io::AsyncRead for GuardedFile<'a> {
     }
 }
 
+#[cfg(not(target_os = "windows"))]
 impl<'a> Reader for GuardedFile<'a> {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         self.file.seekable()
     }
 }
 
+fn map_open_error(error: std::io::Error, full_path: PathBuf) -> AssetReaderError {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        AssetReaderError::NotFound(full_path)
+    } else {
+        error.into()
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+async fn open_file<'a>(full_path: PathBuf) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+    let _guard = maybe_get_semaphore().await;
+    match File::open(&full_path).await {
+        Ok(file) => Ok(Box::new(GuardedFile { file, _guard })),
+        Err(e) => Err(map_open_error(e, full_path)),
+    }
+}
+
+/// Opens the file for overlapped reads, which don't block the threads of the IO task pool while
+/// the file is read. Falls back to blocking reads if overlapped IO is not available.
+#[cfg(target_os = "windows")]
+async fn open_file(full_path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
+    match super::overlapped::OverlappedFile::open(&full_path).await {
+        Ok(Some(file)) => Ok(Box::new(file)),
+        Ok(None) => match File::open(&full_path).await {
+            Ok(file) => Ok(Box::new(file)),
+            Err(e) => Err(map_open_error(e, full_path)),
+        },
+        Err(e) => Err(map_open_error(e, full_path)),
+    }
+}
+
 impl AssetReader for FileAssetReader {
     async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
-        #[cfg(not(target_os = "windows"))]
-        let _guard = maybe_get_semaphore().await;
-
-        let full_path = self.root_path.join(path);
-        File::open(&full_path)
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    AssetReaderError::NotFound(full_path)
-                } else {
-                    e.into()
-                }
-            })
-            .map(|file| GuardedFile {
-                file,
-                #[cfg(not(target_os = "windows"))]
-                _guard,
-                #[cfg(target_os = "windows")]
-                _lifetime: PhantomData,
-            })
+        let full_path = self.full_path(path)?;
+        #[cfg(feature = "mmap")]
+        if let Some(file) = self.map_file(&full_path)? {
+            let reader: Box<dyn Reader + 'a> = Box::new(file);
+            return Ok(reader);
+        }
+        open_file(full_path).await
     }
 
     async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
-        #[cfg(not(target_os = "windows"))]
-        let _guard = maybe_get_semaphore().await;
-
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
-        File::open(&full_path)
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    AssetReaderError::NotFound(full_path)
-                } else {
-                    e.into()
-                }
-            })
-            .map(|file| GuardedFile {
-                file,
-                #[cfg(not(target_os = "windows"))]
-                _guard,
-                #[cfg(target_os = "windows")]
-                _lifetime: PhantomData,
-            })
+        open_file(self.full_path(meta_path)?).await
     }
 
     async fn read_directory<'a>(
         &'a self,
         path: &'a Path,
     ) -> Result<Box<PathStream>, AssetReaderError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         match read_dir(&full_path).await {
             Ok(read_dir) => {
-                let root_path = self.root_path.clone();
+                // The entries are listed relative to `path`, as the full path of the directory is
+                // canonical with some symlink policies.
+                let directory = path.to_owned();
                 let mapped_stream = read_dir.filter_map(move |f| {
                     f.ok().and_then(|dir_entry| {
                         let path = dir_entry.path();
@@ -145,8 +144,7 @@ impl AssetReader for FileAssetReader {
                         {
                             return None;
                         }
-                        let relative_path = path.strip_prefix(&root_path).unwrap();
-                        Some(relative_path.to_owned())
+                        Some(directory.join(dir_entry.file_name()))
                     })
                 });
                 let read_dir: Box<PathStream> = Box::new(mapped_stream);
@@ -163,45 +161,60 @@ impl AssetReader for FileAssetReader {
     }
 
     async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         let metadata = full_path
             .metadata()
             .map_err(|_e| AssetReaderError::NotFound(path.to_owned()))?;
         Ok(metadata.file_type().is_dir())
     }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        self.full_path(path).is_ok_and(|full_path| {
+            full_path
+                .metadata()
+                .is_ok_and(|metadata| metadata.is_file())
+        })
+    }
 }
 
-impl AssetWriter for FileAssetWriter {
-    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
-        let full_path = self.root_path.join(path);
+impl FileAssetWriter {
+    async fn create_writer(&self, full_path: PathBuf) -> Result<Box<Writer>, AssetWriterError> {
         if let Some(parent) = full_path.parent() {
             async_fs::create_dir_all(parent).await?;
         }
-        let file = File::create(&full_path).await?;
-        let writer: Box<Writer> = Box::new(file);
+        if !self.atomic_writes {
+            let file = File::create(&full_path).await?;
+            let writer: Box<Writer> = Box::new(file);
+            return Ok(writer);
+        }
+        let temp_path = get_temp_write_path(&full_path);
+        let file = File::create(&temp_path).await?;
+        let writer: Box<Writer> = Box::new(AtomicFileWriter::new(file, temp_path, full_path));
         Ok(writer)
     }
+}
+
+impl AssetWriter for FileAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        let full_path = self.full_path(path)?;
+        self.create_writer(full_path).await
+    }
 
     async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
-        if let Some(parent) = full_path.parent() {
-            async_fs::create_dir_all(parent).await?;
-        }
-        let file = File::create(&full_path).await?;
-        let writer: Box<Writer> = Box::new(file);
-        Ok(writer)
+        let full_path = self.full_path(meta_path)?;
+        self.create_writer(full_path).await
     }
 
     async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         async_fs::remove_file(full_path).await?;
         Ok(())
     }
 
     async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
+        let full_path = self.full_path(meta_path)?;
         async_fs::remove_file(full_path).await?;
         Ok(())
     }
@@ -211,8 +224,8 @@ impl AssetWriter for FileAssetWriter {
         old_path: &'a Path,
         new_path: &'a Path,
     ) -> Result<(), AssetWriterError> {
-        let full_old_path = self.root_path.join(old_path);
-        let full_new_path = self.root_path.join(new_path);
+        let full_old_path = self.full_path(old_path)?;
+        let full_new_path = self.full_path(new_path)?;
         if let Some(parent) = full_new_path.parent() {
             async_fs::create_dir_all(parent).await?;
         }
@@ -227,8 +240,8 @@ impl AssetWriter for FileAssetWriter {
     ) -> Result<(), AssetWriterError> {
         let old_meta_path = get_meta_path(old_path);
         let new_meta_path = get_meta_path(new_path);
-        let full_old_path = self.root_path.join(old_meta_path);
-        let full_new_path = self.root_path.join(new_meta_path);
+        let full_old_path = self.full_path(old_meta_path)?;
+        let full_new_path = self.full_path(new_meta_path)?;
         if let Some(parent) = full_new_path.parent() {
             async_fs::create_dir_all(parent).await?;
         }
@@ -237,19 +250,19 @@ impl AssetWriter for FileAssetWriter {
     }
 
     async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         async_fs::create_dir_all(full_path).await?;
         Ok(())
     }
 
     async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         async_fs::remove_dir_all(full_path).await?;
         Ok(())
     }
 
     async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         async_fs::remove_dir(full_path).await?;
         Ok(())
     }
@@ -258,7 +271,7 @@ impl AssetWriter for FileAssetWriter {
         &'a self,
         path: &'a Path,
     ) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         async_fs::remove_dir_all(&full_path).await?;
         async_fs::create_dir_all(&full_path).await?;
         Ok(())
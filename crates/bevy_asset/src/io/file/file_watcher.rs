@@ -156,6 +156,20 @@ pub(crate) fn new_asset_event_debouncer(
                                     handler.handle(&paths, asset_event);
                                 }
                             }
+                            notify::EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+                                if super::is_temp_write_path(&paths[0]) =>
+                            {
+                                // An atomic write of `FileAssetWriter` was committed. The destination may or may
+                                // not have existed before, so this is reported as an addition, which also covers
+                                // modifications.
+                                if let Some((path, is_meta)) = handler.get_path(&paths[1]) {
+                                    if is_meta {
+                                        handler.handle(&paths, AssetSourceEvent::AddedMeta(path));
+                                    } else {
+                                        handler.handle(&paths, AssetSourceEvent::AddedAsset(path));
+                                    }
+                                }
+                            }
                             notify::EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
                                 let Some((old_path, old_is_meta)) = handler.get_path(&paths[0])
                                 else {
@@ -260,6 +274,9 @@ impl FilesystemEventHandler for FileEventHandler {
         self.last_event = None;
     }
     fn get_path(&self, absolute_path: &Path) -> Option<(PathBuf, bool)> {
+        if super::is_temp_write_path(absolute_path) {
+            return None;
+        }
         Some(get_asset_path(&self.root, absolute_path))
     }
 
@@ -0,0 +1,110 @@
+use crate::io::{
+    AssetReaderError, AsyncSeek, Reader, ReaderNotSeekableError, SeekableReader, STACK_FUTURE_SIZE,
+};
+use alloc::{borrow::ToOwned, vec::Vec};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_io::{AsyncRead, SeekFrom};
+use memmap2::Mmap;
+use stackfuture::StackFuture;
+use std::{fs::File, path::Path};
+
+use super::FileAssetReader;
+
+/// A [`Reader`] over a memory-mapped file, see [`FileAssetReader::with_mmap`].
+pub(super) struct MmapReader {
+    map: Mmap,
+    bytes_read: usize,
+}
+
+impl FileAssetReader {
+    /// Memory-maps the asset files of at least `min_len` bytes instead of reading them.
+    ///
+    /// Loaders can then read these files with [`Reader::read_to_slice`] without copying them,
+    /// which avoids allocating a buffer the size of the file for large meshes or textures. Small
+    /// files are faster to read than to map, so `min_len` should be at least a few hundred KiB.
+    /// Meta files are never mapped.
+    ///
+    /// # Safety
+    ///
+    /// The mapped files must not be modified (by this or any other process) while they are mapped,
+    /// i.e. until the readers of the assets are dropped. This notably rules out processing or
+    /// re-exporting the assets while the app is running, e.g. with the
+    /// [`AssetProcessor`](crate::processor::AssetProcessor) or hot reloading.
+    pub unsafe fn with_mmap(mut self, min_len: u64) -> Self {
+        self.mmap_min_len = Some(min_len);
+        self
+    }
+
+    /// Maps the file at `full_path` if it is at least as large as the threshold set with
+    /// [`FileAssetReader::with_mmap`], or returns [`None`] to read it normally.
+    pub(super) fn map_file(
+        &self,
+        full_path: &Path,
+    ) -> Result<Option<MmapReader>, AssetReaderError> {
+        let Some(min_len) = self.mmap_min_len else {
+            return Ok(None);
+        };
+        // Opening and mapping the file both block, but so does every page fault on the mapping
+        // while the file is read.
+        let file = File::open(full_path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AssetReaderError::NotFound(full_path.to_owned())
+            } else {
+                e.into()
+            }
+        })?;
+        // Empty files can't be mapped.
+        if file.metadata()?.len() < min_len.max(1) {
+            return Ok(None);
+        }
+        // SAFETY: `FileAssetReader::with_mmap` requires the files not to be modified while mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Some(MmapReader { map, bytes_read: 0 }))
+    }
+}
+
+impl AsyncRead for MmapReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        Poll::Ready(Ok(crate::io::slice_read(
+            &this.map,
+            &mut this.bytes_read,
+            buf,
+        )))
+    }
+}
+
+impl AsyncSeek for MmapReader {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        let this = self.get_mut();
+        Poll::Ready(crate::io::slice_seek(&this.map, &mut this.bytes_read, pos))
+    }
+}
+
+impl Reader for MmapReader {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        crate::io::read_to_end(&self.map, &mut self.bytes_read, buf)
+    }
+
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        Ok(self)
+    }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        Some(crate::io::read_to_slice(&self.map, &mut self.bytes_read))
+    }
+}
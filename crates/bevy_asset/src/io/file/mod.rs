@@ -3,17 +3,31 @@ mod file_watcher;
 
 #[cfg(feature = "multi_threaded")]
 mod file_asset;
+#[cfg(feature = "mmap")]
+#[expect(unsafe_code, reason = "Mapping a file is unsafe.")]
+mod mmap;
+#[cfg(all(feature = "multi_threaded", target_os = "windows"))]
+#[expect(unsafe_code, reason = "Overlapped IO goes through the Win32 API.")]
+mod overlapped;
 #[cfg(not(feature = "multi_threaded"))]
 mod sync_file_asset;
 
 #[cfg(feature = "file_watcher")]
 pub use file_watcher::*;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
-use alloc::borrow::ToOwned;
+use crate::io::{AssetReaderError, SymlinkPolicy};
+use alloc::{borrow::ToOwned, format, sync::Arc};
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+};
+use futures_io::AsyncWrite;
 use std::{
     env,
-    path::{Path, PathBuf},
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
 };
 
 pub(crate) fn get_base_path() -> PathBuf {
@@ -28,11 +42,82 @@ pub(crate) fn get_base_path() -> PathBuf {
     }
 }
 
+/// Converts the root path of a [`FileAssetReader`] or [`FileAssetWriter`] to an absolute,
+/// extended-length path on Windows (e.g. `\\?\C:\project\assets`), which isn't limited to
+/// `MAX_PATH` (260 characters). Deeply nested assets, like the processed assets of large projects,
+/// otherwise fail to load.
+///
+/// This returns `path` unchanged on other platforms, or if it can't be converted.
+fn to_extended_length_path(path: PathBuf) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        use std::path::Prefix;
+
+        // This also normalizes `/` separators and `.` and `..` components, which extended-length
+        // paths don't support.
+        let Ok(absolute) = std::path::absolute(&path) else {
+            return path;
+        };
+        let mut components = absolute.components();
+        let Some(Component::Prefix(prefix)) = components.next() else {
+            return absolute;
+        };
+        let mut extended = match prefix.kind() {
+            Prefix::Disk(letter) => OsString::from(format!(r"\\?\{}:", char::from(letter))),
+            Prefix::UNC(server, share) => {
+                let mut extended = OsString::from(r"\\?\UNC\");
+                extended.push(server);
+                extended.push(r"\");
+                extended.push(share);
+                extended
+            }
+            // The path is already an extended-length path, or a device path.
+            _ => return absolute,
+        };
+        if components.next().is_none() {
+            return absolute;
+        }
+        extended.push(r"\");
+        let mut extended = PathBuf::from(extended);
+        extended.extend(components);
+        extended
+    }
+    #[cfg(not(target_os = "windows"))]
+    path
+}
+
+/// Joins a relative asset `path` to the `root` of a [`FileAssetReader`] or [`FileAssetWriter`].
+///
+/// Extended-length roots (see `to_extended_length_path`) are used verbatim by Windows, so the
+/// components of `path` are pushed one by one to use `\` separators.
+fn join_root(root: &Path, path: &Path) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        let mut full_path = root.to_path_buf();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    full_path.pop();
+                }
+                component => full_path.push(component),
+            }
+        }
+        full_path
+    }
+    #[cfg(not(target_os = "windows"))]
+    root.join(path)
+}
+
 /// I/O implementation for the local filesystem.
 ///
 /// This asset I/O is fully featured but it's not available on `android` and `wasm` targets.
 pub struct FileAssetReader {
     root_path: PathBuf,
+    symlink_policy: SymlinkPolicy,
+    /// The size from which files are memory-mapped, see `FileAssetReader::with_mmap`.
+    #[cfg(feature = "mmap")]
+    mmap_min_len: Option<u64>,
 }
 
 impl FileAssetReader {
@@ -41,18 +126,41 @@ impl FileAssetReader {
     ///
     /// See `get_base_path` below.
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
-        let root_path = Self::get_base_path().join(path.as_ref());
+        let root_path = to_extended_length_path(Self::get_base_path().join(path.as_ref()));
         debug!(
             "Asset Server using {} as its base path.",
             root_path.display()
         );
-        Self { root_path }
+        Self {
+            root_path,
+            symlink_policy: SymlinkPolicy::Follow,
+            #[cfg(feature = "mmap")]
+            mmap_min_len: None,
+        }
+    }
+
+    /// Sets how this reader treats symbolic links and junctions, see [`SymlinkPolicy`].
+    ///
+    /// Denied paths fail to be read with an [`std::io::ErrorKind::PermissionDenied`] error. They
+    /// are still listed by [`AssetReader::read_directory`](crate::io::AssetReader::read_directory).
+    pub fn with_symlink_policy(mut self, symlink_policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = symlink_policy;
+        self
+    }
+
+    /// Returns the [`SymlinkPolicy`] of this reader.
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlink_policy
     }
 
-    /// Returns the base path of the assets directory, which is normally the executable's parent
-    /// directory.
+    /// Returns the base path of the assets directory detected from the environment, which is
+    /// normally the executable's parent directory.
     ///
-    /// To change this, set [`AssetPlugin::file_path`][crate::AssetPlugin::file_path].
+    /// The `BEVY_ASSET_ROOT` and `CARGO_MANIFEST_DIR` environment variables take precedence over
+    /// the executable's directory. This doesn't include
+    /// [`AssetPlugin::base_path`][crate::AssetPlugin::base_path], which only applies to the default
+    /// asset source. To change the assets directory within the base path, set
+    /// [`AssetPlugin::file_path`][crate::AssetPlugin::file_path].
     pub fn get_base_path() -> PathBuf {
         get_base_path()
     }
@@ -60,21 +168,101 @@ impl FileAssetReader {
     /// Returns the root directory where assets are loaded from.
     ///
     /// See `get_base_path`.
+    ///
+    /// On Windows, this is an extended-length path, e.g. `\\?\C:\project\assets`.
     pub fn root_path(&self) -> &PathBuf {
         &self.root_path
     }
+
+    /// Returns the path of the file of the asset at `path`, or an error if the [`SymlinkPolicy`]
+    /// denies reading it. See [`apply_symlink_policy`].
+    fn full_path(&self, path: impl AsRef<Path>) -> Result<PathBuf, AssetReaderError> {
+        apply_symlink_policy(&self.root_path, path.as_ref(), self.symlink_policy)
+            .map_err(|error| AssetReaderError::Io(Arc::new(error)))
+    }
+}
+
+/// Returns the path of the file at `path` in the `root` of a [`FileAssetReader`] or
+/// [`FileAssetWriter`], or a [`PermissionDenied`](std::io::ErrorKind::PermissionDenied) error if
+/// `policy` denies it.
+///
+/// Unless links are followed, this is the canonical path of the file, which was checked to be in
+/// the root directory, so that a link swapped in after the check isn't followed when the file
+/// is opened. Files that don't exist yet, e.g. when they are written, are checked through their
+/// deepest existing directory, so that they can't be created through a link either.
+fn apply_symlink_policy(
+    root: &Path,
+    path: &Path,
+    policy: SymlinkPolicy,
+) -> Result<PathBuf, std::io::Error> {
+    let full_path = join_root(root, path);
+    if policy == SymlinkPolicy::Follow {
+        return Ok(full_path);
+    }
+    let denied = || {
+        std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!(
+                "{} is denied by the symlink policy {policy:?}",
+                full_path.display()
+            ),
+        )
+    };
+    // `..` and absolute paths can leave the root directory without going through a link.
+    if path
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(denied());
+    }
+    if policy == SymlinkPolicy::Deny
+        && full_path
+            .ancestors()
+            .take_while(|ancestor| *ancestor != root)
+            .any(|ancestor| {
+                ancestor
+                    .symlink_metadata()
+                    .is_ok_and(|metadata| metadata.file_type().is_symlink())
+            })
+    {
+        return Err(denied());
+    }
+    // Without a root directory, there are no links in it to check.
+    let Ok(canonical_root) = root.canonicalize() else {
+        return Ok(full_path);
+    };
+    let Some((existing, canonical_path)) = full_path
+        .ancestors()
+        .find_map(|ancestor| Some((ancestor, ancestor.canonicalize().ok()?)))
+    else {
+        return Ok(full_path);
+    };
+    if !canonical_path.starts_with(&canonical_root) {
+        return Err(denied());
+    }
+    match full_path.strip_prefix(existing) {
+        Ok(missing) if !missing.as_os_str().is_empty() => Ok(canonical_path.join(missing)),
+        _ => Ok(canonical_path),
+    }
 }
 
 /// A writer for the local filesystem.
+///
+/// By default, writes are atomic: the contents are first written to a hidden temporary file next to
+/// the destination, which is then renamed over the destination once the [`Writer`](crate::io::Writer)
+/// is closed. This ensures a crash or error in the middle of a write never leaves a partially written
+/// asset behind. This can be disabled with [`FileAssetWriter::with_atomic_writes`].
 pub struct FileAssetWriter {
     root_path: PathBuf,
+    atomic_writes: bool,
+    symlink_policy: SymlinkPolicy,
 }
 
 impl FileAssetWriter {
     /// Creates a new [`FileAssetWriter`] at a path relative to the executable's directory, optionally
     /// watching for changes.
     pub fn new<P: AsRef<Path> + core::fmt::Debug>(path: P, create_root: bool) -> Self {
-        let root_path = get_base_path().join(path.as_ref());
+        let root_path = to_extended_length_path(get_base_path().join(path.as_ref()));
         if create_root && let Err(e) = std::fs::create_dir_all(&root_path) {
             error!(
                 "Failed to create root directory {} for file asset writer: {}",
@@ -82,6 +270,355 @@ impl FileAssetWriter {
                 e
             );
         }
-        Self { root_path }
+        Self {
+            root_path,
+            atomic_writes: true,
+            symlink_policy: SymlinkPolicy::Follow,
+        }
+    }
+
+    /// Sets whether writes go through a temporary file that is renamed over the destination when
+    /// the writer is closed. This is enabled by default.
+    ///
+    /// Disabling this writes directly to the destination, which avoids the extra rename (and the
+    /// temporary file) at the cost of leaving a partially written file behind if writing fails.
+    pub fn with_atomic_writes(mut self, atomic_writes: bool) -> Self {
+        self.atomic_writes = atomic_writes;
+        self
+    }
+
+    /// Returns whether this writer writes atomically. See [`FileAssetWriter::with_atomic_writes`].
+    pub fn atomic_writes(&self) -> bool {
+        self.atomic_writes
+    }
+
+    /// Sets how this writer treats symbolic links and junctions, see [`SymlinkPolicy`].
+    ///
+    /// Denied paths fail to be written, removed or renamed with an
+    /// [`std::io::ErrorKind::PermissionDenied`] error.
+    pub fn with_symlink_policy(mut self, symlink_policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = symlink_policy;
+        self
+    }
+
+    /// Returns the [`SymlinkPolicy`] of this writer.
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlink_policy
+    }
+
+    /// Returns the path of the file of the asset at `path`, or an error if the [`SymlinkPolicy`]
+    /// denies writing it. See [`apply_symlink_policy`].
+    fn full_path(&self, path: impl AsRef<Path>) -> Result<PathBuf, std::io::Error> {
+        apply_symlink_policy(&self.root_path, path.as_ref(), self.symlink_policy)
+    }
+}
+
+/// The extension of the temporary files created by [`FileAssetWriter`] for atomic writes.
+const TEMP_FILE_EXTENSION: &str = "bevy-tmp";
+
+/// Returns a unique, hidden path next to `path` to write its contents to before they are committed.
+fn get_temp_write_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let mut file_name = OsString::from(".");
+    if let Some(name) = path.file_name() {
+        file_name.push(name);
+    }
+    file_name.push(format!(
+        ".{}-{}.{TEMP_FILE_EXTENSION}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    path.with_file_name(file_name)
+}
+
+/// Returns `true` if `path` is a temporary file created by an atomic write of [`FileAssetWriter`].
+#[cfg_attr(
+    not(feature = "file_watcher"),
+    expect(dead_code, reason = "only used by the file watcher")
+)]
+pub(crate) fn is_temp_write_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == TEMP_FILE_EXTENSION)
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'))
+}
+
+/// Wraps the writer of a temporary file, renaming it to its final path once the writer is closed.
+///
+/// Dropping the writer without closing it (e.g. because of an error mid-write) discards the temporary
+/// file, leaving the destination untouched, even if the writes were flushed.
+struct AtomicFileWriter<W> {
+    inner: W,
+    temp_path: PathBuf,
+    final_path: PathBuf,
+    committed: bool,
+}
+
+impl<W> AtomicFileWriter<W> {
+    fn new(inner: W, temp_path: PathBuf, final_path: PathBuf) -> Self {
+        Self {
+            inner,
+            temp_path,
+            final_path,
+            committed: false,
+        }
+    }
+
+    fn commit(&mut self) -> std::io::Result<()> {
+        std::fs::rename(&self.temp_path, &self.final_path)?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AtomicFileWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if this.committed {
+            return Poll::Ready(Ok(()));
+        }
+        match Pin::new(&mut this.inner).poll_close(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(this.commit()),
+            other => other,
+        }
+    }
+}
+
+impl<W> Drop for AtomicFileWriter<W> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        warn!(
+            "Discarding incomplete write to {}, as the writer was dropped before being closed",
+            self.final_path.display()
+        );
+        if let Err(e) = std::fs::remove_file(&self.temp_path) {
+            error!(
+                "Failed to remove temporary file {}: {}",
+                self.temp_path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FileAssetReader, FileAssetWriter};
+    use crate::io::{AssetReader, AssetWriter, AsyncWriteExt, Reader};
+    use alloc::{format, vec::Vec};
+    use bevy_tasks::block_on;
+    use std::path::Path;
+
+    fn test_writer(name: &str) -> FileAssetWriter {
+        let root = std::env::temp_dir().join(format!("bevy_asset_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        FileAssetWriter::new(root, true)
+    }
+
+    #[test]
+    fn atomic_write_only_replaces_on_commit() {
+        let writer = test_writer("atomic_write");
+        let path = Path::new("a.txt");
+        let full_path = writer.full_path(path).unwrap();
+        block_on(writer.write_bytes(path, b"old")).unwrap();
+        assert_eq!(std::fs::read(&full_path).unwrap(), b"old");
+
+        // Writes that aren't closed are discarded when the writer is dropped, even if flushed.
+        block_on(async {
+            let mut file = writer.write(path).await.unwrap();
+            file.write_all(b"partial").await.unwrap();
+        });
+        assert_eq!(std::fs::read(&full_path).unwrap(), b"old");
+        block_on(async {
+            let mut file = writer.write(path).await.unwrap();
+            file.write_all(b"partial").await.unwrap();
+            file.flush().await.unwrap();
+        });
+        assert_eq!(std::fs::read(&full_path).unwrap(), b"old");
+        block_on(async {
+            let _file = writer.write(path).await.unwrap();
+        });
+        assert_eq!(std::fs::read(&full_path).unwrap(), b"old");
+
+        block_on(writer.write_bytes(path, b"new")).unwrap();
+        assert_eq!(std::fs::read(&full_path).unwrap(), b"new");
+
+        // No temporary files are left behind.
+        assert_eq!(std::fs::read_dir(&writer.root_path).unwrap().count(), 1);
+        std::fs::remove_dir_all(&writer.root_path).unwrap();
+    }
+
+    #[test]
+    fn non_atomic_write() {
+        let writer = test_writer("non_atomic_write").with_atomic_writes(false);
+        let path = Path::new("a.txt");
+        let full_path = writer.full_path(path).unwrap();
+
+        block_on(async {
+            let mut file = writer.write(path).await.unwrap();
+            file.write_all(b"partial").await.unwrap();
+            file.flush().await.unwrap();
+            // The contents are visible before the writer is closed.
+            assert_eq!(std::fs::read(&full_path).unwrap(), b"partial");
+        });
+        std::fs::remove_dir_all(&writer.root_path).unwrap();
+    }
+
+    #[test]
+    fn long_paths() {
+        let writer = test_writer("long_paths");
+        // Longer than `MAX_PATH` on Windows.
+        let path = (0..8)
+            .map(|i| format!("nested_directory_with_a_long_name_{i}"))
+            .collect::<std::path::PathBuf>()
+            .join("asset.txt");
+        block_on(writer.write_bytes(&path, b"long")).unwrap();
+
+        let reader = FileAssetReader::new(&writer.root_path);
+        let mut bytes = Vec::new();
+        block_on(async {
+            let mut file = reader.read(&path).await.unwrap();
+            file.read_to_end(&mut bytes).await.unwrap();
+        });
+        assert_eq!(bytes, b"long");
+        std::fs::remove_dir_all(&writer.root_path).unwrap();
+    }
+
+    #[test]
+    fn default_sources_with_different_base_paths() {
+        use crate::io::{AssetSourceBuilder, AssetSourceId};
+
+        let root =
+            std::env::temp_dir().join(format!("bevy_asset_base_paths_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for name in ["a", "b"] {
+            std::fs::create_dir_all(root.join(name).join("assets")).unwrap();
+            std::fs::write(root.join(name).join("assets/name.txt"), name).unwrap();
+        }
+
+        // Each source resolves its own base path, so several apps of the process don't interfere.
+        for name in ["a", "b"] {
+            let source = AssetSourceBuilder::platform_default_in(&root.join(name), "assets", None)
+                .build(AssetSourceId::Default, false, false);
+            let mut bytes = Vec::new();
+            block_on(async {
+                let mut file = source.reader().read(Path::new("name.txt")).await.unwrap();
+                file.read_to_end(&mut bytes).await.unwrap();
+            });
+            assert_eq!(bytes, name.as_bytes());
+        }
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_policy() {
+        use crate::io::{AssetReaderError, AssetWriterError, SymlinkPolicy};
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join(format!("bevy_asset_symlinks_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let assets = root.join("assets");
+        std::fs::create_dir_all(&assets).unwrap();
+        std::fs::create_dir_all(root.join("outside")).unwrap();
+        std::fs::write(assets.join("inside.txt"), "inside").unwrap();
+        std::fs::write(root.join("outside/secret.txt"), "secret").unwrap();
+        symlink(root.join("outside"), assets.join("linked")).unwrap();
+        symlink(assets.join("inside.txt"), assets.join("alias.txt")).unwrap();
+
+        let can_read = |policy: SymlinkPolicy, path: &str| {
+            let reader = FileAssetReader::new(&assets).with_symlink_policy(policy);
+            match block_on(reader.read(Path::new(path))) {
+                Ok(_) => true,
+                Err(AssetReaderError::Io(error)) => {
+                    assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+                    false
+                }
+                Err(error) => panic!("unexpected error: {error}"),
+            }
+        };
+        for path in ["inside.txt", "alias.txt", "linked/secret.txt"] {
+            assert!(can_read(SymlinkPolicy::Follow, path));
+        }
+        assert!(can_read(SymlinkPolicy::Deny, "inside.txt"));
+        assert!(!can_read(SymlinkPolicy::Deny, "alias.txt"));
+        assert!(!can_read(SymlinkPolicy::Deny, "linked/secret.txt"));
+        assert!(!can_read(SymlinkPolicy::Deny, "../outside/secret.txt"));
+        assert!(!can_read(
+            SymlinkPolicy::Deny,
+            root.join("outside/secret.txt").to_str().unwrap()
+        ));
+        assert!(can_read(SymlinkPolicy::DenyOutsideRoot, "inside.txt"));
+        assert!(can_read(SymlinkPolicy::DenyOutsideRoot, "alias.txt"));
+        assert!(!can_read(
+            SymlinkPolicy::DenyOutsideRoot,
+            "linked/secret.txt"
+        ));
+        assert!(!can_read(
+            SymlinkPolicy::DenyOutsideRoot,
+            "../outside/secret.txt"
+        ));
+
+        let can_write = |policy: SymlinkPolicy, path: &str| {
+            let writer = FileAssetWriter::new(&assets, false).with_symlink_policy(policy);
+            match block_on(writer.write_bytes(Path::new(path), b"written")) {
+                Ok(()) => true,
+                Err(AssetWriterError::Io(error)) => {
+                    assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+                    false
+                }
+            }
+        };
+        assert!(can_write(SymlinkPolicy::Deny, "new/inside.txt"));
+        assert!(!can_write(SymlinkPolicy::Deny, "linked/new.txt"));
+        assert!(can_write(SymlinkPolicy::DenyOutsideRoot, "alias.txt"));
+        // Files that don't exist yet can't be created through a link either.
+        assert!(!can_write(SymlinkPolicy::DenyOutsideRoot, "linked/new.txt"));
+        assert!(!can_write(
+            SymlinkPolicy::DenyOutsideRoot,
+            "linked/nested/new.txt"
+        ));
+        assert!(!root.join("outside/new.txt").exists());
+        assert!(can_write(SymlinkPolicy::Follow, "linked/new.txt"));
+        assert!(root.join("outside/new.txt").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn roots_are_extended_length_paths() {
+        use super::{join_root, to_extended_length_path};
+        use std::path::PathBuf;
+
+        let extended = |path: &str| to_extended_length_path(PathBuf::from(path)).into_os_string();
+        assert_eq!(
+            extended(r"C:\project/assets\..\assets"),
+            r"\\?\C:\project\assets"
+        );
+        assert_eq!(
+            extended(r"\\server\share\assets"),
+            r"\\?\UNC\server\share\assets"
+        );
+        assert_eq!(extended(r"\\?\C:\project\assets"), r"\\?\C:\project\assets");
+        assert_eq!(
+            join_root(Path::new(r"\\?\C:\project"), Path::new("textures/./a.png")).into_os_string(),
+            r"\\?\C:\project\textures\a.png"
+        );
     }
 }
@@ -0,0 +1,486 @@
+//! Reads files with overlapped IO completed on an IO completion port, so that reading a file
+//! doesn't block a thread of the [`IoTaskPool`](bevy_tasks::IoTaskPool).
+
+use crate::io::{Reader, ReaderNotSeekableError, SeekableReader, STACK_FUTURE_SIZE};
+use alloc::{sync::Arc, vec, vec::Vec};
+use async_fs::{windows::OpenOptionsExt, File, OpenOptions};
+use bevy_platform::sync::{LazyLock, Mutex, PoisonError};
+use core::{
+    cell::UnsafeCell,
+    ffi::c_void,
+    future::poll_fn,
+    pin::Pin,
+    ptr,
+    task::{Context, Poll, Waker},
+};
+use futures_io::{AsyncRead, AsyncSeek, SeekFrom};
+use stackfuture::StackFuture;
+use std::{io, os::windows::io::AsRawHandle, path::Path, thread};
+use tracing::error;
+
+type Handle = *mut c_void;
+
+/// The `OVERLAPPED` struct of the Win32 API.
+#[repr(C)]
+struct Overlapped {
+    internal: usize,
+    internal_high: usize,
+    offset: u32,
+    offset_high: u32,
+    event: Handle,
+}
+
+const INVALID_HANDLE_VALUE: Handle = -1isize as Handle;
+const INFINITE: u32 = u32::MAX;
+const FILE_FLAG_OVERLAPPED: u32 = 0x4000_0000;
+const ERROR_HANDLE_EOF: i32 = 38;
+const ERROR_IO_PENDING: i32 = 997;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn CreateIoCompletionPort(
+        file_handle: Handle,
+        existing_completion_port: Handle,
+        completion_key: usize,
+        number_of_concurrent_threads: u32,
+    ) -> Handle;
+    fn GetQueuedCompletionStatus(
+        completion_port: Handle,
+        number_of_bytes_transferred: *mut u32,
+        completion_key: *mut usize,
+        overlapped: *mut *mut Overlapped,
+        milliseconds: u32,
+    ) -> i32;
+    fn ReadFile(
+        file: Handle,
+        buffer: *mut u8,
+        number_of_bytes_to_read: u32,
+        number_of_bytes_read: *mut u32,
+        overlapped: *mut Overlapped,
+    ) -> i32;
+    fn CancelIoEx(file: Handle, overlapped: *mut Overlapped) -> i32;
+    fn GetFileSizeEx(file: Handle, file_size: *mut i64) -> i32;
+}
+
+/// The largest read issued at once. Bigger reads are split, to bound the memory of the buffers
+/// owned by in-flight reads.
+const MAX_READ_SIZE: usize = 1024 * 1024;
+
+/// The completion port all the overlapped reads complete on.
+///
+/// A single thread waits for the completions, and wakes the tasks waiting on them.
+#[derive(Clone, Copy)]
+struct CompletionPort(Handle);
+
+// SAFETY: A completion port handle can be used from any thread.
+unsafe impl Send for CompletionPort {}
+// SAFETY: A completion port handle can be used from any thread.
+unsafe impl Sync for CompletionPort {}
+
+static COMPLETION_PORT: LazyLock<Option<CompletionPort>> = LazyLock::new(|| {
+    // SAFETY: Creating a new completion port, not associated with any file.
+    let port = unsafe { CreateIoCompletionPort(INVALID_HANDLE_VALUE, ptr::null_mut(), 0, 1) };
+    if port.is_null() {
+        error!(
+            "Failed to create the IO completion port of the file asset reader, falling back to \
+            blocking reads: {}",
+            io::Error::last_os_error()
+        );
+        return None;
+    }
+    let port = CompletionPort(port);
+    let spawned = thread::Builder::new()
+        .name("Asset IO Completion Port".into())
+        .spawn(move || wait_for_completions(port));
+    if let Err(err) = spawned {
+        error!(
+            "Failed to spawn the IO completion thread of the file asset reader, falling back to \
+            blocking reads: {err}"
+        );
+        // The port is leaked, which is fine as it is only created once.
+        return None;
+    }
+    Some(port)
+});
+
+fn wait_for_completions(port: CompletionPort) -> ! {
+    loop {
+        let mut bytes = 0;
+        let mut key = 0;
+        let mut overlapped = ptr::null_mut();
+        // SAFETY: The port stays open for the lifetime of the process, and the pointers point to
+        // valid locals.
+        let ok = unsafe {
+            GetQueuedCompletionStatus(port.0, &mut bytes, &mut key, &mut overlapped, INFINITE)
+        };
+        let result = if ok != 0 {
+            Ok(bytes as usize)
+        } else {
+            match io::Error::last_os_error() {
+                err if err.raw_os_error() == Some(ERROR_HANDLE_EOF) => Ok(0),
+                err => Err(err),
+            }
+        };
+        if overlapped.is_null() {
+            // The wait itself failed, not a read.
+            if let Err(err) = result {
+                error!("Failed to wait on the IO completion port of the file asset reader: {err}");
+            }
+            continue;
+        }
+        // SAFETY: Every `Overlapped` queued on the port is the first field of an `Operation`,
+        // whose reference was leaked with `Arc::into_raw` when its read was started.
+        let operation = unsafe { Arc::from_raw(overlapped.cast::<Operation>()) };
+        operation.complete(result);
+    }
+}
+
+/// An in-flight read.
+///
+/// The kernel writes to the `Overlapped` and the buffer until the read completes, so they are
+/// owned by the operation, which is kept alive by the completion port until then, even if the
+/// reader is dropped.
+#[repr(C)]
+struct Operation {
+    /// This must be the first field, for the completion thread to find the operation of an
+    /// `Overlapped`.
+    overlapped: UnsafeCell<Overlapped>,
+    buffer: UnsafeCell<Vec<u8>>,
+    state: Mutex<OperationState>,
+}
+
+#[derive(Default)]
+struct OperationState {
+    result: Option<io::Result<usize>>,
+    waker: Option<Waker>,
+}
+
+// SAFETY: The `Overlapped` and the buffer are only accessed by the kernel while the read is in
+// flight, and by the reader once `state` reports the read as completed.
+unsafe impl Send for Operation {}
+// SAFETY: See above.
+unsafe impl Sync for Operation {}
+
+impl Operation {
+    fn complete(&self, result: io::Result<usize>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn poll(&self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A file read with overlapped IO.
+pub(crate) struct OverlappedFile {
+    file: File,
+    len: u64,
+    offset: u64,
+    pending: Option<Arc<Operation>>,
+}
+
+impl OverlappedFile {
+    /// Opens the file at `path` for overlapped reads. Returns [`None`] if overlapped IO is not
+    /// available, in which case the file should be read with blocking reads instead.
+    pub(crate) async fn open(path: &Path) -> io::Result<Option<Self>> {
+        let Some(port) = *COMPLETION_PORT else {
+            return Ok(None);
+        };
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(FILE_FLAG_OVERLAPPED)
+            .open(path)
+            .await?;
+        let handle = file.as_raw_handle();
+        let mut len = 0;
+        // SAFETY: `handle` is an open file handle, and `len` is a valid local.
+        if unsafe { GetFileSizeEx(handle, &mut len) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `handle` is an open file handle, opened for overlapped IO, and the port stays
+        // open for the lifetime of the process.
+        if unsafe { CreateIoCompletionPort(handle, port.0, 0, 0) }.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Some(Self {
+            file,
+            len: len as u64,
+            offset: 0,
+            pending: None,
+        }))
+    }
+
+    /// Starts reading up to `len` bytes at the current offset.
+    fn start_read(&mut self, len: usize) -> io::Result<Arc<Operation>> {
+        let len = len.min(MAX_READ_SIZE);
+        let operation = Arc::new(Operation {
+            overlapped: UnsafeCell::new(Overlapped {
+                internal: 0,
+                internal_high: 0,
+                offset: self.offset as u32,
+                offset_high: (self.offset >> 32) as u32,
+                event: ptr::null_mut(),
+            }),
+            buffer: UnsafeCell::new(vec![0; len]),
+            state: Mutex::new(OperationState::default()),
+        });
+        // This reference is released by the completion thread.
+        let overlapped = Arc::into_raw(operation.clone())
+            .cast::<Overlapped>()
+            .cast_mut();
+        // SAFETY: The buffer and the `Overlapped` are owned by the operation, which is kept alive
+        // by the leaked reference until the read completes. Nothing else accesses them until then.
+        let ok = unsafe {
+            let buffer = &mut *operation.buffer.get();
+            ReadFile(
+                self.file.as_raw_handle(),
+                buffer.as_mut_ptr(),
+                len as u32,
+                ptr::null_mut(),
+                overlapped,
+            )
+        };
+        if ok == 0 {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(ERROR_IO_PENDING) => {}
+                // Reading past the end may fail right away, in which case nothing is queued on
+                // the port.
+                Some(ERROR_HANDLE_EOF) => {
+                    // SAFETY: The read failed, so the completion thread won't release this
+                    // reference.
+                    drop(unsafe { Arc::from_raw(overlapped.cast::<Operation>()) });
+                    operation.complete(Ok(0));
+                }
+                _ => {
+                    // SAFETY: See above.
+                    drop(unsafe { Arc::from_raw(overlapped.cast::<Operation>()) });
+                    return Err(err);
+                }
+            }
+        }
+        // A read completing right away is still queued on the port, and completes there.
+        Ok(operation)
+    }
+}
+
+impl Drop for OverlappedFile {
+    fn drop(&mut self) {
+        if let Some(operation) = self.pending.take() {
+            // SAFETY: The file is still open, and the operation is kept alive until the
+            // cancelled read completes on the port.
+            unsafe {
+                CancelIoEx(self.file.as_raw_handle(), operation.overlapped.get());
+            }
+        }
+    }
+}
+
+impl AsyncRead for OverlappedFile {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let operation = match self.pending.take() {
+            Some(operation) => operation,
+            None => self.start_read(buf.len())?,
+        };
+        match operation.poll(cx) {
+            Poll::Ready(Ok(n)) => {
+                // SAFETY: The read completed, so the kernel doesn't write to the buffer anymore.
+                let buffer = unsafe { &*operation.buffer.get() };
+                // The buffer may be bigger than `buf` if `buf` shrank since the read started.
+                let n = n.min(buf.len());
+                buf[..n].copy_from_slice(&buffer[..n]);
+                self.offset += n as u64;
+                Poll::Ready(Ok(n))
+            }
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => {
+                self.pending = Some(operation);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl AsyncSeek for OverlappedFile {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let offset = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.len.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.offset.checked_add_signed(delta),
+        };
+        let Some(offset) = offset else {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "seek position is out of range",
+            )));
+        };
+        // Reads are positioned, so a read started at the previous offset is discarded.
+        drop(self.pending.take());
+        self.offset = offset;
+        Poll::Ready(Ok(offset))
+    }
+}
+
+impl Reader for OverlappedFile {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, io::Result<usize>, STACK_FUTURE_SIZE> {
+        StackFuture::from(async move {
+            let start = buf.len();
+            // Read the rest of the file with as few reads as possible, instead of the small
+            // chunks of the provided implementation.
+            while self.offset < self.len {
+                let filled = buf.len();
+                let remaining = usize::try_from(self.len - self.offset).unwrap_or(usize::MAX);
+                buf.resize(filled + remaining.min(MAX_READ_SIZE), 0);
+                let result =
+                    poll_fn(|cx| Pin::new(&mut *self).poll_read(cx, &mut buf[filled..])).await;
+                match result {
+                    Ok(n) => {
+                        buf.truncate(filled + n);
+                        if n == 0 {
+                            // The file shrank since it was opened.
+                            break;
+                        }
+                    }
+                    Err(err) => {
+                        buf.truncate(filled);
+                        return Err(err);
+                    }
+                }
+            }
+            Ok(buf.len() - start)
+        })
+    }
+
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OverlappedFile, MAX_READ_SIZE};
+    use crate::io::Reader;
+    use alloc::{format, vec, vec::Vec};
+    use bevy_tasks::block_on;
+    use futures_io::SeekFrom;
+    use futures_lite::{future::poll_once, AsyncReadExt, AsyncSeekExt};
+    use std::path::{Path, PathBuf};
+
+    fn test_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "bevy_asset_overlapped_{name}_{}",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn open(path: &Path) -> OverlappedFile {
+        block_on(OverlappedFile::open(path))
+            .unwrap()
+            .expect("overlapped IO should be available")
+    }
+
+    #[test]
+    fn read_to_end_in_several_reads() {
+        let contents: Vec<u8> = (0..MAX_READ_SIZE * 5 / 2).map(|i| i as u8).collect();
+        let path = test_file("read_to_end", &contents);
+
+        let mut file = open(&path);
+        let mut bytes = vec![1, 2, 3];
+        let read = block_on(Reader::read_to_end(&mut file, &mut bytes)).unwrap();
+        assert_eq!(read, contents.len());
+        assert_eq!(bytes[..3], [1, 2, 3]);
+        assert_eq!(bytes[3..], contents);
+
+        // The file is fully read.
+        let read = block_on(Reader::read_to_end(&mut file, &mut bytes)).unwrap();
+        assert_eq!(read, 0);
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn read_and_seek() {
+        let path = test_file("read_and_seek", b"0123456789");
+        let mut file = open(&path);
+
+        block_on(async {
+            let mut buf = [0; 4];
+            assert_eq!(file.read(&mut buf).await.unwrap(), 4);
+            assert_eq!(&buf, b"0123");
+
+            assert_eq!(file.seek(SeekFrom::Current(2)).await.unwrap(), 6);
+            assert_eq!(file.read(&mut buf).await.unwrap(), 4);
+            assert_eq!(&buf, b"6789");
+            // Reading at the end of the file reads nothing.
+            assert_eq!(file.read(&mut buf).await.unwrap(), 0);
+
+            assert_eq!(file.seek(SeekFrom::End(-3)).await.unwrap(), 7);
+            let mut rest = Vec::new();
+            file.read_to_end(&mut rest).await.unwrap();
+            assert_eq!(rest, b"789");
+
+            assert!(file.seek(SeekFrom::Current(-20)).await.is_err());
+            assert_eq!(file.seek(SeekFrom::Start(1)).await.unwrap(), 1);
+            assert_eq!(file.read(&mut buf).await.unwrap(), 4);
+            assert_eq!(&buf, b"1234");
+        });
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn drop_or_seek_during_a_read() {
+        let contents: Vec<u8> = (0..MAX_READ_SIZE).map(|i| i as u8).collect();
+        let path = test_file("drop_during_read", &contents);
+
+        // Seeking discards a read in flight, and the next read starts at the new offset.
+        let mut file = open(&path);
+        let mut buf = vec![0; MAX_READ_SIZE];
+        block_on(async {
+            let _ = poll_once(file.read(&mut buf)).await;
+            file.seek(SeekFrom::Start(10)).await.unwrap();
+            let mut small = [0; 4];
+            assert_eq!(file.read(&mut small).await.unwrap(), 4);
+            assert_eq!(small, contents[10..14]);
+        });
+
+        // Dropping the file cancels a read in flight, which can't write to freed memory.
+        let mut file = open(&path);
+        block_on(async {
+            let _ = poll_once(file.read(&mut buf)).await;
+        });
+        drop(file);
+
+        let mut bytes = Vec::new();
+        block_on(Reader::read_to_end(&mut open(&path), &mut bytes)).unwrap();
+        assert_eq!(bytes, contents);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -14,7 +14,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use super::{FileAssetReader, FileAssetWriter};
+use super::{get_temp_write_path, AtomicFileWriter, FileAssetReader, FileAssetWriter};
 
 struct FileReader(File);
 
@@ -100,9 +100,17 @@ impl Stream for DirReader {
 
 impl AssetReader for FileAssetReader {
     async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
+        #[cfg(feature = "mmap")]
+        if let Some(file) = self.map_file(&full_path)? {
+            let reader: Box<dyn Reader + 'a> = Box::new(file);
+            return Ok(reader);
+        }
         match File::open(&full_path) {
-            Ok(file) => Ok(FileReader(file)),
+            Ok(file) => {
+                let reader: Box<dyn Reader + 'a> = Box::new(FileReader(file));
+                Ok(reader)
+            }
             Err(e) => {
                 if e.kind() == std::io::ErrorKind::NotFound {
                     Err(AssetReaderError::NotFound(full_path))
@@ -115,7 +123,7 @@ impl AssetReader for FileAssetReader {
 
     async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
+        let full_path = self.full_path(meta_path)?;
         match File::open(&full_path) {
             Ok(file) => Ok(FileReader(file)),
             Err(e) => {
@@ -132,10 +140,12 @@ impl AssetReader for FileAssetReader {
         &'a self,
         path: &'a Path,
     ) -> Result<Box<PathStream>, AssetReaderError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         match read_dir(&full_path) {
             Ok(read_dir) => {
-                let root_path = self.root_path.clone();
+                // The entries are listed relative to `path`, as the full path of the directory is
+                // canonical with some symlink policies.
+                let directory = path.to_owned();
                 let mapped_stream = read_dir.filter_map(move |f| {
                     f.ok().and_then(|dir_entry| {
                         let path = dir_entry.path();
@@ -155,8 +165,7 @@ impl AssetReader for FileAssetReader {
                             return None;
                         }
 
-                        let relative_path = path.strip_prefix(&root_path).unwrap();
-                        Some(relative_path.to_owned())
+                        Some(directory.join(dir_entry.file_name()))
                     })
                 });
                 let read_dir: Box<PathStream> = Box::new(DirReader(mapped_stream.collect()));
@@ -173,63 +182,82 @@ impl AssetReader for FileAssetReader {
     }
 
     async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         let metadata = full_path
             .metadata()
             .map_err(|_e| AssetReaderError::NotFound(path.to_owned()))?;
         Ok(metadata.file_type().is_dir())
     }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        self.full_path(path).is_ok_and(|full_path| {
+            full_path
+                .metadata()
+                .is_ok_and(|metadata| metadata.is_file())
+        })
+    }
 }
 
-impl AssetWriter for FileAssetWriter {
-    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
-        let full_path = self.root_path.join(path);
+impl FileAssetWriter {
+    fn create_writer(&self, full_path: PathBuf) -> Result<Box<Writer>, AssetWriterError> {
         if let Some(parent) = full_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        let file = File::create(&full_path)?;
-        let writer: Box<Writer> = Box::new(FileWriter(file));
+        if !self.atomic_writes {
+            let file = File::create(&full_path)?;
+            let writer: Box<Writer> = Box::new(FileWriter(file));
+            return Ok(writer);
+        }
+        let temp_path = get_temp_write_path(&full_path);
+        let file = File::create(&temp_path)?;
+        let writer: Box<Writer> = Box::new(AtomicFileWriter::new(
+            FileWriter(file),
+            temp_path,
+            full_path,
+        ));
         Ok(writer)
     }
+}
+
+impl AssetWriter for FileAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        let full_path = self.full_path(path)?;
+        self.create_writer(full_path)
+    }
 
     async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
-        if let Some(parent) = full_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        let file = File::create(&full_path)?;
-        let writer: Box<Writer> = Box::new(FileWriter(file));
-        Ok(writer)
+        let full_path = self.full_path(meta_path)?;
+        self.create_writer(full_path)
     }
 
     async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         std::fs::remove_file(full_path)?;
         Ok(())
     }
 
     async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
         let meta_path = get_meta_path(path);
-        let full_path = self.root_path.join(meta_path);
+        let full_path = self.full_path(meta_path)?;
         std::fs::remove_file(full_path)?;
         Ok(())
     }
 
     async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         std::fs::create_dir_all(full_path)?;
         Ok(())
     }
 
     async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         std::fs::remove_dir_all(full_path)?;
         Ok(())
     }
 
     async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         std::fs::remove_dir(full_path)?;
         Ok(())
     }
@@ -238,7 +266,7 @@ impl AssetWriter for FileAssetWriter {
         &'a self,
         path: &'a Path,
     ) -> Result<(), AssetWriterError> {
-        let full_path = self.root_path.join(path);
+        let full_path = self.full_path(path)?;
         std::fs::remove_dir_all(&full_path)?;
         std::fs::create_dir_all(&full_path)?;
         Ok(())
@@ -249,8 +277,8 @@ impl AssetWriter for FileAssetWriter {
         old_path: &'a Path,
         new_path: &'a Path,
     ) -> Result<(), AssetWriterError> {
-        let full_old_path = self.root_path.join(old_path);
-        let full_new_path = self.root_path.join(new_path);
+        let full_old_path = self.full_path(old_path)?;
+        let full_new_path = self.full_path(new_path)?;
         if let Some(parent) = full_new_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
@@ -265,8 +293,8 @@ impl AssetWriter for FileAssetWriter {
     ) -> Result<(), AssetWriterError> {
         let old_meta_path = get_meta_path(old_path);
         let new_meta_path = get_meta_path(new_path);
-        let full_old_path = self.root_path.join(old_meta_path);
-        let full_new_path = self.root_path.join(new_meta_path);
+        let full_old_path = self.full_path(old_meta_path)?;
+        let full_new_path = self.full_path(new_meta_path)?;
         if let Some(parent) = full_new_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
@@ -255,6 +255,18 @@ pub struct MemoryAssetReader {
     pub root: Dir,
 }
 
+#[cfg(test)]
+impl MemoryAssetReader {
+    /// Creates a reader of the given text assets, as `(path, contents)` pairs.
+    pub(crate) fn from_texts(assets: &[(&str, &str)]) -> Self {
+        let root = Dir::default();
+        for (path, contents) in assets {
+            root.insert_asset_text(Path::new(path), contents);
+        }
+        Self { root }
+    }
+}
+
 /// In-memory [`AssetWriter`] implementation.
 ///
 /// This is primarily intended for unit tests.
@@ -358,6 +370,13 @@ impl Reader for DataReader {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         Ok(self)
     }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        Some(crate::io::read_to_slice(
+            self.data.value(),
+            &mut self.bytes_read,
+        ))
+    }
 }
 
 impl AssetReader for MemoryAssetReader {
@@ -397,6 +416,10 @@ impl AssetReader for MemoryAssetReader {
     async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
         Ok(self.root.get_dir(path).is_some())
     }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        self.root.get_asset(path).is_some()
+    }
 }
 
 /// A writer that writes into [`Dir`], buffering internally until flushed/closed.
@@ -0,0 +1,250 @@
+use crate::io::{
+    AssetReader, AssetReaderError, AssetSourceId, ErasedAssetReader, PathStream, Reader,
+    ReaderNotSeekableError, SeekableReader, STACK_FUTURE_SIZE,
+};
+use alloc::{boxed::Box, format, sync::Arc, vec::Vec};
+use bevy_diagnostic::DiagnosticPath;
+use bevy_platform::time::Instant;
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+    time::Duration,
+};
+use futures_io::{AsyncRead, AsyncSeek, SeekFrom};
+use stackfuture::StackFuture;
+use std::path::Path;
+
+/// The upper bounds of the buckets of [`AssetSourceMetrics::latency_histogram`]. The last bucket
+/// counts the reads slower than the last bound.
+pub const ASSET_READ_LATENCY_BUCKETS: [Duration; 6] = [
+    Duration::from_millis(1),
+    Duration::from_millis(4),
+    Duration::from_millis(16),
+    Duration::from_millis(64),
+    Duration::from_millis(256),
+    Duration::from_millis(1024),
+];
+
+/// Counts the reads of an [`AssetSource`](crate::io::AssetSource), enabled with
+/// [`AssetSourceBuilder::with_metrics`](crate::io::AssetSourceBuilder::with_metrics).
+///
+/// Asset and meta reads are both counted. The latency of a read is the time it takes to open the
+/// file, before any byte is read.
+///
+/// These metrics are also published to [`bevy_diagnostic`], at the paths returned by
+/// [`AssetIoDiagnosticPaths::new`].
+#[derive(Debug, Default)]
+pub struct AssetSourceMetrics {
+    reads: AtomicU64,
+    not_found: AtomicU64,
+    errors: AtomicU64,
+    bytes_read: AtomicU64,
+    total_latency_nanos: AtomicU64,
+    latency_buckets: [AtomicU64; ASSET_READ_LATENCY_BUCKETS.len() + 1],
+}
+
+impl AssetSourceMetrics {
+    /// The number of files opened successfully.
+    pub fn reads(&self) -> u64 {
+        self.reads.load(Ordering::Relaxed)
+    }
+
+    /// The number of reads of files that don't exist, such as missing meta files.
+    pub fn not_found(&self) -> u64 {
+        self.not_found.load(Ordering::Relaxed)
+    }
+
+    /// The number of reads that failed for another reason than the file not existing.
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    /// The number of bytes read from the opened files.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// The mean latency of the successful reads, or [`None`] if there weren't any.
+    pub fn mean_latency(&self) -> Option<Duration> {
+        let reads = self.reads();
+        (reads > 0)
+            .then(|| Duration::from_nanos(self.total_latency_nanos.load(Ordering::Relaxed) / reads))
+    }
+
+    /// The number of successful reads in each latency bucket. See [`ASSET_READ_LATENCY_BUCKETS`].
+    pub fn latency_histogram(&self) -> [u64; ASSET_READ_LATENCY_BUCKETS.len() + 1] {
+        core::array::from_fn(|bucket| self.latency_buckets[bucket].load(Ordering::Relaxed))
+    }
+
+    fn record_open<T>(&self, result: &Result<T, AssetReaderError>, latency: Duration) {
+        match result {
+            Ok(_) => {
+                self.reads.fetch_add(1, Ordering::Relaxed);
+                self.total_latency_nanos
+                    .fetch_add(latency.as_nanos() as u64, Ordering::Relaxed);
+                let bucket = ASSET_READ_LATENCY_BUCKETS
+                    .iter()
+                    .position(|bound| latency <= *bound)
+                    .unwrap_or(ASSET_READ_LATENCY_BUCKETS.len());
+                self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+            }
+            Err(AssetReaderError::NotFound(_)) => {
+                self.not_found.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// The [`DiagnosticPath`]s of the [`AssetSourceMetrics`] of an asset source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetIoDiagnosticPaths {
+    /// The path of [`AssetSourceMetrics::reads`].
+    pub reads: DiagnosticPath,
+    /// The path of [`AssetSourceMetrics::bytes_read`].
+    pub bytes_read: DiagnosticPath,
+    /// The path of [`AssetSourceMetrics::errors`].
+    pub errors: DiagnosticPath,
+    /// The path of [`AssetSourceMetrics::mean_latency`], in milliseconds.
+    pub mean_latency: DiagnosticPath,
+}
+
+impl AssetIoDiagnosticPaths {
+    /// Returns the paths of the metrics of the source with the given `id`, such as
+    /// `asset_io/default/reads` or `asset_io/http/mean_latency`.
+    pub fn new(id: &AssetSourceId) -> Self {
+        let source = match id {
+            AssetSourceId::Default => "default",
+            AssetSourceId::Name(name) => name,
+        };
+        let path = |metric: &str| DiagnosticPath::new(format!("asset_io/{source}/{metric}"));
+        Self {
+            reads: path("reads"),
+            bytes_read: path("bytes_read"),
+            errors: path("errors"),
+            mean_latency: path("mean_latency"),
+        }
+    }
+}
+
+/// An [`AssetReader`] recording [`AssetSourceMetrics`] for the reads of another reader.
+pub(crate) struct MetricsAssetReader {
+    reader: Box<dyn ErasedAssetReader>,
+    metrics: Arc<AssetSourceMetrics>,
+}
+
+impl MetricsAssetReader {
+    pub(crate) fn new(
+        reader: Box<dyn ErasedAssetReader>,
+        metrics: Arc<AssetSourceMetrics>,
+    ) -> Self {
+        Self { reader, metrics }
+    }
+}
+
+impl AssetReader for MetricsAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let start = Instant::now();
+        let result = self.reader.read(path).await;
+        self.metrics.record_open(&result, start.elapsed());
+        Ok(MetricsReader {
+            reader: result?,
+            metrics: &self.metrics,
+        })
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let start = Instant::now();
+        let result = self.reader.read_meta(path).await;
+        self.metrics.record_open(&result, start.elapsed());
+        Ok(MetricsReader {
+            reader: result?,
+            metrics: &self.metrics,
+        })
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.reader.read_directory(path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.reader.is_directory(path).await
+    }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        self.reader.exists(path).await
+    }
+}
+
+/// A [`Reader`] adding the bytes it reads to [`AssetSourceMetrics::bytes_read`].
+struct MetricsReader<'a> {
+    reader: Box<dyn Reader + 'a>,
+    metrics: &'a AssetSourceMetrics,
+}
+
+impl AsyncRead for MetricsReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let result = Pin::new(&mut self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = result {
+            self.metrics
+                .bytes_read
+                .fetch_add(n as u64, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+impl Reader for MetricsReader<'_> {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        let future = async {
+            let n = self.reader.read_to_end(buf).await?;
+            self.metrics
+                .bytes_read
+                .fetch_add(n as u64, Ordering::Relaxed);
+            Ok(n)
+        };
+        // The inner future already fills up the stack future, so this one needs to be boxed.
+        StackFuture::from(Box::pin(future))
+    }
+
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        self.reader.seekable()?;
+        Ok(self)
+    }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        let bytes = self.reader.read_to_slice()?;
+        self.metrics
+            .bytes_read
+            .fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Some(bytes)
+    }
+}
+
+impl AsyncSeek for MetricsReader<'_> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        // This is only reachable through `MetricsReader::seekable`, which checks that the inner
+        // reader is seekable.
+        match self.get_mut().reader.seekable() {
+            Ok(inner) => Pin::new(inner).poll_seek(cx, pos),
+            Err(error) => Poll::Ready(Err(std::io::Error::other(error))),
+        }
+    }
+}
@@ -12,6 +12,10 @@ pub mod embedded;
 pub mod file;
 pub mod memory;
 pub mod processor_gated;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod roaming;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod user_library;
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
 #[cfg(any(feature = "http", feature = "https"))]
@@ -20,12 +24,22 @@ pub mod web;
 #[cfg(test)]
 pub mod gated;
 
+mod case_insensitive;
+mod metrics;
+mod mount;
+mod platform;
+mod retry;
 mod source;
 
 pub use futures_lite::AsyncWriteExt;
+pub use metrics::{AssetIoDiagnosticPaths, AssetSourceMetrics, ASSET_READ_LATENCY_BUCKETS};
+pub use mount::*;
+pub use platform::*;
+pub use retry::AssetRetryPolicy;
 pub use source::*;
 
 use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use bevy_platform::collections::HashSet;
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
 use core::{
     mem::size_of,
@@ -33,7 +47,7 @@ use core::{
     task::{Context, Poll},
 };
 use futures_io::{AsyncRead, AsyncSeek, AsyncWrite};
-use futures_lite::Stream;
+use futures_lite::{Stream, StreamExt};
 use std::{
     io::SeekFrom,
     path::{Path, PathBuf},
@@ -58,6 +72,36 @@ pub enum AssetReaderError {
     HttpError(u16),
 }
 
+impl AssetReaderError {
+    /// Returns `true` if this error is likely to go away when the read is retried, e.g. a file
+    /// temporarily locked by another process or a network timeout.
+    ///
+    /// This is the default [`AssetRetryPolicy::retry_if`].
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::NotFound(_) => false,
+            Self::Io(error) => {
+                // Windows reports files opened by another process, e.g. an antivirus scanning
+                // them, with ERROR_SHARING_VIOLATION or ERROR_LOCK_VIOLATION.
+                if cfg!(target_os = "windows") && matches!(error.raw_os_error(), Some(32 | 33)) {
+                    return true;
+                }
+                matches!(
+                    error.kind(),
+                    std::io::ErrorKind::Interrupted
+                        | std::io::ErrorKind::WouldBlock
+                        | std::io::ErrorKind::TimedOut
+                        | std::io::ErrorKind::ResourceBusy
+                        | std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::ConnectionAborted
+                )
+            }
+            // Request timeout, too early, too many requests, and server errors that may go away.
+            Self::HttpError(status) => matches!(status, 408 | 425 | 429 | 500 | 502 | 503 | 504),
+        }
+    }
+}
+
 impl PartialEq for AssetReaderError {
     /// Equality comparison for `AssetReaderError::Io` is not full (only through `ErrorKind` of inner error)
     #[inline]
@@ -137,6 +181,33 @@ pub trait Reader: AsyncRead + Unpin + Send + Sync {
     /// # };
     /// ```
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError>;
+
+    /// Reads the rest of the contents of this reader without copying them, if they are already
+    /// in memory. This is the case for embedded assets, or for memory-mapped files.
+    ///
+    /// Returns [`None`] if the contents are not in memory, in which case nothing is read.
+    /// [`AssetLoader`](crate::AssetLoader) implementations that only need a `&[u8]` can try this
+    /// before falling back to [`Reader::read_to_end`]:
+    ///
+    /// ```
+    /// # use bevy_asset::io::{Reader, VecReader};
+    /// # use std::vec::Vec;
+    /// # async {
+    /// # let mut original_reader = VecReader::new(Vec::new());
+    /// # let reader: &mut dyn Reader = &mut original_reader;
+    /// let mut fallback_bytes = Vec::new();
+    /// let bytes = match reader.read_to_slice() {
+    ///     Some(bytes) => bytes,
+    ///     None => {
+    ///         reader.read_to_end(&mut fallback_bytes).await.unwrap();
+    ///         &fallback_bytes
+    ///     }
+    /// };
+    /// # };
+    /// ```
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        None
+    }
 }
 
 /// A [`Reader`] that also has [`AsyncSeek`] functionality.
@@ -163,6 +234,10 @@ impl Reader for Box<dyn Reader + '_> {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         (**self).seekable()
     }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        (**self).read_to_slice()
+    }
 }
 
 /// A future that returns a value or an [`AssetReaderError`]
@@ -222,6 +297,12 @@ pub trait AssetReader: Send + Sync + 'static {
         &'a self,
         path: &'a Path,
     ) -> impl ConditionalSendFuture<Output = Result<bool, AssetReaderError>>;
+    /// Returns true if an asset exists at the provided path. Readers should override this to check
+    /// for the asset without opening it, as the default implementation calls
+    /// [`AssetReader::read`].
+    fn exists<'a>(&'a self, path: &'a Path) -> impl ConditionalSendFuture<Output = bool> {
+        async { self.read(path).await.is_ok() }
+    }
     /// Reads asset metadata bytes at the given `path` into a [`Vec<u8>`]. This is a convenience
     /// function that wraps [`AssetReader::read_meta`] by default.
     fn read_meta_bytes<'a>(
@@ -260,6 +341,8 @@ pub trait ErasedAssetReader: Send + Sync + 'static {
         &'a self,
         path: &'a Path,
     ) -> BoxedFuture<'a, Result<bool, AssetReaderError>>;
+    /// Returns true if an asset exists at the provided path.
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, bool>;
     /// Reads asset metadata bytes at the given `path` into a [`Vec<u8>`]. This is a convenience
     /// function that wraps [`ErasedAssetReader::read_meta`] by default.
     fn read_meta_bytes<'a>(
@@ -299,6 +382,9 @@ impl<T: AssetReader> ErasedAssetReader for T {
     ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
         Box::pin(Self::is_directory(self, path))
     }
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, bool> {
+        Box::pin(Self::exists(self, path))
+    }
     fn read_meta_bytes<'a>(
         &'a self,
         path: &'a Path,
@@ -394,7 +480,7 @@ pub trait AssetWriter: Send + Sync + 'static {
         async {
             let mut writer = self.write(path).await?;
             writer.write_all(bytes).await?;
-            writer.flush().await?;
+            writer.close().await?;
             Ok(())
         }
     }
@@ -407,7 +493,7 @@ pub trait AssetWriter: Send + Sync + 'static {
         async {
             let mut meta_writer = self.write_meta(path).await?;
             meta_writer.write_all(bytes).await?;
-            meta_writer.flush().await?;
+            meta_writer.close().await?;
             Ok(())
         }
     }
@@ -592,6 +678,25 @@ pub enum AssetSourceEvent {
     },
 }
 
+/// How the readers and writers of the local filesystem treat the symbolic links and junctions in
+/// the paths they access, set with `FileAssetReader::with_symlink_policy` and
+/// `FileAssetWriter::with_symlink_policy`, or with
+/// [`AssetPlugin::symlink_policy`](crate::AssetPlugin::symlink_policy) for the default source.
+///
+/// The root directory of the readers and writers can always be a link.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SymlinkPolicy {
+    /// Links are followed wherever they point to. This lets development setups link asset folders
+    /// shared between projects into their asset directories.
+    #[default]
+    Follow,
+    /// Paths going through a link are denied.
+    Deny,
+    /// Links are followed, but paths resolving to a file outside of the root directory are
+    /// denied, so that assets can't be used to read or write arbitrary files.
+    DenyOutsideRoot,
+}
+
 /// A handle to an "asset watcher" process, that will listen for and emit [`AssetSourceEvent`] values for as long as
 /// [`AssetWatcher`] has not been dropped.
 pub trait AssetWatcher: Send + Sync + 'static {}
@@ -648,6 +753,10 @@ impl Reader for VecReader {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         Ok(self)
     }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        Some(read_to_slice(&self.bytes, &mut self.bytes_read))
+    }
 }
 
 /// An [`AsyncRead`] implementation capable of reading a [`&[u8]`].
@@ -697,6 +806,88 @@ impl Reader for SliceReader<'_> {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         Ok(self)
     }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        Some(read_to_slice(self.bytes, &mut self.bytes_read))
+    }
+}
+
+/// A [`Reader`] counting the bytes read from another [`Reader`].
+///
+/// This is used to track how many bytes were read to load each asset.
+pub(crate) struct CountingReader<'a> {
+    inner: &'a mut dyn Reader,
+    bytes_read: u64,
+}
+
+impl<'a> CountingReader<'a> {
+    pub(crate) fn new(inner: &'a mut dyn Reader) -> Self {
+        Self {
+            inner,
+            bytes_read: 0,
+        }
+    }
+
+    /// The number of bytes read so far.
+    pub(crate) fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+}
+
+impl AsyncRead for CountingReader<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let result = Pin::new(&mut *this.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(n)) = result {
+            this.bytes_read += n as u64;
+        }
+        result
+    }
+}
+
+impl AsyncSeek for CountingReader<'_> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<std::io::Result<u64>> {
+        // This is only reachable through `CountingReader::seekable`, which checks that the inner
+        // reader is seekable.
+        match self.get_mut().inner.seekable() {
+            Ok(inner) => Pin::new(inner).poll_seek(cx, pos),
+            Err(error) => Poll::Ready(Err(std::io::Error::other(error))),
+        }
+    }
+}
+
+impl Reader for CountingReader<'_> {
+    fn read_to_end<'a>(
+        &'a mut self,
+        buf: &'a mut Vec<u8>,
+    ) -> StackFuture<'a, std::io::Result<usize>, STACK_FUTURE_SIZE> {
+        let future = async {
+            let n = self.inner.read_to_end(buf).await?;
+            self.bytes_read += n as u64;
+            Ok(n)
+        };
+        // The inner future already fills up the stack future, so this one needs to be boxed.
+        StackFuture::from(Box::pin(future))
+    }
+
+    fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
+        self.inner.seekable()?;
+        Ok(self)
+    }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        let bytes = self.inner.read_to_slice()?;
+        self.bytes_read += bytes.len() as u64;
+        Some(bytes)
+    }
 }
 
 /// Performs a read from the `slice` into `buf`.
@@ -761,6 +952,44 @@ pub(crate) fn read_to_end<'a>(
     })
 }
 
+/// Returns the bytes of `source` that weren't read yet, and marks them as read.
+pub(crate) fn read_to_slice<'a>(source: &'a [u8], bytes_read: &mut usize) -> &'a [u8] {
+    let rest = source.get(*bytes_read..).unwrap_or_default();
+    *bytes_read = source.len().max(*bytes_read);
+    rest
+}
+
+/// Lists the entries of the directory at `path` in all of the `readers`, in the order of the
+/// readers and without duplicates. The directory is only [`AssetReaderError::NotFound`] if it is
+/// missing from all of them.
+pub(crate) async fn read_merged_directory(
+    readers: &[&dyn ErasedAssetReader],
+    path: &Path,
+) -> Result<Box<PathStream>, AssetReaderError> {
+    let mut found = false;
+    let mut seen = <HashSet<PathBuf>>::default();
+    let mut paths = Vec::new();
+    for reader in readers {
+        match reader.read_directory(path).await {
+            Ok(stream) => {
+                found = true;
+                let entries: Vec<PathBuf> = stream.collect().await;
+                paths.extend(
+                    entries
+                        .into_iter()
+                        .filter(|entry| seen.insert(entry.clone())),
+                );
+            }
+            Err(AssetReaderError::NotFound(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+    if !found {
+        return Err(AssetReaderError::NotFound(path.to_path_buf()));
+    }
+    Ok(Box::new(futures_lite::stream::iter(paths)))
+}
+
 /// Appends `.meta` to the given path:
 /// - `foo` becomes `foo.meta`
 /// - `foo.bar` becomes `foo.bar.meta`
@@ -807,4 +1036,19 @@ mod tests {
             "foo.bar.meta"
         );
     }
+
+    #[test]
+    fn read_to_slice_reads_rest() {
+        let mut reader = VecReader::new(b"hello world".to_vec());
+        let mut buf = [0; 6];
+        assert_eq!(
+            slice_read(&reader.bytes, &mut reader.bytes_read, &mut buf),
+            6
+        );
+
+        let mut counting_reader = CountingReader::new(&mut reader);
+        assert_eq!(counting_reader.read_to_slice(), Some(&b"world"[..]));
+        assert_eq!(counting_reader.read_to_slice(), Some(&b""[..]));
+        assert_eq!(counting_reader.bytes_read, 5);
+    }
 }
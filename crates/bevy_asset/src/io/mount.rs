@@ -0,0 +1,372 @@
+use crate::{
+    io::{
+        read_merged_directory, AssetReader, AssetReaderError, AssetSourceId, ErasedAssetReader,
+        PathStream, Reader, VecReader,
+    },
+    AssetServer,
+};
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use async_channel::{Receiver, Sender};
+use bevy_ecs::{
+    message::{Message, MessageWriter},
+    resource::Resource,
+    system::Res,
+};
+use bevy_platform::sync::RwLock;
+use std::{path::Path, sync::PoisonError};
+
+/// Identifies a mount added with [`AssetMounts::mount`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MountId(u64);
+
+/// The mount table of all asset sources, allowing additional roots to be mounted on top of a source
+/// at runtime, e.g. to load user mods and patches without restarting.
+///
+/// A mount overlays the assets of its source by path: when loading `path/to/asset.png` from a source,
+/// the most recently added mount that contains `path/to/asset.png` is used, falling back to the
+/// source's own reader when no mount contains it. Metadata files are looked up the same way, and
+/// directories list the assets of all mounts and of the source.
+///
+/// Mounts are not watched for changes. When a mount is added or removed, the loaded assets it
+/// contains are reloaded, and an [`AssetMountEvent`] is sent.
+///
+/// In [`AssetMode::Processed`](crate::AssetMode::Processed), mounts overlay the processed assets,
+/// so they must contain processed assets and their metadata.
+///
+/// This resource is created with the [`AssetSourceBuilders`](crate::io::AssetSourceBuilders), and
+/// is inserted by the [`AssetPlugin`](crate::AssetPlugin).
+///
+/// ```no_run
+/// # use bevy_asset::io::{file::FileAssetReader, AssetMounts, AssetSourceId};
+/// # use bevy_ecs::system::Res;
+/// fn enable_mod(mounts: Res<AssetMounts>) {
+///     // Assets in `mods/hd_textures` now replace the default assets with the same path.
+///     let id = mounts.mount(AssetSourceId::Default, FileAssetReader::new("mods/hd_textures"));
+///
+///     // Restores the default assets.
+///     mounts.unmount(id);
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct AssetMounts {
+    table: Arc<RwLock<MountTable>>,
+    event_sender: Sender<(AssetMountEvent, Arc<dyn ErasedAssetReader>)>,
+    event_receiver: Receiver<(AssetMountEvent, Arc<dyn ErasedAssetReader>)>,
+}
+
+#[derive(Default)]
+struct MountTable {
+    next_id: u64,
+    /// The mounts of all sources, from the least to the most recently added.
+    mounts: Vec<Mount>,
+}
+
+struct Mount {
+    id: MountId,
+    source: AssetSourceId<'static>,
+    reader: Arc<dyn ErasedAssetReader>,
+}
+
+/// A [`Message`] sent when a mount is added to or removed from the [`AssetMounts`].
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub enum AssetMountEvent {
+    /// A mount was added on top of the given source.
+    Mounted {
+        /// The id of the new mount.
+        id: MountId,
+        /// The source overlaid by the mount.
+        source: AssetSourceId<'static>,
+    },
+    /// A mount was removed from the given source.
+    Unmounted {
+        /// The id of the removed mount.
+        id: MountId,
+        /// The source that was overlaid by the mount.
+        source: AssetSourceId<'static>,
+    },
+}
+
+impl Default for AssetMounts {
+    fn default() -> Self {
+        let (event_sender, event_receiver) = async_channel::unbounded();
+        Self {
+            table: Default::default(),
+            event_sender,
+            event_receiver,
+        }
+    }
+}
+
+impl AssetMounts {
+    /// Mounts `reader` on top of the asset source `source`. Its assets take priority over the
+    /// assets of the source and of all previous mounts with the same path.
+    pub fn mount(
+        &self,
+        source: impl Into<AssetSourceId<'static>>,
+        reader: impl AssetReader,
+    ) -> MountId {
+        self.mount_erased(source.into(), Arc::new(reader))
+    }
+
+    /// Mounts `reader` on top of the asset source `source`, see [`AssetMounts::mount`].
+    pub(crate) fn mount_erased(
+        &self,
+        source: AssetSourceId<'static>,
+        reader: Arc<dyn ErasedAssetReader>,
+    ) -> MountId {
+        let id = {
+            let mut table = self.table.write().unwrap_or_else(PoisonError::into_inner);
+            let id = MountId(table.next_id);
+            table.next_id += 1;
+            table.mounts.push(Mount {
+                id,
+                source: source.clone(),
+                reader: reader.clone(),
+            });
+            id
+        };
+        let _ = self
+            .event_sender
+            .try_send((AssetMountEvent::Mounted { id, source }, reader));
+        id
+    }
+
+    /// Removes the mount with the given `id`. Returns `false` if it was already removed.
+    pub fn unmount(&self, id: MountId) -> bool {
+        let mount = {
+            let mut table = self.table.write().unwrap_or_else(PoisonError::into_inner);
+            let Some(index) = table.mounts.iter().position(|mount| mount.id == id) else {
+                return false;
+            };
+            table.mounts.remove(index)
+        };
+        let _ = self.event_sender.try_send((
+            AssetMountEvent::Unmounted {
+                id,
+                source: mount.source,
+            },
+            mount.reader,
+        ));
+        true
+    }
+
+    /// Returns the mounts of the asset source `source`, from the lowest to the highest priority.
+    pub fn mounts<'a>(&self, source: impl Into<AssetSourceId<'a>>) -> Vec<MountId> {
+        let source = source.into();
+        let table = self.table.read().unwrap_or_else(PoisonError::into_inner);
+        table
+            .mounts
+            .iter()
+            .filter(|mount| mount.source == source)
+            .map(|mount| mount.id)
+            .collect()
+    }
+
+    /// Returns the readers mounted on `source`, from the highest to the lowest priority.
+    fn readers(&self, source: &AssetSourceId<'static>) -> Vec<Arc<dyn ErasedAssetReader>> {
+        let table = self.table.read().unwrap_or_else(PoisonError::into_inner);
+        table
+            .mounts
+            .iter()
+            .rev()
+            .filter(|mount| mount.source == *source)
+            .map(|mount| mount.reader.clone())
+            .collect()
+    }
+
+    /// Returns the mount events that haven't been handled yet, along with the reader of the added or
+    /// removed mount.
+    pub(crate) fn drain_events(
+        &self,
+    ) -> impl Iterator<Item = (AssetMountEvent, Arc<dyn ErasedAssetReader>)> + '_ {
+        core::iter::from_fn(|| self.event_receiver.try_recv().ok())
+    }
+
+    /// Wraps the `base` reader of `source` so that it is overlaid by the mounts of the source.
+    pub(crate) fn overlay(
+        &self,
+        source: AssetSourceId<'static>,
+        base: Arc<dyn ErasedAssetReader>,
+    ) -> MountedAssetReader {
+        MountedAssetReader {
+            source,
+            base,
+            mounts: self.clone(),
+        }
+    }
+}
+
+/// Sends the [`AssetMountEvent`]s of the [`AssetMounts`], and reloads the loaded assets that are
+/// contained in the added or removed mounts.
+pub fn handle_asset_mount_events(
+    server: Res<AssetServer>,
+    mounts: Res<AssetMounts>,
+    mut mount_events: MessageWriter<AssetMountEvent>,
+) {
+    for (event, reader) in mounts.drain_events() {
+        let (AssetMountEvent::Mounted { source, .. } | AssetMountEvent::Unmounted { source, .. }) =
+            &event;
+        server.reload_assets_in_reader(source.clone(), reader);
+        mount_events.write(event);
+    }
+}
+
+/// An [`AssetReader`] reading from the mounts of an asset source in [`AssetMounts`], before falling
+/// back to the source's own reader.
+pub(crate) struct MountedAssetReader {
+    source: AssetSourceId<'static>,
+    base: Arc<dyn ErasedAssetReader>,
+    mounts: AssetMounts,
+}
+
+/// Reads a whole file from `reader`, so that it can outlive the mount it was read from.
+async fn read_to_vec(mut reader: Box<dyn Reader + '_>) -> Result<VecReader, AssetReaderError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).await?;
+    Ok(VecReader::new(bytes))
+}
+
+impl AssetReader for MountedAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        for reader in self.mounts.readers(&self.source) {
+            match reader.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return Ok(Box::new(read_to_vec(result?).await?)),
+            }
+        }
+        self.base.read(path).await
+    }
+
+    async fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        for reader in self.mounts.readers(&self.source) {
+            match reader.read_meta(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return Ok(Box::new(read_to_vec(result?).await?)),
+            }
+        }
+        self.base.read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mounts = self.mounts.readers(&self.source);
+        if mounts.is_empty() {
+            return self.base.read_directory(path).await;
+        }
+
+        let readers: Vec<&dyn ErasedAssetReader> = mounts
+            .iter()
+            .map(|reader| &**reader)
+            .chain([&*self.base])
+            .collect();
+        read_merged_directory(&readers, path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        for reader in self.mounts.readers(&self.source) {
+            match reader.is_directory(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        self.base.is_directory(path).await
+    }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        for reader in self.mounts.readers(&self.source) {
+            if reader.exists(path).await {
+                return true;
+            }
+        }
+        self.base.exists(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::MemoryAssetReader;
+    use alloc::string::String;
+    use bevy_tasks::block_on;
+    use futures_lite::StreamExt;
+    use std::path::PathBuf;
+
+    fn read_text(reader: &MountedAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            AssetReader::read(reader, Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn last_mount_wins() {
+        let mounts = AssetMounts::default();
+        let reader = mounts.overlay(
+            AssetSourceId::Default,
+            Arc::new(MemoryAssetReader::from_texts(&[
+                ("a.txt", "base"),
+                ("b.txt", "base"),
+            ])),
+        );
+
+        let first = mounts.mount(
+            AssetSourceId::Default,
+            MemoryAssetReader::from_texts(&[("a.txt", "first"), ("c.txt", "first")]),
+        );
+        let second = mounts.mount(
+            AssetSourceId::Default,
+            MemoryAssetReader::from_texts(&[("a.txt", "second")]),
+        );
+        mounts.mount(
+            "other",
+            MemoryAssetReader::from_texts(&[("b.txt", "other source")]),
+        );
+        assert_eq!(mounts.mounts(AssetSourceId::Default), [first, second]);
+
+        assert_eq!(read_text(&reader, "a.txt").unwrap(), "second");
+        assert_eq!(read_text(&reader, "b.txt").unwrap(), "base");
+        assert_eq!(read_text(&reader, "c.txt").unwrap(), "first");
+
+        let mut entries: Vec<PathBuf> = block_on(async {
+            AssetReader::read_directory(&reader, Path::new(""))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        entries.sort();
+        assert_eq!(entries, ["a.txt", "b.txt", "c.txt"].map(PathBuf::from));
+
+        assert!(mounts.unmount(second));
+        assert!(!mounts.unmount(second));
+        assert_eq!(read_text(&reader, "a.txt").unwrap(), "first");
+        assert!(mounts.unmount(first));
+        assert_eq!(read_text(&reader, "a.txt").unwrap(), "base");
+        assert!(matches!(
+            read_text(&reader, "c.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        let events = mounts
+            .drain_events()
+            .map(|(event, _)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[3],
+            AssetMountEvent::Unmounted {
+                id: second,
+                source: AssetSourceId::Default
+            }
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::io::{
+    read_merged_directory, AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader,
+};
+use alloc::boxed::Box;
+use std::path::Path;
+
+/// Selects the [`AssetSourceBuilder`](crate::io::AssetSourceBuilder) of an asset source depending
+/// on the platform the app is compiled for, replacing the `#[cfg(...)]` blocks otherwise needed to
+/// configure a source differently on each platform.
+///
+/// Each arm maps one or more platforms to the expression building the source. The first arm
+/// matching the target platform is used, and `_` matches any platform. Compilation fails if no arm
+/// matches the target platform. The expressions of the other arms are not compiled, so they can
+/// use platform-specific readers.
+///
+/// The supported platforms are `windows`, `macos`, `linux`, `ios`, `android` and `wasm`, as well as
+/// `desktop` (any platform other than `ios`, `android` and `wasm`) and `mobile` (`ios` and
+/// `android`).
+///
+/// ```
+/// use bevy_app::App;
+/// use bevy_asset::{
+///     io::{file::FileAssetReader, AssetSourceBuilder, FallbackAssetReader},
+///     platform_asset_source, AssetApp,
+/// };
+///
+/// # let mut app = App::new();
+/// app.register_asset_source(
+///     "levels",
+///     platform_asset_source! {
+///         // Downloaded levels take priority over the levels shipped with the game.
+///         desktop => AssetSourceBuilder::new(|| {
+///             Box::new(FallbackAssetReader::new(
+///                 FileAssetReader::new("downloads/levels"),
+///                 FileAssetReader::new("assets/levels"),
+///             ))
+///         }),
+///         _ => AssetSourceBuilder::platform_default("assets/levels", None),
+///     },
+/// );
+/// ```
+#[macro_export]
+macro_rules! platform_asset_source {
+    (_ => $source:expr $(,)?) => {
+        $source
+    };
+    ($platform:ident => $source:expr $(,)?) => {
+        $crate::__platform_asset_source!(
+            $platform,
+            $source,
+            ::core::compile_error!("`platform_asset_source!` has no source for this platform")
+        )
+    };
+    ($platform:ident => $source:expr, $($rest:tt)+) => {
+        $crate::__platform_asset_source!(
+            $platform,
+            $source,
+            $crate::platform_asset_source!($($rest)+)
+        )
+    };
+    ($platform:ident $(| $platforms:ident)+ => $source:expr $(, $($rest:tt)*)?) => {
+        $crate::platform_asset_source!(
+            $platform => $source,
+            $($platforms)|+ => $source
+            $(, $($rest)*)?
+        )
+    };
+}
+
+/// Implementation detail of [`platform_asset_source`], do not use this!
+///
+/// Evaluates to `$source` if the target is `$platform`, and to `$otherwise` if it isn't. Only the
+/// selected expression is compiled.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __platform_asset_source {
+    (@cfg ($($cfg:tt)*), $source:expr, $otherwise:expr) => {{
+        #[cfg($($cfg)*)]
+        let source = $source;
+        #[cfg(not($($cfg)*))]
+        let source = $otherwise;
+        source
+    }};
+    (windows, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_os = "windows"), $($args)*)
+    };
+    (macos, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_os = "macos"), $($args)*)
+    };
+    (linux, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_os = "linux"), $($args)*)
+    };
+    (ios, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_os = "ios"), $($args)*)
+    };
+    (android, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_os = "android"), $($args)*)
+    };
+    (wasm, $($args:tt)*) => {
+        $crate::__platform_asset_source!(@cfg (target_arch = "wasm32"), $($args)*)
+    };
+    (mobile, $($args:tt)*) => {
+        $crate::__platform_asset_source!(
+            @cfg (any(target_os = "ios", target_os = "android")),
+            $($args)*
+        )
+    };
+    (desktop, $($args:tt)*) => {
+        $crate::__platform_asset_source!(
+            @cfg (not(any(target_os = "ios", target_os = "android", target_arch = "wasm32"))),
+            $($args)*
+        )
+    };
+    ($platform:ident, $($args:tt)*) => {
+        ::core::compile_error!(::core::concat!(
+            "unknown platform `",
+            ::core::stringify!($platform),
+            "` in `platform_asset_source!`"
+        ))
+    };
+}
+
+/// An [`AssetReader`] reading the assets of another reader when they are not found in its own.
+///
+/// This layers a writable location on top of the assets shipped with the app, e.g. downloaded
+/// content or per-user data on top of the install directory.
+pub struct FallbackAssetReader {
+    primary: Box<dyn ErasedAssetReader>,
+    fallback: Box<dyn ErasedAssetReader>,
+}
+
+impl FallbackAssetReader {
+    /// Creates a reader reading from `primary`, and from `fallback` for the assets `primary` doesn't
+    /// contain.
+    pub fn new(primary: impl AssetReader, fallback: impl AssetReader) -> Self {
+        Self {
+            primary: Box::new(primary),
+            fallback: Box::new(fallback),
+        }
+    }
+}
+
+impl AssetReader for FallbackAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match self.primary.read(path).await {
+            Err(AssetReaderError::NotFound(_)) => self.fallback.read(path).await,
+            result => result,
+        }
+    }
+
+    async fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match self.primary.read_meta(path).await {
+            Err(AssetReaderError::NotFound(_)) => self.fallback.read_meta(path).await,
+            result => result,
+        }
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        read_merged_directory(&[&*self.primary, &*self.fallback], path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        match self.primary.is_directory(path).await {
+            Err(AssetReaderError::NotFound(_)) => self.fallback.is_directory(path).await,
+            result => result,
+        }
+    }
+
+    async fn exists<'a>(&'a self, path: &'a Path) -> bool {
+        self.primary.exists(path).await || self.fallback.exists(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::MemoryAssetReader;
+    use alloc::{string::String, vec::Vec};
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn fallback_reads_missing_assets() {
+        let reader = FallbackAssetReader::new(
+            MemoryAssetReader::from_texts(&[("a.txt", "primary")]),
+            MemoryAssetReader::from_texts(&[("a.txt", "fallback"), ("b.txt", "fallback")]),
+        );
+        let read_text = |path: &str| {
+            block_on(async {
+                let mut bytes = Vec::new();
+                AssetReader::read(&reader, Path::new(path))
+                    .await?
+                    .read_to_end(&mut bytes)
+                    .await?;
+                Ok::<_, AssetReaderError>(String::from_utf8(bytes).unwrap())
+            })
+        };
+        assert_eq!(read_text("a.txt").unwrap(), "primary");
+        assert_eq!(read_text("b.txt").unwrap(), "fallback");
+        assert!(matches!(
+            read_text("c.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn fallback_merges_directories() {
+        use futures_lite::StreamExt;
+        use std::path::PathBuf;
+
+        let reader = FallbackAssetReader::new(
+            MemoryAssetReader::from_texts(&[("a.txt", "primary")]),
+            MemoryAssetReader::from_texts(&[("a.txt", "fallback"), ("b.txt", "fallback")]),
+        );
+        let mut entries: Vec<PathBuf> = block_on(async {
+            AssetReader::read_directory(&reader, Path::new(""))
+                .await
+                .unwrap()
+                .collect()
+                .await
+        });
+        entries.sort();
+        assert_eq!(entries, ["a.txt", "b.txt"].map(PathBuf::from));
+        assert!(matches!(
+            block_on(AssetReader::read_directory(&reader, Path::new("missing"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn platform_selects_first_matching_arm() {
+        let platform = platform_asset_source! {
+            wasm | android => "web or android",
+            desktop => "desktop",
+            _ => "other",
+        };
+        #[cfg(not(any(target_os = "ios", target_os = "android", target_arch = "wasm32")))]
+        assert_eq!(platform, "desktop");
+        #[cfg(target_os = "ios")]
+        assert_eq!(platform, "other");
+    }
+}
@@ -149,4 +149,8 @@ impl Reader for TransactionLockedReader<'_> {
     fn seekable(&mut self) -> Result<&mut dyn SeekableReader, ReaderNotSeekableError> {
         self.reader.seekable()
     }
+
+    fn read_to_slice(&mut self) -> Option<&[u8]> {
+        self.reader.read_to_slice()
+    }
 }
@@ -0,0 +1,259 @@
+use crate::io::{AssetReader, AssetReaderError, ErasedAssetReader, PathStream, Reader};
+use alloc::boxed::Box;
+use core::{future::Future, time::Duration};
+use std::path::Path;
+use tracing::debug;
+
+/// How the reads of an asset source are retried when they fail with a transient error, set with
+/// [`AssetSourceBuilder::with_retry_policy`](crate::io::AssetSourceBuilder::with_retry_policy).
+///
+/// Only opening a file or a directory is retried: errors happening while the bytes of an opened
+/// file are read are returned to the loader.
+#[derive(Debug, Clone)]
+pub struct AssetRetryPolicy {
+    /// The number of times a failed read is retried before its error is returned.
+    pub max_retries: u32,
+    /// The delay before the first retry.
+    pub initial_delay: Duration,
+    /// The factor the delay is multiplied by after each retry.
+    pub backoff_factor: f32,
+    /// The longest delay between two retries.
+    pub max_delay: Duration,
+    /// Returns `true` if a read failing with the given error should be retried. Defaults to
+    /// [`AssetReaderError::is_transient`].
+    pub retry_if: fn(&AssetReaderError) -> bool,
+}
+
+impl AssetRetryPolicy {
+    /// Returns the delay before the retry with the given index, starting at 0.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let mut delay = self.initial_delay;
+        for _ in 0..retry {
+            if delay >= self.max_delay {
+                break;
+            }
+            delay =
+                Duration::try_from_secs_f64(delay.as_secs_f64() * f64::from(self.backoff_factor))
+                    .unwrap_or(self.max_delay);
+        }
+        delay.min(self.max_delay)
+    }
+
+    /// Sets [`AssetRetryPolicy::max_retries`].
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets [`AssetRetryPolicy::initial_delay`].
+    pub fn with_initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Sets [`AssetRetryPolicy::backoff_factor`].
+    pub fn with_backoff_factor(mut self, backoff_factor: f32) -> Self {
+        self.backoff_factor = backoff_factor;
+        self
+    }
+
+    /// Sets [`AssetRetryPolicy::max_delay`].
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets [`AssetRetryPolicy::retry_if`].
+    pub fn with_retry_if(mut self, retry_if: fn(&AssetReaderError) -> bool) -> Self {
+        self.retry_if = retry_if;
+        self
+    }
+}
+
+impl Default for AssetRetryPolicy {
+    /// Retries transient errors 3 times, after 100, 200 and 400 milliseconds.
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(100),
+            backoff_factor: 2.0,
+            max_delay: Duration::from_secs(2),
+            retry_if: AssetReaderError::is_transient,
+        }
+    }
+}
+
+/// An [`AssetReader`] retrying the reads of another reader according to an [`AssetRetryPolicy`].
+pub(crate) struct RetryAssetReader {
+    reader: Box<dyn ErasedAssetReader>,
+    policy: AssetRetryPolicy,
+}
+
+impl RetryAssetReader {
+    pub(crate) fn new(reader: Box<dyn ErasedAssetReader>, policy: AssetRetryPolicy) -> Self {
+        Self { reader, policy }
+    }
+
+    async fn retry<T, F: Future<Output = Result<T, AssetReaderError>>>(
+        &self,
+        path: &Path,
+        mut read: impl FnMut() -> F,
+    ) -> Result<T, AssetReaderError> {
+        let mut retry = 0;
+        loop {
+            match read().await {
+                Err(error) if retry < self.policy.max_retries && (self.policy.retry_if)(&error) => {
+                    let delay = self.policy.delay(retry);
+                    debug!("Retrying to read {path:?} in {delay:?}, as it failed with: {error}");
+                    sleep(delay).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl AssetReader for RetryAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.retry(path, || self.reader.read(path)).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.retry(path, || self.reader.read_meta(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.retry(path, || self.reader.read_directory(path)).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.retry(path, || self.reader.is_directory(path)).await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    if !delay.is_zero() {
+        async_io::Timer::after(delay).await;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(delay: Duration) {
+    use wasm_bindgen::JsCast;
+
+    if delay.is_zero() {
+        return;
+    }
+    let millis = i32::try_from(delay.as_millis()).unwrap_or(i32::MAX);
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let global = js_sys::global();
+        let result = match global.dyn_ref::<web_sys::Window>() {
+            Some(window) => {
+                window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis)
+            }
+            None => global
+                .unchecked_ref::<web_sys::WorkerGlobalScope>()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, millis),
+        };
+        // Retry right away if the timeout can't be set.
+        if result.is_err() {
+            let _ = resolve.call0(&wasm_bindgen::JsValue::UNDEFINED);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+    use alloc::sync::Arc;
+    use bevy_tasks::block_on;
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` reads with `error`, then reads from memory.
+    struct FlakyReader {
+        failures: u32,
+        error: fn() -> AssetReaderError,
+        reads: Arc<AtomicU32>,
+        reader: MemoryAssetReader,
+    }
+
+    impl AssetReader for FlakyReader {
+        async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+            if self.reads.fetch_add(1, Ordering::Relaxed) < self.failures {
+                return Err((self.error)());
+            }
+            AssetReader::read(&self.reader, path).await
+        }
+
+        async fn read_meta<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<impl Reader + 'a, AssetReaderError> {
+            AssetReader::read_meta(&self.reader, path).await
+        }
+
+        async fn read_directory<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<Box<PathStream>, AssetReaderError> {
+            AssetReader::read_directory(&self.reader, path).await
+        }
+
+        async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+            AssetReader::is_directory(&self.reader, path).await
+        }
+    }
+
+    fn read_flaky(failures: u32, error: fn() -> AssetReaderError) -> (bool, u32) {
+        let root = Dir::default();
+        root.insert_asset_text(Path::new("a.txt"), "a");
+        let reads = Arc::new(AtomicU32::new(0));
+        let reader = RetryAssetReader::new(
+            Box::new(FlakyReader {
+                failures,
+                error,
+                reads: reads.clone(),
+                reader: MemoryAssetReader { root },
+            }),
+            AssetRetryPolicy::default().with_initial_delay(Duration::from_millis(1)),
+        );
+        let result = block_on(AssetReader::read(&reader, Path::new("a.txt")));
+        (result.is_ok(), reads.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let timed_out =
+            || AssetReaderError::Io(Arc::new(std::io::Error::from(std::io::ErrorKind::TimedOut)));
+        assert_eq!(read_flaky(2, timed_out), (true, 3));
+        assert_eq!(read_flaky(4, timed_out), (false, 4));
+        assert_eq!(
+            read_flaky(1, || AssetReaderError::HttpError(503)),
+            (true, 2)
+        );
+        assert_eq!(
+            read_flaky(1, || AssetReaderError::HttpError(403)),
+            (false, 1)
+        );
+        assert_eq!(
+            read_flaky(1, || AssetReaderError::NotFound("a.txt".into())),
+            (false, 1)
+        );
+    }
+
+    #[test]
+    fn delay_backs_off() {
+        let policy = AssetRetryPolicy::default();
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(400));
+        assert_eq!(policy.delay(10), Duration::from_secs(2));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::io::{
+    file::{FileAssetReader, FileAssetWriter},
+    AssetSourceBuilder,
+};
+use crate::{AssetApp, AssetPlugin};
+use alloc::{boxed::Box, string::String};
+use bevy_app::{App, Plugin};
+use std::{env, path::PathBuf};
+use tracing::warn;
+
+/// Adds the `roaming` asset source to the app, for small settings and save files that should
+/// follow the user across devices.
+///
+/// Assets are read from and written to a folder named after [`RoamingAssetPlugin::app_name`],
+/// inside the per-user folder returned by [`roaming_base_path`]:
+/// - On Windows, this is the roaming application data folder (`%APPDATA%`), which is synced
+///   across the devices of roaming and domain user profiles.
+/// - On macOS, this is `~/Library/Application Support`.
+/// - On other platforms, this is `$XDG_CONFIG_HOME`, or `~/.config` if it isn't set.
+///
+/// Whether the files actually sync across devices is up to the platform: the source is a plain
+/// per-user folder everywhere it isn't synced by the OS.
+///
+/// NOTE: Make sure to add this plugin *before* `AssetPlugin` to properly register the asset source.
+///
+/// ```no_run
+/// # use bevy_app::{App, TaskPoolPlugin};
+/// # use bevy_asset::{io::roaming::RoamingAssetPlugin, AssetPlugin, AssetServer};
+/// # use bevy_tasks::block_on;
+/// # use std::path::Path;
+/// # let mut app = App::new();
+/// app.add_plugins((
+///     # TaskPoolPlugin::default(),
+///     RoamingAssetPlugin {
+///         app_name: "my_game".into(),
+///         ..Default::default()
+///     },
+///     AssetPlugin::default(),
+/// ));
+/// # let asset_server = app.world().resource::<AssetServer>();
+///
+/// // Writes to `%APPDATA%/my_game/settings.ron` on Windows.
+/// let source = asset_server.get_source("roaming").unwrap();
+/// block_on(source.writer().unwrap().write_bytes(Path::new("settings.ron"), b"(volume: 0.5)"))
+///     .unwrap();
+///
+/// // The settings can then be loaded like any other asset, from `roaming://settings.ron`.
+/// ```
+pub struct RoamingAssetPlugin {
+    /// The name of the folder holding the app's files, inside the platform's roaming folder.
+    ///
+    /// Defaults to the name of the executable.
+    pub app_name: String,
+    /// If set, overrides the platform's roaming folder. The app's files are stored in the
+    /// [`app_name`](Self::app_name) folder inside of it.
+    pub base_path_override: Option<PathBuf>,
+}
+
+impl Default for RoamingAssetPlugin {
+    fn default() -> Self {
+        let app_name = env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "bevy".into());
+        Self {
+            app_name,
+            base_path_override: None,
+        }
+    }
+}
+
+impl RoamingAssetPlugin {
+    /// The name of the asset source added by this plugin.
+    pub const SOURCE_NAME: &'static str = "roaming";
+
+    /// Returns the folder the `roaming` asset source reads from and writes to, or [`None`] if the
+    /// platform has no roaming folder.
+    pub fn path(&self) -> Option<PathBuf> {
+        let base_path = self.base_path_override.clone().or_else(roaming_base_path)?;
+        Some(base_path.join(&self.app_name))
+    }
+}
+
+impl Plugin for RoamingAssetPlugin {
+    fn build(&self, app: &mut App) {
+        if app.is_plugin_added::<AssetPlugin>() {
+            warn!("RoamingAssetPlugin must be added before AssetPlugin for it to work!");
+        }
+        let Some(path) = self.path() else {
+            warn!(
+                "RoamingAssetPlugin could not find a per-user folder on this platform. \
+                The `{}` asset source will not be available.",
+                Self::SOURCE_NAME
+            );
+            return;
+        };
+        let processed_path = path.clone();
+        let writer_path = path.clone();
+        app.register_asset_source(
+            Self::SOURCE_NAME,
+            AssetSourceBuilder::new(move || Box::new(FileAssetReader::new(&path)))
+                .with_processed_reader(move || Box::new(FileAssetReader::new(&processed_path)))
+                .with_writer(move |create_root| {
+                    Some(Box::new(FileAssetWriter::new(&writer_path, create_root)))
+                }),
+        );
+    }
+}
+
+/// Returns the per-user folder that is synced across devices by the platform, or the closest
+/// equivalent where there is none. See [`RoamingAssetPlugin`] for the folder used on each platform.
+///
+/// Returns [`None`] if the folder can't be determined, e.g. when the required environment
+/// variables aren't set.
+pub fn roaming_base_path() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    return env::var_os("APPDATA").map(PathBuf::from);
+
+    #[cfg(target_os = "macos")]
+    return env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+    });
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    return env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        // Relative paths are invalid according to the XDG specification.
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{io::Reader, AssetServer};
+    use alloc::{format, vec::Vec};
+    use bevy_app::TaskPoolPlugin;
+    use bevy_tasks::block_on;
+    use std::path::Path;
+
+    #[test]
+    fn write_and_read_roaming_source() {
+        let base_path = env::temp_dir().join(format!("bevy_asset_roaming_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base_path);
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            RoamingAssetPlugin {
+                app_name: "test_app".into(),
+                base_path_override: Some(base_path.clone()),
+            },
+            AssetPlugin::default(),
+        ));
+        let source = app
+            .world()
+            .resource::<AssetServer>()
+            .get_source(RoamingAssetPlugin::SOURCE_NAME)
+            .unwrap();
+
+        let path = Path::new("settings.ron");
+        block_on(source.writer().unwrap().write_bytes(path, b"(volume: 0.5)")).unwrap();
+        assert_eq!(
+            std::fs::read(base_path.join("test_app").join(path)).unwrap(),
+            b"(volume: 0.5)"
+        );
+
+        let mut bytes = Vec::new();
+        block_on(async {
+            let mut reader = source.reader().read(path).await.unwrap();
+            reader.read_to_end(&mut bytes).await.unwrap();
+        });
+        assert_eq!(bytes, b"(volume: 0.5)");
+
+        std::fs::remove_dir_all(&base_path).unwrap();
+    }
+}
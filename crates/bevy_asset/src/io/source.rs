@@ -1,20 +1,26 @@
 use crate::{
-    io::{processor_gated::ProcessorGatedReader, AssetSourceEvent, AssetWatcher},
+    io::{
+        case_insensitive::{CaseInsensitiveAssetReader, CaseInsensitiveIndex},
+        metrics::MetricsAssetReader,
+        processor_gated::ProcessorGatedReader,
+        retry::RetryAssetReader,
+        AssetMounts, AssetRetryPolicy, AssetSourceEvent, AssetSourceMetrics, AssetWatcher,
+        SymlinkPolicy,
+    },
+    meta::{processed_content_path, AssetHash},
     processor::ProcessingState,
+    AssetMetaCheck, AssetPath, UnapprovedPathMode,
 };
-use alloc::{
-    boxed::Box,
-    string::{String, ToString},
-    sync::Arc,
-};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use atomicow::CowArc;
 use bevy_ecs::resource::Resource;
 use bevy_platform::collections::HashMap;
 use core::{fmt::Display, hash::Hash, time::Duration};
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::warn;
 
-use super::{ErasedAssetReader, ErasedAssetWriter};
+use super::{AssetReaderError, ErasedAssetReader, ErasedAssetWriter};
 
 /// A reference to an "asset source", which maps to an [`AssetReader`](crate::io::AssetReader) and/or [`AssetWriter`](crate::io::AssetWriter).
 ///
@@ -146,6 +152,19 @@ pub struct AssetSourceBuilder {
     pub watch_warning: Option<&'static str>,
     /// The warning message to display when watching a processed asset fails.
     pub processed_watch_warning: Option<&'static str>,
+    /// If set, overrides [`AssetPlugin::meta_check`](crate::AssetPlugin::meta_check) for assets loaded from this source.
+    ///
+    /// This is ignored in [`AssetMode::Processed`](crate::AssetMode::Processed), where meta files are always checked.
+    pub meta_check: Option<AssetMetaCheck>,
+    /// If set, overrides [`AssetPlugin::unapproved_path_mode`](crate::AssetPlugin::unapproved_path_mode) for assets loaded
+    /// from this source.
+    pub unapproved_path_mode: Option<UnapprovedPathMode>,
+    /// If true, the reads of this source are counted in [`AssetSourceMetrics`].
+    pub metrics: bool,
+    /// If set, the reads of this source failing with a transient error are retried with this policy.
+    pub retry_policy: Option<AssetRetryPolicy>,
+    /// If true, the paths of this source are resolved case-insensitively.
+    pub case_insensitive_paths: bool,
 }
 
 impl AssetSourceBuilder {
@@ -162,6 +181,11 @@ impl AssetSourceBuilder {
             processed_watcher: None,
             watch_warning: None,
             processed_watch_warning: None,
+            meta_check: None,
+            unapproved_path_mode: None,
+            metrics: false,
+            retry_policy: None,
+            case_insensitive_paths: false,
         }
     }
 
@@ -173,24 +197,50 @@ impl AssetSourceBuilder {
         watch: bool,
         watch_processed: bool,
     ) -> AssetSource {
-        let reader = self.reader.as_mut()();
+        let mut reader = self.reader.as_mut()();
+        let mut processed_reader = self.processed_reader.as_mut().map(|r| r());
+        let mut case_insensitive_indices = [None, None];
+        if self.case_insensitive_paths {
+            let case_insensitive = CaseInsensitiveAssetReader::new(reader);
+            case_insensitive_indices[0] = Some(case_insensitive.index());
+            reader = Box::new(case_insensitive);
+            processed_reader = processed_reader.map(|processed_reader| {
+                let case_insensitive = CaseInsensitiveAssetReader::new(processed_reader);
+                case_insensitive_indices[1] = Some(case_insensitive.index());
+                Box::new(case_insensitive) as Box<_>
+            });
+        }
+        if let Some(policy) = &self.retry_policy {
+            reader = Box::new(RetryAssetReader::new(reader, policy.clone()));
+            processed_reader = processed_reader.map(|processed_reader| {
+                Box::new(RetryAssetReader::new(processed_reader, policy.clone())) as Box<_>
+            });
+        }
+        let metrics = self.metrics.then(Arc::<AssetSourceMetrics>::default);
+        if let Some(metrics) = &metrics {
+            reader = Box::new(MetricsAssetReader::new(reader, metrics.clone()));
+            processed_reader = processed_reader.map(|processed_reader| {
+                Box::new(MetricsAssetReader::new(processed_reader, metrics.clone())) as Box<_>
+            });
+        }
         let writer = self.writer.as_mut().and_then(|w| w(false));
         let processed_writer = self.processed_writer.as_mut().and_then(|w| w(true));
         let mut source = AssetSource {
             id: id.clone(),
             reader,
             writer,
-            processed_reader: self
-                .processed_reader
-                .as_mut()
-                .map(|r| r())
-                .map(Into::<Arc<_>>::into),
+            processed_reader: processed_reader.map(Into::<Arc<_>>::into),
             ungated_processed_reader: None,
             processed_writer,
             event_receiver: None,
             watcher: None,
             processed_event_receiver: None,
             processed_watcher: None,
+            meta_check: self.meta_check.clone(),
+            unapproved_path_mode: self.unapproved_path_mode.clone(),
+            metrics,
+            case_insensitive_indices,
+            processing_state: None,
         };
 
         if watch {
@@ -297,23 +347,115 @@ impl AssetSourceBuilder {
         self
     }
 
+    /// Overrides [`AssetPlugin::meta_check`](crate::AssetPlugin::meta_check) for assets loaded from this source.
+    ///
+    /// This is ignored in [`AssetMode::Processed`](crate::AssetMode::Processed), where meta files are always checked.
+    pub fn with_meta_check(mut self, meta_check: AssetMetaCheck) -> Self {
+        self.meta_check = Some(meta_check);
+        self
+    }
+
+    /// Overrides [`AssetPlugin::unapproved_path_mode`](crate::AssetPlugin::unapproved_path_mode) for assets loaded
+    /// from this source.
+    pub fn with_unapproved_path_mode(mut self, unapproved_path_mode: UnapprovedPathMode) -> Self {
+        self.unapproved_path_mode = Some(unapproved_path_mode);
+        self
+    }
+
+    /// Counts the reads of this source in [`AssetSourceMetrics`], which are also published to
+    /// [`bevy_diagnostic`]. See [`AssetSource::metrics`].
+    pub fn with_metrics(mut self) -> Self {
+        self.metrics = true;
+        self
+    }
+
+    /// Retries the reads of this source failing with a transient error, such as a file locked by
+    /// an antivirus or a network timeout, instead of failing the load right away.
+    ///
+    /// The reads are retried before being counted in the [`AssetSourceMetrics`] of the source, so
+    /// only their final result is counted.
+    pub fn with_retry_policy(mut self, retry_policy: AssetRetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Resolves the paths of this source case-insensitively, so that assets authored on a
+    /// case-insensitive file system, like on Windows, still load when their paths don't match the
+    /// case of the files, e.g. from a pack file or on Linux.
+    ///
+    /// The [`AssetServer`](crate::AssetServer) changes the paths it is given to the case of their
+    /// files, so that paths differing only by case identify the same asset. This looks the paths
+    /// up in an index of the directories of the source, listing the directories the first time
+    /// they are needed, and a warning is logged for each path that had to be changed so that it
+    /// can be fixed. The index is updated from the changes reported by the
+    /// [`AssetWatcher`] of the source, if it is watched.
+    ///
+    /// Reads through the readers of the source are first done with the path as it is, and with
+    /// the resolved path when the file isn't found. Assets found this way are read into memory
+    /// before being loaded. Paths aren't resolved by the [`AssetServer`](crate::AssetServer) on
+    /// the web.
+    pub fn with_case_insensitive_paths(mut self) -> Self {
+        self.case_insensitive_paths = true;
+        self
+    }
+
     /// Returns a builder containing the "platform default source" for the given `path` and `processed_path`.
     /// For most platforms, this will use [`FileAssetReader`](crate::io::file::FileAssetReader) / [`FileAssetWriter`](crate::io::file::FileAssetWriter),
     /// but some platforms (such as Android) have their own default readers / writers / watchers.
     pub fn platform_default(path: &str, processed_path: Option<&str>) -> Self {
-        let default = Self::new(AssetSource::get_default_reader(path.to_string()))
-            .with_writer(AssetSource::get_default_writer(path.to_string()))
-            .with_watcher(AssetSource::get_default_watcher(
-                path.to_string(),
+        Self::platform_default_with(None, path, processed_path, SymlinkPolicy::Follow)
+    }
+
+    /// Returns a builder containing the "platform default source", like
+    /// [`platform_default`](Self::platform_default), but with `path` and `processed_path` relative
+    /// to `base_path` rather than to the base path detected from the environment.
+    ///
+    /// A relative `base_path` is relative to the current working directory. `base_path` is ignored
+    /// on the web and on Android.
+    pub fn platform_default_in(base_path: &Path, path: &str, processed_path: Option<&str>) -> Self {
+        Self::platform_default_with(Some(base_path), path, processed_path, SymlinkPolicy::Follow)
+    }
+
+    /// Returns a builder containing the "platform default source", with `path` and
+    /// `processed_path` relative to `base_path` if it is set, and with file readers and writers
+    /// using `symlink_policy`.
+    fn platform_default_with(
+        base_path: Option<&Path>,
+        path: &str,
+        processed_path: Option<&str>,
+        symlink_policy: SymlinkPolicy,
+    ) -> Self {
+        // The default readers and writers join relative paths to the detected base path.
+        let base_path = base_path.map(|base_path| {
+            std::path::absolute(base_path).unwrap_or_else(|_| base_path.to_path_buf())
+        });
+        let resolve = |path: &str| match &base_path {
+            Some(base_path) if !cfg!(any(target_arch = "wasm32", target_os = "android")) => {
+                base_path.join(path)
+            }
+            _ => PathBuf::from(path),
+        };
+        let path = resolve(path);
+        let processed_path = processed_path.map(resolve);
+        let default = Self::new(AssetSource::default_reader(path.clone(), symlink_policy))
+            .with_writer(AssetSource::default_writer(path.clone(), symlink_policy))
+            .with_watcher(AssetSource::default_watcher(
+                path,
                 Duration::from_millis(300),
             ))
             .with_watch_warning(AssetSource::get_default_watch_warning());
         if let Some(processed_path) = processed_path {
             default
-                .with_processed_reader(AssetSource::get_default_reader(processed_path.to_string()))
-                .with_processed_writer(AssetSource::get_default_writer(processed_path.to_string()))
-                .with_processed_watcher(AssetSource::get_default_watcher(
-                    processed_path.to_string(),
+                .with_processed_reader(AssetSource::default_reader(
+                    processed_path.clone(),
+                    symlink_policy,
+                ))
+                .with_processed_writer(AssetSource::default_writer(
+                    processed_path.clone(),
+                    symlink_policy,
+                ))
+                .with_processed_watcher(AssetSource::default_watcher(
+                    processed_path,
                     Duration::from_millis(300),
                 ))
                 .with_processed_watch_warning(AssetSource::get_default_watch_warning())
@@ -329,6 +471,8 @@ impl AssetSourceBuilder {
 pub struct AssetSourceBuilders {
     sources: HashMap<CowArc<'static, str>, AssetSourceBuilder>,
     default: Option<AssetSourceBuilder>,
+    mounts: AssetMounts,
+    path_aliases: HashMap<String, AssetPath<'static>>,
 }
 
 impl AssetSourceBuilders {
@@ -355,8 +499,36 @@ impl AssetSourceBuilders {
         }
     }
 
+    /// Mutably iterates all builders in the collection (including the default builder).
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut AssetSourceBuilder> {
+        self.sources.values_mut().chain(self.default.as_mut())
+    }
+
+    /// Returns the mount table shared by the built [`AssetSources`], used to overlay additional
+    /// roots on top of the sources at runtime.
+    pub fn mounts(&self) -> &AssetMounts {
+        &self.mounts
+    }
+
+    /// Adds a path alias, which makes the paths of the `alias` source resolve to paths inside
+    /// `target`. For example, with an alias from `textures` to `art/textures`, the path
+    /// `textures://rock.png` resolves to `art/textures/rock.png` in the default source.
+    ///
+    /// Aliases are resolved before the asset source is looked up, so they take priority over the
+    /// sources with the same name. They are not resolved recursively: the source of `target` must
+    /// not be an alias.
+    pub fn insert_path_alias(
+        &mut self,
+        alias: impl Into<String>,
+        target: impl Into<AssetPath<'static>>,
+    ) {
+        self.path_aliases.insert(alias.into(), target.into());
+    }
+
     /// Builds a new [`AssetSources`] collection. If `watch` is true, the unprocessed sources will watch for changes.
     /// If `watch_processed` is true, the processed sources will watch for changes.
+    ///
+    /// The readers of the built sources are overlaid by the [`mounts`](Self::mounts) of each source.
     pub fn build_sources(&mut self, watch: bool, watch_processed: bool) -> AssetSources {
         let mut sources = <HashMap<_, _>>::default();
         for (id, source) in &mut self.sources {
@@ -365,16 +537,18 @@ impl AssetSourceBuilders {
                 watch,
                 watch_processed,
             );
-            sources.insert(id.clone_owned(), source);
+            sources.insert(id.clone_owned(), self.mounts.overlay_source(source));
         }
 
+        let default = self
+            .default
+            .as_mut()
+            .map(|p| p.build(AssetSourceId::Default, watch, watch_processed))
+            .expect(MISSING_DEFAULT_SOURCE);
         AssetSources {
             sources,
-            default: self
-                .default
-                .as_mut()
-                .map(|p| p.build(AssetSourceId::Default, watch, watch_processed))
-                .expect(MISSING_DEFAULT_SOURCE),
+            default: self.mounts.overlay_source(default),
+            path_aliases: self.path_aliases.clone(),
         }
     }
 
@@ -383,6 +557,39 @@ impl AssetSourceBuilders {
         self.default
             .get_or_insert_with(|| AssetSourceBuilder::platform_default(path, processed_path));
     }
+
+    /// Initializes the default [`AssetSourceBuilder`] if it has not already been set, with `path`
+    /// and `processed_path` relative to `base_path`. See [`AssetSourceBuilder::platform_default_in`].
+    pub fn init_default_source_in(
+        &mut self,
+        base_path: &Path,
+        path: &str,
+        processed_path: Option<&str>,
+    ) {
+        self.default.get_or_insert_with(|| {
+            AssetSourceBuilder::platform_default_in(base_path, path, processed_path)
+        });
+    }
+
+    /// Initializes the default [`AssetSourceBuilder`] if it has not already been set, with `path`
+    /// and `processed_path` relative to `base_path` if it is set, and with file readers and
+    /// writers using `symlink_policy`.
+    pub(crate) fn init_default_source_with(
+        &mut self,
+        base_path: Option<&Path>,
+        path: &str,
+        processed_path: Option<&str>,
+        symlink_policy: SymlinkPolicy,
+    ) {
+        self.default.get_or_insert_with(|| {
+            AssetSourceBuilder::platform_default_with(
+                base_path,
+                path,
+                processed_path,
+                symlink_policy,
+            )
+        });
+    }
 }
 
 /// A collection of unprocessed and processed [`AssetReader`](crate::io::AssetReader), [`AssetWriter`](crate::io::AssetWriter), and [`AssetWatcher`] instances
@@ -402,6 +609,26 @@ pub struct AssetSource {
     processed_watcher: Option<Box<dyn AssetWatcher>>,
     event_receiver: Option<async_channel::Receiver<AssetSourceEvent>>,
     processed_event_receiver: Option<async_channel::Receiver<AssetSourceEvent>>,
+    meta_check: Option<AssetMetaCheck>,
+    unapproved_path_mode: Option<UnapprovedPathMode>,
+    metrics: Option<Arc<AssetSourceMetrics>>,
+    /// The indices of the unprocessed and processed readers, if their paths are resolved
+    /// case-insensitively.
+    case_insensitive_indices: [Option<Arc<CaseInsensitiveIndex>>; 2],
+    /// The state of the [`AssetProcessor`](crate::AssetProcessor) the processed reader is gated on.
+    processing_state: Option<Arc<ProcessingState>>,
+}
+
+impl AssetMounts {
+    /// Overlays the readers of `source` with its mounts.
+    fn overlay_source(&self, mut source: AssetSource) -> AssetSource {
+        let id = source.id();
+        source.reader = Box::new(self.overlay(id.clone(), source.reader.into()));
+        source.processed_reader = source
+            .processed_reader
+            .map(|reader| Arc::new(self.overlay(id, reader)) as Arc<dyn ErasedAssetReader>);
+        source
+    }
 }
 
 impl AssetSource {
@@ -442,6 +669,29 @@ impl AssetSource {
         self.ungated_processed_reader.as_deref()
     }
 
+    /// Reads the content-addressed processed asset bytes with the given `hash`, stored at
+    /// [`processed_content_path`].
+    ///
+    /// Content-addressed bytes never change once written, so this doesn't wait on the
+    /// [`AssetProcessor`](crate::AssetProcessor).
+    pub(crate) async fn read_processed_content(
+        &self,
+        hash: &AssetHash,
+    ) -> Result<Vec<u8>, AssetReaderError> {
+        let path = processed_content_path(hash);
+        let Some(reader) = self
+            .ungated_processed_reader
+            .as_deref()
+            .or(self.processed_reader.as_deref())
+        else {
+            return Err(AssetReaderError::NotFound(path));
+        };
+        let mut reader = reader.read(&path).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(bytes)
+    }
+
     /// Return's this source's processed [`AssetWriter`](crate::io::AssetWriter), if it exists.
     #[inline]
     pub fn processed_writer(
@@ -464,6 +714,43 @@ impl AssetSource {
         self.processed_event_receiver.as_ref()
     }
 
+    /// Returns this source's [`AssetMetaCheck`] override, if it has one.
+    #[inline]
+    pub fn meta_check(&self) -> Option<&AssetMetaCheck> {
+        self.meta_check.as_ref()
+    }
+
+    /// Returns this source's [`UnapprovedPathMode`] override, if it has one.
+    #[inline]
+    pub fn unapproved_path_mode(&self) -> Option<&UnapprovedPathMode> {
+        self.unapproved_path_mode.as_ref()
+    }
+
+    /// Returns the metrics of the reads of this source, if they were enabled with
+    /// [`AssetSourceBuilder::with_metrics`].
+    #[inline]
+    pub fn metrics(&self) -> Option<&AssetSourceMetrics> {
+        self.metrics.as_deref()
+    }
+
+    /// Returns the path of the file matching `path` case-insensitively, if the paths of this
+    /// source are resolved case-insensitively and such a file exists.
+    ///
+    /// This blocks on listing the directories of `path` that weren't listed yet.
+    pub(crate) fn resolve_path_case(&self, path: &Path, processed: bool) -> Option<PathBuf> {
+        self.case_insensitive_indices[processed as usize]
+            .as_ref()?
+            .resolve_blocking(path)
+    }
+
+    /// Updates the case-insensitive index of the unprocessed or processed reader from a watcher
+    /// `event`.
+    pub(crate) fn handle_case_insensitive_event(&self, event: &AssetSourceEvent, processed: bool) {
+        if let Some(index) = &self.case_insensitive_indices[processed as usize] {
+            index.invalidate(event);
+        }
+    }
+
     /// Returns true if the assets in this source should be processed.
     #[inline]
     pub fn should_process(&self) -> bool {
@@ -473,11 +760,20 @@ impl AssetSource {
     /// Returns a builder function for this platform's default [`AssetReader`](crate::io::AssetReader). `path` is the relative path to
     /// the asset root.
     pub fn get_default_reader(
-        _path: String,
+        path: String,
+    ) -> impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync {
+        Self::default_reader(PathBuf::from(path), SymlinkPolicy::Follow)
+    }
+
+    fn default_reader(
+        _path: PathBuf,
+        _symlink_policy: SymlinkPolicy,
     ) -> impl FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync {
         move || {
             #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-            return Box::new(super::file::FileAssetReader::new(&_path));
+            return Box::new(
+                super::file::FileAssetReader::new(&_path).with_symlink_policy(_symlink_policy),
+            );
             #[cfg(target_arch = "wasm32")]
             return Box::new(super::wasm::HttpWasmAssetReader::new(&_path));
             #[cfg(target_os = "android")]
@@ -488,14 +784,21 @@ impl AssetSource {
     /// Returns a builder function for this platform's default [`AssetWriter`](crate::io::AssetWriter). `path` is the relative path to
     /// the asset root. This will return [`None`] if this platform does not support writing assets by default.
     pub fn get_default_writer(
-        _path: String,
+        path: String,
+    ) -> impl FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync {
+        Self::default_writer(PathBuf::from(path), SymlinkPolicy::Follow)
+    }
+
+    fn default_writer(
+        _path: PathBuf,
+        _symlink_policy: SymlinkPolicy,
     ) -> impl FnMut(bool) -> Option<Box<dyn ErasedAssetWriter>> + Send + Sync {
         move |_create_root: bool| {
             #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
-            return Some(Box::new(super::file::FileAssetWriter::new(
-                &_path,
-                _create_root,
-            )));
+            return Some(Box::new(
+                super::file::FileAssetWriter::new(&_path, _create_root)
+                    .with_symlink_policy(_symlink_policy),
+            ));
             #[cfg(any(target_arch = "wasm32", target_os = "android"))]
             return None;
         }
@@ -526,6 +829,14 @@ impl AssetSource {
     /// `file_debounce_time` is the amount of time to wait (and debounce duplicate events) before returning an event.
     /// Higher durations reduce duplicates but increase the amount of time before a change event is processed. If the
     /// duration is set too low, some systems might surface events _before_ their filesystem has the changes.
+    pub fn get_default_watcher(
+        path: String,
+        file_debounce_wait_time: Duration,
+    ) -> impl FnMut(async_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync
+    {
+        Self::default_watcher(PathBuf::from(path), file_debounce_wait_time)
+    }
+
     #[cfg_attr(
         any(
             not(feature = "file_watcher"),
@@ -537,8 +848,8 @@ impl AssetSource {
             reason = "The `path` and `file_debounce_wait_time` arguments are unused when on WASM, Android, or if the `file_watcher` feature is disabled."
         )
     )]
-    pub fn get_default_watcher(
-        path: String,
+    fn default_watcher(
+        path: PathBuf,
         file_debounce_wait_time: Duration,
     ) -> impl FnMut(async_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>> + Send + Sync
     {
@@ -549,7 +860,7 @@ impl AssetSource {
                 not(target_os = "android")
             ))]
             {
-                let path = super::file::get_base_path().join(path.clone());
+                let path = super::file::get_base_path().join(&path);
                 if path.exists() {
                     Some(Box::new(
                         super::file::FileWatcher::new(
@@ -583,8 +894,20 @@ impl AssetSource {
             self.processed_reader = Some(Arc::new(ProcessorGatedReader::new(
                 self.id(),
                 reader,
-                processing_state,
+                processing_state.clone(),
             )));
+            self.processing_state = Some(processing_state);
+        }
+    }
+
+    /// Queues the asset at `path` to be processed again by the
+    /// [`AssetProcessor`](crate::AssetProcessor), as its processed bytes are corrupt. This does
+    /// nothing if the processed assets of this source aren't processed by a running processor.
+    pub(crate) fn reprocess_corrupt_asset(&self, path: &Path) {
+        if let Some(processing_state) = &self.processing_state {
+            processing_state.reprocess_corrupt_asset(
+                AssetPath::from_path_buf(path.to_path_buf()).with_source(self.id()),
+            );
         }
     }
 }
@@ -593,9 +916,27 @@ impl AssetSource {
 pub struct AssetSources {
     sources: HashMap<CowArc<'static, str>, AssetSource>,
     default: AssetSource,
+    path_aliases: HashMap<String, AssetPath<'static>>,
 }
 
 impl AssetSources {
+    /// Resolves `path` to the target of its [path alias](AssetSourceBuilders::insert_path_alias),
+    /// if its source is an alias. Otherwise, `path` is returned as is.
+    pub fn resolve_path_alias<'a>(&self, path: AssetPath<'a>) -> AssetPath<'a> {
+        let AssetSourceId::Name(alias) = path.source() else {
+            return path;
+        };
+        let Some(target) = self.path_aliases.get(&**alias) else {
+            return path;
+        };
+        let resolved = AssetPath::from_path_buf(target.path().join(path.path()))
+            .with_source(target.source().clone_owned());
+        match path.label_cow() {
+            Some(label) => resolved.with_label(label),
+            None => resolved,
+        }
+    }
+
     /// Gets the [`AssetSource`] with the given `id`, if it exists.
     pub fn get<'a, 'b>(
         &'a self,
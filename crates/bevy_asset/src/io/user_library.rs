@@ -0,0 +1,271 @@
+use crate::io::{
+    file::{FileAssetReader, FileAssetWriter},
+    AssetSourceBuilder,
+};
+use crate::{AssetApp, AssetPlugin};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use bevy_app::{App, Plugin};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+/// A folder of the user's media libraries, which [`UserLibraryAssetPlugin`] adds as an asset
+/// source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UserLibrary {
+    /// The user's pictures, e.g. for screenshots. Added as the `pictures` asset source.
+    Pictures,
+    /// The user's videos, e.g. for gameplay recordings. Added as the `videos` asset source.
+    Videos,
+}
+
+impl UserLibrary {
+    /// The name of the asset source of this library.
+    pub const fn source_name(self) -> &'static str {
+        match self {
+            Self::Pictures => "pictures",
+            Self::Videos => "videos",
+        }
+    }
+
+    /// Returns the folder of this library for the current user:
+    /// - On Windows, this is the `Pictures` or `Videos` folder of the user's profile. Libraries
+    ///   moved to another location by the user are not followed.
+    /// - On macOS, this is `~/Pictures` or `~/Movies`.
+    /// - On other platforms, this is the `XDG_PICTURES_DIR` or `XDG_VIDEOS_DIR` set in
+    ///   `user-dirs.dirs`, or `~/Pictures` and `~/Videos` if they aren't set.
+    ///
+    /// Returns [`None`] if the home folder of the user can't be determined, e.g. when the required
+    /// environment variables aren't set.
+    pub fn path(self) -> Option<PathBuf> {
+        #[cfg(target_os = "windows")]
+        let home = env::var_os("USERPROFILE").map(PathBuf::from)?;
+
+        #[cfg(not(target_os = "windows"))]
+        let home = env::var_os("HOME").map(PathBuf::from)?;
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        if let Some(config_home) = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            // Relative paths are invalid according to the XDG specification.
+            .filter(|path| path.is_absolute())
+        {
+            return Some(self.resolve(&home, &config_home));
+        }
+
+        Some(self.path_in(&home))
+    }
+
+    /// Returns the folder of this library in the home folder `home`, see [`UserLibrary::path`].
+    pub fn path_in(self, home: &Path) -> PathBuf {
+        self.resolve(home, &home.join(".config"))
+    }
+
+    #[cfg_attr(
+        any(target_os = "windows", target_os = "macos"),
+        expect(
+            unused_variables,
+            reason = "There is no `user-dirs.dirs` on this platform."
+        )
+    )]
+    fn resolve(self, home: &Path, config_home: &Path) -> PathBuf {
+        #[cfg(target_os = "windows")]
+        return home.join(match self {
+            Self::Pictures => "Pictures",
+            Self::Videos => "Videos",
+        });
+
+        #[cfg(target_os = "macos")]
+        return home.join(match self {
+            Self::Pictures => "Pictures",
+            Self::Videos => "Movies",
+        });
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        {
+            let (key, default) = match self {
+                Self::Pictures => ("XDG_PICTURES_DIR", "Pictures"),
+                Self::Videos => ("XDG_VIDEOS_DIR", "Videos"),
+            };
+            xdg_user_dir(home, config_home, key).unwrap_or_else(|| home.join(default))
+        }
+    }
+}
+
+/// Reads the folder set for `key` in the `user-dirs.dirs` file of the user, see
+/// <https://www.freedesktop.org/wiki/Software/xdg-user-dirs/>.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn xdg_user_dir(home: &Path, config_home: &Path, key: &str) -> Option<PathBuf> {
+    let dirs = std::fs::read_to_string(config_home.join("user-dirs.dirs")).ok()?;
+    dirs.lines().find_map(|line| {
+        let value = line
+            .trim()
+            .strip_prefix(key)?
+            .trim_start()
+            .strip_prefix('=')?;
+        let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+        // Values are either absolute or relative to the home folder.
+        match value.strip_prefix("$HOME") {
+            Some(relative) => Some(home.join(relative.trim_start_matches('/'))),
+            None => Some(PathBuf::from(value)).filter(|path| path.is_absolute()),
+        }
+    })
+}
+
+/// Adds an asset source for each of the user's media [`libraries`](Self::libraries), to export
+/// screenshots and recordings where the user expects to find them.
+///
+/// Assets are read from and written to a folder named after [`UserLibraryAssetPlugin::app_name`]
+/// inside each library, see [`UserLibrary::path`] for the folder of each library.
+///
+/// Only the libraries the app declares in [`libraries`](Self::libraries) are added, and only if
+/// their folder exists: a library that can't be accessed doesn't get an asset source, and a
+/// warning is logged. The app's folder is created inside the library on the first write.
+///
+/// NOTE: Make sure to add this plugin *before* `AssetPlugin` to properly register the asset
+/// sources.
+///
+/// ```no_run
+/// # use bevy_app::{App, TaskPoolPlugin};
+/// # use bevy_asset::{io::user_library::UserLibraryAssetPlugin, AssetPlugin, AssetServer};
+/// # use bevy_tasks::block_on;
+/// # use std::path::Path;
+/// # let mut app = App::new();
+/// # let png_bytes = Vec::new();
+/// app.add_plugins((
+///     # TaskPoolPlugin::default(),
+///     UserLibraryAssetPlugin {
+///         app_name: "my_game".into(),
+///         ..Default::default()
+///     },
+///     AssetPlugin::default(),
+/// ));
+/// # let asset_server = app.world().resource::<AssetServer>();
+///
+/// // Writes to `~/Pictures/my_game/screenshot.png` on macOS.
+/// if let Ok(source) = asset_server.get_source("pictures") {
+///     block_on(source.writer().unwrap().write_bytes(Path::new("screenshot.png"), &png_bytes))
+///         .unwrap();
+/// }
+/// ```
+pub struct UserLibraryAssetPlugin {
+    /// The libraries to add asset sources for.
+    ///
+    /// Defaults to [`UserLibrary::Pictures`] and [`UserLibrary::Videos`].
+    pub libraries: Vec<UserLibrary>,
+    /// The name of the folder holding the app's files, inside each library.
+    ///
+    /// Defaults to the name of the executable.
+    pub app_name: String,
+    /// If set, overrides the home folder of the user, in which the libraries are looked up.
+    pub home_path_override: Option<PathBuf>,
+}
+
+impl Default for UserLibraryAssetPlugin {
+    fn default() -> Self {
+        let app_name = env::current_exe()
+            .ok()
+            .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "bevy".into());
+        Self {
+            libraries: vec![UserLibrary::Pictures, UserLibrary::Videos],
+            app_name,
+            home_path_override: None,
+        }
+    }
+}
+
+impl UserLibraryAssetPlugin {
+    /// Returns the folder the asset source of `library` reads from and writes to, or [`None`] if
+    /// the user's home folder can't be determined.
+    pub fn path(&self, library: UserLibrary) -> Option<PathBuf> {
+        let library_path = match &self.home_path_override {
+            Some(home) => library.path_in(home),
+            None => library.path()?,
+        };
+        Some(library_path.join(&self.app_name))
+    }
+}
+
+impl Plugin for UserLibraryAssetPlugin {
+    fn build(&self, app: &mut App) {
+        if app.is_plugin_added::<AssetPlugin>() {
+            warn!("UserLibraryAssetPlugin must be added before AssetPlugin for it to work!");
+        }
+        for &library in &self.libraries {
+            let source_name = library.source_name();
+            let Some(path) = self.path(library) else {
+                warn!(
+                    "UserLibraryAssetPlugin could not find the home folder of the user. \
+                    The `{source_name}` asset source will not be available."
+                );
+                continue;
+            };
+            if !path.parent().is_some_and(Path::is_dir) {
+                warn!(
+                    "The {library:?} library of the user is not accessible, as {:?} is not a folder. \
+                    The `{source_name}` asset source will not be available.",
+                    path.parent().unwrap_or(&path)
+                );
+                continue;
+            }
+            let processed_path = path.clone();
+            let writer_path = path.clone();
+            app.register_asset_source(
+                source_name,
+                AssetSourceBuilder::new(move || Box::new(FileAssetReader::new(&path)))
+                    .with_processed_reader(move || Box::new(FileAssetReader::new(&processed_path)))
+                    .with_writer(move |create_root| {
+                        Some(Box::new(FileAssetWriter::new(&writer_path, create_root)))
+                    }),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetServer;
+    use alloc::format;
+    use bevy_app::TaskPoolPlugin;
+    use bevy_tasks::block_on;
+
+    #[test]
+    fn adds_sources_for_accessible_libraries() {
+        let home = env::temp_dir().join(format!("bevy_asset_user_library_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&home);
+        let pictures = UserLibrary::Pictures.path_in(&home);
+        std::fs::create_dir_all(&pictures).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            UserLibraryAssetPlugin {
+                app_name: "test_app".into(),
+                home_path_override: Some(home.clone()),
+                ..Default::default()
+            },
+            AssetPlugin::default(),
+        ));
+        let asset_server = app.world().resource::<AssetServer>();
+        // The videos library doesn't exist.
+        assert!(asset_server
+            .get_source(UserLibrary::Videos.source_name())
+            .is_err());
+
+        let source = asset_server
+            .get_source(UserLibrary::Pictures.source_name())
+            .unwrap();
+        let path = Path::new("screenshot.png");
+        block_on(source.writer().unwrap().write_bytes(path, b"png")).unwrap();
+        assert_eq!(
+            std::fs::read(pictures.join("test_app").join(path)).unwrap(),
+            b"png"
+        );
+
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
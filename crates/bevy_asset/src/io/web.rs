@@ -6,6 +6,12 @@ use bevy_tasks::ConditionalSendFuture;
 use std::path::{Path, PathBuf};
 use tracing::warn;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod download;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use download::*;
+
 /// Adds the `http` and `https` asset sources to the app.
 ///
 /// NOTE: Make sure to add this plugin *before* `AssetPlugin` to properly register http asset sources.
@@ -54,6 +60,9 @@ use tracing::warn;
 /// [target.'cfg(not(target_family = "wasm"))'.dev-dependencies]
 /// ureq = { version = "3", default-features = false, features = ["gzip", "brotli"] }
 /// ```
+///
+/// On native platforms, this also adds the [`AssetPackDownloader`], downloading large packs of
+/// assets to disk before mounting them.
 #[derive(Default)]
 pub struct WebAssetPlugin {
     pub silence_startup_warning: bool,
@@ -82,6 +91,11 @@ impl Plugin for WebAssetPlugin {
             AssetSourceBuilder::new(move || Box::new(WebAssetReader::Https))
                 .with_processed_reader(move || Box::new(WebAssetReader::Https)),
         );
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_resource::<AssetPackDownloader>()
+            .add_message::<AssetPackDownloadEvent>()
+            .add_systems(bevy_app::PreUpdate, handle_asset_pack_downloads);
     }
 }
 
@@ -109,6 +123,22 @@ impl WebAssetReader {
     }
 }
 
+/// The agent sending the requests of the web asset sources and of the [`AssetPackDownloader`].
+#[cfg(not(target_arch = "wasm32"))]
+static AGENT: bevy_platform::sync::LazyLock<ureq::Agent> =
+    bevy_platform::sync::LazyLock::new(|| {
+        use ureq::tls::{RootCerts, TlsConfig};
+
+        ureq::Agent::config_builder()
+            .tls_config(
+                TlsConfig::builder()
+                    .root_certs(RootCerts::PlatformVerifier)
+                    .build(),
+            )
+            .build()
+            .new_agent()
+    });
+
 #[cfg(target_arch = "wasm32")]
 async fn get<'a>(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
     use crate::io::wasm::HttpWasmAssetReader;
@@ -123,7 +153,6 @@ async fn get<'a>(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
 async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
     use crate::io::VecReader;
     use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
-    use bevy_platform::sync::LazyLock;
     use blocking::unblock;
     use std::io::{self, BufReader, Read};
 
@@ -140,20 +169,6 @@ async fn get(path: PathBuf) -> Result<Box<dyn Reader>, AssetReaderError> {
     if let Some(data) = web_asset_cache::try_load_from_cache(str_path).await? {
         return Ok(Box::new(VecReader::new(data)));
     }
-    use ureq::tls::{RootCerts, TlsConfig};
-    use ureq::Agent;
-
-    static AGENT: LazyLock<Agent> = LazyLock::new(|| {
-        Agent::config_builder()
-            .tls_config(
-                TlsConfig::builder()
-                    .root_certs(RootCerts::PlatformVerifier)
-                    .build(),
-            )
-            .build()
-            .new_agent()
-    });
-
     let uri = str_path.to_owned();
     // Use [`unblock`] to run the http request on a separately spawned thread as to not block bevy's
     // async executor.
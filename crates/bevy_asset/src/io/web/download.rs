@@ -0,0 +1,504 @@
+use super::AGENT;
+use crate::{
+    io::{AssetMounts, AssetReader, AssetSourceId, ErasedAssetReader, MountId},
+    meta::AssetHash,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+};
+use async_channel::{Receiver, Sender};
+use bevy_ecs::{
+    message::{Message, MessageWriter},
+    resource::Resource,
+    system::Res,
+};
+use bevy_tasks::IoTaskPool;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The number of bytes downloaded between two [`AssetPackDownloadEvent::Progress`] events.
+const PROGRESS_INTERVAL: u64 = 1024 * 1024;
+
+/// Opens the reader of a downloaded pack, to mount it.
+type OpenPack = Box<dyn FnOnce(&Path) -> Arc<dyn ErasedAssetReader> + Send>;
+
+/// A pack of assets to download with the [`AssetPackDownloader`].
+///
+/// The pack is downloaded next to its destination, in a file with the `.part` extension appended.
+/// If the download is interrupted, e.g. because the app was closed or suspended, downloading the
+/// pack again resumes it from that file, when the server supports range requests.
+pub struct AssetPackDownload {
+    url: String,
+    destination: PathBuf,
+    hash: Option<AssetHash>,
+    mount: Option<(AssetSourceId<'static>, OpenPack)>,
+}
+
+impl AssetPackDownload {
+    /// Creates a download of the pack at `url` to the file `destination`.
+    pub fn new(url: impl Into<String>, destination: impl Into<PathBuf>) -> Self {
+        Self {
+            url: url.into(),
+            destination: destination.into(),
+            hash: None,
+            mount: None,
+        }
+    }
+
+    /// Verifies the downloaded pack against its [`blake3`] `hash`. A pack that doesn't match it is
+    /// deleted and fails to download with [`AssetPackDownloadError::HashMismatch`].
+    ///
+    /// A pack already at the destination is only kept if it matches the hash. Otherwise, it is
+    /// downloaded again.
+    pub fn with_hash(mut self, hash: AssetHash) -> Self {
+        self.hash = Some(hash);
+        self
+    }
+
+    /// Mounts the downloaded pack on top of the asset source `source` with [`AssetMounts::mount`],
+    /// using the reader returned by `open` for the path of the pack.
+    ///
+    /// `open` is called on a background thread, so it can read the index of an archive.
+    pub fn mount<R: AssetReader>(
+        mut self,
+        source: impl Into<AssetSourceId<'static>>,
+        open: impl FnOnce(&Path) -> R + Send + 'static,
+    ) -> Self {
+        self.mount = Some((
+            source.into(),
+            Box::new(move |path| Arc::new(open(path)) as Arc<dyn ErasedAssetReader>),
+        ));
+        self
+    }
+}
+
+/// Identifies a download started with [`AssetPackDownloader::download`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AssetPackDownloadId(u64);
+
+/// Downloads packs of assets over HTTP on background threads, and mounts them once they are
+/// downloaded and verified.
+///
+/// The progress and the result of the downloads are sent as [`AssetPackDownloadEvent`]s.
+///
+/// ```no_run
+/// # use bevy_asset::io::{memory::MemoryAssetReader, web::{AssetPackDownload, AssetPackDownloader}};
+/// # use bevy_ecs::system::Res;
+/// # use std::path::Path;
+/// # fn open_archive(path: &Path) -> MemoryAssetReader { unimplemented!() }
+/// fn download_expansion(downloader: Res<AssetPackDownloader>) {
+///     downloader.download(
+///         AssetPackDownload::new("https://example.com/expansion.pack", "downloads/expansion.pack")
+///             // Loads the assets of `expansion://` from the downloaded archive.
+///             .mount("expansion", |path| open_archive(path)),
+///     );
+/// }
+/// ```
+///
+/// This resource is added by the [`WebAssetPlugin`](super::WebAssetPlugin).
+#[derive(Resource, Clone)]
+pub struct AssetPackDownloader {
+    next_id: Arc<AtomicU64>,
+    update_sender: Sender<(AssetPackDownloadId, DownloadUpdate)>,
+    update_receiver: Receiver<(AssetPackDownloadId, DownloadUpdate)>,
+}
+
+enum DownloadUpdate {
+    Progress {
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    Completed {
+        path: PathBuf,
+        mount: Option<(AssetSourceId<'static>, Arc<dyn ErasedAssetReader>)>,
+    },
+    Failed(AssetPackDownloadError),
+}
+
+impl Default for AssetPackDownloader {
+    fn default() -> Self {
+        let (update_sender, update_receiver) = async_channel::unbounded();
+        Self {
+            next_id: Default::default(),
+            update_sender,
+            update_receiver,
+        }
+    }
+}
+
+impl AssetPackDownloader {
+    /// Starts downloading `download` in the background.
+    pub fn download(&self, download: AssetPackDownload) -> AssetPackDownloadId {
+        let id = AssetPackDownloadId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let sender = self.update_sender.clone();
+        IoTaskPool::get()
+            .spawn(blocking::unblock(move || {
+                let AssetPackDownload {
+                    url,
+                    destination,
+                    hash,
+                    mount,
+                } = download;
+                let mut progress = |downloaded, total| {
+                    let _ = sender.try_send((id, DownloadUpdate::Progress { downloaded, total }));
+                };
+                let update = match download_pack(&url, &destination, hash, &mut progress) {
+                    Ok(()) => DownloadUpdate::Completed {
+                        mount: mount.map(|(source, open)| (source, open(&destination))),
+                        path: destination,
+                    },
+                    Err(error) => DownloadUpdate::Failed(error),
+                };
+                let _ = sender.try_send((id, update));
+            }))
+            .detach();
+        id
+    }
+}
+
+/// A [`Message`] sent when an [`AssetPackDownloader`] download progresses, completes or fails.
+#[derive(Message, Clone, Debug)]
+pub enum AssetPackDownloadEvent {
+    /// Some bytes of the pack were downloaded.
+    Progress {
+        /// The id of the download.
+        id: AssetPackDownloadId,
+        /// The number of bytes of the pack downloaded so far, including the bytes downloaded
+        /// before the download was resumed.
+        downloaded: u64,
+        /// The size of the pack, if the server sent it.
+        total: Option<u64>,
+    },
+    /// The pack was downloaded and verified, and mounted if requested.
+    Completed {
+        /// The id of the download.
+        id: AssetPackDownloadId,
+        /// The path of the downloaded pack.
+        path: PathBuf,
+        /// The id of the mount of the pack, if it was mounted.
+        mount: Option<MountId>,
+    },
+    /// The pack failed to download.
+    Failed {
+        /// The id of the download.
+        id: AssetPackDownloadId,
+        /// The reason the download failed.
+        error: AssetPackDownloadError,
+    },
+}
+
+/// An error that occurs while downloading a pack with the [`AssetPackDownloader`].
+#[derive(Error, Debug, Clone)]
+pub enum AssetPackDownloadError {
+    /// The pack couldn't be written to disk, or the connection failed while reading it.
+    #[error("an IO error occurred while downloading the pack: {0}")]
+    Io(#[from] Arc<io::Error>),
+    /// The server responded with an error status code.
+    #[error("the server responded with status code {0}")]
+    HttpError(u16),
+    /// The request couldn't be sent.
+    #[error("the request failed: {0}")]
+    Request(String),
+    /// The downloaded pack doesn't match its [hash](AssetPackDownload::with_hash). It was deleted.
+    #[error("the downloaded pack doesn't match its hash")]
+    HashMismatch,
+}
+
+impl From<io::Error> for AssetPackDownloadError {
+    fn from(error: io::Error) -> Self {
+        Arc::new(error).into()
+    }
+}
+
+/// Sends the [`AssetPackDownloadEvent`]s of the [`AssetPackDownloader`], and mounts the downloaded
+/// packs.
+pub fn handle_asset_pack_downloads(
+    downloader: Res<AssetPackDownloader>,
+    mounts: Res<AssetMounts>,
+    mut events: MessageWriter<AssetPackDownloadEvent>,
+) {
+    while let Ok((id, update)) = downloader.update_receiver.try_recv() {
+        events.write(match update {
+            DownloadUpdate::Progress { downloaded, total } => AssetPackDownloadEvent::Progress {
+                id,
+                downloaded,
+                total,
+            },
+            DownloadUpdate::Completed { path, mount } => AssetPackDownloadEvent::Completed {
+                id,
+                path,
+                mount: mount.map(|(source, reader)| mounts.mount_erased(source, reader)),
+            },
+            DownloadUpdate::Failed(error) => AssetPackDownloadEvent::Failed { id, error },
+        });
+    }
+}
+
+/// Downloads the pack at `url` to `destination`, resuming a previous download if there is one.
+fn download_pack(
+    url: &str,
+    destination: &Path,
+    hash: Option<AssetHash>,
+    progress: &mut dyn FnMut(u64, Option<u64>),
+) -> Result<(), AssetPackDownloadError> {
+    if destination.exists() {
+        let Some(hash) = hash else {
+            return Ok(());
+        };
+        if hash_file(destination)? == hash {
+            return Ok(());
+        }
+        fs::remove_file(destination)?;
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut part_path = OsString::from(destination);
+    part_path.push(".part");
+    let part_path = PathBuf::from(part_path);
+    let mut part = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&part_path)?;
+    let mut downloaded = part.metadata()?.len();
+
+    let mut request = AGENT.get(url);
+    if downloaded > 0 {
+        request = request.header("Range", std::format!("bytes={downloaded}-"));
+    }
+    match request.call() {
+        Ok(mut response) => {
+            if response.status().as_u16() != 206 {
+                // The server doesn't support range requests and sent the whole pack.
+                part.set_len(0)?;
+                downloaded = 0;
+            }
+            let total = response.body().content_length().map(|len| downloaded + len);
+            progress(downloaded, total);
+            let mut body = response.body_mut().as_reader();
+            let mut buffer = vec![0; 64 * 1024];
+            let mut reported = downloaded;
+            loop {
+                let read = match body.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(error.into()),
+                };
+                part.write_all(&buffer[..read])?;
+                downloaded += read as u64;
+                if downloaded - reported >= PROGRESS_INTERVAL {
+                    progress(downloaded, total);
+                    reported = downloaded;
+                }
+            }
+            if reported != downloaded {
+                progress(downloaded, total);
+            }
+        }
+        // The previous download was interrupted after the whole pack was downloaded.
+        Err(ureq::Error::StatusCode(416)) if downloaded > 0 => {}
+        Err(ureq::Error::StatusCode(code)) => return Err(AssetPackDownloadError::HttpError(code)),
+        Err(ureq::Error::Io(error)) => return Err(error.into()),
+        Err(error) => return Err(AssetPackDownloadError::Request(error.to_string())),
+    }
+    part.sync_all()?;
+    drop(part);
+
+    if let Some(hash) = hash
+        && hash_file(&part_path)? != hash
+    {
+        fs::remove_file(&part_path)?;
+        return Err(AssetPackDownloadError::HashMismatch);
+    }
+    fs::rename(&part_path, destination)?;
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<AssetHash, io::Error> {
+    let mut file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => return Ok(*hasher.finalize().as_bytes()),
+            Ok(read) => {
+                hasher.update(&buffer[..read]);
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            web::WebAssetPlugin,
+            AssetSourceBuilder,
+        },
+        tests::run_app_until,
+        AssetApp, AssetPlugin,
+    };
+    use alloc::vec::Vec;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_ecs::message::Messages;
+    use bevy_platform::sync::Mutex;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serves `body` over HTTP, supporting range requests. Returns the url of the body and the
+    /// ranges requested from it.
+    fn serve(body: Vec<u8>) -> (String, Arc<Mutex<Vec<Option<u64>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = std::format!("http://{}/pack", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let requested_ranges = ranges.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut start = None;
+                for line in BufReader::new(&stream).lines() {
+                    let line = line.unwrap();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(range) = line.to_lowercase().strip_prefix("range: bytes=") {
+                        start = range.trim_end_matches('-').parse::<u64>().ok();
+                    }
+                }
+                requested_ranges.lock().unwrap().push(start);
+                let head = match start {
+                    Some(start) => std::format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {start}-{}/{}\r\n",
+                        body.len() - 1,
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 200 OK\r\n".to_string(),
+                };
+                let content = &body[start.unwrap_or(0) as usize..];
+                let _ = write!(
+                    stream,
+                    "{head}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    content.len()
+                );
+                let _ = stream.write_all(content);
+            }
+        });
+        (url, ranges)
+    }
+
+    fn create_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            WebAssetPlugin {
+                silence_startup_warning: true,
+            },
+        ))
+        .register_asset_source(
+            "memory",
+            AssetSourceBuilder::new(|| {
+                Box::new(MemoryAssetReader {
+                    root: Dir::default(),
+                })
+            }),
+        )
+        .add_plugins(AssetPlugin {
+            watch_for_changes_override: Some(false),
+            use_asset_processor_override: Some(false),
+            ..Default::default()
+        });
+        app
+    }
+
+    fn run_download(app: &mut App, download: AssetPackDownload) -> AssetPackDownloadEvent {
+        let id = app
+            .world()
+            .resource::<AssetPackDownloader>()
+            .download(download);
+        let mut result = None;
+        run_app_until(app, |world| {
+            let events = world.resource::<Messages<AssetPackDownloadEvent>>();
+            result = events
+                .iter_current_update_messages()
+                .find(|event| match event {
+                    AssetPackDownloadEvent::Completed { id: done, .. }
+                    | AssetPackDownloadEvent::Failed { id: done, .. } => *done == id,
+                    AssetPackDownloadEvent::Progress { .. } => false,
+                })
+                .cloned();
+            result.as_ref().map(|_| ())
+        });
+        result.unwrap()
+    }
+
+    #[test]
+    fn resumes_verifies_and_mounts_packs() {
+        let body: Vec<u8> = (0..200_000_u32).map(|i| i as u8).collect();
+        let hash = *blake3::hash(&body).as_bytes();
+        let (url, ranges) = serve(body.clone());
+        let dir = std::env::temp_dir().join(std::format!(
+            "bevy_asset_pack_download_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let destination = dir.join("a.pack");
+        // An interrupted download.
+        fs::write(dir.join("a.pack.part"), &body[..50_000]).unwrap();
+
+        let mut app = create_app();
+        let event = run_download(
+            &mut app,
+            AssetPackDownload::new(url.clone(), &destination)
+                .with_hash(hash)
+                .mount("memory", |path| {
+                    let root = Dir::default();
+                    root.insert_asset(Path::new("pack"), fs::read(path).unwrap());
+                    MemoryAssetReader { root }
+                }),
+        );
+        assert!(matches!(
+            event,
+            AssetPackDownloadEvent::Completed { mount: Some(_), .. }
+        ));
+        assert_eq!(fs::read(&destination).unwrap(), body);
+        assert_eq!(*ranges.lock().unwrap(), [Some(50_000)]);
+        let mounts = app.world().resource::<AssetMounts>();
+        assert_eq!(mounts.mounts("memory").len(), 1);
+
+        // A pack that doesn't match its hash is downloaded again, and deleted.
+        let event = run_download(
+            &mut app,
+            AssetPackDownload::new(url, &destination).with_hash([0; 32]),
+        );
+        assert!(matches!(
+            event,
+            AssetPackDownloadEvent::Failed {
+                error: AssetPackDownloadError::HashMismatch,
+                ..
+            }
+        ));
+        assert_eq!(*ranges.lock().unwrap(), [Some(50_000), None]);
+        assert!(!destination.exists());
+        assert!(!dir.join("a.pack.part").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
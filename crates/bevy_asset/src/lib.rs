@@ -179,10 +179,14 @@ mod handle;
 mod id;
 mod loader;
 mod loader_builders;
+mod locale;
 mod path;
+mod preload;
 mod reflect;
 mod render_asset;
 mod server;
+#[cfg(feature = "meta_signatures")]
+mod signature;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
@@ -197,32 +201,43 @@ pub use loader::*;
 pub use loader_builders::{
     Deferred, DynamicTyped, Immediate, NestedLoader, StaticTyped, UnknownTyped,
 };
+pub use locale::*;
 pub use path::*;
+pub use preload::*;
 pub use reflect::*;
 pub use render_asset::*;
 pub use server::*;
+#[cfg(feature = "meta_signatures")]
+pub use signature::*;
 
+#[cfg(feature = "meta_signatures")]
+pub use ed25519_dalek;
 pub use uuid;
 
 use crate::{
-    io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
-    processor::{AssetProcessor, Process},
+    io::{
+        embedded::EmbeddedAssetRegistry, handle_asset_mount_events, AssetIoDiagnosticPaths,
+        AssetMountEvent, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId, SymlinkPolicy,
+    },
+    processor::{AssetProcessor, FileTransactionLogFactory, Process},
 };
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     sync::Arc,
     vec::Vec,
 };
-use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_app::{App, Plugin, PostUpdate, PreStartup, PreUpdate};
 use bevy_ecs::{prelude::Component, schedule::common_conditions::resource_exists};
 use bevy_ecs::{
     reflect::AppTypeRegistry,
     schedule::{IntoScheduleConfigs, SystemSet},
     world::FromWorld,
 };
-use bevy_platform::collections::HashSet;
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath};
 use core::any::TypeId;
+use std::path::PathBuf;
 use tracing::error;
 
 /// Provides "asset" loading and processing functionality. An [`Asset`] is a "runtime value" that is loaded from an [`AssetSource`],
@@ -233,10 +248,16 @@ use tracing::error;
 ///
 /// [`AssetSource`]: io::AssetSource
 pub struct AssetPlugin {
+    /// The project root, relative to which [`file_path`](Self::file_path) and
+    /// [`processed_file_path`](Self::processed_file_path) are resolved. See [`AssetBasePath`].
+    pub base_path: AssetBasePath,
     /// The default file path to use (relative to the project root) for unprocessed assets.
     pub file_path: String,
-    /// The default file path to use (relative to the project root) for processed assets.
-    pub processed_file_path: String,
+    /// The default file path to use for processed assets.
+    ///
+    /// This can be resolved at runtime, e.g. to write processed assets to a writable folder of
+    /// sandboxed platforms. See [`ProcessedFilePath`].
+    pub processed_file_path: ProcessedFilePath,
     /// If set, will override the default "watch for changes" setting. By default "watch for changes" will be `false` unless
     /// the `watch` cargo feature is set. `watch` can be enabled manually, or it will be automatically enabled if a specific watcher
     /// like `file_watcher` is enabled.
@@ -253,12 +274,51 @@ pub struct AssetPlugin {
     /// The [`AssetMode`] to use for this server.
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
+    ///
+    /// Individual asset sources can override this with [`AssetSourceBuilder::with_meta_check`].
     pub meta_check: AssetMetaCheck,
     /// How to handle load requests of files that are outside the approved directories.
     ///
     /// Approved folders are [`AssetPlugin::file_path`] and the folder of each
     /// [`AssetSource`](io::AssetSource). Subfolders within these folders are also valid.
+    ///
+    /// Individual asset sources can override this with [`AssetSourceBuilder::with_unapproved_path_mode`].
     pub unapproved_path_mode: UnapprovedPathMode,
+    /// If true, the reads of every asset source are counted and published to
+    /// [`bevy_diagnostic`].
+    ///
+    /// Individual asset sources can enable this with [`AssetSourceBuilder::with_metrics`].
+    pub io_metrics: bool,
+    /// Path aliases, from the name used as the source of asset paths to the path it resolves to.
+    ///
+    /// With an alias from `textures` to `art/textures`, loading `textures://rock.png` loads
+    /// `art/textures/rock.png` from the default source. Aliases can also point into another
+    /// source, e.g. `remote://textures`. This allows moving folders without changing the paths
+    /// used to load their assets.
+    ///
+    /// See [`AssetSourceBuilders::insert_path_alias`](io::AssetSourceBuilders::insert_path_alias).
+    pub path_aliases: HashMap<String, AssetPath<'static>>,
+    /// If set, the `.meta` files read by the [`AssetServer`] must be signed with the key this
+    /// verifies, see [`sign_asset_bytes`]. Assets whose `.meta` file isn't signed, or was
+    /// modified after it was signed, fail to load with [`AssetLoadError::InvalidMetaSignature`].
+    ///
+    /// This prevents modified asset settings from being used to exploit bugs in asset loaders.
+    /// Assets without a `.meta` file are still loaded, with the default settings of their loader.
+    ///
+    /// Each `.meta` file is signed for the path of the file it describes, which is the path of the
+    /// localized version of an asset when one is loaded instead, see [`Locale`].
+    ///
+    /// In [`AssetMode::Processed`], the `.meta` files read are the ones the
+    /// [`AssetProcessor`](processor::AssetProcessor) writes to the processed asset source, which
+    /// it doesn't sign: sign them once the assets are processed, e.g. when building the release.
+    #[cfg(feature = "meta_signatures")]
+    pub meta_verifying_key: Option<ed25519_dalek::VerifyingKey>,
+    /// How the readers and writers of the default source treat the symbolic links and junctions
+    /// of the local filesystem. This applies to both the unprocessed and the processed assets.
+    ///
+    /// Other file sources set it on their `FileAssetReader` and `FileAssetWriter`. This has no
+    /// effect if the default source was registered by the app, or on the web and Android.
+    pub symlink_policy: SymlinkPolicy,
 }
 
 /// Determines how to react to attempts to load assets not inside the approved folders.
@@ -328,16 +388,155 @@ pub enum AssetMetaCheck {
     Never,
 }
 
+/// The project root of the file asset sources, set with [`AssetPlugin::base_path`].
+///
+/// By default, the project root is detected from the environment: it is the `BEVY_ASSET_ROOT`
+/// environment variable if set, then the `CARGO_MANIFEST_DIR` environment variable when running
+/// with cargo, and the executable's folder otherwise. Environment variables are awkward to set for
+/// packaged apps, which can set the base path programmatically instead.
+///
+/// The base path is resolved once, when the [`AssetPlugin`] is built, and applies to the default
+/// asset source of that plugin and to the transaction log of its [`AssetProcessor`]. Other asset
+/// sources, and [`FileAssetReader::get_base_path`](io::file::FileAssetReader::get_base_path),
+/// keep using the path detected from the environment. It has no effect on the web and on Android.
+#[derive(Clone, Default)]
+pub enum AssetBasePath {
+    /// Detects the base path from the environment.
+    #[default]
+    Detect,
+    /// A fixed path. Relative paths are relative to the current working directory.
+    Fixed(PathBuf),
+    /// A path computed by the given function when the [`AssetPlugin`] is built.
+    Resolver(Arc<dyn Fn() -> PathBuf + Send + Sync>),
+}
+
+impl AssetBasePath {
+    /// Creates an [`AssetBasePath::Resolver`] from the given function.
+    pub fn resolver(resolver: impl Fn() -> PathBuf + Send + Sync + 'static) -> Self {
+        Self::Resolver(Arc::new(resolver))
+    }
+
+    /// Resolves the path, or returns [`None`] if it should be detected from the environment.
+    ///
+    /// Relative paths are made absolute with the current working directory, so that they aren't
+    /// joined to the path detected from the environment by the file asset sources.
+    pub fn resolve(&self) -> Option<PathBuf> {
+        let path = match self {
+            Self::Detect => return None,
+            Self::Fixed(path) => path.clone(),
+            Self::Resolver(resolver) => resolver(),
+        };
+        Some(std::path::absolute(&path).unwrap_or(path))
+    }
+}
+
+impl From<PathBuf> for AssetBasePath {
+    fn from(path: PathBuf) -> Self {
+        Self::Fixed(path)
+    }
+}
+
+impl core::fmt::Debug for AssetBasePath {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Detect => f.write_str("Detect"),
+            Self::Fixed(path) => f.debug_tuple("Fixed").field(path).finish(),
+            Self::Resolver(_) => f.debug_tuple("Resolver").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Where the default [`AssetSource`](io::AssetSource) reads and writes processed assets.
+///
+/// The path is resolved once, when the [`AssetPlugin`] is built. Relative paths are relative to
+/// the project root (or the executable's folder when running outside of cargo).
+///
+/// Platforms that sandbox packaged apps typically don't allow writing next to the executable, so
+/// running the asset processor there requires [`ProcessedFilePath::TemporaryDirectory`] or a
+/// [`ProcessedFilePath::Resolver`] returning a writable folder.
+///
+/// When the path isn't [`ProcessedFilePath::Fixed`], the transaction log of the
+/// [`AssetProcessor`] is stored in a `log` file next to the processed folder, rather than in
+/// `imported_assets/log`.
+#[derive(Clone)]
+pub enum ProcessedFilePath {
+    /// A fixed path.
+    Fixed(String),
+    /// A path relative to the temporary directory of the platform, as returned by
+    /// [`std::env::temp_dir`]. Platforms without a temporary directory use the path as is.
+    TemporaryDirectory(String),
+    /// A path computed by the given function when the [`AssetPlugin`] is built.
+    Resolver(Arc<dyn Fn() -> String + Send + Sync>),
+}
+
+impl ProcessedFilePath {
+    /// Creates a [`ProcessedFilePath::Resolver`] from the given function.
+    pub fn resolver(resolver: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self::Resolver(Arc::new(resolver))
+    }
+
+    /// Resolves the path.
+    pub fn resolve(&self) -> String {
+        match self {
+            Self::Fixed(path) => path.clone(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::TemporaryDirectory(path) => std::env::temp_dir()
+                .join(path)
+                .to_string_lossy()
+                .into_owned(),
+            #[cfg(target_arch = "wasm32")]
+            Self::TemporaryDirectory(path) => path.clone(),
+            Self::Resolver(resolver) => resolver(),
+        }
+    }
+}
+
+impl Default for ProcessedFilePath {
+    fn default() -> Self {
+        Self::Fixed(AssetPlugin::DEFAULT_PROCESSED_FILE_PATH.to_string())
+    }
+}
+
+impl From<String> for ProcessedFilePath {
+    fn from(path: String) -> Self {
+        Self::Fixed(path)
+    }
+}
+
+impl From<&str> for ProcessedFilePath {
+    fn from(path: &str) -> Self {
+        Self::Fixed(path.to_string())
+    }
+}
+
+impl core::fmt::Debug for ProcessedFilePath {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Fixed(path) => f.debug_tuple("Fixed").field(path).finish(),
+            Self::TemporaryDirectory(path) => {
+                f.debug_tuple("TemporaryDirectory").field(path).finish()
+            }
+            Self::Resolver(_) => f.debug_tuple("Resolver").finish_non_exhaustive(),
+        }
+    }
+}
+
 impl Default for AssetPlugin {
     fn default() -> Self {
         Self {
             mode: AssetMode::Unprocessed,
+            base_path: AssetBasePath::default(),
             file_path: Self::DEFAULT_UNPROCESSED_FILE_PATH.to_string(),
-            processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
+            processed_file_path: ProcessedFilePath::default(),
             watch_for_changes_override: None,
             use_asset_processor_override: None,
             meta_check: AssetMetaCheck::default(),
             unapproved_path_mode: UnapprovedPathMode::default(),
+            io_metrics: false,
+            path_aliases: HashMap::default(),
+            #[cfg(feature = "meta_signatures")]
+            meta_verifying_key: None,
+            symlink_policy: SymlinkPolicy::default(),
         }
     }
 }
@@ -351,17 +550,30 @@ impl AssetPlugin {
 
 impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
+        let base_path = self.base_path.resolve();
         let embedded = EmbeddedAssetRegistry::default();
+        let processed_file_path = self.processed_file_path.resolve();
         {
             let mut sources = app
                 .world_mut()
                 .get_resource_or_init::<AssetSourceBuilders>();
-            sources.init_default_source(
+            let processed_path = (!matches!(self.mode, AssetMode::Unprocessed))
+                .then_some(processed_file_path.as_str());
+            sources.init_default_source_with(
+                base_path.as_deref(),
                 &self.file_path,
-                (!matches!(self.mode, AssetMode::Unprocessed))
-                    .then_some(self.processed_file_path.as_str()),
+                processed_path,
+                self.symlink_policy,
             );
             embedded.register_source(&mut sources);
+            for (alias, target) in &self.path_aliases {
+                sources.insert_path_alias(alias.clone(), target.clone());
+            }
+            if self.io_metrics {
+                for source in sources.iter_mut() {
+                    source.metrics = true;
+                }
+            }
         }
         {
             let watch = self
@@ -387,6 +599,27 @@ impl Plugin for AssetPlugin {
                     if use_asset_processor {
                         let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                         let (processor, sources) = AssetProcessor::new(&mut builders, watch);
+                        let log_factory =
+                            if !matches!(self.processed_file_path, ProcessedFilePath::Fixed(_)) {
+                                // The default log location may not be writable either.
+                                let processed_path = match &base_path {
+                                    Some(base_path) => base_path.join(&processed_file_path),
+                                    None => PathBuf::from(&processed_file_path),
+                                };
+                                Some(FileTransactionLogFactory::next_to_processed_path(
+                                    processed_path,
+                                ))
+                            } else {
+                                base_path
+                                    .as_ref()
+                                    .map(FileTransactionLogFactory::in_base_path)
+                            };
+                        if let Some(log_factory) = log_factory {
+                            processor
+                                .data()
+                                .set_log_factory(Box::new(log_factory))
+                                .expect("the asset processor has not started yet");
+                        }
                         // the main asset server shares loaders with the processor asset server
                         app.insert_resource(AssetServer::new_with_loaders(
                             sources,
@@ -412,11 +645,39 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        #[cfg(feature = "meta_signatures")]
+        app.world()
+            .resource::<AssetServer>()
+            .set_meta_verifying_key(self.meta_verifying_key);
+        let mounts = app
+            .world()
+            .resource::<AssetSourceBuilders>()
+            .mounts()
+            .clone();
         app.insert_resource(embedded)
+            .insert_resource(mounts)
+            .add_message::<AssetMountEvent>()
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_message::<UntypedAssetLoadFailedEvent>()
+            .add_message::<UntypedAssetReloadedEvent>()
+            .init_resource::<AssetPreloads>()
+            .init_resource::<Locale>()
+            .add_message::<PreloadProgressEvent>()
+            .add_systems(
+                PreUpdate,
+                update_preload_progress.in_set(AssetTrackingSystems),
+            )
+            // The locale is also applied before startup, for the assets loaded by startup systems.
+            .add_systems(PreStartup, update_asset_locale)
+            .add_systems(PreUpdate, update_asset_locale)
+            .add_systems(
+                PreUpdate,
+                release_expired_keep_alive_assets
+                    .after(handle_internal_asset_events)
+                    .before(AssetTrackingSystems),
+            )
             .configure_sets(
                 PreUpdate,
                 AssetTrackingSystems.after(handle_internal_asset_events),
@@ -429,13 +690,26 @@ impl Plugin for AssetPlugin {
                 PreUpdate,
                 (
                     handle_internal_asset_events.ambiguous_with_all(),
+                    handle_asset_mount_events,
                     // TODO: Remove the run condition and use `If` once
                     // https://github.com/bevyengine/bevy/issues/21549 is resolved.
-                    publish_asset_server_diagnostics.run_if(resource_exists::<DiagnosticsStore>),
+                    (
+                        publish_asset_server_diagnostics,
+                        publish_asset_io_diagnostics,
+                    )
+                        .run_if(resource_exists::<DiagnosticsStore>),
                 )
                     .chain(),
             )
             .register_diagnostic(Diagnostic::new(AssetServer::STARTED_LOAD_COUNT));
+        let sources = app.world().resource::<AssetServer>().data.sources.clone();
+        for source in sources.iter().filter(|source| source.metrics().is_some()) {
+            let paths = AssetIoDiagnosticPaths::new(&source.id());
+            app.register_diagnostic(Diagnostic::new(paths.reads))
+                .register_diagnostic(Diagnostic::new(paths.bytes_read))
+                .register_diagnostic(Diagnostic::new(paths.errors))
+                .register_diagnostic(Diagnostic::new(paths.mean_latency).with_suffix("ms"));
+        }
     }
 }
 
@@ -653,6 +927,7 @@ impl AssetApp for App {
             .allow_ambiguous_resource::<Assets<A>>()
             .add_message::<AssetEvent<A>>()
             .add_message::<AssetLoadFailedEvent<A>>()
+            .add_message::<AssetReloadedEvent<A>>()
             .register_type::<Handle<A>>()
             .add_systems(
                 PostUpdate,
@@ -709,13 +984,15 @@ mod tests {
         io::{
             gated::{GateOpener, GatedReader},
             memory::{Dir, MemoryAssetReader, MemoryAssetWriter},
-            AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId,
-            AssetWatcher, Reader,
+            AssetIoDiagnosticPaths, AssetReader, AssetReaderError, AssetSourceBuilder,
+            AssetSourceEvent, AssetSourceId, AssetWatcher, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, InvalidGenerationError, LoadState, LoadedAsset,
-        UnapprovedPathMode, UntypedHandle, WriteDefaultMetaError,
+        preload::{AssetPreloads, PreloadManifest, PreloadProgressEvent},
+        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetMetaCheck,
+        AssetPath, AssetPlugin, AssetReloadedEvent, AssetServer, Assets, InvalidGenerationError,
+        LoadState, LoadedAsset, Locale, UnapprovedPathMode, UntypedAssetReloadedEvent,
+        UntypedHandle, WriteDefaultMetaError,
     };
     use alloc::{
         boxed::Box,
@@ -1787,6 +2064,197 @@ mod tests {
         assert_eq!(get_started_load_count(app.world()), 4);
     }
 
+    #[test]
+    fn preload_manifest_progress() {
+        let (mut app, dir) = create_app();
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new("a.cool.ron"), a_ron);
+        dir.insert_asset_text(Path::new("b.cool.ron"), b_ron);
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        app.world_mut().resource_mut::<AssetPreloads>().preload(
+            &asset_server,
+            PreloadManifest::new("level")
+                .with_path("a.cool.ron")
+                .with_path("missing.cool.ron"),
+        );
+
+        let mut cursor = MessageCursor::default();
+        run_app_until(&mut app, |world| {
+            let events = world.resource::<Messages<PreloadProgressEvent>>();
+            cursor
+                .read(events)
+                .any(|event| event.name == "level" && event.progress.is_finished())
+                .then_some(())
+        });
+
+        let progress = app
+            .world()
+            .resource::<AssetPreloads>()
+            .progress("level")
+            .unwrap();
+        assert_eq!(progress.loaded, 1);
+        assert_eq!(progress.failed, 1);
+        assert_eq!(progress.total, 2);
+        assert_eq!(progress.bytes, a_ron.len() as u64);
+        assert_eq!(progress.fraction(), 1.0);
+
+        assert!(app
+            .world_mut()
+            .resource_mut::<AssetPreloads>()
+            .release("level"));
+        assert!(app
+            .world()
+            .resource::<AssetPreloads>()
+            .progress("level")
+            .is_none());
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let (mut app, dir) = create_app();
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [
+        "c.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let c_ron = r#"
+(
+    text: "c",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new("a.cool.ron"), a_ron);
+        dir.insert_asset_text(Path::new("b.cool.ron"), b_ron);
+        dir.insert_asset_text(Path::new("c.cool.ron"), c_ron);
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        run_app_until(&mut app, |_| {
+            asset_server.is_loaded_with_dependencies(&a).then_some(())
+        });
+
+        let b = asset_server.get_path_id("b.cool.ron").unwrap();
+        let c = asset_server.get_path_id("c.cool.ron").unwrap();
+
+        let a_info = asset_server.get_dependency_info(&a).unwrap();
+        assert_eq!(a_info.path, Some(AssetPath::from("a.cool.ron")));
+        assert!(a_info.load_state.is_loaded());
+        assert_eq!(a_info.dependencies.len(), 2);
+        assert!(a_info.dependencies.contains(&b) && a_info.dependencies.contains(&c));
+        assert!(a_info.dependents.is_empty());
+        assert_eq!(a_info.strong_handles, 1);
+
+        let c_info = asset_server.get_dependency_info(c).unwrap();
+        assert_eq!(c_info.dependents.len(), 2);
+        assert!(c_info.dependencies.is_empty());
+
+        let recursive_dependencies = asset_server.get_recursive_dependencies(&a);
+        assert_eq!(recursive_dependencies.len(), 2);
+        assert!(recursive_dependencies.contains(&b) && recursive_dependencies.contains(&c));
+
+        let unloaded = asset_server.get_assets_unloaded_with(&a);
+        assert_eq!(unloaded.len(), 3);
+        assert_eq!(unloaded[0], a.id().untyped());
+
+        // Keeping a handle to `b` keeps it alive, along with `c`.
+        let b_handle = asset_server.get_handle_untyped("b.cool.ron").unwrap();
+        assert_eq!(
+            asset_server.get_assets_unloaded_with(&a),
+            [a.id().untyped()]
+        );
+        assert!(asset_server.get_assets_unloaded_with(&b_handle).is_empty());
+        assert_eq!(asset_server.get_asset_ids().len(), 3);
+    }
+
+    #[test]
+    fn keep_alive() {
+        let (mut app, dir) = create_app();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"
+(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        asset_server.set_keep_alive_for_type::<CoolText>(Duration::from_secs(3600));
+
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let id = handle.id();
+        run_app_until(&mut app, |_| asset_server.is_loaded(id).then_some(()));
+        let started_load_tasks = asset_server.read_infos().stats.started_load_tasks;
+
+        // Dropping the last handle keeps the asset loaded.
+        drop(handle);
+        app.update();
+        app.update();
+        assert!(asset_server.is_kept_alive(id));
+        assert!(app.world().resource::<Assets<CoolText>>().contains(id));
+
+        // Loading the asset again returns it without reloading it.
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        assert_eq!(handle.id(), id);
+        assert!(asset_server.is_loaded(id));
+        assert!(!asset_server.is_kept_alive(id));
+        assert_eq!(
+            asset_server.read_infos().stats.started_load_tasks,
+            started_load_tasks
+        );
+
+        // Releasing the kept-alive assets unloads the asset once it is no longer used.
+        drop(handle);
+        asset_server.release_kept_alive_assets();
+        app.update();
+        app.update();
+        assert!(!asset_server.is_kept_alive(id));
+        assert!(!app.world().resource::<Assets<CoolText>>().contains(id));
+        assert!(asset_server.get_load_state(id).is_none());
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -2142,6 +2610,285 @@ mod tests {
         run_app_until(&mut app, |_| asset_server.is_loaded(&handle).then_some(()));
     }
 
+    #[test]
+    fn unapproved_path_source_override() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("../a.cool.ron"),
+            r#"
+(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        let mut app = App::new();
+        let default_reader = MemoryAssetReader { root: dir.clone() };
+        let strict_reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(default_reader.clone())),
+        )
+        .register_asset_source(
+            "strict",
+            AssetSourceBuilder::new(move || Box::new(strict_reader.clone()))
+                .with_unapproved_path_mode(UnapprovedPathMode::Forbid),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                unapproved_path_mode: UnapprovedPathMode::Allow,
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+        ));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        assert_eq!(
+            asset_server.load_override::<CoolText>("strict://../a.cool.ron"),
+            Handle::default()
+        );
+
+        let handle = asset_server.load::<CoolText>("../a.cool.ron");
+        assert_ne!(handle, Handle::default());
+        run_app_until(&mut app, |_| asset_server.is_loaded(&handle).then_some(()));
+    }
+
+    #[test]
+    fn meta_check_source_override() {
+        let dir = Dir::default();
+        let a_path = Path::new("a.cool.ron");
+        dir.insert_asset_text(
+            a_path,
+            r#"
+(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        // An unparseable meta file fails the load unless meta checks are skipped.
+        dir.insert_meta_text(a_path, "not a meta file");
+
+        let mut app = App::new();
+        let default_reader = MemoryAssetReader { root: dir.clone() };
+        let no_meta_reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(default_reader.clone())),
+        )
+        .register_asset_source(
+            "no_meta",
+            AssetSourceBuilder::new(move || Box::new(no_meta_reader.clone()))
+                .with_meta_check(AssetMetaCheck::Never),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                meta_check: AssetMetaCheck::Always,
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+        ));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let checked = asset_server.load::<CoolText>("a.cool.ron");
+        let unchecked = asset_server.load::<CoolText>("no_meta://a.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.load_state(&checked).is_failed() && asset_server.is_loaded(&unchecked))
+                .then_some(())
+        });
+    }
+
+    #[cfg(feature = "meta_signatures")]
+    #[test]
+    fn meta_signatures_are_verified() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[1; 32]);
+        let dir = Dir::default();
+        let meta = r#"(
+    meta_format_version: "1.0",
+    asset: Load(
+        loader: "bevy_asset::tests::CoolTextLoader",
+        settings: (),
+    ),
+)"#;
+        let text = r#"(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        for path in ["signed.cool.ron", "unsigned.cool.ron", "tampered.cool.ron"] {
+            dir.insert_asset_text(Path::new(path), text);
+        }
+        let signed =
+            crate::sign_asset_bytes(meta.as_bytes(), &AssetPath::parse("signed.cool.ron"), &key);
+        dir.insert_meta(Path::new("signed.cool.ron"), signed.clone());
+        dir.insert_meta_text(Path::new("unsigned.cool.ron"), meta);
+        // The meta file of a localized asset is signed for the localized path.
+        dir.insert_asset_text(Path::new("localized.cool.fr.ron"), text);
+        dir.insert_meta(
+            Path::new("localized.cool.fr.ron"),
+            crate::sign_asset_bytes(
+                meta.as_bytes(),
+                &AssetPath::parse("localized.cool.fr.ron"),
+                &key,
+            ),
+        );
+        // A signed meta file copied next to another asset isn't valid for it.
+        dir.insert_asset_text(Path::new("copied.cool.ron"), text);
+        dir.insert_meta(Path::new("copied.cool.ron"), signed.clone());
+        let tampered = String::from_utf8(signed).unwrap().replace("()", "(())");
+        dir.insert_meta_text(Path::new("tampered.cool.ron"), &tampered);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                meta_verifying_key: Some(key.verifying_key()),
+                ..Default::default()
+            },
+        ));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .insert_resource(Locale::new("fr"));
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let signed = asset_server.load::<CoolText>("signed.cool.ron");
+        let localized = asset_server.load::<CoolText>("localized.cool.ron");
+        let unsigned = asset_server.load::<CoolText>("unsigned.cool.ron");
+        let tampered = asset_server.load::<CoolText>("tampered.cool.ron");
+        let copied = asset_server.load::<CoolText>("copied.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded(&signed)
+                && asset_server.is_loaded(&localized)
+                && asset_server.load_state(&unsigned).is_failed()
+                && asset_server.load_state(&tampered).is_failed()
+                && asset_server.load_state(&copied).is_failed())
+            .then_some(())
+        });
+        let LoadState::Failed(error) = asset_server.load_state(&tampered) else {
+            unreachable!();
+        };
+        assert!(matches!(
+            *error,
+            AssetLoadError::InvalidMetaSignature {
+                error: crate::AssetSignatureError::Invalid,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn path_aliases_resolve_before_source_lookup() {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("art/textures/a.cool.ron"),
+            r#"
+(
+    text: "a",
+    dependencies: ["textures://b.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new("art/textures/b.cool.ron"),
+            r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        let mut app = App::new();
+        let memory_reader = MemoryAssetReader { root: dir };
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(memory_reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                path_aliases: [("textures".into(), "art/textures".into())].into(),
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+        ));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        assert_eq!(
+            asset_server.resolve_path_alias("textures://a.cool.ron#label"),
+            AssetPath::from("art/textures/a.cool.ron#label")
+        );
+        let handle = asset_server.load::<CoolText>("textures://a.cool.ron");
+        assert_eq!(handle, asset_server.load("art/textures/a.cool.ron"));
+        run_app_until(&mut app, |_| {
+            asset_server
+                .is_loaded_with_dependencies(&handle)
+                .then_some(())
+        });
+        assert!(asset_server
+            .get_handle::<CoolText>("art/textures/b.cool.ron")
+            .is_some());
+    }
+
+    #[test]
+    fn locale_loads_and_reloads_localized_assets() {
+        let (mut app, dir) = create_app();
+        let cool_text = |text: &str| {
+            format!(
+                r#"(
+    text: "{text}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+            )
+        };
+        dir.insert_asset_text(Path::new("a.cool.ron"), &cool_text("a"));
+        dir.insert_asset_text(Path::new("a.cool.fr.ron"), &cool_text("a fr"));
+        dir.insert_asset_text(Path::new("lang/de/a.cool.ron"), &cool_text("a de"));
+        app.init_asset::<CoolText>()
+            .register_asset_loader(CoolTextLoader)
+            .insert_resource(Locale::new("fr-FR"));
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let text_is = |app: &mut App, expected: &str| {
+            run_app_until(app, |world| {
+                (get(world, handle.id())?.text == expected).then_some(())
+            });
+        };
+        text_is(&mut app, "a fr");
+
+        *app.world_mut().resource_mut::<Locale>() = Locale::new("de");
+        text_is(&mut app, "a de");
+
+        *app.world_mut().resource_mut::<Locale>() = Locale::default();
+        text_is(&mut app, "a");
+    }
+
     #[test]
     fn insert_dropped_handle_returns_error() {
         let mut app = create_app().0;
@@ -2414,6 +3161,124 @@ mod tests {
         });
     }
 
+    #[test]
+    fn modified_localized_asset_reloads_its_asset() {
+        let (mut app, dir, source_events) = create_app_with_source_event_sender();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let cool_text = |text: &str| {
+            format!(
+                r#"(
+    text: "{text}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+            )
+        };
+        dir.insert_asset_text(Path::new("a.cool.ron"), &cool_text("a"));
+        dir.insert_asset_text(Path::new("a.cool.fr.ron"), &cool_text("a fr"));
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .insert_resource(Locale::new("fr"));
+
+        let handle: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let text_is = |app: &mut App, expected: &str| {
+            run_app_until(app, |world| {
+                (get(world, handle.id())?.text == expected).then_some(())
+            });
+        };
+        text_is(&mut app, "a fr");
+
+        dir.insert_asset_text(Path::new("a.cool.fr.ron"), &cool_text("a fr 2"));
+        source_events
+            .send_blocking(AssetSourceEvent::ModifiedAsset(PathBuf::from(
+                "a.cool.fr.ron",
+            )))
+            .unwrap();
+        text_is(&mut app, "a fr 2");
+
+        dir.remove_asset(Path::new("a.cool.fr.ron"));
+        source_events
+            .send_blocking(AssetSourceEvent::RemovedAsset(PathBuf::from(
+                "a.cool.fr.ron",
+            )))
+            .unwrap();
+        text_is(&mut app, "a");
+    }
+
+    #[test]
+    fn reload_events_report_changed_path_and_dependents() {
+        let (mut app, dir, source_events) = create_app_with_source_event_sender();
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: ["b.cool.ron"],
+    sub_texts: [],
+)"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded(&a) && asset_server.is_loaded(&b)).then_some(())
+        });
+
+        let mut reloads = Vec::new();
+        for _ in 0..2 {
+            source_events
+                .send_blocking(AssetSourceEvent::ModifiedAsset(PathBuf::from("b.cool.ron")))
+                .unwrap();
+            reloads.clear();
+            run_app_until(&mut app, |world| {
+                reloads.extend(
+                    world
+                        .resource_mut::<Messages<UntypedAssetReloadedEvent>>()
+                        .drain(),
+                );
+                (reloads.len() == 2).then_some(())
+            });
+        }
+
+        let b_reload = reloads.iter().find(|r| r.id == b.id().untyped()).unwrap();
+        assert!(!b_reload.is_dependency_change());
+        assert_eq!(
+            b_reload.invalidated_dependents,
+            [AssetPath::from("a.cool.ron")]
+        );
+        assert!(b_reload.previous_modified_at.is_some());
+
+        let a_reload = reloads.iter().find(|r| r.id == a.id().untyped()).unwrap();
+        assert!(a_reload.is_dependency_change());
+        assert_eq!(a_reload.changed_path, AssetPath::from("b.cool.ron"));
+        assert!(a_reload.invalidated_dependents.is_empty());
+        assert_eq!(a_reload.modified_at, b_reload.modified_at);
+
+        let typed_reloads = app
+            .world_mut()
+            .resource_mut::<Messages<AssetReloadedEvent<CoolText>>>()
+            .drain()
+            .count();
+        assert_eq!(typed_reloads, 2);
+    }
+
     #[test]
     fn added_asset_reloads_previously_missing_asset() {
         let (mut app, dir, source_events) = create_app_with_source_event_sender();
@@ -3069,4 +3934,145 @@ mod tests {
             TestLoadState::Failed(TestAssetLoadError::MissingAssetLoader),
         );
     }
+
+    #[test]
+    fn resolve_processed_file_path() {
+        use crate::ProcessedFilePath;
+
+        assert_eq!(
+            ProcessedFilePath::from("imported_assets/Custom").resolve(),
+            "imported_assets/Custom"
+        );
+        assert_eq!(
+            ProcessedFilePath::resolver(|| format!("imported_assets/{}", 1 + 1)).resolve(),
+            "imported_assets/2"
+        );
+        assert_eq!(
+            PathBuf::from(ProcessedFilePath::TemporaryDirectory("my_game".to_string()).resolve()),
+            std::env::temp_dir().join("my_game")
+        );
+    }
+
+    #[test]
+    fn resolve_base_path() {
+        use crate::AssetBasePath;
+
+        assert_eq!(AssetBasePath::Detect.resolve(), None);
+        assert_eq!(
+            AssetBasePath::from(std::env::temp_dir().join("my_game")).resolve(),
+            Some(std::env::temp_dir().join("my_game"))
+        );
+        // Relative paths are relative to the current working directory.
+        assert_eq!(
+            AssetBasePath::from(PathBuf::from("games/my_game")).resolve(),
+            Some(std::env::current_dir().unwrap().join("games/my_game"))
+        );
+        assert_eq!(
+            AssetBasePath::resolver(|| std::env::temp_dir().join("my_game")).resolve(),
+            Some(std::env::temp_dir().join("my_game"))
+        );
+    }
+
+    #[test]
+    fn io_metrics_count_reads() {
+        let mut app = App::new();
+        let dir = Dir::default();
+        let a_ron = r#"(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new("a.cool.ron"), a_ron);
+        let dir_clone = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || {
+                Box::new(MemoryAssetReader {
+                    root: dir_clone.clone(),
+                })
+            })
+            .with_metrics(),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+            DiagnosticsPlugin,
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        run_app_until(&mut app, |_| asset_server.is_loaded(&a).then_some(()));
+        app.update();
+
+        let metrics = asset_server
+            .get_source(AssetSourceId::Default)
+            .unwrap()
+            .metrics()
+            .unwrap();
+        // The meta file doesn't exist, so only the asset itself is read.
+        assert_eq!(metrics.reads(), 1);
+        assert_eq!(metrics.not_found(), 1);
+        assert_eq!(metrics.errors(), 0);
+        assert_eq!(metrics.bytes_read(), a_ron.len() as u64);
+        assert_eq!(metrics.latency_histogram().iter().sum::<u64>(), 1);
+
+        let paths = AssetIoDiagnosticPaths::new(&AssetSourceId::Default);
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let reads = diagnostics.get_measurement(&paths.reads).unwrap();
+        assert_eq!(reads.value, 1.0);
+        let bytes_read = diagnostics.get_measurement(&paths.bytes_read).unwrap();
+        assert_eq!(bytes_read.value, a_ron.len() as f64);
+    }
+
+    #[test]
+    fn case_insensitive_paths_identify_the_same_asset() {
+        let mut app = App::new();
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("Texts/A.cool.ron"),
+            r#"(
+    text: "a",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#,
+        );
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() }))
+                .with_case_insensitive_paths(),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                use_asset_processor_override: Some(false),
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("texts/a.COOL.ron");
+        assert_eq!(a.path(), Some(&AssetPath::from("Texts/A.cool.ron")));
+        let b: Handle<CoolText> = asset_server.load("Texts/A.cool.ron");
+        assert_eq!(a, b);
+        run_app_until(&mut app, |_| asset_server.is_loaded(&a).then_some(()));
+
+        // Paths that don't exist are kept as they are.
+        assert_eq!(
+            asset_server.resolve_path_alias("texts/missing.cool.ron"),
+            AssetPath::from("texts/missing.cool.ron")
+        );
+    }
 }
@@ -577,19 +577,44 @@ impl<'a> LoadContext<'a> {
             AssetServerMode::Unprocessed => source.reader(),
             AssetServerMode::Processed => source.processed_reader()?,
         };
-        let mut reader = asset_reader.read(path.path()).await?;
-        let hash = if self.populate_hashes {
+        let localized_path = self.asset_server.localize_path(asset_reader, &path).await;
+        // Processed assets may be stored content-addressed, in which case their meta file records
+        // where their bytes are.
+        if let AssetServerMode::Processed = self.asset_server.mode()
+            && let Ok(meta_bytes) = asset_reader.read_meta_bytes(&localized_path).await
+            && let Ok(minimal) = ron::de::from_bytes::<ProcessedInfoMinimal>(&meta_bytes)
+            && let Some(processed_info) = minimal.processed_info
+            && let Some(content_hash) = processed_info.content_hash
+        {
+            let bytes = source.read_processed_content(&content_hash).await?;
+            if !processed_info.matches_processed_bytes(&bytes) {
+                source.reprocess_corrupt_asset(path.path());
+                return Err(ReadAssetBytesError::CorruptProcessedAsset(
+                    path.path().to_path_buf(),
+                ));
+            }
+            let hash = if self.populate_hashes {
+                processed_info.full_hash
+            } else {
+                Default::default()
+            };
+            self.loader_dependencies.insert(path.clone_owned(), hash);
+            return Ok(bytes);
+        }
+        let mut reader = asset_reader.read(&localized_path).await?;
+        let processed_info = if self.populate_hashes {
             // NOTE: ensure meta is read while the asset bytes reader is still active to ensure transactionality
             // See `ProcessorGatedReader` for more info
-            let meta_bytes = asset_reader.read_meta_bytes(path.path()).await?;
+            let meta_bytes = asset_reader.read_meta_bytes(&localized_path).await?;
             let minimal: ProcessedInfoMinimal = ron::de::from_bytes(&meta_bytes)
                 .map_err(DeserializeMetaError::DeserializeMinimal)?;
-            let processed_info = minimal
-                .processed_info
-                .ok_or(ReadAssetBytesError::MissingAssetHash)?;
-            processed_info.full_hash
+            Some(
+                minimal
+                    .processed_info
+                    .ok_or(ReadAssetBytesError::MissingAssetHash)?,
+            )
         } else {
-            Default::default()
+            None
         };
         let mut bytes = Vec::new();
         reader
@@ -599,6 +624,18 @@ impl<'a> LoadContext<'a> {
                 path: path.path().to_path_buf(),
                 source,
             })?;
+        let hash = match processed_info {
+            Some(processed_info) => {
+                if !processed_info.matches_processed_bytes(&bytes) {
+                    source.reprocess_corrupt_asset(path.path());
+                    return Err(ReadAssetBytesError::CorruptProcessedAsset(
+                        path.path().to_path_buf(),
+                    ));
+                }
+                processed_info.full_hash
+            }
+            None => Default::default(),
+        };
         self.loader_dependencies.insert(path.clone_owned(), hash);
         Ok(bytes)
     }
@@ -701,4 +738,7 @@ pub enum ReadAssetBytesError {
     },
     #[error("The LoadContext for this read_asset_bytes call requires hash metadata, but it was not provided. This is likely an internal implementation error.")]
     MissingAssetHash,
+    /// The processed asset bytes don't match the hash recorded when they were processed.
+    #[error("Processed asset at `{}` is corrupt: its bytes don't match the hash recorded when it was processed", _0.display())]
+    CorruptProcessedAsset(PathBuf),
 }
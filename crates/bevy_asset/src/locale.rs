@@ -0,0 +1,221 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{io::ErasedAssetReader, AssetPath, AssetServer};
+use bevy_ecs::{change_detection::DetectChanges, resource::Resource, system::Res};
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_tasks::IoTaskPool;
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+/// The languages assets are localized in, from the most to the least preferred.
+///
+/// When loading `ui/title.png`, the [`AssetServer`] first looks for a localized version of the
+/// asset in each language, before falling back to `ui/title.png` itself. For the `fr` language,
+/// these are `ui/title.fr.png` and `lang/fr/ui/title.png`, in this order.
+///
+/// When this resource changes, the loaded assets that have a localized version in the previous or
+/// the new languages are reloaded.
+///
+/// ```
+/// # use bevy_asset::Locale;
+/// let locale = Locale::new("fr-CA");
+/// // Assets localized for French in general are used when there is none for Canadian French.
+/// assert_eq!(locale.languages(), ["fr-CA", "fr"]);
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Locale {
+    languages: Vec<String>,
+}
+
+impl Locale {
+    /// Creates a locale for the language tag `language`, e.g. `en-US`, falling back to the tags
+    /// with fewer subtags, e.g. `en`.
+    pub fn new(language: impl Into<String>) -> Self {
+        let language = language.into();
+        let mut languages = Vec::new();
+        let mut tag = language.as_str();
+        while !tag.is_empty() {
+            languages.push(tag.to_string());
+            tag = tag.rsplit_once('-').map_or("", |(parent, _)| parent);
+        }
+        Self { languages }
+    }
+
+    /// Adds `language` as a fallback, used when an asset is localized in none of the languages
+    /// before it.
+    pub fn with_fallback(mut self, language: impl Into<String>) -> Self {
+        self.languages.push(language.into());
+        self
+    }
+
+    /// Returns the languages of this locale, from the most to the least preferred.
+    pub fn languages(&self) -> &[String] {
+        &self.languages
+    }
+}
+
+/// The languages the [`AssetServer`] loads localized assets in, and the localized versions found
+/// in them.
+#[derive(Default)]
+pub(crate) struct Localization {
+    pub(crate) languages: Vec<String>,
+    /// The localized version of each asset path looked up, without label, or [`None`] if it has
+    /// none. Cleared when the languages change, or when assets are added or removed.
+    pub(crate) paths: HashMap<AssetPath<'static>, Option<PathBuf>>,
+}
+
+/// Returns the paths of the localized versions of the asset at `path`, in the order they are
+/// looked up.
+pub(crate) fn localized_paths<'a>(
+    path: &'a Path,
+    languages: &'a [String],
+) -> impl Iterator<Item = PathBuf> + 'a {
+    languages.iter().flat_map(move |language| {
+        let mut file_name = OsString::new();
+        if let Some(stem) = path.file_stem() {
+            file_name.push(stem);
+            file_name.push(".");
+            file_name.push(language);
+            if let Some(extension) = path.extension() {
+                file_name.push(".");
+                file_name.push(extension);
+            }
+        }
+        let suffixed = (!file_name.is_empty()).then(|| path.with_file_name(file_name));
+        let in_folder = Path::new("lang").join(language).join(path);
+        suffixed.into_iter().chain([in_folder])
+    })
+}
+
+/// Returns the paths of the assets whose localized versions in `languages` could be at `path`,
+/// the inverse of [`localized_paths`].
+pub(crate) fn unlocalized_paths(path: &Path, languages: &[String]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for language in languages {
+        if let Ok(unlocalized) = path.strip_prefix(Path::new("lang").join(language)) {
+            paths.push(unlocalized.to_path_buf());
+        }
+        let (Some(stem), extension) = (
+            path.file_stem().and_then(OsStr::to_str),
+            path.extension().and_then(OsStr::to_str),
+        ) else {
+            continue;
+        };
+        // `title.fr` localizes `title`.
+        if extension == Some(language.as_str()) {
+            paths.push(path.with_file_name(stem));
+        }
+        // `title.fr.png` localizes `title.png`.
+        if let Some(extension) = extension
+            && let Some(stem) = stem.strip_suffix(language.as_str())
+            && let Some(stem) = stem.strip_suffix('.')
+            && !stem.is_empty()
+        {
+            paths.push(path.with_file_name(format!("{stem}.{extension}")));
+        }
+    }
+    paths
+}
+
+/// Returns the path of the localized version of the asset at `path` in `reader`, or [`None`] if
+/// it has none.
+pub(crate) async fn find_localized_path(
+    reader: &dyn ErasedAssetReader,
+    path: &Path,
+    languages: &[String],
+) -> Option<PathBuf> {
+    for localized in localized_paths(path, languages) {
+        if reader.exists(&localized).await {
+            return Some(localized);
+        }
+    }
+    None
+}
+
+/// Applies the changes of the [`Locale`] to the [`AssetServer`], reloading the assets whose
+/// localized version changed.
+pub fn update_asset_locale(locale: Res<Locale>, server: Res<AssetServer>) {
+    if !locale.is_changed() {
+        return;
+    }
+    let previous = server.set_languages(locale.languages.clone());
+    if previous == locale.languages {
+        return;
+    }
+
+    let paths = server
+        .read_infos()
+        .paths()
+        .map(|path| path.without_label().into_owned())
+        .collect::<HashSet<_>>();
+    if paths.is_empty() {
+        return;
+    }
+    // Assets are reloaded if they were localized before, or if they are localized now.
+    let mut languages = previous;
+    languages.extend(locale.languages.iter().cloned());
+    let server = server.clone();
+    IoTaskPool::get()
+        .spawn(async move {
+            for path in paths {
+                let Ok(reader) = server.reader(path.source()) else {
+                    continue;
+                };
+                if find_localized_path(reader, path.path(), &languages)
+                    .await
+                    .is_some()
+                {
+                    server.reload(path);
+                }
+            }
+        })
+        .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localized_paths_are_looked_up_in_order() {
+        let locale = Locale::new("fr-CA").with_fallback("en");
+        assert_eq!(locale.languages(), ["fr-CA", "fr", "en"]);
+        let paths: Vec<PathBuf> =
+            localized_paths(Path::new("ui/title.png"), locale.languages()).collect();
+        assert_eq!(
+            paths,
+            [
+                "ui/title.fr-CA.png",
+                "lang/fr-CA/ui/title.png",
+                "ui/title.fr.png",
+                "lang/fr/ui/title.png",
+                "ui/title.en.png",
+                "lang/en/ui/title.png",
+            ]
+            .map(PathBuf::from)
+        );
+    }
+
+    #[test]
+    fn localized_paths_are_traced_back_to_their_asset() {
+        let languages = Locale::new("fr-CA").languages().to_vec();
+        for path in ["ui/title.png", "ui/title", "ui/title.en.png"].map(Path::new) {
+            for localized in localized_paths(path, &languages) {
+                assert!(
+                    unlocalized_paths(&localized, &languages).contains(&path.to_path_buf()),
+                    "{} doesn't trace back to {}",
+                    localized.display(),
+                    path.display()
+                );
+            }
+        }
+        assert!(unlocalized_paths(Path::new("ui/title.en.png"), &languages).is_empty());
+        assert!(unlocalized_paths(Path::new("ui/.fr.png"), &languages).is_empty());
+    }
+}
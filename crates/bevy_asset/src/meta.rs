@@ -14,6 +14,7 @@ use crate::{
 use downcast_rs::{impl_downcast, Downcast};
 use ron::ser::PrettyConfig;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use tracing::error;
 
 pub const META_FORMAT_VERSION: &str = "1.0";
@@ -86,6 +87,24 @@ pub struct ProcessedInfo {
     pub full_hash: AssetHash,
     /// Information about the "process dependencies" used to process this asset.
     pub process_dependencies: Vec<ProcessDependencyInfo>,
+    /// A hash of the processed asset bytes, if they are stored content-addressed at
+    /// [`processed_content_path`] instead of at the path of the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<AssetHash>,
+    /// A hash of the processed asset bytes, which is checked when they are read to detect processed
+    /// assets that were corrupted after being processed, e.g. by a partially restored cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed_hash: Option<AssetHash>,
+}
+
+impl ProcessedInfo {
+    /// Returns `true` if `bytes` hash to the processed bytes recorded in this info. Assets processed
+    /// without recording their hash are assumed to be intact.
+    pub(crate) fn matches_processed_bytes(&self, bytes: &[u8]) -> bool {
+        self.processed_hash
+            .or(self.content_hash)
+            .is_none_or(|hash| get_content_hash(bytes) == hash)
+    }
 }
 
 /// Information about a dependency used to process an asset. This is used to determine whether an asset's "process dependency"
@@ -257,6 +276,22 @@ pub(crate) fn loader_settings_meta_transform<S: Settings>(
 
 pub type AssetHash = [u8; 32];
 
+/// The directory of processed asset sources holding content-addressed processed assets.
+///
+/// See [`AssetProcessor::set_content_addressed`](crate::processor::AssetProcessor::set_content_addressed).
+pub const PROCESSED_CONTENT_DIRECTORY: &str = ".content";
+
+/// Returns the path, relative to the root of the processed asset source, of the processed asset
+/// bytes with the given content `hash`.
+pub fn processed_content_path(hash: &AssetHash) -> PathBuf {
+    Path::new(PROCESSED_CONTENT_DIRECTORY).join(blake3::Hash::from_bytes(*hash).to_hex().as_str())
+}
+
+/// Returns the content hash of processed asset bytes.
+pub(crate) fn get_content_hash(bytes: &[u8]) -> AssetHash {
+    *blake3::hash(bytes).as_bytes()
+}
+
 /// NOTE: changing the hashing logic here is a _breaking change_ that requires a [`META_FORMAT_VERSION`] bump.
 pub(crate) async fn get_asset_hash(
     meta_bytes: &[u8],
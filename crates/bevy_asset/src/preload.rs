@@ -0,0 +1,218 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{AssetPath, AssetServer, Handle, LoadedUntypedAsset, RecursiveDependencyLoadState};
+use bevy_ecs::{
+    message::{Message, MessageWriter},
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// A named group of assets to load together, such as all the assets of a level.
+///
+/// Start loading a manifest with [`AssetPreloads::preload`], then follow its [`LoadingProgress`]
+/// to display a loading screen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreloadManifest {
+    /// The name of the manifest, used to query its progress.
+    pub name: Cow<'static, str>,
+    /// The paths of the assets to load.
+    pub paths: Vec<AssetPath<'static>>,
+}
+
+impl PreloadManifest {
+    /// Creates an empty manifest with the given `name`.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Adds the asset at `path` to the manifest.
+    pub fn with_path(mut self, path: impl Into<AssetPath<'static>>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+}
+
+/// The aggregate loading progress of a [`PreloadManifest`].
+///
+/// An asset is only counted as loaded once its recursive dependencies are loaded too.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+pub struct LoadingProgress {
+    /// The number of assets that are loaded, along with their recursive dependencies.
+    pub loaded: usize,
+    /// The number of assets that failed to load, or that have a dependency that failed to load.
+    pub failed: usize,
+    /// The number of assets in the manifest.
+    pub total: usize,
+    /// The number of bytes read by the loaders of the assets in the manifest so far.
+    ///
+    /// This doesn't include the bytes of the dependencies of these assets. See
+    /// [`AssetServer::get_bytes_read`].
+    pub bytes: u64,
+}
+
+impl LoadingProgress {
+    /// Returns the fraction of assets that finished loading, successfully or not, between `0.0` and
+    /// `1.0`. An empty manifest is always done loading.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+
+    /// Returns `true` if all assets finished loading, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed >= self.total
+    }
+}
+
+/// A [`Message`] emitted when the [`LoadingProgress`] of a [`PreloadManifest`] changes.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct PreloadProgressEvent {
+    /// The name of the manifest.
+    pub name: Cow<'static, str>,
+    /// The new progress of the manifest.
+    pub progress: LoadingProgress,
+}
+
+#[derive(Debug)]
+struct Preload {
+    paths: Vec<AssetPath<'static>>,
+    handles: Vec<Handle<LoadedUntypedAsset>>,
+    progress: LoadingProgress,
+    /// Whether a [`PreloadProgressEvent`] has been emitted for the current progress.
+    reported: bool,
+}
+
+/// Tracks the loading of [`PreloadManifest`]s.
+///
+/// The handles of the assets of a manifest are kept alive until the manifest is
+/// [released](AssetPreloads::release).
+///
+/// ```
+/// # use bevy_asset::prelude::*;
+/// # use bevy_asset::{AssetPreloads, PreloadManifest, PreloadProgressEvent};
+/// # use bevy_ecs::prelude::*;
+/// fn start_loading_level(asset_server: Res<AssetServer>, mut preloads: ResMut<AssetPreloads>) {
+///     let manifest = PreloadManifest::new("level_1")
+///         .with_path("levels/1.scn.ron")
+///         .with_path("music/level_1.ogg");
+///     preloads.preload(&asset_server, manifest);
+/// }
+///
+/// fn update_loading_bar(mut progress_events: MessageReader<PreloadProgressEvent>) {
+///     for event in progress_events.read() {
+///         println!("{}: {:.0}%", event.name, event.progress.fraction() * 100.0);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct AssetPreloads {
+    preloads: HashMap<Cow<'static, str>, Preload>,
+}
+
+impl AssetPreloads {
+    /// Starts loading the assets of `manifest`.
+    ///
+    /// If a manifest with the same name is already loading, it is replaced.
+    pub fn preload(&mut self, asset_server: &AssetServer, manifest: PreloadManifest) {
+        let handles = manifest
+            .paths
+            .iter()
+            .map(|path| asset_server.load_untyped(path))
+            .collect();
+        let progress = LoadingProgress {
+            total: manifest.paths.len(),
+            ..Default::default()
+        };
+        self.preloads.insert(
+            manifest.name,
+            Preload {
+                paths: manifest.paths,
+                handles,
+                progress,
+                reported: false,
+            },
+        );
+    }
+
+    /// Returns the progress of the manifest with the given `name`, or [`None`] if it isn't
+    /// preloaded.
+    pub fn progress(&self, name: &str) -> Option<LoadingProgress> {
+        self.preloads.get(name).map(|preload| preload.progress)
+    }
+
+    /// Returns the handles of the assets of the manifest with the given `name`.
+    ///
+    /// The handles point to [`LoadedUntypedAsset`]s, which hold the handles of the actual assets once
+    /// they are loaded.
+    pub fn handles(&self, name: &str) -> Option<&[Handle<LoadedUntypedAsset>]> {
+        self.preloads
+            .get(name)
+            .map(|preload| preload.handles.as_slice())
+    }
+
+    /// Stops tracking the manifest with the given `name`, and drops the handles of its assets.
+    ///
+    /// Returns `true` if the manifest was preloaded.
+    pub fn release(&mut self, name: &str) -> bool {
+        self.preloads.remove(name).is_some()
+    }
+
+    /// Iterates over the names and progress of the preloaded manifests.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, LoadingProgress)> {
+        self.preloads
+            .iter()
+            .map(|(name, preload)| (name.as_ref(), preload.progress))
+    }
+}
+
+/// Updates the [`LoadingProgress`] of [`AssetPreloads`], and emits [`PreloadProgressEvent`]s when it
+/// changes.
+pub fn update_preload_progress(
+    asset_server: Res<AssetServer>,
+    mut preloads: ResMut<AssetPreloads>,
+    mut progress_events: MessageWriter<PreloadProgressEvent>,
+) {
+    for (name, preload) in preloads.preloads.iter_mut() {
+        if preload.reported && preload.progress.is_finished() {
+            continue;
+        }
+
+        let mut progress = LoadingProgress {
+            total: preload.handles.len(),
+            ..Default::default()
+        };
+        for (path, handle) in preload.paths.iter().zip(&preload.handles) {
+            match asset_server.get_recursive_dependency_load_state(handle) {
+                Some(RecursiveDependencyLoadState::Loaded) => progress.loaded += 1,
+                Some(RecursiveDependencyLoadState::Failed(_)) => progress.failed += 1,
+                _ => {}
+            }
+            // The bytes are tracked on the asset actually read from disk, which is the base asset
+            // for labeled paths.
+            progress.bytes += asset_server
+                .get_path_ids(path.without_label())
+                .into_iter()
+                .filter_map(|id| asset_server.get_bytes_read(id))
+                .max()
+                .unwrap_or(0);
+        }
+
+        if !preload.reported || progress != preload.progress {
+            preload.progress = progress;
+            preload.reported = true;
+            progress_events.write(PreloadProgressEvent {
+                name: name.clone(),
+                progress,
+            });
+        }
+    }
+}
@@ -10,7 +10,7 @@ use bevy_ecs::error::BevyError;
 use bevy_platform::collections::HashSet;
 use bevy_tasks::BoxedFuture;
 use futures_lite::{AsyncReadExt, AsyncWriteExt};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::error;
 
@@ -124,7 +124,33 @@ impl Default for FileTransactionLogFactory {
         let base_path = crate::io::file::get_base_path();
         #[cfg(target_arch = "wasm32")]
         let base_path = PathBuf::new();
-        let file_path = base_path.join(LOG_PATH);
+        Self::in_base_path(base_path)
+    }
+}
+
+impl FileTransactionLogFactory {
+    /// Creates a factory storing the log at its default location, `imported_assets/log`, within
+    /// the given project root. See [`AssetPlugin::base_path`](crate::AssetPlugin::base_path).
+    pub fn in_base_path(base_path: impl AsRef<Path>) -> Self {
+        let file_path = base_path.as_ref().join(LOG_PATH);
+        Self { file_path }
+    }
+
+    /// Creates a factory storing the log in a `log` file next to the given processed asset folder,
+    /// mirroring the default layout of `imported_assets/Default` and `imported_assets/log`.
+    ///
+    /// Relative paths are resolved the same way as
+    /// [`AssetPlugin::processed_file_path`](crate::AssetPlugin::processed_file_path).
+    pub fn next_to_processed_path(processed_path: impl AsRef<Path>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let base_path = crate::io::file::get_base_path();
+        #[cfg(target_arch = "wasm32")]
+        let base_path = PathBuf::new();
+        let processed_path = base_path.join(processed_path);
+        let file_path = processed_path
+            .parent()
+            .unwrap_or(&processed_path)
+            .join("log");
         Self { file_path }
     }
 }
@@ -47,11 +47,12 @@ pub use process::*;
 use crate::{
     io::{
         AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
-        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError,
+        AssetSources, AssetWriterError, ErasedAssetReader, MissingAssetSourceError, Writer,
     },
     meta::{
-        get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
-        AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
+        get_asset_hash, get_content_hash, get_full_asset_hash, processed_content_path, AssetAction,
+        AssetActionMinimal, AssetHash, AssetMeta, AssetMetaDyn, AssetMetaMinimal, ProcessedInfo,
+        ProcessedInfoMinimal, PROCESSED_CONTENT_DIRECTORY,
     },
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
     MissingAssetLoaderForExtensionError, UnapprovedPathMode, WriteDefaultMetaError,
@@ -63,7 +64,12 @@ use bevy_platform::{
     sync::{PoisonError, RwLock},
 };
 use bevy_tasks::IoTaskPool;
-use futures_io::ErrorKind;
+use core::{
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+};
+use futures_io::{AsyncWrite, ErrorKind};
 use futures_lite::{AsyncWriteExt, StreamExt};
 use futures_util::{select_biased, FutureExt};
 use std::{
@@ -114,6 +120,8 @@ pub struct AssetProcessorData {
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
     sources: Arc<AssetSources>,
+    /// Whether processed assets are stored content-addressed.
+    content_addressed: AtomicBool,
 }
 
 /// The current state of processing, including the overall state and the state of all assets.
@@ -128,6 +136,10 @@ pub(crate) struct ProcessingState {
     finished_receiver: async_broadcast::Receiver<()>,
     /// The current state of the assets.
     asset_infos: async_lock::RwLock<ProcessorAssetInfos>,
+    /// The channel of the assets to reprocess, as their processed bytes were found corrupt while
+    /// they were loaded.
+    corrupt_sender: async_channel::Sender<AssetPath<'static>>,
+    corrupt_receiver: async_channel::Receiver<AssetPath<'static>>,
 }
 
 #[derive(Default)]
@@ -208,6 +220,33 @@ impl AssetProcessor {
         &self.data.sources
     }
 
+    /// Sets whether processed assets are stored content-addressed.
+    ///
+    /// When enabled, the bytes of each processed asset are written to the
+    /// [`PROCESSED_CONTENT_DIRECTORY`] of its processed source, named after their hash, and the
+    /// meta file written at the path of the asset records that hash. Identical processed assets,
+    /// such as the same texture copied into several folders, are then only stored once, and
+    /// reprocessing an asset into the same bytes leaves its processed files untouched.
+    ///
+    /// The [`AssetServer`] follows the recorded hash when loading processed assets, so this is
+    /// transparent to loaders, but requires reading meta files, which is the default for processed
+    /// assets. Stored bytes that are no longer used by any asset are removed the next time the
+    /// processor starts.
+    ///
+    /// This should be set before the processor starts, in the `Startup` schedule.
+    pub fn set_content_addressed(&self, content_addressed: bool) {
+        self.data
+            .content_addressed
+            .store(content_addressed, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if processed assets are stored content-addressed.
+    ///
+    /// See [`AssetProcessor::set_content_addressed`].
+    pub fn is_content_addressed(&self) -> bool {
+        self.data.content_addressed.load(Ordering::Relaxed)
+    }
+
     /// Logs an unrecoverable error. On the next run of the processor, all assets will be regenerated. This should only be used as a last resort.
     /// Every call to this should be considered with scrutiny and ideally replaced with something more granular.
     async fn log_unrecoverable(&self) {
@@ -285,6 +324,7 @@ impl AssetProcessor {
 
                 debug!("Listening for changes to source assets");
                 processor.spawn_source_change_event_listeners(&new_task_sender);
+                processor.spawn_corrupt_asset_listener(&new_task_sender);
             })
             .detach();
     }
@@ -320,6 +360,7 @@ impl AssetProcessor {
                         let Ok(source) = processor.get_source(source_id.clone()) else {
                             return;
                         };
+                        source.handle_case_insensitive_event(&event, false);
                         processor
                             .handle_asset_source_event(source, event, &sender)
                             .await;
@@ -329,6 +370,43 @@ impl AssetProcessor {
         }
     }
 
+    /// Spawns a listener that reprocesses the assets whose processed bytes were found corrupt while
+    /// they were loaded.
+    fn spawn_corrupt_asset_listener(
+        &self,
+        sender: &async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
+    ) {
+        let receiver = self.data.processing_state.corrupt_receiver.clone();
+        let processor = self.clone();
+        let sender = sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                while let Ok(asset_path) = receiver.recv().await {
+                    warn!("Reprocessing {asset_path} because its processed bytes don't match their hash");
+                    // Forgetting the processed info prevents the asset from being skipped as
+                    // unchanged.
+                    if let Some(info) = processor
+                        .data
+                        .processing_state
+                        .asset_infos
+                        .write()
+                        .await
+                        .get_mut(&asset_path)
+                    {
+                        info.processed_info = None;
+                    }
+                    let task = (
+                        asset_path.source().clone_owned(),
+                        asset_path.path().to_path_buf(),
+                    );
+                    if sender.send(task).await.is_err() {
+                        return;
+                    }
+                }
+            })
+            .detach();
+    }
+
     /// Executes all tasks that come through `receiver`, and updates the processor's overall state
     /// based on task starts and ends.
     ///
@@ -914,6 +992,11 @@ impl AssetProcessor {
                 asset_infos.get_or_insert(AssetPath::from(path).with_source(source.id()));
             }
 
+            // Content-addressed bytes aren't assets: they are kept as long as an asset uses them.
+            let (content_paths, processed_paths): (Vec<_>, Vec<_>) = processed_paths
+                .into_iter()
+                .partition(|path| path.starts_with(PROCESSED_CONTENT_DIRECTORY));
+
             for path in processed_paths {
                 let mut dependencies = Vec::new();
                 let asset_path = AssetPath::from(path).with_source(source.id());
@@ -927,6 +1010,25 @@ impl AssetProcessor {
                                         minimal.processed_info
                                     );
 
+                                    if let Some(processed_info) = &minimal.processed_info
+                                        && !self
+                                            .processed_asset_is_intact(
+                                                source,
+                                                &asset_path,
+                                                processed_info,
+                                            )
+                                            .await
+                                    {
+                                        // Leaving the processed info empty reprocesses the asset.
+                                        warn!("Reprocessing {asset_path} because its processed bytes don't match their hash");
+                                        self.remove_processed_asset_and_meta(
+                                            source,
+                                            asset_path.path(),
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+
                                     if let Some(processed_info) = &minimal.processed_info {
                                         for process_dependency_info in
                                             &processed_info.process_dependencies
@@ -959,6 +1061,22 @@ impl AssetProcessor {
                     asset_infos.add_dependent(&dependency, asset_path.clone());
                 }
             }
+
+            let used_content_paths = asset_infos
+                .infos
+                .iter()
+                .filter(|(asset_path, _)| *asset_path.source() == source.id())
+                .filter_map(|(_, info)| info.processed_info.as_ref()?.content_hash)
+                .map(|content_hash| processed_content_path(&content_hash))
+                .collect::<HashSet<_>>();
+            for content_path in content_paths {
+                if !used_content_paths.contains(&content_path) {
+                    trace!("Removing unused processed content {content_path:?}");
+                    if let Err(err) = processed_writer.remove(&content_path).await {
+                        warn!("Failed to remove unused processed content {content_path:?}: {err}");
+                    }
+                }
+            }
         }
 
         self.data
@@ -984,6 +1102,37 @@ impl AssetProcessor {
             .await;
     }
 
+    /// Returns `true` if the processed bytes of an asset still hash to the hash recorded when it
+    /// was processed. Processed assets can be corrupted after being written, e.g. when a cache
+    /// directory is only partially restored from a backup.
+    async fn processed_asset_is_intact(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        processed_info: &ProcessedInfo,
+    ) -> bool {
+        let Some(expected_hash) = processed_info
+            .processed_hash
+            .or(processed_info.content_hash)
+        else {
+            return true;
+        };
+        let path = match processed_info.content_hash {
+            Some(content_hash) => processed_content_path(&content_hash),
+            None => asset_path.path().to_path_buf(),
+        };
+        let Some(reader) = source.ungated_processed_reader() else {
+            return true;
+        };
+        let Ok(mut reader) = reader.read(&path).await else {
+            return false;
+        };
+        // The processed bytes are hashed without any meta, like `get_content_hash` does.
+        get_asset_hash(&[], &mut reader)
+            .await
+            .is_ok_and(|hash| hash == expected_hash)
+    }
+
     async fn clean_empty_processed_ancestor_folders(&self, source: &AssetSource, path: &Path) {
         // As a safety precaution don't delete absolute paths to avoid deleting folders outside of the destination folder
         if path.is_absolute() {
@@ -1116,6 +1265,8 @@ impl AssetProcessor {
             hash: new_hash,
             full_hash: new_hash,
             process_dependencies: Vec::new(),
+            content_hash: None,
+            processed_hash: None,
         };
 
         {
@@ -1160,6 +1311,7 @@ impl AssetProcessor {
         // Directly writing to the asset destination in the processor necessitates this behavior
         // TODO: this class of failure can be recovered via re-processing + smarter log validation that allows for duplicate transactions in the event of failures
         self.log_begin_processing(asset_path).await;
+        let content_addressed = self.is_content_addressed();
         if let Some(processor) = processor {
             // Unwrap is ok since we have a processor, so the `AssetAction` must have been
             // `AssetAction::Process` (which includes its settings).
@@ -1176,7 +1328,20 @@ impl AssetProcessor {
             // reads or not.
             let reader_for_process = reader.read(path).await.map_err(reader_err)?;
 
-            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
+            // Content-addressed assets are hashed before being stored, so they are processed in
+            // memory.
+            let mut content = Vec::new();
+            let mut file_writer = if content_addressed {
+                None
+            } else {
+                Some(HashingWriter::new(
+                    processed_writer.write(path).await.map_err(writer_err)?,
+                ))
+            };
+            let writer: &mut Writer = match &mut file_writer {
+                Some(file_writer) => file_writer,
+                None => &mut content,
+            };
             let mut processed_meta = {
                 let mut context = ProcessContext::new(
                     self,
@@ -1198,12 +1363,22 @@ impl AssetProcessor {
             };
 
             writer
-                .flush()
+                .close()
                 .await
                 .map_err(|e| ProcessError::AssetWriterError {
                     path: asset_path.clone(),
                     err: AssetWriterError::Io(e),
                 })?;
+            new_processed_info.processed_hash = match file_writer {
+                Some(file_writer) => Some(file_writer.finish()),
+                None => {
+                    new_processed_info.content_hash = Some(
+                        self.write_processed_content(source, asset_path, &content)
+                            .await?,
+                    );
+                    new_processed_info.content_hash
+                }
+            };
 
             let full_hash = get_full_asset_hash(
                 new_hash,
@@ -1216,32 +1391,108 @@ impl AssetProcessor {
             *processed_meta.processed_info_mut() = Some(new_processed_info.clone());
             let meta_bytes = processed_meta.serialize();
 
-            processed_writer
-                .write_meta_bytes(path, &meta_bytes)
-                .await
-                .map_err(writer_err)?;
+            self.write_processed_meta(source, asset_path, &meta_bytes, content_addressed)
+                .await?;
         } else {
             // See the reasoning for processing why it's ok to do a second read here.
             let mut reader_for_copy = reader.read(path).await.map_err(reader_err)?;
-            let mut writer = processed_writer.write(path).await.map_err(writer_err)?;
-            futures_lite::io::copy(&mut reader_for_copy, &mut writer)
-                .await
-                .map_err(|err| ProcessError::AssetWriterError {
-                    path: asset_path.clone_owned(),
-                    err: err.into(),
-                })?;
+            if content_addressed {
+                let mut content = Vec::new();
+                reader_for_copy
+                    .read_to_end(&mut content)
+                    .await
+                    .map_err(|err| reader_err(err.into()))?;
+                new_processed_info.content_hash = Some(
+                    self.write_processed_content(source, asset_path, &content)
+                        .await?,
+                );
+                new_processed_info.processed_hash = new_processed_info.content_hash;
+            } else {
+                let mut writer =
+                    HashingWriter::new(processed_writer.write(path).await.map_err(writer_err)?);
+                futures_lite::io::copy(&mut reader_for_copy, &mut writer)
+                    .await
+                    .map_err(|err| ProcessError::AssetWriterError {
+                        path: asset_path.clone_owned(),
+                        err: err.into(),
+                    })?;
+                writer
+                    .close()
+                    .await
+                    .map_err(|err| ProcessError::AssetWriterError {
+                        path: asset_path.clone_owned(),
+                        err: err.into(),
+                    })?;
+                new_processed_info.processed_hash = Some(writer.finish());
+            }
             *source_meta.processed_info_mut() = Some(new_processed_info.clone());
             let meta_bytes = source_meta.serialize();
-            processed_writer
-                .write_meta_bytes(path, &meta_bytes)
-                .await
-                .map_err(writer_err)?;
+            self.write_processed_meta(source, asset_path, &meta_bytes, content_addressed)
+                .await?;
         }
         self.log_end_processing(asset_path).await;
 
         Ok(ProcessResult::Processed(new_processed_info))
     }
 
+    /// Stores the processed bytes of an asset content-addressed, unless identical bytes are already
+    /// stored, and returns their hash.
+    async fn write_processed_content(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        content: &[u8],
+    ) -> Result<AssetHash, ProcessError> {
+        let content_hash = get_content_hash(content);
+        let content_path = processed_content_path(&content_hash);
+        let processed_writer = source.processed_writer()?;
+        let already_stored = match source.ungated_processed_reader() {
+            Some(reader) => reader.read(&content_path).await.is_ok(),
+            None => false,
+        };
+        if !already_stored {
+            processed_writer
+                .write_bytes(&content_path, content)
+                .await
+                .map_err(|err| ProcessError::AssetWriterError {
+                    path: asset_path.clone(),
+                    err,
+                })?;
+        }
+        // Remove the bytes processed before content addressing was enabled, if any. This fails
+        // when there is nothing to remove, which is fine.
+        let _ = processed_writer.remove(asset_path.path()).await;
+        Ok(content_hash)
+    }
+
+    /// Writes the processed meta of an asset. Content-addressed assets skip writing meta that didn't
+    /// change, so that reprocessing an asset into the same output doesn't trigger reloads.
+    async fn write_processed_meta(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        meta_bytes: &[u8],
+        content_addressed: bool,
+    ) -> Result<(), ProcessError> {
+        if content_addressed
+            && let Some(reader) = source.ungated_processed_reader()
+            && reader
+                .read_meta_bytes(asset_path.path())
+                .await
+                .is_ok_and(|current_meta_bytes| current_meta_bytes == meta_bytes)
+        {
+            return Ok(());
+        }
+        source
+            .processed_writer()?
+            .write_meta_bytes(asset_path.path(), meta_bytes)
+            .await
+            .map_err(|err| ProcessError::AssetWriterError {
+                path: asset_path.clone(),
+                err,
+            })
+    }
+
     async fn validate_transaction_log_and_recover(&self) {
         let log_factory = self
             .data
@@ -1337,6 +1588,50 @@ impl AssetProcessor {
     }
 }
 
+/// A [`Writer`] hashing the processed bytes written to it, so that they can be verified when they
+/// are read back.
+struct HashingWriter {
+    writer: Box<Writer>,
+    hasher: blake3::Hasher,
+}
+
+impl HashingWriter {
+    fn new(writer: Box<Writer>) -> Self {
+        Self {
+            writer,
+            hasher: blake3::Hasher::new(),
+        }
+    }
+
+    /// Returns the hash of the bytes written, which is the same as their [`get_content_hash`].
+    fn finish(self) -> AssetHash {
+        *self.hasher.finalize().as_bytes()
+    }
+}
+
+impl AsyncWrite for HashingWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let this = &mut *self;
+        let result = Pin::new(&mut this.writer).poll_write(cx, buf);
+        if let Poll::Ready(Ok(written)) = result {
+            this.hasher.update(&buf[..written]);
+        }
+        result
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<futures_io::Result<()>> {
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<futures_io::Result<()>> {
+        Pin::new(&mut self.writer).poll_close(cx)
+    }
+}
+
 impl AssetProcessorData {
     /// Initializes a new [`AssetProcessorData`] using the given [`AssetSources`].
     pub(crate) fn new(sources: Arc<AssetSources>, processing_state: Arc<ProcessingState>) -> Self {
@@ -1346,6 +1641,7 @@ impl AssetProcessorData {
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
             processors: Default::default(),
+            content_addressed: AtomicBool::new(false),
         }
     }
 
@@ -1396,6 +1692,7 @@ impl ProcessingState {
         // not block if there was older state present.
         initialized_sender.set_overflow(true);
         finished_sender.set_overflow(true);
+        let (corrupt_sender, corrupt_receiver) = async_channel::unbounded();
 
         Self {
             state: async_lock::RwLock::new(ProcessorState::Initializing),
@@ -1404,9 +1701,18 @@ impl ProcessingState {
             finished_sender,
             finished_receiver,
             asset_infos: Default::default(),
+            corrupt_sender,
+            corrupt_receiver,
         }
     }
 
+    /// Queues the asset at `asset_path` to be processed again, as its processed bytes don't match
+    /// the hash recorded when it was processed.
+    pub(crate) fn reprocess_corrupt_asset(&self, asset_path: AssetPath<'static>) {
+        // The channel is unbounded, and it is only closed when the processor is dropped.
+        let _ = self.corrupt_sender.try_send(asset_path);
+    }
+
     /// Sets the overall state of processing and broadcasts appropriate events.
     async fn set_state(&self, state: ProcessorState) {
         let mut state_guard = self.state.write().await;
@@ -1681,6 +1987,8 @@ impl ProcessorAssetInfos {
                         hash: AssetHash::default(),
                         full_hash: AssetHash::default(),
                         process_dependencies: vec![],
+                        content_hash: None,
+                        processed_hash: None,
                     });
                     self.add_dependent(dependency.path(), asset_path.to_owned());
                 }
@@ -28,6 +28,7 @@ use crate::{
         AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceBuilders, AssetSourceEvent,
         AssetSourceId, AssetWatcher, PathStream, Reader,
     },
+    meta::{get_content_hash, processed_content_path, ProcessedInfoMinimal},
     processor::{
         AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
         ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
@@ -38,8 +39,8 @@ use crate::{
         CoolTextRon, SubText,
     },
     transformer::{AssetTransformer, TransformedAsset},
-    Asset, AssetApp, AssetLoader, AssetMode, AssetPath, AssetPlugin, LoadContext,
-    WriteDefaultMetaError,
+    Asset, AssetApp, AssetLoadError, AssetLoader, AssetMode, AssetPath, AssetPlugin, AssetServer,
+    Assets, LoadContext, LoadState, WriteDefaultMetaError,
 };
 
 #[derive(TypePath)]
@@ -400,6 +401,50 @@ fn create_app_with_asset_processor(extra_sources: &[String]) -> AppWithProcessor
     }
 }
 
+/// Creates an app processing the assets of existing dirs, e.g. the ones of an app created with
+/// [`create_app_with_asset_processor`], to test what happens when the app is restarted.
+fn restart_app_with_asset_processor(
+    source_dir: &Dir,
+    processed_dir: &Dir,
+) -> (App, Arc<RwLock<()>>) {
+    let mut app = App::new();
+    let source_gate = Arc::new(RwLock::new(()));
+
+    let source_memory_reader = LockGatedReader::new(
+        source_gate.clone(),
+        MemoryAssetReader {
+            root: source_dir.clone(),
+        },
+    );
+    let processed_memory_reader = MemoryAssetReader {
+        root: processed_dir.clone(),
+    };
+    let processed_memory_writer = MemoryAssetWriter {
+        root: processed_dir.clone(),
+    };
+
+    app.register_asset_source(
+        AssetSourceId::Default,
+        AssetSourceBuilder::new(move || Box::new(source_memory_reader.clone()))
+            .with_processed_reader(move || Box::new(processed_memory_reader.clone()))
+            .with_processed_writer(move |_| Some(Box::new(processed_memory_writer.clone()))),
+    );
+
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        AssetPlugin {
+            mode: AssetMode::Processed,
+            use_asset_processor_override: Some(true),
+            watch_for_changes_override: Some(true),
+            ..Default::default()
+        },
+    ));
+
+    set_fake_transaction_log(&mut app);
+
+    (app, source_gate)
+}
+
 fn run_app_until_finished_processing(app: &mut App, guard: RwLockWriteGuard<'_, ()>) {
     let processor = app.world().resource::<AssetProcessor>().clone();
     // We can't just wait for the processor state to be finished since we could have already
@@ -1615,41 +1660,8 @@ fn only_reprocesses_wrong_hash_on_startup() {
         4
     );
 
-    // Hand-make the app, since we need to pass in our already existing Dirs from the last app.
-    let mut app = App::new();
-    let source_gate = Arc::new(RwLock::new(()));
-
-    let source_memory_reader = LockGatedReader::new(
-        source_gate.clone(),
-        MemoryAssetReader {
-            root: default_source_dir.clone(),
-        },
-    );
-    let processed_memory_reader = MemoryAssetReader {
-        root: default_processed_dir.clone(),
-    };
-    let processed_memory_writer = MemoryAssetWriter {
-        root: default_processed_dir.clone(),
-    };
-
-    app.register_asset_source(
-        AssetSourceId::Default,
-        AssetSourceBuilder::new(move || Box::new(source_memory_reader.clone()))
-            .with_processed_reader(move || Box::new(processed_memory_reader.clone()))
-            .with_processed_writer(move |_| Some(Box::new(processed_memory_writer.clone()))),
-    );
-
-    app.add_plugins((
-        TaskPoolPlugin::default(),
-        AssetPlugin {
-            mode: AssetMode::Processed,
-            use_asset_processor_override: Some(true),
-            watch_for_changes_override: Some(true),
-            ..Default::default()
-        },
-    ));
-
-    set_fake_transaction_log(&mut app);
+    let (mut app, source_gate) =
+        restart_app_with_asset_processor(&default_source_dir, &default_processed_dir);
 
     app.init_asset::<CoolText>()
         .init_asset::<SubText>()
@@ -1841,3 +1853,173 @@ fn write_default_meta_does_not_overwrite() {
         META_TEXT
     );
 }
+
+#[test]
+fn content_addressed_processing_deduplicates_outputs() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                ..
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<AddText, CoolText>,
+        CoolTextSaver,
+    >;
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(CoolTextProcessor::new(
+            RootAssetTransformer::new(AddText("_def".into())),
+            CoolTextSaver,
+        ))
+        .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+    app.world()
+        .resource::<AssetProcessor>()
+        .set_content_addressed(true);
+
+    let guard = source_gate.write_blocking();
+
+    let path_a = Path::new("a/abc.cool.ron");
+    let path_b = Path::new("b/abc.cool.ron");
+    let source_asset = serialize_as_cool_text("abc");
+    source_dir.insert_asset_text(path_a, &source_asset);
+    source_dir.insert_asset_text(path_b, &source_asset);
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    // Both assets are processed into the same bytes, which are stored once.
+    let content_hash = get_content_hash(serialize_as_cool_text("abc_def").as_bytes());
+    assert!(processed_dir
+        .get_asset(&processed_content_path(&content_hash))
+        .is_some());
+    for path in [path_a, path_b] {
+        assert!(processed_dir.get_asset(path).is_none());
+        let meta = read_meta_as_string(&processed_dir, path);
+        let minimal: ProcessedInfoMinimal = ron::de::from_str(&meta).unwrap();
+        assert_eq!(
+            minimal.processed_info.unwrap().content_hash,
+            Some(content_hash)
+        );
+    }
+
+    // Loading the processed assets reads the content-addressed bytes.
+    let asset_server = app.world().resource::<AssetServer>().clone();
+    let handle_a = asset_server.load::<CoolText>(path_a);
+    let handle_b = asset_server.load::<CoolText>(path_b);
+    run_app_until(&mut app, |world| {
+        let assets = world.resource::<Assets<CoolText>>();
+        let a = assets.get(&handle_a)?;
+        let b = assets.get(&handle_b)?;
+        assert_eq!(a.text, "abc_def");
+        assert_eq!(b.text, "abc_def");
+        Some(())
+    });
+
+    // Both assets were loaded the same way from the same bytes, so renderers can share them.
+    let loaded_content_hash = asset_server.get_content_hash(&handle_a);
+    assert!(loaded_content_hash.is_some());
+    assert_eq!(
+        asset_server.get_content_hash(&handle_b),
+        loaded_content_hash
+    );
+}
+
+#[test]
+fn corrupt_processed_assets_are_not_loaded_and_reprocessed() {
+    let asset_path = Path::new("abc.cool.ron");
+    let source_dir;
+    let processed_dir;
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<AddText, CoolText>,
+        CoolTextSaver,
+    >;
+    let register = |app: &mut App| {
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .register_asset_processor(CoolTextProcessor::new(
+                RootAssetTransformer::new(AddText("_def".into())),
+                CoolTextSaver,
+            ))
+            .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+    };
+
+    {
+        let AppWithProcessor {
+            mut app,
+            source_gate,
+            default_source_dirs,
+            ..
+        } = create_app_with_asset_processor(&[]);
+        source_dir = default_source_dirs.source;
+        processed_dir = default_source_dirs.processed;
+        register(&mut app);
+
+        let guard = source_gate.write_blocking();
+        source_dir.insert_asset_text(asset_path, &serialize_as_cool_text("abc"));
+        run_app_until_finished_processing(&mut app, guard);
+
+        let processed = serialize_as_cool_text("abc_def");
+        let meta = read_meta_as_string(&processed_dir, asset_path);
+        let minimal: ProcessedInfoMinimal = ron::de::from_str(&meta).unwrap();
+        assert_eq!(
+            minimal.processed_info.unwrap().processed_hash,
+            Some(get_content_hash(processed.as_bytes()))
+        );
+
+        // Intact processed assets are verified before being handed to their loader, which can
+        // still seek them.
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let path = AssetPath::from_path(asset_path);
+        let (_, _, mut reader) =
+            bevy_tasks::block_on(asset_server.get_meta_loader_and_reader(&path, path.path(), None))
+                .unwrap();
+        assert!(reader.seekable().is_ok());
+        drop(reader);
+
+        // Simulate a cache that was only partially restored.
+        processed_dir.insert_asset_text(asset_path, &processed[..processed.len() / 2]);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle = asset_server.load::<CoolText>(asset_path);
+        run_app_until(&mut app, |_| match asset_server.load_state(&handle) {
+            LoadState::Failed(err) => {
+                assert!(matches!(*err, AssetLoadError::CorruptProcessedAsset { .. }));
+                Some(())
+            }
+            LoadState::Loaded => panic!("corrupt processed asset was loaded"),
+            _ => None,
+        });
+
+        // Loading the corrupt asset queued it to be reprocessed.
+        run_app_until(&mut app, |_| {
+            processed_dir
+                .get_asset(asset_path)
+                .is_some_and(|data| *data.value() == *processed.as_bytes())
+                .then_some(())
+        });
+
+        // Corrupt it again while the app isn't running, to be found on startup.
+        processed_dir.insert_asset_text(asset_path, &processed[..processed.len() / 2]);
+    }
+
+    let (mut app, source_gate) = restart_app_with_asset_processor(&source_dir, &processed_dir);
+    register(&mut app);
+    let guard = source_gate.write_blocking();
+    run_app_until_finished_processing(&mut app, guard);
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, asset_path),
+        serialize_as_cool_text("abc_def")
+    );
+}
@@ -540,7 +540,7 @@ pub async fn save_using_saver<S: AssetSaver>(
         .await
         .map_err(|err| SaveAssetError::SaverError(Arc::new(err.into())))?;
 
-    file_writer.flush().await.map_err(AssetWriterError::Io)?;
+    file_writer.close().await.map_err(AssetWriterError::Io)?;
 
     let meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
         loader: S::OutputLoader::type_path().into(),
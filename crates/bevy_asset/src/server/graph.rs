@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+use crate::{
+    AssetPath, AssetServer, DependencyLoadState, ErasedAssetIndex, LoadState,
+    RecursiveDependencyLoadState, UntypedAssetId,
+};
+use bevy_platform::collections::{HashMap, HashSet};
+
+/// A snapshot of an asset's place in the dependency graph of the [`AssetServer`].
+///
+/// This is returned by [`AssetServer::get_dependency_info`].
+#[derive(Debug, Clone)]
+pub struct AssetDependencyInfo {
+    /// The id of the asset.
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it was loaded from a path.
+    pub path: Option<AssetPath<'static>>,
+    /// The load state of the asset itself.
+    pub load_state: LoadState,
+    /// The load state of the direct dependencies of the asset.
+    pub dependency_load_state: DependencyLoadState,
+    /// The load state of the recursive dependencies of the asset.
+    pub recursive_dependency_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of the asset. This is empty until the asset is loaded.
+    pub dependencies: Vec<UntypedAssetId>,
+    /// The assets that directly depend on this asset.
+    pub dependents: Vec<UntypedAssetId>,
+    /// The number of strong handles to the asset that are still alive, including the handles held by
+    /// its dependents.
+    pub strong_handles: usize,
+}
+
+impl AssetServer {
+    /// Returns a snapshot of the dependencies, dependents and load states of the given asset `id`,
+    /// or [`None`] if the asset isn't tracked by the asset server.
+    ///
+    /// Only assets loaded by the asset server are part of the dependency graph: assets added
+    /// directly to [`Assets`](crate::Assets) aren't tracked.
+    ///
+    /// Note that finding the dependents of an asset requires visiting every asset tracked by the
+    /// asset server. This is meant for tooling and debugging, not for use every frame.
+    pub fn get_dependency_info(
+        &self,
+        id: impl Into<UntypedAssetId>,
+    ) -> Option<AssetDependencyInfo> {
+        let id = id.into();
+        let index = ErasedAssetIndex::try_from(id).ok()?;
+        let infos = self.read_infos();
+        let info = infos.get(index)?;
+        Some(AssetDependencyInfo {
+            id,
+            path: info.path.clone(),
+            load_state: info.load_state.clone(),
+            dependency_load_state: info.dep_load_state.clone(),
+            recursive_dependency_load_state: info.rec_dep_load_state.clone(),
+            dependencies: info.dependencies.iter().copied().map(Into::into).collect(),
+            dependents: infos
+                .iter()
+                .filter(|(_, other)| other.dependencies.contains(&index))
+                .map(|(other_index, _)| other_index.into())
+                .collect(),
+            strong_handles: info.strong_handle_count(),
+        })
+    }
+
+    /// Returns the recursive dependencies of the given asset `id`: its dependencies, their
+    /// dependencies, and so on. The asset itself isn't included.
+    ///
+    /// The dependencies are returned in breadth-first order, without duplicates.
+    pub fn get_recursive_dependencies(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let Ok(index) = ErasedAssetIndex::try_from(id.into()) else {
+            return Vec::new();
+        };
+        let infos = self.read_infos();
+        let mut visited = <HashSet<_>>::from_iter([index]);
+        let mut order = Vec::from([index]);
+        let mut next = 0;
+        while let Some(&index) = order.get(next) {
+            next += 1;
+            let Some(info) = infos.get(index) else {
+                continue;
+            };
+            for &dependency in &info.dependencies {
+                if visited.insert(dependency) {
+                    order.push(dependency);
+                }
+            }
+        }
+        order.into_iter().skip(1).map(Into::into).collect()
+    }
+
+    /// Returns the assets that would be unloaded if the caller's handle to the given asset `id` was
+    /// dropped: the asset itself, if the caller holds its last strong handle, along with the
+    /// recursive dependencies that are only kept alive by these assets.
+    ///
+    /// This is conservative: an asset holding several handles to the same dependency will keep that
+    /// dependency out of the result, even if it would be unloaded.
+    pub fn get_assets_unloaded_with(&self, id: impl Into<UntypedAssetId>) -> Vec<UntypedAssetId> {
+        let Ok(index) = ErasedAssetIndex::try_from(id.into()) else {
+            return Vec::new();
+        };
+        let infos = self.read_infos();
+        if infos
+            .get(index)
+            .is_none_or(|info| info.strong_handle_count() > 1)
+        {
+            return Vec::new();
+        }
+
+        let dependents = {
+            let mut dependents = HashMap::<_, Vec<_>>::default();
+            for (dependent, info) in infos.iter() {
+                for &dependency in &info.dependencies {
+                    dependents.entry(dependency).or_default().push(dependent);
+                }
+            }
+            dependents
+        };
+
+        let mut unloaded = <HashSet<_>>::from_iter([index]);
+        let mut order = Vec::from([index]);
+        let mut next = 0;
+        while let Some(&index) = order.get(next) {
+            next += 1;
+            let Some(info) = infos.get(index) else {
+                continue;
+            };
+            for &dependency in &info.dependencies {
+                if unloaded.contains(&dependency) {
+                    continue;
+                }
+                let Some(dependency_info) = infos.get(dependency) else {
+                    continue;
+                };
+                // Every dependent holds at least one handle to the dependency, so if all the strong
+                // handles are accounted for by unloaded dependents, the dependency is unloaded too.
+                let dependents = dependents
+                    .get(&dependency)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                if dependents
+                    .iter()
+                    .all(|dependent| unloaded.contains(dependent))
+                    && dependency_info.strong_handle_count() <= dependents.len()
+                {
+                    unloaded.insert(dependency);
+                    order.push(dependency);
+                }
+            }
+        }
+        order.into_iter().map(Into::into).collect()
+    }
+
+    /// Returns the ids of all the assets tracked by the asset server, including the assets that are
+    /// still loading.
+    ///
+    /// Combined with [`AssetServer::get_dependency_info`], this can be used to find assets that are
+    /// kept alive by handles outside of the dependency graph, such as leaked handles in long-running
+    /// apps.
+    pub fn get_asset_ids(&self) -> Vec<UntypedAssetId> {
+        self.read_infos()
+            .iter()
+            .map(|(index, _)| index.into())
+            .collect()
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetIndex, AssetLoadError, AssetPath, DependencyLoadState,
     ErasedAssetIndex, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
-    RecursiveDependencyLoadState, StrongHandle, UntypedHandle,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetReloadedEvent, UntypedHandle,
 };
 use alloc::{
     borrow::ToOwned,
@@ -11,10 +11,13 @@ use alloc::{
     vec::Vec,
 };
 use bevy_ecs::world::World;
-use bevy_platform::collections::{hash_map::Entry, HashMap, HashSet};
+use bevy_platform::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    time::Instant,
+};
 use bevy_tasks::Task;
 use bevy_utils::TypeIdMap;
-use core::{any::TypeId, task::Waker};
+use core::{any::TypeId, task::Waker, time::Duration};
 use crossbeam_channel::Sender;
 use either::Either;
 use thiserror::Error;
@@ -27,6 +30,8 @@ pub(crate) struct AssetInfo {
     pub(crate) load_state: LoadState,
     pub(crate) dep_load_state: DependencyLoadState,
     pub(crate) rec_dep_load_state: RecursiveDependencyLoadState,
+    /// The direct dependencies of this asset, set once it has been loaded.
+    pub(crate) dependencies: HashSet<ErasedAssetIndex>,
     loading_dependencies: HashSet<ErasedAssetIndex>,
     failed_dependencies: HashSet<ErasedAssetIndex>,
     loading_rec_dependencies: HashSet<ErasedAssetIndex>,
@@ -40,6 +45,16 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The number of bytes read by the loader of this asset, once it has been loaded.
+    pub(crate) bytes_read: Option<u64>,
+    /// The key of the content-addressed processed bytes and loader settings of this asset, once it
+    /// has been loaded. See [`AssetServer::get_content_hash`](crate::AssetServer::get_content_hash).
+    pub(crate) content_hash: Option<AssetHash>,
+    /// How long to keep this asset loaded after its last strong handle is dropped, overriding
+    /// [`AssetInfos::type_keep_alive`].
+    pub(crate) keep_alive: Option<Duration>,
+    /// Set when the keep-alive handle of this asset expired, so that its drop unloads the asset.
+    keep_alive_expired: bool,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
@@ -55,6 +70,7 @@ impl AssetInfo {
             load_state: LoadState::NotLoaded,
             dep_load_state: DependencyLoadState::NotLoaded,
             rec_dep_load_state: RecursiveDependencyLoadState::NotLoaded,
+            dependencies: HashSet::default(),
             loading_dependencies: HashSet::default(),
             failed_dependencies: HashSet::default(),
             loading_rec_dependencies: HashSet::default(),
@@ -62,10 +78,19 @@ impl AssetInfo {
             loader_dependencies: HashMap::default(),
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
+            bytes_read: None,
+            content_hash: None,
+            keep_alive: None,
+            keep_alive_expired: false,
             handle_drops_to_skip: 0,
             waiting_tasks: Vec::new(),
         }
     }
+
+    /// The number of strong handles to this asset that are still alive.
+    pub(crate) fn strong_handle_count(&self) -> usize {
+        self.weak_handle.strong_count()
+    }
 }
 
 /// Tracks statistics of the asset server.
@@ -92,7 +117,19 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, AssetIndex)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, AssetIndex, AssetPath<'static>, AssetLoadError)>,
+    pub(crate) reloaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetReloadedEvent)>,
+    /// The reload events to emit once the hot-reloaded assets finish loading.
+    /// This should only be set when watching for changes to avoid unnecessary work.
+    pub(crate) pending_reloads: HashMap<ErasedAssetIndex, UntypedAssetReloadedEvent>,
+    /// When the last change of each changed path was detected.
+    /// This should only be set when watching for changes to avoid unnecessary work.
+    pub(crate) last_modified: HashMap<AssetPath<'static>, Instant>,
     pub(crate) pending_tasks: HashMap<ErasedAssetIndex, Task<()>>,
+    /// How long to keep assets of a given type loaded after their last strong handle is dropped.
+    pub(crate) type_keep_alive: TypeIdMap<Duration>,
+    /// The handles keeping assets alive after their last strong handle was dropped, along with
+    /// when to release them.
+    pub(crate) kept_alive: HashMap<ErasedAssetIndex, (UntypedHandle, Instant)>,
     /// The stats that have collected during usage of the asset server.
     pub(crate) stats: AssetServerStats,
 }
@@ -314,6 +351,11 @@ impl AssetInfos {
         self.get_index_handle(ErasedAssetIndex::new(index, type_id))
     }
 
+    /// Returns the paths of all the assets that are tracked, including labeled assets.
+    pub(crate) fn paths(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.path_to_index.keys()
+    }
+
     pub(crate) fn get_path_indices<'a>(
         &'a self,
         path: &'a AssetPath<'_>,
@@ -357,6 +399,11 @@ impl AssetInfos {
             .filter_map(|id| self.get_index_handle(id))
     }
 
+    /// Iterates over the indices and infos of all the assets tracked by the server.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (ErasedAssetIndex, &AssetInfo)> {
+        self.infos.iter().map(|(index, info)| (*index, info))
+    }
+
     pub(crate) fn get_index_handle(&self, index: ErasedAssetIndex) -> Option<UntypedHandle> {
         let info = self.infos.get(&index)?;
         let strong_handle = info.weak_handle.upgrade()?;
@@ -385,7 +432,10 @@ impl AssetInfos {
 
     /// Returns `true` if the asset should be removed from the collection.
     pub(crate) fn process_handle_drop(&mut self, index: ErasedAssetIndex) -> bool {
-        Self::process_handle_drop_internal(
+        if self.try_keep_alive(index) {
+            return false;
+        }
+        let removed = Self::process_handle_drop_internal(
             &mut self.infos,
             &mut self.path_to_index,
             &mut self.loader_dependents,
@@ -393,7 +443,70 @@ impl AssetInfos {
             &mut self.pending_tasks,
             self.watching_for_changes,
             index,
-        )
+        );
+        if removed {
+            self.pending_reloads.remove(&index);
+        }
+        removed
+    }
+
+    /// Keeps the asset alive for its keep-alive duration after its last strong handle was dropped,
+    /// by creating a new strong handle. Returns `true` if the asset is kept alive.
+    fn try_keep_alive(&mut self, index: ErasedAssetIndex) -> bool {
+        let Some(info) = self.infos.get_mut(&index) else {
+            return false;
+        };
+        if info.handle_drops_to_skip > 0 || core::mem::take(&mut info.keep_alive_expired) {
+            return false;
+        }
+        // Only loaded assets with a path are kept alive, as they are the only ones that can be
+        // requested again without reloading them.
+        if !info.load_state.is_loaded() || info.path.is_none() {
+            return false;
+        }
+        let Some(duration) = info
+            .keep_alive
+            .or_else(|| self.type_keep_alive.get(&index.type_id).copied())
+            .filter(|duration| !duration.is_zero())
+        else {
+            return false;
+        };
+        let Some(provider) = self.handle_providers.get(&index.type_id) else {
+            return false;
+        };
+        let handle = provider.get_handle(index.index, true, info.path.clone(), None);
+        info.weak_handle = Arc::downgrade(&handle);
+        self.kept_alive.insert(
+            index,
+            (UntypedHandle::Strong(handle), Instant::now() + duration),
+        );
+        true
+    }
+
+    /// Removes the keep-alive handles that expired at `now`, or all of them if `now` is [`None`].
+    ///
+    /// The handles are returned so they can be dropped once the lock on the [`AssetInfos`] is
+    /// released.
+    pub(crate) fn take_expired_keep_alive_handles(
+        &mut self,
+        now: Option<Instant>,
+    ) -> Vec<UntypedHandle> {
+        let mut expired = Vec::new();
+        self.kept_alive.retain(|index, (handle, expiration)| {
+            if now.is_some_and(|now| now < *expiration) {
+                return true;
+            }
+            // If the keep-alive handle is the last one, its drop must unload the asset.
+            if let UntypedHandle::Strong(strong_handle) = handle
+                && Arc::strong_count(strong_handle) == 1
+                && let Some(info) = self.infos.get_mut(index)
+            {
+                info.keep_alive_expired = true;
+            }
+            expired.push(handle.clone());
+            false
+        });
+        expired
     }
 
     /// Updates [`AssetInfo`] / load state for an asset that has finished loading (and relevant dependencies / dependents).
@@ -427,6 +540,7 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_index.index, world);
+        let dependencies = loaded_asset.dependencies.clone();
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
@@ -523,6 +637,7 @@ impl AssetInfos {
             let info = self
                 .get_mut(loaded_asset_index)
                 .expect("Asset info should always exist at this point");
+            info.dependencies = dependencies;
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -0,0 +1,71 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{any::TypeId, time::Duration};
+
+use crate::{Asset, AssetServer, ErasedAssetIndex, UntypedAssetId, UntypedHandle};
+use bevy_ecs::system::Res;
+use bevy_platform::time::Instant;
+
+impl AssetServer {
+    /// Keeps assets of type `A` loaded for `duration` after their last strong handle is dropped.
+    ///
+    /// Requesting a kept-alive asset again, for example with [`AssetServer::load`], returns it
+    /// without reloading it. This avoids reading and uploading the same assets again when a scene
+    /// transition drops them right before loading them again.
+    ///
+    /// Only assets loaded from a path by the asset server are kept alive. A `duration` of
+    /// [`Duration::ZERO`] disables the keep-alive, which is the default.
+    pub fn set_keep_alive_for_type<A: Asset>(&self, duration: Duration) {
+        self.write_infos()
+            .type_keep_alive
+            .insert(TypeId::of::<A>(), duration);
+    }
+
+    /// Keeps the asset with the given `id` loaded for `duration` after its last strong handle is
+    /// dropped, overriding the duration set with [`AssetServer::set_keep_alive_for_type`].
+    ///
+    /// A `duration` of [`Duration::ZERO`] unloads the asset as soon as its last strong handle is
+    /// dropped. Does nothing if the asset isn't tracked by the asset server.
+    pub fn set_keep_alive(&self, id: impl Into<UntypedAssetId>, duration: Duration) {
+        let Ok(index) = ErasedAssetIndex::try_from(id.into()) else {
+            return;
+        };
+        if let Some(info) = self.write_infos().get_mut(index) {
+            info.keep_alive = Some(duration);
+        }
+    }
+
+    /// Returns `true` if the asset with the given `id` is only loaded because it is kept alive.
+    pub fn is_kept_alive(&self, id: impl Into<UntypedAssetId>) -> bool {
+        let Ok(index) = ErasedAssetIndex::try_from(id.into()) else {
+            return false;
+        };
+        self.read_infos()
+            .kept_alive
+            .get(&index)
+            .is_some_and(|(handle, _)| match handle {
+                UntypedHandle::Strong(handle) => Arc::strong_count(handle) == 1,
+                UntypedHandle::Uuid { .. } => false,
+            })
+    }
+
+    /// Stops keeping assets alive, unloading the ones that are no longer used. This can be used to
+    /// free memory right away, for example when the app is sent to the background.
+    pub fn release_kept_alive_assets(&self) {
+        self.release_keep_alive_handles(None);
+    }
+
+    fn release_keep_alive_handles(&self, now: Option<Instant>) {
+        let handles: Vec<_> = self.write_infos().take_expired_keep_alive_handles(now);
+        // The drop events of the handles are processed by `Assets::track_assets`.
+        drop(handles);
+    }
+}
+
+/// A system that releases the assets kept alive by [`AssetServer::set_keep_alive_for_type`] and
+/// [`AssetServer::set_keep_alive`] once their keep-alive duration elapsed.
+pub fn release_expired_keep_alive_assets(asset_server: Res<AssetServer>) {
+    if asset_server.read_infos().kept_alive.is_empty() {
+        return;
+    }
+    asset_server.release_keep_alive_handles(Some(Instant::now()));
+}
@@ -1,25 +1,33 @@
+mod graph;
 mod info;
+mod keep_alive;
 mod loaders;
 
 use crate::{
     folder::LoadedFolder,
     io::{
-        AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        AssetWriterError, ErasedAssetReader, MissingAssetSourceError, MissingAssetWriterError,
-        MissingProcessedAssetReaderError, Reader,
+        AssetIoDiagnosticPaths, AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId,
+        AssetSources, AssetWriterError, CountingReader, ErasedAssetReader, MissingAssetSourceError,
+        MissingAssetWriterError, MissingProcessedAssetReaderError, Reader, VecReader,
     },
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
+    locale::{find_localized_path, unlocalized_paths, Localization},
     meta::{
-        loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
-        MetaTransform, Settings,
+        get_content_hash, loader_settings_meta_transform, AssetActionMinimal, AssetHash,
+        AssetMetaDyn, AssetMetaMinimal, MetaTransform, ProcessedInfo, Settings,
     },
     path::AssetPath,
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetIndex, AssetLoadFailedEvent,
-    AssetMetaCheck, Assets, DeserializeMetaError, ErasedAssetIndex, ErasedLoadedAsset, Handle,
-    LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId, UntypedAssetLoadFailedEvent,
-    UntypedHandle,
+    AssetMetaCheck, AssetReloadedEvent, Assets, DeserializeMetaError, ErasedAssetIndex,
+    ErasedLoadedAsset, Handle, LoadedUntypedAsset, UnapprovedPathMode, UntypedAssetId,
+    UntypedAssetLoadFailedEvent, UntypedAssetReloadedEvent, UntypedHandle,
+};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    vec,
+    vec::Vec,
 };
-use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
     format,
     string::{String, ToString},
@@ -29,17 +37,23 @@ use atomicow::CowArc;
 use bevy_diagnostic::{DiagnosticPath, Diagnostics};
 use bevy_ecs::prelude::*;
 use bevy_platform::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Instant,
 };
 use bevy_tasks::IoTaskPool;
 use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, task::Poll};
 use crossbeam_channel::{Receiver, Sender};
 use either::Either;
-use futures_lite::{FutureExt, StreamExt};
+use futures_lite::{AsyncSeekExt, FutureExt, StreamExt};
+pub use graph::*;
 use info::*;
+pub use keep_alive::*;
 use loaders::*;
-use std::path::{Path, PathBuf};
+use std::{
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 use tracing::{error, info};
 
@@ -69,10 +83,15 @@ pub(crate) struct AssetServerData {
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
-    sources: Arc<AssetSources>,
+    pub(crate) sources: Arc<AssetSources>,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
     unapproved_path_mode: UnapprovedPathMode,
+    /// The languages of the [`Locale`](crate::Locale), used to load localized assets.
+    localization: RwLock<Localization>,
+    /// The key the signatures of `.meta` files are verified with, if they must be signed.
+    #[cfg(feature = "meta_signatures")]
+    meta_verifying_key: RwLock<Option<ed25519_dalek::VerifyingKey>>,
 }
 
 /// The "asset mode" the server is currently in.
@@ -146,6 +165,9 @@ impl AssetServer {
                 loaders,
                 infos: RwLock::new(infos),
                 unapproved_path_mode,
+                localization: Default::default(),
+                #[cfg(feature = "meta_signatures")]
+                meta_verifying_key: Default::default(),
             }),
         }
     }
@@ -186,6 +208,134 @@ impl AssetServer {
         self.data.sources.get(source.into())
     }
 
+    /// Resolves the [path alias](crate::AssetPlugin::path_aliases) of `path`, if its source is one.
+    ///
+    /// If the paths of the source are resolved case-insensitively, see
+    /// [`AssetSourceBuilder::with_case_insensitive_paths`](crate::io::AssetSourceBuilder::with_case_insensitive_paths),
+    /// the path is also changed to the case of its file, so that paths differing only by case
+    /// identify the same asset.
+    ///
+    /// The paths passed to the [`AssetServer`] are resolved automatically. This is only needed
+    /// to compare them with the paths of loaded assets, which are always resolved.
+    pub fn resolve_path_alias<'a>(&self, path: impl Into<AssetPath<'a>>) -> AssetPath<'a> {
+        let path = self.data.sources.resolve_path_alias(path.into());
+        let processed = matches!(self.data.mode, AssetServerMode::Processed);
+        let Some(resolved) = self
+            .data
+            .sources
+            .get(path.source())
+            .ok()
+            .and_then(|source| source.resolve_path_case(path.path(), processed))
+            .filter(|resolved| resolved != path.path())
+        else {
+            return path;
+        };
+        let resolved = AssetPath::from_path_buf(resolved).with_source(path.source().clone_owned());
+        match path.label_cow() {
+            Some(label) => resolved.with_label(label),
+            None => resolved,
+        }
+    }
+
+    /// Returns the reader assets are loaded from in the asset source `source`, depending on the
+    /// [`AssetServerMode`].
+    pub(crate) fn reader<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+    ) -> Result<&dyn ErasedAssetReader, AssetLoadError> {
+        let source = self.get_source(source)?;
+        Ok(match self.data.mode {
+            AssetServerMode::Unprocessed => source.reader(),
+            AssetServerMode::Processed => source.processed_reader()?,
+        })
+    }
+
+    /// Sets the languages localized assets are loaded in, returning the previous ones.
+    pub(crate) fn set_languages(&self, languages: Vec<String>) -> Vec<String> {
+        let mut localization = self
+            .data
+            .localization
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        localization.paths.clear();
+        core::mem::replace(&mut localization.languages, languages)
+    }
+
+    /// Forgets the localized versions found for the assets, e.g. after assets were added or removed.
+    fn clear_localized_paths(&self) {
+        self.data
+            .localization
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .paths
+            .clear();
+    }
+
+    /// Sets the key the signatures of `.meta` files are verified with, see
+    /// [`AssetPlugin::meta_verifying_key`](crate::AssetPlugin::meta_verifying_key).
+    #[cfg(feature = "meta_signatures")]
+    pub(crate) fn set_meta_verifying_key(&self, key: Option<ed25519_dalek::VerifyingKey>) {
+        *self
+            .data
+            .meta_verifying_key
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = key;
+    }
+
+    /// Returns the path of the localized version of the asset at `asset_path` in `reader`, or its
+    /// path if it has none in the languages of the [`Locale`](crate::Locale).
+    ///
+    /// The localized versions are looked up once per asset path, until the languages change or
+    /// assets are added or removed.
+    pub(crate) async fn localize_path<'p>(
+        &self,
+        reader: &dyn ErasedAssetReader,
+        asset_path: &'p AssetPath<'_>,
+    ) -> Cow<'p, Path> {
+        let path = asset_path.path();
+        let key = asset_path.without_label().into_owned();
+        let languages = {
+            let localization = self
+                .data
+                .localization
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+            if localization.languages.is_empty() {
+                return Cow::Borrowed(path);
+            }
+            if let Some(localized) = localization.paths.get(&key) {
+                return localized.clone().map_or(Cow::Borrowed(path), Cow::Owned);
+            }
+            localization.languages.clone()
+        };
+        let localized = find_localized_path(reader, path, &languages).await;
+        let mut localization = self
+            .data
+            .localization
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        // The languages may have changed while the localized versions were looked up.
+        if localization.languages == languages {
+            localization.paths.insert(key, localized.clone());
+        }
+        localized.map_or(Cow::Borrowed(path), Cow::Owned)
+    }
+
+    /// Returns the path of the localized version of the asset at `asset_path`, like
+    /// [`Self::localize_path`], in the reader assets are loaded from.
+    ///
+    /// If the source of the asset is missing, `asset_path` is returned, and the error is reported
+    /// when reading it.
+    pub(crate) async fn localize_asset_path<'p>(
+        &self,
+        asset_path: &'p AssetPath<'_>,
+    ) -> Cow<'p, Path> {
+        match self.reader(asset_path.source()) {
+            Ok(reader) => self.localize_path(reader, asset_path).await,
+            Err(_) => Cow::Borrowed(asset_path.path()),
+        }
+    }
+
     /// Returns true if the [`AssetServer`] watches for changes.
     pub fn watching_for_changes(&self) -> bool {
         self.read_infos().watching_for_changes
@@ -219,6 +369,19 @@ impl AssetServer {
                 });
         }
 
+        fn reloaded_sender<A: Asset>(world: &mut World, event: UntypedAssetReloadedEvent) {
+            world
+                .resource_mut::<Messages<AssetReloadedEvent<A>>>()
+                .write(AssetReloadedEvent {
+                    id: event.id.typed_debug_checked(),
+                    path: event.path,
+                    changed_path: event.changed_path,
+                    previous_modified_at: event.previous_modified_at,
+                    modified_at: event.modified_at,
+                    invalidated_dependents: event.invalidated_dependents,
+                });
+        }
+
         let mut infos = self.write_infos();
 
         infos
@@ -228,6 +391,10 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .reloaded_event_sender
+            .insert(TypeId::of::<A>(), reloaded_sender::<A>);
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -363,7 +530,7 @@ impl AssetServer {
 
     /// Same as [`load`](AssetServer::load), but you can load assets from unapproved paths
     /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
-    /// is [`Deny`](UnapprovedPathMode::Deny).
+    /// (or the asset source's override) is [`Deny`](UnapprovedPathMode::Deny).
     ///
     /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
     pub fn load_override<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
@@ -396,7 +563,7 @@ impl AssetServer {
 
     /// Same as [`load`](AssetServer::load_acquire), but you can load assets from unapproved paths
     /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
-    /// is [`Deny`](UnapprovedPathMode::Deny).
+    /// (or the asset source's override) is [`Deny`](UnapprovedPathMode::Deny).
     ///
     /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
     pub fn load_acquire_override<'a, A: Asset, G: Send + Sync + 'static>(
@@ -426,7 +593,7 @@ impl AssetServer {
 
     /// Same as [`load`](AssetServer::load_with_settings), but you can load assets from unapproved paths
     /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
-    /// is [`Deny`](UnapprovedPathMode::Deny).
+    /// (or the asset source's override) is [`Deny`](UnapprovedPathMode::Deny).
     ///
     /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
     pub fn load_with_settings_override<'a, A: Asset, S: Settings>(
@@ -468,7 +635,7 @@ impl AssetServer {
 
     /// Same as [`load`](AssetServer::load_acquire_with_settings), but you can load assets from unapproved paths
     /// if [`AssetPlugin::unapproved_path_mode`](super::AssetPlugin::unapproved_path_mode)
-    /// is [`Deny`](UnapprovedPathMode::Deny).
+    /// (or the asset source's override) is [`Deny`](UnapprovedPathMode::Deny).
     ///
     /// See [`UnapprovedPathMode`] and [`AssetPath::is_unapproved`]
     pub fn load_acquire_with_settings_override<
@@ -497,10 +664,17 @@ impl AssetServer {
         guard: G,
         override_unapproved: bool,
     ) -> Handle<A> {
-        let path = path.into().into_owned();
+        let path = self.resolve_path_alias(path).into_owned();
 
         if path.is_unapproved() {
-            match (&self.data.unapproved_path_mode, override_unapproved) {
+            let unapproved_path_mode = self
+                .data
+                .sources
+                .get(path.source())
+                .ok()
+                .and_then(AssetSource::unapproved_path_mode)
+                .unwrap_or(&self.data.unapproved_path_mode);
+            match (unapproved_path_mode, override_unapproved) {
                 (UnapprovedPathMode::Allow, _) | (UnapprovedPathMode::Deny, true) => {}
                 (UnapprovedPathMode::Deny, false) | (UnapprovedPathMode::Forbid, _) => {
                     error!("Asset path {path} is unapproved. See UnapprovedPathMode for details.");
@@ -530,7 +704,7 @@ impl AssetServer {
         meta_transform: Option<MetaTransform>,
         guard: G,
     ) -> UntypedHandle {
-        let path = path.into().into_owned();
+        let path = self.resolve_path_alias(path).into_owned();
         let mut infos = self.write_infos();
         let (handle, should_load) = infos.get_or_create_path_handle_erased(
             path.clone(),
@@ -594,7 +768,7 @@ impl AssetServer {
     ) -> Result<UntypedHandle, AssetLoadError> {
         self.write_infos().stats.started_load_tasks += 1;
 
-        let path: AssetPath = path.into();
+        let path = self.resolve_path_alias(path);
         self.load_internal(None, path, false, None)
             .await
             .map(|h| h.expect("handle must be returned, since we didn't pass in an input handle"))
@@ -605,7 +779,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
     ) -> Handle<LoadedUntypedAsset> {
-        let path = path.into().into_owned();
+        let path = self.resolve_path_alias(path).into_owned();
         let untyped_source = AssetSourceId::Name(match path.source() {
             AssetSourceId::Default => CowArc::Static(UNTYPED_SOURCE_SUFFIX),
             AssetSourceId::Name(source) => {
@@ -711,8 +885,9 @@ impl AssetServer {
 
         let path = path.into_owned();
         let path_clone = path.clone();
+        let localized_path = self.localize_asset_path(&path_clone).await;
         let (mut meta, loader, mut reader) = self
-            .get_meta_loader_and_reader(&path_clone, input_handle_type_id)
+            .get_meta_loader_and_reader(&path_clone, &localized_path, input_handle_type_id)
             .await
             .inspect_err(|e| {
                 // if there was an input handle, a "load" operation has already started, so we must produce a "failure" event, if
@@ -729,6 +904,7 @@ impl AssetServer {
         if let Some(meta_transform) = input_handle.as_ref().and_then(|h| h.meta_transform()) {
             (*meta_transform)(&mut *meta);
         }
+        let content_hash = self.get_loaded_content_hash(&mut *meta);
 
         let asset_id: Option<ErasedAssetIndex>; // The asset ID of the asset we are trying to load.
         let fetched_handle; // The handle if one was looked up/created.
@@ -825,18 +1001,23 @@ impl AssetServer {
             (asset_id.unwrap(), None, path.clone())
         };
 
+        let mut reader = CountingReader::new(&mut *reader);
         match self
             .load_with_settings_loader_and_reader(
                 &base_path,
                 meta.loader_settings().expect("meta is set to Load"),
                 &*loader,
-                &mut *reader,
+                &mut reader,
                 true,
                 false,
             )
             .await
         {
             Ok(loaded_asset) => {
+                if let Some(info) = self.write_infos().get_mut(base_asset_id) {
+                    info.bytes_read = Some(reader.bytes_read());
+                    info.content_hash = content_hash;
+                }
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.label_to_asset_index.get(&label) {
                         Some(labeled_asset) => {
@@ -913,7 +1094,7 @@ impl AssetServer {
 
     fn reload_internal<'a>(&self, path: impl Into<AssetPath<'a>>, log: bool) {
         let server = self.clone();
-        let path = path.into().into_owned();
+        let path = self.resolve_path_alias(path).into_owned();
         IoTaskPool::get()
             .spawn(async move {
                 let mut reloaded = false;
@@ -958,6 +1139,36 @@ impl AssetServer {
             .detach();
     }
 
+    /// Kicks off a reload of the loaded assets of `source` that can be read from `reader`.
+    ///
+    /// This is used when `reader` is mounted on top of the source or unmounted from it, as the assets
+    /// it contains are then read from a different location.
+    pub(crate) fn reload_assets_in_reader(
+        &self,
+        source: AssetSourceId<'static>,
+        reader: Arc<dyn ErasedAssetReader>,
+    ) {
+        let paths = self
+            .read_infos()
+            .paths()
+            .filter(|path| path.source() == &source)
+            .map(|path| path.without_label().into_owned())
+            .collect::<HashSet<_>>();
+        if paths.is_empty() {
+            return;
+        }
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                for path in paths {
+                    if reader.read(path.path()).await.is_ok() {
+                        server.reload(path);
+                    }
+                }
+            })
+            .detach();
+    }
+
     /// Queues a new asset to be tracked by the [`AssetServer`] and returns a [`Handle`] to it. This can be used to track
     /// dependencies of assets created at runtime.
     ///
@@ -1072,7 +1283,7 @@ impl AssetServer {
     /// or removing complete subdirectories.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
     pub fn load_folder<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<LoadedFolder> {
-        let path = path.into().into_owned();
+        let path = self.resolve_path_alias(path).into_owned();
         let (handle, should_load) = self
             .write_infos()
             .get_or_create_path_handle::<LoadedFolder>(
@@ -1210,6 +1421,36 @@ impl AssetServer {
         self.read_infos().get(index).map(|i| i.load_state.clone())
     }
 
+    /// Retrieves the number of bytes read by the [`AssetLoader`] of the given asset `id`, once it
+    /// has been loaded.
+    ///
+    /// This doesn't include the bytes of the asset's dependencies, nor the bytes of the `.meta`
+    /// file. For labeled assets, this is [`None`]: query the base asset instead.
+    pub fn get_bytes_read(&self, id: impl Into<UntypedAssetId>) -> Option<u64> {
+        let Ok(index) = id.into().try_into() else {
+            // Always say we don't have Uuid assets.
+            return None;
+        };
+        self.read_infos().get(index).and_then(|i| i.bytes_read)
+    }
+
+    /// Retrieves the content hash of the given asset `id`, once it has been loaded from
+    /// content-addressed processed bytes.
+    ///
+    /// The hash covers both the processed bytes and the loader settings of the asset, so assets with
+    /// the same content hash are loaded the same way from the same bytes, even if they are at
+    /// different paths. Renderers use it to upload their data to the GPU once. This is [`None`] for
+    /// assets that weren't loaded from content-addressed processed bytes, and for labeled assets.
+    ///
+    /// See [`AssetProcessor::set_content_addressed`](crate::processor::AssetProcessor::set_content_addressed).
+    pub fn get_content_hash(&self, id: impl Into<UntypedAssetId>) -> Option<AssetHash> {
+        let Ok(index) = id.into().try_into() else {
+            // Always say we don't have Uuid assets.
+            return None;
+        };
+        self.read_infos().get(index).and_then(|i| i.content_hash)
+    }
+
     /// Retrieves the [`DependencyLoadState`] of a given asset `id`'s dependencies.
     ///
     /// Note that this is only the load state of direct dependencies of the root asset. To get
@@ -1304,7 +1545,7 @@ impl AssetServer {
     /// Returns an active handle for the given path, if the asset at the given path has already started loading,
     /// or is still "alive".
     pub fn get_handle<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Option<Handle<A>> {
-        self.get_path_and_type_id_handle(&path.into(), TypeId::of::<A>())
+        self.get_path_and_type_id_handle(&self.resolve_path_alias(path), TypeId::of::<A>())
             .map(UntypedHandle::typed_debug_checked)
     }
 
@@ -1348,7 +1589,7 @@ impl AssetServer {
     /// [`get_path_ids`][Self::get_path_ids] for all handles.
     pub fn get_path_id<'a>(&self, path: impl Into<AssetPath<'a>>) -> Option<UntypedAssetId> {
         let infos = self.read_infos();
-        let path = path.into();
+        let path = self.resolve_path_alias(path);
         let mut ids = infos.get_path_indices(&path);
         ids.next().map(Into::into)
     }
@@ -1357,7 +1598,7 @@ impl AssetServer {
     /// or are still "alive".
     /// Multiple IDs will be returned in the event that a single path is used by multiple [`AssetLoader`]'s.
     pub fn get_path_ids<'a>(&self, path: impl Into<AssetPath<'a>>) -> Vec<UntypedAssetId> {
-        let path = path.into();
+        let path = self.resolve_path_alias(path);
         self.read_infos()
             .get_path_indices(&path)
             .map(Into::into)
@@ -1371,7 +1612,7 @@ impl AssetServer {
     /// # See also
     /// [`get_handles_untyped`][Self::get_handles_untyped] for all handles.
     pub fn get_handle_untyped<'a>(&self, path: impl Into<AssetPath<'a>>) -> Option<UntypedHandle> {
-        let path = path.into();
+        let path = self.resolve_path_alias(path);
         self.read_infos().get_path_handles(&path).next()
     }
 
@@ -1379,7 +1620,7 @@ impl AssetServer {
     /// or are still "alive".
     /// Multiple handles will be returned in the event that a single path is used by multiple [`AssetLoader`]'s.
     pub fn get_handles_untyped<'a>(&self, path: impl Into<AssetPath<'a>>) -> Vec<UntypedHandle> {
-        let path = path.into();
+        let path = self.resolve_path_alias(path);
         self.read_infos().get_path_handles(&path).collect()
     }
 
@@ -1427,7 +1668,7 @@ impl AssetServer {
     ) -> Handle<A> {
         self.write_infos()
             .get_or_create_path_handle::<A>(
-                path.into().into_owned(),
+                self.resolve_path_alias(path).into_owned(),
                 HandleLoadingMode::NotLoading,
                 meta_transform,
             )
@@ -1446,7 +1687,7 @@ impl AssetServer {
     ) -> UntypedHandle {
         self.write_infos()
             .get_or_create_path_handle_erased(
-                path.into().into_owned(),
+                self.resolve_path_alias(path).into_owned(),
                 type_id,
                 None,
                 HandleLoadingMode::NotLoading,
@@ -1455,9 +1696,31 @@ impl AssetServer {
             .0
     }
 
+    /// Returns the meta and loader of the asset at `asset_path`, and a reader of its bytes.
+    ///
+    /// The asset is read from `path`, its path or the path of its localized version, see
+    /// [`Self::localize_asset_path`].
+    /// Returns the content hash of an asset loaded with `meta`, from its content-addressed processed
+    /// bytes and its loader settings. See [`AssetServer::get_content_hash`].
+    fn get_loaded_content_hash(&self, meta: &mut dyn AssetMetaDyn) -> Option<AssetHash> {
+        if let AssetServerMode::Unprocessed = self.data.mode {
+            return None;
+        }
+        let content_hash = meta.processed_info().as_ref()?.content_hash?;
+        // The processed info differs between paths, only the loader and its settings matter.
+        let processed_info = meta.processed_info_mut().take();
+        let settings = meta.serialize();
+        *meta.processed_info_mut() = processed_info;
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&content_hash);
+        hasher.update(&settings);
+        Some(*hasher.finalize().as_bytes())
+    }
+
     pub(crate) async fn get_meta_loader_and_reader<'a>(
         &'a self,
         asset_path: &'a AssetPath<'_>,
+        path: &'a Path,
         asset_type_id: Option<TypeId>,
     ) -> Result<
         (
@@ -1468,11 +1731,13 @@ impl AssetServer {
         AssetLoadError,
     > {
         let source = self.get_source(asset_path.source())?;
-        let asset_reader = match self.data.mode {
-            AssetServerMode::Unprocessed => source.reader(),
-            AssetServerMode::Processed => source.processed_reader()?,
+        let asset_reader = self.reader(asset_path.source())?;
+        // Processed assets always have meta files, so source overrides only apply to unprocessed loads.
+        let meta_check = match self.data.mode {
+            AssetServerMode::Unprocessed => source.meta_check().unwrap_or(&self.data.meta_check),
+            AssetServerMode::Processed => &self.data.meta_check,
         };
-        let read_meta = match &self.data.meta_check {
+        let read_meta = match meta_check {
             AssetMetaCheck::Always => true,
             AssetMetaCheck::Paths(paths) => paths.contains(asset_path),
             AssetMetaCheck::Never => false,
@@ -1485,7 +1750,7 @@ impl AssetServer {
         let mut meta_reader;
 
         let (meta, loader) = if read_meta {
-            match asset_reader.read_meta(asset_path.path()).await {
+            match asset_reader.read_meta(path).await {
                 Ok(new_meta_reader) => {
                     meta_reader = new_meta_reader;
                     let mut meta_bytes = vec![];
@@ -1493,6 +1758,12 @@ impl AssetServer {
                         .read_to_end(&mut meta_bytes)
                         .await
                         .map_err(|err| AssetLoadError::AssetReaderError(err.into()))?;
+                    // The meta file read is the one of the localized version of the asset, if any.
+                    #[cfg(feature = "meta_signatures")]
+                    let meta_bytes = self.verify_meta_signature(
+                        &AssetPath::from_path(path).with_source(asset_path.source().clone_owned()),
+                        meta_bytes,
+                    )?;
                     // TODO: this isn't fully minimal yet. we only need the loader
                     let minimal: AssetMetaMinimal =
                         ron::de::from_bytes(&meta_bytes).map_err(|e| {
@@ -1563,10 +1834,112 @@ impl AssetServer {
             let meta = loader.default_meta();
             (meta, loader)
         };
-        let reader = asset_reader.read(asset_path.path()).await?;
+        let processed_info = match self.data.mode {
+            AssetServerMode::Unprocessed => None,
+            AssetServerMode::Processed => meta.processed_info().clone(),
+        };
+        let reader: Box<dyn Reader + 'a> =
+            match processed_info.as_ref().and_then(|info| info.content_hash) {
+                // Read into memory, as the content path is made here, see
+                // `CaseInsensitiveAssetReader::read`.
+                Some(hash) => Box::new(VecReader::new(source.read_processed_content(&hash).await?)),
+                None => asset_reader.read(path).await?,
+            };
+        let reader = match processed_info {
+            Some(processed_info) => {
+                self.verify_processed_reader(asset_path, &processed_info, reader)
+                    .await?
+            }
+            None => reader,
+        };
         Ok((meta, loader, reader))
     }
 
+    /// Checks the bytes of `reader` against the hash recorded in `processed_info` before any loader
+    /// reads them, so that loaders never see corrupt processed bytes.
+    ///
+    /// Seekable readers are hashed and then rewound, which keeps streaming and memory-mapped readers
+    /// working. Other readers are read into memory. If the bytes don't match, the asset is queued to
+    /// be processed again.
+    async fn verify_processed_reader<'a>(
+        &self,
+        asset_path: &AssetPath<'_>,
+        processed_info: &ProcessedInfo,
+        mut reader: Box<dyn Reader + 'a>,
+    ) -> Result<Box<dyn Reader + 'a>, AssetLoadError> {
+        let Some(expected_hash) = processed_info
+            .processed_hash
+            .or(processed_info.content_hash)
+        else {
+            return Ok(reader);
+        };
+        let read_err = |err: std::io::Error| AssetLoadError::AssetReaderError(err.into());
+        let (hash, reader) = match reader.seekable() {
+            Ok(seekable) => {
+                let mut hasher = blake3::Hasher::new();
+                let mut buf = vec![0; 64 * 1024];
+                loop {
+                    let n = futures_lite::AsyncReadExt::read(seekable, &mut buf)
+                        .await
+                        .map_err(read_err)?;
+                    if n == 0 {
+                        break;
+                    }
+                    hasher.update(&buf[..n]);
+                }
+                seekable.seek(SeekFrom::Start(0)).await.map_err(read_err)?;
+                (*hasher.finalize().as_bytes(), reader)
+            }
+            Err(_) => {
+                let mut bytes = Vec::new();
+                reader.read_to_end(&mut bytes).await.map_err(read_err)?;
+                let hash = get_content_hash(&bytes);
+                let reader: Box<dyn Reader + 'a> = Box::new(VecReader::new(bytes));
+                (hash, reader)
+            }
+        };
+        if hash != expected_hash {
+            let asset_path = asset_path.without_label().into_owned();
+            error!(
+                "The processed bytes of {asset_path} don't match their hash, so it is reprocessed"
+            );
+            if let Ok(source) = self.get_source(asset_path.source()) {
+                source.reprocess_corrupt_asset(asset_path.path());
+            }
+            return Err(AssetLoadError::CorruptProcessedAsset { path: asset_path });
+        }
+        Ok(reader)
+    }
+
+    /// Returns `meta_bytes` without their signature if they are signed with the key set in
+    /// [`AssetPlugin::meta_verifying_key`](crate::AssetPlugin::meta_verifying_key), or as they
+    /// are if there is no key.
+    #[cfg(feature = "meta_signatures")]
+    fn verify_meta_signature(
+        &self,
+        asset_path: &AssetPath,
+        mut meta_bytes: Vec<u8>,
+    ) -> Result<Vec<u8>, AssetLoadError> {
+        let Some(key) = *self
+            .data
+            .meta_verifying_key
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+        else {
+            return Ok(meta_bytes);
+        };
+        let content =
+            crate::verify_asset_signature(&meta_bytes, asset_path, &key).map_err(|error| {
+                AssetLoadError::InvalidMetaSignature {
+                    path: asset_path.clone_owned(),
+                    error,
+                }
+            })?;
+        // The signed content is at the start of the meta file.
+        meta_bytes.truncate(content.len());
+        Ok(meta_bytes)
+    }
+
     pub(crate) async fn load_with_settings_loader_and_reader(
         &self,
         asset_path: &AssetPath<'_>,
@@ -1786,6 +2159,7 @@ pub fn handle_internal_asset_events(world: &mut World) {
         let mut infos = server.write_infos();
         let var_name = vec![];
         let mut untyped_failures = var_name;
+        let mut reloads = vec![];
         for event in server.data.asset_event_receiver.try_iter() {
             match event {
                 InternalAssetEvent::Loaded {
@@ -1798,6 +2172,14 @@ pub fn handle_internal_asset_events(world: &mut World) {
                         world,
                         &server.data.asset_event_sender,
                     );
+                    if let Some(reload) = infos.pending_reloads.remove(&index) {
+                        let sender = infos
+                            .reloaded_event_sender
+                            .get(&index.type_id)
+                            .expect("Asset reloaded event sender should exist");
+                        sender(world, reload.clone());
+                        reloads.push(reload);
+                    }
                 }
                 InternalAssetEvent::LoadedWithDependencies { index } => {
                     let sender = infos
@@ -1813,6 +2195,7 @@ pub fn handle_internal_asset_events(world: &mut World) {
                 }
                 InternalAssetEvent::Failed { index, path, error } => {
                     infos.process_asset_fail(index, error.clone());
+                    infos.pending_reloads.remove(&index);
 
                     // Send untyped failure event
                     untyped_failures.push(UntypedAssetLoadFailedEvent {
@@ -1835,6 +2218,10 @@ pub fn handle_internal_asset_events(world: &mut World) {
             world.write_message_batch(untyped_failures);
         }
 
+        if !reloads.is_empty() {
+            world.write_message_batch(reloads);
+        }
+
         // The following code all deals with hot-reloading, which we can skip if the server isn't
         // watching for changes.
         if !infos.watching_for_changes {
@@ -1867,14 +2254,49 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         };
 
-        let mut paths_to_reload = <HashSet<_>>::default();
+        // Maps the paths to reload to the changed path that caused their reload.
+        let mut paths_to_reload = <HashMap<_, AssetPath<'static>>>::default();
+        // Maps the changed paths to the paths of their loader dependents.
+        let mut changed_paths = <HashMap<_, HashSet<_>>>::default();
         let mut reload_path = |path: PathBuf, source: &AssetSourceId<'static>| {
             let path = AssetPath::from(path).with_source(source);
-            queue_ancestors(&path, &infos, &mut paths_to_reload);
-            paths_to_reload.insert(path);
+            let mut dependents = HashSet::default();
+            queue_ancestors(&path, &infos, &mut dependents);
+            for dependent in &dependents {
+                paths_to_reload
+                    .entry(dependent.clone())
+                    .or_insert_with(|| path.clone());
+            }
+            paths_to_reload.insert(path.clone(), path.clone());
+            changed_paths.insert(path, dependents);
         };
 
+        let languages = server
+            .data
+            .localization
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .languages
+            .clone();
         let mut handle_event = |source: AssetSourceId<'static>, event: AssetSourceEvent| {
+            // Localized versions of the assets may have been added or removed.
+            if !matches!(
+                event,
+                AssetSourceEvent::ModifiedAsset(_) | AssetSourceEvent::ModifiedMeta(_)
+            ) {
+                server.clear_localized_paths();
+            }
+            // The assets loaded from a localized version are reloaded when it changes, and when
+            // it is added or removed, since they then load from another file.
+            if let AssetSourceEvent::AddedAsset(path)
+            | AssetSourceEvent::ModifiedAsset(path)
+            | AssetSourceEvent::ModifiedMeta(path)
+            | AssetSourceEvent::RemovedAsset(path) = &event
+            {
+                for unlocalized in unlocalized_paths(path, &languages) {
+                    reload_path(unlocalized, &source);
+                }
+            }
             match event {
                 AssetSourceEvent::AddedAsset(path) => {
                     reload_parent_folders(&path, &source);
@@ -1903,6 +2325,7 @@ pub fn handle_internal_asset_events(world: &mut World) {
                 AssetServerMode::Unprocessed => {
                     if let Some(receiver) = source.event_receiver() {
                         while let Ok(event) = receiver.try_recv() {
+                            source.handle_case_insensitive_event(&event, false);
                             handle_event(source.id(), event);
                         }
                     }
@@ -1910,6 +2333,7 @@ pub fn handle_internal_asset_events(world: &mut World) {
                 AssetServerMode::Processed => {
                     if let Some(receiver) = source.processed_event_receiver() {
                         while let Ok(event) = receiver.try_recv() {
+                            source.handle_case_insensitive_event(&event, true);
                             handle_event(source.id(), event);
                         }
                     }
@@ -1917,12 +2341,41 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         }
 
+        // Queue the reload events, to be emitted once the new versions of the assets are loaded.
+        let modified_at = Instant::now();
+        let previous_modified_at = changed_paths
+            .keys()
+            .map(|path| {
+                let previous = infos.last_modified.insert(path.clone(), modified_at);
+                (path.clone(), previous)
+            })
+            .collect::<HashMap<_, _>>();
+        for (path, changed_path) in &paths_to_reload {
+            let invalidated_dependents = if path == changed_path {
+                changed_paths[path].iter().cloned().collect()
+            } else {
+                Vec::new()
+            };
+            let indices = infos.get_path_indices(path).collect::<Vec<_>>();
+            for index in indices {
+                let reload = UntypedAssetReloadedEvent {
+                    id: index.into(),
+                    path: path.clone(),
+                    changed_path: changed_path.clone(),
+                    previous_modified_at: previous_modified_at[changed_path],
+                    modified_at,
+                    invalidated_dependents: invalidated_dependents.clone(),
+                };
+                infos.pending_reloads.insert(index, reload);
+            }
+        }
+
         // Drop the lock on `AssetInfos` before spawning a task that may block on it in
         // single-threaded.
         #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
         drop(infos);
 
-        for path in paths_to_reload {
+        for path in paths_to_reload.into_keys() {
             server.reload_internal(path, true);
         }
 
@@ -1944,6 +2397,29 @@ pub fn publish_asset_server_diagnostics(
     });
 }
 
+/// A system publishing the [`AssetSourceMetrics`](crate::io::AssetSourceMetrics) of every asset
+/// source to [`bevy_diagnostic`], at the paths returned by [`AssetIoDiagnosticPaths::new`].
+pub fn publish_asset_io_diagnostics(
+    asset_server: Res<AssetServer>,
+    mut diagnostics: Diagnostics,
+    mut paths: Local<HashMap<AssetSourceId<'static>, AssetIoDiagnosticPaths>>,
+) {
+    for source in asset_server.data.sources.iter() {
+        let Some(metrics) = source.metrics() else {
+            continue;
+        };
+        let paths = paths
+            .entry(source.id())
+            .or_insert_with_key(AssetIoDiagnosticPaths::new);
+        diagnostics.add_measurement(&paths.reads, || metrics.reads() as _);
+        diagnostics.add_measurement(&paths.bytes_read, || metrics.bytes_read() as _);
+        diagnostics.add_measurement(&paths.errors, || metrics.errors() as _);
+        if let Some(latency) = metrics.mean_latency() {
+            diagnostics.add_measurement(&paths.mean_latency, || latency.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
 /// Internal events for asset load results
 pub(crate) enum InternalAssetEvent {
     Loaded {
@@ -2112,6 +2588,16 @@ pub enum AssetLoadError {
     #[error("Asset '{path}' is configured to be ignored. It cannot be loaded.")]
     #[from(ignore)]
     CannotLoadIgnoredAsset { path: AssetPath<'static> },
+    #[error("Processed asset '{path}' is corrupt: its bytes don't match the hash recorded when it was processed")]
+    #[from(ignore)]
+    CorruptProcessedAsset { path: AssetPath<'static> },
+    #[cfg(feature = "meta_signatures")]
+    #[error("The meta file of asset '{path}' failed signature verification: {error}")]
+    #[from(ignore)]
+    InvalidMetaSignature {
+        path: AssetPath<'static>,
+        error: crate::AssetSignatureError,
+    },
     #[error("Failed to load asset '{path}', asset loader '{loader_name}' panicked")]
     AssetLoaderPanic {
         path: AssetPath<'static>,
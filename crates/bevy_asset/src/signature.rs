@@ -0,0 +1,173 @@
+use crate::AssetPath;
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use thiserror::Error;
+
+/// The start of the line holding the signature of signed bytes. As it is a comment, signed `.meta`
+/// files remain valid RON.
+pub const ASSET_SIGNATURE_PREFIX: &str = "// signature: ";
+
+/// The start of every signed message, so that signatures of assets can't be mistaken for
+/// signatures of other data made with the same key.
+const SIGNATURE_DOMAIN: &[u8] = b"bevy_asset signature v1\0";
+
+/// Returns `bytes` signed with `key`: the [`ed25519`](ed25519_dalek) signature of `bytes` is
+/// appended to them on a line starting with [`ASSET_SIGNATURE_PREFIX`]. A signature already at the
+/// end of `bytes` is replaced.
+///
+/// The signature also covers the source and the path of `asset_path`, the asset the bytes belong
+/// to, so that they can't be copied next to another asset: for a `.meta` file, this is the path of
+/// its asset. The label of `asset_path` is ignored.
+///
+/// This is meant to sign `.meta` files, or the indexes of asset packs, when building a release of
+/// the app. The [`AssetServer`](crate::AssetServer) verifies the signatures of the `.meta` files it
+/// reads when [`AssetPlugin::meta_verifying_key`](crate::AssetPlugin::meta_verifying_key) is set.
+pub fn sign_asset_bytes(bytes: &[u8], asset_path: &AssetPath, key: &SigningKey) -> Vec<u8> {
+    let content = split_signature(bytes).map_or(bytes, |(content, _)| content);
+    let mut signed = content.to_vec();
+    if !signed.is_empty() && !signed.ends_with(b"\n") {
+        signed.push(b'\n');
+    }
+    let mut line = String::from(ASSET_SIGNATURE_PREFIX);
+    for byte in key.sign(&signed_message(&signed, asset_path)).to_bytes() {
+        let _ = write!(line, "{byte:02x}");
+    }
+    line.push('\n');
+    signed.extend_from_slice(line.as_bytes());
+    signed
+}
+
+/// Verifies `bytes` signed with [`sign_asset_bytes`] for `asset_path` against `key`, returning the
+/// signed content, without the signature.
+pub fn verify_asset_signature<'a>(
+    bytes: &'a [u8],
+    asset_path: &AssetPath,
+    key: &VerifyingKey,
+) -> Result<&'a [u8], AssetSignatureError> {
+    let (content, signature) = split_signature(bytes).ok_or(AssetSignatureError::Missing)?;
+    let signature = decode_signature(signature).ok_or(AssetSignatureError::Malformed)?;
+    key.verify_strict(&signed_message(content, asset_path), &signature)
+        .map_err(|_| AssetSignatureError::Invalid)?;
+    Ok(content)
+}
+
+/// Returns the message signed for the `content` of `asset_path`: the [`SIGNATURE_DOMAIN`], the
+/// name of the source and the path of the asset, with `/` separators on all platforms, each
+/// followed by a nul byte, and then the content.
+fn signed_message(content: &[u8], asset_path: &AssetPath) -> Vec<u8> {
+    let mut message = SIGNATURE_DOMAIN.to_vec();
+    if let Some(source) = asset_path.source().as_str() {
+        message.extend_from_slice(source.as_bytes());
+    }
+    message.push(0);
+    let mut components = asset_path.path().components().filter_map(|component| {
+        let std::path::Component::Normal(name) = component else {
+            return None;
+        };
+        Some(name.to_string_lossy())
+    });
+    if let Some(first) = components.next() {
+        message.extend_from_slice(first.as_bytes());
+        for name in components {
+            message.push(b'/');
+            message.extend_from_slice(name.as_bytes());
+        }
+    }
+    message.push(0);
+    message.extend_from_slice(content);
+    message
+}
+
+/// Splits `bytes` into their content and the hex-encoded signature on their last line, if that line
+/// starts with [`ASSET_SIGNATURE_PREFIX`].
+fn split_signature(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let trimmed = bytes.trim_ascii_end();
+    let line_start = trimmed
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let (content, line) = trimmed.split_at(line_start);
+    let signature = line.strip_prefix(ASSET_SIGNATURE_PREFIX.as_bytes())?;
+    Some((content, signature))
+}
+
+fn decode_signature(hex: &[u8]) -> Option<Signature> {
+    let mut bytes = [0; Signature::BYTE_SIZE];
+    if hex.len() != bytes.len() * 2 {
+        return None;
+    }
+    for (byte, digits) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        let digits = core::str::from_utf8(digits).ok()?;
+        *byte = u8::from_str_radix(digits, 16).ok()?;
+    }
+    Some(Signature::from_bytes(&bytes))
+}
+
+/// An error that occurs when verifying the signature of bytes with [`verify_asset_signature`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetSignatureError {
+    /// The bytes are not signed.
+    #[error("the bytes are not signed")]
+    Missing,
+    /// The signature of the bytes couldn't be decoded.
+    #[error("the signature is malformed")]
+    Malformed,
+    /// The signature doesn't match the bytes, or wasn't made with the key of the verifier.
+    #[error("the signature doesn't match the signed bytes")]
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_bytes_are_verified() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let verifying_key = key.verifying_key();
+        let meta = b"(\n    meta_format_version: \"1.0\",\n)";
+
+        let path = AssetPath::parse("textures/rock.png");
+
+        let signed = sign_asset_bytes(meta, &path, &key);
+        let content = verify_asset_signature(&signed, &path, &verifying_key).unwrap();
+        assert_eq!(content, b"(\n    meta_format_version: \"1.0\",\n)\n");
+        assert!(ron::de::from_bytes::<ron::Value>(&signed).is_ok());
+        // Signing signed bytes replaces their signature.
+        assert_eq!(sign_asset_bytes(&signed, &path, &key), signed);
+        // The label isn't signed.
+        assert!(
+            verify_asset_signature(&signed, &path.clone().with_label("a"), &verifying_key).is_ok()
+        );
+
+        // The signature is only valid for the asset it was made for.
+        for other_path in ["textures/lava.png", "other://textures/rock.png"] {
+            assert_eq!(
+                verify_asset_signature(&signed, &AssetPath::parse(other_path), &verifying_key),
+                Err(AssetSignatureError::Invalid)
+            );
+        }
+
+        let tampered = String::from_utf8(signed.clone())
+            .unwrap()
+            .replace("1.0", "2.0");
+        assert_eq!(
+            verify_asset_signature(tampered.as_bytes(), &path, &verifying_key),
+            Err(AssetSignatureError::Invalid)
+        );
+        let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        assert_eq!(
+            verify_asset_signature(&signed, &path, &other_key),
+            Err(AssetSignatureError::Invalid)
+        );
+        assert_eq!(
+            verify_asset_signature(meta, &path, &verifying_key),
+            Err(AssetSignatureError::Missing)
+        );
+        assert_eq!(
+            verify_asset_signature(b"()\n// signature: 00", &path, &verifying_key),
+            Err(AssetSignatureError::Malformed)
+        );
+    }
+}
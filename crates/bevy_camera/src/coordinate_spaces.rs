@@ -0,0 +1,225 @@
+use crate::{Camera, ViewportConversionError};
+use bevy_math::{Ray3d, Rect, URect, Vec2, Vec3};
+use bevy_transform::components::GlobalTransform;
+
+/// Conversions between the coordinate spaces of a [`Camera`].
+///
+/// Positions on screen can be expressed in many spaces, and mixing them up is an easy mistake to
+/// make, especially on displays with a scale factor other than `1.0`:
+///
+/// - **Logical** positions are in logical pixels relative to the top-left corner of the camera's
+///   render target. This is the space of [`Window::cursor_position`] and of picking pointer
+///   locations, and the "viewport position" expected by [`Camera::viewport_to_world`].
+/// - **Physical** positions are in physical pixels relative to the top-left corner of the render
+///   target. This is the space of [`Window::physical_cursor_position`].
+/// - **Viewport** positions are in logical pixels relative to the top-left corner of the camera's
+///   [`Viewport`](crate::Viewport).
+/// - **UI** positions are in physical pixels relative to the top-left corner of the camera's
+///   viewport. This is the space of the layout of UI nodes rendered by the camera.
+/// - **NDC** (Normalized Device Coordinates) range from `-1.0` (bottom left) to `1.0` (top right)
+///   across the viewport.
+/// - **World** positions are in the space of [`GlobalTransform`].
+///
+/// ```
+/// # use bevy_camera::{Camera, CoordinateSpaces};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::components::GlobalTransform;
+/// # use bevy_window::Window;
+/// fn cursor_ray(camera: Single<(&Camera, &GlobalTransform)>, window: Single<&Window>) {
+///     let (camera, camera_transform) = *camera;
+///     let Some(spaces) = CoordinateSpaces::new(camera, camera_transform) else {
+///         return;
+///     };
+///     if let Some(cursor) = window.cursor_position()
+///         && spaces.contains(cursor)
+///         && let Ok(ray) = spaces.logical_to_world_ray(cursor)
+///     {
+///         println!("{ray:?}");
+///     }
+/// }
+/// ```
+///
+/// [`Window::cursor_position`]: bevy_window::Window::cursor_position
+/// [`Window::physical_cursor_position`]: bevy_window::Window::physical_cursor_position
+#[derive(Debug, Clone, Copy)]
+pub struct CoordinateSpaces<'a> {
+    camera: &'a Camera,
+    camera_transform: &'a GlobalTransform,
+    scale_factor: f32,
+    physical_viewport: URect,
+    logical_viewport: Rect,
+}
+
+impl<'a> CoordinateSpaces<'a> {
+    /// Creates the coordinate spaces of `camera`.
+    ///
+    /// Returns [`None`] if the render target of the camera isn't known yet, which is the case
+    /// before the camera is first updated.
+    pub fn new(camera: &'a Camera, camera_transform: &'a GlobalTransform) -> Option<Self> {
+        Some(Self {
+            camera,
+            camera_transform,
+            scale_factor: camera.target_scaling_factor()?,
+            physical_viewport: camera.physical_viewport_rect()?,
+            logical_viewport: camera.logical_viewport_rect()?,
+        })
+    }
+
+    /// The camera these coordinate spaces belong to.
+    pub fn camera(&self) -> &'a Camera {
+        self.camera
+    }
+
+    /// The scale factor of the render target: the number of physical pixels per logical pixel.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// The viewport of the camera, in logical pixels relative to the render target.
+    pub fn logical_viewport_rect(&self) -> Rect {
+        self.logical_viewport
+    }
+
+    /// The viewport of the camera, in physical pixels relative to the render target.
+    pub fn physical_viewport_rect(&self) -> URect {
+        self.physical_viewport
+    }
+
+    /// Returns `true` if the logical position is inside the viewport of the camera.
+    pub fn contains(&self, logical_position: Vec2) -> bool {
+        self.logical_viewport.contains(logical_position)
+    }
+
+    /// Converts a logical position to a physical position.
+    pub fn logical_to_physical(&self, logical_position: Vec2) -> Vec2 {
+        logical_position * self.scale_factor
+    }
+
+    /// Converts a physical position to a logical position.
+    pub fn physical_to_logical(&self, physical_position: Vec2) -> Vec2 {
+        physical_position / self.scale_factor
+    }
+
+    /// Converts a logical position to a position relative to the viewport of the camera.
+    pub fn logical_to_viewport(&self, logical_position: Vec2) -> Vec2 {
+        logical_position - self.logical_viewport.min
+    }
+
+    /// Converts a position relative to the viewport of the camera to a logical position.
+    pub fn viewport_to_logical(&self, viewport_position: Vec2) -> Vec2 {
+        viewport_position + self.logical_viewport.min
+    }
+
+    /// Converts a logical position to the physical UI space of the camera's viewport.
+    pub fn logical_to_ui(&self, logical_position: Vec2) -> Vec2 {
+        self.logical_to_physical(logical_position) - self.physical_viewport.min.as_vec2()
+    }
+
+    /// Converts a position in the physical UI space of the camera's viewport to a logical position.
+    pub fn ui_to_logical(&self, ui_position: Vec2) -> Vec2 {
+        self.physical_to_logical(ui_position + self.physical_viewport.min.as_vec2())
+    }
+
+    /// Converts a logical position to Normalized Device Coordinates.
+    ///
+    /// See [`Camera::viewport_to_ndc`].
+    pub fn logical_to_ndc(&self, logical_position: Vec2) -> Vec2 {
+        let rect_relative =
+            (logical_position - self.logical_viewport.min) / self.logical_viewport.size();
+        let ndc = rect_relative * 2. - Vec2::ONE;
+        // Flip the Y co-ordinate from the top to the bottom to enter NDC.
+        Vec2::new(ndc.x, -ndc.y)
+    }
+
+    /// Converts Normalized Device Coordinates to a logical position.
+    pub fn ndc_to_logical(&self, ndc: Vec2) -> Vec2 {
+        let rect_relative = (Vec2::new(ndc.x, -ndc.y) + Vec2::ONE) / 2.;
+        rect_relative * self.logical_viewport.size() + self.logical_viewport.min
+    }
+
+    /// Returns the ray from the camera passing through the logical position.
+    ///
+    /// See [`Camera::viewport_to_world`].
+    pub fn logical_to_world_ray(
+        &self,
+        logical_position: Vec2,
+    ) -> Result<Ray3d, ViewportConversionError> {
+        self.camera
+            .viewport_to_world(self.camera_transform, logical_position)
+    }
+
+    /// Returns the 2D world position under the logical position, for cameras looking along the Z
+    /// axis.
+    ///
+    /// See [`Camera::viewport_to_world_2d`].
+    pub fn logical_to_world_2d(
+        &self,
+        logical_position: Vec2,
+    ) -> Result<Vec2, ViewportConversionError> {
+        self.camera
+            .viewport_to_world_2d(self.camera_transform, logical_position)
+    }
+
+    /// Returns the logical position of a point in world space.
+    ///
+    /// See [`Camera::world_to_viewport`].
+    pub fn world_to_logical(&self, world_position: Vec3) -> Result<Vec2, ViewportConversionError> {
+        self.camera
+            .world_to_viewport(self.camera_transform, world_position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RenderTargetInfo, Viewport};
+    use bevy_math::UVec2;
+
+    #[test]
+    fn round_trips() {
+        let mut camera = Camera {
+            viewport: Some(Viewport {
+                physical_position: UVec2::new(200, 100),
+                physical_size: UVec2::new(800, 600),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        camera.computed.target_info = Some(RenderTargetInfo {
+            physical_size: UVec2::new(1600, 1200),
+            scale_factor: 2.0,
+        });
+        let transform = GlobalTransform::IDENTITY;
+        let spaces = CoordinateSpaces::new(&camera, &transform).unwrap();
+
+        let logical = Vec2::new(300.0, 200.0);
+        assert_eq!(spaces.logical_to_physical(logical), Vec2::new(600.0, 400.0));
+        assert_eq!(spaces.logical_to_viewport(logical), Vec2::new(200.0, 150.0));
+        assert_eq!(spaces.logical_to_ui(logical), Vec2::new(400.0, 300.0));
+        assert_eq!(spaces.logical_to_ndc(logical), Vec2::ZERO);
+        assert!(spaces.contains(logical));
+        assert!(!spaces.contains(Vec2::new(50.0, 50.0)));
+
+        assert_eq!(
+            spaces.physical_to_logical(spaces.logical_to_physical(logical)),
+            logical
+        );
+        assert_eq!(
+            spaces.viewport_to_logical(spaces.logical_to_viewport(logical)),
+            logical
+        );
+        assert_eq!(spaces.ui_to_logical(spaces.logical_to_ui(logical)), logical);
+        assert_eq!(
+            spaces.ndc_to_logical(spaces.logical_to_ndc(logical)),
+            logical
+        );
+        assert_eq!(
+            spaces.logical_to_ndc(Vec2::new(100.0, 350.0)),
+            Vec2::new(-1.0, -1.0)
+        );
+        assert_eq!(
+            spaces.logical_to_ndc(camera.logical_viewport_rect().unwrap().min),
+            camera.viewport_to_ndc(Vec2::new(100.0, 50.0)).unwrap()
+        );
+    }
+}
@@ -3,7 +3,21 @@
 @group(0) @binding(0) var in_texture: texture_2d<f32>;
 @group(0) @binding(1) var in_sampler: sampler;
 
+#ifdef ENCODE_SRGB
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        return value * 12.92;
+    }
+    return 1.055 * pow(value, 1.0 / 2.4) - 0.055;
+}
+#endif
+
 @fragment
 fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(in_texture, in_sampler, in.uv);
+    let color = textureSample(in_texture, in_sampler, in.uv);
+#ifdef ENCODE_SRGB
+    return vec4(linear_to_srgb(color.r), linear_to_srgb(color.g), linear_to_srgb(color.b), color.a);
+#else
+    return color;
+#endif
 }
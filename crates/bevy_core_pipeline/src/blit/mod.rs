@@ -86,6 +86,9 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// Whether the colors are encoded to sRGB when written, for a `texture_format` that has no
+    /// sRGB variant, e.g. [`TextureFormat::Rgb10a2Unorm`].
+    pub encode_srgb: bool,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
@@ -98,6 +101,11 @@ impl SpecializedRenderPipeline for BlitPipeline {
             vertex: self.fullscreen_shader.to_vertex_state(),
             fragment: Some(FragmentState {
                 shader: self.fragment_shader.clone(),
+                shader_defs: if key.encode_srgb {
+                    vec!["ENCODE_SRGB".into()]
+                } else {
+                    vec![]
+                },
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
                     blend: key.blend_state,
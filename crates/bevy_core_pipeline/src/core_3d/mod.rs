@@ -64,7 +64,7 @@ use bevy_render::{
     renderer::RenderDevice,
     sync_world::{MainEntity, RenderEntity},
     texture::{ColorAttachment, TextureCache},
-    view::{ExtractedView, ViewDepthTexture},
+    view::{ExtractedView, MsaaTextureFormats, ViewDepthTexture},
     Extract, ExtractSchedule, Render, RenderApp, RenderSystems,
 };
 use nonmax::NonMaxU32;
@@ -80,10 +80,14 @@ use crate::{
         DEFERRED_PREPASS_FORMAT,
     },
     prepass::{
+        readback::{
+            extract_prepass_readbacks, queue_prepass_readbacks, ExtractedPrepassReadbacks,
+            PrepassReadbackTexture,
+        },
         AlphaMask3dPrepass, DeferredPrepass, DeferredPrepassDoubleBuffer, DepthPrepass,
-        DepthPrepassDoubleBuffer, MotionVectorPrepass, NormalPrepass, Opaque3dPrepass,
-        OpaqueNoLightmap3dBatchSetKey, OpaqueNoLightmap3dBinKey, ViewPrepassTextures,
-        MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
+        DepthPrepassDoubleBuffer, DepthPyramidPrepass, MotionVectorPrepass, NormalPrepass,
+        Opaque3dPrepass, OpaqueNoLightmap3dBatchSetKey, OpaqueNoLightmap3dBinKey,
+        ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
     },
     schedule::Core3d,
     skybox::SkyboxPlugin,
@@ -102,6 +106,10 @@ impl Plugin for Core3dPlugin {
             .register_required_components::<Camera3d, Tonemapping>()
             .add_plugins((SkyboxPlugin, ExtractComponentPlugin::<Camera3d>::default()))
             .add_systems(PostUpdate, check_msaa);
+        // The prepass textures are multisampled like the main texture.
+        app.world_mut()
+            .get_resource_or_init::<MsaaTextureFormats>()
+            .extend([NORMAL_PREPASS_FORMAT, MOTION_VECTOR_PREPASS_FORMAT]);
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -121,8 +129,10 @@ impl Plugin for Core3dPlugin {
             .init_resource::<ViewBinnedRenderPhases<Opaque3dDeferred>>()
             .init_resource::<ViewBinnedRenderPhases<AlphaMask3dDeferred>>()
             .init_resource::<ViewSortedRenderPhases<Transparent3d>>()
+            .init_resource::<ExtractedPrepassReadbacks>()
             .add_systems(ExtractSchedule, extract_core_3d_camera_phases)
             .add_systems(ExtractSchedule, extract_camera_prepass_phase)
+            .add_systems(ExtractSchedule, extract_prepass_readbacks)
             .add_systems(
                 Render,
                 (
@@ -133,6 +143,9 @@ impl Plugin for Core3dPlugin {
                         .ambiguous_with(RenderSystems::PrepareViews),
                     prepare_core_3d_depth_textures.in_set(RenderSystems::PrepareResources),
                     prepare_prepass_textures.in_set(RenderSystems::PrepareResources),
+                    queue_prepass_readbacks
+                        .after(prepare_prepass_textures)
+                        .in_set(RenderSystems::PrepareResources),
                 ),
             )
             .add_schedule(Core3d::base_schedule())
@@ -549,6 +562,7 @@ pub fn extract_camera_prepass_phase(
                 Has<DeferredPrepass>,
                 Has<DepthPrepassDoubleBuffer>,
                 Has<DeferredPrepassDoubleBuffer>,
+                Has<DepthPyramidPrepass>,
             ),
             With<Camera3d>,
         >,
@@ -569,6 +583,7 @@ pub fn extract_camera_prepass_phase(
         deferred_prepass,
         depth_prepass_double_buffer,
         deferred_prepass_double_buffer,
+        depth_pyramid_prepass,
     ) in cameras_3d.iter()
     {
         if !camera.is_active {
@@ -648,6 +663,12 @@ pub fn extract_camera_prepass_phase(
         } else {
             camera_commands.remove::<DeferredPrepassDoubleBuffer>();
         }
+
+        if depth_pyramid_prepass {
+            camera_commands.insert(DepthPyramidPrepass);
+        } else {
+            camera_commands.remove::<DepthPyramidPrepass>();
+        }
     }
 
     opaque_3d_prepass_phases.retain(|view_entity, _| live_entities.contains(view_entity));
@@ -765,6 +786,7 @@ pub fn prepare_prepass_textures(
     alpha_mask_3d_prepass_phases: Res<ViewBinnedRenderPhases<AlphaMask3dPrepass>>,
     opaque_3d_deferred_phases: Res<ViewBinnedRenderPhases<Opaque3dDeferred>>,
     alpha_mask_3d_deferred_phases: Res<ViewBinnedRenderPhases<AlphaMask3dDeferred>>,
+    prepass_readbacks: Res<ExtractedPrepassReadbacks>,
     views_3d: Query<(
         Entity,
         &ExtractedCamera,
@@ -813,6 +835,21 @@ pub fn prepare_prepass_textures(
 
         let size = physical_target_size.to_extents();
 
+        // Textures read back to the CPU are copied from.
+        let readback_usages = |texture| {
+            let read_back = prepass_readbacks
+                .0
+                .get(&entity)
+                .is_some_and(|readbacks| readbacks.iter().any(|&(_, read)| read == texture));
+            if read_back {
+                TextureUsages::COPY_SRC
+            } else {
+                TextureUsages::empty()
+            }
+        };
+        let depth_readback_usages = readback_usages(PrepassReadbackTexture::Depth);
+        let normal_readback_usages = readback_usages(PrepassReadbackTexture::Normal);
+
         let cached_depth_texture1 = depth_prepass.then(|| {
             depth_textures1
                 .entry(camera.target.clone())
@@ -826,7 +863,8 @@ pub fn prepare_prepass_textures(
                         format: CORE_3D_DEPTH_FORMAT,
                         usage: TextureUsages::COPY_DST
                             | TextureUsages::RENDER_ATTACHMENT
-                            | TextureUsages::TEXTURE_BINDING,
+                            | TextureUsages::TEXTURE_BINDING
+                            | depth_readback_usages,
                         view_formats: &[],
                     };
                     texture_cache.get(&render_device, descriptor)
@@ -847,7 +885,8 @@ pub fn prepare_prepass_textures(
                         format: CORE_3D_DEPTH_FORMAT,
                         usage: TextureUsages::COPY_DST
                             | TextureUsages::RENDER_ATTACHMENT
-                            | TextureUsages::TEXTURE_BINDING,
+                            | TextureUsages::TEXTURE_BINDING
+                            | depth_readback_usages,
                         view_formats: &[],
                     };
                     texture_cache.get(&render_device, descriptor)
@@ -869,7 +908,8 @@ pub fn prepare_prepass_textures(
                             dimension: TextureDimension::D2,
                             format: NORMAL_PREPASS_FORMAT,
                             usage: TextureUsages::RENDER_ATTACHMENT
-                                | TextureUsages::TEXTURE_BINDING,
+                                | TextureUsages::TEXTURE_BINDING
+                                | normal_readback_usages,
                             view_formats: &[],
                         },
                     )
@@ -1,11 +1,12 @@
-//! Generation of hierarchical Z buffers for occlusion culling.
+//! Generation of hierarchical Z buffers.
 //!
-//! Currently, this module only supports generation of hierarchical Z buffers
-//! for occlusion culling.
+//! Hierarchical Z buffers are generated for views with occlusion culling, and
+//! for views with a [`DepthPyramidPrepass`], which shares the same depth
+//! pyramid with screen space effects.
 
 use core::array;
 
-use crate::mip_generation::DownsampleShaders;
+use crate::{mip_generation::DownsampleShaders, prepass::DepthPyramidPrepass};
 
 use bevy_asset::Handle;
 use bevy_derive::{Deref, DerefMut};
@@ -61,12 +62,15 @@ pub const DEPTH_PYRAMID_MIP_COUNT: usize = 12;
 ///
 /// This system won't do anything if occlusion culling isn't on.
 pub fn early_downsample_depth(
-    view: ViewQuery<(
-        &ViewDepthPyramid,
-        &ViewDownsampleDepthBindGroup,
-        &ViewDepthTexture,
-        Option<&OcclusionCullingSubviewEntities>,
-    )>,
+    view: ViewQuery<
+        (
+            &ViewDepthPyramid,
+            &ViewDownsampleDepthBindGroup,
+            &ViewDepthTexture,
+            Option<&OcclusionCullingSubviewEntities>,
+        ),
+        With<OcclusionCulling>,
+    >,
     shadow_view_query: Query<(
         &ViewDepthPyramid,
         &ViewDownsampleDepthBindGroup,
@@ -136,12 +140,15 @@ pub fn early_downsample_depth(
 ///
 /// This system won't do anything if occlusion culling isn't on.
 pub fn late_downsample_depth(
-    view: ViewQuery<(
-        &ViewDepthPyramid,
-        &ViewDownsampleDepthBindGroup,
-        &ViewDepthTexture,
-        Option<&OcclusionCullingSubviewEntities>,
-    )>,
+    view: ViewQuery<
+        (
+            &ViewDepthPyramid,
+            &ViewDownsampleDepthBindGroup,
+            &ViewDepthTexture,
+            Option<&OcclusionCullingSubviewEntities>,
+        ),
+        With<OcclusionCulling>,
+    >,
     shadow_view_query: Query<(
         &ViewDepthPyramid,
         &ViewDownsampleDepthBindGroup,
@@ -199,6 +206,48 @@ pub fn late_downsample_depth(
     }
 }
 
+/// Produces a hierarchical Z-buffer (depth pyramid) from the prepass depth, for
+/// views with a [`DepthPyramidPrepass`].
+///
+/// This runs at the end of the prepass, so that the depth pyramid of the
+/// current frame is available to screen space effects running before the main
+/// pass. For views that also use occlusion culling, the depth pyramid is
+/// regenerated by [`late_downsample_depth`] at the end of the frame.
+pub fn prepass_downsample_depth(
+    view: ViewQuery<
+        (
+            &ViewDepthPyramid,
+            &ViewDownsampleDepthBindGroup,
+            &ViewDepthTexture,
+        ),
+        With<DepthPyramidPrepass>,
+    >,
+    downsample_depth_pipelines: Option<Res<DownsampleDepthPipelines>>,
+    pipeline_cache: Res<PipelineCache>,
+    mut ctx: RenderContext,
+) {
+    let Some(downsample_depth_pipelines) = downsample_depth_pipelines.as_deref() else {
+        return;
+    };
+
+    let (view_depth_pyramid, view_downsample_depth_bind_group, view_depth_texture) =
+        view.into_inner();
+
+    downsample_depth(
+        "prepass_downsample_depth",
+        &mut ctx,
+        downsample_depth_pipelines,
+        &pipeline_cache,
+        view_depth_pyramid,
+        view_downsample_depth_bind_group,
+        uvec2(
+            view_depth_texture.texture.width(),
+            view_depth_texture.texture.height(),
+        ),
+        view_depth_texture.texture.sample_count(),
+    );
+}
+
 /// Produces a depth pyramid from the current depth buffer for a single view.
 /// The resulting depth pyramid can be used for occlusion testing.
 fn downsample_depth(
@@ -529,7 +578,9 @@ pub fn create_depth_pyramid_dummy_texture(
 /// Stores a hierarchical Z-buffer for a view, which is a series of mipmaps
 /// useful for efficient occlusion culling.
 ///
-/// This will only be present on a view when occlusion culling is enabled.
+/// Each texel holds the farthest depth of the area it covers. This will only
+/// be present on a view when occlusion culling is enabled, or when the view
+/// has a [`DepthPyramidPrepass`].
 #[derive(Component)]
 pub struct ViewDepthPyramid {
     /// A texture view containing the entire depth texture.
@@ -690,13 +741,20 @@ impl ViewDepthPyramid {
     }
 }
 
-/// Creates depth pyramids for views that have occlusion culling enabled.
+/// Creates depth pyramids for views that have occlusion culling enabled, or
+/// that have a [`DepthPyramidPrepass`].
 pub fn prepare_view_depth_pyramids(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
     mut texture_cache: ResMut<TextureCache>,
     depth_pyramid_dummy_texture: Res<DepthPyramidDummyTexture>,
-    views: Query<(Entity, &ExtractedView), (With<OcclusionCulling>, Without<NoIndirectDrawing>)>,
+    views: Query<
+        (Entity, &ExtractedView),
+        Or<(
+            (With<OcclusionCulling>, Without<NoIndirectDrawing>),
+            With<DepthPyramidPrepass>,
+        )>,
+    >,
 ) {
     for (view_entity, view) in &views {
         commands.entity(view_entity).insert(ViewDepthPyramid::new(
@@ -713,12 +771,13 @@ pub fn prepare_view_depth_pyramids(
 /// The bind group that we use to attach the depth buffer and depth pyramid for
 /// a view to the `downsample_depth.wgsl` shader.
 ///
-/// This will only be present for a view if occlusion culling is enabled.
+/// This will only be present for a view if occlusion culling is enabled, or if
+/// the view has a [`DepthPyramidPrepass`].
 #[derive(Component, Deref, DerefMut)]
 pub struct ViewDownsampleDepthBindGroup(BindGroup);
 
-/// Creates the [`ViewDownsampleDepthBindGroup`]s for all views with occlusion
-/// culling enabled.
+/// Creates the [`ViewDownsampleDepthBindGroup`]s for all views with a depth
+/// pyramid.
 pub fn prepare_downsample_depth_view_bind_groups(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
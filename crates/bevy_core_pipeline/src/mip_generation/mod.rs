@@ -12,9 +12,10 @@
 
 use crate::core_3d::prepare_core_3d_depth_textures;
 use crate::deferred::node::early_deferred_prepass;
+use crate::deferred::node::late_deferred_prepass;
 use crate::mip_generation::experimental::depth::{
-    self, early_downsample_depth, late_downsample_depth, DownsampleDepthPipeline,
-    DownsampleDepthPipelines,
+    self, early_downsample_depth, late_downsample_depth, prepass_downsample_depth,
+    DownsampleDepthPipeline, DownsampleDepthPipelines,
 };
 use crate::prepass::node::late_prepass;
 use crate::schedule::{Core3d, Core3dSystems};
@@ -299,6 +300,9 @@ impl Plugin for MipGenerationPlugin {
                     early_downsample_depth
                         .after(early_deferred_prepass)
                         .before(late_prepass),
+                    prepass_downsample_depth
+                        .after(late_deferred_prepass)
+                        .in_set(Core3dSystems::Prepass),
                     late_downsample_depth.in_set(Core3dSystems::PostProcess),
                 ),
             )
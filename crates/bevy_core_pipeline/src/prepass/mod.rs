@@ -26,6 +26,7 @@
 //! Currently only works for 3D.
 
 pub mod node;
+pub mod readback;
 
 use core::ops::Range;
 
@@ -77,6 +78,21 @@ pub struct MotionVectorPrepass;
 #[reflect(Component, Default)]
 pub struct DeferredPrepass;
 
+/// If added to a [`bevy_camera::Camera3d`] then a hierarchical Z-buffer (depth pyramid) will be
+/// generated from the prepass depth, and made available to subsequent passes as a
+/// [`ViewDepthPyramid`](crate::mip_generation::experimental::depth::ViewDepthPyramid).
+///
+/// Views using occlusion culling already have a depth pyramid. With this component, it's also
+/// refreshed right after the prepass, so that effects like screen space ambient occlusion can share
+/// it instead of building their own depth hierarchy. Screen space reflections don't sample it yet.
+///
+/// Generating the depth pyramid requires compute shaders, so this has no effect on platforms that
+/// don't support GPU culling, such as GLES 3.0 and WebGL2.
+#[derive(Component, Default, Reflect, Clone)]
+#[reflect(Component, Default, Clone)]
+#[require(DepthPrepass)]
+pub struct DepthPyramidPrepass;
+
 /// Allows querying the previous frame's [`DepthPrepass`].
 #[derive(Component, Default, Reflect, Clone)]
 #[reflect(Component, Default, Clone)]
@@ -0,0 +1,120 @@
+//! Reads the prepass textures of a camera back to the CPU.
+
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_log::warn_once;
+use bevy_render::{gpu_readback::RenderTextureReadbacks, sync_world::RenderEntity, Extract};
+
+use super::ViewPrepassTextures;
+
+/// Reads a prepass texture of a camera back to the CPU every frame, e.g. for CPU-side picking,
+/// screenshots with depth, or to capture training data.
+///
+/// Like a [`Readback`](bevy_render::gpu_readback::Readback), this component is spawned on its own
+/// entity, and a [`ReadbackComplete`](bevy_render::gpu_readback::ReadbackComplete) event is
+/// triggered on it with the texture, which
+/// [`ReadbackComplete::to_image`](bevy_render::gpu_readback::ReadbackComplete::to_image) converts
+/// to an [`Image`](bevy_image::Image):
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_core_pipeline::prepass::{readback::PrepassReadback, DepthPrepass};
+/// # use bevy_render::{gpu_readback::ReadbackComplete, view::Msaa};
+/// # use bevy_camera::Camera3d;
+/// fn setup(mut commands: Commands) {
+///     let camera = commands.spawn((Camera3d::default(), DepthPrepass, Msaa::Off)).id();
+///     commands
+///         .spawn(PrepassReadback::depth(camera))
+///         .observe(|readback: On<ReadbackComplete>| {
+///             let depth = readback.to_image().unwrap();
+///         });
+/// }
+/// ```
+///
+/// The camera needs the prepass of the texture, e.g. [`DepthPrepass`](super::DepthPrepass), and
+/// [`Msaa::Off`](bevy_render::view::Msaa::Off), as multisampled textures can't be read back.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PrepassReadback {
+    /// The camera to read the texture of.
+    pub camera: Entity,
+    /// The prepass texture to read.
+    pub texture: PrepassReadbackTexture,
+}
+
+/// The prepass texture read by a [`PrepassReadback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PrepassReadbackTexture {
+    /// The depth texture of the [`DepthPrepass`](super::DepthPrepass), in
+    /// [`CORE_3D_DEPTH_FORMAT`](crate::core_3d::CORE_3D_DEPTH_FORMAT). Depth is reversed: `1.0`
+    /// is on the near plane, and `0.0` at infinity.
+    Depth,
+    /// The normal texture of the [`NormalPrepass`](super::NormalPrepass), in
+    /// [`NORMAL_PREPASS_FORMAT`](super::NORMAL_PREPASS_FORMAT). World space normals are mapped
+    /// from `-1.0..1.0` to `0.0..1.0`.
+    Normal,
+}
+
+impl PrepassReadback {
+    /// Reads the depth prepass texture of `camera`.
+    pub fn depth(camera: Entity) -> Self {
+        Self {
+            camera,
+            texture: PrepassReadbackTexture::Depth,
+        }
+    }
+
+    /// Reads the normal prepass texture of `camera`.
+    pub fn normal(camera: Entity) -> Self {
+        Self {
+            camera,
+            texture: PrepassReadbackTexture::Normal,
+        }
+    }
+}
+
+/// The [`PrepassReadback`]s of each render world camera, with their main world entity.
+#[derive(Resource, Default)]
+pub struct ExtractedPrepassReadbacks(pub EntityHashMap<Vec<(Entity, PrepassReadbackTexture)>>);
+
+pub fn extract_prepass_readbacks(
+    mut extracted: ResMut<ExtractedPrepassReadbacks>,
+    readbacks: Extract<Query<(Entity, &PrepassReadback)>>,
+    cameras: Extract<Query<&RenderEntity>>,
+) {
+    extracted.0.clear();
+    for (entity, readback) in &readbacks {
+        let Ok(camera) = cameras.get(readback.camera) else {
+            continue;
+        };
+        extracted
+            .0
+            .entry(camera.id())
+            .or_default()
+            .push((entity, readback.texture));
+    }
+}
+
+pub fn queue_prepass_readbacks(
+    extracted: Res<ExtractedPrepassReadbacks>,
+    views: Query<&ViewPrepassTextures>,
+    mut texture_readbacks: ResMut<RenderTextureReadbacks>,
+) {
+    for (view, readbacks) in &extracted.0 {
+        let Ok(textures) = views.get(*view) else {
+            continue;
+        };
+        for &(entity, texture) in readbacks {
+            let attachment = match texture {
+                PrepassReadbackTexture::Depth => textures.depth.as_ref(),
+                PrepassReadbackTexture::Normal => textures.normal.as_ref(),
+            };
+            let Some(attachment) = attachment else {
+                warn_once!(
+                    "The camera of a `PrepassReadback` of the {texture:?} texture doesn't have \
+                    this prepass."
+                );
+                continue;
+            };
+            texture_readbacks.push(entity, attachment.texture.texture.clone());
+        }
+    }
+}
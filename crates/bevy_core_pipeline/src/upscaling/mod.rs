@@ -1,6 +1,6 @@
 use crate::blit::{BlitPipeline, BlitPipelineKey};
 use bevy_app::prelude::*;
-use bevy_camera::CameraOutputMode;
+use bevy_camera::{CameraOutputMode, NormalizedRenderTarget};
 use bevy_ecs::prelude::*;
 use bevy_platform::collections::HashSet;
 use bevy_render::{
@@ -76,10 +76,20 @@ fn prepare_view_upscaling_pipelines(
             None
         };
 
+        let texture_format = view_target.out_texture_view_format();
+        // Window surfaces are rendered to through an sRGB view, which normalized formats without an
+        // sRGB variant, e.g. a requested `Rgb10a2Unorm` surface, don't have.
+        let encode_srgb = camera
+            .is_some_and(|camera| matches!(camera.target, Some(NormalizedRenderTarget::Window(_))))
+            && matches!(
+                texture_format,
+                TextureFormat::Rgb10a2Unorm | TextureFormat::Rgba16Unorm
+            );
         let key = BlitPipelineKey {
-            texture_format: view_target.out_texture_view_format(),
+            texture_format,
             blend_state,
             samples: 1,
+            encode_srgb,
         };
 
         if maybe_pipeline.is_none_or(|ViewUpscalingPipeline(_, cached_key)| *cached_key != key) {
@@ -1,12 +1,13 @@
 use crate::{blit::BlitPipeline, upscaling::ViewUpscalingPipeline};
-use bevy_camera::{CameraOutputMode, ClearColor, ClearColorConfig};
+use bevy_camera::{CameraOutputMode, ClearColor, ClearColorConfig, NormalizedRenderTarget};
 use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
 use bevy_render::{
     camera::ExtractedCamera,
     diagnostic::RecordDiagnostics,
     render_resource::{BindGroup, PipelineCache, RenderPassDescriptor, TextureViewId},
     renderer::{RenderContext, ViewQuery},
-    view::ViewTarget,
+    view::{ExtractedWindows, ViewTarget},
 };
 
 #[derive(Default)]
@@ -23,6 +24,7 @@ pub fn upscaling(
     pipeline_cache: Res<PipelineCache>,
     blit_pipeline: Res<BlitPipeline>,
     clear_color_global: Res<ClearColor>,
+    windows: Res<ExtractedWindows>,
     mut cache: Local<UpscalingBindGroupCache>,
     mut ctx: RenderContext,
 ) {
@@ -88,8 +90,20 @@ pub fn upscaling(
         if let Some(camera) = camera
             && let Some(viewport) = &camera.viewport
         {
-            let size = viewport.physical_size;
-            let position = viewport.physical_position;
+            let mut size = viewport.physical_size;
+            let mut position = viewport.physical_position;
+            // While the surface of a window waits for a resize to settle, it keeps its previous
+            // size and the frame is stretched onto it, so scale the viewport the same way.
+            if let Some(NormalizedRenderTarget::Window(window)) = &camera.target
+                && let Some(window) = windows.get(&window.entity())
+                && let Some(target_size) = camera.physical_target_size
+                && window.resize_pending()
+            {
+                let surface_size = UVec2::new(window.physical_width, window.physical_height);
+                position = position * surface_size / target_size.max(UVec2::ONE);
+                size = (size * surface_size / target_size.max(UVec2::ONE))
+                    .min(surface_size.saturating_sub(position));
+            }
             render_pass.set_scissor_rect(position.x, position.y, size.x, size.y);
         }
 
@@ -15,7 +15,7 @@ use bevy_ecs::{
 };
 use bevy_picking::Pickable;
 use bevy_reflect::Reflect;
-use bevy_render::storage::ShaderBuffer;
+use bevy_render::{diagnostic::RenderDiagnosticsPlugin, storage::ShaderBuffer};
 use bevy_text::{RemSize, TextColor, TextFont, TextSpan};
 use bevy_time::common_conditions::on_timer;
 use bevy_ui::{
@@ -120,6 +120,11 @@ pub struct FpsOverlayConfig {
     pub refresh_interval: Duration,
     /// Configuration of the frame time graph
     pub frame_time_graph_config: FrameTimeGraphConfig,
+    /// Displays the GPU time of the frame next to the FPS if true.
+    ///
+    /// The GPU time is only measured when the [`RenderDiagnosticsPlugin`] is added, and the
+    /// backend supports timestamp queries.
+    pub show_gpu_time: bool,
 }
 
 impl Default for FpsOverlayConfig {
@@ -131,6 +136,7 @@ impl Default for FpsOverlayConfig {
             refresh_interval: Duration::from_millis(100),
             // TODO set this to display refresh rate if possible
             frame_time_graph_config: FrameTimeGraphConfig::target_fps(60.0),
+            show_gpu_time: true,
         }
     }
 }
@@ -208,7 +214,10 @@ fn setup(
                 FpsText,
                 Pickable::IGNORE,
             ))
-            .with_child((TextSpan::default(), overlay_config.text_config.clone()));
+            .with_children(|p| {
+                p.spawn((TextSpan::default(), overlay_config.text_config.clone()));
+                p.spawn((TextSpan::default(), overlay_config.text_config.clone()));
+            });
 
             #[cfg(all(target_arch = "wasm32", not(feature = "webgpu")))]
             {
@@ -256,15 +265,28 @@ fn setup(
 
 fn update_text(
     diagnostic: Res<DiagnosticsStore>,
+    overlay_config: Res<FpsOverlayConfig>,
     query: Query<Entity, With<FpsText>>,
     mut writer: TextUiWriter,
 ) {
-    if let Ok(entity) = query.single()
-        && let Some(fps) = diagnostic.get(&FrameTimeDiagnosticsPlugin::FPS)
+    let Ok(entity) = query.single() else {
+        return;
+    };
+
+    if let Some(fps) = diagnostic.get(&FrameTimeDiagnosticsPlugin::FPS)
         && let Some(value) = fps.smoothed()
     {
         *writer.text(entity, 1) = format!("{value:.2}");
     }
+
+    let gpu_time = diagnostic
+        .get(&RenderDiagnosticsPlugin::ELAPSED_GPU)
+        .and_then(|gpu_time| gpu_time.smoothed())
+        .filter(|_| overlay_config.show_gpu_time);
+    *writer.text(entity, 2) = match gpu_time {
+        Some(value) => format!(" GPU: {value:.2} ms"),
+        None => String::new(),
+    };
 }
 
 fn customize_overlay(
@@ -0,0 +1,242 @@
+//! Entity pools recycle entities instead of despawning them and spawning new ones.
+//!
+//! Games often spawn and despawn many short-lived entities with the same components,
+//! such as bullets, particles or the rows of a scrolling list.
+//! Spawning an entity allocates it and moves its components into a table,
+//! which adds up when it happens hundreds of times per frame.
+//!
+//! An [`EntityPool`] spawns its entities ahead of time, and keeps the released ones around as [`Disabled`] entities,
+//! which don't show up in queries.
+//! Acquiring an entity from the pool re-enables one of them, and resets its components
+//! by inserting the bundle of the pool again.
+//!
+//! ```
+//! use bevy_ecs::{entity_pool::EntityPool, prelude::*};
+//!
+//! #[derive(Component, Clone, Default)]
+//! struct Bullet {
+//!     traveled: f32,
+//! }
+//!
+//! fn setup(mut commands: Commands) {
+//!     let mut pool = EntityPool::new(Bullet::default());
+//!     pool.prespawn(&mut commands, 256);
+//!     commands.insert_resource(pool);
+//! }
+//!
+//! fn fire(mut commands: Commands, mut pool: ResMut<EntityPool<Bullet>>) {
+//!     pool.acquire(&mut commands);
+//! }
+//!
+//! fn update_bullets(
+//!     mut commands: Commands,
+//!     mut pool: ResMut<EntityPool<Bullet>>,
+//!     mut bullets: Query<(Entity, &mut Bullet)>,
+//! ) {
+//!     for (entity, mut bullet) in &mut bullets {
+//!         bullet.traveled += 1.0;
+//!         if bullet.traveled > 100.0 {
+//!             pool.release(&mut commands, entity);
+//!         }
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(setup);
+//! # bevy_ecs::system::assert_is_system(fire);
+//! # bevy_ecs::system::assert_is_system(update_bullets);
+//! ```
+//!
+//! ## Change detection
+//!
+//! As acquiring an entity inserts the bundle again, [`Changed`] filters see the components of acquired entities,
+//! but [`Added`] filters only see the newly spawned ones.
+//! Releasing an entity inserts [`Disabled`], and acquiring it removes [`Disabled`],
+//! so observers of these lifecycle events can react to entities entering and leaving the pool.
+//!
+//! [`Changed`]: crate::query::Changed
+//! [`Added`]: crate::query::Added
+
+use crate::{
+    bundle::Bundle,
+    entity::{Entity, EntityIndexSet},
+    entity_disabling::Disabled,
+    resource::Resource,
+    system::{Commands, EntityCommands},
+    world::EntityWorldMut,
+};
+use log::warn;
+
+/// A pool of entities spawned with a `B` bundle, see the [module docs](crate::entity_pool).
+///
+/// The pool is typically stored as a resource, and used from systems with [`Commands`].
+/// Add a marker component to the bundle to tell pools of similar entities apart.
+#[derive(Resource)]
+pub struct EntityPool<B: Bundle + Clone> {
+    bundle: B,
+    free: EntityIndexSet,
+    len: usize,
+    reset: Option<fn(&mut EntityWorldMut)>,
+}
+
+impl<B: Bundle + Clone> EntityPool<B> {
+    /// Creates an empty pool, spawning its entities with `bundle`.
+    pub fn new(bundle: B) -> Self {
+        Self {
+            bundle,
+            free: EntityIndexSet::new(),
+            len: 0,
+            reset: None,
+        }
+    }
+
+    /// Sets a function called on the entities released to the pool, after they are disabled.
+    ///
+    /// The components of the bundle are reset when the entity is acquired again, so this is only
+    /// needed to clean up the rest of the entity, e.g. to remove the components inserted after the
+    /// entity was acquired, or to despawn its children.
+    pub fn with_reset(mut self, reset: fn(&mut EntityWorldMut)) -> Self {
+        self.reset = Some(reset);
+        self
+    }
+
+    /// Spawns `count` disabled entities, ready to be acquired.
+    pub fn prespawn(&mut self, commands: &mut Commands, count: usize) {
+        self.free.reserve(count);
+        for _ in 0..count {
+            let entity = commands.spawn((self.bundle.clone(), Disabled)).id();
+            self.free.insert(entity);
+        }
+        self.len += count;
+    }
+
+    /// Takes an entity out of the pool, and resets its components to the bundle of the pool.
+    ///
+    /// If all the entities of the pool are in use, this spawns a new entity, which is added to the
+    /// pool once released.
+    pub fn acquire<'a>(&mut self, commands: &'a mut Commands) -> EntityCommands<'a> {
+        match self.free.pop() {
+            Some(entity) => {
+                let mut entity_commands = commands.entity(entity);
+                entity_commands
+                    .insert(self.bundle.clone())
+                    .remove::<Disabled>();
+                entity_commands
+            }
+            None => {
+                self.len += 1;
+                commands.spawn(self.bundle.clone())
+            }
+        }
+    }
+
+    /// Returns `entity` to the pool, disabling it until it is acquired again.
+    ///
+    /// `entity` must have been acquired from this pool, and must not be despawned afterwards.
+    /// Releasing an entity that is already in the pool does nothing.
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        if !self.free.insert(entity) {
+            warn!("Entity {entity} was released to an `EntityPool` it is already in.");
+            return;
+        }
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Disabled);
+        if let Some(reset) = self.reset {
+            entity_commands.queue(move |mut entity: EntityWorldMut| reset(&mut entity));
+        }
+    }
+
+    /// Returns `true` if `entity` is in the pool, i.e. it is disabled and can be acquired.
+    pub fn is_free(&self, entity: Entity) -> bool {
+        self.free.contains(&entity)
+    }
+
+    /// Returns the number of entities in the pool, which can be acquired without spawning new ones.
+    pub fn free_len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Returns the number of entities spawned by the pool, including the ones in use.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the pool hasn't spawned any entity yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::Component,
+        query::{Changed, With},
+        world::{CommandQueue, World},
+    };
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Burning;
+
+    fn apply(world: &mut World, f: impl FnOnce(&mut Commands)) {
+        let mut queue = CommandQueue::default();
+        f(&mut Commands::new(&mut queue, world));
+        queue.apply(world);
+    }
+
+    #[test]
+    fn acquire_reuses_released_entities() {
+        let mut world = World::new();
+        let mut pool = EntityPool::new(Health(10)).with_reset(|entity| {
+            entity.remove::<Burning>();
+        });
+        apply(&mut world, |commands| pool.prespawn(commands, 2));
+        assert_eq!(pool.free_len(), 2);
+        assert_eq!(world.query::<&Health>().iter(&world).count(), 0);
+
+        let mut entity = Entity::PLACEHOLDER;
+        apply(&mut world, |commands| entity = pool.acquire(commands).id());
+        assert!(!pool.is_free(entity));
+        world.entity_mut(entity).insert((Health(3), Burning));
+
+        apply(&mut world, |commands| pool.release(commands, entity));
+        assert!(pool.is_free(entity));
+        assert!(world.entity(entity).contains::<Disabled>());
+        assert!(!world.entity(entity).contains::<Burning>());
+
+        // Releasing twice doesn't hand out the same entity twice.
+        apply(&mut world, |commands| pool.release(commands, entity));
+        assert_eq!(pool.free_len(), 2);
+
+        let mut acquired = [Entity::PLACEHOLDER; 3];
+        apply(&mut world, |commands| {
+            acquired = core::array::from_fn(|_| pool.acquire(commands).id());
+        });
+        assert!(acquired.contains(&entity));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool.free_len(), 0);
+        assert_eq!(world.query::<&Health>().iter(&world).count(), 3);
+    }
+
+    #[test]
+    fn acquired_components_are_changed() {
+        let mut world = World::new();
+        let mut pool = EntityPool::new(Health(10));
+        apply(&mut world, |commands| pool.prespawn(commands, 1));
+        let mut changed = world.query_filtered::<(), Changed<Health>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+
+        world.clear_trackers();
+        let mut entity = Entity::PLACEHOLDER;
+        apply(&mut world, |commands| entity = pool.acquire(commands).id());
+        assert_eq!(changed.iter(&world).count(), 1);
+
+        world.clear_trackers();
+        apply(&mut world, |commands| pool.release(commands, entity));
+        let mut disabled = world.query_filtered::<(), With<Disabled>>();
+        assert_eq!(disabled.iter(&world).count(), 1);
+    }
+}
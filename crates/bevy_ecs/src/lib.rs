@@ -32,6 +32,7 @@ pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod entity_disabling;
+pub mod entity_pool;
 pub mod error;
 pub mod event;
 pub mod hierarchy;
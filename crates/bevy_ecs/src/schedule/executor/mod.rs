@@ -38,6 +38,7 @@ pub(super) trait SystemExecutor: Send + Sync {
         error_handler: fn(BevyError, ErrorContext),
     );
     fn set_apply_final_deferred(&mut self, value: bool);
+    fn apply_final_deferred(&self) -> bool;
 }
 
 /// Specifies how a [`Schedule`](super::Schedule) will be run.
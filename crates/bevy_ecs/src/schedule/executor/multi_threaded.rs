@@ -323,6 +323,10 @@ impl SystemExecutor for MultiThreadedExecutor {
     fn set_apply_final_deferred(&mut self, value: bool) {
         self.apply_final_deferred = value;
     }
+
+    fn apply_final_deferred(&self) -> bool {
+        self.apply_final_deferred
+    }
 }
 
 impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
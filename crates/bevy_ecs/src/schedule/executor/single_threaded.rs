@@ -178,6 +178,10 @@ impl SystemExecutor for SingleThreadedExecutor {
     fn set_apply_final_deferred(&mut self, apply_final_deferred: bool) {
         self.apply_final_deferred = apply_final_deferred;
     }
+
+    fn apply_final_deferred(&self) -> bool {
+        self.apply_final_deferred
+    }
 }
 
 impl SingleThreadedExecutor {
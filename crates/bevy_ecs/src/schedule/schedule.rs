@@ -517,6 +517,19 @@ impl Schedule {
         self
     }
 
+    /// Replaces the executor with a new one of the same kind, keeping its settings.
+    ///
+    /// The executor keeps the progress of a run until it completes, so a schedule whose run
+    /// panicked, e.g. when caught with `std::panic::catch_unwind`, must be reset before it runs
+    /// again.
+    pub fn reset_executor(&mut self) -> &mut Self {
+        let apply_final_deferred = self.executor.apply_final_deferred();
+        self.executor = make_executor(self.executor.kind());
+        self.executor.set_apply_final_deferred(apply_final_deferred);
+        self.executor_initialized = false;
+        self
+    }
+
     /// Set whether the schedule applies deferred system buffers on final time or not. This is a catch-all
     /// in case a system uses commands but was not explicitly ordered before an instance of
     /// [`ApplyDeferred`]. By default this
@@ -1657,6 +1670,48 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn reset_executor_after_panic() {
+        use crate::schedule::ExecutorKind;
+        use core::panic::AssertUnwindSafe;
+        use std::panic::catch_unwind;
+
+        #[derive(Resource, Default)]
+        struct Runs(usize);
+
+        #[derive(Resource)]
+        struct Panic;
+
+        for kind in [ExecutorKind::SingleThreaded, ExecutorKind::MultiThreaded] {
+            let mut world = World::new();
+            world.init_resource::<Runs>();
+            world.insert_resource(Panic);
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(kind);
+            schedule.set_apply_final_deferred(false);
+            schedule.add_systems(
+                (
+                    |mut runs: ResMut<Runs>| runs.0 += 1,
+                    |panic: Option<Res<Panic>>, mut commands: Commands| {
+                        commands.insert_resource(Resource1);
+                        assert!(panic.is_none());
+                    },
+                )
+                    .chain(),
+            );
+            assert!(catch_unwind(AssertUnwindSafe(|| schedule.run(&mut world))).is_err());
+
+            world.remove_resource::<Panic>();
+            schedule.reset_executor();
+            assert_eq!(schedule.get_executor_kind(), kind);
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Runs>().0, 2);
+            // The settings of the executor are kept.
+            assert!(!world.contains_resource::<Resource1>());
+        }
+    }
+
     // regression test for https://github.com/bevyengine/bevy/issues/9114
     #[test]
     fn ambiguous_with_not_breaking_run_conditions() {
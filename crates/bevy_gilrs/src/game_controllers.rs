@@ -0,0 +1,316 @@
+//! The arcade sticks, racing wheels and flight sticks of `Windows.Gaming.Input`, which gilrs only
+//! reports as generic gamepads.
+use crate::{Gilrs, GilrsGamepads};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    entity::EntityHashMap,
+    message::MessageReader,
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_input::{
+    game_controller::{ArcadeStick, FlightStick, RacingWheel, RacingWheelForceFeedback},
+    gamepad::GamepadConnectionEvent,
+};
+use bevy_math::CompassOctant;
+use tracing::warn;
+use windows::{
+    Foundation::{AsyncStatus, IAsyncOperation, Numerics::Vector3, TimeSpan},
+    Gaming::Input::{
+        ArcadeStick as WgiArcadeStick, ArcadeStickButtons, FlightStick as WgiFlightStick,
+        FlightStickButtons,
+        ForceFeedback::{ConstantForceEffect, ForceFeedbackLoadEffectResult},
+        GameControllerSwitchPosition, RacingWheel as WgiRacingWheel, RacingWheelButtons,
+    },
+};
+
+/// The connected game controllers that aren't gamepads.
+#[derive(Resource, Default)]
+pub(crate) struct GameControllers(EntityHashMap<GameController>);
+
+/// A game controller that isn't a gamepad.
+pub(crate) enum GameController {
+    ArcadeStick(WgiArcadeStick),
+    RacingWheel {
+        wheel: WgiRacingWheel,
+        /// The constant force effect of [`RacingWheelForceFeedback`], loaded on the first request.
+        force: WheelForce,
+    },
+    FlightStick(WgiFlightStick),
+}
+
+/// The state of the constant force effect of a racing wheel.
+pub(crate) enum WheelForce {
+    /// No force was requested yet.
+    Unloaded,
+    /// The effect is being loaded into the wheel motor, which is polled every frame instead of
+    /// blocking the schedule.
+    Loading {
+        effect: ConstantForceEffect,
+        operation: IAsyncOperation<ForceFeedbackLoadEffectResult>,
+        /// The last force requested while loading, applied once the effect is loaded.
+        force: f32,
+    },
+    Loaded(ConstantForceEffect),
+    /// The wheel has no force feedback motor, or the effect failed to load.
+    Unsupported,
+}
+
+/// How long a [`RacingWheelForceFeedback`] lasts, in units of 100 nanoseconds. Its effect is
+/// stopped by another request, so this is only a day.
+const FORCE_DURATION: i64 = 24 * 60 * 60 * 10_000_000;
+
+/// Identifies the game controllers that aren't gamepads when they connect, and updates their
+/// [`ArcadeStick`], [`RacingWheel`] and [`FlightStick`] components.
+pub(crate) fn update_game_controllers(
+    mut commands: Commands,
+    mut gilrs: ResMut<Gilrs>,
+    gamepads: Res<GilrsGamepads>,
+    mut controllers: ResMut<GameControllers>,
+    mut connection_events: MessageReader<GamepadConnectionEvent>,
+    mut arcade_sticks: Query<&mut ArcadeStick>,
+    mut racing_wheels: Query<&mut RacingWheel>,
+    mut flight_sticks: Query<&mut FlightStick>,
+) {
+    for event in connection_events.read() {
+        let entity = event.gamepad;
+        if event.disconnected() {
+            if controllers.0.remove(&entity).is_some() {
+                commands
+                    .entity(entity)
+                    .remove::<(ArcadeStick, RacingWheel, FlightStick)>();
+            }
+            continue;
+        }
+        let Some(gamepad_id) = gamepads.get_gamepad_id(entity) else {
+            continue;
+        };
+        let mut raw_controller = None;
+        gilrs.with(|gilrs| raw_controller = crate::wgi::find_raw_controller(gilrs, gamepad_id));
+        let Some(raw_controller) = raw_controller else {
+            continue;
+        };
+        if let Ok(wheel) = WgiRacingWheel::FromGameController(&raw_controller) {
+            commands.entity(entity).insert(RacingWheel {
+                max_wheel_angle: wheel.MaxWheelAngle().unwrap_or(0.0) as f32,
+                clutch: wheel.HasClutch().unwrap_or(false).then_some(0.0),
+                handbrake: wheel.HasHandbrake().unwrap_or(false).then_some(0.0),
+                gear: wheel.HasPatternShifter().unwrap_or(false).then_some(0),
+                ..Default::default()
+            });
+            controllers.0.insert(
+                entity,
+                GameController::RacingWheel {
+                    wheel,
+                    force: WheelForce::Unloaded,
+                },
+            );
+        } else if let Ok(flight_stick) = WgiFlightStick::FromGameController(&raw_controller) {
+            commands.entity(entity).insert(FlightStick::default());
+            controllers
+                .0
+                .insert(entity, GameController::FlightStick(flight_stick));
+        } else if let Ok(arcade_stick) = WgiArcadeStick::FromGameController(&raw_controller) {
+            commands.entity(entity).insert(ArcadeStick::default());
+            controllers
+                .0
+                .insert(entity, GameController::ArcadeStick(arcade_stick));
+        }
+    }
+
+    for (entity, controller) in &controllers.0 {
+        match controller {
+            GameController::ArcadeStick(arcade_stick) => {
+                let (Ok(reading), Ok(mut component)) = (
+                    arcade_stick.GetCurrentReading(),
+                    arcade_sticks.get_mut(*entity),
+                ) else {
+                    continue;
+                };
+                let pressed = |button: ArcadeStickButtons| reading.Buttons.0 & button.0 != 0;
+                component.set_if_neq(ArcadeStick {
+                    stick: stick_octant(
+                        pressed(ArcadeStickButtons::StickUp),
+                        pressed(ArcadeStickButtons::StickDown),
+                        pressed(ArcadeStickButtons::StickLeft),
+                        pressed(ArcadeStickButtons::StickRight),
+                    ),
+                    actions: [
+                        ArcadeStickButtons::Action1,
+                        ArcadeStickButtons::Action2,
+                        ArcadeStickButtons::Action3,
+                        ArcadeStickButtons::Action4,
+                        ArcadeStickButtons::Action5,
+                        ArcadeStickButtons::Action6,
+                    ]
+                    .map(pressed),
+                    specials: [ArcadeStickButtons::Special1, ArcadeStickButtons::Special2]
+                        .map(pressed),
+                });
+            }
+            GameController::RacingWheel { wheel, .. } => {
+                let (Ok(reading), Ok(mut component)) =
+                    (wheel.GetCurrentReading(), racing_wheels.get_mut(*entity))
+                else {
+                    continue;
+                };
+                let pressed = |button: RacingWheelButtons| reading.Buttons.0 & button.0 != 0;
+                let updated = RacingWheel {
+                    wheel: reading.Wheel as f32,
+                    throttle: reading.Throttle as f32,
+                    brake: reading.Brake as f32,
+                    clutch: component.clutch.map(|_| reading.Clutch as f32),
+                    handbrake: component.handbrake.map(|_| reading.Handbrake as f32),
+                    gear: component.gear.map(|_| reading.PatternShifterGear),
+                    previous_gear: pressed(RacingWheelButtons::PreviousGear),
+                    next_gear: pressed(RacingWheelButtons::NextGear),
+                    ..*component
+                };
+                component.set_if_neq(updated);
+            }
+            GameController::FlightStick(flight_stick) => {
+                let (Ok(reading), Ok(mut component)) = (
+                    flight_stick.GetCurrentReading(),
+                    flight_sticks.get_mut(*entity),
+                ) else {
+                    continue;
+                };
+                let pressed = |button: FlightStickButtons| reading.Buttons.0 & button.0 != 0;
+                component.set_if_neq(FlightStick {
+                    roll: reading.Roll as f32,
+                    pitch: reading.Pitch as f32,
+                    yaw: reading.Yaw as f32,
+                    throttle: reading.Throttle as f32,
+                    hat_switch: switch_octant(reading.HatSwitch),
+                    first_fire: pressed(FlightStickButtons::FirePrimary),
+                    second_fire: pressed(FlightStickButtons::FireSecondary),
+                });
+            }
+        }
+    }
+}
+
+/// Applies the [`RacingWheelForceFeedback`] requests to the wheel motors.
+///
+/// The force effect of a wheel is loaded on its first request without waiting for it, and the
+/// last force requested meanwhile is applied once it is loaded.
+pub(crate) fn play_racing_wheel_force_feedback(
+    mut controllers: ResMut<GameControllers>,
+    mut requests: MessageReader<RacingWheelForceFeedback>,
+) {
+    for request in requests.read() {
+        let Some(GameController::RacingWheel { wheel, force }) =
+            controllers.0.get_mut(&request.wheel)
+        else {
+            continue;
+        };
+        let result = match force {
+            WheelForce::Unloaded => start_loading_force(wheel, request.force).map(|loading| {
+                *force = loading;
+            }),
+            WheelForce::Loading { force, .. } => {
+                *force = request.force;
+                Ok(())
+            }
+            WheelForce::Loaded(effect) => apply_force(effect, request.force),
+            WheelForce::Unsupported => Ok(()),
+        };
+        if let Err(err) = result {
+            warn!(
+                "Failed to apply force feedback to racing wheel {}: {err}",
+                request.wheel
+            );
+        }
+    }
+
+    for (entity, controller) in &mut controllers.0 {
+        let GameController::RacingWheel { force, .. } = controller else {
+            continue;
+        };
+        let WheelForce::Loading {
+            effect,
+            operation,
+            force: requested,
+        } = force
+        else {
+            continue;
+        };
+        let loaded = match operation.Status() {
+            Ok(AsyncStatus::Started) => continue,
+            Ok(AsyncStatus::Completed) => operation
+                .GetResults()
+                .is_ok_and(|result| result == ForceFeedbackLoadEffectResult::Succeeded),
+            _ => false,
+        };
+        if !loaded {
+            *force = WheelForce::Unsupported;
+            continue;
+        }
+        let effect = effect.clone();
+        if let Err(err) = apply_force(&effect, *requested) {
+            warn!("Failed to apply force feedback to racing wheel {entity}: {err}");
+        }
+        *force = WheelForce::Loaded(effect);
+    }
+}
+
+/// Starts loading the constant force effect into the motor of `wheel`, to apply `force` once it
+/// is loaded.
+fn start_loading_force(wheel: &WgiRacingWheel, force: f32) -> windows::core::Result<WheelForce> {
+    // Wheels without force feedback don't have a motor.
+    let Ok(motor) = wheel.WheelMotor() else {
+        return Ok(WheelForce::Unsupported);
+    };
+    let effect = ConstantForceEffect::new()?;
+    let operation = motor.LoadEffectAsync(&effect)?;
+    Ok(WheelForce::Loading {
+        effect,
+        operation,
+        force,
+    })
+}
+
+fn apply_force(effect: &ConstantForceEffect, force: f32) -> windows::core::Result<()> {
+    if force == 0.0 {
+        return effect.Stop();
+    }
+    effect.SetParameters(
+        Vector3 {
+            X: force.clamp(-1.0, 1.0),
+            Y: 0.0,
+            Z: 0.0,
+        },
+        TimeSpan {
+            Duration: FORCE_DURATION,
+        },
+    )?;
+    effect.Start()
+}
+
+fn stick_octant(up: bool, down: bool, left: bool, right: bool) -> Option<CompassOctant> {
+    match (up && !down, down && !up, left && !right, right && !left) {
+        (true, _, true, _) => Some(CompassOctant::NorthWest),
+        (true, _, _, true) => Some(CompassOctant::NorthEast),
+        (true, _, _, _) => Some(CompassOctant::North),
+        (_, true, true, _) => Some(CompassOctant::SouthWest),
+        (_, true, _, true) => Some(CompassOctant::SouthEast),
+        (_, true, _, _) => Some(CompassOctant::South),
+        (_, _, true, _) => Some(CompassOctant::West),
+        (_, _, _, true) => Some(CompassOctant::East),
+        _ => None,
+    }
+}
+
+fn switch_octant(position: GameControllerSwitchPosition) -> Option<CompassOctant> {
+    match position {
+        GameControllerSwitchPosition::Up => Some(CompassOctant::North),
+        GameControllerSwitchPosition::UpRight => Some(CompassOctant::NorthEast),
+        GameControllerSwitchPosition::Right => Some(CompassOctant::East),
+        GameControllerSwitchPosition::DownRight => Some(CompassOctant::SouthEast),
+        GameControllerSwitchPosition::Down => Some(CompassOctant::South),
+        GameControllerSwitchPosition::DownLeft => Some(CompassOctant::SouthWest),
+        GameControllerSwitchPosition::Left => Some(CompassOctant::West),
+        GameControllerSwitchPosition::UpLeft => Some(CompassOctant::NorthWest),
+        _ => None,
+    }
+}
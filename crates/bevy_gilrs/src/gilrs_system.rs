@@ -9,6 +9,7 @@ use bevy_input::gamepad::{
     GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
     RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
+use bevy_platform::time::Instant;
 use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter};
 
 pub fn gilrs_event_startup_system(
@@ -88,12 +89,16 @@ pub fn gilrs_event_system(
                         .get(&gilrs_event.id)
                         .copied()
                         .expect("mapping should exist from connection");
-                    events.write(
-                        RawGamepadButtonChangedEvent::new(gamepad, button, raw_value).into(),
-                    );
-                    button_events.write(RawGamepadButtonChangedEvent::new(
-                        gamepad, button, raw_value,
-                    ));
+                    // gilrs reports when the event happened as a system time, which is
+                    // converted to an instant from how long ago it was.
+                    let timestamp =
+                        Instant::now().checked_sub(gilrs_event.time.elapsed().unwrap_or_default());
+                    let event = RawGamepadButtonChangedEvent {
+                        timestamp,
+                        ..RawGamepadButtonChangedEvent::new(gamepad, button, raw_value)
+                    };
+                    events.write(event.into());
+                    button_events.write(event);
                 }
                 EventType::AxisChanged(gilrs_axis, raw_value, _) => {
                     let Some(axis) = convert_axis(gilrs_axis) else {
@@ -0,0 +1,48 @@
+//! Rumble of the impulse triggers of Xbox gamepads, which gilrs doesn't support, through
+//! `Windows.Gaming.Input`.
+use bevy_input::gamepad::GamepadRumbleIntensity;
+use gilrs::GamepadId;
+
+/// Returns `true` if the gamepad can rumble its triggers, with [`set_vibration`].
+#[cfg(windows)]
+pub(crate) fn supports_impulse_triggers(gilrs: &gilrs::Gilrs, gamepad_id: GamepadId) -> bool {
+    crate::wgi::find_gamepad(gilrs, gamepad_id).is_some()
+}
+
+/// Returns `true` if the gamepad can rumble its triggers, with [`set_vibration`].
+#[cfg(not(windows))]
+pub(crate) fn supports_impulse_triggers(_gilrs: &gilrs::Gilrs, _gamepad_id: GamepadId) -> bool {
+    false
+}
+
+/// Sets the intensity of all the motors of the gamepad, including the trigger motors.
+///
+/// This overrides the rumble effects played by gilrs on the gamepad.
+#[cfg(windows)]
+pub(crate) fn set_vibration(
+    gilrs: &gilrs::Gilrs,
+    gamepad_id: GamepadId,
+    intensity: GamepadRumbleIntensity,
+) {
+    let Some(gamepad) = crate::wgi::find_gamepad(gilrs, gamepad_id) else {
+        return;
+    };
+    let vibration = windows::Gaming::Input::GamepadVibration {
+        LeftMotor: intensity.strong_motor.clamp(0.0, 1.0) as f64,
+        RightMotor: intensity.weak_motor.clamp(0.0, 1.0) as f64,
+        LeftTrigger: intensity.left_trigger.clamp(0.0, 1.0) as f64,
+        RightTrigger: intensity.right_trigger.clamp(0.0, 1.0) as f64,
+    };
+    if let Err(err) = gamepad.SetVibration(vibration) {
+        tracing::warn!("Failed to rumble the impulse triggers of {gamepad_id:?}: {err}");
+    }
+}
+
+/// Sets the intensity of all the motors of the gamepad, including the trigger motors.
+#[cfg(not(windows))]
+pub(crate) fn set_vibration(
+    _gilrs: &gilrs::Gilrs,
+    _gamepad_id: GamepadId,
+    _intensity: GamepadRumbleIntensity,
+) {
+}
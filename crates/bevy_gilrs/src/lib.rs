@@ -11,8 +11,15 @@
 //! that handles abstracting over platform-specific gamepad APIs.
 
 mod converter;
+#[cfg(windows)]
+mod game_controllers;
 mod gilrs_system;
+mod impulse_triggers;
 mod rumble;
+#[cfg(windows)]
+mod users;
+#[cfg(windows)]
+mod wgi;
 
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_platform::cell::SyncCell;
@@ -109,6 +116,21 @@ impl Plugin for GilrsPlugin {
                     .add_systems(PreStartup, gilrs_event_startup_system)
                     .add_systems(PreUpdate, gilrs_event_system.before(InputSystems))
                     .add_systems(PostUpdate, play_gilrs_rumble.in_set(RumbleSystems));
+                #[cfg(windows)]
+                app.init_resource::<game_controllers::GameControllers>()
+                    .add_systems(
+                        PreUpdate,
+                        (
+                            users::update_gamepad_users,
+                            game_controllers::update_game_controllers,
+                        )
+                            .after(gilrs_event_system)
+                            .before(InputSystems),
+                    )
+                    .add_systems(
+                        PostUpdate,
+                        game_controllers::play_racing_wheel_force_feedback.in_set(RumbleSystems),
+                    );
             }
             Err(err) => error!("Failed to start Gilrs. {}", err),
         }
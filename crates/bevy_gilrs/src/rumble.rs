@@ -1,7 +1,10 @@
-//! Handle user specified rumble request events.
-use crate::{Gilrs, GilrsGamepads};
-use bevy_ecs::prelude::{MessageReader, Res, ResMut, Resource};
-use bevy_input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest};
+//! Handle user specified rumble and haptic request events.
+use crate::{impulse_triggers, Gilrs, GilrsGamepads};
+use bevy_ecs::prelude::{Entity, MessageReader, Res, ResMut, Resource};
+use bevy_input::{
+    gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    haptics::{HapticDevice, HapticEffect, HapticRequest},
+};
 use bevy_platform::cell::SyncCell;
 use bevy_platform::collections::HashMap;
 use bevy_time::{Real, Time};
@@ -17,14 +20,39 @@ use tracing::{debug, warn};
 struct RunningRumble {
     /// Duration from app startup when this effect will be finished
     deadline: Duration,
+    /// How the rumble is played
+    effect: RumbleEffect,
+    /// The haptic effect played by the rumble, which scales its intensity over time
+    haptic: Option<RunningHaptic>,
+}
+
+/// A [`HapticEffect`] played as a rumble.
+struct RunningHaptic {
+    device: HapticDevice,
+    effect: HapticEffect,
+    /// Duration from app startup when this effect started
+    start: Duration,
+}
+
+impl RunningRumble {
+    /// Returns the current intensity of the rumble, relative to the intensity it was added with.
+    fn gain(&self, current_time: Duration) -> f32 {
+        self.haptic.as_ref().map_or(1.0, |haptic| {
+            haptic
+                .effect
+                .intensity_at(current_time.saturating_sub(haptic.start))
+        })
+    }
+}
+
+enum RumbleEffect {
     /// A ref-counted handle to the specific force-feedback effect
     ///
     /// Dropping it will cause the effect to stop
-    #[expect(
-        dead_code,
-        reason = "We don't need to read this field, as its purpose is to keep the rumble effect going until the field is dropped."
-    )]
-    effect: SyncCell<ff::Effect>,
+    Gilrs(SyncCell<ff::Effect>),
+    /// A rumble with impulse triggers, which gilrs doesn't support, played through
+    /// [`impulse_triggers`] instead
+    ImpulseTriggers(GamepadRumbleIntensity),
 }
 
 #[derive(Error, Debug)]
@@ -41,6 +69,8 @@ pub(crate) struct RunningRumbleEffects {
     /// If multiple rumbles are running at the same time, their resulting rumble
     /// will be the saturated sum of their strengths up until [`u16::MAX`]
     rumbles: HashMap<GamepadId, Vec<RunningRumble>>,
+    /// The intensity last set on the gamepads rumbling their impulse triggers
+    impulse_triggers: HashMap<GamepadId, GamepadRumbleIntensity>,
 }
 
 /// gilrs uses magnitudes from 0 to [`u16::MAX`], while ours go from `0.0` to `1.0` ([`f32`])
@@ -52,6 +82,7 @@ fn get_base_effects(
     GamepadRumbleIntensity {
         weak_motor,
         strong_motor,
+        ..
     }: GamepadRumbleIntensity,
     duration: Duration,
 ) -> Vec<BaseEffect> {
@@ -103,32 +134,234 @@ fn handle_rumble_request(
             intensity,
             ..
         } => {
-            let mut effect_builder = ff::EffectBuilder::new();
+            add_rumble(
+                running_rumbles,
+                gilrs,
+                gamepad,
+                gamepad_id,
+                intensity,
+                duration,
+                current_time,
+                None,
+            )?;
+        }
+    }
 
-            for effect in get_base_effects(intensity, duration) {
-                effect_builder.add_effect(effect);
-                effect_builder.repeat(Repeat::For(duration.into()));
+    Ok(())
+}
+
+fn handle_haptic_request(
+    running_rumbles: &mut RunningRumbleEffects,
+    gilrs: &mut gilrs::Gilrs,
+    gamepads: &GilrsGamepads,
+    request: HapticRequest,
+    current_time: Duration,
+) -> Result<(), RumbleError> {
+    let device = request.device();
+    let Some((gamepad, intensity)) = haptic_rumble_intensity(device) else {
+        // Pens are handled by the windowing backend.
+        return Ok(());
+    };
+    let gamepad_id = gamepads
+        .get_gamepad_id(gamepad)
+        .ok_or(RumbleError::GamepadNotFound)?;
+
+    match request {
+        HapticRequest::Stop { .. } => {
+            if let Some(rumbles) = running_rumbles.rumbles.get_mut(&gamepad_id) {
+                // `ff::Effect` uses RAII, dropping = deactivating
+                rumbles.retain(|rumble| {
+                    !is_stopped_by(rumble.haptic.as_ref().map(|haptic| haptic.device), device)
+                });
             }
+        }
+        HapticRequest::Play { effect, .. } => {
+            add_rumble(
+                running_rumbles,
+                gilrs,
+                gamepad,
+                gamepad_id,
+                intensity,
+                effect.duration(),
+                current_time,
+                Some(RunningHaptic {
+                    device,
+                    effect,
+                    start: current_time,
+                }),
+            )?;
+        }
+    }
 
-            let effect = effect_builder.gamepads(&[gamepad_id]).finish(gilrs)?;
-            effect.play()?;
+    Ok(())
+}
+
+/// Returns the gamepad a haptic effect on `device` is played on, and the intensity of its motors
+/// at full strength, or [`None`] if `device` isn't a gamepad.
+fn haptic_rumble_intensity(device: HapticDevice) -> Option<(Entity, GamepadRumbleIntensity)> {
+    match device {
+        HapticDevice::Gamepad(gamepad) => Some((
+            gamepad,
+            GamepadRumbleIntensity {
+                strong_motor: 1.0,
+                weak_motor: 1.0,
+                ..GamepadRumbleIntensity::ZERO
+            },
+        )),
+        HapticDevice::GamepadTriggers(gamepad) => Some((
+            gamepad,
+            GamepadRumbleIntensity {
+                left_trigger: 1.0,
+                right_trigger: 1.0,
+                ..GamepadRumbleIntensity::ZERO
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Returns whether a [`HapticRequest::Stop`] on `device` stops a rumble, which was started by a
+/// haptic effect on `haptic_device`, or by a [`GamepadRumbleRequest`] if it is [`None`].
+///
+/// Stopping a gamepad stops all of its rumbles except the haptic effects on its triggers, which
+/// are stopped by stopping its triggers.
+fn is_stopped_by(haptic_device: Option<HapticDevice>, device: HapticDevice) -> bool {
+    match haptic_device {
+        Some(haptic_device) => haptic_device == device,
+        None => matches!(device, HapticDevice::Gamepad(_)),
+    }
+}
 
-            let gamepad_rumbles = running_rumbles.rumbles.entry(gamepad_id).or_default();
-            let deadline = current_time + duration;
-            gamepad_rumbles.push(RunningRumble {
-                deadline,
-                effect: SyncCell::new(effect),
-            });
+/// Returns the intensity of a haptic effect meant for the triggers of a gamepad without impulse
+/// triggers, which is played on the motors instead: the left trigger on the strong motor, and the
+/// right trigger on the weak motor.
+fn triggers_on_motors(intensity: GamepadRumbleIntensity) -> GamepadRumbleIntensity {
+    GamepadRumbleIntensity {
+        strong_motor: intensity.strong_motor.max(intensity.left_trigger),
+        weak_motor: intensity.weak_motor.max(intensity.right_trigger),
+        ..intensity
+    }
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    reason = "all the arguments are needed to start the rumble"
+)]
+fn add_rumble(
+    running_rumbles: &mut RunningRumbleEffects,
+    gilrs: &mut gilrs::Gilrs,
+    gamepad: Entity,
+    gamepad_id: GamepadId,
+    mut intensity: GamepadRumbleIntensity,
+    duration: Duration,
+    current_time: Duration,
+    haptic: Option<RunningHaptic>,
+) -> Result<(), RumbleError> {
+    let deadline = current_time + duration;
+    if intensity.has_triggers() {
+        if impulse_triggers::supports_impulse_triggers(gilrs, gamepad_id) {
+            running_rumbles
+                .rumbles
+                .entry(gamepad_id)
+                .or_default()
+                .push(RunningRumble {
+                    deadline,
+                    effect: RumbleEffect::ImpulseTriggers(intensity),
+                    haptic,
+                });
+            return Ok(());
+        }
+        debug!(
+            "Tried to rumble the triggers of {gamepad:?}, but it doesn't support impulse triggers"
+        );
+        if haptic.is_some() {
+            intensity = triggers_on_motors(intensity);
         }
     }
 
+    let mut effect_builder = ff::EffectBuilder::new();
+
+    for effect in get_base_effects(intensity, duration) {
+        effect_builder.add_effect(effect);
+        effect_builder.repeat(Repeat::For(duration.into()));
+    }
+
+    let effect = effect_builder.gamepads(&[gamepad_id]).finish(gilrs)?;
+    if let Some(haptic) = &haptic {
+        effect.set_gain(haptic.effect.intensity_at(Duration::ZERO))?;
+    }
+    effect.play()?;
+
+    let gamepad_rumbles = running_rumbles.rumbles.entry(gamepad_id).or_default();
+    gamepad_rumbles.push(RunningRumble {
+        deadline,
+        effect: RumbleEffect::Gilrs(SyncCell::new(effect)),
+        haptic,
+    });
     Ok(())
 }
+
+/// Sets the summed intensity of the running impulse trigger rumbles on their gamepads, and stops
+/// the gamepads that don't have any anymore.
+///
+/// The intensity is set again every frame, even if it didn't change: the force feedback server of
+/// gilrs regularly sets the state of the motors of every gamepad, which resets the trigger motors.
+fn update_impulse_triggers(
+    running_rumbles: &mut RunningRumbleEffects,
+    gilrs: &gilrs::Gilrs,
+    current_time: Duration,
+) {
+    let mut intensities: HashMap<GamepadId, GamepadRumbleIntensity> = running_rumbles
+        .rumbles
+        .iter()
+        .filter_map(|(gamepad_id, rumbles)| {
+            rumbles
+                .iter()
+                .filter_map(|rumble| match rumble.effect {
+                    RumbleEffect::ImpulseTriggers(intensity) => {
+                        let gain = rumble.gain(current_time);
+                        Some(GamepadRumbleIntensity {
+                            strong_motor: intensity.strong_motor * gain,
+                            weak_motor: intensity.weak_motor * gain,
+                            left_trigger: intensity.left_trigger * gain,
+                            right_trigger: intensity.right_trigger * gain,
+                        })
+                    }
+                    RumbleEffect::Gilrs(_) => None,
+                })
+                .reduce(|a, b| GamepadRumbleIntensity {
+                    strong_motor: (a.strong_motor + b.strong_motor).min(1.0),
+                    weak_motor: (a.weak_motor + b.weak_motor).min(1.0),
+                    left_trigger: (a.left_trigger + b.left_trigger).min(1.0),
+                    right_trigger: (a.right_trigger + b.right_trigger).min(1.0),
+                })
+                .map(|intensity| (*gamepad_id, intensity))
+        })
+        .collect();
+    for gamepad_id in running_rumbles.impulse_triggers.keys() {
+        intensities
+            .entry(*gamepad_id)
+            .or_insert(GamepadRumbleIntensity::ZERO);
+    }
+
+    for (gamepad_id, intensity) in intensities {
+        impulse_triggers::set_vibration(gilrs, gamepad_id, intensity);
+        if intensity == GamepadRumbleIntensity::ZERO {
+            running_rumbles.impulse_triggers.remove(&gamepad_id);
+        } else {
+            running_rumbles
+                .impulse_triggers
+                .insert(gamepad_id, intensity);
+        }
+    }
+}
+
 pub(crate) fn play_gilrs_rumble(
     time: Res<Time<Real>>,
     mut gilrs: ResMut<Gilrs>,
     gamepads: Res<GilrsGamepads>,
     mut requests: MessageReader<GamepadRumbleRequest>,
+    mut haptic_requests: MessageReader<HapticRequest>,
     mut running_rumbles: ResMut<RunningRumbleEffects>,
 ) {
     gilrs.with(|gilrs| {
@@ -161,12 +394,47 @@ pub(crate) fn play_gilrs_rumble(
                 }
             };
         }
+        for request in haptic_requests.read().copied() {
+            let device = request.device();
+            match handle_haptic_request(&mut running_rumbles, gilrs, &gamepads, request, current_time) {
+                Ok(()) => {}
+                Err(RumbleError::GilrsError(err)) => {
+                    if let ff::Error::FfNotSupported(_) = err {
+                        debug!("Tried to play a haptic effect on {device:?}, but it doesn't support force feedback");
+                    } else {
+                        warn!(
+                        "Tried to handle haptic request for {device:?} but an error occurred: {err}"
+                        );
+                    }
+                }
+                Err(RumbleError::GamepadNotFound) => {
+                    warn!("Tried to handle haptic request {device:?} but it doesn't exist!");
+                }
+            };
+        }
+
+        // Follow the intensity of the haptic effects.
+        for rumble in running_rumbles.rumbles.values_mut().flatten() {
+            if rumble.haptic.is_none() {
+                continue;
+            }
+            let gain = rumble.gain(current_time);
+            if let RumbleEffect::Gilrs(effect) = &mut rumble.effect
+                && let Err(err) = effect.get().set_gain(gain)
+            {
+                debug!("Failed to update the intensity of a haptic effect: {err}");
+            }
+        }
+
+        update_impulse_triggers(&mut running_rumbles, gilrs, current_time);
     });
 }
 
 #[cfg(test)]
 mod tests {
-    use super::to_gilrs_magnitude;
+    use super::{haptic_rumble_intensity, is_stopped_by, to_gilrs_magnitude, triggers_on_motors};
+    use bevy_ecs::entity::Entity;
+    use bevy_input::{gamepad::GamepadRumbleIntensity, haptics::HapticDevice};
 
     #[test]
     fn magnitude_conversion() {
@@ -182,4 +450,77 @@ mod tests {
         assert_eq!(to_gilrs_magnitude(-1.0), 0);
         assert_eq!(to_gilrs_magnitude(-0.1), 0);
     }
+
+    #[test]
+    fn haptic_effects_are_routed_to_the_gamepad_motors() {
+        let gamepad = Entity::from_raw_u32(1).unwrap();
+        assert_eq!(
+            haptic_rumble_intensity(HapticDevice::Gamepad(gamepad)),
+            Some((
+                gamepad,
+                GamepadRumbleIntensity {
+                    strong_motor: 1.0,
+                    weak_motor: 1.0,
+                    ..GamepadRumbleIntensity::ZERO
+                }
+            ))
+        );
+        assert_eq!(
+            haptic_rumble_intensity(HapticDevice::GamepadTriggers(gamepad)),
+            Some((
+                gamepad,
+                GamepadRumbleIntensity {
+                    left_trigger: 1.0,
+                    right_trigger: 1.0,
+                    ..GamepadRumbleIntensity::ZERO
+                }
+            ))
+        );
+        // Pens are handled by the windowing backend.
+        assert_eq!(haptic_rumble_intensity(HapticDevice::Pen(0)), None);
+    }
+
+    #[test]
+    fn trigger_effects_fall_back_to_the_motors() {
+        let (_, triggers) =
+            haptic_rumble_intensity(HapticDevice::GamepadTriggers(Entity::PLACEHOLDER)).unwrap();
+        assert_eq!(
+            triggers_on_motors(triggers),
+            GamepadRumbleIntensity {
+                strong_motor: 1.0,
+                weak_motor: 1.0,
+                left_trigger: 1.0,
+                right_trigger: 1.0,
+            }
+        );
+        assert_eq!(
+            triggers_on_motors(GamepadRumbleIntensity {
+                strong_motor: 0.5,
+                weak_motor: 0.2,
+                left_trigger: 0.1,
+                right_trigger: 0.8,
+            }),
+            GamepadRumbleIntensity {
+                strong_motor: 0.5,
+                weak_motor: 0.8,
+                left_trigger: 0.1,
+                right_trigger: 0.8,
+            }
+        );
+    }
+
+    #[test]
+    fn stopping_a_gamepad_stops_its_rumbles() {
+        let gamepad = HapticDevice::Gamepad(Entity::from_raw_u32(1).unwrap());
+        let triggers = HapticDevice::GamepadTriggers(Entity::from_raw_u32(1).unwrap());
+
+        // Rumbles requested with `GamepadRumbleRequest`.
+        assert!(is_stopped_by(None, gamepad));
+        assert!(!is_stopped_by(None, triggers));
+
+        assert!(is_stopped_by(Some(gamepad), gamepad));
+        assert!(!is_stopped_by(Some(triggers), gamepad));
+        assert!(is_stopped_by(Some(triggers), triggers));
+        assert!(!is_stopped_by(Some(gamepad), triggers));
+    }
 }
@@ -0,0 +1,48 @@
+//! The `Windows.System.User` paired with each gamepad, on Xbox and multi-user Windows, through
+//! `Windows.Gaming.Input`.
+use crate::{Gilrs, GilrsGamepads};
+use bevy_ecs::{
+    entity::EntityHashMap,
+    message::MessageWriter,
+    system::{Local, Res, ResMut},
+};
+use bevy_input::gamepad::{GamepadUser, GamepadUserChangedEvent};
+use bevy_time::{Real, Time};
+use core::time::Duration;
+
+/// How often the users of the gamepads are polled, as `Windows.Gaming.Input` is queried for each
+/// gamepad.
+const USER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Writes a [`GamepadUserChangedEvent`] when a gamepad is paired with another user, or unpaired.
+pub(crate) fn update_gamepad_users(
+    time: Res<Time<Real>>,
+    mut gilrs: ResMut<Gilrs>,
+    gamepads: Res<GilrsGamepads>,
+    mut last_poll: Local<Option<Duration>>,
+    mut users: Local<EntityHashMap<GamepadUser>>,
+    mut user_events: MessageWriter<GamepadUserChangedEvent>,
+) {
+    let now = time.elapsed();
+    if last_poll.is_some_and(|last_poll| now.saturating_sub(last_poll) < USER_POLL_INTERVAL) {
+        return;
+    }
+    *last_poll = Some(now);
+
+    gilrs.with(|gilrs| {
+        for (&entity, &gamepad_id) in &gamepads.entity_to_id {
+            let user = crate::wgi::find_gamepad(gilrs, gamepad_id)
+                .and_then(|gamepad| gamepad.User().ok())
+                .and_then(|user| user.NonRoamableId().ok())
+                .map(|id| GamepadUser { id: id.to_string() });
+            if users.get(&entity) == user.as_ref() {
+                continue;
+            }
+            match &user {
+                Some(user) => users.insert(entity, user.clone()),
+                None => users.remove(&entity),
+            };
+            user_events.write(GamepadUserChangedEvent::new(entity, user));
+        }
+    });
+}
@@ -0,0 +1,160 @@
+//! Access to the `Windows.Gaming.Input` gamepads behind the gilrs gamepads, for the features gilrs
+//! doesn't support.
+use bevy_platform::sync::{Mutex, PoisonError};
+use gilrs::GamepadId;
+use windows::Gaming::Input::{Gamepad, RawGameController};
+
+/// The vendor and product ids of a gamepad.
+type HardwareIds = (Option<u16>, Option<u16>);
+
+/// The `NonRoamableId` of the raw game controller paired with each connected gilrs gamepad, by
+/// gilrs id.
+static PAIRED_CONTROLLERS: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+
+/// Finds the `Windows.Gaming.Input` gamepad of a gilrs gamepad.
+///
+/// It is matched like in [`find_raw_controller`].
+pub(crate) fn find_gamepad(gilrs: &gilrs::Gilrs, gamepad_id: GamepadId) -> Option<Gamepad> {
+    Gamepad::FromGameController(&find_raw_controller(gilrs, gamepad_id)?).ok()
+}
+
+/// Finds the `Windows.Gaming.Input` raw game controller of a gilrs gamepad, which can be any kind
+/// of game controller, e.g. a racing wheel.
+///
+/// gilrs doesn't expose its `Windows.Gaming.Input` controllers, so each gilrs gamepad is paired
+/// with a controller with the same vendor and product ids the first time it is looked up, and
+/// keeps it until either is disconnected. When several gamepads with the same ids are connected
+/// before being looked up, e.g. at startup, they may be paired with each other's controller.
+pub(crate) fn find_raw_controller(
+    gilrs: &gilrs::Gilrs,
+    gamepad_id: GamepadId,
+) -> Option<RawGameController> {
+    let controllers: Vec<RawGameController> = RawGameController::RawGameControllers()
+        .ok()?
+        .into_iter()
+        .collect();
+    let controller_ids: Vec<(String, HardwareIds)> = controllers
+        .iter()
+        .filter_map(|controller| {
+            Some((
+                controller.NonRoamableId().ok()?.to_string(),
+                hardware_ids(controller),
+            ))
+        })
+        .collect();
+    let gamepad_ids: Vec<(usize, HardwareIds)> = gilrs
+        .gamepads()
+        .map(|(id, gamepad)| (usize::from(id), (gamepad.vendor_id(), gamepad.product_id())))
+        .collect();
+
+    let mut paired = PAIRED_CONTROLLERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    pair_controllers(&mut paired, &gamepad_ids, &controller_ids);
+    let (_, controller_id) = paired
+        .iter()
+        .find(|(gamepad, _)| *gamepad == usize::from(gamepad_id))?;
+    controllers.into_iter().find(|controller| {
+        controller
+            .NonRoamableId()
+            .is_ok_and(|id| id.to_string() == *controller_id)
+    })
+}
+
+/// Pairs the connected gilrs `gamepads` with the raw game `controllers` that have the same
+/// hardware ids, keeping the existing pairs of the gamepads and controllers still connected.
+///
+/// Gamepads are paired in the order of their gilrs ids, which is the order they were connected
+/// in, with the first unpaired controller in the order of `controllers`.
+fn pair_controllers(
+    paired: &mut Vec<(usize, String)>,
+    gamepads: &[(usize, HardwareIds)],
+    controllers: &[(String, HardwareIds)],
+) {
+    paired.retain(|(gamepad, controller)| {
+        gamepads.iter().any(|(id, _)| id == gamepad)
+            && controllers.iter().any(|(id, _)| id == controller)
+    });
+    let mut gamepads = gamepads.to_vec();
+    gamepads.sort_by_key(|(id, _)| *id);
+    for (gamepad, ids) in gamepads {
+        if ids.0.is_none()
+            || ids.1.is_none()
+            || paired
+                .iter()
+                .any(|(paired_gamepad, _)| *paired_gamepad == gamepad)
+        {
+            continue;
+        }
+        let controller = controllers.iter().find(|(controller, controller_ids)| {
+            *controller_ids == ids
+                && !paired
+                    .iter()
+                    .any(|(_, paired_controller)| paired_controller == controller)
+        });
+        if let Some((controller, _)) = controller {
+            paired.push((gamepad, controller.clone()));
+        }
+    }
+}
+
+fn hardware_ids(controller: &RawGameController) -> HardwareIds {
+    (
+        controller.HardwareVendorId().ok(),
+        controller.HardwareProductId().ok(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAD: HardwareIds = (Some(0x045e), Some(0x0b12));
+
+    fn controllers(ids: &[&str]) -> Vec<(String, HardwareIds)> {
+        ids.iter().map(|id| (id.to_string(), PAD)).collect()
+    }
+
+    #[test]
+    fn identical_gamepads_keep_their_controllers() {
+        let mut paired = Vec::new();
+        pair_controllers(
+            &mut paired,
+            &[(0, PAD), (1, PAD)],
+            &controllers(&["a", "b"]),
+        );
+        assert_eq!(paired, vec![(0, "a".to_string()), (1, "b".to_string())]);
+
+        // The order of the controllers doesn't change the pairs.
+        pair_controllers(
+            &mut paired,
+            &[(0, PAD), (1, PAD)],
+            &controllers(&["b", "a"]),
+        );
+        assert_eq!(paired, vec![(0, "a".to_string()), (1, "b".to_string())]);
+
+        // Disconnecting the first gamepad doesn't give its controller to the second one.
+        pair_controllers(&mut paired, &[(1, PAD)], &controllers(&["b"]));
+        assert_eq!(paired, vec![(1, "b".to_string())]);
+
+        // A gamepad connected later gets the new controller.
+        pair_controllers(
+            &mut paired,
+            &[(1, PAD), (2, PAD)],
+            &controllers(&["c", "b"]),
+        );
+        assert_eq!(paired, vec![(1, "b".to_string()), (2, "c".to_string())]);
+    }
+
+    #[test]
+    fn gamepads_are_paired_with_controllers_of_the_same_ids() {
+        let other = (Some(0x054c), Some(0x0ce6));
+        let mut paired = Vec::new();
+        pair_controllers(
+            &mut paired,
+            &[(0, other), (1, PAD), (2, (None, None))],
+            &[("a".to_string(), PAD), ("b".to_string(), (None, None))],
+        );
+        assert_eq!(paired, vec![(1, "a".to_string())]);
+    }
+}
@@ -246,10 +246,14 @@ impl Default for GizmoConfig {
 #[derive(Clone, Reflect, Debug)]
 #[reflect(Clone, Default)]
 pub struct GizmoLineConfig {
-    /// Line width specified in pixels.
+    /// Line width specified in physical pixels.
     ///
     /// If `perspective` is `true` then this is the size in pixels at the camera's near plane.
     ///
+    /// Lines look thinner on displays with a higher scale factor. To keep the same size in logical
+    /// pixels, multiply the width by
+    /// [`CoordinateSpaces::scale_factor`](bevy_camera::CoordinateSpaces::scale_factor).
+    ///
     /// Defaults to `2.0`.
     pub width: f32,
     /// Apply perspective to gizmo lines.
@@ -73,6 +73,7 @@ impl AssetSaver for CompressedImageSaver {
             asset_usage: image.asset_usage,
             texture_format: None,
             array_layout: None,
+            ignore_budget: false,
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::ImageLoader;
+use crate::{ImageBudget, ImageLoader};
 
 #[cfg(feature = "basis-universal")]
 use super::basis::*;
@@ -174,6 +174,9 @@ pub const TRANSPARENT_IMAGE_HANDLE: Handle<Image> =
 pub struct ImagePlugin {
     /// The default image sampler to use when [`ImageSampler`] is set to `Default`.
     pub default_sampler: ImageSamplerDescriptor,
+    /// The limits applied to images as they are loaded. These are further limited by the
+    /// maximum texture size supported by the GPU.
+    pub budget: ImageBudget,
 }
 
 impl Default for ImagePlugin {
@@ -187,6 +190,7 @@ impl ImagePlugin {
     pub fn default_linear() -> ImagePlugin {
         ImagePlugin {
             default_sampler: ImageSamplerDescriptor::linear(),
+            budget: ImageBudget::default(),
         }
     }
 
@@ -194,6 +198,7 @@ impl ImagePlugin {
     pub fn default_nearest() -> ImagePlugin {
         ImagePlugin {
             default_sampler: ImageSamplerDescriptor::nearest(),
+            budget: ImageBudget::default(),
         }
     }
 }
@@ -206,7 +211,7 @@ impl Plugin for ImagePlugin {
         #[cfg(feature = "hdr")]
         app.init_asset_loader::<crate::HdrTextureLoader>();
 
-        app.init_asset::<Image>();
+        app.init_asset::<Image>().insert_resource(self.budget);
         #[cfg(feature = "bevy_reflect")]
         app.register_asset_reflect::<Image>();
 
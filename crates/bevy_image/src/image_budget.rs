@@ -0,0 +1,323 @@
+use crate::Image;
+use bevy_ecs::resource::Resource;
+use serde::{Deserialize, Serialize};
+use wgpu_types::{TextureDimension, TextureFormat};
+
+/// Limits applied to images as they are loaded, so that large source textures fit within the
+/// size and memory limits of the device instead of failing to upload or thrashing memory.
+///
+/// The budget is configured through [`ImagePlugin::budget`](crate::ImagePlugin::budget), and is
+/// further limited at runtime by the maximum texture size supported by the GPU. The resulting
+/// budget is available as a resource once the renderer is initialized, and is applied by the
+/// [`ImageLoader`](crate::ImageLoader) unless
+/// [`ImageLoaderSettings::ignore_budget`](crate::ImageLoaderSettings::ignore_budget) is set.
+///
+/// Use [`ImageBudget::low_memory`] on platforms with little memory, e.g. mobile and ARM devices:
+///
+/// ```
+/// # use bevy_image::{ImageBudget, ImagePlugin};
+/// let image_plugin = ImagePlugin {
+///     budget: if cfg!(any(target_os = "android", target_os = "ios")) {
+///         ImageBudget::low_memory()
+///     } else {
+///         ImageBudget::default()
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageBudget {
+    /// The maximum width and height of loaded images.
+    ///
+    /// Larger images are halved in size until they fit. If the image has a mip chain, its largest
+    /// mip levels are dropped instead.
+    pub max_dimension: u32,
+    /// The number of times every image is halved in size, whether or not it fits in
+    /// [`max_dimension`](Self::max_dimension). E.g. `1` loads all images at half resolution.
+    pub downscale_steps: u32,
+    /// Converts images with 32-bit float channels to 16-bit float channels, halving their size
+    /// in memory.
+    pub prefer_half_float: bool,
+}
+
+impl Default for ImageBudget {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+impl ImageBudget {
+    /// A budget that leaves images untouched.
+    pub const UNLIMITED: Self = Self {
+        max_dimension: u32::MAX,
+        downscale_steps: 0,
+        prefer_half_float: false,
+    };
+
+    /// A budget for devices with little memory, limiting images to 2048 pixels and using 16-bit
+    /// float channels.
+    pub const fn low_memory() -> Self {
+        Self {
+            max_dimension: 2048,
+            downscale_steps: 0,
+            prefer_half_float: true,
+        }
+    }
+
+    /// Returns this budget, limited to textures of at most `max_texture_dimension_2d` pixels,
+    /// as supported by the GPU.
+    pub fn with_max_texture_dimension(mut self, max_texture_dimension_2d: u32) -> Self {
+        self.max_dimension = self.max_dimension.min(max_texture_dimension_2d);
+        self
+    }
+
+    /// Returns `true` if `image` fits in this budget.
+    pub fn fits(&self, image: &Image) -> bool {
+        image.width().max(image.height()) <= self.max_dimension
+    }
+}
+
+impl Image {
+    /// Reduces the size and precision of this image to fit in `budget`.
+    ///
+    /// Only 2D images that aren't arrays can be downscaled. Mip chains are supported by dropping
+    /// their largest levels, and images without mips can be downscaled if they use a format with
+    /// 8 bits per channel, such as [`TextureFormat::Rgba8UnormSrgb`].
+    ///
+    /// Returns `false` if the image still doesn't fit in the budget.
+    pub fn apply_budget(&mut self, budget: &ImageBudget) -> bool {
+        if budget.prefer_half_float {
+            self.convert_to_half_float();
+        }
+
+        let size = self.texture_descriptor.size;
+        // Zero-sized images have nothing to downscale.
+        if self.data.is_none()
+            || self.texture_descriptor.dimension != TextureDimension::D2
+            || size.depth_or_array_layers != 1
+            || size.width == 0
+            || size.height == 0
+        {
+            return budget.fits(self);
+        }
+
+        // Find the number of times the image needs to be halved, without going below one pixel.
+        let max_size = size.width.max(size.height);
+        let mut steps = budget.downscale_steps;
+        while max_size.checked_shr(steps).unwrap_or(0) > budget.max_dimension {
+            steps += 1;
+        }
+        steps = steps.min(max_size.ilog2());
+
+        while steps > 0 && (self.drop_largest_mip() || self.halve_8bit_channels()) {
+            steps -= 1;
+        }
+        budget.fits(self)
+    }
+
+    /// Drops the largest level of the mip chain, if any.
+    fn drop_largest_mip(&mut self) -> bool {
+        let descriptor = &self.texture_descriptor;
+        let format = descriptor.format;
+        let (block_width, block_height) = format.block_dimensions();
+        let Some(block_size) = format.block_copy_size(None) else {
+            return false;
+        };
+        let size = descriptor.size;
+        let next_width = (size.width / 2).max(1);
+        let next_height = (size.height / 2).max(1);
+        // The size of compressed textures must be a multiple of their block size.
+        if descriptor.mip_level_count <= 1
+            || !next_width.is_multiple_of(block_width)
+            || !next_height.is_multiple_of(block_height)
+        {
+            return false;
+        }
+
+        let largest_mip_size = size.width.div_ceil(block_width) as usize
+            * size.height.div_ceil(block_height) as usize
+            * block_size as usize;
+        let Some(data) = &mut self.data else {
+            return false;
+        };
+        if data.len() <= largest_mip_size {
+            return false;
+        }
+        data.drain(..largest_mip_size);
+        self.texture_descriptor.size.width = next_width;
+        self.texture_descriptor.size.height = next_height;
+        self.texture_descriptor.mip_level_count -= 1;
+        true
+    }
+
+    /// Halves the size of an image with 8 bits per channel and no mips, by averaging each 2x2 block
+    /// of pixels.
+    fn halve_8bit_channels(&mut self) -> bool {
+        let channels = match self.texture_descriptor.format {
+            TextureFormat::R8Unorm => 1,
+            TextureFormat::Rg8Unorm => 2,
+            TextureFormat::Rgba8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Bgra8UnormSrgb => 4,
+            _ => return false,
+        };
+        let size = self.texture_descriptor.size;
+        if self.texture_descriptor.mip_level_count != 1 || size.width.max(size.height) <= 1 {
+            return false;
+        }
+        let Some(data) = &self.data else {
+            return false;
+        };
+
+        let (width, height) = (size.width as usize, size.height as usize);
+        let (new_width, new_height) = ((width / 2).max(1), (height / 2).max(1));
+        let mut new_data = Vec::with_capacity(new_width * new_height * channels);
+        for y in 0..new_height {
+            let rows = [(y * 2).min(height - 1), (y * 2 + 1).min(height - 1)];
+            for x in 0..new_width {
+                let columns = [(x * 2).min(width - 1), (x * 2 + 1).min(width - 1)];
+                for channel in 0..channels {
+                    let sum: u32 = rows
+                        .iter()
+                        .flat_map(|row| columns.iter().map(move |column| (row, column)))
+                        .map(|(row, column)| {
+                            data[(row * width + column) * channels + channel] as u32
+                        })
+                        .sum();
+                    new_data.push(((sum + 2) / 4) as u8);
+                }
+            }
+        }
+
+        self.data = Some(new_data);
+        self.texture_descriptor.size.width = new_width as u32;
+        self.texture_descriptor.size.height = new_height as u32;
+        true
+    }
+
+    /// Converts 32-bit float channels to 16-bit float channels.
+    fn convert_to_half_float(&mut self) {
+        let format = match self.texture_descriptor.format {
+            TextureFormat::R32Float => TextureFormat::R16Float,
+            TextureFormat::Rg32Float => TextureFormat::Rg16Float,
+            TextureFormat::Rgba32Float => TextureFormat::Rgba16Float,
+            _ => return,
+        };
+        let Some(data) = &self.data else {
+            return;
+        };
+        self.data = Some(
+            data.chunks_exact(4)
+                .flat_map(|bytes| {
+                    let value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    half::f16::from_f32(value).to_le_bytes()
+                })
+                .collect(),
+        );
+        self.texture_descriptor.format = format;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::RenderAssetUsages;
+    use wgpu_types::Extent3d;
+
+    fn rgba8_image(width: u32, height: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn downscale_to_max_dimension() {
+        let mut image = rgba8_image(1000, 250);
+        assert!(image.apply_budget(&ImageBudget {
+            max_dimension: 256,
+            ..ImageBudget::default()
+        }));
+        assert_eq!((image.width(), image.height()), (250, 62));
+        assert_eq!(image.data.as_ref().unwrap().len(), 250 * 62 * 4);
+        assert_eq!(
+            image.get_color_at(10, 10).unwrap(),
+            rgba8_image(1, 1).get_color_at(0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn forced_downscale() {
+        let mut image = rgba8_image(64, 64);
+        assert!(image.apply_budget(&ImageBudget {
+            downscale_steps: 2,
+            ..ImageBudget::default()
+        }));
+        assert_eq!((image.width(), image.height()), (16, 16));
+    }
+
+    #[test]
+    fn zero_sized_images_are_kept() {
+        for (width, height) in [(0, 0), (0, 4096), (4096, 0)] {
+            let mut image = Image::new(
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                Vec::new(),
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::default(),
+            );
+            let fits = image.apply_budget(&ImageBudget {
+                max_dimension: 256,
+                downscale_steps: 2,
+                ..ImageBudget::default()
+            });
+            assert_eq!(fits, width == 0 && height == 0);
+            assert_eq!((image.width(), image.height()), (width, height));
+        }
+    }
+
+    #[test]
+    fn drop_largest_mips() {
+        let mut image = rgba8_image(4, 4);
+        // Mips of 4x4, 2x2 and 1x1 pixels.
+        image.data = Some((0..(16 + 4 + 1) * 4).map(|i| i as u8).collect());
+        image.texture_descriptor.mip_level_count = 3;
+        assert!(image.apply_budget(&ImageBudget {
+            max_dimension: 2,
+            ..ImageBudget::default()
+        }));
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.texture_descriptor.mip_level_count, 2);
+        assert_eq!(image.data.as_ref().unwrap()[0], 64);
+    }
+
+    #[test]
+    fn half_float() {
+        let mut image = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            bytemuck::cast_slice(&[0.5f32, 1.0, 2.0, 1.0]),
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::default(),
+        );
+        assert!(image.apply_budget(&ImageBudget::low_memory()));
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba16Float);
+        let expected: Vec<u8> = [0.5, 1.0, 2.0, 1.0]
+            .into_iter()
+            .flat_map(|value| half::f16::from_f32(value).to_le_bytes())
+            .collect();
+        assert_eq!(image.data.unwrap(), expected);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
     image::{Image, ImageFormat, ImageType, TextureError},
-    TextureReinterpretationError,
+    ImageBudget, TextureReinterpretationError,
 };
 use bevy_asset::{io::Reader, AssetLoader, LoadContext, RenderAssetUsages};
 use bevy_reflect::TypePath;
 use thiserror::Error;
+use tracing::warn;
 
 use super::{CompressedImageFormats, ImageSampler};
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, TypePath)]
 pub struct ImageLoader {
     supported_compressed_formats: CompressedImageFormats,
+    budget: ImageBudget,
 }
 
 impl ImageLoader {
@@ -81,8 +83,15 @@ impl ImageLoader {
     pub fn new(supported_compressed_formats: CompressedImageFormats) -> Self {
         Self {
             supported_compressed_formats,
+            budget: ImageBudget::default(),
         }
     }
+
+    /// Sets the [`ImageBudget`] applied to loaded images.
+    pub fn with_budget(mut self, budget: ImageBudget) -> Self {
+        self.budget = budget;
+        self
+    }
 }
 
 /// How to determine an image's format when loading.
@@ -136,6 +145,11 @@ pub struct ImageLoaderSettings {
     /// uniform type.
     #[serde(default)]
     pub array_layout: Option<ImageArrayLayout>,
+    /// Loads the image as is, instead of reducing it to fit in the [`ImageBudget`].
+    ///
+    /// Useful for images that must keep their exact size, e.g. lookup tables.
+    #[serde(default)]
+    pub ignore_budget: bool,
 }
 
 impl Default for ImageLoaderSettings {
@@ -147,6 +161,7 @@ impl Default for ImageLoaderSettings {
             sampler: ImageSampler::Default,
             asset_usage: RenderAssetUsages::default(),
             array_layout: None,
+            ignore_budget: false,
         }
     }
 }
@@ -176,8 +191,15 @@ impl AssetLoader for ImageLoader {
         settings: &ImageLoaderSettings,
         load_context: &mut LoadContext<'_>,
     ) -> Result<Image, Self::Error> {
-        let mut bytes = Vec::new();
-        reader.read_to_end(&mut bytes).await?;
+        // Decode memory-mapped or embedded images in place, instead of copying them first.
+        let mut buffer = Vec::new();
+        let bytes = match reader.read_to_slice() {
+            Some(bytes) => bytes,
+            None => {
+                reader.read_to_end(&mut buffer).await?;
+                &buffer
+            }
+        };
         let image_type = match settings.format {
             ImageFormatSetting::FromExtension => {
                 // use the file extension for the image type
@@ -192,7 +214,7 @@ impl AssetLoader for ImageLoader {
             }
             ImageFormatSetting::Format(format) => ImageType::Format(format),
             ImageFormatSetting::Guess => {
-                let format = image::guess_format(&bytes).map_err(|err| FileTextureError {
+                let format = image::guess_format(bytes).map_err(|err| FileTextureError {
                     error: err.into(),
                     path: format!("{}", load_context.path().path().display()),
                 })?;
@@ -206,7 +228,7 @@ impl AssetLoader for ImageLoader {
         };
 
         let mut image = Image::from_buffer(
-            &bytes,
+            bytes,
             image_type,
             self.supported_compressed_formats,
             settings.is_srgb,
@@ -231,6 +253,15 @@ impl AssetLoader for ImageLoader {
             image.reinterpret_stacked_2d_as_array(layers)?;
         }
 
+        if !settings.ignore_budget && !image.apply_budget(&self.budget) {
+            warn!(
+                "Image {} is larger than the maximum size of {} pixels of the image budget, and \
+                could not be downscaled. It may fail to load on the GPU.",
+                load_context.path(),
+                self.budget.max_dimension
+            );
+        }
+
         Ok(image)
     }
 
@@ -32,6 +32,7 @@ mod dynamic_texture_atlas_builder;
 mod exr_texture_loader;
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
+mod image_budget;
 mod image_loader;
 #[cfg(feature = "ktx2")]
 mod ktx2;
@@ -47,6 +48,7 @@ pub use dynamic_texture_atlas_builder::*;
 pub use exr_texture_loader::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
+pub use image_budget::*;
 pub use image_loader::*;
 #[cfg(feature = "ktx2")]
 pub use ktx2::*;
@@ -0,0 +1,448 @@
+//! Maps the keys, mouse buttons and gamepad buttons and axes to the actions of a game.
+//!
+//! Games usually read their input as actions, like "jump" or "pause", each bound to a few inputs
+//! that the player can rebind. An [`InputMap`] holds the [`InputBinding`]s of each action, and
+//! the [`InputMapPlugin`] updates the [`ButtonInput`] and [`Axis`] of the actions from it every
+//! frame:
+//!
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{action::{InputMap, InputMapPlugin}, prelude::*};
+//! #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! enum Action {
+//!     Jump,
+//!     Back,
+//! }
+//!
+//! fn setup(mut input_map: ResMut<InputMap<Action>>) {
+//!     input_map
+//!         .bind(Action::Jump, KeyCode::Space)
+//!         .bind(Action::Jump, GamepadButton::South)
+//!         .bind(Action::Back, KeyCode::Escape)
+//!         .bind(Action::Back, KeyCode::Tab)
+//!         .bind(Action::Back, GamepadButton::East);
+//! }
+//!
+//! fn go_back(actions: Res<ButtonInput<Action>>) {
+//!     if actions.just_pressed(Action::Back) {
+//!         // ...
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(InputMapPlugin::<Action>::default())
+//!     .add_systems(Startup, setup)
+//!     .add_systems(Update, go_back);
+//! ```
+//!
+//! With the `serialize` feature, the [`InputMap`] can be saved with the settings of the player.
+
+use crate::{
+    gamepad::{Gamepad, GamepadAxis, GamepadButton},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    Axis, ButtonInput, InputSystems,
+};
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    resource::Resource,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::{Query, Res, ResMut},
+};
+use bevy_platform::collections::HashMap;
+use core::{hash::Hash, marker::PhantomData};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The actions of a game, usually a fieldless enum, read from an [`InputMap`].
+pub trait InputAction: Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<A: Copy + Eq + Hash + Send + Sync + 'static> InputAction for A {}
+
+/// Adds an [`InputMap`] of the actions `A`, and updates the [`ButtonInput<A>`] and [`Axis<A>`] of
+/// the actions from it, in [`InputActionSystems`].
+pub struct InputMapPlugin<A: InputAction>(PhantomData<A>);
+
+impl<A: InputAction> Default for InputMapPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: InputAction> Plugin for InputMapPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputMap<A>>()
+            .init_resource::<ButtonInput<A>>()
+            .init_resource::<Axis<A>>()
+            .add_systems(
+                PreUpdate,
+                input_action_system::<A>
+                    .in_set(InputActionSystems)
+                    .after(InputSystems),
+            );
+    }
+}
+
+/// Label for the systems that update the actions of the [`InputMap`]s.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InputActionSystems;
+
+/// The direction of a [`GamepadAxis`] bound to an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisDirection {
+    /// The axis is pushed towards `1.0`, e.g. right or up.
+    Positive,
+    /// The axis is pushed towards `-1.0`, e.g. left or down.
+    Negative,
+}
+
+/// An input bound to an action in an [`InputMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A keyboard key.
+    Key(KeyCode),
+    /// A mouse button.
+    Mouse(MouseButton),
+    /// A button of any gamepad.
+    GamepadButton(GamepadButton),
+    /// An axis of any gamepad, pushed in a direction.
+    ///
+    /// The action is pressed when the axis is pushed past [`InputBinding::AXIS_PRESS_THRESHOLD`],
+    /// and its [`Axis`] value is how far it is pushed, from `0.0` to `1.0`.
+    GamepadAxis(GamepadAxis, AxisDirection),
+}
+
+impl InputBinding {
+    /// How far a [`GamepadAxis`](InputBinding::GamepadAxis) binding must be pushed for its action
+    /// to be pressed.
+    pub const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+    /// Returns the first of the `keys`, mouse `buttons` and buttons of the `gamepads` pressed this
+    /// frame, e.g. to rebind an action to the next input of the player.
+    ///
+    /// Gamepad axes aren't captured, as they are easily pushed by accident.
+    pub fn just_pressed<'a>(
+        keys: &ButtonInput<KeyCode>,
+        buttons: &ButtonInput<MouseButton>,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> Option<Self> {
+        if let Some(key) = keys.get_just_pressed().next() {
+            return Some(Self::Key(*key));
+        }
+        if let Some(button) = buttons.get_just_pressed().next() {
+            return Some(Self::Mouse(*button));
+        }
+        gamepads
+            .into_iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+            .map(Self::GamepadButton)
+    }
+
+    /// Returns how far this input is pressed, from `0.0` to `1.0`, and whether it is pressed.
+    fn state<'a>(
+        &self,
+        keys: Option<&ButtonInput<KeyCode>>,
+        buttons: Option<&ButtonInput<MouseButton>>,
+        gamepads: impl IntoIterator<Item = &'a Gamepad>,
+    ) -> (f32, bool) {
+        let digital = |pressed: bool| if pressed { 1.0 } else { 0.0 };
+        match *self {
+            Self::Key(key) => {
+                let pressed = keys.is_some_and(|keys| keys.pressed(key));
+                (digital(pressed), pressed)
+            }
+            Self::Mouse(button) => {
+                let pressed = buttons.is_some_and(|buttons| buttons.pressed(button));
+                (digital(pressed), pressed)
+            }
+            Self::GamepadButton(button) => gamepads
+                .into_iter()
+                .map(|gamepad| {
+                    let pressed = gamepad.pressed(button);
+                    // Analog buttons, like the triggers, have a value.
+                    let value = gamepad.get(button).unwrap_or(digital(pressed));
+                    (value, pressed)
+                })
+                .fold(
+                    (0.0, false),
+                    |(value, pressed), (other_value, other_pressed)| {
+                        (value.max(other_value), pressed || other_pressed)
+                    },
+                ),
+            Self::GamepadAxis(axis, direction) => {
+                let value = gamepads
+                    .into_iter()
+                    .filter_map(|gamepad| gamepad.get(axis))
+                    .map(|value| match direction {
+                        AxisDirection::Positive => value.max(0.0),
+                        AxisDirection::Negative => (-value).max(0.0),
+                    })
+                    .fold(0.0, f32::max);
+                (value, value >= Self::AXIS_PRESS_THRESHOLD)
+            }
+        }
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(key: KeyCode) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl From<GamepadButton> for InputBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::GamepadButton(button)
+    }
+}
+
+/// The [`InputBinding`]s of each action `A`, read by the [`InputMapPlugin`].
+///
+/// An action is pressed while any of its bindings is pressed, and its [`Axis`] value is the
+/// largest value of its bindings.
+#[derive(Resource, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap<A: InputAction> {
+    bindings: HashMap<A, Vec<InputBinding>>,
+}
+
+impl<A: InputAction> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: HashMap::default(),
+        }
+    }
+}
+
+impl<A: InputAction> InputMap<A> {
+    /// Binds the `input` to the `action`, in addition to its other bindings.
+    pub fn bind(&mut self, action: A, input: impl Into<InputBinding>) -> &mut Self {
+        let input = input.into();
+        let bindings = self.bindings.entry(action).or_default();
+        if !bindings.contains(&input) {
+            bindings.push(input);
+        }
+        self
+    }
+
+    /// Unbinds the `input` from the `action`, returning `true` if it was bound.
+    pub fn unbind(&mut self, action: A, input: impl Into<InputBinding>) -> bool {
+        let input = input.into();
+        let Some(bindings) = self.bindings.get_mut(&action) else {
+            return false;
+        };
+        let len = bindings.len();
+        bindings.retain(|binding| *binding != input);
+        bindings.len() != len
+    }
+
+    /// Replaces the `old` binding of the `action` by the `new` one, keeping its position, or
+    /// binds `new` if `old` isn't bound.
+    pub fn rebind(
+        &mut self,
+        action: A,
+        old: impl Into<InputBinding>,
+        new: impl Into<InputBinding>,
+    ) -> &mut Self {
+        let (old, new) = (old.into(), new.into());
+        let bindings = self.bindings.entry(action).or_default();
+        match bindings.iter().position(|binding| *binding == old) {
+            Some(index) if !bindings.contains(&new) => bindings[index] = new,
+            Some(index) => {
+                bindings.remove(index);
+            }
+            None if !bindings.contains(&new) => bindings.push(new),
+            None => {}
+        }
+        self
+    }
+
+    /// Removes all the bindings of the `action`.
+    pub fn clear_action(&mut self, action: A) {
+        self.bindings.remove(&action);
+    }
+
+    /// Returns the bindings of the `action`, in the order they were bound.
+    pub fn bindings(&self, action: A) -> &[InputBinding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the actions the `input` is bound to, e.g. to warn the player about a conflict
+    /// before rebinding.
+    pub fn actions(&self, input: impl Into<InputBinding>) -> impl Iterator<Item = A> + '_ {
+        let input = input.into();
+        self.bindings
+            .iter()
+            .filter(move |(_, bindings)| bindings.contains(&input))
+            .map(|(action, _)| *action)
+    }
+
+    /// Returns an iterator over the actions and their bindings.
+    pub fn iter(&self) -> impl Iterator<Item = (A, &[InputBinding])> {
+        self.bindings
+            .iter()
+            .map(|(action, bindings)| (*action, bindings.as_slice()))
+    }
+}
+
+/// Updates the [`ButtonInput<A>`] and [`Axis<A>`] of the actions from the [`InputMap<A>`].
+pub fn input_action_system<A: InputAction>(
+    input_map: Res<InputMap<A>>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    buttons: Option<Res<ButtonInput<MouseButton>>>,
+    gamepads: Query<&Gamepad>,
+    mut actions: ResMut<ButtonInput<A>>,
+    mut values: ResMut<Axis<A>>,
+) {
+    // Avoid clearing if not empty to ensure change detection is not triggered.
+    actions.bypass_change_detection().clear();
+
+    for (action, bindings) in input_map.iter() {
+        let (value, pressed) = bindings
+            .iter()
+            .map(|binding| binding.state(keys.as_deref(), buttons.as_deref(), gamepads.iter()))
+            .fold(
+                (0.0, false),
+                |(value, pressed), (other_value, other_pressed)| {
+                    (f32::max(value, other_value), pressed || other_pressed)
+                },
+            );
+        if pressed && !actions.pressed(action) {
+            actions.press(action);
+        } else if !pressed && actions.pressed(action) {
+            actions.release(action);
+        }
+        if values.get_unclamped(action) != Some(value) {
+            values.set(action, value);
+        }
+    }
+
+    // Actions whose bindings were removed, e.g. with `InputMap::clear_action`, are released.
+    let unbound = actions
+        .get_pressed()
+        .chain(values.all_axes())
+        .filter(|action| input_map.bindings(**action).is_empty())
+        .copied()
+        .collect::<Vec<_>>();
+    for action in unbound {
+        if actions.pressed(action) {
+            actions.release(action);
+        }
+        if values
+            .get_unclamped(action)
+            .is_some_and(|value| value != 0.0)
+        {
+            values.set(action, 0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPlugin;
+    use bevy_app::App;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum Action {
+        Jump,
+        Back,
+    }
+
+    #[test]
+    fn actions_follow_bindings() {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputMapPlugin::<Action>::default()));
+        app.world_mut()
+            .resource_mut::<InputMap<Action>>()
+            .bind(Action::Jump, KeyCode::Space)
+            .bind(Action::Back, KeyCode::Escape)
+            .bind(Action::Back, KeyCode::Tab);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+        let actions = app.world().resource::<ButtonInput<Action>>();
+        assert!(actions.just_pressed(Action::Back));
+        assert!(!actions.pressed(Action::Jump));
+        let values = app.world().resource::<Axis<Action>>();
+        assert_eq!(values.get(Action::Back), Some(1.0));
+
+        // Rebind the back action from tab to backspace.
+        app.world_mut().resource_mut::<InputMap<Action>>().rebind(
+            Action::Back,
+            KeyCode::Tab,
+            KeyCode::Backspace,
+        );
+        app.update();
+        let actions = app.world().resource::<ButtonInput<Action>>();
+        assert!(actions.just_released(Action::Back));
+        assert_eq!(
+            app.world()
+                .resource::<InputMap<Action>>()
+                .bindings(Action::Back),
+            [KeyCode::Escape.into(), KeyCode::Backspace.into()]
+        );
+    }
+
+    #[test]
+    fn cleared_actions_are_released() {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputMapPlugin::<Action>::default()));
+        app.world_mut()
+            .resource_mut::<InputMap<Action>>()
+            .bind(Action::Jump, KeyCode::Space);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+        assert!(app
+            .world()
+            .resource::<ButtonInput<Action>>()
+            .pressed(Action::Jump));
+
+        // The action is released even though space is still held.
+        app.world_mut()
+            .resource_mut::<InputMap<Action>>()
+            .clear_action(Action::Jump);
+        app.update();
+        let actions = app.world().resource::<ButtonInput<Action>>();
+        assert!(actions.just_released(Action::Jump));
+        assert!(!actions.pressed(Action::Jump));
+        let values = app.world().resource::<Axis<Action>>();
+        assert_eq!(values.get(Action::Jump), Some(0.0));
+    }
+}
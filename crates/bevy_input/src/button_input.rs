@@ -1,8 +1,11 @@
 //! The generic input type.
 
 use bevy_ecs::resource::Resource;
-use bevy_platform::collections::HashSet;
-use core::hash::Hash;
+use bevy_platform::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+use core::{hash::Hash, time::Duration};
 #[cfg(feature = "bevy_reflect")]
 use {
     bevy_ecs::reflect::ReflectResource,
@@ -20,6 +23,14 @@ use {
 /// * [`ButtonInput::just_pressed`] will return `true` for one frame after a press event.
 /// * [`ButtonInput::just_released`] will return `true` for one frame after a release event.
 ///
+/// The time of each press and release is recorded as well, see [`ButtonInput::time_pressed`] and
+/// [`ButtonInput::time_released`]. This is useful to buffer inputs, to allow some leniency between
+/// the buttons of a combination, or to measure the latency of the inputs.
+///
+/// The times come from the sources of the inputs, through [`ButtonInput::press_at`] and
+/// [`ButtonInput::release_at`]. [`ButtonInput::press`], [`ButtonInput::release`] and
+/// [`ButtonInput::release_all`] don't know when the inputs changed, so they don't record a time.
+///
 /// ## Multiple systems
 ///
 /// In case multiple systems are checking for [`ButtonInput::just_pressed`] or [`ButtonInput::just_released`]
@@ -50,6 +61,8 @@ use {
 /// | [`ButtonInput::just_pressed`]           | *O*(1)~                      |
 /// | [`ButtonInput::just_released`]          | *O*(1)~                      |
 /// | [`ButtonInput::pressed`]                | *O*(1)~                      |
+/// | [`ButtonInput::time_pressed`]           | *O*(1)~                      |
+/// | [`ButtonInput::time_released`]          | *O*(1)~                      |
 /// | [`ButtonInput::press`]                  | *O*(1)~*                     |
 /// | [`ButtonInput::release`]                | *O*(1)~*                     |
 /// | [`ButtonInput::release_all`]            | *O*(n)~*                     |
@@ -129,6 +142,10 @@ pub struct ButtonInput<T: Clone + Eq + Hash + Send + Sync + 'static> {
     just_pressed: HashSet<T>,
     /// A collection of every button that has just been released.
     just_released: HashSet<T>,
+    /// When every button that is currently being pressed was pressed.
+    press_times: HashMap<T, Instant>,
+    /// When every button that has just been released was released.
+    release_times: HashMap<T, Instant>,
 }
 
 impl<T: Clone + Eq + Hash + Send + Sync + 'static> Default for ButtonInput<T> {
@@ -137,6 +154,8 @@ impl<T: Clone + Eq + Hash + Send + Sync + 'static> Default for ButtonInput<T> {
             pressed: Default::default(),
             just_pressed: Default::default(),
             just_released: Default::default(),
+            press_times: Default::default(),
+            release_times: Default::default(),
         }
     }
 }
@@ -145,11 +164,27 @@ impl<T> ButtonInput<T>
 where
     T: Clone + Eq + Hash + Send + Sync + 'static,
 {
-    /// Registers a press for the given `input`.
+    /// Registers a press for the given `input`, without recording when it happened.
     pub fn press(&mut self, input: T) {
+        self.press_timed(input, None);
+    }
+
+    /// Registers a press for the given `input`, that happened at `time`.
+    ///
+    /// Use this instead of [`ButtonInput::press`] when the source of the input reports when it
+    /// was pressed.
+    pub fn press_at(&mut self, input: T, time: Instant) {
+        self.press_timed(input, Some(time));
+    }
+
+    /// Registers a press for the given `input`, that happened at `time` if it is known.
+    pub(crate) fn press_timed(&mut self, input: T, time: Option<Instant>) {
         // Returns `true` if the `input` wasn't pressed.
         if self.pressed.insert(input.clone()) {
-            self.just_pressed.insert(input);
+            self.just_pressed.insert(input.clone());
+            if let Some(time) = time {
+                self.press_times.insert(input, time);
+            }
         }
     }
 
@@ -168,18 +203,60 @@ where
         inputs.into_iter().all(|it| self.pressed(it))
     }
 
-    /// Registers a release for the given `input`.
+    /// Registers a release for the given `input`, without recording when it happened.
     pub fn release(&mut self, input: T) {
+        self.release_timed(input, None);
+    }
+
+    /// Registers a release for the given `input`, that happened at `time`.
+    ///
+    /// Use this instead of [`ButtonInput::release`] when the source of the input reports when it
+    /// was released.
+    pub fn release_at(&mut self, input: T, time: Instant) {
+        self.release_timed(input, Some(time));
+    }
+
+    /// Registers a release for the given `input`, that happened at `time` if it is known.
+    pub(crate) fn release_timed(&mut self, input: T, time: Option<Instant>) {
         // Returns `true` if the `input` was pressed.
         if self.pressed.remove(&input) {
-            self.just_released.insert(input);
+            self.press_times.remove(&input);
+            self.just_released.insert(input.clone());
+            match time {
+                Some(time) => self.release_times.insert(input, time),
+                None => self.release_times.remove(&input),
+            };
         }
     }
 
-    /// Registers a release for all currently pressed inputs.
+    /// Registers a release for all currently pressed inputs, without recording when it happened.
     pub fn release_all(&mut self) {
+        self.press_times.clear();
         // Move all items from pressed into just_released
-        self.just_released.extend(self.pressed.drain());
+        for input in self.pressed.drain() {
+            self.release_times.remove(&input);
+            self.just_released.insert(input);
+        }
+    }
+
+    /// Returns when the `input` was pressed, or `None` if it isn't pressed or the time of its
+    /// press isn't known.
+    pub fn time_pressed(&self, input: T) -> Option<Instant> {
+        self.press_times.get(&input).copied()
+    }
+
+    /// Returns for how long the `input` has been pressed, or `None` if it isn't pressed or the
+    /// time of its press isn't known.
+    ///
+    /// This reads the current time, see [`Instant::elapsed`].
+    pub fn pressed_duration(&self, input: T) -> Option<Duration> {
+        self.time_pressed(input).map(|time| time.elapsed())
+    }
+
+    /// Returns when the `input` was released, or `None` if it hasn't been released during the
+    /// current frame or the time of its release isn't known.
+    pub fn time_released(&self, input: T) -> Option<Instant> {
+        self.release_times.get(&input).copied()
     }
 
     /// Returns `true` if the `input` has been pressed during the current frame.
@@ -227,6 +304,7 @@ where
     ///
     /// Future calls to [`ButtonInput::just_released`] for the given input will return false until a new release event occurs.
     pub fn clear_just_released(&mut self, input: T) -> bool {
+        self.release_times.remove(&input);
         self.just_released.remove(&input)
     }
 
@@ -235,6 +313,8 @@ where
         self.pressed.remove(&input);
         self.just_pressed.remove(&input);
         self.just_released.remove(&input);
+        self.press_times.remove(&input);
+        self.release_times.remove(&input);
     }
 
     /// Clears the `pressed`, `just_pressed`, and `just_released` data for every input.
@@ -244,6 +324,8 @@ where
         self.pressed.clear();
         self.just_pressed.clear();
         self.just_released.clear();
+        self.press_times.clear();
+        self.release_times.clear();
     }
 
     /// Clears the `just pressed` and `just released` data for every input.
@@ -252,6 +334,7 @@ where
     pub fn clear(&mut self) {
         self.just_pressed.clear();
         self.just_released.clear();
+        self.release_times.clear();
     }
 
     /// An iterator visiting every pressed input in arbitrary order.
@@ -277,6 +360,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::ButtonInput;
+    use bevy_platform::time::Instant;
+    use core::time::Duration;
 
     /// Used for testing the functionality of [`ButtonInput`].
     #[derive(Copy, Clone, Eq, PartialEq, Hash)]
@@ -517,6 +602,59 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_press_and_release_times() {
+        let mut input = ButtonInput::default();
+        let start = Instant::now();
+        input.press_at(DummyInput::Input1, start);
+        input.press_at(DummyInput::Input2, start + Duration::from_millis(10));
+        assert_eq!(input.time_pressed(DummyInput::Input1), Some(start));
+        assert_eq!(
+            input.time_pressed(DummyInput::Input2),
+            Some(start + Duration::from_millis(10))
+        );
+
+        // Holding the input doesn't move the time of the press.
+        input.clear();
+        input.press(DummyInput::Input1);
+        assert_eq!(input.time_pressed(DummyInput::Input1), Some(start));
+
+        let end = start + Duration::from_millis(100);
+        input.release_at(DummyInput::Input1, end);
+        assert_eq!(input.time_pressed(DummyInput::Input1), None);
+        assert_eq!(input.time_released(DummyInput::Input1), Some(end));
+        assert_eq!(input.time_released(DummyInput::Input2), None);
+
+        input.clear();
+        assert_eq!(input.time_released(DummyInput::Input1), None);
+
+        input.release_all();
+        assert_eq!(input.time_pressed(DummyInput::Input2), None);
+        assert_eq!(input.time_released(DummyInput::Input2), None);
+        assert!(input.just_released(DummyInput::Input2));
+    }
+
+    #[test]
+    fn test_untimed_press_and_release() {
+        let mut input = ButtonInput::default();
+        let start = Instant::now();
+
+        // Inputs registered without a time are tracked, without recording when they changed.
+        input.press(DummyInput::Input1);
+        assert!(input.just_pressed(DummyInput::Input1));
+        assert_eq!(input.time_pressed(DummyInput::Input1), None);
+        assert_eq!(input.pressed_duration(DummyInput::Input1), None);
+
+        input.release_at(DummyInput::Input1, start);
+        assert_eq!(input.time_released(DummyInput::Input1), Some(start));
+
+        // A later release without a time doesn't keep the time of the earlier one.
+        input.press_at(DummyInput::Input1, start);
+        input.release(DummyInput::Input1);
+        assert!(input.just_released(DummyInput::Input1));
+        assert_eq!(input.time_released(DummyInput::Input1), None);
+    }
+
     #[test]
     fn test_general_input_handling() {
         let mut input = ButtonInput::default();
@@ -1,7 +1,107 @@
 use crate::ButtonInput;
-use bevy_ecs::system::Res;
+use bevy_ecs::system::{ReadOnlySystemParam, Res, StaticSystemParam, SystemParam, SystemParamItem};
 use core::hash::Hash;
 
+#[cfg(feature = "keyboard")]
+use crate::keyboard::{Key, KeyCode};
+#[cfg(feature = "mouse")]
+use crate::mouse::MouseButton;
+#[cfg(feature = "gamepad")]
+use {
+    crate::gamepad::{Gamepad, GamepadButton},
+    bevy_ecs::system::Query,
+};
+
+/// An input that can be read by the run conditions of this module.
+///
+/// Most inputs are read from their [`ButtonInput`] resource, e.g. [`KeyCode`](crate::keyboard::KeyCode),
+/// logical [`Key`](crate::keyboard::Key)s and [`MouseButton`](crate::mouse::MouseButton)s. Each
+/// gamepad has its own buttons, so [`GamepadButton`](crate::gamepad::GamepadButton)s are read from
+/// every [`Gamepad`](crate::gamepad::Gamepad) instead, and are pressed when they are pressed on any
+/// of them.
+///
+/// Other inputs read from their [`ButtonInput`] resource, like the actions of an
+/// [`InputMap`](crate::action::InputMap), implement [`ResourceButtonInput`].
+pub trait ButtonInputSource: Clone + Eq + Hash + Send + Sync + 'static {
+    /// The system parameter the [`ButtonInput`]s of this input are read from.
+    type Inputs: ReadOnlySystemParam;
+
+    /// Returns whether `check` is true for any of the [`ButtonInput`]s read from `inputs`.
+    fn any_input(
+        inputs: &SystemParamItem<Self::Inputs>,
+        check: impl FnMut(&ButtonInput<Self>) -> bool,
+    ) -> bool;
+}
+
+/// An input read from its [`ButtonInput`] resource by the run conditions of this module, which
+/// makes it a [`ButtonInputSource`].
+///
+/// ```
+/// # use bevy_input::common_conditions::ResourceButtonInput;
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// impl ResourceButtonInput for Action {}
+/// ```
+pub trait ResourceButtonInput: Clone + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: ResourceButtonInput> ButtonInputSource for T {
+    type Inputs = Option<Res<'static, ButtonInput<Self>>>;
+
+    fn any_input(
+        inputs: &SystemParamItem<Self::Inputs>,
+        check: impl FnMut(&ButtonInput<Self>) -> bool,
+    ) -> bool {
+        inputs.as_deref().is_some_and(check)
+    }
+}
+
+#[cfg(feature = "keyboard")]
+impl ResourceButtonInput for KeyCode {}
+
+#[cfg(feature = "keyboard")]
+impl ResourceButtonInput for Key {}
+
+#[cfg(feature = "mouse")]
+impl ResourceButtonInput for MouseButton {}
+
+#[cfg(feature = "gamepad")]
+impl ButtonInputSource for GamepadButton {
+    type Inputs = Query<'static, 'static, &'static Gamepad>;
+
+    fn any_input(
+        gamepads: &SystemParamItem<Self::Inputs>,
+        check: impl FnMut(&ButtonInput<Self>) -> bool,
+    ) -> bool {
+        gamepads.iter().map(Gamepad::digital).any(check)
+    }
+}
+
+/// The inputs of type `T` read by the run conditions of this module, see [`ButtonInputSource`].
+#[derive(SystemParam)]
+pub struct ButtonInputs<'w, 's, T: ButtonInputSource> {
+    inputs: StaticSystemParam<'w, 's, <T as ButtonInputSource>::Inputs>,
+}
+
+impl<T: ButtonInputSource> ButtonInputs<'_, '_, T> {
+    /// Returns whether the input is pressed.
+    pub fn pressed(&self, input: &T) -> bool {
+        T::any_input(&self.inputs, |inputs| inputs.pressed(input.clone()))
+    }
+
+    /// Returns whether the input was pressed this frame.
+    pub fn just_pressed(&self, input: &T) -> bool {
+        T::any_input(&self.inputs, |inputs| inputs.just_pressed(input.clone()))
+    }
+
+    /// Returns whether the input was released this frame.
+    pub fn just_released(&self, input: &T) -> bool {
+        T::any_input(&self.inputs, |inputs| inputs.just_released(input.clone()))
+    }
+}
+
 /// Stateful run condition that can be toggled via an input press using [`ButtonInput::just_pressed`].
 ///
 /// ```no_run
@@ -21,6 +121,9 @@ use core::hash::Hash;
 /// }
 /// ```
 ///
+/// Any input read by [`ButtonInputs`] can be used, e.g. `input_toggle_active(false,
+/// GamepadButton::Start)` toggles the pause menu with the start button of any gamepad.
+///
 /// If you want other systems to be able to access whether the toggled state is active,
 /// you should use a custom resource or a state for that:
 /// ```no_run
@@ -51,23 +154,23 @@ use core::hash::Hash;
 pub fn input_toggle_active<T>(
     default: bool,
     input: T,
-) -> impl FnMut(Res<ButtonInput<T>>) -> bool + Clone
+) -> impl FnMut(ButtonInputs<T>) -> bool + Clone
 where
-    T: Clone + Eq + Hash + Send + Sync + 'static,
+    T: ButtonInputSource,
 {
     let mut active = default;
-    move |inputs: Res<ButtonInput<T>>| {
-        active ^= inputs.just_pressed(input.clone());
+    move |inputs: ButtonInputs<T>| {
+        active ^= inputs.just_pressed(&input);
         active
     }
 }
 
 /// Run condition that is active if [`ButtonInput::pressed`] is true for the given input.
-pub fn input_pressed<T>(input: T) -> impl FnMut(Res<ButtonInput<T>>) -> bool + Clone
+pub fn input_pressed<T>(input: T) -> impl FnMut(ButtonInputs<T>) -> bool + Clone
 where
-    T: Clone + Eq + Hash + Send + Sync + 'static,
+    T: ButtonInputSource,
 {
-    move |inputs: Res<ButtonInput<T>>| inputs.pressed(input.clone())
+    move |inputs: ButtonInputs<T>| inputs.pressed(&input)
 }
 
 /// Run condition that is active if [`ButtonInput::just_pressed`] is true for the given input.
@@ -85,26 +188,34 @@ where
 ///
 /// # fn jump() {}
 /// ```
-pub fn input_just_pressed<T>(input: T) -> impl FnMut(Res<ButtonInput<T>>) -> bool + Clone
+pub fn input_just_pressed<T>(input: T) -> impl FnMut(ButtonInputs<T>) -> bool + Clone
 where
-    T: Clone + Eq + Hash + Send + Sync + 'static,
+    T: ButtonInputSource,
 {
-    move |inputs: Res<ButtonInput<T>>| inputs.just_pressed(input.clone())
+    move |inputs: ButtonInputs<T>| inputs.just_pressed(&input)
 }
 
 /// Run condition that is active if [`ButtonInput::just_released`] is true for the given input.
-pub fn input_just_released<T>(input: T) -> impl FnMut(Res<ButtonInput<T>>) -> bool + Clone
+pub fn input_just_released<T>(input: T) -> impl FnMut(ButtonInputs<T>) -> bool + Clone
 where
-    T: Clone + Eq + Hash + Send + Sync + 'static,
+    T: ButtonInputSource,
 {
-    move |inputs: Res<ButtonInput<T>>| inputs.just_released(input.clone())
+    move |inputs: ButtonInputs<T>| inputs.just_released(&input)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::prelude::KeyCode;
-    use bevy_ecs::schedule::{IntoScheduleConfigs, Schedule};
+    use crate::{
+        gamepad::{Gamepad, GamepadButton},
+        keyboard::Key,
+        prelude::KeyCode,
+    };
+    use bevy_ecs::{
+        schedule::{IntoScheduleConfigs, Schedule},
+        system::RunSystemOnce,
+        world::World,
+    };
 
     fn test_system() {}
 
@@ -116,7 +227,32 @@ mod tests {
                 .distributive_run_if(input_toggle_active(false, KeyCode::Escape))
                 .distributive_run_if(input_pressed(KeyCode::Escape))
                 .distributive_run_if(input_just_pressed(KeyCode::Escape))
-                .distributive_run_if(input_just_released(KeyCode::Escape)),
+                .distributive_run_if(input_just_released(KeyCode::Escape))
+                .distributive_run_if(input_pressed(Key::Escape))
+                .distributive_run_if(input_just_pressed(GamepadButton::Start)),
         );
     }
+
+    #[test]
+    fn gamepad_button_conditions() {
+        let mut world = World::new();
+        let mut pressed = Gamepad::default();
+        pressed.digital_mut().press(GamepadButton::North);
+        world.spawn(Gamepad::default());
+        world.spawn(pressed);
+
+        let toggle = world.register_system(input_toggle_active(false, GamepadButton::North));
+        assert!(world.run_system(toggle).unwrap());
+        assert!(!world.run_system(toggle).unwrap());
+        assert!(world
+            .run_system_once(input_pressed(GamepadButton::North))
+            .unwrap());
+        assert!(!world
+            .run_system_once(input_pressed(GamepadButton::South))
+            .unwrap());
+        // Other inputs are still read from their resource.
+        assert!(!world
+            .run_system_once(input_pressed(KeyCode::Escape))
+            .unwrap());
+    }
 }
@@ -0,0 +1,135 @@
+//! The typed input of the game controllers that aren't gamepads: arcade sticks, racing wheels and
+//! flight sticks.
+//!
+//! These controllers are also [`Gamepad`](crate::gamepad::Gamepad)s, with their buttons and axes
+//! reported as generic ones. On the platforms that identify them, the gamepad backend also inserts
+//! one of the [`ArcadeStick`], [`RacingWheel`] or [`FlightStick`] components on their entity, with
+//! their input under meaningful names, e.g. [`RacingWheel::clutch`].
+//!
+//! `bevy_gilrs` identifies them through `Windows.Gaming.Input` on Windows.
+
+use bevy_ecs::{component::Component, entity::Entity, message::Message};
+use bevy_math::CompassOctant;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::prelude::ReflectComponent;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The input of an arcade stick, updated by the gamepad backend.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Component, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ArcadeStick {
+    /// The direction the stick is pushed in, or `None` when it is centered.
+    pub stick: Option<CompassOctant>,
+    /// Whether each of the six action buttons is pressed.
+    pub actions: [bool; 6],
+    /// Whether each of the two special buttons, e.g. start and select, is pressed.
+    pub specials: [bool; 2],
+}
+
+/// The input of a racing wheel, updated by the gamepad backend.
+///
+/// Its force feedback is controlled with [`RacingWheelForceFeedback`].
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Component, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct RacingWheel {
+    /// The rotation of the wheel, from `-1.0` when turned fully left to `1.0` when turned fully
+    /// right.
+    pub wheel: f32,
+    /// How far the wheel turns each way, in degrees.
+    pub max_wheel_angle: f32,
+    /// How far the throttle pedal is pressed, from `0.0` to `1.0`.
+    pub throttle: f32,
+    /// How far the brake pedal is pressed, from `0.0` to `1.0`.
+    pub brake: f32,
+    /// How far the clutch pedal is pressed, from `0.0` to `1.0`, if the wheel has one.
+    pub clutch: Option<f32>,
+    /// How far the handbrake is pulled, from `0.0` to `1.0`, if the wheel has one.
+    pub handbrake: Option<f32>,
+    /// The gear of the pattern shifter, `0` being neutral and `-1` reverse, if the wheel has one.
+    pub gear: Option<i32>,
+    /// Whether the previous gear paddle is pressed.
+    pub previous_gear: bool,
+    /// Whether the next gear paddle is pressed.
+    pub next_gear: bool,
+}
+
+/// The input of a flight stick, updated by the gamepad backend.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Component, Default, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FlightStick {
+    /// The roll of the stick, from `-1.0` when tilted fully left to `1.0` when tilted fully right.
+    pub roll: f32,
+    /// The pitch of the stick, from `-1.0` to `1.0`.
+    pub pitch: f32,
+    /// The yaw of the stick, from `-1.0` when twisted fully left to `1.0` when twisted fully right.
+    pub yaw: f32,
+    /// The position of the throttle, from `0.0` to `1.0`.
+    pub throttle: f32,
+    /// The direction the hat switch is pushed in, or `None` when it is centered.
+    pub hat_switch: Option<CompassOctant>,
+    /// Whether the first fire button, usually the trigger, is pressed.
+    pub first_fire: bool,
+    /// Whether the second fire button is pressed.
+    pub second_fire: bool,
+}
+
+/// Applies a constant force to the wheel of a [`RacingWheel`], e.g. to push it back to the center
+/// or to simulate the grip of the tires.
+///
+/// The force stays applied until another force is requested, or the wheel is disconnected. A
+/// `force` of `0.0` stops it.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct RacingWheelForceFeedback {
+    /// The racing wheel to apply the force to.
+    pub wheel: Entity,
+    /// The force, from `-1.0` turning the wheel fully left to `1.0` turning it fully right.
+    pub force: f32,
+}
+
+impl RacingWheelForceFeedback {
+    /// Applies a constant `force` to the `wheel`.
+    pub fn new(wheel: Entity, force: f32) -> Self {
+        Self { wheel, force }
+    }
+
+    /// Stops the force applied to the `wheel`.
+    pub fn stop(wheel: Entity) -> Self {
+        Self::new(wheel, 0.0)
+    }
+}
@@ -16,7 +16,7 @@ use bevy_ecs::{
 };
 use bevy_math::ops;
 use bevy_math::Vec2;
-use bevy_platform::collections::HashMap;
+use bevy_platform::{collections::HashMap, time::Instant};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
@@ -98,15 +98,22 @@ pub struct RawGamepadButtonChangedEvent {
     pub button: GamepadButton,
     /// The value of the button.
     pub value: f32,
+    /// When the change was received from the operating system.
+    ///
+    /// This is `None` if the backend doesn't report it, in which case the time of the press or
+    /// release isn't recorded by the [`gamepad_event_processing_system`].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub timestamp: Option<Instant>,
 }
 
 impl RawGamepadButtonChangedEvent {
-    /// Creates a [`RawGamepadButtonChangedEvent`].
+    /// Creates a [`RawGamepadButtonChangedEvent`], without a timestamp.
     pub fn new(gamepad: Entity, button_type: GamepadButton, value: f32) -> Self {
         Self {
             gamepad,
             button: button_type,
             value,
+            timestamp: None,
         }
     }
 }
@@ -1544,6 +1551,77 @@ pub fn gamepad_connection_system(
     }
 }
 
+/// The platform user a [`Gamepad`] is paired with, e.g. the Xbox profile signed in with it.
+///
+/// On platforms where several users share the device, like Xbox consoles, each gamepad is paired
+/// with a user, so couch co-op games can attribute each gamepad to a profile. The gamepad backend
+/// inserts this component when the gamepad is paired, and removes it when it is unpaired, writing
+/// a [`GamepadUserChangedEvent`] in both cases.
+///
+/// `bevy_gilrs` reports the `Windows.System.User` of the gamepads on Windows.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Component, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadUser {
+    /// The identifier of the user on this device, e.g. the `NonRoamableId` of a
+    /// `Windows.System.User`.
+    pub id: String,
+}
+
+/// A change of the [`GamepadUser`] a gamepad is paired with.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadUserChangedEvent {
+    /// The gamepad whose user changed.
+    pub gamepad: Entity,
+    /// The user the gamepad is now paired with, or `None` if it was unpaired.
+    pub user: Option<GamepadUser>,
+}
+
+impl GamepadUserChangedEvent {
+    /// Creates a [`GamepadUserChangedEvent`].
+    pub fn new(gamepad: Entity, user: Option<GamepadUser>) -> Self {
+        Self { gamepad, user }
+    }
+}
+
+/// Inserts or removes the [`GamepadUser`] of the gamepads from the [`GamepadUserChangedEvent`]s.
+pub fn gamepad_user_system(
+    mut commands: Commands,
+    mut user_events: MessageReader<GamepadUserChangedEvent>,
+) {
+    for event in user_events.read() {
+        let Ok(mut gamepad) = commands.get_entity(event.gamepad) else {
+            continue;
+        };
+        match &event.user {
+            Some(user) => {
+                gamepad.insert(user.clone());
+            }
+            None => {
+                gamepad.remove::<GamepadUser>();
+            }
+        }
+    }
+}
+
 // Note that we don't expose `gilrs::Gamepad::uuid` due to
 // https://gitlab.com/gilrs-project/gilrs/-/issues/153.
 //
@@ -1625,8 +1703,9 @@ pub fn gamepad_event_processing_system(
                 gamepad,
                 button,
                 value,
+                timestamp,
             }) => {
-                let (gamepad, button, value) = (*gamepad, *button, *value);
+                let (gamepad, button, value, timestamp) = (*gamepad, *button, *value, *timestamp);
                 let Ok((mut gamepad_buttons, settings)) = gamepads.get_mut(gamepad) else {
                     continue;
                 };
@@ -1650,7 +1729,7 @@ pub fn gamepad_event_processing_system(
                     }
                     // We don't have to check if the button was previously pressed here
                     // because that check is performed within Input<T>::release()
-                    gamepad_buttons.digital.release(button);
+                    gamepad_buttons.digital.release_timed(button, timestamp);
                 } else if button_settings.is_pressed(filtered_value.raw) {
                     // Check if button was previously not pressed
                     if !gamepad_buttons.pressed(button) {
@@ -1660,7 +1739,7 @@ pub fn gamepad_event_processing_system(
                             ButtonState::Pressed,
                         ));
                     }
-                    gamepad_buttons.digital.press(button);
+                    gamepad_buttons.digital.press_timed(button, timestamp);
                 };
 
                 let button_state = if gamepad_buttons.digital.pressed(button) {
@@ -1703,25 +1782,57 @@ pub struct GamepadRumbleIntensity {
     /// By convention, this is usually a high-frequency motor on the right-hand
     /// side of the gamepad, though it may vary across platforms and hardware.
     pub weak_motor: f32,
+    /// The rumble intensity of the motor in the left trigger, on gamepads with impulse triggers
+    /// like the Xbox One and later controllers.
+    ///
+    /// Ranges from `0.0` to `1.0`.
+    ///
+    /// This is only supported on Windows, and is ignored on other platforms and on gamepads
+    /// without impulse triggers, which only rumble their main motors.
+    pub left_trigger: f32,
+    /// The rumble intensity of the motor in the right trigger, on gamepads with impulse triggers
+    /// like the Xbox One and later controllers.
+    ///
+    /// Ranges from `0.0` to `1.0`.
+    ///
+    /// This is only supported on Windows, and is ignored on other platforms and on gamepads
+    /// without impulse triggers, which only rumble their main motors.
+    pub right_trigger: f32,
 }
 
 impl GamepadRumbleIntensity {
+    /// No rumble.
+    pub const ZERO: Self = GamepadRumbleIntensity {
+        strong_motor: 0.0,
+        weak_motor: 0.0,
+        left_trigger: 0.0,
+        right_trigger: 0.0,
+    };
+
     /// Rumble both gamepad motors at maximum intensity.
     pub const MAX: Self = GamepadRumbleIntensity {
         strong_motor: 1.0,
         weak_motor: 1.0,
+        ..Self::ZERO
     };
 
     /// Rumble the weak motor at maximum intensity.
     pub const WEAK_MAX: Self = GamepadRumbleIntensity {
-        strong_motor: 0.0,
         weak_motor: 1.0,
+        ..Self::ZERO
     };
 
     /// Rumble the strong motor at maximum intensity.
     pub const STRONG_MAX: Self = GamepadRumbleIntensity {
         strong_motor: 1.0,
-        weak_motor: 0.0,
+        ..Self::ZERO
+    };
+
+    /// Rumble both trigger motors at maximum intensity.
+    pub const TRIGGERS_MAX: Self = GamepadRumbleIntensity {
+        left_trigger: 1.0,
+        right_trigger: 1.0,
+        ..Self::ZERO
     };
 
     /// Creates a new rumble intensity with weak motor intensity set to the given value.
@@ -1730,7 +1841,7 @@ impl GamepadRumbleIntensity {
     pub const fn weak_motor(intensity: f32) -> Self {
         Self {
             weak_motor: intensity,
-            strong_motor: 0.0,
+            ..Self::ZERO
         }
     }
 
@@ -1740,9 +1851,34 @@ impl GamepadRumbleIntensity {
     pub const fn strong_motor(intensity: f32) -> Self {
         Self {
             strong_motor: intensity,
-            weak_motor: 0.0,
+            ..Self::ZERO
         }
     }
+
+    /// Creates a new rumble intensity with left trigger motor intensity set to the given value.
+    ///
+    /// Clamped within the `0.0` to `1.0` range.
+    pub const fn left_trigger(intensity: f32) -> Self {
+        Self {
+            left_trigger: intensity,
+            ..Self::ZERO
+        }
+    }
+
+    /// Creates a new rumble intensity with right trigger motor intensity set to the given value.
+    ///
+    /// Clamped within the `0.0` to `1.0` range.
+    pub const fn right_trigger(intensity: f32) -> Self {
+        Self {
+            right_trigger: intensity,
+            ..Self::ZERO
+        }
+    }
+
+    /// Returns `true` if the trigger motors rumble.
+    pub fn has_triggers(&self) -> bool {
+        self.left_trigger > 0.0 || self.right_trigger > 0.0
+    }
 }
 
 /// An event that controls force-feedback rumbling of a [`Gamepad`] [`entity`](Entity).
@@ -1814,13 +1950,14 @@ impl GamepadRumbleRequest {
 #[cfg(test)]
 mod tests {
     use super::{
-        gamepad_connection_system, gamepad_event_processing_system, AxisSettings,
-        AxisSettingsError, ButtonAxisSettings, ButtonSettings, ButtonSettingsError, Gamepad,
-        GamepadAxis, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
+        gamepad_connection_system, gamepad_event_processing_system, gamepad_user_system,
+        AxisSettings, AxisSettingsError, ButtonAxisSettings, ButtonSettings, ButtonSettingsError,
+        Gamepad, GamepadAxis, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
         GamepadButtonStateChangedEvent,
         GamepadConnection::{Connected, Disconnected},
-        GamepadConnectionEvent, GamepadEvent, GamepadSettings, RawGamepadAxisChangedEvent,
-        RawGamepadButtonChangedEvent, RawGamepadEvent,
+        GamepadConnectionEvent, GamepadEvent, GamepadSettings, GamepadUser,
+        GamepadUserChangedEvent, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
+        RawGamepadEvent,
     };
     use crate::ButtonState;
     use alloc::string::ToString;
@@ -1828,6 +1965,8 @@ mod tests {
     use bevy_ecs::entity::Entity;
     use bevy_ecs::message::Messages;
     use bevy_ecs::schedule::IntoScheduleConfigs;
+    use bevy_platform::time::Instant;
+    use core::time::Duration;
 
     fn test_button_axis_settings_filter(
         settings: ButtonAxisSettings,
@@ -2201,6 +2340,7 @@ mod tests {
                 (
                     gamepad_connection_system,
                     gamepad_event_processing_system.after(gamepad_connection_system),
+                    gamepad_user_system.after(gamepad_connection_system),
                 ),
             )
             .add_message::<GamepadEvent>()
@@ -2210,7 +2350,8 @@ mod tests {
             .add_message::<GamepadButtonStateChangedEvent>()
             .add_message::<GamepadAxisChangedEvent>()
             .add_message::<RawGamepadAxisChangedEvent>()
-            .add_message::<RawGamepadEvent>();
+            .add_message::<RawGamepadEvent>()
+            .add_message::<GamepadUserChangedEvent>();
             Self { app }
         }
 
@@ -2440,6 +2581,26 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn user_pairing_event() {
+        let mut ctx = TestContext::new();
+        let gamepad = ctx.send_gamepad_connection_event(None);
+        let user = GamepadUser {
+            id: "user".to_string(),
+        };
+        ctx.app
+            .world_mut()
+            .write_message(GamepadUserChangedEvent::new(gamepad, Some(user.clone())));
+        ctx.update();
+        assert_eq!(ctx.app.world().get::<GamepadUser>(gamepad), Some(&user));
+
+        ctx.app
+            .world_mut()
+            .write_message(GamepadUserChangedEvent::new(gamepad, None));
+        ctx.update();
+        assert!(ctx.app.world().get::<GamepadUser>(gamepad).is_none());
+    }
+
     #[test]
     fn gamepad_axis_valid() {
         let mut ctx = TestContext::new();
@@ -2777,6 +2938,47 @@ mod tests {
             .pressed(GamepadButton::DPadDown));
     }
 
+    #[test]
+    fn gamepad_button_times_come_from_the_events() {
+        let mut ctx = TestContext::new();
+
+        let entity = ctx.send_gamepad_connection_event(None);
+        let digital_settings = GamepadSettings::default().default_button_settings;
+        let start = Instant::now();
+        let end = start + Duration::from_millis(100);
+
+        ctx.send_raw_gamepad_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent {
+            timestamp: Some(start),
+            ..RawGamepadButtonChangedEvent::new(
+                entity,
+                GamepadButton::South,
+                digital_settings.press_threshold,
+            )
+        }));
+        ctx.update();
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert_eq!(
+            gamepad.digital().time_pressed(GamepadButton::South),
+            Some(start)
+        );
+
+        ctx.send_raw_gamepad_event(RawGamepadEvent::Button(RawGamepadButtonChangedEvent {
+            timestamp: Some(end),
+            ..RawGamepadButtonChangedEvent::new(
+                entity,
+                GamepadButton::South,
+                digital_settings.release_threshold,
+            )
+        }));
+        ctx.update();
+        let gamepad = ctx.app.world().get::<Gamepad>(entity).unwrap();
+        assert_eq!(gamepad.digital().time_pressed(GamepadButton::South), None);
+        assert_eq!(
+            gamepad.digital().time_released(GamepadButton::South),
+            Some(end)
+        );
+    }
+
     #[test]
     fn gamepad_buttons_just_pressed() {
         let mut ctx = TestContext::new();
@@ -0,0 +1,483 @@
+//! Platform-specific glyphs for input prompts, e.g. "Press Ⓑ to go back".
+//!
+//! The same logical button is labeled differently on each device: the bottom face button of a
+//! gamepad is "A" on Xbox controllers, "Cross" on `PlayStation` controllers and "B" on Nintendo Switch
+//! controllers. An [`InputPrompt`] holds the keyboard and gamepad bindings of an action, and is
+//! resolved into the [`InputGlyph`] to display using the [`InputGlyphStyle`] of the
+//! [`ActiveInputDevice`], which follows the device the player used last.
+
+use crate::{
+    gamepad::{Gamepad, GamepadButton, GamepadButtonStateChangedEvent},
+    keyboard::{is_numpad_key, Key, KeyCode, KeyboardInput, KeyboardLayout},
+    ButtonState,
+};
+use alloc::{borrow::Cow, format};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    entity::Entity,
+    message::MessageReader,
+    resource::Resource,
+    system::{Query, ResMut},
+};
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The family of glyphs used to display input prompts.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputGlyphStyle {
+    /// Keyboard and mouse glyphs.
+    #[default]
+    KeyboardMouse,
+    /// Xbox controller glyphs: A, B, X, Y, LB, RB...
+    Xbox,
+    /// `PlayStation` controller glyphs: Cross, Circle, Square, Triangle, L1, R1...
+    PlayStation,
+    /// Nintendo Switch controller glyphs: B, A, Y, X, L, R...
+    ///
+    /// Note that the face buttons are swapped compared to Xbox controllers: the bottom button
+    /// ([`GamepadButton::South`]) is labeled "B".
+    Switch,
+    /// Glyphs for gamepads from other vendors, naming buttons by their position.
+    GenericGamepad,
+}
+
+impl InputGlyphStyle {
+    /// The USB vendor ID of Microsoft.
+    const MICROSOFT_VENDOR_ID: u16 = 0x045e;
+    /// The USB vendor ID of Sony.
+    const SONY_VENDOR_ID: u16 = 0x054c;
+    /// The USB vendor ID of Nintendo.
+    const NINTENDO_VENDOR_ID: u16 = 0x057e;
+
+    /// Returns the glyph style matching the vendor of `gamepad`.
+    pub fn from_gamepad(gamepad: &Gamepad) -> Self {
+        match gamepad.vendor_id() {
+            Some(Self::MICROSOFT_VENDOR_ID) => Self::Xbox,
+            Some(Self::SONY_VENDOR_ID) => Self::PlayStation,
+            Some(Self::NINTENDO_VENDOR_ID) => Self::Switch,
+            _ => Self::GenericGamepad,
+        }
+    }
+
+    /// Returns `true` if this style displays gamepad glyphs.
+    pub fn is_gamepad(&self) -> bool {
+        !matches!(self, Self::KeyboardMouse)
+    }
+}
+
+/// The input device the player used last, and the glyphs to use for its input prompts.
+///
+/// This is updated when a key or a gamepad button is pressed, and only changes when another device
+/// is used, so that change detection can be used to update input prompts.
+#[derive(Resource, Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, Default, PartialEq, Clone)
+)]
+pub struct ActiveInputDevice {
+    /// The gamepad entity that was used last, or [`None`] if the keyboard or mouse was used last.
+    pub gamepad: Option<Entity>,
+    /// The glyph style of the device.
+    pub glyph_style: InputGlyphStyle,
+}
+
+/// Updates the [`ActiveInputDevice`] when a key or gamepad button is pressed.
+pub fn update_active_input_device(
+    mut active_device: ResMut<ActiveInputDevice>,
+    mut keyboard_inputs: MessageReader<KeyboardInput>,
+    #[cfg(feature = "mouse")] mut mouse_button_inputs: MessageReader<
+        crate::mouse::MouseButtonInput,
+    >,
+    mut gamepad_button_events: MessageReader<GamepadButtonStateChangedEvent>,
+    gamepads: Query<&Gamepad>,
+) {
+    let mut new_device = None;
+
+    let keyboard_pressed = keyboard_inputs
+        .read()
+        .any(|input| input.state == ButtonState::Pressed);
+    #[cfg(feature = "mouse")]
+    let keyboard_pressed = mouse_button_inputs
+        .read()
+        .fold(keyboard_pressed, |pressed, input| {
+            pressed || input.state == ButtonState::Pressed
+        });
+    if keyboard_pressed {
+        new_device = Some(ActiveInputDevice::default());
+    }
+
+    // Gamepads take priority if both were used in the same frame, as they are less likely to be
+    // pressed by accident.
+    if let Some(event) = gamepad_button_events
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .last()
+    {
+        new_device = Some(ActiveInputDevice {
+            gamepad: Some(event.entity),
+            glyph_style: gamepads
+                .get(event.entity)
+                .map(InputGlyphStyle::from_gamepad)
+                .unwrap_or(InputGlyphStyle::GenericGamepad),
+        });
+    }
+
+    if let Some(new_device) = new_device {
+        active_device.set_if_neq(new_device);
+    }
+}
+
+/// A single key or button to display in an input prompt.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputGlyph {
+    /// A keyboard key.
+    Key(KeyCode),
+    /// A gamepad button, displayed with a given [`InputGlyphStyle`].
+    GamepadButton(GamepadButton, InputGlyphStyle),
+}
+
+impl InputGlyph {
+    /// The number of columns of the gamepad glyph atlas, with one glyph per
+    /// [standard gamepad button](GamepadButton::all).
+    pub const GAMEPAD_ATLAS_COLUMNS: u32 = 19;
+    /// The number of rows of the gamepad glyph atlas, with one row per gamepad
+    /// [`InputGlyphStyle`].
+    pub const GAMEPAD_ATLAS_ROWS: u32 = 4;
+
+    /// Returns the index of this glyph in a gamepad glyph atlas, or [`None`] for keys and
+    /// non-standard buttons.
+    ///
+    /// The atlas is a grid of [`GAMEPAD_ATLAS_COLUMNS`](Self::GAMEPAD_ATLAS_COLUMNS) by
+    /// [`GAMEPAD_ATLAS_ROWS`](Self::GAMEPAD_ATLAS_ROWS) glyphs. Its rows hold the
+    /// [`Xbox`](InputGlyphStyle::Xbox), [`PlayStation`](InputGlyphStyle::PlayStation),
+    /// [`Switch`](InputGlyphStyle::Switch) and [`GenericGamepad`](InputGlyphStyle::GenericGamepad)
+    /// glyphs, in this order, and its columns hold the buttons in the order of [`GamepadButton::all`].
+    pub fn gamepad_atlas_index(&self) -> Option<usize> {
+        let Self::GamepadButton(button, style) = self else {
+            return None;
+        };
+        let row = match style {
+            InputGlyphStyle::Xbox => 0,
+            InputGlyphStyle::PlayStation => 1,
+            InputGlyphStyle::Switch => 2,
+            InputGlyphStyle::GenericGamepad => 3,
+            InputGlyphStyle::KeyboardMouse => return None,
+        };
+        let column = GamepadButton::all()
+            .iter()
+            .position(|standard_button| standard_button == button)?;
+        Some(row * Self::GAMEPAD_ATLAS_COLUMNS as usize + column)
+    }
+
+    /// Returns a short text label for this glyph, such as `"Esc"`, `"A"` or `"Cross"`.
+    ///
+    /// This can be displayed when no glyph image is available.
+    pub fn label(&self) -> Cow<'static, str> {
+        match *self {
+            Self::Key(key_code) => key_label(key_code),
+            Self::GamepadButton(GamepadButton::Other(index), _) => {
+                Cow::Owned(format!("Button {index}"))
+            }
+            Self::GamepadButton(button, style) => {
+                Cow::Borrowed(gamepad_button_label(button, style))
+            }
+        }
+    }
+
+    /// Returns a short text label for this glyph, like [`label`](Self::label), with the character
+    /// the key types in the current [`KeyboardLayout`], e.g. `"A"` for `KeyQ` on an AZERTY keyboard.
+    pub fn label_in_layout(&self, layout: &KeyboardLayout) -> Cow<'static, str> {
+        if let Self::Key(key_code) = *self
+            && let Some(Key::Character(character)) = layout.logical_key(key_code)
+            && !is_numpad_key(key_code)
+        {
+            return Cow::Owned(character.to_uppercase());
+        }
+        self.label()
+    }
+}
+
+fn key_label(key_code: KeyCode) -> Cow<'static, str> {
+    let label = match key_code {
+        KeyCode::Escape => "Esc",
+        KeyCode::Enter | KeyCode::NumpadEnter => "Enter",
+        KeyCode::Space => "Space",
+        KeyCode::Tab => "Tab",
+        KeyCode::Backspace => "Backspace",
+        KeyCode::Delete => "Del",
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift",
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl",
+        KeyCode::AltLeft | KeyCode::AltRight => "Alt",
+        KeyCode::ArrowUp => "↑",
+        KeyCode::ArrowDown => "↓",
+        KeyCode::ArrowLeft => "←",
+        KeyCode::ArrowRight => "→",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::Backquote => "`",
+        _ => {
+            // `KeyA` -> `A`, `Digit1` -> `1`, `F1` -> `F1`, `Numpad1` -> `Num 1`
+            let name = format!("{key_code:?}");
+            let label = if let Some(letter) = name.strip_prefix("Key") {
+                letter.into()
+            } else if let Some(digit) = name.strip_prefix("Digit") {
+                digit.into()
+            } else if let Some(numpad_key) = name.strip_prefix("Numpad") {
+                format!("Num {numpad_key}")
+            } else {
+                name
+            };
+            return Cow::Owned(label);
+        }
+    };
+    Cow::Borrowed(label)
+}
+
+#[expect(
+    clippy::match_same_arms,
+    reason = "Each style lists all of its labels, even when they match the generic ones."
+)]
+fn gamepad_button_label(button: GamepadButton, style: InputGlyphStyle) -> &'static str {
+    use GamepadButton::*;
+    use InputGlyphStyle::*;
+    match (style, button) {
+        (Xbox, South) => "A",
+        (Xbox, East) => "B",
+        (Xbox, North) => "Y",
+        (Xbox, West) => "X",
+        (Xbox, LeftTrigger) => "LB",
+        (Xbox, LeftTrigger2) => "LT",
+        (Xbox, RightTrigger) => "RB",
+        (Xbox, RightTrigger2) => "RT",
+        (Xbox, Select) => "View",
+        (Xbox, Start) => "Menu",
+        (Xbox, Mode) => "Xbox",
+        (Xbox, LeftThumb) => "LS",
+        (Xbox, RightThumb) => "RS",
+
+        (PlayStation, South) => "Cross",
+        (PlayStation, East) => "Circle",
+        (PlayStation, North) => "Triangle",
+        (PlayStation, West) => "Square",
+        (PlayStation, LeftTrigger) => "L1",
+        (PlayStation, LeftTrigger2) => "L2",
+        (PlayStation, RightTrigger) => "R1",
+        (PlayStation, RightTrigger2) => "R2",
+        (PlayStation, Select) => "Create",
+        (PlayStation, Start) => "Options",
+        (PlayStation, Mode) => "PS",
+        (PlayStation, LeftThumb) => "L3",
+        (PlayStation, RightThumb) => "R3",
+
+        (Switch, South) => "B",
+        (Switch, East) => "A",
+        (Switch, North) => "X",
+        (Switch, West) => "Y",
+        (Switch, LeftTrigger) => "L",
+        (Switch, LeftTrigger2) => "ZL",
+        (Switch, RightTrigger) => "R",
+        (Switch, RightTrigger2) => "ZR",
+        (Switch, Select) => "-",
+        (Switch, Start) => "+",
+        (Switch, Mode) => "Home",
+        (Switch, LeftThumb) => "LS",
+        (Switch, RightThumb) => "RS",
+
+        (_, South) => "South",
+        (_, East) => "East",
+        (_, North) => "North",
+        (_, West) => "West",
+        (_, LeftTrigger) => "L1",
+        (_, LeftTrigger2) => "L2",
+        (_, RightTrigger) => "R1",
+        (_, RightTrigger2) => "R2",
+        (_, Select) => "Select",
+        (_, Start) => "Start",
+        (_, Mode) => "Mode",
+        (_, LeftThumb) => "L3",
+        (_, RightThumb) => "R3",
+        (_, C) => "C",
+        (_, Z) => "Z",
+        (_, DPadUp) => "D-Pad Up",
+        (_, DPadDown) => "D-Pad Down",
+        (_, DPadLeft) => "D-Pad Left",
+        (_, DPadRight) => "D-Pad Right",
+        (_, Other(_)) => "Button",
+    }
+}
+
+/// The bindings of a logical action, such as "go back" or "jump", used to display the glyph of the
+/// binding matching the [`ActiveInputDevice`].
+///
+/// ```
+/// # use bevy_input::{glyph::{InputGlyphStyle, InputPrompt}, gamepad::GamepadButton, keyboard::KeyCode};
+/// let go_back = InputPrompt::new(KeyCode::Escape, GamepadButton::East);
+///
+/// assert_eq!(go_back.glyph(InputGlyphStyle::KeyboardMouse).unwrap().label(), "Esc");
+/// assert_eq!(go_back.glyph(InputGlyphStyle::Xbox).unwrap().label(), "B");
+/// assert_eq!(go_back.glyph(InputGlyphStyle::PlayStation).unwrap().label(), "Circle");
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Hash, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputPrompt {
+    /// The key bound to the action, if any.
+    pub key: Option<KeyCode>,
+    /// The gamepad button bound to the action, if any.
+    pub gamepad_button: Option<GamepadButton>,
+}
+
+impl InputPrompt {
+    /// Creates a prompt for an action bound to both a key and a gamepad button.
+    pub const fn new(key: KeyCode, gamepad_button: GamepadButton) -> Self {
+        Self {
+            key: Some(key),
+            gamepad_button: Some(gamepad_button),
+        }
+    }
+
+    /// Returns the glyph to display for the given style, or [`None`] if the action has no binding for
+    /// this kind of device.
+    pub fn glyph(&self, style: InputGlyphStyle) -> Option<InputGlyph> {
+        if style.is_gamepad() {
+            self.gamepad_button
+                .map(|button| InputGlyph::GamepadButton(button, style))
+        } else {
+            self.key.map(InputGlyph::Key)
+        }
+    }
+}
+
+impl From<KeyCode> for InputPrompt {
+    fn from(key: KeyCode) -> Self {
+        Self {
+            key: Some(key),
+            gamepad_button: None,
+        }
+    }
+}
+
+impl From<GamepadButton> for InputPrompt {
+    fn from(gamepad_button: GamepadButton) -> Self {
+        Self {
+            key: None,
+            gamepad_button: Some(gamepad_button),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gamepad::{GamepadConnection, GamepadConnectionEvent},
+        InputPlugin,
+    };
+    use alloc::string::ToString;
+    use bevy_app::App;
+    use bevy_ecs::message::Messages;
+
+    #[test]
+    fn atlas_index() {
+        assert_eq!(
+            InputGlyph::GamepadButton(GamepadButton::South, InputGlyphStyle::Xbox)
+                .gamepad_atlas_index(),
+            Some(0)
+        );
+        assert_eq!(
+            InputGlyph::GamepadButton(GamepadButton::DPadRight, InputGlyphStyle::GenericGamepad)
+                .gamepad_atlas_index(),
+            Some(4 * 19 - 1)
+        );
+        assert_eq!(InputGlyph::Key(KeyCode::KeyA).gamepad_atlas_index(), None);
+        assert_eq!(InputGlyph::Key(KeyCode::KeyA).label(), "A");
+        assert_eq!(InputGlyph::Key(KeyCode::F5).label(), "F5");
+
+        let mut layout = KeyboardLayout::default();
+        layout.insert(KeyCode::KeyQ, Key::Character("a".into()));
+        assert_eq!(InputGlyph::Key(KeyCode::KeyQ).label_in_layout(&layout), "A");
+        assert_eq!(InputGlyph::Key(KeyCode::KeyW).label_in_layout(&layout), "W");
+    }
+
+    #[test]
+    fn active_device_follows_last_input() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let gamepad = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .resource_mut::<Messages<GamepadConnectionEvent>>()
+            .write(GamepadConnectionEvent::new(
+                gamepad,
+                GamepadConnection::Connected {
+                    name: "Pad".to_string(),
+                    vendor_id: Some(0x054c),
+                    product_id: None,
+                },
+            ));
+        app.update();
+        assert_eq!(
+            *app.world().resource::<ActiveInputDevice>(),
+            ActiveInputDevice::default()
+        );
+
+        app.world_mut()
+            .resource_mut::<Messages<GamepadButtonStateChangedEvent>>()
+            .write(GamepadButtonStateChangedEvent::new(
+                gamepad,
+                GamepadButton::South,
+                ButtonState::Pressed,
+            ));
+        app.update();
+        assert_eq!(
+            *app.world().resource::<ActiveInputDevice>(),
+            ActiveInputDevice {
+                gamepad: Some(gamepad),
+                glyph_style: InputGlyphStyle::PlayStation,
+            }
+        );
+    }
+}
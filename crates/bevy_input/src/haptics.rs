@@ -0,0 +1,246 @@
+//! Haptic feedback played on any device that can vibrate: the motors of gamepads, the impulse
+//! triggers of Xbox gamepads, and haptic pens.
+//!
+//! Gameplay code describes the feedback with a [`HapticEffect`] and plays it on a
+//! [`HapticDevice`] with the [`Haptics`] system parameter, which sends a [`HapticRequest`]. The
+//! input backends then play it in the way the device supports:
+//!
+//! - `bevy_gilrs` plays it on the motors of gamepads, and on the impulse triggers of Xbox
+//!   gamepads on Windows.
+//! - `bevy_winit` plays it on the pens with haptic feedback on Windows, through
+//!   `Windows.Devices.Haptics`.
+//!
+//! Requests for devices that can't vibrate are ignored.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::haptics::{HapticDevice, Haptics};
+//! # use core::time::Duration;
+//! #[derive(Resource)]
+//! struct Player {
+//!     gamepad: Entity,
+//! }
+//!
+//! fn on_hit(player: Res<Player>, mut haptics: Haptics) {
+//!     haptics.ramp(
+//!         HapticDevice::Gamepad(player.gamepad),
+//!         1.0,
+//!         0.0,
+//!         Duration::from_millis(300),
+//!     );
+//! }
+//! ```
+
+use bevy_ecs::{
+    entity::Entity,
+    message::{Message, MessageWriter},
+    system::SystemParam,
+};
+use core::time::Duration;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// A haptic feedback, from a single pulse to a vibration changing over time.
+///
+/// Intensities go from `0.0` to `1.0`, and are clamped to that range when played.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum HapticEffect {
+    /// A short and sharp pulse at full intensity, like the click of a button.
+    ///
+    /// It lasts [`HapticEffect::CLICK_DURATION`]. Devices with a built-in click waveform, such as
+    /// haptic pens, play it instead.
+    Click,
+    /// A vibration with a constant intensity.
+    Buzz {
+        /// The intensity of the vibration.
+        intensity: f32,
+        /// How long the vibration lasts.
+        duration: Duration,
+    },
+    /// A vibration whose intensity changes linearly over time.
+    Ramp {
+        /// The intensity at the start of the vibration.
+        from: f32,
+        /// The intensity at the end of the vibration.
+        to: f32,
+        /// How long the vibration lasts.
+        duration: Duration,
+    },
+}
+
+impl HapticEffect {
+    /// How long a [`HapticEffect::Click`] lasts on devices without a built-in click waveform.
+    pub const CLICK_DURATION: Duration = Duration::from_millis(20);
+
+    /// Creates a [`HapticEffect::Buzz`].
+    pub const fn buzz(intensity: f32, duration: Duration) -> Self {
+        Self::Buzz {
+            intensity,
+            duration,
+        }
+    }
+
+    /// Creates a [`HapticEffect::Ramp`].
+    pub const fn ramp(from: f32, to: f32, duration: Duration) -> Self {
+        Self::Ramp { from, to, duration }
+    }
+
+    /// Returns how long the effect lasts.
+    pub const fn duration(&self) -> Duration {
+        match *self {
+            Self::Click => Self::CLICK_DURATION,
+            Self::Buzz { duration, .. } | Self::Ramp { duration, .. } => duration,
+        }
+    }
+
+    /// Returns the intensity of the effect after it played for `elapsed`, or `0.0` once it's over.
+    pub fn intensity_at(&self, elapsed: Duration) -> f32 {
+        if elapsed >= self.duration() {
+            return 0.0;
+        }
+        let intensity = match *self {
+            Self::Click => 1.0,
+            Self::Buzz { intensity, .. } => intensity,
+            Self::Ramp { from, to, duration } => {
+                let t = elapsed.as_secs_f32() / duration.as_secs_f32();
+                from + (to - from) * t
+            }
+        };
+        intensity.clamp(0.0, 1.0)
+    }
+
+    /// Returns the highest intensity of the effect.
+    pub fn peak_intensity(&self) -> f32 {
+        let peak = match *self {
+            Self::Click => 1.0,
+            Self::Buzz { intensity, .. } => intensity,
+            Self::Ramp { from, to, .. } => from.max(to),
+        };
+        peak.clamp(0.0, 1.0)
+    }
+}
+
+/// A device that can play a [`HapticEffect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+pub enum HapticDevice {
+    /// Both rumble motors of a gamepad.
+    Gamepad(Entity),
+    /// Both impulse triggers of a gamepad.
+    ///
+    /// Only Xbox gamepads on Windows have them. On other gamepads, the effect is played on the
+    /// rumble motors instead.
+    GamepadTriggers(Entity),
+    /// The pen with the given touch id, see `TouchInput::id`.
+    ///
+    /// Only pens with haptic feedback on Windows can play effects.
+    Pen(u64),
+}
+
+/// A request to play or stop a [`HapticEffect`], usually sent with [`Haptics`].
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub enum HapticRequest {
+    /// Plays an effect on a device.
+    ///
+    /// Effects played at the same time on the same device add up, like
+    /// [`GamepadRumbleRequest`](crate::gamepad::GamepadRumbleRequest)s. Pens play one effect at a
+    /// time, so the last one replaces the others.
+    Play {
+        /// The device to play the effect on.
+        device: HapticDevice,
+        /// The effect to play.
+        effect: HapticEffect,
+    },
+    /// Stops the effects playing on a device.
+    ///
+    /// Stopping a [`HapticDevice::Gamepad`] also stops the rumbles started with
+    /// [`GamepadRumbleRequest`](crate::gamepad::GamepadRumbleRequest)s, but not the effects
+    /// played on its [`HapticDevice::GamepadTriggers`].
+    Stop {
+        /// The device to stop.
+        device: HapticDevice,
+    },
+}
+
+impl HapticRequest {
+    /// Returns the device of the request.
+    pub const fn device(&self) -> HapticDevice {
+        match *self {
+            Self::Play { device, .. } | Self::Stop { device } => device,
+        }
+    }
+}
+
+/// A [`SystemParam`] to play haptic feedback on any [`HapticDevice`].
+///
+/// See the [module docs](self) for an example.
+#[derive(SystemParam)]
+pub struct Haptics<'w> {
+    requests: MessageWriter<'w, HapticRequest>,
+}
+
+impl Haptics<'_> {
+    /// Plays an effect on a device.
+    pub fn play(&mut self, device: HapticDevice, effect: HapticEffect) {
+        self.requests.write(HapticRequest::Play { device, effect });
+    }
+
+    /// Plays a [`HapticEffect::Click`] on a device.
+    pub fn click(&mut self, device: HapticDevice) {
+        self.play(device, HapticEffect::Click);
+    }
+
+    /// Plays a [`HapticEffect::Buzz`] on a device.
+    pub fn buzz(&mut self, device: HapticDevice, intensity: f32, duration: Duration) {
+        self.play(device, HapticEffect::buzz(intensity, duration));
+    }
+
+    /// Plays a [`HapticEffect::Ramp`] on a device.
+    pub fn ramp(&mut self, device: HapticDevice, from: f32, to: f32, duration: Duration) {
+        self.play(device, HapticEffect::ramp(from, to, duration));
+    }
+
+    /// Stops the effects playing on a device.
+    pub fn stop(&mut self, device: HapticDevice) {
+        self.requests.write(HapticRequest::Stop { device });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HapticEffect;
+    use core::time::Duration;
+
+    #[test]
+    fn effect_intensity() {
+        let second = Duration::from_secs(1);
+        assert_eq!(HapticEffect::Click.intensity_at(Duration::ZERO), 1.0);
+        assert_eq!(HapticEffect::Click.intensity_at(second), 0.0);
+
+        let buzz = HapticEffect::buzz(1.5, second);
+        assert_eq!(buzz.intensity_at(second / 2), 1.0);
+        assert_eq!(buzz.peak_intensity(), 1.0);
+        assert_eq!(buzz.intensity_at(second), 0.0);
+
+        let ramp = HapticEffect::ramp(1.0, 0.0, second);
+        assert_eq!(ramp.intensity_at(Duration::ZERO), 1.0);
+        assert_eq!(ramp.intensity_at(second / 4), 0.75);
+        assert_eq!(ramp.peak_intensity(), 1.0);
+        assert_eq!(ramp.intensity_at(second * 2), 0.0);
+    }
+}
@@ -69,12 +69,17 @@ use crate::{ButtonInput, ButtonState};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
-    message::{Message, MessageReader},
-    system::ResMut,
+    message::{Message, MessageReader, MessageWriter},
+    resource::Resource,
+    system::{Res, ResMut},
 };
+use bevy_platform::{collections::HashMap, time::Instant};
 
 #[cfg(feature = "bevy_reflect")]
-use bevy_reflect::Reflect;
+use {
+    bevy_ecs::reflect::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
 
 #[cfg(not(feature = "smol_str"))]
 use alloc::string::String as SmolStr;
@@ -136,6 +141,12 @@ pub struct KeyboardInput {
     pub repeat: bool,
     /// Window that received the input.
     pub window: Entity,
+    /// When the input was received from the operating system.
+    ///
+    /// This is `None` if the backend doesn't report it, in which case the time of the input isn't
+    /// recorded by the [`keyboard_input_system`].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub timestamp: Option<Instant>,
 }
 
 /// Gets generated from `bevy_winit::winit_runner`
@@ -153,6 +164,194 @@ pub struct KeyboardInput {
 )]
 pub struct KeyboardFocusLost;
 
+/// Gets generated when the keyboard layout of the system changes, e.g. when the user switches from
+/// QWERTY to AZERTY.
+///
+/// The [`KeyboardLayout`] is already updated to the new layout when this is read, so keybinding
+/// UIs can refresh the labels of their keys.
+///
+/// On Windows, `bevy_winit` detects the layout switches of the system. On the other platforms,
+/// this is written by [`keyboard_layout_system`] on the first key press that doesn't match the
+/// known layout.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Clone, PartialEq))]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct KeyboardLayoutChanged;
+
+/// Maps the physical [`KeyCode`]s to the logical [`Key`]s they produce without modifiers, in the
+/// current keyboard layout of the system.
+///
+/// This lets keybinding UIs show the glyph printed on the keyboard of the user, e.g. `KeyQ` is `a`
+/// on an AZERTY keyboard, and find the physical key of a logical key.
+///
+/// On Windows, `bevy_winit` fills the layout from the system, and updates it when the user
+/// switches layouts. On the other platforms, the keys are learned from the [`KeyboardInput`]s
+/// pressed without modifiers, by [`keyboard_layout_system`]. Letters are stored in lowercase.
+///
+/// A backend filling the layout from the system marks it with
+/// [`set_from_system`](Self::set_from_system), so that the learned keys don't overwrite it.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+pub struct KeyboardLayout {
+    keys: HashMap<KeyCode, Key>,
+    from_system: bool,
+}
+
+impl KeyboardLayout {
+    /// Returns the logical key produced by the physical `key_code` without modifiers, if known.
+    pub fn logical_key(&self, key_code: KeyCode) -> Option<&Key> {
+        self.keys.get(&key_code)
+    }
+
+    /// Returns the physical key producing the logical `key` without modifiers, if known.
+    ///
+    /// Letters are compared in lowercase.
+    pub fn key_code(&self, key: &Key) -> Option<KeyCode> {
+        let key = normalize_key(key);
+        self.keys
+            .iter()
+            .find(|(_, other)| **other == key)
+            .map(|(key_code, _)| *key_code)
+    }
+
+    /// Sets the logical key produced by the physical `key_code` without modifiers, returning the
+    /// previous one.
+    pub fn insert(&mut self, key_code: KeyCode, key: Key) -> Option<Key> {
+        self.keys.insert(key_code, normalize_key(&key))
+    }
+
+    /// Forgets all the keys, e.g. before filling the layout again after it changed.
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    /// Returns an iterator over the known physical keys and their logical keys.
+    pub fn iter(&self) -> impl Iterator<Item = (KeyCode, &Key)> {
+        self.keys.iter().map(|(key_code, key)| (*key_code, key))
+    }
+
+    /// Returns `true` if the layout is filled by the backend from the system, instead of being
+    /// learned by [`keyboard_layout_system`].
+    pub fn is_from_system(&self) -> bool {
+        self.from_system
+    }
+
+    /// Marks the layout as filled by the backend from the system, which stops
+    /// [`keyboard_layout_system`] from learning keys.
+    pub fn set_from_system(&mut self, from_system: bool) {
+        self.from_system = from_system;
+    }
+}
+
+/// Returns `true` if the `key_code` is a key of the numeric keypad.
+///
+/// Their logical keys depend on num lock, e.g. `Numpad7` produces `7` or `Home`, so they don't
+/// tell the layout of the keyboard.
+pub(crate) fn is_numpad_key(key_code: KeyCode) -> bool {
+    matches!(
+        key_code,
+        KeyCode::Numpad0
+            | KeyCode::Numpad1
+            | KeyCode::Numpad2
+            | KeyCode::Numpad3
+            | KeyCode::Numpad4
+            | KeyCode::Numpad5
+            | KeyCode::Numpad6
+            | KeyCode::Numpad7
+            | KeyCode::Numpad8
+            | KeyCode::Numpad9
+            | KeyCode::NumpadAdd
+            | KeyCode::NumpadBackspace
+            | KeyCode::NumpadClear
+            | KeyCode::NumpadClearEntry
+            | KeyCode::NumpadComma
+            | KeyCode::NumpadDecimal
+            | KeyCode::NumpadDivide
+            | KeyCode::NumpadEnter
+            | KeyCode::NumpadEqual
+            | KeyCode::NumpadHash
+            | KeyCode::NumpadMemoryAdd
+            | KeyCode::NumpadMemoryClear
+            | KeyCode::NumpadMemoryRecall
+            | KeyCode::NumpadMemoryStore
+            | KeyCode::NumpadMemorySubtract
+            | KeyCode::NumpadMultiply
+            | KeyCode::NumpadParenLeft
+            | KeyCode::NumpadParenRight
+            | KeyCode::NumpadStar
+            | KeyCode::NumpadSubtract
+    )
+}
+
+/// Lowercases the letters of a [`Key`], which may be uppercase with caps lock.
+fn normalize_key(key: &Key) -> Key {
+    match key {
+        Key::Character(character) if character.chars().any(char::is_uppercase) => {
+            Key::Character(character.to_lowercase().as_str().into())
+        }
+        key => key.clone(),
+    }
+}
+
+/// Learns the [`KeyboardLayout`] from the characters typed by the [`KeyboardInput`]s pressed
+/// without modifiers. Dead keys are skipped, as the platforms report them differently, and so are
+/// the keys of the numeric keypad, which depend on num lock.
+///
+/// Nothing is learned while the layout [is filled by the backend](KeyboardLayout::is_from_system).
+///
+/// When a key produces another logical key than the one it is known to produce, the layout must
+/// have changed, so it is cleared and a [`KeyboardLayoutChanged`] is written.
+pub fn keyboard_layout_system(
+    mut layout: ResMut<KeyboardLayout>,
+    keycode_input: Res<ButtonInput<KeyCode>>,
+    mut keyboard_input_reader: MessageReader<KeyboardInput>,
+    mut layout_changed_writer: MessageWriter<KeyboardLayoutChanged>,
+) {
+    if layout.is_from_system() {
+        keyboard_input_reader.clear();
+        return;
+    }
+    let modified = keycode_input.any_pressed([
+        KeyCode::ShiftLeft,
+        KeyCode::ShiftRight,
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::AltLeft,
+        KeyCode::AltRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    for event in keyboard_input_reader.read() {
+        if modified
+            || event.state != ButtonState::Pressed
+            || !matches!(event.logical_key, Key::Character(_))
+            || is_numpad_key(event.key_code)
+        {
+            continue;
+        }
+        let key = normalize_key(&event.logical_key);
+        match layout.logical_key(event.key_code) {
+            Some(known) if *known == key => {}
+            Some(_) => {
+                layout.clear();
+                layout.insert(event.key_code, key);
+                layout_changed_writer.write(KeyboardLayoutChanged);
+            }
+            None => {
+                layout.insert(event.key_code, key);
+            }
+        }
+    }
+}
+
 /// Updates the [`ButtonInput<KeyCode>`] and [`ButtonInput<Key>`] resources with the latest [`KeyboardInput`] events.
 ///
 /// ## Differences
@@ -176,16 +375,17 @@ pub fn keyboard_input_system(
             key_code,
             logical_key,
             state,
+            timestamp,
             ..
         } = event;
         match state {
             ButtonState::Pressed => {
-                keycode_input.press(*key_code);
-                key_input.press(logical_key.clone());
+                keycode_input.press_timed(*key_code, *timestamp);
+                key_input.press_timed(logical_key.clone(), *timestamp);
             }
             ButtonState::Released => {
-                keycode_input.release(*key_code);
-                key_input.release(logical_key.clone());
+                keycode_input.release_timed(*key_code, *timestamp);
+                key_input.release_timed(logical_key.clone(), *timestamp);
             }
         }
     }
@@ -1551,3 +1751,135 @@ pub enum Key {
     /// General-purpose function key.
     F35,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputPlugin;
+    use bevy_app::App;
+    use bevy_ecs::message::Messages;
+    use core::time::Duration;
+
+    fn press(app: &mut App, key_code: KeyCode, logical_key: Key) {
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            app.world_mut().write_message(KeyboardInput {
+                key_code,
+                logical_key: logical_key.clone(),
+                state,
+                text: None,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+                timestamp: None,
+            });
+            app.update();
+        }
+    }
+
+    #[test]
+    fn input_times_come_from_the_events() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let start = Instant::now();
+        let end = start + Duration::from_millis(100);
+        for (state, timestamp) in [
+            (ButtonState::Pressed, Some(start)),
+            (ButtonState::Released, Some(end)),
+            (ButtonState::Pressed, None),
+        ] {
+            app.world_mut().write_message(KeyboardInput {
+                key_code: KeyCode::KeyA,
+                logical_key: Key::Character("a".into()),
+                state,
+                text: None,
+                repeat: false,
+                window: Entity::PLACEHOLDER,
+                timestamp,
+            });
+            app.update();
+
+            let key_codes = app.world().resource::<ButtonInput<KeyCode>>();
+            let keys = app.world().resource::<ButtonInput<Key>>();
+            match state {
+                ButtonState::Pressed => {
+                    assert_eq!(key_codes.time_pressed(KeyCode::KeyA), timestamp);
+                    assert_eq!(keys.time_pressed(Key::Character("a".into())), timestamp);
+                }
+                ButtonState::Released => {
+                    assert_eq!(key_codes.time_released(KeyCode::KeyA), timestamp);
+                    assert_eq!(keys.time_released(Key::Character("a".into())), timestamp);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn layout_is_learned_from_inputs() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        press(&mut app, KeyCode::KeyQ, Key::Character("Q".into()));
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(
+            layout.logical_key(KeyCode::KeyQ),
+            Some(&Key::Character("q".into()))
+        );
+        assert_eq!(
+            layout.key_code(&Key::Character("Q".into())),
+            Some(KeyCode::KeyQ)
+        );
+        assert!(app
+            .world()
+            .resource::<Messages<KeyboardLayoutChanged>>()
+            .is_empty());
+
+        // Switch from QWERTY to AZERTY.
+        press(&mut app, KeyCode::KeyW, Key::Character("z".into()));
+        press(&mut app, KeyCode::KeyQ, Key::Character("a".into()));
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(
+            layout.logical_key(KeyCode::KeyQ),
+            Some(&Key::Character("a".into()))
+        );
+        assert_eq!(layout.logical_key(KeyCode::KeyW), None);
+        assert!(!app
+            .world()
+            .resource::<Messages<KeyboardLayoutChanged>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn numpad_and_system_layouts_are_not_relearned() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        press(&mut app, KeyCode::KeyQ, Key::Character("a".into()));
+        // Num lock changes the keys of the numeric keypad, not the layout.
+        press(&mut app, KeyCode::Numpad7, Key::Character("7".into()));
+        press(&mut app, KeyCode::Numpad7, Key::Home);
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(
+            layout.logical_key(KeyCode::KeyQ),
+            Some(&Key::Character("a".into()))
+        );
+        assert_eq!(layout.logical_key(KeyCode::Numpad7), None);
+        assert!(app
+            .world()
+            .resource::<Messages<KeyboardLayoutChanged>>()
+            .is_empty());
+
+        let mut layout = app.world_mut().resource_mut::<KeyboardLayout>();
+        layout.set_from_system(true);
+        layout.insert(KeyCode::KeyW, Key::Character("z".into()));
+        press(&mut app, KeyCode::KeyW, Key::Character("w".into()));
+        let layout = app.world().resource::<KeyboardLayout>();
+        assert_eq!(
+            layout.logical_key(KeyCode::KeyW),
+            Some(&Key::Character("z".into()))
+        );
+        assert!(app
+            .world()
+            .resource::<Messages<KeyboardLayoutChanged>>()
+            .is_empty());
+    }
+}
@@ -22,21 +22,38 @@ mod button_input;
 /// Common run conditions
 pub mod common_conditions;
 
+#[cfg(all(feature = "keyboard", feature = "mouse", feature = "gamepad"))]
+pub mod action;
+
+#[cfg(feature = "gamepad")]
+pub mod game_controller;
+
 #[cfg(feature = "gamepad")]
 pub mod gamepad;
 
 #[cfg(feature = "gestures")]
 pub mod gestures;
 
+#[cfg(all(feature = "gamepad", feature = "keyboard"))]
+pub mod glyph;
+
+pub mod haptics;
+
 #[cfg(feature = "keyboard")]
 pub mod keyboard;
 
 #[cfg(feature = "mouse")]
 pub mod mouse;
 
+#[cfg(feature = "touch")]
+pub mod pointer_sample;
+
 #[cfg(feature = "touch")]
 pub mod touch;
 
+#[cfg(all(feature = "gestures", feature = "touch"))]
+pub mod touch_gestures;
+
 pub use axis::*;
 pub use button_input::*;
 
@@ -73,13 +90,16 @@ use bevy_reflect::Reflect;
 use gestures::*;
 
 #[cfg(feature = "keyboard")]
-use keyboard::{keyboard_input_system, Key, KeyCode, KeyboardFocusLost, KeyboardInput};
+use keyboard::{
+    keyboard_input_system, keyboard_layout_system, Key, KeyCode, KeyboardFocusLost, KeyboardInput,
+    KeyboardLayout, KeyboardLayoutChanged,
+};
 
 #[cfg(feature = "mouse")]
 use mouse::{
     accumulate_mouse_motion_system, accumulate_mouse_scroll_system, mouse_button_input_system,
     AccumulatedMouseMotion, AccumulatedMouseScroll, MouseButton, MouseButtonInput, MouseMotion,
-    MouseWheel,
+    MouseMotionSettings, MouseWheel,
 };
 
 #[cfg(feature = "touch")]
@@ -87,10 +107,10 @@ use touch::{touch_screen_input_system, TouchInput, Touches};
 
 #[cfg(feature = "gamepad")]
 use gamepad::{
-    gamepad_connection_system, gamepad_event_processing_system, GamepadAxisChangedEvent,
-    GamepadButtonChangedEvent, GamepadButtonStateChangedEvent, GamepadConnectionEvent,
-    GamepadEvent, GamepadRumbleRequest, RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent,
-    RawGamepadEvent,
+    gamepad_connection_system, gamepad_event_processing_system, gamepad_user_system,
+    GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadButtonStateChangedEvent,
+    GamepadConnectionEvent, GamepadEvent, GamepadRumbleRequest, GamepadUserChangedEvent,
+    RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
 };
 
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
@@ -108,17 +128,27 @@ impl Plugin for InputPlugin {
     #[expect(clippy::allow_attributes, reason = "this is only sometimes unused")]
     #[allow(unused, reason = "all features could be disabled")]
     fn build(&self, app: &mut App) {
+        app.add_message::<haptics::HapticRequest>();
+
         #[cfg(feature = "keyboard")]
         app.add_message::<KeyboardInput>()
             .add_message::<KeyboardFocusLost>()
+            .add_message::<KeyboardLayoutChanged>()
             .init_resource::<ButtonInput<KeyCode>>()
             .init_resource::<ButtonInput<Key>>()
-            .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystems));
+            .init_resource::<KeyboardLayout>()
+            .add_systems(
+                PreUpdate,
+                (keyboard_input_system, keyboard_layout_system)
+                    .chain()
+                    .in_set(InputSystems),
+            );
 
         #[cfg(feature = "mouse")]
         app.add_message::<MouseButtonInput>()
             .add_message::<MouseMotion>()
             .add_message::<MouseWheel>()
+            .init_resource::<MouseMotionSettings>()
             .init_resource::<AccumulatedMouseMotion>()
             .init_resource::<AccumulatedMouseScroll>()
             .init_resource::<ButtonInput<MouseButton>>()
@@ -148,19 +178,32 @@ impl Plugin for InputPlugin {
             .add_message::<RawGamepadAxisChangedEvent>()
             .add_message::<RawGamepadButtonChangedEvent>()
             .add_message::<GamepadRumbleRequest>()
+            .add_message::<GamepadUserChangedEvent>()
+            .add_message::<game_controller::RacingWheelForceFeedback>()
             .add_systems(
                 PreUpdate,
                 (
                     gamepad_connection_system,
                     gamepad_event_processing_system.after(gamepad_connection_system),
+                    gamepad_user_system.after(gamepad_connection_system),
                 )
                     .in_set(InputSystems),
             );
 
         #[cfg(feature = "touch")]
         app.add_message::<TouchInput>()
+            .add_message::<pointer_sample::PointerSample>()
             .init_resource::<Touches>()
+            .init_resource::<pointer_sample::PointerSampleSettings>()
             .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystems));
+
+        #[cfg(all(feature = "gamepad", feature = "keyboard"))]
+        app.init_resource::<glyph::ActiveInputDevice>().add_systems(
+            PreUpdate,
+            glyph::update_active_input_device
+                .after(gamepad_event_processing_system)
+                .in_set(InputSystems),
+        );
     }
 }
 
@@ -9,6 +9,7 @@ use bevy_ecs::{
     system::ResMut,
 };
 use bevy_math::Vec2;
+use bevy_platform::time::Instant;
 #[cfg(feature = "bevy_reflect")]
 use {
     bevy_ecs::reflect::ReflectResource,
@@ -44,6 +45,12 @@ pub struct MouseButtonInput {
     pub state: ButtonState,
     /// Window that received the input.
     pub window: Entity,
+    /// When the input was received from the operating system.
+    ///
+    /// This is `None` if the backend doesn't report it, in which case the time of the input isn't
+    /// recorded by the [`mouse_button_input_system`].
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub timestamp: Option<Instant>,
 }
 
 /// A button on a mouse device.
@@ -84,12 +91,15 @@ pub enum MouseButton {
 
 /// An event reporting the change in physical position of a pointing device.
 ///
-/// This represents raw, unfiltered physical motion.
+/// By default, this represents raw, unfiltered physical motion.
 /// It is the translated version of [`DeviceEvent::MouseMotion`] from the `winit` crate.
 ///
 /// All pointing devices connected to a single machine at the same time can emit the event independently.
 /// However, the event data does not make it possible to distinguish which device it is referring to.
 ///
+/// The [`MouseMotionSettings`] resource configures how often the event is sent, and whether the
+/// motion bypasses the pointer acceleration of the system.
+///
 /// [`DeviceEvent::MouseMotion`]: https://docs.rs/winit/latest/winit/event/enum.DeviceEvent.html#variant.MouseMotion
 #[derive(Message, Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
@@ -105,6 +115,70 @@ pub enum MouseButton {
 pub struct MouseMotion {
     /// The change in the position of the pointing device since the last event was sent.
     pub delta: Vec2,
+    /// When the motion was received from the operating system, or the last motion of the frame
+    /// with [`MouseMotionAccumulation::PerFrame`].
+    ///
+    /// This is `None` if the backend doesn't report it.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub timestamp: Option<Instant>,
+}
+
+/// Configures the [`MouseMotion`] events sent by the window backend.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Resource, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MouseMotionSettings {
+    /// How the motion is split into [`MouseMotion`] events.
+    pub accumulation: MouseMotionAccumulation,
+    /// Whether the motion is the raw motion of the mouse, bypassing the pointer acceleration of
+    /// the system, e.g. "Enhance pointer precision" on Windows. Defaults to `true`.
+    ///
+    /// Raw motion feels the same whatever the speed of the mouse, which aiming in games usually
+    /// wants. When `false`, the motion is the motion of the cursor instead, as the system
+    /// accelerates it. The cursor stops at the edges of the screen, and doesn't move while it is
+    /// [locked](https://docs.rs/bevy/latest/bevy/window/enum.CursorGrabMode.html#variant.Locked),
+    /// and neither does that motion.
+    pub bypass_pointer_acceleration: bool,
+}
+
+impl Default for MouseMotionSettings {
+    fn default() -> Self {
+        Self {
+            accumulation: MouseMotionAccumulation::PerEvent,
+            bypass_pointer_acceleration: true,
+        }
+    }
+}
+
+/// How the motion of the mouse is split into [`MouseMotion`] events, see [`MouseMotionSettings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Hash, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum MouseMotionAccumulation {
+    /// An event is sent for each motion reported by the operating system, with its timestamp.
+    ///
+    /// Mice with a high polling rate report many motions per frame, which lets the app follow
+    /// the path and the speed of the mouse within a frame.
+    #[default]
+    PerEvent,
+    /// The motions of a frame are summed into a single event, like the [`AccumulatedMouseMotion`].
+    PerFrame,
 }
 
 /// The scroll unit.
@@ -187,8 +261,10 @@ pub fn mouse_button_input_system(
     mouse_button_input.bypass_change_detection().clear();
     for event in mouse_button_input_events.read() {
         match event.state {
-            ButtonState::Pressed => mouse_button_input.press(event.button),
-            ButtonState::Released => mouse_button_input.release(event.button),
+            ButtonState::Pressed => mouse_button_input.press_timed(event.button, event.timestamp),
+            ButtonState::Released => {
+                mouse_button_input.release_timed(event.button, event.timestamp);
+            }
         }
     }
 }
@@ -0,0 +1,180 @@
+//! Every position of the mouse cursor and touches between two frames, with their timestamps, for
+//! inking and fast aiming.
+//!
+//! `CursorMoved` and [`TouchInput`](crate::touch::TouchInput) already report every move, but
+//! without timing, so drawing apps can't interpolate strokes by speed, and there is always a frame
+//! of latency between the last move and the displayed stroke.
+//!
+//! [`PointerSample`]s add the time each position was received from the operating system, and, when
+//! [`PointerSampleSettings::prediction`] is set, a predicted sample extrapolating where the pointer
+//! will be, to draw ahead of the input and hide the latency.
+//!
+//! The timestamps are the times the window backend received the events, not hardware timestamps
+//! from the device: events delivered together by the operating system get close timestamps. Points
+//! coalesced by the operating system aren't reported either.
+//!
+//! Strokes still begin and end with the [`TouchInput`](crate::touch::TouchInput) and mouse button
+//! events.
+
+use bevy_ecs::{entity::Entity, message::Message, resource::Resource};
+use bevy_math::Vec2;
+use bevy_platform::time::Instant;
+use core::time::Duration;
+
+use crate::touch::{ForceTouch, TouchProperties};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectResource;
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// A position of a pointer, at the time it was received.
+///
+/// See the [module docs](self) for how it relates to the other pointer events.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct PointerSample {
+    /// The window of the pointer.
+    pub window: Entity,
+    /// The pointer the sample is from.
+    pub source: PointerSampleSource,
+    /// The position of the pointer in the window, in logical pixels.
+    pub position: Vec2,
+    /// The force of touches and pens, when the platform reports it.
+    pub force: Option<ForceTouch>,
+    /// The tilt, twist and contact size of touches and pens, when the platform reports them.
+    pub properties: TouchProperties,
+    /// When the position was received by the window backend, or will be reached for predicted
+    /// samples.
+    ///
+    /// This isn't a hardware timestamp, see the [module docs](self).
+    pub timestamp: Instant,
+    /// Whether the sample is a prediction, instead of an actual position of the pointer.
+    ///
+    /// Predicted samples are only written after the actual samples of the frame, and are replaced
+    /// by the actual samples of the next frame.
+    pub predicted: bool,
+}
+
+/// The pointer of a [`PointerSample`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone)
+)]
+pub enum PointerSampleSource {
+    /// The mouse cursor.
+    Mouse,
+    /// The touch or pen with the given [`TouchInput::id`](crate::touch::TouchInput::id).
+    Touch(u64),
+}
+
+impl PointerSample {
+    /// The shortest time the velocity of [`predict`](Self::predict) is measured over.
+    ///
+    /// Samples delivered together by the operating system are received nanoseconds apart, which
+    /// would otherwise give a huge velocity and a prediction far off the window.
+    pub const MIN_PREDICTION_ELAPSED: Duration = Duration::from_millis(4);
+
+    /// Extrapolates the position of the pointer `ahead` of this sample, from its velocity since
+    /// the `previous` sample.
+    ///
+    /// The velocity is measured over at least [`Self::MIN_PREDICTION_ELAPSED`]. Returns [`None`]
+    /// if `previous` is later than this sample, or is from a different pointer.
+    pub fn predict(&self, previous: &PointerSample, ahead: Duration) -> Option<PointerSample> {
+        if self.source != previous.source || self.window != previous.window {
+            return None;
+        }
+        let elapsed = self
+            .timestamp
+            .checked_duration_since(previous.timestamp)?
+            .max(Self::MIN_PREDICTION_ELAPSED)
+            .as_secs_f32();
+        let velocity = (self.position - previous.position) / elapsed;
+        Some(PointerSample {
+            position: self.position + velocity * ahead.as_secs_f32(),
+            timestamp: self.timestamp + ahead,
+            predicted: true,
+            ..*self
+        })
+    }
+}
+
+/// Configures the [`PointerSample`]s.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, Default, PartialEq, Clone)
+)]
+pub struct PointerSampleSettings {
+    /// How far ahead of the last actual sample to predict a sample, for each moving pointer.
+    ///
+    /// Around the time between two displayed frames is usually a good fit. No sample is predicted
+    /// when [`None`], which is the default.
+    pub prediction: Option<Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PointerSample, PointerSampleSource};
+    use crate::touch::TouchProperties;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+    use bevy_platform::time::Instant;
+    use core::time::Duration;
+
+    #[test]
+    fn linear_prediction() {
+        let start = Instant::now();
+        let sample = |position: Vec2, time: u64, source| PointerSample {
+            window: Entity::PLACEHOLDER,
+            source,
+            position,
+            force: None,
+            properties: TouchProperties::default(),
+            timestamp: start + Duration::from_millis(time),
+            predicted: false,
+        };
+        let previous = sample(Vec2::ZERO, 0, PointerSampleSource::Mouse);
+        let latest = sample(Vec2::new(10.0, -5.0), 10, PointerSampleSource::Mouse);
+
+        let predicted = latest.predict(&previous, Duration::from_millis(5)).unwrap();
+        assert!(predicted.predicted);
+        assert!(predicted.position.abs_diff_eq(Vec2::new(15.0, -7.5), 1e-3));
+        assert_eq!(predicted.timestamp, start + Duration::from_millis(15));
+
+        assert!(previous
+            .predict(&latest, Duration::from_millis(5))
+            .is_none());
+        let touch = sample(Vec2::ZERO, 0, PointerSampleSource::Touch(0));
+        assert!(latest.predict(&touch, Duration::from_millis(5)).is_none());
+    }
+
+    #[test]
+    fn prediction_of_samples_received_together() {
+        let start = Instant::now();
+        let sample = |position: Vec2, timestamp| PointerSample {
+            window: Entity::PLACEHOLDER,
+            source: PointerSampleSource::Mouse,
+            position,
+            force: None,
+            properties: TouchProperties::default(),
+            timestamp,
+            predicted: false,
+        };
+        // Samples delivered in the same batch are received nanoseconds apart.
+        let previous = sample(Vec2::ZERO, start);
+        let latest = sample(Vec2::new(4.0, 0.0), start + Duration::from_nanos(10));
+
+        let predicted = latest.predict(&previous, Duration::from_millis(4)).unwrap();
+        assert!(predicted.position.abs_diff_eq(Vec2::new(8.0, 0.0), 1e-3));
+        let identical = latest.predict(&latest, Duration::from_millis(4)).unwrap();
+        assert_eq!(identical.position, latest.position);
+    }
+}
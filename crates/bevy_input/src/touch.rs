@@ -9,7 +9,7 @@ use bevy_ecs::{
 use bevy_math::Vec2;
 use bevy_platform::collections::HashMap;
 #[cfg(feature = "bevy_reflect")]
-use bevy_reflect::Reflect;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 #[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
 use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
@@ -60,10 +60,55 @@ pub struct TouchInput {
     /// May be [`None`] if the platform does not support pressure sensitivity.
     /// This feature is only available on **iOS** 9.0+ and **Windows** 8+.
     pub force: Option<ForceTouch>,
+    /// The tilt, twist and contact size of the pen or finger, when the platform reports them.
+    pub properties: TouchProperties,
     /// The unique identifier of the finger.
     pub id: u64,
 }
 
+/// The properties of the pen or finger of a [`TouchInput`], for drawing apps and pressure-sensitive
+/// gameplay.
+///
+/// Each property is [`None`] if the platform or the device doesn't report it. The `winit` backend
+/// reports them on **Windows**, from the `PointerPoint` of the touch. The pressure is the
+/// [`TouchInput::force`], see [`ForceTouch::pressure`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TouchProperties {
+    /// The tilt of the pen, in radians, around the y axis for `x` and around the x axis for `y`.
+    ///
+    /// Positive values tilt the pen towards the right of the screen, and towards the user.
+    pub tilt: Option<Vec2>,
+    /// The clockwise rotation of the pen around its own axis, in radians, from `0.0` to `TAU`.
+    pub twist: Option<f32>,
+    /// The size of the contact area, in logical pixels, centered on the position of the touch.
+    pub contact_size: Option<Vec2>,
+}
+
+impl ForceTouch {
+    /// Returns the force as a pressure from `0.0` to `1.0`.
+    pub fn pressure(&self) -> f32 {
+        match *self {
+            Self::Calibrated {
+                force,
+                max_possible_force,
+                ..
+            } if max_possible_force > 0.0 => (force / max_possible_force).clamp(0.0, 1.0) as f32,
+            Self::Calibrated { .. } => 0.0,
+            Self::Normalized(force) => force.clamp(0.0, 1.0) as f32,
+        }
+    }
+}
+
 /// A force description of a [`Touch`] input.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(
@@ -163,6 +208,8 @@ pub struct Touch {
     position: Vec2,
     /// The current force of the touch input.
     force: Option<ForceTouch>,
+    /// The current properties of the touch input.
+    properties: TouchProperties,
 }
 
 impl Touch {
@@ -217,6 +264,12 @@ impl Touch {
     pub fn force(&self) -> Option<ForceTouch> {
         self.force
     }
+
+    /// Returns the current tilt, twist and contact size of the touch.
+    #[inline]
+    pub fn properties(&self) -> TouchProperties {
+        self.properties
+    }
 }
 
 impl From<&TouchInput> for Touch {
@@ -229,6 +282,7 @@ impl From<&TouchInput> for Touch {
             previous_force: input.force,
             position: input.position,
             force: input.force,
+            properties: input.properties,
         }
     }
 }
@@ -394,6 +448,7 @@ impl Touches {
                     // See https://github.com/bevyengine/bevy/issues/12442
                     new_touch.position = event.position;
                     new_touch.force = event.force;
+                    new_touch.properties = event.properties;
                     self.pressed.insert(event.id, new_touch);
                 }
             }
@@ -475,6 +530,7 @@ mod test {
             previous_force: None,
             position: Vec2::ZERO,
             force: None,
+            properties: Default::default(),
         };
 
         // Add a touch to `just_pressed`, 'just_released', and 'just canceled'
@@ -506,6 +562,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -522,6 +579,7 @@ mod test {
             position: Vec2::splat(5.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: touch_event.id,
         };
 
@@ -544,6 +602,7 @@ mod test {
             position: Vec2::ONE,
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: touch_event.id,
         };
 
@@ -560,6 +619,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: touch_event.id,
         };
 
@@ -589,6 +649,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -597,6 +658,7 @@ mod test {
             position: Vec2::splat(5.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: started_touch_event.id,
         };
 
@@ -605,6 +667,7 @@ mod test {
             position: Vec2::splat(6.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: started_touch_event.id,
         };
 
@@ -651,6 +714,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -678,6 +742,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -705,6 +770,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -731,6 +797,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -757,6 +824,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -765,6 +833,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -795,6 +864,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -803,6 +873,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 5,
         };
 
@@ -811,6 +882,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 6,
         };
 
@@ -845,6 +917,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 4,
         };
 
@@ -853,6 +926,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 5,
         };
 
@@ -861,6 +935,7 @@ mod test {
             position: Vec2::splat(4.0),
             window: Entity::PLACEHOLDER,
             force: None,
+            properties: Default::default(),
             id: 6,
         };
 
@@ -882,6 +957,47 @@ mod test {
         assert!(!touches.just_released(touch_released_event.id));
     }
 
+    #[test]
+    fn touch_properties() {
+        use crate::touch::{ForceTouch, TouchInput, TouchPhase, TouchProperties};
+        use bevy_ecs::entity::Entity;
+        use bevy_math::Vec2;
+
+        let mut touches = Touches::default();
+
+        let mut touch_event = TouchInput {
+            phase: TouchPhase::Started,
+            position: Vec2::ZERO,
+            window: Entity::PLACEHOLDER,
+            force: Some(ForceTouch::Normalized(0.25)),
+            properties: TouchProperties {
+                twist: Some(1.0),
+                ..Default::default()
+            },
+            id: 4,
+        };
+        touches.process_touch_event(&touch_event);
+
+        touch_event.phase = TouchPhase::Moved;
+        touch_event.properties = TouchProperties {
+            tilt: Some(Vec2::new(0.1, -0.2)),
+            ..Default::default()
+        };
+        touches.process_touch_event(&touch_event);
+
+        let touch = touches.get_pressed(4).unwrap();
+        assert_eq!(touch.properties().twist, None);
+        assert_eq!(touch.properties().tilt, Some(Vec2::new(0.1, -0.2)));
+
+        assert_eq!(ForceTouch::Normalized(0.25).pressure(), 0.25);
+        let calibrated = ForceTouch::Calibrated {
+            force: 3.0,
+            max_possible_force: 6.0,
+            altitude_angle: None,
+        };
+        assert_eq!(calibrated.pressure(), 0.5);
+    }
+
     fn clear_all(touch_state: &mut Touches) {
         touch_state.just_pressed.clear();
         touch_state.just_released.clear();
@@ -0,0 +1,349 @@
+//! Pinch, rotation and pan gestures recognized from the touches of two fingers, on every platform.
+//!
+//! The [`PinchGesture`](crate::gestures::PinchGesture), [`RotationGesture`](crate::gestures::RotationGesture)
+//! and [`PanGesture`](crate::gestures::PanGesture) messages come from the gesture recognizers of
+//! the system, which only exist on some platforms. The [`TouchGesturePlugin`] recognizes the same
+//! gestures from the [`TouchInput`]s of any touchscreen instead, and writes them together as a
+//! [`TouchGesture`], with their velocity to keep a map or a canvas moving after the fingers are
+//! lifted.
+//!
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::touch_gestures::{TouchGesture, TouchGesturePhase, TouchGesturePlugin};
+//! # use bevy_math::Vec2;
+//! #[derive(Resource, Default)]
+//! struct Map {
+//!     offset: Vec2,
+//!     zoom: f32,
+//!     angle: f32,
+//!     fling: Vec2,
+//! }
+//!
+//! fn move_map(mut gestures: MessageReader<TouchGesture>, mut map: ResMut<Map>) {
+//!     for gesture in gestures.read() {
+//!         map.offset += gesture.pan;
+//!         map.zoom *= 1.0 + gesture.pinch;
+//!         map.angle += gesture.rotation;
+//!         if gesture.phase == TouchGesturePhase::Ended {
+//!             map.fling = gesture.velocity.pan;
+//!         }
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(TouchGesturePlugin)
+//!     .init_resource::<Map>()
+//!     .add_systems(Update, move_map);
+//! ```
+
+use crate::{
+    touch::{TouchInput, TouchPhase},
+    InputSystems,
+};
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    entity::Entity,
+    message::{Message, MessageReader, MessageWriter},
+    schedule::IntoScheduleConfigs,
+    system::Local,
+};
+use bevy_math::{ops, Vec2};
+use bevy_platform::time::Instant;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// How long it takes for the velocity of a [`TouchGesture`] to mostly follow a change of speed, in
+/// seconds. This smooths out the jitter of the touch positions.
+const VELOCITY_TIME_CONSTANT: f32 = 0.05;
+
+/// Recognizes [`TouchGesture`]s from the [`TouchInput`]s.
+///
+/// It requires the `InputPlugin`.
+#[derive(Default)]
+pub struct TouchGesturePlugin;
+
+impl Plugin for TouchGesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TouchGesture>()
+            .add_systems(PreUpdate, touch_gesture_system.in_set(InputSystems));
+    }
+}
+
+/// A two-finger gesture on a touchscreen, recognized by the [`TouchGesturePlugin`].
+///
+/// Pinching, rotating and panning are recognized at the same time, so a single gesture can move,
+/// zoom and rotate a map. The deltas are relative to the previous [`TouchGesture`] of the same
+/// gesture.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct TouchGesture {
+    /// The window of the touches.
+    pub window: Entity,
+    /// The phase of the gesture.
+    pub phase: TouchGesturePhase,
+    /// The point between the two fingers, in logical pixels.
+    pub center: Vec2,
+    /// How much the center moved, in logical pixels.
+    pub pan: Vec2,
+    /// How much the fingers moved apart, relative to their previous distance.
+    ///
+    /// Like [`PinchGesture`](crate::gestures::PinchGesture), positive values indicate
+    /// magnification (zooming in) and negative values indicate shrinking (zooming out).
+    pub pinch: f32,
+    /// How much the fingers turned around the center, in radians.
+    ///
+    /// Like [`RotationGesture`](crate::gestures::RotationGesture), positive values indicate
+    /// rotation counterclockwise and negative values indicate rotation clockwise.
+    pub rotation: f32,
+    /// The velocity of the gesture, per second.
+    pub velocity: TouchGestureVelocity,
+}
+
+/// The phase of a [`TouchGesture`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Hash, PartialEq, Clone)
+)]
+pub enum TouchGesturePhase {
+    /// A second finger touched the screen. The deltas are zero.
+    Started,
+    /// The fingers moved.
+    Moved,
+    /// One of the fingers was lifted. The deltas are zero, and the velocity is the one the fingers
+    /// had when it was lifted.
+    Ended,
+    /// The system canceled the tracking of one of the fingers. The deltas are zero.
+    Canceled,
+}
+
+/// The velocity of a [`TouchGesture`], smoothed over the last few frames.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default, Clone)
+)]
+pub struct TouchGestureVelocity {
+    /// The speed of the center, in logical pixels per second.
+    pub pan: Vec2,
+    /// The relative change of the distance between the fingers per second.
+    pub pinch: f32,
+    /// The angular speed of the fingers around the center, in radians per second.
+    pub rotation: f32,
+}
+
+#[derive(Default)]
+struct TouchGestureRecognizer {
+    /// The touches on the screen, in the order they started.
+    touches: Vec<TrackedTouch>,
+    gesture: Option<ActiveGesture>,
+    last_update: Option<Instant>,
+}
+
+struct TrackedTouch {
+    id: u64,
+    window: Entity,
+    position: Vec2,
+    /// Whether the touch ended or was canceled this frame.
+    lifted: Option<TouchPhase>,
+}
+
+struct ActiveGesture {
+    ids: [u64; 2],
+    window: Entity,
+    positions: [Vec2; 2],
+    velocity: TouchGestureVelocity,
+}
+
+impl ActiveGesture {
+    fn message(&self, phase: TouchGesturePhase) -> TouchGesture {
+        TouchGesture {
+            window: self.window,
+            phase,
+            center: self.positions[0].midpoint(self.positions[1]),
+            pan: Vec2::ZERO,
+            pinch: 0.0,
+            rotation: 0.0,
+            velocity: self.velocity,
+        }
+    }
+
+    /// Moves the fingers of the gesture, and returns the [`TouchGesturePhase::Moved`] message if
+    /// they moved.
+    fn update(&mut self, positions: [Vec2; 2], elapsed: f32) -> Option<TouchGesture> {
+        let previous = self.positions;
+        self.positions = positions;
+        let pan = positions[0].midpoint(positions[1]) - previous[0].midpoint(previous[1]);
+        let span = positions[1] - positions[0];
+        let previous_span = previous[1] - previous[0];
+        let (pinch, rotation) = if previous_span == Vec2::ZERO || span == Vec2::ZERO {
+            (0.0, 0.0)
+        } else {
+            // The y axis of the window points down, so `angle_to` is clockwise on the screen.
+            (
+                span.length() / previous_span.length() - 1.0,
+                -previous_span.angle_to(span),
+            )
+        };
+
+        if elapsed > 0.0 {
+            let weight = 1.0 - ops::exp(-elapsed / VELOCITY_TIME_CONSTANT);
+            let velocity = &mut self.velocity;
+            velocity.pan = velocity.pan.lerp(pan / elapsed, weight);
+            velocity.pinch += (pinch / elapsed - velocity.pinch) * weight;
+            velocity.rotation += (rotation / elapsed - velocity.rotation) * weight;
+        }
+
+        (positions != previous).then(|| TouchGesture {
+            pan,
+            pinch,
+            rotation,
+            ..self.message(TouchGesturePhase::Moved)
+        })
+    }
+}
+
+/// Recognizes the [`TouchGesture`]s of the first two fingers on the same window.
+fn touch_gesture_system(
+    mut touch_inputs: MessageReader<TouchInput>,
+    mut gestures: MessageWriter<TouchGesture>,
+    mut recognizer: Local<TouchGestureRecognizer>,
+) {
+    let now = Instant::now();
+    let elapsed = recognizer
+        .last_update
+        .replace(now)
+        .map_or(0.0, |last| (now - last).as_secs_f32());
+
+    let recognizer = &mut *recognizer;
+    for input in touch_inputs.read() {
+        let touch = recognizer
+            .touches
+            .iter_mut()
+            .find(|touch| touch.id == input.id && touch.lifted.is_none());
+        match (input.phase, touch) {
+            (TouchPhase::Started, None) => recognizer.touches.push(TrackedTouch {
+                id: input.id,
+                window: input.window,
+                position: input.position,
+                lifted: None,
+            }),
+            (TouchPhase::Started | TouchPhase::Moved, Some(touch)) => {
+                touch.position = input.position;
+            }
+            (TouchPhase::Ended | TouchPhase::Canceled, Some(touch)) => {
+                touch.position = input.position;
+                touch.lifted = Some(input.phase);
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(mut gesture) = recognizer.gesture.take() {
+        let [first, second] = gesture.ids.map(|id| {
+            recognizer
+                .touches
+                .iter()
+                .find(|touch| touch.id == id)
+                .expect("the touches of the gesture are tracked until it ends")
+        });
+        gestures.write_batch(gesture.update([first.position, second.position], elapsed));
+        match first.lifted.or(second.lifted) {
+            Some(TouchPhase::Canceled) => {
+                gestures.write(gesture.message(TouchGesturePhase::Canceled));
+            }
+            Some(_) => {
+                gestures.write(gesture.message(TouchGesturePhase::Ended));
+            }
+            None => recognizer.gesture = Some(gesture),
+        }
+    }
+    recognizer.touches.retain(|touch| touch.lifted.is_none());
+
+    if recognizer.gesture.is_none()
+        && let [first, rest @ ..] = recognizer.touches.as_slice()
+        && let Some(second) = rest.iter().find(|touch| touch.window == first.window)
+    {
+        let gesture = ActiveGesture {
+            ids: [first.id, second.id],
+            window: first.window,
+            positions: [first.position, second.position],
+            velocity: TouchGestureVelocity::default(),
+        };
+        gestures.write(gesture.message(TouchGesturePhase::Started));
+        recognizer.gesture = Some(gesture);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TouchGesture, TouchGesturePhase, TouchGesturePlugin};
+    use crate::{
+        touch::{TouchInput, TouchPhase},
+        InputPlugin,
+    };
+    use alloc::vec::Vec;
+    use bevy_app::App;
+    use bevy_ecs::{entity::Entity, message::Messages};
+    use bevy_math::Vec2;
+    use core::f32::consts::FRAC_PI_2;
+
+    fn touch(app: &mut App, id: u64, phase: TouchPhase, position: Vec2) {
+        app.world_mut().write_message(TouchInput {
+            phase,
+            position,
+            window: Entity::PLACEHOLDER,
+            force: None,
+            properties: Default::default(),
+            id,
+        });
+    }
+
+    fn gestures(app: &mut App) -> Vec<TouchGesture> {
+        app.update();
+        app.world_mut()
+            .resource_mut::<Messages<TouchGesture>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, TouchGesturePlugin));
+
+        touch(&mut app, 0, TouchPhase::Started, Vec2::new(0.0, 0.0));
+        assert!(gestures(&mut app).is_empty());
+
+        touch(&mut app, 1, TouchPhase::Started, Vec2::new(10.0, 0.0));
+        let started = gestures(&mut app);
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].phase, TouchGesturePhase::Started);
+        assert_eq!(started[0].center, Vec2::new(5.0, 0.0));
+
+        // The second finger turns a quarter turn counterclockwise on the screen around the first
+        // one, and moves twice as far from it.
+        touch(&mut app, 1, TouchPhase::Moved, Vec2::new(0.0, -20.0));
+        let moved = gestures(&mut app);
+        assert_eq!(moved.len(), 1);
+        assert_eq!(moved[0].phase, TouchGesturePhase::Moved);
+        assert_eq!(moved[0].pan, Vec2::new(-5.0, -10.0));
+        assert!((moved[0].pinch - 1.0).abs() < 1e-5);
+        assert!((moved[0].rotation - FRAC_PI_2).abs() < 1e-5);
+
+        touch(&mut app, 0, TouchPhase::Ended, Vec2::new(0.0, 0.0));
+        let ended = gestures(&mut app);
+        assert_eq!(ended.len(), 1);
+        assert_eq!(ended[0].phase, TouchGesturePhase::Ended);
+        assert_eq!(ended[0].pan, Vec2::ZERO);
+    }
+}
@@ -446,6 +446,7 @@ mod tests {
             text: Some("A".into()),
             repeat: false,
             window: Entity::PLACEHOLDER,
+            timestamp: None,
         }
     }
 
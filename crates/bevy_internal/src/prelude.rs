@@ -99,6 +99,10 @@ pub use crate::gilrs::*;
 #[cfg(feature = "bevy_state")]
 pub use crate::state::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_rng")]
+pub use crate::rng::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_gltf")]
 pub use crate::gltf::prelude::*;
@@ -94,6 +94,10 @@ pub struct MaterialProperties {
     pub shadows_enabled: bool,
     /// Whether prepass is enabled for this material
     pub prepass_enabled: bool,
+    /// Whether the pipelines of this material are created synchronously, so that it's never
+    /// skipped while its pipelines are compiling, even when pipelines are otherwise compiled
+    /// asynchronously.
+    pub synchronous_pipeline_compilation: bool,
 }
 
 impl MaterialProperties {
@@ -0,0 +1,179 @@
+use super::{RayCastable2d, RayHit2d};
+use crate::{
+    ops::{self, FloatPow},
+    primitives::{Capsule2d, Circle, Rectangle},
+    Dir2, Ray2d, Vec2,
+};
+
+/// The hit of a ray starting inside of a solid shape.
+#[inline]
+fn inside_hit(ray: Ray2d) -> RayHit2d {
+    RayHit2d {
+        distance: 0.0,
+        normal: -ray.direction,
+    }
+}
+
+/// Finds the first intersection of a ray with a circle of the given `radius` centered at `center`.
+fn ray_cast_circle(center: Vec2, radius: f32, ray: Ray2d, max_distance: f32) -> Option<RayHit2d> {
+    let offset = ray.origin - center;
+    let c = offset.length_squared() - radius.squared();
+    if c <= 0.0 {
+        return Some(inside_hit(ray));
+    }
+    let b = offset.dot(*ray.direction);
+    // The ray starts outside the circle and points away from it.
+    if b > 0.0 {
+        return None;
+    }
+    let discriminant = b.squared() - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let distance = -b - ops::sqrt(discriminant);
+    if distance > max_distance {
+        return None;
+    }
+    let normal = Dir2::new(ray.get_point(distance) - center).unwrap_or(-ray.direction);
+    Some(RayHit2d { distance, normal })
+}
+
+impl RayCastable2d for Circle {
+    fn local_ray_cast(&self, ray: Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        ray_cast_circle(Vec2::ZERO, self.radius, ray, max_distance)
+    }
+}
+
+impl RayCastable2d for Rectangle {
+    fn local_ray_cast(&self, ray: Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        let mut entry = 0.0_f32;
+        let mut exit = max_distance;
+        let mut normal = None;
+        for axis in 0..2 {
+            let origin = ray.origin[axis];
+            let direction = ray.direction[axis];
+            let half_size = self.half_size[axis];
+            if direction == 0.0 {
+                if ops::abs(origin) > half_size {
+                    return None;
+                }
+                continue;
+            }
+            let inverse = direction.recip();
+            let near = (-ops::copysign(half_size, direction) - origin) * inverse;
+            let far = (ops::copysign(half_size, direction) - origin) * inverse;
+            if near > entry {
+                entry = near;
+                let mut axis_normal = Vec2::ZERO;
+                axis_normal[axis] = ops::copysign(1.0, -direction);
+                normal = Some(axis_normal);
+            }
+            exit = exit.min(far);
+            if entry > exit {
+                return None;
+            }
+        }
+        Some(match normal {
+            Some(normal) => RayHit2d {
+                distance: entry,
+                normal: Dir2::new_unchecked(normal),
+            },
+            None => inside_hit(ray),
+        })
+    }
+}
+
+impl RayCastable2d for Capsule2d {
+    fn local_ray_cast(&self, ray: Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        let rectangle = Rectangle {
+            half_size: Vec2::new(self.radius, self.half_length),
+        };
+        let top = Vec2::Y * self.half_length;
+        [
+            rectangle.local_ray_cast(ray, max_distance),
+            ray_cast_circle(top, self.radius, ray, max_distance),
+            ray_cast_circle(-top, self.radius, ray, max_distance),
+        ]
+        .into_iter()
+        .flatten()
+        // A rectangle hit on its top or bottom edge is inside one of the semicircles,
+        // so it's never the closest.
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Isometry2d, Rot2};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn circle_ray_cast() {
+        let circle = Circle::new(1.0);
+
+        let hit = circle
+            .local_ray_cast(Ray2d::new(Vec2::new(-5.0, 0.0), Dir2::X), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Dir2::NEG_X);
+
+        assert!(circle
+            .local_ray_cast(Ray2d::new(Vec2::new(-5.0, 0.0), Dir2::X), 3.0)
+            .is_none());
+        assert!(circle
+            .local_ray_cast(Ray2d::new(Vec2::new(-5.0, 2.0), Dir2::X), 10.0)
+            .is_none());
+
+        let hit = circle
+            .local_ray_cast(Ray2d::new(Vec2::ZERO, Dir2::Y), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 0.0);
+        assert_eq!(hit.normal, Dir2::NEG_Y);
+    }
+
+    #[test]
+    fn rectangle_ray_cast() {
+        let rectangle = Rectangle::new(2.0, 4.0);
+
+        let hit = rectangle
+            .local_ray_cast(Ray2d::new(Vec2::new(0.5, 5.0), Dir2::NEG_Y), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 3.0);
+        assert_eq!(hit.normal, Dir2::Y);
+
+        assert!(rectangle
+            .local_ray_cast(Ray2d::new(Vec2::new(2.0, 5.0), Dir2::NEG_Y), 10.0)
+            .is_none());
+    }
+
+    #[test]
+    fn capsule_ray_cast() {
+        let capsule = Capsule2d::new(1.0, 2.0);
+
+        let hit = capsule
+            .local_ray_cast(Ray2d::new(Vec2::new(0.0, 5.0), Dir2::NEG_Y), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 3.0);
+        assert_relative_eq!(hit.normal.as_vec2(), Vec2::Y);
+
+        let hit = capsule
+            .local_ray_cast(Ray2d::new(Vec2::new(-5.0, 0.5), Dir2::X), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_relative_eq!(hit.normal.as_vec2(), Vec2::NEG_X);
+    }
+
+    #[test]
+    fn transformed_ray_cast() {
+        let rectangle = Rectangle::new(2.0, 4.0);
+        let isometry = Isometry2d::new(Vec2::new(10.0, 0.0), Rot2::degrees(90.0));
+
+        // The rectangle is rotated so that its long side is along the X axis.
+        let hit = rectangle
+            .ray_cast(isometry, Ray2d::new(Vec2::ZERO, Dir2::X), 20.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 8.0, epsilon = 1e-5);
+        assert_relative_eq!(hit.normal.as_vec2(), Vec2::NEG_X, epsilon = 1e-5);
+    }
+}
@@ -0,0 +1,345 @@
+use super::{RayCastable3d, RayHit3d};
+use crate::{
+    ops::{self, FloatPow},
+    primitives::{Capsule3d, Cuboid, Cylinder, Sphere, Triangle3d},
+    Dir3, Ray3d, Vec3, Vec3Swizzles,
+};
+
+/// The hit of a ray starting inside of a solid shape.
+#[inline]
+fn inside_hit(ray: Ray3d) -> RayHit3d {
+    RayHit3d {
+        distance: 0.0,
+        normal: -ray.direction,
+    }
+}
+
+/// Finds the first intersection of a ray with a sphere of the given `radius` centered at `center`.
+fn ray_cast_sphere(center: Vec3, radius: f32, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+    let offset = ray.origin - center;
+    let c = offset.length_squared() - radius.squared();
+    if c <= 0.0 {
+        return Some(inside_hit(ray));
+    }
+    let b = offset.dot(*ray.direction);
+    // The ray starts outside the sphere and points away from it.
+    if b > 0.0 {
+        return None;
+    }
+    let discriminant = b.squared() - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let distance = -b - ops::sqrt(discriminant);
+    if distance > max_distance {
+        return None;
+    }
+    let normal = Dir3::new(ray.get_point(distance) - center).unwrap_or(-ray.direction);
+    Some(RayHit3d { distance, normal })
+}
+
+impl RayCastable3d for Sphere {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        ray_cast_sphere(Vec3::ZERO, self.radius, ray, max_distance)
+    }
+}
+
+impl RayCastable3d for Cuboid {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        let mut entry = 0.0_f32;
+        let mut exit = max_distance;
+        let mut normal = None;
+        for axis in 0..3 {
+            let origin = ray.origin[axis];
+            let direction = ray.direction[axis];
+            let half_size = self.half_size[axis];
+            if direction == 0.0 {
+                if ops::abs(origin) > half_size {
+                    return None;
+                }
+                continue;
+            }
+            let inverse = direction.recip();
+            let near = (-ops::copysign(half_size, direction) - origin) * inverse;
+            let far = (ops::copysign(half_size, direction) - origin) * inverse;
+            if near > entry {
+                entry = near;
+                let mut axis_normal = Vec3::ZERO;
+                axis_normal[axis] = ops::copysign(1.0, -direction);
+                normal = Some(axis_normal);
+            }
+            exit = exit.min(far);
+            if entry > exit {
+                return None;
+            }
+        }
+        Some(match normal {
+            Some(normal) => RayHit3d {
+                distance: entry,
+                normal: Dir3::new_unchecked(normal),
+            },
+            None => inside_hit(ray),
+        })
+    }
+}
+
+/// Finds the first intersection of a ray with the curved surface of an infinite cylinder around
+/// the Y axis, for a ray starting outside of it.
+fn ray_cast_infinite_cylinder(radius: f32, ray: Ray3d) -> Option<f32> {
+    let origin = ray.origin.xz();
+    let direction = ray.direction.xz();
+    let a = direction.length_squared();
+    if a == 0.0 {
+        return None;
+    }
+    let b = origin.dot(direction);
+    let c = origin.length_squared() - radius.squared();
+    let discriminant = b.squared() - a * c;
+    if b > 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    Some((-b - ops::sqrt(discriminant)) / a)
+}
+
+impl RayCastable3d for Cylinder {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        let inside_radius = ray.origin.xz().length_squared() <= self.radius.squared();
+        let inside_height = ops::abs(ray.origin.y) <= self.half_height;
+        if inside_radius && inside_height {
+            return Some(inside_hit(ray));
+        }
+
+        // Hit the caps.
+        if !inside_height && ray.direction.y != 0.0 {
+            let cap_y = ops::copysign(self.half_height, ray.origin.y);
+            let distance = (cap_y - ray.origin.y) / ray.direction.y;
+            if distance >= 0.0
+                && distance <= max_distance
+                && ray.get_point(distance).xz().length_squared() <= self.radius.squared()
+            {
+                return Some(RayHit3d {
+                    distance,
+                    normal: if cap_y > 0.0 { Dir3::Y } else { Dir3::NEG_Y },
+                });
+            }
+        }
+
+        // Hit the curved surface.
+        if inside_radius {
+            return None;
+        }
+        let distance = ray_cast_infinite_cylinder(self.radius, ray)?;
+        let point = ray.get_point(distance);
+        if distance > max_distance || ops::abs(point.y) > self.half_height {
+            return None;
+        }
+        let normal = Dir3::new(Vec3::new(point.x, 0.0, point.z)).unwrap_or(-ray.direction);
+        Some(RayHit3d { distance, normal })
+    }
+}
+
+impl RayCastable3d for Capsule3d {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        let cylinder = Cylinder {
+            radius: self.radius,
+            half_height: self.half_length,
+        };
+        let top = Vec3::Y * self.half_length;
+        [
+            cylinder.local_ray_cast(ray, max_distance),
+            ray_cast_sphere(top, self.radius, ray, max_distance),
+            ray_cast_sphere(-top, self.radius, ray, max_distance),
+        ]
+        .into_iter()
+        .flatten()
+        // A cylinder hit on a cap is inside one of the hemispheres, so it's never the closest.
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+}
+
+impl RayCastable3d for Triangle3d {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        // Möller–Trumbore intersection.
+        let [a, b, c] = self.vertices;
+        let edge_ab = b - a;
+        let edge_ac = c - a;
+        let p = ray.direction.cross(edge_ac);
+        let determinant = edge_ab.dot(p);
+        if ops::abs(determinant) < f32::EPSILON {
+            return None;
+        }
+        let inverse_determinant = determinant.recip();
+        let offset = ray.origin - a;
+        let u = offset.dot(p) * inverse_determinant;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(edge_ab);
+        let v = ray.direction.dot(q) * inverse_determinant;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let distance = edge_ac.dot(q) * inverse_determinant;
+        if distance < 0.0 || distance > max_distance {
+            return None;
+        }
+        // Triangles are two-sided, so the normal faces the origin of the ray.
+        let normal = Dir3::new(edge_ab.cross(edge_ac) * ops::copysign(1.0, determinant)).ok()?;
+        Some(RayHit3d { distance, normal })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Isometry3d, Quat};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn sphere_ray_cast() {
+        let sphere = Sphere::new(1.0);
+
+        let hit = sphere
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, -5.0), Dir3::Z), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Dir3::NEG_Z);
+
+        // Out of range, missing, and pointing away.
+        assert!(sphere
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, -5.0), Dir3::Z), 3.0)
+            .is_none());
+        assert!(sphere
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 2.0, -5.0), Dir3::Z), 10.0)
+            .is_none());
+        assert!(sphere
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, -5.0), Dir3::NEG_Z), 10.0)
+            .is_none());
+
+        // Starting inside.
+        let hit = sphere
+            .local_ray_cast(Ray3d::new(Vec3::ZERO, Dir3::X), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 0.0);
+        assert_eq!(hit.normal, Dir3::NEG_X);
+    }
+
+    #[test]
+    fn cuboid_ray_cast() {
+        let cuboid = Cuboid::new(2.0, 4.0, 6.0);
+
+        let hit = cuboid
+            .local_ray_cast(Ray3d::new(Vec3::new(5.0, 1.0, 2.0), Dir3::NEG_X), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Dir3::X);
+
+        let hit = cuboid
+            .local_ray_cast(Ray3d::new(Vec3::new(0.5, -5.0, 0.0), Dir3::Y), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 3.0);
+        assert_eq!(hit.normal, Dir3::NEG_Y);
+
+        let diagonal = Dir3::new(Vec3::new(-1.0, 0.0, -1.0)).unwrap();
+        assert!(cuboid
+            .local_ray_cast(Ray3d::new(Vec3::new(5.0, 0.0, 0.0), diagonal), 10.0)
+            .is_none());
+        assert!(cuboid
+            .local_ray_cast(Ray3d::new(Vec3::new(5.0, 3.0, 0.0), Dir3::NEG_X), 10.0)
+            .is_none());
+
+        let hit = cuboid
+            .local_ray_cast(Ray3d::new(Vec3::ZERO, Dir3::Z), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 0.0);
+    }
+
+    #[test]
+    fn cylinder_ray_cast() {
+        let cylinder = Cylinder::new(1.0, 2.0);
+
+        let hit = cylinder
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.5, -5.0), Dir3::Z), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::NEG_Z);
+
+        let hit = cylinder
+            .local_ray_cast(Ray3d::new(Vec3::new(0.5, 5.0, 0.0), Dir3::NEG_Y), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_eq!(hit.normal, Dir3::Y);
+
+        assert!(cylinder
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 1.5, -5.0), Dir3::Z), 10.0)
+            .is_none());
+        assert!(cylinder
+            .local_ray_cast(Ray3d::new(Vec3::new(2.0, 5.0, 0.0), Dir3::NEG_Y), 10.0)
+            .is_none());
+    }
+
+    #[test]
+    fn capsule_ray_cast() {
+        let capsule = Capsule3d::new(1.0, 2.0);
+
+        let hit = capsule
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 5.0, 0.0), Dir3::NEG_Y), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 3.0);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::Y);
+
+        let hit = capsule
+            .local_ray_cast(Ray3d::new(Vec3::new(-5.0, 0.5, 0.0), Dir3::X), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 4.0);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::NEG_X);
+
+        assert!(capsule
+            .local_ray_cast(Ray3d::new(Vec3::new(-5.0, 2.5, 0.0), Dir3::X), 10.0)
+            .is_none());
+    }
+
+    #[test]
+    fn triangle_ray_cast() {
+        let triangle = Triangle3d::new(
+            Vec3::new(-1.0, -1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+        );
+
+        // Triangles are hit from both sides.
+        let hit = triangle
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, 2.0), Dir3::NEG_Z), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 2.0);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::Z);
+        let hit = triangle
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, -2.0), Dir3::Z), 10.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 2.0);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::NEG_Z);
+
+        assert!(triangle
+            .local_ray_cast(Ray3d::new(Vec3::new(1.0, 1.0, 2.0), Dir3::NEG_Z), 10.0)
+            .is_none());
+        assert!(triangle
+            .local_ray_cast(Ray3d::new(Vec3::new(0.0, 0.0, 2.0), Dir3::Z), 10.0)
+            .is_none());
+    }
+
+    #[test]
+    fn transformed_ray_cast() {
+        let cuboid = Cuboid::new(2.0, 4.0, 6.0);
+        let isometry = Isometry3d::new(
+            Vec3::new(10.0, 0.0, 0.0),
+            Quat::from_rotation_y(core::f32::consts::FRAC_PI_2),
+        );
+
+        // The cuboid is rotated so that its Z axis is along the world X axis.
+        let hit = cuboid
+            .ray_cast(isometry, Ray3d::new(Vec3::ZERO, Dir3::X), 20.0)
+            .unwrap();
+        assert_relative_eq!(hit.distance, 7.0, epsilon = 1e-5);
+        assert_relative_eq!(hit.normal.as_vec3(), Vec3::NEG_X, epsilon = 1e-5);
+    }
+}
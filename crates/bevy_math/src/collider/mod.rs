@@ -0,0 +1,274 @@
+//! Collider shapes and ray casts against them, shared by picking, physics and gameplay code.
+//!
+//! There are two traits used:
+//! - [`RayCastable2d`]/[`RayCastable3d`] find the first intersection of a ray with a shape. They
+//!   are implemented for the most common [primitives](crate::primitives), and can be implemented
+//!   by third-party shapes such as meshes or heightfields.
+//! - [`Bounded2d`]/[`Bounded3d`] get bounding volumes for the shapes, to quickly discard shapes that
+//!   a ray can't hit.
+//!
+//! [`Collider2d`] and [`Collider3d`] hold any of the primitives supported by both traits, so that
+//! a single shape representation of a level can be used by every system querying it.
+
+mod dim2;
+mod dim3;
+
+use crate::{
+    bounding::{Aabb2d, Aabb3d, Bounded2d, Bounded3d, BoundingCircle, BoundingSphere},
+    primitives::{Capsule2d, Capsule3d, Circle, Cuboid, Cylinder, Rectangle, Sphere, Triangle3d},
+    Dir2, Dir3, Isometry2d, Isometry3d, Ray2d, Ray3d,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The first intersection of a [`Ray2d`] with a [`RayCastable2d`] shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct RayHit2d {
+    /// The distance from the origin of the ray to the hit point.
+    pub distance: f32,
+    /// The normal of the shape at the hit point.
+    ///
+    /// If the ray starts inside the shape, this is the opposite of the direction of the ray.
+    pub normal: Dir2,
+}
+
+/// The first intersection of a [`Ray3d`] with a [`RayCastable3d`] shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+pub struct RayHit3d {
+    /// The distance from the origin of the ray to the hit point.
+    pub distance: f32,
+    /// The normal of the shape at the hit point.
+    ///
+    /// If the ray starts inside the shape, this is the opposite of the direction of the ray.
+    /// For surfaces without an inside, like triangles, the normal faces the origin of the ray.
+    pub normal: Dir3,
+}
+
+/// A 2D shape that rays can be cast against.
+///
+/// Shapes are solid: a ray starting inside the shape hits it at a distance of zero.
+pub trait RayCastable2d {
+    /// Finds the first intersection of `ray` with the shape centered at the origin, within
+    /// `max_distance` of the origin of the ray.
+    fn local_ray_cast(&self, ray: Ray2d, max_distance: f32) -> Option<RayHit2d>;
+
+    /// Finds the first intersection of `ray` with the shape translated and rotated by the given
+    /// isometry, within `max_distance` of the origin of the ray.
+    fn ray_cast(
+        &self,
+        isometry: impl Into<Isometry2d>,
+        ray: Ray2d,
+        max_distance: f32,
+    ) -> Option<RayHit2d> {
+        let isometry = isometry.into();
+        let local_ray = Ray2d {
+            origin: isometry.inverse_transform_point(ray.origin),
+            direction: isometry.rotation.inverse() * ray.direction,
+        };
+        self.local_ray_cast(local_ray, max_distance)
+            .map(|hit| RayHit2d {
+                distance: hit.distance,
+                normal: isometry.rotation * hit.normal,
+            })
+    }
+}
+
+/// A 3D shape that rays can be cast against.
+///
+/// Shapes are solid: a ray starting inside the shape hits it at a distance of zero.
+pub trait RayCastable3d {
+    /// Finds the first intersection of `ray` with the shape centered at the origin, within
+    /// `max_distance` of the origin of the ray.
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d>;
+
+    /// Finds the first intersection of `ray` with the shape translated and rotated by the given
+    /// isometry, within `max_distance` of the origin of the ray.
+    fn ray_cast(
+        &self,
+        isometry: impl Into<Isometry3d>,
+        ray: Ray3d,
+        max_distance: f32,
+    ) -> Option<RayHit3d> {
+        let isometry = isometry.into();
+        let local_ray = Ray3d {
+            origin: isometry.inverse_transform_point(ray.origin).into(),
+            direction: isometry.rotation.inverse() * ray.direction,
+        };
+        self.local_ray_cast(local_ray, max_distance)
+            .map(|hit| RayHit3d {
+                distance: hit.distance,
+                normal: isometry.rotation * hit.normal,
+            })
+    }
+}
+
+/// A 2D collider shape, holding one of the primitives supported by [`RayCastable2d`] and
+/// [`Bounded2d`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum Collider2d {
+    /// A [`Circle`] collider.
+    Circle(Circle),
+    /// A [`Rectangle`] collider.
+    Rectangle(Rectangle),
+    /// A [`Capsule2d`] collider.
+    Capsule(Capsule2d),
+}
+
+impl RayCastable2d for Collider2d {
+    fn local_ray_cast(&self, ray: Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        match self {
+            Self::Circle(circle) => circle.local_ray_cast(ray, max_distance),
+            Self::Rectangle(rectangle) => rectangle.local_ray_cast(ray, max_distance),
+            Self::Capsule(capsule) => capsule.local_ray_cast(ray, max_distance),
+        }
+    }
+}
+
+impl Bounded2d for Collider2d {
+    fn aabb_2d(&self, isometry: impl Into<Isometry2d>) -> Aabb2d {
+        match self {
+            Self::Circle(circle) => circle.aabb_2d(isometry),
+            Self::Rectangle(rectangle) => rectangle.aabb_2d(isometry),
+            Self::Capsule(capsule) => capsule.aabb_2d(isometry),
+        }
+    }
+
+    fn bounding_circle(&self, isometry: impl Into<Isometry2d>) -> BoundingCircle {
+        match self {
+            Self::Circle(circle) => circle.bounding_circle(isometry),
+            Self::Rectangle(rectangle) => rectangle.bounding_circle(isometry),
+            Self::Capsule(capsule) => capsule.bounding_circle(isometry),
+        }
+    }
+}
+
+impl From<Circle> for Collider2d {
+    fn from(circle: Circle) -> Self {
+        Self::Circle(circle)
+    }
+}
+
+impl From<Rectangle> for Collider2d {
+    fn from(rectangle: Rectangle) -> Self {
+        Self::Rectangle(rectangle)
+    }
+}
+
+impl From<Capsule2d> for Collider2d {
+    fn from(capsule: Capsule2d) -> Self {
+        Self::Capsule(capsule)
+    }
+}
+
+/// A 3D collider shape, holding one of the primitives supported by [`RayCastable3d`] and
+/// [`Bounded3d`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum Collider3d {
+    /// A [`Sphere`] collider.
+    Sphere(Sphere),
+    /// A [`Cuboid`] collider.
+    Cuboid(Cuboid),
+    /// A [`Cylinder`] collider.
+    Cylinder(Cylinder),
+    /// A [`Capsule3d`] collider.
+    Capsule(Capsule3d),
+    /// A [`Triangle3d`] collider.
+    Triangle(Triangle3d),
+}
+
+impl RayCastable3d for Collider3d {
+    fn local_ray_cast(&self, ray: Ray3d, max_distance: f32) -> Option<RayHit3d> {
+        match self {
+            Self::Sphere(sphere) => sphere.local_ray_cast(ray, max_distance),
+            Self::Cuboid(cuboid) => cuboid.local_ray_cast(ray, max_distance),
+            Self::Cylinder(cylinder) => cylinder.local_ray_cast(ray, max_distance),
+            Self::Capsule(capsule) => capsule.local_ray_cast(ray, max_distance),
+            Self::Triangle(triangle) => triangle.local_ray_cast(ray, max_distance),
+        }
+    }
+}
+
+impl Bounded3d for Collider3d {
+    fn aabb_3d(&self, isometry: impl Into<Isometry3d>) -> Aabb3d {
+        match self {
+            Self::Sphere(sphere) => sphere.aabb_3d(isometry),
+            Self::Cuboid(cuboid) => cuboid.aabb_3d(isometry),
+            Self::Cylinder(cylinder) => cylinder.aabb_3d(isometry),
+            Self::Capsule(capsule) => capsule.aabb_3d(isometry),
+            Self::Triangle(triangle) => triangle.aabb_3d(isometry),
+        }
+    }
+
+    fn bounding_sphere(&self, isometry: impl Into<Isometry3d>) -> BoundingSphere {
+        match self {
+            Self::Sphere(sphere) => sphere.bounding_sphere(isometry),
+            Self::Cuboid(cuboid) => cuboid.bounding_sphere(isometry),
+            Self::Cylinder(cylinder) => cylinder.bounding_sphere(isometry),
+            Self::Capsule(capsule) => capsule.bounding_sphere(isometry),
+            Self::Triangle(triangle) => triangle.bounding_sphere(isometry),
+        }
+    }
+}
+
+impl From<Sphere> for Collider3d {
+    fn from(sphere: Sphere) -> Self {
+        Self::Sphere(sphere)
+    }
+}
+
+impl From<Cuboid> for Collider3d {
+    fn from(cuboid: Cuboid) -> Self {
+        Self::Cuboid(cuboid)
+    }
+}
+
+impl From<Cylinder> for Collider3d {
+    fn from(cylinder: Cylinder) -> Self {
+        Self::Cylinder(cylinder)
+    }
+}
+
+impl From<Capsule3d> for Collider3d {
+    fn from(capsule: Capsule3d) -> Self {
+        Self::Capsule(capsule)
+    }
+}
+
+impl From<Triangle3d> for Collider3d {
+    fn from(triangle: Triangle3d) -> Self {
+        Self::Triangle(triangle)
+    }
+}
@@ -29,6 +29,7 @@ extern crate alloc;
 mod affine3;
 mod aspect_ratio;
 pub mod bounding;
+pub mod collider;
 pub mod common_traits;
 mod compass;
 pub mod cubic_splines;
@@ -329,6 +329,173 @@ impl AabbAccumulator {
     }
 }
 
+/// Joint matrices of a skinned mesh, sampled at a fixed rate over one or more animation clips.
+///
+/// Rendering characters from baked animations avoids evaluating animations and uploading joint
+/// matrices for every character, which makes rendering large crowds sharing a skeleton feasible,
+/// even on low-end targets. The matrices are stored relative to the root of the skinned mesh, so
+/// the same baked animation can be used by instances anywhere in the world.
+///
+/// Frames are recorded with [`BakedSkinAnimation::push_frame`] (or
+/// [`BakedSkinAnimation::push_skinned_mesh_frame`]) after starting a clip with
+/// [`BakedSkinAnimation::begin_clip`].
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Clone, Default, Debug, PartialEq)]
+pub struct BakedSkinAnimation {
+    joint_count: usize,
+    frames_per_second: f32,
+    clips: Vec<BakedSkinAnimationClip>,
+    // `joint_count` matrices per frame, transforming from the bind pose to the model space of the
+    // skinned mesh.
+    joint_matrices: Vec<Affine3A>,
+}
+
+/// The range of frames of a clip in a [`BakedSkinAnimation`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Clone, Default, Debug, PartialEq)]
+pub struct BakedSkinAnimationClip {
+    /// The index of the first frame of the clip.
+    pub first_frame: u32,
+    /// The number of frames in the clip.
+    pub frame_count: u32,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug, Error)]
+pub enum BakedSkinAnimationError {
+    #[error("No clip was started with `BakedSkinAnimation::begin_clip`")]
+    NoClip,
+    #[error("Expected {expected} joint matrices for the frame, got {found}")]
+    JointCountMismatch { expected: usize, found: usize },
+    #[error("The joint entity {0} has no `GlobalTransform`")]
+    MissingJoint(Entity),
+}
+
+impl BakedSkinAnimation {
+    /// Creates an empty baked animation for a skeleton of `joint_count` joints, sampled at
+    /// `frames_per_second`.
+    pub fn new(joint_count: usize, frames_per_second: f32) -> Self {
+        Self {
+            joint_count,
+            frames_per_second,
+            clips: Vec::new(),
+            joint_matrices: Vec::new(),
+        }
+    }
+
+    /// Returns the number of joints of the skeleton.
+    pub fn joint_count(&self) -> usize {
+        self.joint_count
+    }
+
+    /// Returns the rate at which frames are sampled.
+    pub fn frames_per_second(&self) -> f32 {
+        self.frames_per_second
+    }
+
+    /// Returns the clips of this animation, indexed by the values returned by
+    /// [`BakedSkinAnimation::begin_clip`].
+    pub fn clips(&self) -> &[BakedSkinAnimationClip] {
+        &self.clips
+    }
+
+    /// Returns the total number of frames, across all clips.
+    pub fn frame_count(&self) -> usize {
+        self.joint_matrices
+            .len()
+            .checked_div(self.joint_count)
+            .unwrap_or_default()
+    }
+
+    /// Starts a new clip, returning its index. Following frames are added to this clip.
+    pub fn begin_clip(&mut self) -> usize {
+        self.clips.push(BakedSkinAnimationClip {
+            first_frame: self.frame_count() as u32,
+            frame_count: 0,
+        });
+        self.clips.len() - 1
+    }
+
+    /// Adds a frame to the current clip, given the matrix of each joint transforming from the bind
+    /// pose to the model space of the skinned mesh.
+    pub fn push_frame(
+        &mut self,
+        joint_matrices: impl IntoIterator<Item = Affine3A>,
+    ) -> Result<(), BakedSkinAnimationError> {
+        let Some(clip) = self.clips.last_mut() else {
+            return Err(BakedSkinAnimationError::NoClip);
+        };
+        let start = self.joint_matrices.len();
+        self.joint_matrices.extend(joint_matrices);
+        let found = self.joint_matrices.len() - start;
+        if found != self.joint_count {
+            self.joint_matrices.truncate(start);
+            return Err(BakedSkinAnimationError::JointCountMismatch {
+                expected: self.joint_count,
+                found,
+            });
+        }
+        clip.frame_count += 1;
+        Ok(())
+    }
+
+    /// Adds a frame to the current clip from the current pose of a skinned mesh entity.
+    ///
+    /// `world_from_entity` is the transform of the skinned mesh entity, which the joint matrices
+    /// are made relative to.
+    pub fn push_skinned_mesh_frame(
+        &mut self,
+        joint_entities: &Query<&GlobalTransform>,
+        skinned_mesh: &SkinnedMesh,
+        skinned_mesh_inverse_bindposes: &SkinnedMeshInverseBindposes,
+        world_from_entity: &GlobalTransform,
+    ) -> Result<(), BakedSkinAnimationError> {
+        let entity_from_world = world_from_entity.affine().inverse();
+        let joint_matrices = skinned_mesh
+            .joints
+            .iter()
+            .zip(skinned_mesh_inverse_bindposes.iter())
+            .map(|(&joint, &joint_from_model)| {
+                let world_from_joint = joint_entities
+                    .get(joint)
+                    .map_err(|_| BakedSkinAnimationError::MissingJoint(joint))?;
+                Ok(entity_from_world
+                    * world_from_joint.affine()
+                    * Affine3A::from_mat4(joint_from_model))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.push_frame(joint_matrices)
+    }
+
+    /// Returns the matrix of `joint` at the given `frame`, counted across all clips.
+    pub fn joint_matrix(&self, frame: usize, joint: usize) -> Option<Affine3A> {
+        if joint >= self.joint_count {
+            return None;
+        }
+        self.joint_matrices
+            .get(frame * self.joint_count + joint)
+            .copied()
+    }
+
+    /// Returns the joint matrices as rows of texels, for use in a texture.
+    ///
+    /// Each row of the texture is a frame, and each joint takes three consecutive texels, holding the
+    /// rows of its 3x4 affine matrix.
+    pub fn to_texels(&self) -> Vec<[f32; 4]> {
+        self.joint_matrices
+            .iter()
+            .flat_map(|matrix| {
+                let m = matrix.matrix3;
+                let t = matrix.translation;
+                [
+                    [m.x_axis.x, m.y_axis.x, m.z_axis.x, t.x],
+                    [m.x_axis.y, m.y_axis.y, m.z_axis.y, t.y],
+                    [m.x_axis.z, m.y_axis.z, m.z_axis.z, t.z],
+                ]
+            })
+            .collect()
+    }
+}
+
 // An index that corresponds to `Mesh::ATTRIBUTE_JOINT_INDEX` and `SkinnedMesh::joints`.
 #[derive(Copy, Clone, PartialEq, Debug, Reflect)]
 pub struct JointIndex(pub u16);
@@ -645,6 +812,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn baked_skin_animation() {
+        let mut animation = BakedSkinAnimation::new(2, 30.0);
+        assert_eq!(
+            animation.push_frame([Affine3A::IDENTITY; 2]),
+            Err(BakedSkinAnimationError::NoClip)
+        );
+
+        assert_eq!(animation.begin_clip(), 0);
+        animation.push_frame([Affine3A::IDENTITY; 2]).unwrap();
+        assert_eq!(
+            animation.push_frame([Affine3A::IDENTITY; 3]),
+            Err(BakedSkinAnimationError::JointCountMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+
+        let translation = Affine3A::from_translation(vec3(1.0, 2.0, 3.0));
+        let scale = Affine3A::from_scale(vec3(4.0, 5.0, 6.0));
+        assert_eq!(animation.begin_clip(), 1);
+        animation.push_frame([translation, scale]).unwrap();
+        animation.push_frame([scale, translation]).unwrap();
+
+        assert_eq!(animation.frame_count(), 3);
+        assert_eq!(
+            animation.clips(),
+            &[
+                BakedSkinAnimationClip {
+                    first_frame: 0,
+                    frame_count: 1
+                },
+                BakedSkinAnimationClip {
+                    first_frame: 1,
+                    frame_count: 2
+                },
+            ]
+        );
+        assert_eq!(animation.joint_matrix(2, 1), Some(translation));
+        assert_eq!(animation.joint_matrix(2, 2), None);
+
+        let texels = animation.to_texels();
+        assert_eq!(texels.len(), 3 * 2 * 3);
+        // Second frame, first joint.
+        assert_eq!(
+            &texels[6..9],
+            &[
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 2.0],
+                [0.0, 0.0, 1.0, 3.0]
+            ]
+        );
+    }
+
     fn aabb_assert_eq(a: Aabb3d, b: Aabb3d) {
         assert_abs_diff_eq!(a.min.x, b.min.x);
         assert_abs_diff_eq!(a.min.y, b.min.y);
@@ -0,0 +1,163 @@
+// Vertex shader for crowd meshes, skinned from a baked animation texture.
+//
+// The joint matrices of each frame are stored in a row of the animation texture, as the three rows
+// of a 3x4 matrix per joint. The clip and time offset of each instance are packed in its mesh tag.
+
+#import bevy_pbr::{
+    mesh_functions,
+    view_transformations::position_world_to_clip,
+}
+#import bevy_render::globals::Globals
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+
+@group(0) @binding(1) var<uniform> globals: Globals;
+#else
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput},
+    mesh_view_bindings::globals,
+}
+#endif
+
+struct CrowdSkinning {
+    // The first frame and frame count of each clip, in `x` and `y`.
+    clips: array<vec4<u32>, 16>,
+    joint_count: u32,
+    frames_per_second: f32,
+    clip_count: u32,
+}
+
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> crowd_skinning: CrowdSkinning;
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var crowd_animation_texture: texture_2d<f32>;
+
+struct CrowdVertex {
+    @location(14) joint_indices: vec4<u32>,
+    @location(15) joint_weights: vec4<f32>,
+}
+
+// Returns the frame of the animation texture played by the given instance at the given time.
+fn crowd_frame(instance_index: u32, time: f32) -> u32 {
+    let tag = mesh_functions::get_tag(instance_index);
+    let clip = crowd_skinning.clips[min(tag >> 16u, crowd_skinning.clip_count - 1u)];
+    let time_offset = f32(tag & 0xffffu) / 1000.0;
+    let frame = u32(max(floor((time + time_offset) * crowd_skinning.frames_per_second), 0.0));
+    return clip.x + frame % max(clip.y, 1u);
+}
+
+fn crowd_joint_matrix(frame: u32, joint: u32) -> mat4x4<f32> {
+    let x = i32(min(joint, crowd_skinning.joint_count - 1u) * 3u);
+    let y = i32(frame);
+    let row_x = textureLoad(crowd_animation_texture, vec2(x, y), 0);
+    let row_y = textureLoad(crowd_animation_texture, vec2(x + 1, y), 0);
+    let row_z = textureLoad(crowd_animation_texture, vec2(x + 2, y), 0);
+    return transpose(mat4x4<f32>(row_x, row_y, row_z, vec4(0.0, 0.0, 0.0, 1.0)));
+}
+
+// Returns the matrix transforming the vertex from the local space of the mesh to its animated pose,
+// still in local space.
+fn crowd_skin_model(crowd: CrowdVertex, frame: u32) -> mat4x4<f32> {
+    return crowd.joint_weights.x * crowd_joint_matrix(frame, crowd.joint_indices.x)
+        + crowd.joint_weights.y * crowd_joint_matrix(frame, crowd.joint_indices.y)
+        + crowd.joint_weights.z * crowd_joint_matrix(frame, crowd.joint_indices.z)
+        + crowd.joint_weights.w * crowd_joint_matrix(frame, crowd.joint_indices.w);
+}
+
+fn inverse_transpose_3x3m(in: mat3x3<f32>) -> mat3x3<f32> {
+    let x = cross(in[1], in[2]);
+    let y = cross(in[2], in[0]);
+    let z = cross(in[0], in[1]);
+    let det = dot(in[2], z);
+    return mat3x3<f32>(
+        x / det,
+        y / det,
+        z / det
+    );
+}
+
+@vertex
+fn vertex(vertex: Vertex, crowd: CrowdVertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let mesh_world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let frame = crowd_frame(vertex.instance_index, globals.time);
+    let world_from_local = mesh_world_from_local * crowd_skin_model(crowd, frame);
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4<f32>(vertex.position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0); // Clamp depth to avoid clipping
+#endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif // VERTEX_UVS_A
+
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif // VERTEX_UVS_B
+
+#ifdef VERTEX_NORMALS
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    out.world_normal = normalize(inverse_transpose_3x3m(mat3x3<f32>(
+        world_from_local[0].xyz,
+        world_from_local[1].xyz,
+        world_from_local[2].xyz,
+    )) * vertex.normal);
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#else // PREPASS_PIPELINE
+    out.world_normal = normalize(inverse_transpose_3x3m(mat3x3<f32>(
+        world_from_local[0].xyz,
+        world_from_local[1].xyz,
+        world_from_local[2].xyz,
+    )) * vertex.normal);
+#endif // PREPASS_PIPELINE
+#endif // VERTEX_NORMALS
+
+#ifdef VERTEX_TANGENTS
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex.instance_index
+    );
+#endif // NORMAL_PREPASS_OR_DEFERRED_PREPASS
+#else // PREPASS_PIPELINE
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(
+        world_from_local,
+        vertex.tangent,
+        vertex.instance_index
+    );
+#endif // PREPASS_PIPELINE
+#endif // VERTEX_TANGENTS
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The animation texture is sampled at the time of the previous frame as well, so that
+    // animated instances get motion vectors even when they don't move.
+    let prev_frame = crowd_frame(vertex.instance_index, globals.time - globals.delta_time);
+    let prev_world_from_local = mesh_functions::get_previous_world_from_local(vertex.instance_index) *
+        crowd_skin_model(crowd, prev_frame);
+    out.previous_world_position = mesh_functions::mesh_position_local_to_world(
+        prev_world_from_local,
+        vec4<f32>(vertex.position, 1.0)
+    );
+#endif // MOTION_VECTOR_PREPASS
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        vertex.instance_index, mesh_world_from_local[3]);
+#endif // VISIBILITY_RANGE_DITHER
+
+    return out;
+}
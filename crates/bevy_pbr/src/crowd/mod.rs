@@ -0,0 +1,266 @@
+//! Rendering of large crowds of skinned characters.
+//!
+//! Regular skinned meshes upload the joint matrices of every character each frame and can't be
+//! batched together, which limits the number of animated characters that can be on screen, in
+//! particular on low-end targets. Crowd skinning instead samples the joint matrices from a
+//! [`BakedSkinAnimation`] stored in a texture, so all the characters sharing a mesh, a skeleton and
+//! a set of animations are drawn in a single instanced draw call. Each instance plays one of the
+//! baked clips, offset in time by its [`CrowdAnimation`].
+//!
+//! To render a crowd:
+//!
+//! 1. Add the [`CrowdSkinningPlugin`].
+//! 2. Bake the animations of the character into a [`BakedSkinAnimation`], e.g. by recording an
+//!    animated [`SkinnedMesh`](bevy_mesh::skinning::SkinnedMesh) with
+//!    [`BakedSkinAnimation::push_skinned_mesh_frame`].
+//! 3. Convert the mesh of the character with [`convert_to_crowd_mesh`].
+//! 4. Spawn instances with a [`Mesh3d`](bevy_mesh::Mesh3d) of the converted mesh, a
+//!    [`MeshMaterial3d`](crate::MeshMaterial3d) of a [`CrowdMaterial`] created with
+//!    [`CrowdSkinningExt::new`], and a [`CrowdAnimation`].
+//!
+//! The bounding box of crowd meshes is computed from their bind pose, so instances whose animations
+//! move far away from it may need a `NoFrustumCulling` component. Morph targets aren't supported.
+
+use crate::{
+    ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline,
+    MaterialPlugin, StandardMaterial,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{embedded_asset, Asset, Assets, Handle, RenderAssetUsages};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_math::UVec4;
+use bevy_mesh::{
+    skinning::{BakedSkinAnimation, BakedSkinAnimationClip},
+    Mesh, MeshTag, MeshVertexAttribute, MeshVertexBufferLayoutRef, VertexFormat,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{
+        AsBindGroup, AsBindGroupShaderType, Extent3d, RenderPipelineDescriptor, ShaderType,
+        SpecializedMeshPipelineError, TextureDimension, TextureFormat,
+    },
+    texture::GpuImage,
+};
+use bevy_shader::ShaderRef;
+use tracing::warn;
+
+/// Per vertex joint indices of a crowd mesh, replacing [`Mesh::ATTRIBUTE_JOINT_INDEX`].
+///
+/// See [`convert_to_crowd_mesh`].
+pub const ATTRIBUTE_CROWD_JOINT_INDEX: MeshVertexAttribute = MeshVertexAttribute::new(
+    "Vertex_CrowdJointIndex",
+    2_718_305_113,
+    VertexFormat::Uint16x4,
+);
+
+/// Per vertex joint weights of a crowd mesh, replacing [`Mesh::ATTRIBUTE_JOINT_WEIGHT`].
+///
+/// See [`convert_to_crowd_mesh`].
+pub const ATTRIBUTE_CROWD_JOINT_WEIGHT: MeshVertexAttribute = MeshVertexAttribute::new(
+    "Vertex_CrowdJointWeight",
+    2_718_305_114,
+    VertexFormat::Float32x4,
+);
+
+/// The maximum number of clips of a [`CrowdMaterial`].
+pub const MAX_CROWD_ANIMATION_CLIPS: usize = 16;
+
+// These are above the locations used by the forward and prepass vertex shaders, while staying
+// within the 16 vertex attributes guaranteed by WebGL 2.
+const CROWD_JOINT_INDEX_SHADER_LOCATION: u32 = 14;
+const CROWD_JOINT_WEIGHT_SHADER_LOCATION: u32 = 15;
+
+/// Adds support for rendering crowds of skinned characters with a [`CrowdMaterial`].
+///
+/// See the [module docs](self) for more details.
+pub struct CrowdSkinningPlugin;
+
+impl Plugin for CrowdSkinningPlugin {
+    fn build(&self, app: &mut App) {
+        embedded_asset!(app, "crowd_skinning.wgsl");
+
+        app.add_plugins(MaterialPlugin::<CrowdMaterial>::default())
+            .add_systems(PostUpdate, update_crowd_animation_mesh_tags);
+    }
+}
+
+/// A [`StandardMaterial`] skinned from a [`BakedSkinAnimation`] texture.
+pub type CrowdMaterial = ExtendedMaterial<StandardMaterial, CrowdSkinningExt>;
+
+/// Material extension sampling the joint matrices of a crowd mesh from a baked animation texture.
+///
+/// Meshes using this extension must be converted with [`convert_to_crowd_mesh`], and must not have
+/// a [`SkinnedMesh`](bevy_mesh::skinning::SkinnedMesh) component.
+#[derive(Asset, AsBindGroup, Reflect, Clone, Debug, Default)]
+#[reflect(Clone, Default, Debug)]
+#[uniform(100, CrowdSkinningExtUniform)]
+pub struct CrowdSkinningExt {
+    /// The texture holding the joint matrices, as created by [`crowd_animation_image`].
+    #[texture(101, sample_type = "float", filterable = false, visibility(vertex))]
+    pub animation_texture: Handle<Image>,
+    /// The number of joints of the skeleton.
+    pub joint_count: u32,
+    /// The rate at which the frames of the animation texture were sampled.
+    pub frames_per_second: f32,
+    /// The clips of the animation, indexed by [`CrowdAnimation::clip`].
+    ///
+    /// Only the first [`MAX_CROWD_ANIMATION_CLIPS`] clips are used.
+    pub clips: Vec<BakedSkinAnimationClip>,
+}
+
+impl CrowdSkinningExt {
+    /// Creates an extension rendering the given baked animation, adding its texture to `images`.
+    pub fn new(animation: &BakedSkinAnimation, images: &mut Assets<Image>) -> Self {
+        if animation.clips().len() > MAX_CROWD_ANIMATION_CLIPS {
+            warn!(
+                "Crowd animations support up to {MAX_CROWD_ANIMATION_CLIPS} clips, but the baked animation has {}. Extra clips are ignored.",
+                animation.clips().len()
+            );
+        }
+        Self {
+            animation_texture: images.add(crowd_animation_image(
+                animation,
+                RenderAssetUsages::RENDER_WORLD,
+            )),
+            joint_count: animation.joint_count() as u32,
+            frames_per_second: animation.frames_per_second(),
+            clips: animation.clips().to_vec(),
+        }
+    }
+}
+
+/// The GPU representation of a [`CrowdSkinningExt`].
+#[derive(Clone, Default, ShaderType)]
+pub struct CrowdSkinningExtUniform {
+    /// The first frame and frame count of each clip, in `x` and `y`.
+    pub clips: [UVec4; MAX_CROWD_ANIMATION_CLIPS],
+    pub joint_count: u32,
+    pub frames_per_second: f32,
+    pub clip_count: u32,
+}
+
+impl AsBindGroupShaderType<CrowdSkinningExtUniform> for CrowdSkinningExt {
+    fn as_bind_group_shader_type(
+        &self,
+        _images: &RenderAssets<GpuImage>,
+    ) -> CrowdSkinningExtUniform {
+        let mut clips = [UVec4::ZERO; MAX_CROWD_ANIMATION_CLIPS];
+        for (gpu_clip, clip) in clips.iter_mut().zip(&self.clips) {
+            *gpu_clip = UVec4::new(clip.first_frame, clip.frame_count, 0, 0);
+        }
+        CrowdSkinningExtUniform {
+            clips,
+            joint_count: self.joint_count,
+            frames_per_second: self.frames_per_second,
+            clip_count: self.clips.len().clamp(1, MAX_CROWD_ANIMATION_CLIPS) as u32,
+        }
+    }
+}
+
+impl MaterialExtension for CrowdSkinningExt {
+    fn vertex_shader() -> ShaderRef {
+        "embedded://bevy_pbr/crowd/crowd_skinning.wgsl".into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        "embedded://bevy_pbr/crowd/crowd_skinning.wgsl".into()
+    }
+
+    fn deferred_vertex_shader() -> ShaderRef {
+        "embedded://bevy_pbr/crowd/crowd_skinning.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        // All the attributes of a mesh are interleaved in a single vertex buffer, so the crowd
+        // attributes can be appended to the ones selected by the base pipeline.
+        let crowd_layout = layout.0.get_layout(&[
+            ATTRIBUTE_CROWD_JOINT_INDEX.at_shader_location(CROWD_JOINT_INDEX_SHADER_LOCATION),
+            ATTRIBUTE_CROWD_JOINT_WEIGHT.at_shader_location(CROWD_JOINT_WEIGHT_SHADER_LOCATION),
+        ])?;
+        if let Some(buffer) = descriptor.vertex.buffers.first_mut() {
+            buffer.attributes.extend(crowd_layout.attributes);
+        }
+
+        if let Some(label) = &mut descriptor.label {
+            *label = format!("crowd_{label}").into();
+        }
+
+        Ok(())
+    }
+}
+
+/// Selects the clip played by an instance of a crowd mesh, and its time offset.
+///
+/// This is stored in the [`MeshTag`] of the entity, which can't be used for other purposes: the
+/// clip index is stored in the upper 16 bits, and the time offset in milliseconds, wrapping every
+/// 65.536 seconds, in the lower 16 bits.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+#[require(MeshTag)]
+pub struct CrowdAnimation {
+    /// The index of the clip to play, in [`CrowdSkinningExt::clips`].
+    pub clip: u16,
+    /// The time offset of the instance in seconds, so that instances playing the same clip aren't
+    /// synchronized.
+    pub time_offset: f32,
+}
+
+impl CrowdAnimation {
+    /// Returns the [`MeshTag`] encoding this animation for the crowd skinning shader.
+    pub fn mesh_tag(&self) -> MeshTag {
+        let time_offset_ms = (self.time_offset.rem_euclid(65.536) * 1000.0) as u32 & 0xffff;
+        MeshTag(((self.clip as u32) << 16) | time_offset_ms)
+    }
+}
+
+/// Updates the [`MeshTag`] of entities whose [`CrowdAnimation`] changed.
+pub fn update_crowd_animation_mesh_tags(
+    mut query: Query<(&CrowdAnimation, &mut MeshTag), Changed<CrowdAnimation>>,
+) {
+    for (animation, mut tag) in &mut query {
+        tag.set_if_neq(animation.mesh_tag());
+    }
+}
+
+/// Creates the texture of a [`BakedSkinAnimation`] for a [`CrowdSkinningExt`].
+///
+/// Each row of the texture is a frame, and each joint takes three consecutive texels. The texture
+/// uses an unfilterable float format, which is supported on all backends, including WebGL 2.
+pub fn crowd_animation_image(
+    animation: &BakedSkinAnimation,
+    asset_usage: RenderAssetUsages,
+) -> Image {
+    let texels = animation.to_texels();
+    Image::new(
+        Extent3d {
+            width: (animation.joint_count() * 3) as u32,
+            height: animation.frame_count() as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        bytemuck::cast_slice(&texels).to_vec(),
+        TextureFormat::Rgba32Float,
+        asset_usage,
+    )
+}
+
+/// Converts a skinned mesh to a crowd mesh, for use with a [`CrowdMaterial`].
+///
+/// This moves the joint attributes of the mesh to [`ATTRIBUTE_CROWD_JOINT_INDEX`] and
+/// [`ATTRIBUTE_CROWD_JOINT_WEIGHT`], so that it isn't treated as a regular skinned mesh.
+pub fn convert_to_crowd_mesh(mut mesh: Mesh) -> Mesh {
+    if let Some(joint_indices) = mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_INDEX) {
+        mesh.insert_attribute(ATTRIBUTE_CROWD_JOINT_INDEX, joint_indices);
+    }
+    if let Some(joint_weights) = mesh.remove_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
+        mesh.insert_attribute(ATTRIBUTE_CROWD_JOINT_WEIGHT, joint_weights);
+    }
+    mesh
+}
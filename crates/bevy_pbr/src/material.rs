@@ -197,6 +197,18 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         true
     }
 
+    /// Controls if the pipelines of the Material are created synchronously, blocking rendering
+    /// until they are ready, instead of in the background.
+    ///
+    /// Meshes are not drawn while the pipelines of their material are compiling. This avoids
+    /// materials popping in, e.g. for a loading screen or the UI of a game, without blocking on
+    /// the compilation of all the pipelines at startup like
+    /// [`RenderPlugin::synchronous_pipeline_compilation`](bevy_render::RenderPlugin::synchronous_pipeline_compilation).
+    #[inline]
+    fn synchronous_pipeline_compilation() -> bool {
+        false
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the default prepass vertex shader
     /// will be used.
     ///
@@ -1436,7 +1448,11 @@ pub fn base_specialize(
                 properties: properties.clone(),
             };
 
-            pipelines.specialize(pipeline_cache, &specializer, key, layout)
+            let id = pipelines.specialize(pipeline_cache, &specializer, key, layout)?;
+            if properties.synchronous_pipeline_compilation {
+                pipeline_cache.create_synchronously(id.id());
+            }
+            Ok(id)
         },
     )
 }
@@ -1456,7 +1472,11 @@ fn prepass_specialize(
                 properties: properties.clone(),
             };
 
-            pipelines.specialize(pipeline_cache, &specializer, key, layout)
+            let id = pipelines.specialize(pipeline_cache, &specializer, key, layout)?;
+            if properties.synchronous_pipeline_compilation {
+                pipeline_cache.create_synchronously(id.id());
+            }
+            Ok(id)
         },
     )
 }
@@ -1593,6 +1613,7 @@ where
 
         let shadows_enabled = M::enable_shadows();
         let prepass_enabled = M::enable_prepass();
+        let synchronous_pipeline_compilation = M::synchronous_pipeline_compilation();
 
         let draw_opaque_pbr = opaque_draw_functions.read().id::<DrawMaterial>();
         let draw_alpha_mask_pbr = alpha_mask_draw_functions.read().id::<DrawMaterial>();
@@ -1728,6 +1749,7 @@ where
                 material_key,
                 shadows_enabled,
                 prepass_enabled,
+                synchronous_pipeline_compilation,
             }),
         })
     }
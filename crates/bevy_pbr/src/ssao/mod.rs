@@ -2,7 +2,8 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{embedded_asset, load_embedded_asset, Handle};
 use bevy_camera::{Camera, Camera3d};
 use bevy_core_pipeline::{
-    prepass::{DepthPrepass, NormalPrepass, ViewPrepassTextures},
+    mip_generation::experimental::depth::{DownsampleDepthPipelines, ViewDepthPyramid},
+    prepass::{DepthPrepass, DepthPyramidPrepass, NormalPrepass, ViewPrepassTextures},
     schedule::{Core3d, Core3dSystems},
 };
 use bevy_ecs::{
@@ -63,9 +64,9 @@ impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
             .resource::<RenderDevice>()
             .limits()
             .max_storage_textures_per_shader_stage
-            < 5
+            < 2
         {
-            warn!("ScreenSpaceAmbientOcclusionPlugin not loaded. GPU lacks support: Limits::max_storage_textures_per_shader_stage is less than 5.");
+            warn!("ScreenSpaceAmbientOcclusionPlugin not loaded. GPU lacks support: Limits::max_storage_textures_per_shader_stage is less than 2.");
             return;
         }
 
@@ -107,6 +108,15 @@ impl Plugin for ScreenSpaceAmbientOcclusionPlugin {
 /// TAA (`TemporalAntiAliasing`).
 /// Doing so greatly reduces SSAO noise.
 ///
+/// If the camera also has a [`DepthPyramidPrepass`], SSAO samples the depth
+/// pyramid shared with other effects and occlusion culling, instead of
+/// generating its own depth hierarchy. This is cheaper, at the cost of
+/// slightly less accurate occlusion from distant samples.
+///
+/// SSAO requires compute shaders. On GPUs supporting fewer than 5 storage
+/// textures per shader stage, such as many GLES devices, a reduced depth
+/// hierarchy is used.
+///
 /// SSAO is not supported on `WebGL2`, and is not currently supported on `WebGPU`.
 #[derive(Component, ExtractComponent, Reflect, PartialEq, Clone, Debug)]
 #[reflect(Component, Debug, Default, PartialEq, Clone)]
@@ -184,7 +194,7 @@ fn ssao(
         camera.physical_viewport_size,
         pipeline_cache.get_compute_pipeline(pipelines.preprocess_depth_pipeline),
         pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_pipeline),
-        pipeline_cache.get_compute_pipeline(pipeline_id.0),
+        pipeline_cache.get_compute_pipeline(pipeline_id.id),
     )
     else {
         return;
@@ -197,14 +207,15 @@ fn ssao(
     let command_encoder = ctx.command_encoder();
     command_encoder.push_debug_group("ssao");
 
-    {
+    // When sharing the view's depth pyramid, there's no depth to preprocess.
+    if let Some(preprocess_depth_bind_group) = &bind_groups.preprocess_depth_bind_group {
         let mut preprocess_depth_pass =
             command_encoder.begin_compute_pass(&ComputePassDescriptor {
                 label: Some("ssao_preprocess_depth"),
                 timestamp_writes: None,
             });
         preprocess_depth_pass.set_pipeline(preprocess_depth_pipeline);
-        preprocess_depth_pass.set_bind_group(0, &bind_groups.preprocess_depth_bind_group, &[]);
+        preprocess_depth_pass.set_bind_group(0, preprocess_depth_bind_group, &[]);
         preprocess_depth_pass.set_bind_group(
             1,
             &bind_groups.common_bind_group,
@@ -263,6 +274,7 @@ struct SsaoPipelines {
     common_bind_group_layout: BindGroupLayoutDescriptor,
     preprocess_depth_bind_group_layout: BindGroupLayoutDescriptor,
     ssao_bind_group_layout: BindGroupLayoutDescriptor,
+    ssao_depth_pyramid_bind_group_layout: BindGroupLayoutDescriptor,
     spatial_denoise_bind_group_layout: BindGroupLayoutDescriptor,
 
     hilbert_index_lut: TextureView,
@@ -271,6 +283,9 @@ struct SsaoPipelines {
 
     shader: Handle<Shader>,
     depth_format: TextureFormat,
+    /// The number of MIP levels generated by the depth preprocessing pass, which is limited by the
+    /// number of storage textures per shader stage that the GPU supports.
+    preprocessed_depth_mip_count: u32,
 }
 
 impl FromWorld for SsaoPipelines {
@@ -291,6 +306,13 @@ impl FromWorld for SsaoPipelines {
             TextureFormat::R32Float
         };
 
+        let preprocessed_depth_mip_count =
+            if render_device.limits().max_storage_textures_per_shader_stage >= 5 {
+                5
+            } else {
+                2
+            };
+
         let hilbert_index_lut = render_device
             .create_texture_with_data(
                 render_queue,
@@ -342,33 +364,64 @@ impl FromWorld for SsaoPipelines {
             ),
         );
 
-        let preprocess_depth_bind_group_layout = BindGroupLayoutDescriptor::new(
-            "ssao_preprocess_depth_bind_group_layout",
+        let preprocess_depth_bind_group_layout = if preprocessed_depth_mip_count == 5 {
+            BindGroupLayoutDescriptor::new(
+                "ssao_preprocess_depth_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        texture_depth_2d(),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                    ),
+                ),
+            )
+        } else {
+            BindGroupLayoutDescriptor::new(
+                "ssao_preprocess_depth_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        texture_depth_2d(),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                        texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                    ),
+                ),
+            )
+        };
+
+        let ssao_bind_group_layout = BindGroupLayoutDescriptor::new(
+            "ssao_ssao_bind_group_layout",
             &BindGroupLayoutEntries::sequential(
                 ShaderStages::COMPUTE,
                 (
-                    texture_depth_2d(),
-                    texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
-                    texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
-                    texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
-                    texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Uint),
                     texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
+                    texture_storage_2d(TextureFormat::R32Uint, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<GlobalsUniform>(false),
+                    uniform_buffer::<f32>(false),
                 ),
             ),
         );
 
-        let ssao_bind_group_layout = BindGroupLayoutDescriptor::new(
-            "ssao_ssao_bind_group_layout",
+        let ssao_depth_pyramid_bind_group_layout = BindGroupLayoutDescriptor::new(
+            "ssao_ssao_depth_pyramid_bind_group_layout",
             &BindGroupLayoutEntries::sequential(
                 ShaderStages::COMPUTE,
                 (
-                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
                     texture_2d(TextureSampleType::Float { filterable: false }),
                     texture_2d(TextureSampleType::Uint),
                     texture_storage_2d(depth_format, StorageTextureAccess::WriteOnly),
                     texture_storage_2d(TextureFormat::R32Uint, StorageTextureAccess::WriteOnly),
                     uniform_buffer::<GlobalsUniform>(false),
                     uniform_buffer::<f32>(false),
+                    texture_depth_2d(),
                 ),
             ),
         );
@@ -385,7 +438,10 @@ impl FromWorld for SsaoPipelines {
             ),
         );
 
-        let mut shader_defs = Vec::new();
+        let mut shader_defs = vec![ShaderDefVal::Int(
+            "PREPROCESSED_DEPTH_MIP_COUNT".to_string(),
+            preprocessed_depth_mip_count as i32,
+        )];
         if depth_format == TextureFormat::R16Float {
             shader_defs.push("USE_R16FLOAT".into());
         }
@@ -421,6 +477,7 @@ impl FromWorld for SsaoPipelines {
             common_bind_group_layout,
             preprocess_depth_bind_group_layout,
             ssao_bind_group_layout,
+            ssao_depth_pyramid_bind_group_layout,
             spatial_denoise_bind_group_layout,
 
             hilbert_index_lut,
@@ -429,6 +486,7 @@ impl FromWorld for SsaoPipelines {
 
             shader: load_embedded_asset!(world, "ssao.wgsl"),
             depth_format,
+            preprocessed_depth_mip_count,
         }
     }
 }
@@ -437,6 +495,7 @@ impl FromWorld for SsaoPipelines {
 struct SsaoPipelineKey {
     quality_level: ScreenSpaceAmbientOcclusionQualityLevel,
     temporal_jitter: bool,
+    depth_pyramid: bool,
 }
 
 impl SpecializedComputePipeline for SsaoPipelines {
@@ -461,10 +520,26 @@ impl SpecializedComputePipeline for SsaoPipelines {
             shader_defs.push("USE_R16FLOAT".into());
         }
 
+        let ssao_bind_group_layout = if key.depth_pyramid {
+            shader_defs.push("DEPTH_PYRAMID".into());
+            // The depth pyramid has all the MIP levels we need.
+            shader_defs.push(ShaderDefVal::Int(
+                "PREPROCESSED_DEPTH_MIP_COUNT".to_string(),
+                5,
+            ));
+            &self.ssao_depth_pyramid_bind_group_layout
+        } else {
+            shader_defs.push(ShaderDefVal::Int(
+                "PREPROCESSED_DEPTH_MIP_COUNT".to_string(),
+                self.preprocessed_depth_mip_count as i32,
+            ));
+            &self.ssao_bind_group_layout
+        };
+
         ComputePipelineDescriptor {
             label: Some("ssao_ssao_pipeline".into()),
             layout: vec![
-                self.ssao_bind_group_layout.clone(),
+                ssao_bind_group_layout.clone(),
                 self.common_bind_group_layout.clone(),
             ],
             shader: self.shader.clone(),
@@ -504,7 +579,8 @@ fn extract_ssao_settings(
 
 #[derive(Component)]
 pub struct ScreenSpaceAmbientOcclusionResources {
-    preprocessed_depth_texture: CachedTexture,
+    /// The depth hierarchy generated by SSAO, if it doesn't share the view's depth pyramid.
+    preprocessed_depth_texture: Option<CachedTexture>,
     ssao_noisy_texture: CachedTexture, // Pre-spatially denoised texture
     pub screen_space_ambient_occlusion_texture: CachedTexture, // Spatially denoised texture
     depth_differences_texture: CachedTexture,
@@ -516,27 +592,37 @@ fn prepare_ssao_textures(
     mut texture_cache: ResMut<TextureCache>,
     render_device: Res<RenderDevice>,
     pipelines: Res<SsaoPipelines>,
-    views: Query<(Entity, &ExtractedCamera, &ScreenSpaceAmbientOcclusion)>,
+    downsample_depth_pipelines: Option<Res<DownsampleDepthPipelines>>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ScreenSpaceAmbientOcclusion,
+        Has<DepthPyramidPrepass>,
+    )>,
 ) {
-    for (entity, camera, ssao_settings) in &views {
+    for (entity, camera, ssao_settings, depth_pyramid_prepass) in &views {
         let Some(physical_viewport_size) = camera.physical_viewport_size else {
             continue;
         };
         let size = physical_viewport_size.to_extents();
 
-        let preprocessed_depth_texture = texture_cache.get(
-            &render_device,
-            TextureDescriptor {
-                label: Some("ssao_preprocessed_depth_texture"),
-                size,
-                mip_level_count: 5,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format: pipelines.depth_format,
-                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-        );
+        let preprocessed_depth_texture =
+            (!uses_depth_pyramid(depth_pyramid_prepass, downsample_depth_pipelines.as_deref()))
+                .then(|| {
+                    texture_cache.get(
+                        &render_device,
+                        TextureDescriptor {
+                            label: Some("ssao_preprocessed_depth_texture"),
+                            size,
+                            mip_level_count: pipelines.preprocessed_depth_mip_count,
+                            sample_count: 1,
+                            dimension: TextureDimension::D2,
+                            format: pipelines.depth_format,
+                            usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                            view_formats: &[],
+                        },
+                    )
+                });
 
         let ssao_noisy_texture = texture_cache.get(
             &render_device,
@@ -598,34 +684,59 @@ fn prepare_ssao_textures(
     }
 }
 
+/// Returns true if SSAO should sample the view's depth pyramid instead of preprocessing the depth.
+///
+/// The depth pyramid is only generated if the platform supports downsampling depth.
+fn uses_depth_pyramid(
+    depth_pyramid_prepass: bool,
+    downsample_depth_pipelines: Option<&DownsampleDepthPipelines>,
+) -> bool {
+    depth_pyramid_prepass && downsample_depth_pipelines.is_some()
+}
+
 #[derive(Component)]
-struct SsaoPipelineId(CachedComputePipelineId);
+struct SsaoPipelineId {
+    id: CachedComputePipelineId,
+    depth_pyramid: bool,
+}
 
 fn prepare_ssao_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedComputePipelines<SsaoPipelines>>,
     pipeline: Res<SsaoPipelines>,
-    views: Query<(Entity, &ScreenSpaceAmbientOcclusion, Has<TemporalJitter>)>,
+    downsample_depth_pipelines: Option<Res<DownsampleDepthPipelines>>,
+    views: Query<(
+        Entity,
+        &ScreenSpaceAmbientOcclusion,
+        Has<TemporalJitter>,
+        Has<DepthPyramidPrepass>,
+    )>,
 ) {
-    for (entity, ssao_settings, temporal_jitter) in &views {
+    for (entity, ssao_settings, temporal_jitter, depth_pyramid_prepass) in &views {
+        let depth_pyramid =
+            uses_depth_pyramid(depth_pyramid_prepass, downsample_depth_pipelines.as_deref());
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
             SsaoPipelineKey {
                 quality_level: ssao_settings.quality_level,
                 temporal_jitter,
+                depth_pyramid,
             },
         );
 
-        commands.entity(entity).insert(SsaoPipelineId(pipeline_id));
+        commands.entity(entity).insert(SsaoPipelineId {
+            id: pipeline_id,
+            depth_pyramid,
+        });
     }
 }
 
 #[derive(Component)]
 struct SsaoBindGroups {
     common_bind_group: BindGroup,
-    preprocess_depth_bind_group: BindGroup,
+    preprocess_depth_bind_group: Option<BindGroup>,
     ssao_bind_group: BindGroup,
     spatial_denoise_bind_group: BindGroup,
 }
@@ -641,6 +752,8 @@ fn prepare_ssao_bind_groups(
         Entity,
         &ScreenSpaceAmbientOcclusionResources,
         &ViewPrepassTextures,
+        &SsaoPipelineId,
+        Option<&ViewDepthPyramid>,
     )>,
 ) {
     let (Some(view_uniforms), Some(globals_uniforms)) = (
@@ -650,7 +763,7 @@ fn prepare_ssao_bind_groups(
         return;
     };
 
-    for (entity, ssao_resources, prepass_textures) in &views {
+    for (entity, ssao_resources, prepass_textures, pipeline_id, view_depth_pyramid) in &views {
         let common_bind_group = render_device.create_bind_group(
             "ssao_common_bind_group",
             &pipeline_cache.get_bind_group_layout(&pipelines.common_bind_group_layout),
@@ -661,47 +774,92 @@ fn prepare_ssao_bind_groups(
             )),
         );
 
-        let create_depth_view = |mip_level| {
-            ssao_resources
-                .preprocessed_depth_texture
-                .texture
-                .create_view(&TextureViewDescriptor {
-                    label: Some("ssao_preprocessed_depth_texture_mip_view"),
-                    base_mip_level: mip_level,
-                    format: Some(pipelines.depth_format),
-                    dimension: Some(TextureViewDimension::D2),
-                    mip_level_count: Some(1),
-                    ..default()
-                })
+        let (preprocess_depth_bind_group, ssao_bind_group) = match (
+            pipeline_id.depth_pyramid,
+            view_depth_pyramid,
+            &ssao_resources.preprocessed_depth_texture,
+        ) {
+            (true, Some(view_depth_pyramid), _) => {
+                let ssao_bind_group = render_device.create_bind_group(
+                    "ssao_ssao_bind_group",
+                    &pipeline_cache
+                        .get_bind_group_layout(&pipelines.ssao_depth_pyramid_bind_group_layout),
+                    &BindGroupEntries::sequential((
+                        &view_depth_pyramid.all_mips,
+                        prepass_textures.normal_view().unwrap(),
+                        &pipelines.hilbert_index_lut,
+                        &ssao_resources.ssao_noisy_texture.default_view,
+                        &ssao_resources.depth_differences_texture.default_view,
+                        globals_uniforms.clone(),
+                        ssao_resources.thickness_buffer.as_entire_binding(),
+                        prepass_textures.depth_view().unwrap(),
+                    )),
+                );
+                (None, ssao_bind_group)
+            }
+            (false, _, Some(preprocessed_depth_texture)) => {
+                let create_depth_view = |mip_level| {
+                    preprocessed_depth_texture
+                        .texture
+                        .create_view(&TextureViewDescriptor {
+                            label: Some("ssao_preprocessed_depth_texture_mip_view"),
+                            base_mip_level: mip_level,
+                            format: Some(pipelines.depth_format),
+                            dimension: Some(TextureViewDimension::D2),
+                            mip_level_count: Some(1),
+                            ..default()
+                        })
+                };
+
+                let preprocess_depth_layout = pipeline_cache
+                    .get_bind_group_layout(&pipelines.preprocess_depth_bind_group_layout);
+                let preprocess_depth_bind_group = if pipelines.preprocessed_depth_mip_count == 5 {
+                    render_device.create_bind_group(
+                        "ssao_preprocess_depth_bind_group",
+                        &preprocess_depth_layout,
+                        &BindGroupEntries::sequential((
+                            prepass_textures.depth_view().unwrap(),
+                            &create_depth_view(0),
+                            &create_depth_view(1),
+                            &create_depth_view(2),
+                            &create_depth_view(3),
+                            &create_depth_view(4),
+                        )),
+                    )
+                } else {
+                    render_device.create_bind_group(
+                        "ssao_preprocess_depth_bind_group",
+                        &preprocess_depth_layout,
+                        &BindGroupEntries::sequential((
+                            prepass_textures.depth_view().unwrap(),
+                            &create_depth_view(0),
+                            &create_depth_view(1),
+                        )),
+                    )
+                };
+
+                let ssao_bind_group = render_device.create_bind_group(
+                    "ssao_ssao_bind_group",
+                    &pipeline_cache.get_bind_group_layout(&pipelines.ssao_bind_group_layout),
+                    &BindGroupEntries::sequential((
+                        &preprocessed_depth_texture.default_view,
+                        prepass_textures.normal_view().unwrap(),
+                        &pipelines.hilbert_index_lut,
+                        &ssao_resources.ssao_noisy_texture.default_view,
+                        &ssao_resources.depth_differences_texture.default_view,
+                        globals_uniforms.clone(),
+                        ssao_resources.thickness_buffer.as_entire_binding(),
+                    )),
+                );
+                (Some(preprocess_depth_bind_group), ssao_bind_group)
+            }
+            // The view's depth pyramid or textures don't match the pipeline yet.
+            _ => {
+                commands.entity(entity).remove::<SsaoBindGroups>();
+                continue;
+            }
         };
 
-        let preprocess_depth_bind_group = render_device.create_bind_group(
-            "ssao_preprocess_depth_bind_group",
-            &pipeline_cache.get_bind_group_layout(&pipelines.preprocess_depth_bind_group_layout),
-            &BindGroupEntries::sequential((
-                prepass_textures.depth_view().unwrap(),
-                &create_depth_view(0),
-                &create_depth_view(1),
-                &create_depth_view(2),
-                &create_depth_view(3),
-                &create_depth_view(4),
-            )),
-        );
-
-        let ssao_bind_group = render_device.create_bind_group(
-            "ssao_ssao_bind_group",
-            &pipeline_cache.get_bind_group_layout(&pipelines.ssao_bind_group_layout),
-            &BindGroupEntries::sequential((
-                &ssao_resources.preprocessed_depth_texture.default_view,
-                prepass_textures.normal_view().unwrap(),
-                &pipelines.hilbert_index_lut,
-                &ssao_resources.ssao_noisy_texture.default_view,
-                &ssao_resources.depth_differences_texture.default_view,
-                globals_uniforms.clone(),
-                ssao_resources.thickness_buffer.as_entire_binding(),
-            )),
-        );
-
         let spatial_denoise_bind_group = render_device.create_bind_group(
             "ssao_spatial_denoise_bind_group",
             &pipeline_cache.get_bind_group_layout(&pipelines.spatial_denoise_bind_group_layout),
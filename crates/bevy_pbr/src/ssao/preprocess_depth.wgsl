@@ -11,16 +11,20 @@
 #ifdef USE_R16FLOAT
 @group(0) @binding(1) var preprocessed_depth_mip0: texture_storage_2d<r16float, write>;
 @group(0) @binding(2) var preprocessed_depth_mip1: texture_storage_2d<r16float, write>;
+#if PREPROCESSED_DEPTH_MIP_COUNT > 2
 @group(0) @binding(3) var preprocessed_depth_mip2: texture_storage_2d<r16float, write>;
 @group(0) @binding(4) var preprocessed_depth_mip3: texture_storage_2d<r16float, write>;
 @group(0) @binding(5) var preprocessed_depth_mip4: texture_storage_2d<r16float, write>;
+#endif
 #else
 @group(0) @binding(1) var preprocessed_depth_mip0: texture_storage_2d<r32float, write>;
 @group(0) @binding(2) var preprocessed_depth_mip1: texture_storage_2d<r32float, write>;
+#if PREPROCESSED_DEPTH_MIP_COUNT > 2
 @group(0) @binding(3) var preprocessed_depth_mip2: texture_storage_2d<r32float, write>;
 @group(0) @binding(4) var preprocessed_depth_mip3: texture_storage_2d<r32float, write>;
 @group(0) @binding(5) var preprocessed_depth_mip4: texture_storage_2d<r32float, write>;
 #endif
+#endif
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var linear_clamp_sampler: sampler;
 @group(1) @binding(2) var<uniform> view: View;
@@ -70,6 +74,8 @@ fn preprocess_depth(@builtin(global_invocation_id) global_id: vec3<u32>, @builti
     textureStore(preprocessed_depth_mip1, base_coordinates, vec4<f32>(depth_mip1, 0.0, 0.0, 0.0));
     previous_mip_depth[local_id.x][local_id.y] = depth_mip1;
 
+    // GPUs with fewer storage textures per shader stage only get the first two MIPs.
+#if PREPROCESSED_DEPTH_MIP_COUNT > 2
     workgroupBarrier();
 
     // MIP 2 - Weighted average of MIP 1's depth values (per invocation, 4x4 invocations per workgroup)
@@ -107,4 +113,5 @@ fn preprocess_depth(@builtin(global_invocation_id) global_id: vec3<u32>, @builti
         let depth_mip4 = weighted_average(depth0, depth1, depth2, depth3);
         textureStore(preprocessed_depth_mip4, base_coordinates / 8i, vec4<f32>(depth_mip4, 0.0, 0.0, 0.0));
     }
+#endif
 }
@@ -29,6 +29,11 @@
 @group(0) @binding(4) var depth_differences: texture_storage_2d<r32uint, write>;
 @group(0) @binding(5) var<uniform> globals: Globals;
 @group(0) @binding(6) var<uniform> thickness: f32;
+#ifdef DEPTH_PYRAMID
+// When sharing the view's depth pyramid, `preprocessed_depth` is the depth pyramid, and the full
+// resolution depth is read from the prepass.
+@group(0) @binding(7) var prepass_depth: texture_depth_2d;
+#endif
 @group(1) @binding(0) var point_clamp_sampler: sampler;
 @group(1) @binding(1) var linear_clamp_sampler: sampler;
 @group(1) @binding(2) var<uniform> view: View;
@@ -48,8 +53,13 @@ fn load_noise(pixel_coordinates: vec2<i32>) -> vec2<f32> {
 fn calculate_neighboring_depth_differences(pixel_coordinates: vec2<i32>) -> f32 {
     // Sample the pixel's depth and 4 depths around it
     let uv = vec2<f32>(pixel_coordinates) / view.viewport.zw;
+#ifdef DEPTH_PYRAMID
+    let depths_upper_left = textureGather(prepass_depth, point_clamp_sampler, uv);
+    let depths_bottom_right = textureGather(prepass_depth, point_clamp_sampler, uv, vec2<i32>(1i, 1i));
+#else
     let depths_upper_left = textureGather(0, preprocessed_depth, point_clamp_sampler, uv);
     let depths_bottom_right = textureGather(0, preprocessed_depth, point_clamp_sampler, uv, vec2<i32>(1i, 1i));
+#endif
     let depth_center = depths_upper_left.y;
     let depth_left = depths_upper_left.x;
     let depth_top = depths_upper_left.z;
@@ -92,7 +102,20 @@ fn reconstruct_view_space_position(depth: f32, uv: vec2<f32>) -> vec3<f32> {
 }
 
 fn load_and_reconstruct_view_space_position(uv: vec2<f32>, sample_mip_level: f32) -> vec3<f32> {
+#ifdef DEPTH_PYRAMID
+    // The first level of the depth pyramid is the size of the viewport rounded down to a power of
+    // two, so its levels are offset by about one from the preprocessed depth. The depth pyramid
+    // holds the farthest depths and can't be filtered, which slightly reduces the quality of the
+    // far samples.
+    var depth: f32;
+    if sample_mip_level < 1.0 {
+        depth = textureSampleLevel(prepass_depth, point_clamp_sampler, uv, 0);
+    } else {
+        depth = textureSampleLevel(preprocessed_depth, point_clamp_sampler, uv, sample_mip_level - 1.0).r;
+    }
+#else
     let depth = textureSampleLevel(preprocessed_depth, linear_clamp_sampler, uv, sample_mip_level).r;
+#endif
     return reconstruct_view_space_position(depth, uv);
 }
 
@@ -182,7 +205,7 @@ fn ssao(@builtin(global_invocation_id) global_id: vec3<u32>) {
             let sample = s * sample_mul;
 
             // * view.viewport.zw gets us from [0, 1] to [0, viewport_size], which is needed for this to get the correct mip levels
-            let sample_mip_level = clamp(log2(length(sample * view.viewport.zw)) - 3.3, 0.0, 5.0); // https://github.com/GameTechDev/XeGTAO#memory-bandwidth-bottleneck
+            let sample_mip_level = clamp(log2(length(sample * view.viewport.zw)) - 3.3, 0.0, f32(#PREPROCESSED_DEPTH_MIP_COUNT)); // https://github.com/GameTechDev/XeGTAO#memory-bandwidth-bottleneck
             let sample_position_1 = load_and_reconstruct_view_space_position(uv + sample, sample_mip_level);
             let sample_position_2 = load_and_reconstruct_view_space_position(uv - sample, sample_mip_level);
 
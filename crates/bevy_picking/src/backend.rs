@@ -129,7 +129,7 @@ pub mod ray {
     //! Types and systems for constructing rays from cameras and pointers.
 
     use crate::backend::prelude::{PointerId, PointerLocation};
-    use bevy_camera::{Camera, RenderTarget};
+    use bevy_camera::{Camera, CoordinateSpaces, RenderTarget};
     use bevy_ecs::prelude::*;
     use bevy_math::Ray3d;
     use bevy_platform::collections::{hash_map::Iter, HashMap};
@@ -234,8 +234,8 @@ pub mod ray {
         if !pointer_loc.is_in_viewport(camera, render_target, primary_window_entity) {
             return None;
         }
-        camera
-            .viewport_to_world(camera_tfm, pointer_loc.position)
+        CoordinateSpaces::new(camera, camera_tfm)?
+            .logical_to_world_ray(pointer_loc.position)
             .ok()
     }
 }
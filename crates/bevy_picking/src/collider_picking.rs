@@ -0,0 +1,156 @@
+//! A [collider](bevy_math::collider) ray casting backend for [`bevy_picking`](crate).
+//!
+//! Entities with a [`PickingCollider`] are pickable using their collider shape, without needing a
+//! mesh. This lets picking share the same shapes as physics, audio occlusion, or gameplay ray
+//! queries, and is much cheaper than ray casting against meshes.
+//!
+//! Picking can be disabled for individual entities by adding [`Pickable::IGNORE`].
+//!
+//! ## Implementation Notes
+//!
+//! - The collider is placed using the translation and rotation of the entity's
+//!   [`GlobalTransform`]. Scale is ignored: scale the collider shape itself instead.
+//! - The `position` and `normal` reported in `HitData` are in world space.
+
+use crate::{
+    backend::{ray::RayMap, HitData, PointerHits},
+    prelude::*,
+    PickingSystems,
+};
+use bevy_app::prelude::*;
+use bevy_camera::{visibility::RenderLayers, Camera};
+use bevy_ecs::prelude::*;
+use bevy_math::collider::{Collider3d, RayCastable3d};
+use bevy_reflect::prelude::*;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// A collider used to pick this entity with the [`ColliderPickingPlugin`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+#[require(Transform)]
+pub struct PickingCollider(pub Collider3d);
+
+impl<T: Into<Collider3d>> From<T> for PickingCollider {
+    fn from(collider: T) -> Self {
+        Self(collider.into())
+    }
+}
+
+/// Adds the collider picking backend to your app.
+#[derive(Clone, Default)]
+pub struct ColliderPickingPlugin;
+
+impl Plugin for ColliderPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, update_hits.in_set(PickingSystems::Backend));
+    }
+}
+
+/// Casts rays against [`PickingCollider`]s and sends [`PointerHits`] events.
+pub fn update_hits(
+    ray_map: Res<RayMap>,
+    picking_cameras: Query<(&Camera, Option<&RenderLayers>)>,
+    colliders: Query<(
+        Entity,
+        &PickingCollider,
+        &GlobalTransform,
+        Option<&Pickable>,
+        Option<&RenderLayers>,
+    )>,
+    mut pointer_hits_writer: MessageWriter<PointerHits>,
+) {
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok((camera, cam_layers)) = picking_cameras.get(ray_id.camera) else {
+            continue;
+        };
+        let cam_layers = cam_layers.to_owned().unwrap_or_default();
+
+        let mut hits = colliders
+            .iter()
+            .filter(|(.., pickable, layers)| {
+                // Entities missing render layers are on the default layer 0
+                let entity_layers = layers.cloned().unwrap_or_default();
+                let render_layers_match = cam_layers.intersects(&entity_layers);
+                let is_pickable = pickable.is_none_or(|p| p.is_hoverable);
+                render_layers_match && is_pickable
+            })
+            .filter_map(|(entity, collider, transform, pickable, _)| {
+                let hit = collider
+                    .0
+                    .ray_cast(transform.to_isometry(), ray, f32::MAX)?;
+                let blocks_lower = pickable.is_none_or(|p| p.should_block_lower);
+                Some((entity, hit, blocks_lower))
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|(_, a, _), (_, b, _)| a.distance.total_cmp(&b.distance));
+
+        // Only keep the hits up to and including the first one blocking the entities below it.
+        if let Some(first_blocking) = hits.iter().position(|(.., blocks_lower)| *blocks_lower) {
+            hits.truncate(first_blocking + 1);
+        }
+
+        let picks = hits
+            .into_iter()
+            .map(|(entity, hit, _)| {
+                let hit_data = HitData::new(
+                    ray_id.camera,
+                    hit.distance,
+                    Some(ray.get_point(hit.distance)),
+                    Some(hit.normal.as_vec3()),
+                );
+                (entity, hit_data)
+            })
+            .collect::<Vec<_>>();
+        let order = camera.order as f32;
+        if !picks.is_empty() {
+            pointer_hits_writer.write(PointerHits::new(ray_id.pointer, picks, order));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::ray::RayId, pointer::PointerId};
+    use bevy_ecs::{message::Messages, system::RunSystemOnce};
+    use bevy_math::{primitives::Sphere, Dir3, Ray3d, Vec3};
+
+    #[test]
+    fn picks_closest_blocking_collider() {
+        let mut world = World::new();
+        world.init_resource::<RayMap>();
+        world.init_resource::<Messages<PointerHits>>();
+
+        let camera = world.spawn(Camera::default()).id();
+        let spawn_sphere = |world: &mut World, z: f32| {
+            world
+                .spawn((
+                    PickingCollider::from(Sphere::new(1.0)),
+                    GlobalTransform::from_translation(Vec3::new(0.0, 0.0, z)),
+                ))
+                .id()
+        };
+        let near = spawn_sphere(&mut world, -5.0);
+        spawn_sphere(&mut world, -10.0);
+        let non_blocking = spawn_sphere(&mut world, -2.0);
+        world.entity_mut(non_blocking).insert(Pickable {
+            should_block_lower: false,
+            is_hoverable: true,
+        });
+
+        world.resource_mut::<RayMap>().map.insert(
+            RayId::new(camera, PointerId::Mouse),
+            Ray3d::new(Vec3::ZERO, Dir3::NEG_Z),
+        );
+        world.run_system_once(update_hits).unwrap();
+
+        let messages = world.resource::<Messages<PointerHits>>();
+        let hits = messages.iter_current_update_messages().next().unwrap();
+        let entities = hits.picks.iter().map(|(e, _)| *e).collect::<Vec<_>>();
+        assert_eq!(entities, [non_blocking, near]);
+        let hit = &hits.picks[1].1;
+        assert_eq!(hit.depth, 4.0);
+        assert_eq!(hit.position, Some(Vec3::new(0.0, 0.0, -4.0)));
+        assert_eq!(hit.normal, Some(Vec3::Z));
+    }
+}
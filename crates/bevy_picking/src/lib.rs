@@ -155,6 +155,7 @@
 extern crate alloc;
 
 pub mod backend;
+pub mod collider_picking;
 pub mod events;
 pub mod hover;
 pub mod input;
@@ -180,8 +181,11 @@ pub mod prelude {
     };
     #[doc(hidden)]
     pub use crate::{
-        events::*, input::PointerInputPlugin, pointer::PointerButton, DefaultPickingPlugins,
-        InteractionPlugin, Pickable, PickingPlugin,
+        collider_picking::{ColliderPickingPlugin, PickingCollider},
+        events::*,
+        input::PointerInputPlugin,
+        pointer::PointerButton,
+        DefaultPickingPlugins, InteractionPlugin, Pickable, PickingPlugin,
     };
 }
 
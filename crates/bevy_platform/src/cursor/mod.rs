@@ -0,0 +1,14 @@
+//! APIs that confine the cursor of the system to a region of the screen.
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::clip_cursor;
+
+/// Confines the cursor to a rectangle of the screen, given as `(left, top, right, bottom)` in
+/// physical pixels, or releases it with `None`. Returns `false` if the platform can't confine the
+/// cursor, which this version never can.
+#[cfg(not(target_os = "windows"))]
+pub fn clip_cursor(_rect: Option<(i32, i32, i32, i32)>) -> bool {
+    false
+}
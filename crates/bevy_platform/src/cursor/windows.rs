@@ -0,0 +1,24 @@
+extern crate windows_sys as windows;
+use windows::Win32::{Foundation::RECT, UI::WindowsAndMessaging::ClipCursor};
+
+/// Confines the cursor to a rectangle of the screen, given as `(left, top, right, bottom)` in
+/// physical pixels, or releases it with `None`. Returns `false` if the cursor couldn't be confined.
+///
+/// The cursor is shared by all the apps, and Windows releases it when another window is activated,
+/// so it should be confined again while the window has the focus.
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+pub fn clip_cursor(rect: Option<(i32, i32, i32, i32)>) -> bool {
+    let rect = rect.map(|(left, top, right, bottom)| RECT {
+        left,
+        top,
+        right,
+        bottom,
+    });
+    // SAFETY: The rectangle outlives the call, and a null pointer releases the cursor.
+    unsafe {
+        ClipCursor(
+            rect.as_ref()
+                .map_or(core::ptr::null(), |rect| rect as *const RECT),
+        ) != 0
+    }
+}
@@ -0,0 +1,22 @@
+//! APIs that return the keyboard layout of the system, e.g. to show the keys of a keybinding as
+//! they are labeled on the keyboard of the user.
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::{keyboard_layout, scan_code_character};
+
+/// Returns an identifier of the current keyboard layout of the native `window`, which changes when
+/// the user switches layouts. This version always returns `None`.
+#[cfg(not(target_os = "windows"))]
+pub fn keyboard_layout(_window: isize) -> Option<u64> {
+    None
+}
+
+/// Returns the character typed without modifiers by the key with the platform `scan_code`, in the
+/// keyboard `layout` returned by [`keyboard_layout`], if it is printable. This version always
+/// returns `None`.
+#[cfg(not(target_os = "windows"))]
+pub fn scan_code_character(_layout: u64, _scan_code: u32) -> Option<char> {
+    None
+}
@@ -0,0 +1,50 @@
+extern crate windows_sys as windows;
+use windows::Win32::UI::{
+    Input::KeyboardAndMouse::{
+        GetKeyboardLayout, MapVirtualKeyExW, MAPVK_VK_TO_CHAR, MAPVK_VSC_TO_VK_EX,
+    },
+    WindowsAndMessaging::GetWindowThreadProcessId,
+};
+
+/// The bit set by `MapVirtualKeyExW` on the characters of dead keys.
+const DEAD_KEY_BIT: u32 = 1 << 31;
+
+/// Returns the handle of the keyboard layout of the thread of the `window`, as an identifier.
+///
+/// Keyboard layouts are per thread on Windows, and the systems of the app don't run on the thread
+/// of its windows, so the layout of the thread owning the `window` of the app is used. `window` is
+/// the `HWND` of the window.
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+pub fn keyboard_layout(window: isize) -> Option<u64> {
+    // SAFETY: These functions only read the state of the system. An invalid window gives the
+    // thread `0`, which is checked.
+    let layout = unsafe {
+        let thread = GetWindowThreadProcessId(window as _, core::ptr::null_mut());
+        if thread == 0 {
+            return None;
+        }
+        GetKeyboardLayout(thread)
+    };
+    Some(layout as usize as u64)
+}
+
+/// Returns the character typed without modifiers by the key with the `scan_code`, in the keyboard
+/// `layout` returned by [`keyboard_layout`]. Extended keys have the `0xE0` prefix, e.g. `0xE01D`.
+///
+/// Letters are returned in lowercase, and dead keys return their accent. Keys typing whitespace or
+/// control characters, like the space bar, return `None`.
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+pub fn scan_code_character(layout: u64, scan_code: u32) -> Option<char> {
+    let layout = layout as usize as _;
+    // SAFETY: These functions only read the state of the system.
+    let character = unsafe {
+        let virtual_key = MapVirtualKeyExW(scan_code, MAPVK_VSC_TO_VK_EX, layout);
+        if virtual_key == 0 {
+            return None;
+        }
+        MapVirtualKeyExW(virtual_key, MAPVK_VK_TO_CHAR, layout)
+    };
+    char::from_u32(character & !DEAD_KEY_BIT)
+        .filter(|character| !character.is_control() && !character.is_whitespace())
+        .map(|character| character.to_lowercase().next().unwrap_or(character))
+}
@@ -12,7 +12,12 @@
 cfg::std! {
     extern crate std;
 
+    pub mod cursor;
     pub mod dirs;
+    pub mod keyboard;
+    #[cfg(feature = "on_screen_keyboard")]
+    pub mod on_screen_keyboard;
+    pub mod power;
 }
 
 cfg::alloc! {
@@ -0,0 +1,22 @@
+//! APIs that show and hide the on-screen keyboard of the system, e.g. the touch keyboard of
+//! Windows tablets, for a window.
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::{occluded_rect, set_visible};
+
+/// Shows or hides the on-screen keyboard for the window with the native handle `window`. Returns
+/// `false` if the keyboard couldn't be shown or hidden, which this version never can.
+#[cfg(not(target_os = "windows"))]
+pub fn set_visible(_window: isize, _visible: bool) -> bool {
+    false
+}
+
+/// Returns the rectangle of the screen covered by the on-screen keyboard of the window with the
+/// native handle `window`, given as `(x, y, width, height)` in logical pixels, or `None` while it
+/// is hidden. This version always returns `None`.
+#[cfg(not(target_os = "windows"))]
+pub fn occluded_rect(_window: isize) -> Option<(f32, f32, f32, f32)> {
+    None
+}
@@ -0,0 +1,34 @@
+use winrt::{
+    core::factory,
+    Win32::{Foundation::HWND, System::WinRT::IInputPaneInterop},
+    UI::ViewManagement::InputPane,
+};
+
+/// Shows or hides the touch keyboard for the window with the native handle `window`, through its
+/// `InputPane`. Returns `false` if the keyboard couldn't be shown or hidden.
+pub fn set_visible(window: isize, visible: bool) -> bool {
+    let Some(input_pane) = input_pane(window) else {
+        return false;
+    };
+    let result = if visible {
+        input_pane.TryShow()
+    } else {
+        input_pane.TryHide()
+    };
+    result.unwrap_or(false)
+}
+
+/// Returns the rectangle of the screen covered by the touch keyboard of the window with the native
+/// handle `window`, given as `(x, y, width, height)` in logical pixels, or `None` while it is
+/// hidden.
+pub fn occluded_rect(window: isize) -> Option<(f32, f32, f32, f32)> {
+    let rect = input_pane(window)?.OccludedRect().ok()?;
+    (rect.Width > 0.0 && rect.Height > 0.0).then_some((rect.X, rect.Y, rect.Width, rect.Height))
+}
+
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+fn input_pane(window: isize) -> Option<InputPane> {
+    let interop = factory::<InputPane, IInputPaneInterop>().ok()?;
+    // SAFETY: `GetForWindow` only fails on handles that aren't windows of the current process.
+    unsafe { interop.GetForWindow(HWND(window as *mut _)) }.ok()
+}
@@ -0,0 +1,81 @@
+use super::PowerStatus;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use std::{fs, path::Path};
+
+/// Reads a sysfs attribute, without its trailing newline.
+fn read_attribute(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+/// Returns the power state of the system, from the power supplies listed in
+/// `/sys/class/power_supply`, the ACPI platform profile and the thermal zones.
+pub fn power_status() -> Option<PowerStatus> {
+    let supplies = fs::read_dir("/sys/class/power_supply").ok()?;
+    let mut status = PowerStatus::default();
+    let mut mains_online = false;
+    let mut discharging = false;
+    for supply in supplies.flatten() {
+        let path = supply.path();
+        match read_attribute(&path.join("type")).as_deref() {
+            Some("Mains") => {
+                mains_online |= read_attribute(&path.join("online")).as_deref() == Some("1");
+            }
+            // Batteries of peripherals like mice have `scope` set to `Device`.
+            Some("Battery") if read_attribute(&path.join("scope")).as_deref() != Some("Device") => {
+                discharging |=
+                    read_attribute(&path.join("status")).as_deref() == Some("Discharging");
+                if let Some(capacity) = read_attribute(&path.join("capacity"))
+                    .and_then(|capacity| capacity.parse::<f32>().ok())
+                {
+                    status.battery_level = Some((capacity / 100.0).clamp(0.0, 1.0));
+                }
+            }
+            _ => {}
+        }
+    }
+    status.on_battery = discharging || (status.battery_level.is_some() && !mains_online);
+    status.battery_saver = read_attribute(Path::new("/sys/firmware/acpi/platform_profile"))
+        .is_some_and(|profile| profile == "low-power");
+    status.thermal_throttled = thermal_throttled();
+    Some(status)
+}
+
+/// Returns whether a thermal zone of `/sys/class/thermal` reached one of its passive trip points,
+/// above which the kernel slows the system down to cool it.
+fn thermal_throttled() -> bool {
+    let Ok(zones) = fs::read_dir("/sys/class/thermal") else {
+        return false;
+    };
+    let read_temperature =
+        |path: &Path| read_attribute(path).and_then(|temperature| temperature.parse::<i64>().ok());
+    zones
+        .flatten()
+        .filter(|zone| {
+            zone.file_name()
+                .to_string_lossy()
+                .starts_with("thermal_zone")
+        })
+        .any(|zone| {
+            let path = zone.path();
+            let Some(temperature) = read_temperature(&path.join("temp")) else {
+                return false;
+            };
+            (0..)
+                .map_while(|trip| {
+                    read_attribute(&path.join(format!("trip_point_{trip}_type")))
+                        .map(|kind| (trip, kind))
+                })
+                .any(|(trip, kind)| {
+                    kind == "passive"
+                        && read_temperature(&path.join(format!("trip_point_{trip}_temp")))
+                            .is_some_and(|trip_temperature| {
+                                trip_temperature > 0 && temperature >= trip_temperature
+                            })
+                })
+        })
+}
@@ -0,0 +1,34 @@
+//! APIs that return the power state of the system, e.g. whether it runs on battery.
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::power_status;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "linux")]
+pub use linux::power_status;
+
+/// The power state of the system, as returned by [`power_status`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PowerStatus {
+    /// Whether the system runs on battery, instead of being plugged in.
+    pub on_battery: bool,
+    /// The charge of the battery, from `0.0` to `1.0`, if the system has one.
+    pub battery_level: Option<f32>,
+    /// Whether the user enabled the battery saver, or a low power profile.
+    pub battery_saver: bool,
+    /// Whether the system slows down to cool down.
+    ///
+    /// On Windows, this is reported when the frequency of a processor is limited below its
+    /// maximum, which a power plan capping the processor state also does. On Linux, this is
+    /// reported when a thermal zone reaches a passive trip point.
+    pub thermal_throttled: bool,
+}
+
+/// Returns the power state of the system. This version always returns `None`.
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn power_status() -> Option<PowerStatus> {
+    None
+}
@@ -0,0 +1,71 @@
+extern crate windows_sys as windows;
+use super::PowerStatus;
+use alloc::vec;
+use windows::Win32::{
+    Foundation::{STATUS_BUFFER_TOO_SMALL, STATUS_SUCCESS},
+    System::Power::{
+        CallNtPowerInformation, GetSystemPowerStatus, ProcessorInformation,
+        PROCESSOR_POWER_INFORMATION, SYSTEM_POWER_STATUS,
+    },
+};
+
+/// The `ACLineStatus` of a system running on battery.
+const AC_LINE_OFFLINE: u8 = 0;
+/// The `BatteryFlag` of a system without battery.
+const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+/// The `BatteryLifePercent` of a battery with an unknown charge.
+const BATTERY_PERCENTAGE_UNKNOWN: u8 = 255;
+/// The `SystemStatusFlag` of a system with the battery saver on.
+const SYSTEM_STATUS_BATTERY_SAVER: u8 = 1;
+
+/// Returns the power state of the system.
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+pub fn power_status() -> Option<PowerStatus> {
+    // SAFETY: SYSTEM_POWER_STATUS is a plain struct that GetSystemPowerStatus fills in.
+    let status = unsafe {
+        let mut status: SYSTEM_POWER_STATUS = core::mem::zeroed();
+        if GetSystemPowerStatus(&mut status) == 0 {
+            return None;
+        }
+        status
+    };
+    let has_battery = status.BatteryFlag & BATTERY_FLAG_NO_BATTERY == 0;
+    Some(PowerStatus {
+        on_battery: status.ACLineStatus == AC_LINE_OFFLINE,
+        battery_level: (has_battery && status.BatteryLifePercent != BATTERY_PERCENTAGE_UNKNOWN)
+            .then(|| f32::from(status.BatteryLifePercent.min(100)) / 100.0),
+        battery_saver: status.SystemStatusFlag & SYSTEM_STATUS_BATTERY_SAVER != 0,
+        thermal_throttled: thermal_throttled(),
+    })
+}
+
+/// Returns whether the frequency of a processor is limited below its maximum, as when the system
+/// slows down to cool down. A power plan capping the processor state is reported the same way.
+#[expect(unsafe_code, reason = "Uses unsafe Windows API functions")]
+fn thermal_throttled() -> bool {
+    let mut len = std::thread::available_parallelism().map_or(1, |len| len.get());
+    loop {
+        let mut processors = vec![PROCESSOR_POWER_INFORMATION::default(); len];
+        // SAFETY: The output buffer is an array of `len` PROCESSOR_POWER_INFORMATION, which is what
+        // the ProcessorInformation level writes, and its size is passed along.
+        let status = unsafe {
+            CallNtPowerInformation(
+                ProcessorInformation,
+                core::ptr::null(),
+                0,
+                processors.as_mut_ptr().cast(),
+                (len * size_of::<PROCESSOR_POWER_INFORMATION>()) as u32,
+            )
+        };
+        match status {
+            STATUS_SUCCESS => {
+                return processors
+                    .iter()
+                    .any(|processor| processor.MhzLimit < processor.MaxMhz);
+            }
+            // Some processors may be hidden from the process by its affinity.
+            STATUS_BUFFER_TOO_SMALL if len < 4096 => len *= 2,
+            _ => return false,
+        }
+    }
+}
@@ -4,6 +4,15 @@ use syn::{parse_macro_input, parse_quote, DeriveInput, Path};
 
 pub fn derive_extract_component(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
+    extract_component_impls(&mut ast)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Generates the `SyncComponent` and `ExtractComponent` impls extracting the component by cloning it.
+pub(crate) fn extract_component_impls(
+    ast: &mut DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
     let bevy_render_path: Path = crate::bevy_render_path();
     let bevy_ecs_path: Path = bevy_macro_utils::BevyManifest::shared(|manifest| {
         manifest
@@ -24,10 +33,7 @@ pub fn derive_extract_component(input: TokenStream) -> TokenStream {
         .iter()
         .find(|a| a.path().is_ident("extract_component_filter"))
     {
-        let filter = match attr.parse_args::<syn::Type>() {
-            Ok(filter) => filter,
-            Err(e) => return e.to_compile_error().into(),
-        };
+        let filter = attr.parse_args::<syn::Type>()?;
 
         quote! {
             #filter
@@ -38,7 +44,7 @@ pub fn derive_extract_component(input: TokenStream) -> TokenStream {
         }
     };
 
-    TokenStream::from(quote! {
+    Ok(quote! {
         impl #impl_generics #bevy_render_path::sync_component::SyncComponent for #struct_name #type_generics #where_clause {
             type Out = Self;
         }
@@ -0,0 +1,50 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Ident, Path};
+
+use crate::extract_component::extract_component_impls;
+
+const GPU_COMPONENT_ATTRIBUTE_NAME: &str = "gpu_component";
+
+pub fn derive_gpu_component(input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as DeriveInput);
+    impl_gpu_component(&mut ast)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn impl_gpu_component(ast: &mut DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let bevy_render_path: Path = crate::bevy_render_path();
+
+    let mut buffer = quote! { Uniform };
+    for attr in &ast.attrs {
+        if !attr.path().is_ident(GPU_COMPONENT_ATTRIBUTE_NAME) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let Some(ident) = meta.path.get_ident() else {
+                return Err(meta.error("expected `uniform` or `array`"));
+            };
+            buffer = match ident.to_string().as_str() {
+                "uniform" => quote! { Uniform },
+                "array" => quote! { Array },
+                _ => return Err(meta.error("expected `uniform` or `array`")),
+            };
+            Ok(())
+        })?;
+    }
+
+    let extract_component_impls = extract_component_impls(ast)?;
+
+    let struct_name: &Ident = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    Ok(quote! {
+        #extract_component_impls
+
+        impl #impl_generics #bevy_render_path::gpu_component::GpuComponent for #struct_name #type_generics #where_clause {
+            const BUFFER: #bevy_render_path::gpu_component::GpuComponentBuffer =
+                #bevy_render_path::gpu_component::GpuComponentBuffer::#buffer;
+        }
+    })
+}
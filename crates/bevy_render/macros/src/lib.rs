@@ -4,6 +4,7 @@
 mod as_bind_group;
 mod extract_component;
 mod extract_resource;
+mod gpu_component;
 mod specializer;
 
 use bevy_macro_utils::{derive_label, BevyManifest};
@@ -56,6 +57,41 @@ pub fn derive_extract_component(input: TokenStream) -> TokenStream {
     extract_component::derive_extract_component(input)
 }
 
+/// Implements `GpuComponent` for a component, uploading it to the GPU for each entity.
+///
+/// The component must implement [`Clone`] and `ShaderType`. Like the [`ExtractComponent`]
+/// derive, this implements `ExtractComponent` by cloning the component, and supports the
+/// `extract_component_filter` attribute. Don't derive both.
+///
+/// By default, the components are stored in a dynamic uniform buffer. Use `#[gpu_component(array)]`
+/// to store them in a `GpuArrayBuffer` instead.
+/// See `GpuComponentPlugin` to actually perform the extraction and upload.
+///
+/// # Example
+///
+/// ```no_compile
+/// use bevy_ecs::component::Component;
+/// use bevy_render::render_resource::ShaderType;
+/// use bevy_render_macros::GpuComponent;
+///
+/// #[derive(Component, Clone, ShaderType, GpuComponent)]
+/// pub struct Wobble {
+///     pub amplitude: f32,
+///     pub frequency: f32,
+/// }
+///
+/// #[derive(Component, Clone, ShaderType, GpuComponent)]
+/// #[gpu_component(array)]
+/// #[extract_component_filter(With<Mesh3d>)]
+/// pub struct Tint {
+///     pub color: Vec4,
+/// }
+/// ```
+#[proc_macro_derive(GpuComponent, attributes(gpu_component, extract_component_filter))]
+pub fn derive_gpu_component(input: TokenStream) -> TokenStream {
+    gpu_component::derive_gpu_component(input)
+}
+
 #[proc_macro_derive(
     AsBindGroup,
     attributes(
@@ -0,0 +1,174 @@
+//! Detects adapters being added or removed while the app runs.
+
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use core::time::Duration;
+use wgpu::AdapterInfo;
+#[cfg(not(target_family = "wasm"))]
+use {
+    crate::{
+        renderer::{RenderAdapterInfo, RenderInstance},
+        Render, RenderApp, RenderSystems,
+    },
+    bevy_platform::time::Instant,
+};
+
+/// Detects adapters being added or removed, e.g. when an external GPU is plugged in or unplugged,
+/// or when a laptop is docked, and writes them as [`AdapterChanged`] messages in the main world.
+///
+/// Adapter changes aren't errors, so they aren't reported to the
+/// [`RenderErrorHandler`](crate::error_handler::RenderErrorHandler). To move the renderer to an
+/// added adapter, call [`RenderControl::migrate_adapter`](crate::error_handler::RenderControl::migrate_adapter).
+/// When the adapter in use is removed, its device is lost, which is reported as a
+/// [`RenderErrorCode::DeviceRemoved`](crate::error_handler::RenderErrorCode::DeviceRemoved)
+/// error, and the default handler recreates it on one of the remaining adapters.
+///
+/// The adapters of the backend in use are listed again on a background thread every
+/// [`poll_interval`](Self::poll_interval), with the [`RenderInstance`](crate::renderer::RenderInstance)
+/// of the renderer, so that the drivers aren't loaded again by each poll. This plugin does nothing
+/// on the web.
+pub struct AdapterChangePlugin {
+    /// How often the available adapters are listed.
+    pub poll_interval: Duration,
+}
+
+impl Default for AdapterChangePlugin {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// The adapters that were added or removed while the app was running, written by the
+/// [`AdapterChangePlugin`] in the main world.
+#[derive(Message, Debug, Clone, Default)]
+pub struct AdapterChanged {
+    /// The adapters that became available.
+    pub added: Vec<AdapterInfo>,
+    /// The adapters that aren't available anymore.
+    pub removed: Vec<AdapterInfo>,
+    /// Whether the adapter in use by the renderer was removed. Its device is lost, which is
+    /// reported separately as a
+    /// [`RenderErrorCode::DeviceRemoved`](crate::error_handler::RenderErrorCode::DeviceRemoved)
+    /// error.
+    pub current_removed: bool,
+}
+
+impl Plugin for AdapterChangePlugin {
+    #[cfg_attr(
+        target_family = "wasm",
+        expect(unused_variables, reason = "adapters can't be listed on the web")
+    )]
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::unbounded();
+        app.add_message::<AdapterChanged>()
+            .insert_resource(AdapterChangeReceiver(receiver))
+            .add_systems(First, write_adapter_changes);
+
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(AdapterWatcher {
+                    poll_interval: self.poll_interval,
+                    adapters: None,
+                    task: None,
+                    last_poll: None,
+                    sender,
+                })
+                .add_systems(Render, watch_adapters.in_set(RenderSystems::Cleanup));
+        }
+    }
+}
+
+/// Receives the [`AdapterChanged`] messages detected in the render world.
+#[derive(Resource)]
+struct AdapterChangeReceiver(async_channel::Receiver<AdapterChanged>);
+
+fn write_adapter_changes(
+    receiver: Res<AdapterChangeReceiver>,
+    mut changes: MessageWriter<AdapterChanged>,
+) {
+    while let Ok(change) = receiver.0.try_recv() {
+        changes.write(change);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource)]
+struct AdapterWatcher {
+    poll_interval: Duration,
+    /// The adapters found by the last poll.
+    adapters: Option<Vec<AdapterInfo>>,
+    task: Option<bevy_tasks::Task<Vec<AdapterInfo>>>,
+    last_poll: Option<Instant>,
+    sender: async_channel::Sender<AdapterChanged>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn watch_adapters(
+    mut watcher: ResMut<AdapterWatcher>,
+    adapter_info: Res<RenderAdapterInfo>,
+    instance: Res<RenderInstance>,
+) {
+    let watcher = &mut *watcher;
+    let current_adapter: &AdapterInfo = &adapter_info;
+    let Some(task) = &mut watcher.task else {
+        let now = Instant::now();
+        if watcher
+            .last_poll
+            .is_none_or(|last_poll| now >= last_poll + watcher.poll_interval)
+        {
+            watcher.last_poll = Some(now);
+            let backends = wgpu::Backends::from(adapter_info.backend);
+            let instance = instance.0.clone();
+            watcher.task = Some(bevy_tasks::AsyncComputeTaskPool::get().spawn(async move {
+                instance
+                    .enumerate_adapters(backends)
+                    .await
+                    .iter()
+                    .map(wgpu::Adapter::get_info)
+                    .collect()
+            }));
+        }
+        return;
+    };
+    let Some(adapters) = bevy_tasks::futures::check_ready(task) else {
+        return;
+    };
+    watcher.task = None;
+
+    let Some(previous) = watcher.adapters.replace(adapters.clone()) else {
+        return;
+    };
+    let change = AdapterChanged {
+        added: adapters
+            .iter()
+            .filter(|adapter| !previous.contains(adapter))
+            .cloned()
+            .collect(),
+        removed: previous
+            .iter()
+            .filter(|adapter| !adapters.contains(adapter))
+            .cloned()
+            .collect(),
+        current_removed: !adapters.contains(current_adapter),
+    };
+    if change.added.is_empty() && change.removed.is_empty() {
+        return;
+    }
+
+    let names = |adapters: &[AdapterInfo]| {
+        adapters
+            .iter()
+            .map(|adapter| adapter.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    bevy_log::info!(
+        "Adapters changed. Added: {:?}, removed: {:?}",
+        names(&change.added),
+        names(&change.removed)
+    );
+    // The receiver lives as long as the main world.
+    let _ = watcher.sender.try_send(change);
+}
@@ -18,7 +18,7 @@ use wgpu::{
 
 use crate::renderer::{RenderAdapterInfo, RenderDevice, RenderQueue, WgpuWrapper};
 
-use super::RecordDiagnostics;
+use super::{RecordDiagnostics, RenderDiagnosticsPlugin};
 
 // buffer offset must be divisible by 256, so this constant must be divisible by 32 (=256/8)
 const MAX_TIMESTAMP_QUERIES: u32 = 256;
@@ -593,6 +593,7 @@ impl FrameData {
             .collect::<Vec<u64>>();
 
         let mut diagnostics = Vec::new();
+        let mut frame_timestamps: Option<(u64, u64)> = None;
 
         for span in &self.closed_spans {
             if let (Some(begin), Some(end)) = (span.begin_instant, span.end_instant) {
@@ -605,8 +606,14 @@ impl FrameData {
 
             if let (Some(begin), Some(end)) = (span.begin_timestamp_index, span.end_timestamp_index)
             {
-                let begin = timestamps[begin as usize] as f64;
-                let end = timestamps[end as usize] as f64;
+                let (begin, end) = (timestamps[begin as usize], timestamps[end as usize]);
+                frame_timestamps = Some(match frame_timestamps {
+                    Some((first, last)) => (first.min(begin), last.max(end)),
+                    None => (begin, end),
+                });
+
+                let begin = begin as f64;
+                let end = end as f64;
                 let value = (end - begin) * (timestamp_period_ns as f64) / 1e6;
 
                 #[cfg(feature = "tracing-tracy")]
@@ -668,6 +675,14 @@ impl FrameData {
             }
         }
 
+        if let Some((first, last)) = frame_timestamps {
+            diagnostics.push(RenderDiagnostic {
+                path: RenderDiagnosticsPlugin::ELAPSED_GPU,
+                suffix: "ms",
+                value: (last - first) as f64 * (timestamp_period_ns as f64) / 1e6,
+            });
+        }
+
         for (buffer, diagnostic_path, is_f32) in self.value_buffers.drain(..) {
             let buffer = buffer.get_mapped_range(..);
             diagnostics.push(RenderDiagnostic {
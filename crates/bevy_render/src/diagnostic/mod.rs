@@ -10,6 +10,7 @@ mod render_asset_diagnostic_plugin;
 mod tracy_gpu;
 
 use alloc::{borrow::Cow, sync::Arc};
+use bevy_diagnostic::DiagnosticPath;
 use bevy_ecs::{
     schedule::IntoScheduleConfigs,
     system::{Res, ResMut},
@@ -56,12 +57,20 @@ use crate::renderer::{RenderDevice, RenderQueue};
 ///     time_span.end(render_context.command_encoder());
 ///     ```
 ///
+/// The GPU time of the whole frame, from the start of the first pass to the end of the last one,
+/// is recorded as [`RenderDiagnosticsPlugin::ELAPSED_GPU`].
+///
 /// # Supported platforms
 /// Timestamp queries and pipeline statistics are currently supported only on Vulkan and DX12.
 /// On other platforms (Metal, WebGPU, WebGL2) only CPU time will be recorded.
 #[derive(Default)]
 pub struct RenderDiagnosticsPlugin;
 
+impl RenderDiagnosticsPlugin {
+    /// The GPU time of the frame, in milliseconds.
+    pub const ELAPSED_GPU: DiagnosticPath = DiagnosticPath::const_new("render/elapsed_gpu");
+}
+
 impl Plugin for RenderDiagnosticsPlugin {
     fn build(&self, app: &mut App) {
         let render_diagnostics_mutex = RenderDiagnosticsMutex::default();
@@ -1,6 +1,6 @@
 use crate::{
-    render_resource::AsBindGroupError, ExtractSchedule, MainWorld, Render, RenderApp,
-    RenderSystems, Res,
+    error_handler::RenderDeviceGeneration, render_resource::AsBindGroupError, ExtractSchedule,
+    MainWorld, Render, RenderApp, RenderSystems, Res,
 };
 use bevy_app::{App, Plugin, SubApp};
 use bevy_asset::RenderAssetUsages;
@@ -230,12 +230,17 @@ struct CachedExtractErasedRenderAssetSystemState<A: ErasedRenderAsset> {
         MessageReader<'static, 'static, AssetEvent<A::SourceAsset>>,
         ResMut<'static, Assets<A::SourceAsset>>,
     )>,
+    device_generation: RenderDeviceGeneration,
 }
 
 impl<A: ErasedRenderAsset> FromWorld for CachedExtractErasedRenderAssetSystemState<A> {
     fn from_world(world: &mut bevy_ecs::world::World) -> Self {
         Self {
             state: SystemState::new(world),
+            device_generation: world
+                .get_resource::<RenderDeviceGeneration>()
+                .copied()
+                .unwrap_or_default(),
         }
     }
 }
@@ -248,9 +253,21 @@ pub(crate) fn extract_erased_render_asset<A: ErasedRenderAsset>(
 ) {
     main_world.resource_scope(
         |world, mut cached_state: Mut<CachedExtractErasedRenderAssetSystemState<A>>| {
+            let device_generation = world
+                .get_resource::<RenderDeviceGeneration>()
+                .copied()
+                .unwrap_or_default();
+            let device_recreated = cached_state.device_generation != device_generation;
+            cached_state.device_generation = device_generation;
             let (mut events, mut assets) = cached_state.state.get_mut(world);
 
             let mut needs_extracting = <HashSet<_>>::default();
+            if device_recreated {
+                // The GPU representations of the assets were created on the previous device, so
+                // every asset is extracted again.
+                needs_extracting.extend(assets.ids());
+                commands.insert_resource(ErasedRenderAssets::<A::ErasedAsset>::default());
+            }
             let mut removed = <HashSet<_>>::default();
             let mut modified = <HashSet<_>>::default();
 
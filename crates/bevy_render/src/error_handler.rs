@@ -1,3 +1,4 @@
+use alloc::boxed::Box;
 use alloc::sync::Arc;
 use bevy_ecs::{
     resource::Resource,
@@ -10,9 +11,11 @@ use wgpu_types::error::ErrorType;
 use crate::{
     insert_future_resources,
     render_resource::PipelineCache,
-    renderer::{RenderDevice, WgpuWrapper},
-    settings::RenderCreation,
-    FutureRenderResources, RenderStartup,
+    renderer::{RenderAdapterInfo, RenderDevice, RenderInitializationError, WgpuWrapper},
+    settings::{AdapterSelector, Backends, RenderCreation, RenderResources, WgpuSettings},
+    view::{ExtractedWindows, WindowSurfaces},
+    wgpu_trace::update_trace_capture,
+    FutureRenderResources, RenderDeviceReset, RenderStartup,
 };
 
 /// Resource to indicate renderer behavior upon error.
@@ -22,56 +25,366 @@ pub enum RenderErrorPolicy {
     Ignore,
     /// Keeps the app alive, but stops rendering further.
     /// This keeps the error state, and will continue polling the [`RenderErrorHandler`]
-    /// every frame until some other policy is returned.
+    /// every frame until some other policy is returned, or until rendering is resumed with
+    /// [`RenderControl::resume`].
     StopRendering,
     /// Attempt renderer recovery with the given [`RenderCreation`].
     Recover(RenderCreation),
+    /// Attempt renderer recovery by recreating the render device with the [`WgpuSettings`] the
+    /// renderer was created with: a new adapter is requested, the window surfaces are recreated,
+    /// and the render assets are extracted again.
+    ///
+    /// This is the appropriate response to [`ErrorType::DeviceLost`] errors, e.g. when the GPU
+    /// was reset or its driver was updated. If the renderer was created with
+    /// [`RenderCreation::Manual`], it can't be recreated, and rendering stops instead.
+    ///
+    /// Render assets with [`RenderAssetUsages::RENDER_WORLD`](bevy_asset::RenderAssetUsages)
+    /// only are not kept in the main world, so they can't be extracted again and are lost.
+    RecreateDevice,
+    /// Attempt renderer recovery by recreating the render device on the next of the given
+    /// backends, in the order of [`BACKEND_FALLBACK_ORDER`], that hasn't failed yet.
+    ///
+    /// This lets the app survive broken drivers: if the DX12 driver can't create a device, the
+    /// renderer falls back to Vulkan, then to OpenGL. The backend that works is then used when
+    /// recreating the device with [`RenderErrorPolicy::RecreateDevice`].
+    ///
+    /// When all the backends failed, or if the renderer was created with
+    /// [`RenderCreation::Manual`], rendering stops instead.
+    FallbackBackend(Backends),
+    /// Attempt renderer recovery by recreating the render device on the adapter chosen by the
+    /// given [`AdapterSelector`]. The selector is then kept when recreating the device with
+    /// [`RenderErrorPolicy::RecreateDevice`].
+    ///
+    /// To move to an adapter without an error, e.g. to an external GPU that was plugged in, use
+    /// [`RenderControl::migrate_adapter`] instead.
+    ///
+    /// If the renderer was created with [`RenderCreation::Manual`], rendering stops instead.
+    MigrateAdapter(AdapterSelector),
+}
+
+/// The order in which backends are tried by [`RenderErrorPolicy::FallbackBackend`].
+pub const BACKEND_FALLBACK_ORDER: [Backends; 5] = [
+    Backends::DX12,
+    Backends::VULKAN,
+    Backends::METAL,
+    Backends::GL,
+    Backends::BROWSER_WEBGPU,
+];
+
+/// The number of times the render device was recreated to recover from an error, see
+/// [`RenderErrorPolicy::Recover`] and [`RenderErrorPolicy::RecreateDevice`]. This resource is in
+/// the main world.
+///
+/// GPU resources created on a previous device can't be used anymore. Render world resources
+/// holding them should be created in [`RenderStartup`], which runs again for each new device, or
+/// be recreated when this resource changes.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderDeviceGeneration(pub u32);
+
+/// The settings the renderer was created with, used by [`RenderErrorPolicy::RecreateDevice`] and
+/// [`RenderErrorPolicy::FallbackBackend`].
+#[derive(Resource)]
+pub(crate) struct RenderRecreationSettings {
+    /// This is [`None`] if the renderer was created with [`RenderCreation::Manual`].
+    pub(crate) settings: Option<Box<WgpuSettings>>,
+    /// The backends that failed, which [`RenderErrorPolicy::FallbackBackend`] doesn't try again.
+    pub(crate) failed_backends: Backends,
+}
+
+/// What a [`RenderErrorHandler`] has access to when responding to a [`RenderError`].
+pub struct RenderErrorContext<'a> {
+    /// The error to respond to. It has already been logged.
+    pub error: &'a RenderError,
+    /// The main world.
+    pub main_world: &'a mut World,
+    /// The render world.
+    pub render_world: &'a mut World,
 }
 
 /// Determines what [`RenderErrorPolicy`] should be used to respond to a given [`RenderError`].
 ///
-/// The handler has access to both the main world and the render world in that order.
-/// By the time this is invoked, the error has already been logged. The error is provided
-/// for the decision-making reason of how to appropriately respond to it. Not all errors
-/// are equally severe: validation errors may be ignored for example, while device lost errors
-/// require recovery to continue rendering.
+/// The handler has access to both the main world and the render world through the
+/// [`RenderErrorContext`]. By the time this is invoked, the error has already been logged. The
+/// error is provided for the decision-making reason of how to appropriately respond to it. Not all
+/// errors are equally severe: validation errors may be ignored for example, while device lost
+/// errors require recovery to continue rendering.
+///
+/// The handler is a closure, so it can keep state across errors, e.g. to give up on recovery after
+/// a few attempts:
+///
+/// ```
+/// # use bevy_render::error_handler::{RenderErrorHandler, RenderErrorPolicy};
+/// let mut recoveries = 0;
+/// let handler = RenderErrorHandler::new(move |_context| {
+///     recoveries += 1;
+///     if recoveries <= 3 {
+///         RenderErrorPolicy::RecreateDevice
+///     } else {
+///         RenderErrorPolicy::StopRendering
+///     }
+/// });
+/// ```
 #[derive(Resource)]
 pub struct RenderErrorHandler(
-    pub for<'a> fn(&'a RenderError, &'a mut World, &'a mut World) -> RenderErrorPolicy,
+    Box<dyn FnMut(RenderErrorContext) -> RenderErrorPolicy + Send + Sync>,
 );
 
 impl RenderErrorHandler {
-    fn handle(&self, error: &RenderError, main_world: &mut World, render_world: &mut World) {
-        match self.0(error, main_world, render_world) {
-            RenderErrorPolicy::Ignore => {
-                // Pretend that didn't happen.
-                render_world.insert_resource(RenderState::Ready);
-            }
-            RenderErrorPolicy::StopRendering => {
-                // do nothing
+    /// Creates a handler responding to errors with the policy returned by `handler`.
+    pub fn new(
+        handler: impl FnMut(RenderErrorContext) -> RenderErrorPolicy + Send + Sync + 'static,
+    ) -> Self {
+        Self(Box::new(handler))
+    }
+
+    fn handle(&mut self, error: &RenderError, main_world: &mut World, render_world: &mut World) {
+        let policy = (self.0)(RenderErrorContext {
+            error,
+            main_world,
+            render_world,
+        });
+        apply_policy(policy, error, main_world, render_world);
+    }
+}
+
+fn apply_policy(
+    policy: RenderErrorPolicy,
+    error: &RenderError,
+    main_world: &mut World,
+    render_world: &mut World,
+) {
+    match policy {
+        RenderErrorPolicy::Ignore => {
+            // Pretend that didn't happen.
+            render_world.insert_resource(RenderState::Ready);
+        }
+        RenderErrorPolicy::StopRendering => {
+            // do nothing
+        }
+        RenderErrorPolicy::Recover(render_creation) => {
+            assert!(insert_future_resources(&render_creation, main_world));
+            render_world.insert_resource(RenderState::Reinitializing);
+        }
+        RenderErrorPolicy::RecreateDevice => {
+            let Some(settings) = main_world
+                .get_resource::<RenderRecreationSettings>()
+                .and_then(|recreation| recreation.settings.clone())
+            else {
+                bevy_log::error!(
+                    "The render device can't be recreated, as the renderer was created manually. Rendering is stopped."
+                );
+                return;
+            };
+            bevy_log::info!("Recreating the render device");
+            if insert_future_resources(&RenderCreation::Automatic(settings), main_world) {
+                render_world.insert_resource(RenderState::Reinitializing);
             }
-            RenderErrorPolicy::Recover(render_creation) => {
-                assert!(insert_future_resources(&render_creation, main_world));
+        }
+        RenderErrorPolicy::FallbackBackend(backends) => {
+            // Errors from a running device are blamed on the backend of its adapter.
+            let failed_backends = error.failed_backends.or_else(|| {
+                main_world
+                    .get_resource::<RenderAdapterInfo>()
+                    .map(|info| Backends::from(info.backend))
+            });
+            let Some(mut recreation) = main_world.get_resource_mut::<RenderRecreationSettings>()
+            else {
+                return;
+            };
+            let Some(mut settings) = recreation.settings.clone() else {
+                bevy_log::error_once!(
+                    "The renderer can't fall back to another backend, as it was created manually. Rendering is stopped."
+                );
+                return;
+            };
+            recreation.failed_backends |= failed_backends.unwrap_or(Backends::empty());
+            let Some(backend) = BACKEND_FALLBACK_ORDER.into_iter().find(|&backend| {
+                backends.contains(backend) && !recreation.failed_backends.contains(backend)
+            }) else {
+                bevy_log::error_once!("No backend is left to fall back to. Rendering is stopped.");
+                return;
+            };
+            bevy_log::info!("Falling back to the {backend:?} backend");
+            settings.backends = Some(backend);
+            recreation.settings = Some(settings.clone());
+            if insert_future_resources(&RenderCreation::Automatic(settings), main_world) {
                 render_world.insert_resource(RenderState::Reinitializing);
             }
         }
+        RenderErrorPolicy::MigrateAdapter(selector) => {
+            migrate_adapter(selector, main_world, render_world);
+        }
+    }
+}
+
+/// Recreates the render device on the adapter chosen by `selector`, see
+/// [`RenderErrorPolicy::MigrateAdapter`].
+fn migrate_adapter(selector: AdapterSelector, main_world: &mut World, render_world: &mut World) {
+    let Some(mut recreation) = main_world.get_resource_mut::<RenderRecreationSettings>() else {
+        return;
+    };
+    let Some(mut settings) = recreation.settings.clone() else {
+        bevy_log::error!(
+            "The renderer can't migrate to another adapter, as it was created manually."
+        );
+        return;
+    };
+    bevy_log::info!("Migrating the renderer to another adapter");
+    settings.adapter_selector = Some(selector);
+    recreation.settings = Some(settings.clone());
+    if insert_future_resources(&RenderCreation::Automatic(settings), main_world) {
+        render_world.insert_resource(RenderState::Reinitializing);
     }
 }
 
 impl Default for RenderErrorHandler {
     fn default() -> Self {
-        // This is what we've always done historically,
-        // but we could choose a new default once recovery works better.
-        Self(|_, _, _| RenderErrorPolicy::Ignore)
+        // Ignoring errors is what we've always done historically, but a lost device can't render
+        // anymore, so it is recreated instead, a renderer that failed to initialize tries the
+        // other backends, and a render world left inconsistent by a panic isn't used anymore.
+        Self::new(|context| match context.error.code {
+            RenderErrorCode::Initialization => RenderErrorPolicy::FallbackBackend(Backends::all()),
+            RenderErrorCode::DeviceRemoved => RenderErrorPolicy::RecreateDevice,
+            RenderErrorCode::Panic => RenderErrorPolicy::StopRendering,
+            _ => RenderErrorPolicy::Ignore,
+        })
+    }
+}
+
+/// Controls the renderer from the main world, e.g. to resume rendering after the
+/// [`RenderErrorHandler`] stopped it.
+#[derive(Resource, Default, Debug)]
+pub struct RenderControl {
+    stopped: bool,
+    resume_requested: bool,
+    migration_requested: Option<AdapterSelector>,
+}
+
+impl RenderControl {
+    /// Resumes rendering stopped by [`RenderErrorPolicy::StopRendering`], e.g. when the user
+    /// clicks a "Retry" button after being shown the error.
+    ///
+    /// The window surfaces are created again. If the render device was lost or failed to be
+    /// created, it is recreated as with [`RenderErrorPolicy::RecreateDevice`]. This does nothing if rendering isn't stopped.
+    pub fn resume(&mut self) {
+        self.resume_requested = true;
+    }
+
+    /// Recreates the render device on the adapter chosen by `selector`, e.g. to move to an
+    /// external GPU that was plugged in, as reported by
+    /// [`AdapterChanged`](crate::adapter_change::AdapterChanged) messages.
+    ///
+    /// The selector is then kept when recreating the device with
+    /// [`RenderErrorPolicy::RecreateDevice`]. This does nothing if the renderer was created with
+    /// [`RenderCreation::Manual`].
+    pub fn migrate_adapter(&mut self, selector: AdapterSelector) {
+        self.migration_requested = Some(selector);
+    }
+
+    /// Returns `true` if rendering is stopped by an error, see
+    /// [`RenderErrorPolicy::StopRendering`].
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+}
+
+/// The cause of a [`RenderError`], to respond to errors without parsing their description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderErrorCode {
+    /// The renderer failed to initialize, see [`RenderError::failed_backends`].
+    Initialization,
+    /// The surface of a window was lost, e.g. because the window moved to another GPU. The
+    /// surface is created again on the next frame.
+    SurfaceLost,
+    /// The render device was lost, e.g. because the GPU was reset, its driver was updated, or it
+    /// was removed. See [`RenderError::device_lost_reason`].
+    DeviceRemoved,
+    /// The GPU ran out of memory.
+    OutOfMemory,
+    /// A shader failed to be processed or compiled.
+    ShaderCompile,
+    /// The GPU was used incorrectly, e.g. a resource was bound with the wrong layout.
+    Validation,
+    /// An internal error of wgpu or of the driver.
+    Internal,
+    /// A system of the [`Render`](crate::Render) schedule panicked. The panic is caught so that
+    /// the app can respond to it, e.g. by saving and showing the error to the user, instead of
+    /// aborting. This requires panics to unwind, which is the default.
+    ///
+    /// The render world may be left in an inconsistent state, e.g. the schedules nested in the
+    /// render schedule that were running are lost, so rendering is stopped by default.
+    Panic,
+}
+
+impl RenderErrorCode {
+    /// Returns the [`ErrorType`] of the errors with this code.
+    pub fn error_type(self) -> ErrorType {
+        match self {
+            Self::DeviceRemoved => ErrorType::DeviceLost,
+            Self::OutOfMemory => ErrorType::OutOfMemory,
+            Self::ShaderCompile | Self::Validation => ErrorType::Validation,
+            Self::Initialization | Self::SurfaceLost | Self::Internal | Self::Panic => {
+                ErrorType::Internal
+            }
+        }
     }
 }
 
 /// An error encountered during rendering.
 #[derive(Debug)]
 pub struct RenderError {
+    /// The cause of the error.
+    pub code: RenderErrorCode,
     pub ty: ErrorType,
     pub description: String,
+    /// The underlying error reported by wgpu, if any. Its [`source`](core::error::Error::source)
+    /// chain leads to the errors of the backend.
     pub source: Option<WgpuWrapper<ErrorSource>>,
+    /// If the error occurred while initializing the renderer, the backends that failed to
+    /// initialize.
+    pub failed_backends: Option<Backends>,
+    /// Why the render device was lost, for [`RenderErrorCode::DeviceRemoved`] errors.
+    pub device_lost_reason: Option<wgpu::DeviceLostReason>,
+}
+
+impl RenderError {
+    /// Creates an error with the given code and description, e.g. to report it with the
+    /// [`RenderErrorReporter`].
+    pub fn new(code: RenderErrorCode, description: impl Into<String>) -> Self {
+        Self {
+            code,
+            ty: code.error_type(),
+            description: description.into(),
+            source: None,
+            failed_backends: None,
+            device_lost_reason: None,
+        }
+    }
+
+    /// Creates the error reported when the renderer fails to initialize on the `requested`
+    /// backends.
+    pub(crate) fn initialization(error: &RenderInitializationError, requested: Backends) -> Self {
+        Self {
+            failed_backends: Some(error.failed_backends(requested)),
+            ..Self::new(RenderErrorCode::Initialization, error.to_string())
+        }
+    }
+}
+
+/// Reports errors caught outside of the error callbacks of the render device to the
+/// [`RenderErrorHandler`], e.g. when a window surface is lost or a shader fails to compile.
+///
+/// This resource is in the render world, and is replaced when the render device is created again.
+#[derive(Resource, Clone, Default)]
+pub struct RenderErrorReporter(Arc<Mutex<Option<RenderError>>>);
+
+impl RenderErrorReporter {
+    /// Logs `error` and reports it to the [`RenderErrorHandler`]. Only the first error reported
+    /// before the handler runs is kept, as it often causes the other ones.
+    pub fn report(&self, error: RenderError) {
+        bevy_log::error!("Caught rendering error: {}", error.description);
+        self.0.lock().unwrap().get_or_insert(error);
+    }
 }
 
 /// The current state of the renderer.
@@ -92,6 +405,7 @@ pub(crate) enum RenderState {
 pub(crate) struct DeviceErrorHandler {
     device_lost: Arc<Mutex<Option<(wgpu::DeviceLostReason, String)>>>,
     uncaptured: Arc<Mutex<Option<WgpuWrapper<wgpu::Error>>>>,
+    reporter: RenderErrorReporter,
 }
 
 impl DeviceErrorHandler {
@@ -121,40 +435,44 @@ impl DeviceErrorHandler {
         Self {
             device_lost,
             uncaptured,
+            reporter: RenderErrorReporter::default(),
         }
     }
 
+    /// Returns the reporter of the errors caught outside of the error callbacks of the device.
+    pub(crate) fn reporter(&self) -> RenderErrorReporter {
+        self.reporter.clone()
+    }
+
     /// Checks to see if any errors have been caught, and returns an appropriate `RenderState`
     pub(crate) fn poll(&self) -> Option<RenderError> {
         // Device lost is more important so we let it take precedence; every error gets logged anyways.
-        if let Some((_, description)) = self.device_lost.lock().unwrap().take() {
+        if let Some((reason, description)) = self.device_lost.lock().unwrap().take() {
             return Some(RenderError {
-                ty: ErrorType::DeviceLost,
-                description,
-                source: None,
+                device_lost_reason: Some(reason),
+                ..RenderError::new(RenderErrorCode::DeviceRemoved, description)
             });
         }
         if let Some(error) = self.uncaptured.lock().unwrap().take() {
-            let (ty, description, source) = match error.into_inner() {
+            let (code, description, source) = match error.into_inner() {
                 wgpu::Error::OutOfMemory { source } => {
-                    (ErrorType::OutOfMemory, "".to_string(), source)
+                    (RenderErrorCode::OutOfMemory, "".to_string(), source)
                 }
                 wgpu::Error::Validation {
                     source,
                     description,
-                } => (ErrorType::Validation, description, source),
+                } => (RenderErrorCode::Validation, description, source),
                 wgpu::Error::Internal {
                     source,
                     description,
-                } => (ErrorType::Internal, description, source),
+                } => (RenderErrorCode::Internal, description, source),
             };
             return Some(RenderError {
-                ty,
-                description,
                 source: Some(WgpuWrapper::new(source)),
+                ..RenderError::new(code, description)
             });
         }
-        None
+        self.reporter.0.lock().unwrap().take()
     }
 }
 
@@ -172,37 +490,81 @@ pub(crate) fn update_state(main_world: &mut World, render_world: &mut World) {
     // Remove the render state so we can provide both worlds to the `RenderErrorHandler`.
     let state = render_world.remove_resource::<RenderState>().unwrap();
 
+    let migration = main_world
+        .get_resource_mut::<RenderControl>()
+        .and_then(|mut control| control.migration_requested.take());
+    if let Some(selector) = migration
+        && !matches!(state, RenderState::Reinitializing)
+    {
+        migrate_adapter(selector, main_world, render_world);
+    }
+    let migrating = render_world.contains_resource::<RenderState>();
+
     match &state {
+        _ if migrating => {}
         RenderState::Initializing => {
             render_world.run_schedule(RenderStartup);
             render_world.insert_resource(RenderState::Ready);
         }
         RenderState::Ready => {
             // all is well
+            update_trace_capture(main_world, render_world);
         }
         RenderState::Errored(error) => {
-            main_world.resource_scope(|main_world, error_handler: Mut<RenderErrorHandler>| {
-                error_handler.handle(error, main_world, render_world);
-            });
+            let resume = main_world
+                .get_resource_mut::<RenderControl>()
+                .is_some_and(|mut control| core::mem::take(&mut control.resume_requested));
+            if !resume {
+                main_world.resource_scope(
+                    |main_world, mut error_handler: Mut<RenderErrorHandler>| {
+                        error_handler.handle(error, main_world, render_world);
+                    },
+                );
+            } else if matches!(
+                error.code,
+                RenderErrorCode::DeviceRemoved | RenderErrorCode::Initialization
+            ) {
+                // The device was lost, or it failed to be created, e.g. when recreating a lost
+                // device, so there is no usable device to resume with.
+                apply_policy(
+                    RenderErrorPolicy::RecreateDevice,
+                    error,
+                    main_world,
+                    render_world,
+                );
+            } else {
+                bevy_log::info!("Resuming rendering");
+                reset_window_surfaces(render_world);
+                render_world.insert_resource(RenderState::Ready);
+            }
         }
         RenderState::Reinitializing => {
-            if let Some(render_resources) = main_world
+            let render_resources = main_world
                 .get_resource::<FutureRenderResources>()
                 .unwrap()
                 .clone()
                 .lock()
                 .unwrap()
-                .take()
-            {
-                let synchronous_pipeline_compilation = render_world
-                    .resource::<PipelineCache>()
-                    .synchronous_pipeline_compilation;
-                render_resources.unpack_into(
-                    main_world,
-                    render_world,
-                    synchronous_pipeline_compilation,
-                );
-                render_world.insert_resource(RenderState::Initializing);
+                .take();
+            match render_resources {
+                Some(Ok(render_resources)) => {
+                    let synchronous_pipeline_compilation = render_world
+                        .resource::<PipelineCache>()
+                        .synchronous_pipeline_compilation;
+                    render_resources.unpack_into(
+                        main_world,
+                        render_world,
+                        synchronous_pipeline_compilation,
+                    );
+                    reset_device_resources(render_world);
+                    main_world.resource_mut::<RenderDeviceGeneration>().0 += 1;
+                    render_world.insert_resource(RenderState::Initializing);
+                }
+                Some(Err(error)) => {
+                    // The handler is polled again, e.g. to fall back to another backend.
+                    render_world.insert_resource(RenderState::Errored(error));
+                }
+                None => {}
             }
         }
     }
@@ -211,4 +573,69 @@ pub(crate) fn update_state(main_world: &mut World, render_world: &mut World) {
     if render_world.get_resource::<RenderState>().is_none() {
         render_world.insert_resource(state);
     }
+
+    let stopped = matches!(
+        render_world.resource::<RenderState>(),
+        RenderState::Errored(_)
+    );
+    if let Some(mut control) = main_world.get_resource_mut::<RenderControl>() {
+        // Resuming only applies to the current error.
+        control.resume_requested = false;
+        if control.stopped != stopped {
+            control.stopped = stopped;
+        }
+    }
+}
+
+/// Lets the [`RenderErrorHandler`] respond to the renderer failing to initialize on startup, and
+/// returns the result of the next attempt.
+///
+/// # Panics
+///
+/// Panics if the handler doesn't retry initialization, as the other plugins need a render device
+/// to finish building, or if the next attempt doesn't complete synchronously, as on the web.
+pub(crate) fn retry_initialization(
+    error: RenderError,
+    main_world: &mut World,
+    render_world: &mut World,
+) -> Result<RenderResources, RenderError> {
+    bevy_log::error!("Failed to initialize the renderer: {}", error.description);
+    let initial_state = render_world.remove_resource::<RenderState>();
+    main_world.resource_scope(|main_world, mut error_handler: Mut<RenderErrorHandler>| {
+        error_handler.handle(&error, main_world, render_world);
+    });
+    if !matches!(
+        render_world.remove_resource::<RenderState>(),
+        Some(RenderState::Reinitializing)
+    ) {
+        panic!("{}", error.description);
+    }
+    if let Some(initial_state) = initial_state {
+        render_world.insert_resource(initial_state);
+    }
+    main_world
+        .remove_resource::<FutureRenderResources>()
+        .and_then(|future_resources| future_resources.lock().unwrap().take())
+        .expect("The renderer can only be initialized again synchronously on startup")
+}
+
+/// Drops the resources of the render world that hold GPU objects of the previous device, and
+/// aren't recreated by [`RenderStartup`], by running the [`RenderDeviceReset`] schedule.
+fn reset_device_resources(render_world: &mut World) {
+    // The surfaces were created by the previous instance, so they are created again.
+    reset_window_surfaces(render_world);
+    render_world.run_schedule(RenderDeviceReset);
+}
+
+/// Drops the window surfaces, so that they are created again.
+fn reset_window_surfaces(render_world: &mut World) {
+    if let Some(mut windows) = render_world.get_resource_mut::<ExtractedWindows>() {
+        for window in windows.windows.values_mut() {
+            window.swap_chain_texture_view = None;
+            window.swap_chain_texture = None;
+        }
+    }
+    if render_world.contains_resource::<WindowSurfaces>() {
+        render_world.insert_resource(WindowSurfaces::default());
+    }
 }
@@ -1,9 +1,10 @@
 use crate::{
     render_resource::{encase::internal::WriteInto, DynamicUniformBuffer, ShaderType},
     renderer::{RenderDevice, RenderQueue},
+    reset_render_resource,
     sync_component::{SyncComponent, SyncComponentPlugin},
     sync_world::RenderEntity,
-    Extract, ExtractSchedule, Render, RenderApp, RenderSystems,
+    Extract, ExtractSchedule, Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 use bevy_app::{App, Plugin};
 use bevy_camera::visibility::ViewVisibility;
@@ -76,6 +77,10 @@ impl<C: Component + ShaderType + WriteInto + Clone> Plugin for UniformComponentP
                 .add_systems(
                     Render,
                     prepare_uniform_components::<C>.in_set(RenderSystems::PrepareResources),
+                )
+                .add_systems(
+                    RenderDeviceReset,
+                    reset_render_resource::<ComponentUniforms<C>>,
                 );
         }
     }
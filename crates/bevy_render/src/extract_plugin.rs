@@ -1,4 +1,5 @@
 use crate::{
+    report_panic, run_schedule_catching_panics,
     sync_world::{despawn_temporary_render_entities, entity_sync_system, SyncWorldPlugin},
     Render, RenderApp, RenderSystems,
 };
@@ -117,12 +118,17 @@ pub fn extract(main_world: &mut World, render_world: &mut World) {
     let scratch_world = main_world.remove_resource::<ScratchMainWorld>().unwrap();
     let inserted_world = core::mem::replace(main_world, scratch_world.0);
     render_world.insert_resource(MainWorld(inserted_world));
-    render_world.run_schedule(ExtractSchedule);
+    // A panic is only reported once the app world is back, so that it isn't lost.
+    let result = run_schedule_catching_panics(render_world, ExtractSchedule);
 
     // move the app world back, as if nothing happened.
     let inserted_world = render_world.remove_resource::<MainWorld>().unwrap();
     let scratch_world = core::mem::replace(main_world, inserted_world.0);
     main_world.insert_resource(ScratchMainWorld(scratch_world));
+
+    if let Err(message) = result {
+        report_panic(render_world, "extract schedule", &message);
+    }
 }
 
 #[cfg(test)]
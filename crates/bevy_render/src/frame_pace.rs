@@ -0,0 +1,123 @@
+//! Paces the simulation on the GPU, to keep frame times even and input latency low.
+
+use crate::{
+    error_handler::RenderDeviceGeneration,
+    renderer::{render_system, RenderDevice, RenderQueue},
+    Render, RenderApp, RenderSystems,
+};
+use alloc::{collections::VecDeque, sync::Arc};
+use bevy_app::{App, First, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use core::{num::NonZero, time::Duration};
+use std::sync::Mutex;
+use wgpu::{PollType, SubmissionIndex};
+
+/// How long [`wait_for_gpu`] waits for a frame before giving up, so that a lost device doesn't
+/// block the app forever.
+const MAX_FRAME_WAIT: Duration = Duration::from_secs(1);
+
+/// Blocks the start of each frame until at most
+/// [`max_frames_in_flight`](Self::max_frames_in_flight) - 1 of the previous frames are still
+/// being rendered by the GPU.
+///
+/// Without it, the simulation runs ahead of the GPU until the swap chain is full, and the time
+/// spent blocked on acquiring a swap chain texture lands in the middle of the frame. Waiting for
+/// the GPU before the simulation starts instead gives even frame times, and input that is sampled
+/// as late as possible. This is the equivalent of waiting on the frame latency waitable object of
+/// a DXGI swap chain.
+///
+/// This plugin is not part of the `DefaultPlugins`, and must be added after the
+/// [`RenderPlugin`](crate::RenderPlugin).
+pub struct FramePacePlugin {
+    /// The number of frames the GPU may work on while the next frame is simulated.
+    ///
+    /// `1` gives the lowest latency, while higher values let the CPU and GPU work in parallel for
+    /// GPU-bound apps.
+    pub max_frames_in_flight: NonZero<u32>,
+}
+
+impl Default for FramePacePlugin {
+    fn default() -> Self {
+        Self {
+            max_frames_in_flight: NonZero::<u32>::MIN,
+        }
+    }
+}
+
+impl Plugin for FramePacePlugin {
+    fn build(&self, app: &mut App) {
+        let pacer = FramePacer {
+            submissions: Arc::default(),
+            max_frames_in_flight: self.max_frames_in_flight,
+        };
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.insert_resource(pacer.clone()).add_systems(
+            Render,
+            record_frame_submission
+                .in_set(RenderSystems::Render)
+                .after(render_system),
+        );
+
+        app.insert_resource(pacer).add_systems(First, wait_for_gpu);
+    }
+}
+
+/// The frames submitted to the GPU that may still be in flight, shared between the main world and
+/// the render world.
+#[derive(Resource, Clone)]
+pub struct FramePacer {
+    submissions: Arc<Mutex<VecDeque<SubmissionIndex>>>,
+    max_frames_in_flight: NonZero<u32>,
+}
+
+impl FramePacer {
+    /// The number of frames the GPU may work on while the next frame is simulated.
+    pub fn max_frames_in_flight(&self) -> NonZero<u32> {
+        self.max_frames_in_flight
+    }
+}
+
+/// Records the end of the work of this frame on the GPU.
+fn record_frame_submission(pacer: Res<FramePacer>, render_queue: Res<RenderQueue>) {
+    // An empty submission completes once all the work submitted before it has.
+    let submission = render_queue.submit([]);
+    pacer.submissions.lock().unwrap().push_back(submission);
+}
+
+/// Waits for the GPU to finish the previous frames, until at most
+/// [`FramePacer::max_frames_in_flight`] - 1 are in flight.
+pub fn wait_for_gpu(
+    pacer: Res<FramePacer>,
+    render_device: Option<Res<RenderDevice>>,
+    generation: Option<Res<RenderDeviceGeneration>>,
+) {
+    let Some(render_device) = render_device else {
+        return;
+    };
+    if generation.is_some_and(|generation| generation.is_changed()) {
+        // The submissions of a previous device can't be waited on.
+        pacer.submissions.lock().unwrap().clear();
+    }
+
+    loop {
+        let submission = {
+            let mut submissions = pacer.submissions.lock().unwrap();
+            if submissions.len() < pacer.max_frames_in_flight.get() as usize {
+                return;
+            }
+            submissions.pop_front().unwrap()
+        };
+        if let Err(error) = render_device.poll(PollType::Wait {
+            submission_index: Some(submission),
+            timeout: Some(MAX_FRAME_WAIT),
+        }) {
+            warn!("Failed to wait for the GPU to finish a frame: {error}");
+            pacer.submissions.lock().unwrap().clear();
+            return;
+        }
+    }
+}
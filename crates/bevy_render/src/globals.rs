@@ -2,7 +2,8 @@ use crate::{
     extract_resource::ExtractResource,
     render_resource::{ShaderType, UniformBuffer},
     renderer::{RenderDevice, RenderQueue},
-    Extract, ExtractSchedule, Render, RenderApp, RenderSystems,
+    reset_render_resource, Extract, ExtractSchedule, Render, RenderApp, RenderDeviceReset,
+    RenderSystems,
 };
 use bevy_app::{App, Plugin};
 use bevy_diagnostic::FrameCount;
@@ -24,7 +25,8 @@ impl Plugin for GlobalsPlugin {
                 .add_systems(
                     Render,
                     prepare_globals_buffer.in_set(RenderSystems::PrepareResources),
-                );
+                )
+                .add_systems(RenderDeviceReset, reset_render_resource::<GlobalsBuffer>);
         }
     }
 }
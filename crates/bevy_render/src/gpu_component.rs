@@ -0,0 +1,86 @@
+use crate::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin},
+    gpu_component_array_buffer::GpuComponentArrayBufferPlugin,
+    render_resource::GpuArrayBufferable,
+};
+use bevy_app::{App, Plugin};
+use core::marker::PhantomData;
+
+pub use bevy_render_macros::GpuComponent;
+
+/// How the per-entity data of a [`GpuComponent`] is stored on the GPU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GpuComponentBuffer {
+    /// The components are stored in a dynamic uniform buffer, in the
+    /// [`ComponentUniforms`](crate::extract_component::ComponentUniforms) resource.
+    ///
+    /// A [`DynamicUniformIndex`](crate::extract_component::DynamicUniformIndex) holding the
+    /// dynamic offset of the component is inserted on each render entity.
+    Uniform,
+    /// The components are stored in a [`GpuArrayBuffer`](crate::render_resource::GpuArrayBuffer)
+    /// resource.
+    ///
+    /// A [`GpuArrayBufferIndex`](crate::render_resource::GpuArrayBufferIndex) holding the
+    /// index of the component is inserted on each render entity.
+    Array,
+}
+
+/// A component whose data is uploaded to the GPU for each entity, to drive custom shaders.
+///
+/// This ties together everything needed to use a component in a shader: its layout on the GPU
+/// (from [`ShaderType`](crate::render_resource::ShaderType)), its extraction to the render world
+/// (from [`ExtractComponent`]), and how it's stored on the GPU ([`GpuComponent::BUFFER`]).
+/// Add a [`GpuComponentPlugin`] to extract and upload the components every frame.
+///
+/// This trait is usually derived, along with [`ShaderType`](crate::render_resource::ShaderType).
+/// See the [`GpuComponent`](derive@GpuComponent) derive for the supported attributes.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::component::Component;
+/// # use bevy_render::{gpu_component::{GpuComponent, GpuComponentPlugin}, render_resource::ShaderType};
+/// #[derive(Component, Clone, ShaderType, GpuComponent)]
+/// struct Wobble {
+///     amplitude: f32,
+///     frequency: f32,
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GpuComponentPlugin::<Wobble>::default());
+/// ```
+///
+/// The components can then be bound in the render world, using the uniform buffer in
+/// [`ComponentUniforms<Wobble>`](crate::extract_component::ComponentUniforms) and the dynamic
+/// offset in the [`DynamicUniformIndex<Wobble>`](crate::extract_component::DynamicUniformIndex)
+/// of each entity.
+pub trait GpuComponent: ExtractComponent<Out = Self> + GpuArrayBufferable {
+    /// How the components are stored on the GPU.
+    const BUFFER: GpuComponentBuffer = GpuComponentBuffer::Uniform;
+}
+
+/// Extracts the [`GpuComponent`]s of type `C` to the render world, and uploads them to the GPU
+/// every frame.
+///
+/// This adds an [`ExtractComponentPlugin`], along with an [`UniformComponentPlugin`] or a
+/// [`GpuComponentArrayBufferPlugin`] depending on [`GpuComponent::BUFFER`].
+pub struct GpuComponentPlugin<C>(PhantomData<fn() -> C>);
+
+impl<C> Default for GpuComponentPlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: GpuComponent> Plugin for GpuComponentPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<C>::default());
+        match C::BUFFER {
+            GpuComponentBuffer::Uniform => {
+                app.add_plugins(UniformComponentPlugin::<C>::default());
+            }
+            GpuComponentBuffer::Array => {
+                app.add_plugins(GpuComponentArrayBufferPlugin::<C>::default());
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     render_resource::{GpuArrayBuffer, GpuArrayBufferable},
     renderer::{RenderDevice, RenderQueue},
-    Render, RenderApp, RenderSystems,
+    Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 use bevy_app::{App, Plugin};
 use bevy_ecs::{
@@ -18,10 +18,13 @@ pub struct GpuComponentArrayBufferPlugin<C: Component + GpuArrayBufferable>(Phan
 impl<C: Component + GpuArrayBufferable> Plugin for GpuComponentArrayBufferPlugin<C> {
     fn build(&self, app: &mut App) {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.add_systems(
-                Render,
-                prepare_gpu_component_array_buffers::<C>.in_set(RenderSystems::PrepareResources),
-            );
+            render_app
+                .add_systems(
+                    Render,
+                    prepare_gpu_component_array_buffers::<C>
+                        .in_set(RenderSystems::PrepareResources),
+                )
+                .add_systems(RenderDeviceReset, reset_gpu_component_array_buffer::<C>);
         }
     }
 
@@ -40,6 +43,13 @@ impl<C: Component + GpuArrayBufferable> Default for GpuComponentArrayBufferPlugi
     }
 }
 
+fn reset_gpu_component_array_buffer<C: Component + GpuArrayBufferable>(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+) {
+    commands.insert_resource(GpuArrayBuffer::<C>::new(&render_device.limits()));
+}
+
 fn prepare_gpu_component_array_buffers<C: Component + GpuArrayBufferable>(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
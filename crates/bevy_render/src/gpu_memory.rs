@@ -0,0 +1,147 @@
+//! Tracks the GPU memory used by the renderer against a budget.
+
+use crate::renderer::RenderDevice;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_platform::time::Instant;
+use core::time::Duration;
+
+/// Polls the GPU memory used by the renderer into the [`GpuMemoryBudget`] resource, and writes a
+/// [`GpuMemoryPressureChanged`] message when the usage crosses its thresholds.
+///
+/// Streaming systems can react to the message by dropping mip levels or evicting render assets,
+/// before the device runs out of memory, or the OS kills the app.
+pub struct GpuMemoryPlugin {
+    /// How often the memory usage is polled. Generating the report has a cost that grows with the
+    /// number of allocations, so it isn't done every frame.
+    pub poll_interval: Duration,
+}
+
+impl Default for GpuMemoryPlugin {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for GpuMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(GpuMemoryPollInterval(self.poll_interval))
+            .init_resource::<GpuMemoryBudget>()
+            .add_message::<GpuMemoryPressureChanged>()
+            .add_systems(PreUpdate, update_gpu_memory_budget);
+    }
+}
+
+#[derive(Resource)]
+struct GpuMemoryPollInterval(Duration);
+
+/// How close the GPU memory usage is to the [`GpuMemoryBudget`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GpuMemoryPressure {
+    /// The usage is below the warning threshold, or there is no budget.
+    #[default]
+    Normal,
+    /// The usage is above [`GpuMemoryBudget::warning_threshold`].
+    Warning,
+    /// The usage is above [`GpuMemoryBudget::critical_threshold`].
+    Critical,
+}
+
+/// The GPU memory used by the renderer, and the budget it should stay within.
+///
+/// wgpu doesn't report the budget given to the app by the OS, so it must be set by the app, e.g.
+/// from the memory limits of the platform it targets.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct GpuMemoryBudget {
+    /// The memory used by live allocations, in bytes.
+    ///
+    /// This is [`None`] if the backend doesn't report its allocations, as is the case of GL and
+    /// WebGPU.
+    pub allocated_bytes: Option<u64>,
+    /// The memory reserved by the allocator, including the unused parts of its memory blocks, in
+    /// bytes.
+    pub reserved_bytes: Option<u64>,
+    /// The budget the reserved memory should stay within, in bytes.
+    pub budget_bytes: Option<u64>,
+    /// The fraction of the budget above which the pressure is [`GpuMemoryPressure::Warning`].
+    pub warning_threshold: f32,
+    /// The fraction of the budget above which the pressure is [`GpuMemoryPressure::Critical`].
+    pub critical_threshold: f32,
+    /// The pressure at the last poll.
+    pub pressure: GpuMemoryPressure,
+}
+
+impl Default for GpuMemoryBudget {
+    fn default() -> Self {
+        Self {
+            allocated_bytes: None,
+            reserved_bytes: None,
+            budget_bytes: None,
+            warning_threshold: 0.8,
+            critical_threshold: 0.95,
+            pressure: GpuMemoryPressure::Normal,
+        }
+    }
+}
+
+impl GpuMemoryBudget {
+    /// The fraction of the budget in use, if both the usage and the budget are known.
+    pub fn usage(&self) -> Option<f32> {
+        let reserved_bytes = self.reserved_bytes?;
+        let budget_bytes = self.budget_bytes.filter(|budget| *budget > 0)?;
+        Some(reserved_bytes as f32 / budget_bytes as f32)
+    }
+
+    fn current_pressure(&self) -> GpuMemoryPressure {
+        match self.usage() {
+            Some(usage) if usage >= self.critical_threshold => GpuMemoryPressure::Critical,
+            Some(usage) if usage >= self.warning_threshold => GpuMemoryPressure::Warning,
+            _ => GpuMemoryPressure::Normal,
+        }
+    }
+}
+
+/// Written when the [`GpuMemoryPressure`] of the [`GpuMemoryBudget`] changes.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GpuMemoryPressureChanged {
+    /// The new pressure.
+    pub pressure: GpuMemoryPressure,
+    /// The pressure before the change.
+    pub previous: GpuMemoryPressure,
+}
+
+fn update_gpu_memory_budget(
+    render_device: Option<Res<RenderDevice>>,
+    interval: Res<GpuMemoryPollInterval>,
+    mut budget: ResMut<GpuMemoryBudget>,
+    mut pressure_changed: MessageWriter<GpuMemoryPressureChanged>,
+    mut last_poll: Local<Option<Instant>>,
+) {
+    let Some(render_device) = render_device else {
+        return;
+    };
+    let now = Instant::now();
+    if last_poll.is_some_and(|last_poll| now < last_poll + interval.0) {
+        return;
+    }
+    *last_poll = Some(now);
+
+    let report = render_device.wgpu_device().generate_allocator_report();
+    let allocated_bytes = report.as_ref().map(|report| report.total_allocated_bytes);
+    let reserved_bytes = report.as_ref().map(|report| report.total_reserved_bytes);
+    if budget.allocated_bytes != allocated_bytes || budget.reserved_bytes != reserved_bytes {
+        budget.allocated_bytes = allocated_bytes;
+        budget.reserved_bytes = reserved_bytes;
+    }
+
+    let pressure = budget.current_pressure();
+    if pressure != budget.pressure {
+        pressure_changed.write(GpuMemoryPressureChanged {
+            pressure,
+            previous: budget.pressure,
+        });
+        budget.pressure = pressure;
+    }
+}
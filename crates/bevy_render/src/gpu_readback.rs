@@ -3,17 +3,18 @@ use crate::{
     render_asset::RenderAssets,
     render_resource::{
         Buffer, BufferUsages, CommandEncoder, Extent3d, TexelCopyBufferLayout, Texture,
-        TextureFormat,
+        TextureDimension, TextureFormat, TextureUsages,
     },
     renderer::RenderDevice,
+    reset_render_resource,
     storage::{GpuShaderBuffer, ShaderBuffer},
     sync_world::MainEntity,
     texture::GpuImage,
-    ExtractSchedule, MainWorld, Render, RenderApp, RenderSystems,
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 use async_channel::{Receiver, Sender};
 use bevy_app::{App, Plugin};
-use bevy_asset::Handle;
+use bevy_asset::{Handle, RenderAssetUsages};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::schedule::IntoScheduleConfigs;
 use bevy_ecs::{
@@ -24,7 +25,7 @@ use bevy_ecs::{
     system::{Query, Res},
 };
 use bevy_image::{Image, TextureFormatPixelInfo};
-use bevy_log::warn;
+use bevy_log::{warn, warn_once};
 use bevy_platform::collections::HashMap;
 use bevy_reflect::Reflect;
 use bevy_render_macros::ExtractComponent;
@@ -55,15 +56,27 @@ impl Plugin for GpuReadbackPlugin {
             render_app
                 .init_resource::<GpuReadbackBufferPool>()
                 .init_resource::<GpuReadbacks>()
+                .init_resource::<RenderTextureReadbacks>()
                 .insert_resource(GpuReadbackMaxUnusedFrames(self.max_unused_frames))
                 .add_systems(ExtractSchedule, sync_readbacks.ambiguous_with_all())
                 .add_systems(
                     Render,
                     (
                         prepare_buffers.in_set(RenderSystems::PrepareResources),
+                        prepare_render_texture_buffers.in_set(RenderSystems::PrepareResourcesFlush),
                         // TODO: this should be in the graph somehow
                         map_buffers.in_set(RenderSystems::Cleanup),
                     ),
+                )
+                // The pending readbacks return their buffers to the pool, so they are dropped
+                // along with it.
+                .add_systems(
+                    RenderDeviceReset,
+                    (
+                        reset_render_resource::<GpuReadbackBufferPool>,
+                        reset_render_resource::<GpuReadbacks>,
+                        reset_render_resource::<RenderTextureReadbacks>,
+                    ),
                 );
         }
     }
@@ -73,6 +86,24 @@ impl Plugin for GpuReadbackPlugin {
 ///
 /// Data is read asynchronously and will be triggered on the entity via the [`ReadbackComplete`] event
 /// when complete. If this component is not removed, the readback will be attempted every frame
+///
+/// Reading back the texture a camera renders to gives its frames on the CPU, which doesn't need a
+/// window: with no primary window in the `WindowPlugin`, the app renders headless, e.g. to compare
+/// frames to golden images in CI.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::Handle;
+/// # use bevy_image::Image;
+/// # use bevy_render::gpu_readback::{Readback, ReadbackComplete};
+/// fn read_frames(mut commands: Commands, render_target: Handle<Image>) {
+///     commands
+///         .spawn(Readback::texture(render_target))
+///         .observe(|readback: On<ReadbackComplete>| {
+///             let frame: Image = readback.to_image().unwrap();
+///         });
+/// }
+/// ```
 #[derive(Component, ExtractComponent, Clone, Debug)]
 pub enum Readback {
     Texture(Handle<Image>),
@@ -106,19 +137,70 @@ impl Readback {
     }
 }
 
+/// Textures of the render world to read back to the CPU this frame, which aren't [`Image`] assets
+/// a [`Readback`] could refer to, e.g. the prepass textures of a view.
+///
+/// Each texture is read back like a [`Readback::Texture`], triggering [`ReadbackComplete`] on the
+/// given main world entity. Textures must be pushed before [`RenderSystems::PrepareResourcesFlush`],
+/// e.g. in [`RenderSystems::PrepareResources`] after creating them, and can only be read back if
+/// they aren't multisampled and have the [`TextureUsages::COPY_SRC`] usage.
+#[derive(Resource, Default)]
+pub struct RenderTextureReadbacks(Vec<(Entity, Texture)>);
+
+impl RenderTextureReadbacks {
+    /// Reads `texture` back at the end of the frame, triggering [`ReadbackComplete`] on `entity`
+    /// in the main world.
+    pub fn push(&mut self, entity: Entity, texture: Texture) {
+        self.0.push((entity, texture));
+    }
+}
+
 /// An event that is triggered when a gpu readback is complete.
 ///
 /// The event contains the data as a `Vec<u8>`, which can be interpreted as the raw bytes of the
-/// requested buffer or texture.
+/// requested buffer or texture. The rows of textures are padded to
+/// [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`], use [`to_image`](Self::to_image) to remove the padding.
 #[derive(EntityEvent, Deref, DerefMut, Reflect, Debug)]
 #[reflect(Debug)]
 pub struct ReadbackComplete {
     pub entity: Entity,
     #[deref]
     pub data: Vec<u8>,
+    /// The size and format of the texture, for [`Readback::Texture`] readbacks.
+    #[reflect(ignore)]
+    pub texture: Option<ReadbackTexture>,
+}
+
+/// The texture read back by a [`Readback::Texture`].
+#[derive(Clone, Copy, Debug)]
+pub struct ReadbackTexture {
+    pub size: Extent3d,
+    pub format: TextureFormat,
 }
 
 impl ReadbackComplete {
+    /// Convert the data of a texture readback to an [`Image`], without the row padding.
+    ///
+    /// Returns [`None`] for buffer readbacks, and for compressed texture formats.
+    pub fn to_image(&self) -> Option<Image> {
+        let ReadbackTexture { size, format } = self.texture?;
+        let pixel_size = format.pixel_size().ok()?;
+        let row_bytes = size.width as usize * pixel_size;
+        let padded_row_bytes = align_byte_size(row_bytes as u32) as usize;
+        let rows = (size.height * size.depth_or_array_layers) as usize;
+        let mut data = Vec::with_capacity(row_bytes * rows);
+        for row in self.data.chunks(padded_row_bytes).take(rows) {
+            data.extend_from_slice(&row[..row_bytes]);
+        }
+        Some(Image::new(
+            size,
+            TextureDimension::D2,
+            data,
+            format,
+            RenderAssetUsages::default(),
+        ))
+    }
+
     /// Convert the raw bytes of the event to a shader type.
     pub fn to_shader_type<T: ShaderType + ReadFrom + Default>(&self) -> T {
         let mut val = T::default();
@@ -225,6 +307,7 @@ struct GpuReadbacks {
 struct GpuReadback {
     pub entity: Entity,
     pub src: ReadbackSource,
+    pub texture: Option<ReadbackTexture>,
     pub buffer: Buffer,
     pub rx: Receiver<(Entity, Buffer, Vec<u8>)>,
     pub tx: Sender<(Entity, Buffer, Vec<u8>)>,
@@ -238,7 +321,11 @@ fn sync_readbacks(
 ) {
     readbacks.mapped.retain(|readback| {
         if let Ok((entity, buffer, data)) = readback.rx.try_recv() {
-            main_world.trigger(ReadbackComplete { data, entity });
+            main_world.trigger(ReadbackComplete {
+                data,
+                entity,
+                texture: readback.texture,
+            });
             buffer_pool.return_buffer(&buffer);
             false
         } else {
@@ -260,30 +347,16 @@ fn prepare_buffers(
     for (entity, readback) in handles.iter() {
         match readback {
             Readback::Texture(image) => {
-                if let Some(gpu_image) = gpu_images.get(image)
-                    && let Ok(pixel_size) = gpu_image.texture_descriptor.format.pixel_size()
-                {
-                    let layout = layout_data(
+                if let Some(gpu_image) = gpu_images.get(image) {
+                    request_texture_readback(
+                        &mut readbacks,
+                        &mut buffer_pool,
+                        &render_device,
+                        entity.id(),
+                        &gpu_image.texture,
                         gpu_image.texture_descriptor.size,
                         gpu_image.texture_descriptor.format,
                     );
-                    let buffer = buffer_pool.get(
-                        &render_device,
-                        get_aligned_size(gpu_image.texture_descriptor.size, pixel_size as u32)
-                            as u64,
-                    );
-                    let (tx, rx) = async_channel::bounded(1);
-                    readbacks.requested.push(GpuReadback {
-                        entity: entity.id(),
-                        src: ReadbackSource::Texture {
-                            texture: gpu_image.texture.clone(),
-                            layout,
-                            size: gpu_image.texture_descriptor.size,
-                        },
-                        buffer,
-                        rx,
-                        tx,
-                    });
                 }
             }
             Readback::Buffer {
@@ -312,6 +385,7 @@ fn prepare_buffers(
                             start_offset_and_size: *start_offset_and_size,
                             buffer: ssbo.buffer.clone(),
                         },
+                        texture: None,
                         buffer,
                         rx,
                         tx,
@@ -322,6 +396,64 @@ fn prepare_buffers(
     }
 }
 
+fn prepare_render_texture_buffers(
+    render_device: Res<RenderDevice>,
+    mut render_texture_readbacks: ResMut<RenderTextureReadbacks>,
+    mut readbacks: ResMut<GpuReadbacks>,
+    mut buffer_pool: ResMut<GpuReadbackBufferPool>,
+) {
+    for (entity, texture) in render_texture_readbacks.0.drain(..) {
+        if texture.sample_count() != 1 || !texture.usage().contains(TextureUsages::COPY_SRC) {
+            warn_once!(
+                "Render textures can only be read back if they aren't multisampled, and have the \
+                `COPY_SRC` usage."
+            );
+            continue;
+        }
+        request_texture_readback(
+            &mut readbacks,
+            &mut buffer_pool,
+            &render_device,
+            entity,
+            &texture,
+            texture.size(),
+            texture.format(),
+        );
+    }
+}
+
+fn request_texture_readback(
+    readbacks: &mut GpuReadbacks,
+    buffer_pool: &mut GpuReadbackBufferPool,
+    render_device: &RenderDevice,
+    entity: Entity,
+    texture: &Texture,
+    size: Extent3d,
+    format: TextureFormat,
+) {
+    let Ok(pixel_size) = format.pixel_size() else {
+        return;
+    };
+    let layout = layout_data(size, format);
+    let buffer = buffer_pool.get(
+        render_device,
+        get_aligned_size(size, pixel_size as u32) as u64,
+    );
+    let (tx, rx) = async_channel::bounded(1);
+    readbacks.requested.push(GpuReadback {
+        entity,
+        src: ReadbackSource::Texture {
+            texture: texture.clone(),
+            layout,
+            size,
+        },
+        texture: Some(ReadbackTexture { size, format }),
+        buffer,
+        rx,
+        tx,
+    });
+}
+
 pub(crate) fn submit_readback_commands(world: &World, command_encoder: &mut CommandEncoder) {
     let readbacks = world.resource::<GpuReadbacks>();
     for readback in &readbacks.requested {
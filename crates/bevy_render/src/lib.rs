@@ -9,6 +9,7 @@
 //! - `WGPU_ADAPTER_NAME` allows selecting a specific adapter by name.
 //! - `WGPU_SETTINGS_PRIO=webgl2` uses webgl2 limits.
 //! - `WGPU_SETTINGS_PRIO=compatibility` uses webgpu limits.
+//! - `WGPU_BACKEND=vulkan` selects the backends to use, and `WGPU_POWER_PREF=low` the power preference of the adapter.
 //! - `VERBOSE_SHADER_ERROR=1` prints more detailed information about WGSL compilation errors, such as shader defs and shader entrypoint.
 
 #![expect(missing_docs, reason = "Not all docs are written yet, see #3492.")]
@@ -35,6 +36,7 @@ extern crate core;
 // Required to make proc macros work in bevy itself.
 extern crate self as bevy_render;
 
+pub mod adapter_change;
 pub mod batching;
 pub mod camera;
 pub mod diagnostic;
@@ -45,9 +47,14 @@ pub mod extract_instances;
 mod extract_param;
 pub mod extract_plugin;
 pub mod extract_resource;
+pub mod frame_pace;
 pub mod globals;
+pub mod gpu_component;
 pub mod gpu_component_array_buffer;
+pub mod gpu_memory;
 pub mod gpu_readback;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod log_file;
 pub mod mesh;
 pub mod occlusion_culling;
 #[cfg(not(target_arch = "wasm32"))]
@@ -58,10 +65,12 @@ pub mod render_resource;
 pub mod renderer;
 pub mod settings;
 pub mod storage;
+pub mod suspend;
 pub mod sync_component;
 pub mod sync_world;
 pub mod texture;
 pub mod view;
+pub mod wgpu_trace;
 
 /// The render prelude.
 ///
@@ -79,28 +88,36 @@ pub use extract_plugin::{ExtractSchedule, MainWorld};
 
 use crate::{
     camera::CameraPlugin,
-    error_handler::{RenderErrorHandler, RenderState},
+    error_handler::{
+        RenderControl, RenderDeviceGeneration, RenderError, RenderErrorCode, RenderErrorHandler,
+        RenderErrorReporter, RenderRecreationSettings, RenderState,
+    },
     extract_plugin::ExtractPlugin,
+    gpu_memory::GpuMemoryPlugin,
     gpu_readback::GpuReadbackPlugin,
     mesh::{MeshRenderAssetPlugin, RenderMesh},
     render_asset::prepare_assets,
-    render_resource::PipelineCache,
+    render_resource::{
+        PipelineCache, PipelineCacheDir, PipelineCompilationProgress, SharedCompilationProgress,
+    },
     renderer::{render_system, RenderAdapterInfo, RenderGraph},
-    settings::RenderCreation,
+    settings::{Backends, RenderCreation},
     storage::StoragePlugin,
+    suspend::RenderSuspendPlugin,
     texture::TexturePlugin,
-    view::{ViewPlugin, WindowRenderPlugin},
+    view::{window::DefaultFrameLatency, ViewPlugin, WindowRenderPlugin},
+    wgpu_trace::WgpuTraceCapture,
 };
 use alloc::sync::Arc;
 use batching::gpu_preprocessing::BatchingPlugin;
-use bevy_app::{App, AppLabel, Plugin};
+use bevy_app::{App, AppLabel, First, Plugin};
 use bevy_asset::{AssetApp, AssetServer};
 use bevy_derive::Deref;
 use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
 use bevy_platform::time::Instant;
 use bevy_shader::{load_shader_library, Shader, ShaderLoader};
 use bevy_time::TimeSender;
-use bevy_window::{PrimaryWindow, RawHandleWrapperHolder};
+use bevy_window::{GraphicsBackend, GraphicsSettings, PrimaryWindow, RawHandleWrapperHolder};
 use bitflags::bitflags;
 use globals::GlobalsPlugin;
 use occlusion_culling::OcclusionCullingPlugin;
@@ -109,7 +126,11 @@ use render_asset::{
     RenderAssetBytesPerFrame, RenderAssetBytesPerFrameLimiter,
 };
 use settings::RenderResources;
-use std::sync::Mutex;
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Mutex,
+};
 
 /// Contains the default Bevy rendering backend based on wgpu.
 ///
@@ -124,9 +145,25 @@ pub struct RenderPlugin {
     pub render_creation: RenderCreation,
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, Wasm, iOS, or without the `multi_threaded` feature.
+    ///
+    /// To only create some pipelines synchronously, see
+    /// [`PipelineCache::create_synchronously`](render_resource::PipelineCache::create_synchronously).
     pub synchronous_pipeline_compilation: bool,
     /// Debugging flags that can optionally be set when constructing the renderer.
     pub debug_flags: RenderDebugFlags,
+    /// If set, the pipelines compiled by the driver are persisted in this folder, so that they
+    /// are compiled faster on the next launches of the app, see [`PipelineCacheDir`].
+    ///
+    /// [`PipelineCacheDir::in_user_cache`] returns a folder in the cache folder of the user.
+    pub pipeline_cache_dir: Option<PathBuf>,
+    /// If set, the [`WgpuSettings`](settings::WgpuSettings) of
+    /// [`RenderCreation::Automatic`] are overridden by this config file and by environment
+    /// variables, see [`WgpuSettingsOverrides`](settings::WgpuSettingsOverrides).
+    ///
+    /// Relative paths are relative to the asset base path detected from the environment, see
+    /// [`FileAssetReader::get_base_path`](bevy_asset::io::file::FileAssetReader::get_base_path).
+    /// This has no effect on the web.
+    pub wgpu_settings_file: Option<PathBuf>,
 }
 
 bitflags! {
@@ -205,6 +242,15 @@ pub enum RenderSystems {
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone, Default)]
 pub struct RenderStartup;
 
+/// The schedule of the [`RenderApp`] that runs after the [`RenderDevice`](renderer::RenderDevice)
+/// was recreated, e.g. to recover from a lost device, before [`RenderStartup`] runs again.
+///
+/// Resources holding GPU objects of the previous device, such as caches of textures, buffers or
+/// bind groups, must be dropped or recreated here. Plugins owning such resources add systems to
+/// this schedule, see [`reset_render_resource`].
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone, Default)]
+pub struct RenderDeviceReset;
+
 /// The render recovery schedule. This schedule runs the [`Render`] schedule if
 /// we are in [`RenderState::Ready`], and is otherwise hidden from users.
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
@@ -265,7 +311,7 @@ impl Render {
 }
 
 #[derive(Resource, Default, Clone, Deref)]
-pub(crate) struct FutureRenderResources(Arc<Mutex<Option<RenderResources>>>);
+pub(crate) struct FutureRenderResources(Arc<Mutex<Option<Result<RenderResources, RenderError>>>>);
 
 /// A label for the rendering sub-app.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, AppLabel)]
@@ -280,7 +326,43 @@ impl Plugin for RenderPlugin {
         load_shader_library!(app, "color_operations.wgsl");
         load_shader_library!(app, "bindless.wgsl");
 
-        if insert_future_resources(&self.render_creation, app.world_mut()) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let overridden_creation;
+        #[cfg(not(target_arch = "wasm32"))]
+        let render_creation = match (&self.render_creation, &self.wgpu_settings_file) {
+            (RenderCreation::Automatic(settings), Some(file)) => {
+                let path = bevy_asset::io::file::FileAssetReader::get_base_path().join(file);
+                overridden_creation =
+                    RenderCreation::from(settings.as_ref().clone().with_overrides(path));
+                &overridden_creation
+            }
+            _ => &self.render_creation,
+        };
+        #[cfg(target_arch = "wasm32")]
+        let render_creation = &self.render_creation;
+
+        // The backend picked in the graphics settings (e.g. loaded from a config file) is used
+        // when the renderer is created.
+        let graphics_creation;
+        let render_creation = match (
+            render_creation,
+            app.world().get_resource::<GraphicsSettings>(),
+        ) {
+            (RenderCreation::Automatic(settings), Some(graphics_settings))
+                if graphics_settings.backend != GraphicsBackend::Auto =>
+            {
+                graphics_creation = RenderCreation::from(
+                    settings
+                        .as_ref()
+                        .clone()
+                        .with_graphics_backend(graphics_settings.backend),
+                );
+                &graphics_creation
+            }
+            _ => render_creation,
+        };
+
+        if insert_future_resources(render_creation, app.world_mut()) {
             // We only create the render world and set up extraction if we
             // have a rendering backend available.
             app.add_plugins(ExtractPlugin {
@@ -301,6 +383,8 @@ impl Plugin for RenderPlugin {
             StoragePlugin,
             GpuReadbackPlugin::default(),
             OcclusionCullingPlugin,
+            RenderSuspendPlugin,
+            GpuMemoryPlugin::default(),
             #[cfg(feature = "tracing-tracy")]
             diagnostic::RenderDiagnosticsPlugin,
         ));
@@ -309,14 +393,34 @@ impl Plugin for RenderPlugin {
         app.insert_resource(receiver);
 
         let asset_server = app.world().resource::<AssetServer>().clone();
+        let compilation_progress = SharedCompilationProgress::default();
         app.init_resource::<RenderAssetBytesPerFrame>()
-            .init_resource::<RenderErrorHandler>();
+            .init_resource::<PipelineCompilationProgress>()
+            .insert_resource(compilation_progress.clone())
+            .add_systems(First, PipelineCache::sync_compilation_progress)
+            .init_resource::<RenderErrorHandler>()
+            .init_resource::<RenderControl>()
+            .init_resource::<WgpuTraceCapture>()
+            .init_resource::<RenderDeviceGeneration>()
+            .insert_resource(RenderRecreationSettings {
+                settings: match render_creation {
+                    RenderCreation::Automatic(settings) => Some(settings.clone()),
+                    RenderCreation::Manual(_) => None,
+                },
+                failed_backends: Backends::empty(),
+            });
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<RenderAssetBytesPerFrameLimiter>();
             render_app.init_resource::<renderer::PendingCommandBuffers>();
             render_app.insert_resource(sender);
             render_app.insert_resource(asset_server);
+            render_app.insert_resource(compilation_progress);
             render_app.insert_resource(RenderState::Initializing);
+            if let RenderCreation::Automatic(settings) = render_creation
+                && let Some(latency) = settings.desired_maximum_frame_latency
+            {
+                render_app.insert_resource(DefaultFrameLatency(latency));
+            }
             render_app.add_systems(
                 ExtractSchedule,
                 (
@@ -328,6 +432,10 @@ impl Plugin for RenderPlugin {
             render_app.add_schedule(RenderGraph::base_schedule());
 
             render_app.init_schedule(RenderStartup);
+            render_app.init_schedule(RenderDeviceReset);
+            if let Some(dir) = &self.pipeline_cache_dir {
+                render_app.insert_resource(PipelineCacheDir(dir.clone()));
+            }
             render_app.update_schedule = Some(RenderRecovery.intern());
             render_app.add_systems(
                 RenderRecovery,
@@ -336,7 +444,12 @@ impl Plugin for RenderPlugin {
             render_app.add_systems(
                 Render,
                 (
-                    (PipelineCache::process_pipeline_queue_system, render_system)
+                    (
+                        PipelineCache::process_pipeline_queue_system,
+                        PipelineCache::share_compilation_progress,
+                        render_system,
+                        PipelineCache::save_persistent_cache,
+                    )
                         .chain()
                         .in_set(RenderSystems::Render),
                     reset_render_asset_bytes_per_frame.in_set(RenderSystems::Cleanup),
@@ -366,13 +479,42 @@ impl Plugin for RenderPlugin {
         {
             let bevy_app::SubApps { main, sub_apps } = app.sub_apps_mut();
             let render = sub_apps.get_mut(&RenderApp.intern()).unwrap();
-            let render_resources = future_render_resources.0.lock().unwrap().take().unwrap();
+            let mut render_resources = future_render_resources.0.lock().unwrap().take().unwrap();
+            // The other plugins need a render device to finish, so initialization is retried
+            // until it succeeds, as long as the `RenderErrorHandler` asks for it.
+            let render_resources = loop {
+                match render_resources {
+                    Ok(render_resources) => break render_resources,
+                    Err(error) => {
+                        render_resources = error_handler::retry_initialization(
+                            error,
+                            main.world_mut(),
+                            render.world_mut(),
+                        );
+                    }
+                }
+            };
 
             render_resources.unpack_into(
                 main.world_mut(),
                 render.world_mut(),
                 self.synchronous_pipeline_compilation,
             );
+
+            // The backend of graphics settings inserted after this plugin was built, or of a
+            // backend the platform doesn't support, can't be used.
+            let world = main.world();
+            if let Some(graphics_settings) = world.get_resource::<GraphicsSettings>()
+                && let Some(backends) = settings::graphics_backends(graphics_settings.backend)
+                && let Some(adapter_info) = world.get_resource::<RenderAdapterInfo>()
+                && !backends.contains(adapter_info.backend.into())
+            {
+                bevy_log::warn!(
+                    "The {:?} backend of the GraphicsSettings isn't used, the renderer uses {:?}. \
+                    Insert the GraphicsSettings before adding the RenderPlugin to render with it.",
+                    graphics_settings.backend, adapter_info.backend
+                );
+            }
         }
     }
 }
@@ -381,8 +523,57 @@ fn renderer_is_ready(state: Res<RenderState>) -> bool {
     matches!(*state, RenderState::Ready)
 }
 
+/// Runs the [`Render`] schedule, reporting its panics to the [`RenderErrorHandler`] as
+/// [`RenderErrorCode::Panic`] errors instead of aborting the app.
 fn run_render_schedule(world: &mut World) {
-    world.run_schedule(Render);
+    if let Err(message) = run_schedule_catching_panics(world, Render) {
+        report_panic(world, "render schedule", &message);
+    }
+}
+
+/// Runs the schedule `label`, returning the message of its panic if it panicked.
+///
+/// The executor of a panicked schedule is reset, so that the schedule can run again.
+pub(crate) fn run_schedule_catching_panics(
+    world: &mut World,
+    label: impl ScheduleLabel,
+) -> Result<(), String> {
+    world.schedule_scope(label, |world, schedule| {
+        catch_unwind(AssertUnwindSafe(|| schedule.run(world))).map_err(|payload| {
+            schedule.reset_executor();
+            payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic payload".to_string())
+        })
+    })
+}
+
+/// Reports a panic caught by [`run_schedule_catching_panics`] to the [`RenderErrorHandler`], or
+/// resumes it if the renderer isn't initialized, as there is no handler to report it to yet.
+pub(crate) fn report_panic(world: &World, what: &str, message: &str) {
+    let description = format!("The {what} panicked: {message}");
+    match world.get_resource::<RenderErrorReporter>() {
+        Some(reporter) => reporter.report(RenderError::new(RenderErrorCode::Panic, description)),
+        None => panic!("{description}"),
+    }
+}
+
+/// Recreates the resource `R` with [`FromWorld`] if it exists, dropping the GPU objects it
+/// holds.
+///
+/// This is meant to be added to the [`RenderDeviceReset`] schedule for resources created from
+/// the [`RenderDevice`](renderer::RenderDevice) outside of [`RenderStartup`]:
+///
+/// ```ignore
+/// render_app.add_systems(RenderDeviceReset, reset_render_resource::<TextureCache>);
+/// ```
+pub fn reset_render_resource<R: Resource + FromWorld>(world: &mut World) {
+    if world.contains_resource::<R>() {
+        let resource = R::from_world(world);
+        world.insert_resource(resource);
+    }
 }
 
 fn send_time(time_sender: Res<TimeSender>) {
@@ -0,0 +1,245 @@
+//! Mirrors the warnings and errors of the renderer into a log file.
+
+use bevy_log::{
+    tracing::{
+        field::{Field, Visit},
+        Event, Level, Subscriber,
+    },
+    tracing_subscriber::{layer::Context, Layer},
+    BoxedLayer,
+};
+use core::fmt::{self, Write as _};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write as _,
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The targets of the logs mirrored by [`RenderLogFile`].
+const RENDER_LOG_TARGETS: [&str; 4] = ["bevy_render", "wgpu", "naga", "bevy_core_pipeline"];
+
+/// Mirrors the warnings and errors logged by the renderer and wgpu, like surface errors and
+/// validation errors, into a rolling log file.
+///
+/// Apps without a console, like packaged Windows apps, can then collect the file in bug reports,
+/// instead of only hearing about a black screen. The file is written to as the logs are emitted,
+/// so it also holds the logs of the renderer before a crash.
+///
+/// This is a [`tracing`](bevy_log::tracing) layer, added with
+/// [`LogPlugin::custom_layer`](bevy_log::LogPlugin::custom_layer):
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_log::LogPlugin;
+/// # use bevy_render::log_file::RenderLogFile;
+/// App::new().add_plugins(LogPlugin {
+///     custom_layer: |_| {
+///         let directory = bevy_platform::dirs::preferences_dir()?.join("my_game");
+///         Some(RenderLogFile::new(directory).layer())
+///     },
+///     ..Default::default()
+/// });
+/// ```
+///
+/// Packaged apps should use their own local state folder, as the folders of the user may not be
+/// writable from their sandbox.
+#[derive(Debug, Clone)]
+pub struct RenderLogFile {
+    /// The folder the log files are written to. It is created if it doesn't exist.
+    pub directory: PathBuf,
+    /// The name of the current log file. Older files get a number before the extension, e.g.
+    /// `render.1.log`.
+    pub file_name: String,
+    /// The size in bytes above which the current file is rotated.
+    pub max_file_size: u64,
+    /// The number of files kept, including the current one.
+    pub max_files: usize,
+    /// The most verbose level that is mirrored.
+    pub level: Level,
+}
+
+impl RenderLogFile {
+    /// Mirrors the warnings and errors of the renderer to `render.log` in `directory`, keeping
+    /// three files of up to 1 MiB.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_name: "render.log".to_string(),
+            max_file_size: 1024 * 1024,
+            max_files: 3,
+            level: Level::WARN,
+        }
+    }
+
+    /// Returns the path of the `index`th log file, the current one being `0`.
+    pub fn path(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            return self.directory.join(&self.file_name);
+        }
+        let path = PathBuf::from(&self.file_name);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = match path.extension() {
+            Some(extension) => format!("{stem}.{index}.{}", extension.to_string_lossy()),
+            None => format!("{stem}.{index}"),
+        };
+        self.directory.join(file_name)
+    }
+
+    /// Creates the layer writing to the log file.
+    pub fn layer(self) -> BoxedLayer {
+        Box::new(RenderLogFileLayer {
+            state: Mutex::new(RenderLogFileState {
+                file: None,
+                size: 0,
+            }),
+            settings: self,
+        })
+    }
+}
+
+struct RenderLogFileLayer {
+    settings: RenderLogFile,
+    state: Mutex<RenderLogFileState>,
+}
+
+struct RenderLogFileState {
+    /// The current log file, opened on the first log.
+    file: Option<File>,
+    /// The size of the current log file.
+    size: u64,
+}
+
+impl RenderLogFileLayer {
+    fn write(&self, line: &str) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if state.file.is_some() && state.size + line.len() as u64 > self.settings.max_file_size {
+            state.file = None;
+            self.rotate();
+        }
+        if state.file.is_none() {
+            // Failing to open the file can't be logged, as it would be mirrored again.
+            let _ = fs::create_dir_all(&self.settings.directory);
+            let path = self.settings.path(0);
+            let Ok(file) = OpenOptions::new().create(true).append(true).open(&path) else {
+                return;
+            };
+            state.size = file.metadata().map_or(0, |metadata| metadata.len());
+            state.file = Some(file);
+        }
+        if let Some(file) = &mut state.file
+            && file.write_all(line.as_bytes()).is_ok()
+        {
+            state.size += line.len() as u64;
+        }
+    }
+
+    /// Shifts the log files by one, dropping the oldest one.
+    fn rotate(&self) {
+        let max_files = self.settings.max_files.max(1);
+        let _ = fs::remove_file(self.settings.path(max_files - 1));
+        for index in (0..max_files - 1).rev() {
+            let _ = fs::rename(self.settings.path(index), self.settings.path(index + 1));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RenderLogFileLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if *metadata.level() > self.settings.level
+            || !RENDER_LOG_TARGETS
+                .iter()
+                .any(|target| metadata.target().starts_with(target))
+        {
+            return;
+        }
+
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let mut line = format!(
+            "{}.{:03} {} {}:",
+            time.as_secs(),
+            time.subsec_millis(),
+            metadata.level(),
+            metadata.target()
+        );
+        event.record(&mut LineVisitor(&mut line));
+        line.push('\n');
+        self.write(&line);
+    }
+}
+
+/// Appends the fields of an event to a log line.
+struct LineVisitor<'a>(&'a mut String);
+
+impl Visit for LineVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.0, " {value:?}");
+        } else {
+            let _ = write!(self.0, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderLogFile;
+    use bevy_log::{
+        tracing::{self, subscriber},
+        tracing_subscriber::{layer::SubscriberExt, registry},
+    };
+    use std::{fs, path::Path};
+
+    #[test]
+    fn rotated_file_paths() {
+        let log_file = RenderLogFile::new("logs");
+        assert_eq!(log_file.path(0), Path::new("logs").join("render.log"));
+        assert_eq!(log_file.path(2), Path::new("logs").join("render.2.log"));
+
+        let log_file = RenderLogFile {
+            file_name: "render".to_string(),
+            ..log_file
+        };
+        assert_eq!(log_file.path(1), Path::new("logs").join("render.1"));
+    }
+
+    #[test]
+    fn render_logs_are_mirrored_to_rotating_files() {
+        let directory =
+            std::env::temp_dir().join(format!("bevy_render_log_file_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        // Each line is too long for two to fit in a file.
+        let log_file = RenderLogFile {
+            max_file_size: 64,
+            max_files: 2,
+            ..RenderLogFile::new(&directory)
+        };
+
+        subscriber::with_default(registry().with(log_file.clone().layer()), || {
+            tracing::warn!(target: "wgpu", "first");
+            tracing::info!(target: "wgpu", "too verbose");
+            tracing::error!(target: "my_game", "not from the renderer");
+            tracing::error!(target: "bevy_render::view", surface = 1, "second");
+            tracing::warn!(target: "naga", "third");
+        });
+
+        let current = fs::read_to_string(log_file.path(0)).unwrap();
+        let previous = fs::read_to_string(log_file.path(1)).unwrap();
+        assert_eq!(current.lines().count(), 1);
+        assert!(current.contains(" WARN naga: third"));
+        assert_eq!(previous.lines().count(), 1);
+        assert!(previous.contains(" ERROR bevy_render::view:"));
+        assert!(previous.contains(" surface=1"));
+        assert!(previous.contains(" second"));
+        // The oldest file is dropped.
+        assert!(!log_file.path(2).exists());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
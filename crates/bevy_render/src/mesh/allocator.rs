@@ -31,7 +31,7 @@ use crate::{
     render_asset::{prepare_assets, ExtractedAssets},
     render_resource::Buffer,
     renderer::{RenderAdapter, RenderDevice, RenderQueue},
-    Render, RenderApp, RenderSystems,
+    reset_render_resource, Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 
 /// A plugin that manages GPU memory for mesh data.
@@ -330,7 +330,8 @@ impl Plugin for MeshAllocatorPlugin {
                 allocate_and_free_meshes
                     .in_set(RenderSystems::PrepareAssets)
                     .before(prepare_assets::<RenderMesh>),
-            );
+            )
+            .add_systems(RenderDeviceReset, reset_render_resource::<MeshAllocator>);
     }
 
     fn finish(&self, app: &mut App) {
@@ -8,7 +8,7 @@ use bevy_ecs::{
 };
 use bevy_tasks::ComputeTaskPool;
 
-use crate::RenderApp;
+use crate::{report_panic, run_schedule_catching_panics, RenderApp};
 
 /// A Label for the sub app that runs the parts of pipelined rendering that need to run on the main thread.
 ///
@@ -166,7 +166,15 @@ impl Plugin for PipelinedRenderingPlugin {
                     #[cfg(feature = "trace")]
                     let _sub_app_span =
                         bevy_log::info_span!("sub app", name = ?RenderApp).entered();
-                    render_app.update();
+                    // This is `SubApp::update`, with the panics of the systems around the render
+                    // schedule reported like those of the render schedule.
+                    if let Some(label) = render_app.update_schedule
+                        && let Err(message) =
+                            run_schedule_catching_panics(render_app.world_mut(), label)
+                    {
+                        report_panic(render_app.world(), "render thread", &message);
+                    }
+                    render_app.world_mut().clear_trackers();
                 }
 
                 if render_to_app_sender.send_blocking(render_app).is_err() {
@@ -1,6 +1,6 @@
 use crate::{
-    render_resource::AsBindGroupError, Extract, ExtractSchedule, MainWorld, Render, RenderApp,
-    RenderSystems, Res,
+    error_handler::RenderDeviceGeneration, render_resource::AsBindGroupError, Extract,
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderSystems, Res,
 };
 use bevy_app::{App, Plugin, SubApp};
 use bevy_asset::{Asset, AssetEvent, AssetId, Assets, RenderAssetUsages};
@@ -244,12 +244,17 @@ struct CachedExtractRenderAssetSystemState<A: RenderAsset> {
         ResMut<'static, Assets<A::SourceAsset>>,
         Option<Res<'static, RenderAssets<A>>>,
     )>,
+    device_generation: RenderDeviceGeneration,
 }
 
 impl<A: RenderAsset> FromWorld for CachedExtractRenderAssetSystemState<A> {
     fn from_world(world: &mut bevy_ecs::world::World) -> Self {
         Self {
             state: SystemState::new(world),
+            device_generation: world
+                .get_resource::<RenderDeviceGeneration>()
+                .copied()
+                .unwrap_or_default(),
         }
     }
 }
@@ -262,9 +267,21 @@ pub(crate) fn extract_render_asset<A: RenderAsset>(
 ) {
     main_world.resource_scope(
         |world, mut cached_state: Mut<CachedExtractRenderAssetSystemState<A>>| {
+            let device_generation = world
+                .get_resource::<RenderDeviceGeneration>()
+                .copied()
+                .unwrap_or_default();
+            let device_recreated = cached_state.device_generation != device_generation;
+            cached_state.device_generation = device_generation;
             let (mut events, mut assets, maybe_render_assets) = cached_state.state.get_mut(world);
 
             let mut needs_extracting = <HashSet<_>>::default();
+            if device_recreated {
+                // The GPU representations of the assets were created on the previous device, so
+                // every asset is extracted again.
+                needs_extracting.extend(assets.ids());
+                commands.insert_resource(RenderAssets::<A>::default());
+            }
             let mut removed = <HashSet<_>>::default();
             let mut modified = <HashSet<_>>::default();
 
@@ -4,28 +4,38 @@ use bevy_material::descriptor::{
 };
 
 use crate::{
+    error_handler::{RenderError, RenderErrorCode, RenderErrorReporter},
     render_resource::*,
     renderer::{RenderAdapter, RenderDevice, WgpuWrapper},
+    settings::WgpuFeatures,
     Extract,
 };
 use alloc::{borrow::Cow, sync::Arc};
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{
+    change_detection::DetectChangesMut,
     message::MessageReader,
     resource::Resource,
     system::{Res, ResMut},
 };
-use bevy_log::error;
+use bevy_log::{error, warn};
 use bevy_platform::collections::{HashMap, HashSet};
 use bevy_shader::{
     CachedPipelineId, Shader, ShaderCache, ShaderCacheError, ShaderCacheSource, ShaderDefVal,
     ValidateShader,
 };
-use bevy_tasks::Task;
-use bevy_utils::default;
-use core::{future::Future, mem};
-use std::sync::{Mutex, PoisonError};
-use wgpu::{PipelineCompilationOptions, VertexBufferLayout as RawVertexBufferLayout};
+use bevy_tasks::{IoTaskPool, Task};
+use bevy_utils::{default, define_atomic_id};
+use core::{
+    future::Future,
+    mem,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    path::PathBuf,
+    sync::{Mutex, PoisonError},
+};
+use wgpu::{AdapterInfo, PipelineCompilationOptions, VertexBufferLayout as RawVertexBufferLayout};
 
 /// A pipeline defining the data layout and shader logic for a specific GPU task.
 ///
@@ -186,6 +196,8 @@ impl BindGroupLayoutCache {
     }
 }
 
+define_atomic_id!(PipelineCacheId);
+
 /// Cache for render and compute pipelines.
 ///
 /// The cache stores existing render and compute pipelines allocated on the GPU, as well as
@@ -200,6 +212,7 @@ impl BindGroupLayoutCache {
 /// [`RenderSystems::Render`]: crate::RenderSystems::Render
 #[derive(Resource)]
 pub struct PipelineCache {
+    id: PipelineCacheId,
     layout_cache: Arc<Mutex<LayoutCache>>,
     bindgroup_layout_cache: Arc<Mutex<BindGroupLayoutCache>>,
     shader_cache: Arc<Mutex<ShaderCache<WgpuWrapper<ShaderModule>, RenderDevice>>>,
@@ -211,9 +224,145 @@ pub struct PipelineCache {
     /// If `true`, disables asynchronous pipeline compilation.
     /// This has no effect on macOS, wasm, or without the `multi_threaded` feature.
     pub(crate) synchronous_pipeline_compilation: bool,
+    /// The pipelines that must be created synchronously, see
+    /// [`PipelineCache::create_synchronously`].
+    synchronous_pipelines: Mutex<HashSet<CachedPipelineId>>,
+    /// Reports the shaders that failed to compile to the
+    /// [`RenderErrorHandler`](crate::error_handler::RenderErrorHandler).
+    pub(crate) error_reporter: Option<RenderErrorReporter>,
+    /// The driver's cache of compiled pipelines, and the file it is persisted to, see
+    /// [`PipelineCacheDir`].
+    persistent_cache: Option<(Arc<WgpuWrapper<wgpu::PipelineCache>>, PathBuf)>,
+    /// Whether pipelines were created since the `persistent_cache` was last saved.
+    unsaved_pipelines: bool,
+}
+
+/// The folder where the pipelines compiled by the driver are persisted, so that they are compiled
+/// faster on the next launches of the app. Each adapter has its own file in this folder.
+///
+/// This resource is in the render world, see
+/// [`RenderPlugin::pipeline_cache_dir`](crate::RenderPlugin::pipeline_cache_dir). It only has an
+/// effect when the render device supports [`WgpuFeatures::PIPELINE_CACHE`], currently on Vulkan.
+#[derive(Resource, Clone, Debug)]
+pub struct PipelineCacheDir(pub PathBuf);
+
+impl PipelineCacheDir {
+    /// Returns the `pipelines` folder of the app named `app_name` in the cache folder of the user:
+    /// - On Windows, this is `%LOCALAPPDATA%\{app_name}\pipelines`.
+    /// - On macOS, this is `~/Library/Caches/{app_name}/pipelines`.
+    /// - On other platforms, this is `$XDG_CACHE_HOME/{app_name}/pipelines`, or
+    ///   `~/.cache/{app_name}/pipelines` if `XDG_CACHE_HOME` isn't set.
+    ///
+    /// Returns [`None`] if the cache folder of the user can't be determined.
+    pub fn in_user_cache(app_name: &str) -> Option<Self> {
+        #[cfg(target_os = "windows")]
+        let cache = PathBuf::from(std::env::var_os("LOCALAPPDATA")?);
+
+        #[cfg(target_os = "macos")]
+        let cache = PathBuf::from(std::env::var_os("HOME")?).join("Library/Caches");
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let cache = match std::env::var_os("XDG_CACHE_HOME").map(PathBuf::from) {
+            // Relative paths are invalid according to the XDG specification.
+            Some(cache) if cache.is_absolute() => cache,
+            _ => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+        };
+
+        Some(Self(cache.join(app_name).join("pipelines")))
+    }
 }
 
+/// The progress of the compilation of the pipelines queued in the [`PipelineCache`]. This resource
+/// is in the main world, and is updated at the start of each frame with the progress at the end of
+/// the last rendered frame, once its pipelines were queued.
+///
+/// Pipelines are queued when the entities that need them are first rendered, and compiling them
+/// can cause hitches. Apps can show a loading screen until [`all_pipelines_ready`] instead, e.g.
+/// while rendering the scene behind it.
+///
+/// With pipelined rendering, the last rendered frame is the one before the previous frame, as
+/// it is rendered while the main world updates.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipelineCompilationProgress {
+    /// Whether a frame was rendered yet. Until then, the pipelines of the entities spawned at
+    /// startup aren't queued.
+    pub rendered: bool,
+    /// The number of pipelines waiting to be compiled, e.g. because their shaders are loading.
+    pub queued: usize,
+    /// The number of pipelines being compiled.
+    pub compiling: usize,
+    /// The number of pipelines that are ready to be used.
+    pub ready: usize,
+    /// The number of pipelines that failed to compile.
+    pub failed: usize,
+}
+
+impl PipelineCompilationProgress {
+    /// Returns the number of pipelines that are queued or being compiled.
+    pub fn pending(&self) -> usize {
+        self.queued + self.compiling
+    }
+
+    /// Returns the fraction of the pipelines that are done compiling, successfully or not, from
+    /// `0.0` to `1.0`.
+    pub fn fraction_done(&self) -> f32 {
+        let done = self.ready + self.failed;
+        let total = done + self.pending();
+        if total == 0 {
+            1.0
+        } else {
+            done as f32 / total as f32
+        }
+    }
+}
+
+/// A run condition that returns `true` when no pipeline is queued or being compiled, once a frame
+/// was rendered, see [`PipelineCompilationProgress`].
+///
+/// ```
+/// # use bevy_app::{App, Update};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::render_resource::all_pipelines_ready;
+/// # fn start_gameplay() {}
+/// # let mut app = App::new();
+/// app.add_systems(Update, start_gameplay.run_if(all_pipelines_ready));
+/// ```
+pub fn all_pipelines_ready(progress: Option<Res<PipelineCompilationProgress>>) -> bool {
+    progress.is_some_and(|progress| progress.rendered && progress.pending() == 0)
+}
+
+/// The [`PipelineCompilationProgress`] of the last rendered frame, shared by the render world that
+/// sets it and the main world that takes it.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct SharedCompilationProgress(Arc<Mutex<Option<PipelineCompilationProgress>>>);
+
 impl PipelineCache {
+    /// Returns the progress of the compilation of the pipelines in the pipeline cache.
+    pub fn compilation_progress(&self) -> PipelineCompilationProgress {
+        let mut progress = PipelineCompilationProgress {
+            rendered: true,
+            queued: self
+                .new_pipelines
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .len(),
+            ..default()
+        };
+        for pipeline in &self.pipelines {
+            match &pipeline.state {
+                CachedPipelineState::Queued
+                | CachedPipelineState::Err(
+                    ShaderCacheError::ShaderNotLoaded(_)
+                    | ShaderCacheError::ShaderImportNotYetAvailable,
+                ) => progress.queued += 1,
+                CachedPipelineState::Creating(_) => progress.compiling += 1,
+                CachedPipelineState::Ok(_) => progress.ready += 1,
+                CachedPipelineState::Err(_) => progress.failed += 1,
+            }
+        }
+        progress
+    }
+
     /// Returns an iterator over the pipelines in the pipeline cache.
     pub fn pipelines(&self) -> impl Iterator<Item = &CachedPipeline> {
         self.pipelines.iter()
@@ -248,6 +397,7 @@ impl PipelineCache {
         ));
 
         Self {
+            id: PipelineCacheId::new(),
             shader_cache: Arc::new(Mutex::new(ShaderCache::new(
                 device.clone(),
                 device.features(),
@@ -262,9 +412,22 @@ impl PipelineCache {
             pipelines: default(),
             global_shader_defs,
             synchronous_pipeline_compilation,
+            synchronous_pipelines: default(),
+            error_reporter: None,
+            persistent_cache: None,
+            unsaved_pipelines: false,
         }
     }
 
+    /// Returns the unique identifier of this cache.
+    ///
+    /// A new cache is created along with every [`RenderDevice`], so the ids of the pipelines
+    /// queued in another cache are invalid in this one.
+    #[inline]
+    pub fn id(&self) -> PipelineCacheId {
+        self.id
+    }
+
     /// Get the state of a cached render pipeline.
     ///
     /// See [`PipelineCache::queue_render_pipeline()`].
@@ -402,6 +565,23 @@ impl PipelineCache {
         id
     }
 
+    /// Marks the pipeline `id` to be created synchronously when the queue is next processed, so
+    /// that it's ready before its first use, even though the other pipelines are created
+    /// asynchronously, see [`RenderPlugin::synchronous_pipeline_compilation`].
+    ///
+    /// This blocks the render world until the pipeline is created, so it should be reserved to
+    /// the pipelines that would visibly pop in, rather than used for all of them at startup. A
+    /// pipeline whose shaders aren't loaded yet is still created once they are. The mark is removed
+    /// once the pipeline is created or fails to compile.
+    ///
+    /// [`RenderPlugin::synchronous_pipeline_compilation`]: crate::RenderPlugin::synchronous_pipeline_compilation
+    pub fn create_synchronously(&self, id: CachedPipelineId) {
+        self.synchronous_pipelines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(id);
+    }
+
     /// Insert a compute pipeline into the cache, and queue its creation.
     ///
     /// The pipeline is always inserted and queued for creation. There is no attempt to deduplicate it with
@@ -465,10 +645,15 @@ impl PipelineCache {
         &mut self,
         id: CachedPipelineId,
         descriptor: RenderPipelineDescriptor,
+        sync: bool,
     ) -> CachedPipelineState {
         let device = self.device.clone();
         let shader_cache = self.shader_cache.clone();
         let layout_cache = self.layout_cache.clone();
+        let wgpu_cache = self
+            .persistent_cache
+            .as_ref()
+            .map(|(cache, _)| cache.clone());
         let mut bindgroup_layout_cache = self.bindgroup_layout_cache.lock().unwrap();
         let bind_group_layout = descriptor
             .layout
@@ -558,14 +743,14 @@ impl PipelineCache {
                             // TODO: Should this be the same as the vertex compilation options?
                             compilation_options,
                         }),
-                    cache: None,
+                    cache: wgpu_cache.as_deref().map(|cache| &**cache),
                 };
 
                 Ok(Pipeline::RenderPipeline(
                     device.create_render_pipeline(&descriptor),
                 ))
             },
-            self.synchronous_pipeline_compilation,
+            sync,
         )
     }
 
@@ -573,10 +758,15 @@ impl PipelineCache {
         &mut self,
         id: CachedPipelineId,
         descriptor: ComputePipelineDescriptor,
+        sync: bool,
     ) -> CachedPipelineState {
         let device = self.device.clone();
         let shader_cache = self.shader_cache.clone();
         let layout_cache = self.layout_cache.clone();
+        let wgpu_cache = self
+            .persistent_cache
+            .as_ref()
+            .map(|(cache, _)| cache.clone());
         let mut bindgroup_layout_cache = self.bindgroup_layout_cache.lock().unwrap();
         let bind_group_layout = descriptor
             .layout
@@ -616,14 +806,14 @@ impl PipelineCache {
                         zero_initialize_workgroup_memory: descriptor
                             .zero_initialize_workgroup_memory,
                     },
-                    cache: None,
+                    cache: wgpu_cache.as_deref().map(|cache| &**cache),
                 };
 
                 Ok(Pipeline::ComputePipeline(
                     device.create_compute_pipeline(&descriptor),
                 ))
             },
-            self.synchronous_pipeline_compilation,
+            sync,
         )
     }
 
@@ -653,22 +843,57 @@ impl PipelineCache {
             self.process_pipeline(&mut pipelines[id], id);
         }
 
+        self.synchronous_pipelines
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|id| {
+                // Pipelines that are created or failed to compile are no longer pending.
+                pipelines.get(*id).is_some_and(|pipeline| {
+                    matches!(
+                        pipeline.state,
+                        CachedPipelineState::Queued
+                            | CachedPipelineState::Creating(_)
+                            | CachedPipelineState::Err(
+                                ShaderCacheError::ShaderNotLoaded(_)
+                                    | ShaderCacheError::ShaderImportNotYetAvailable
+                            )
+                    )
+                })
+            });
+
         self.pipelines = pipelines;
     }
 
     fn process_pipeline(&mut self, cached_pipeline: &mut CachedPipeline, id: usize) {
+        let sync = self.synchronous_pipeline_compilation
+            || self
+                .synchronous_pipelines
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .contains(&id);
+
         match &mut cached_pipeline.state {
             CachedPipelineState::Queued => {
+                self.unsaved_pipelines = true;
                 cached_pipeline.state = match &cached_pipeline.descriptor {
                     PipelineDescriptor::RenderPipelineDescriptor(descriptor) => {
-                        self.start_create_render_pipeline(id, *descriptor.clone())
+                        self.start_create_render_pipeline(id, *descriptor.clone(), sync)
                     }
                     PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
-                        self.start_create_compute_pipeline(id, *descriptor.clone())
+                        self.start_create_compute_pipeline(id, *descriptor.clone(), sync)
                     }
                 };
             }
 
+            // The pipeline was marked as synchronous while it was being created asynchronously.
+            CachedPipelineState::Creating(task) if sync => match bevy_tasks::block_on(task) {
+                Ok(pipeline) => {
+                    cached_pipeline.state = CachedPipelineState::Ok(pipeline);
+                    return;
+                }
+                Err(err) => cached_pipeline.state = CachedPipelineState::Err(err),
+            },
+
             CachedPipelineState::Creating(task) => match bevy_tasks::futures::check_ready(task) {
                 Some(Ok(pipeline)) => {
                     cached_pipeline.state = CachedPipelineState::Ok(pipeline);
@@ -694,11 +919,15 @@ impl PipelineCache {
                     {
                         error!("{}", pipeline_error_context(cached_pipeline));
                     }
-                    error!("failed to process shader error:\n{}", error_detail);
+                    self.report_shader_error(format!(
+                        "failed to process shader error:\n{error_detail}"
+                    ));
                     return;
                 }
                 ShaderCacheError::CreateShaderModule(description) => {
-                    error!("failed to create shader module: {}", description);
+                    self.report_shader_error(format!(
+                        "failed to create shader module: {description}"
+                    ));
                     return;
                 }
             },
@@ -710,10 +939,119 @@ impl PipelineCache {
         self.waiting_pipelines.insert(id);
     }
 
+    fn report_shader_error(&self, description: String) {
+        match &self.error_reporter {
+            Some(reporter) => {
+                reporter.report(RenderError::new(
+                    RenderErrorCode::ShaderCompile,
+                    description,
+                ));
+            }
+            None => error!("{description}"),
+        }
+    }
+
     pub(crate) fn process_pipeline_queue_system(mut cache: ResMut<Self>) {
         cache.process_queue();
     }
 
+    /// Creates the driver's cache of compiled pipelines from the file of the adapter in `dir`, if
+    /// the device supports it.
+    pub(crate) fn load_persistent_cache(
+        &mut self,
+        dir: &PipelineCacheDir,
+        adapter_info: &AdapterInfo,
+    ) {
+        if !self
+            .device
+            .features()
+            .contains(WgpuFeatures::PIPELINE_CACHE)
+        {
+            return;
+        }
+        let Some(key) = wgpu::util::pipeline_cache_key(adapter_info) else {
+            return;
+        };
+        let path = dir.0.join(key);
+        let data = std::fs::read(&path).ok();
+        // SAFETY: The data was returned by `get_data` on a cache of an adapter with the same key.
+        // Data that is outdated or corrupted is detected by wgpu, which creates an empty cache
+        // instead thanks to `fallback`.
+        let wgpu_cache = unsafe {
+            self.device
+                .wgpu_device()
+                .create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                    label: Some("bevy_pipeline_cache"),
+                    data: data.as_deref(),
+                    fallback: true,
+                })
+        };
+        self.persistent_cache = Some((Arc::new(WgpuWrapper::new(wgpu_cache)), path));
+    }
+
+    /// Saves the driver's cache of compiled pipelines to the [`PipelineCacheDir`] once the queued
+    /// pipelines are created.
+    pub(crate) fn save_persistent_cache(mut cache: ResMut<Self>) {
+        if !cache.unsaved_pipelines || !cache.waiting_pipelines.is_empty() {
+            return;
+        }
+        cache.unsaved_pipelines = false;
+        let Some((wgpu_cache, path)) = &cache.persistent_cache else {
+            return;
+        };
+        let Some(data) = wgpu_cache.get_data() else {
+            return;
+        };
+        let path = path.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                // The cache is written to a temporary file first, so that it's never read partially
+                // written. Each save has its own temporary file, as saves of this and other apps
+                // using the same adapter can run concurrently.
+                static SAVES: AtomicUsize = AtomicUsize::new(0);
+                let temp_path = path.with_extension(format!(
+                    "{}.{}.tmp",
+                    std::process::id(),
+                    SAVES.fetch_add(1, Ordering::Relaxed)
+                ));
+                let result = path
+                    .parent()
+                    .map_or(Ok(()), std::fs::create_dir_all)
+                    .and_then(|()| std::fs::write(&temp_path, &data))
+                    .and_then(|()| std::fs::rename(&temp_path, &path));
+                if let Err(error) = result {
+                    warn!("Failed to save the pipeline cache to {path:?}: {error}");
+                }
+            })
+            .detach();
+    }
+
+    /// Shares the [`PipelineCompilationProgress`] of the rendered frame with the main world, once
+    /// the pipelines queued by this frame are processed.
+    pub(crate) fn share_compilation_progress(
+        cache: Res<Self>,
+        shared: Res<SharedCompilationProgress>,
+    ) {
+        *shared.0.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some(cache.compilation_progress());
+    }
+
+    /// Updates the [`PipelineCompilationProgress`] of the main world with the progress of the last
+    /// rendered frame.
+    pub(crate) fn sync_compilation_progress(
+        shared: Res<SharedCompilationProgress>,
+        mut progress: ResMut<PipelineCompilationProgress>,
+    ) {
+        if let Some(rendered) = shared
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            progress.set_if_neq(rendered);
+        }
+    }
+
     pub(crate) fn extract_shaders(
         mut cache: ResMut<Self>,
         shaders: Extract<Res<Assets<Shader>>>,
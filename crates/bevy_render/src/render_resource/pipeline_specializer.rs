@@ -3,7 +3,7 @@ use bevy_material::descriptor::{
     RenderPipelineDescriptor,
 };
 
-use crate::render_resource::PipelineCache;
+use crate::render_resource::{PipelineCache, PipelineCacheId};
 use bevy_ecs::resource::Resource;
 use bevy_log::error;
 use bevy_material::specialize::SpecializedMeshPipelineError;
@@ -50,11 +50,16 @@ pub trait SpecializedRenderPipeline {
 #[derive(Resource)]
 pub struct SpecializedRenderPipelines<S: SpecializedRenderPipeline> {
     cache: HashMap<S::Key, CachedRenderPipelineId>,
+    /// The [`PipelineCache`] the pipelines were queued in.
+    pipeline_cache: Option<PipelineCacheId>,
 }
 
 impl<S: SpecializedRenderPipeline> Default for SpecializedRenderPipelines<S> {
     fn default() -> Self {
-        Self { cache: default() }
+        Self {
+            cache: default(),
+            pipeline_cache: None,
+        }
     }
 }
 
@@ -66,6 +71,11 @@ impl<S: SpecializedRenderPipeline> SpecializedRenderPipelines<S> {
         pipeline_specializer: &S,
         key: S::Key,
     ) -> CachedRenderPipelineId {
+        // The pipeline cache is replaced when the render device is recreated.
+        if self.pipeline_cache != Some(cache.id()) {
+            self.cache.clear();
+            self.pipeline_cache = Some(cache.id());
+        }
         *self.cache.entry(key.clone()).or_insert_with(|| {
             let descriptor = pipeline_specializer.specialize(key);
             cache.queue_render_pipeline(descriptor)
@@ -105,11 +115,16 @@ pub trait SpecializedComputePipeline {
 #[derive(Resource)]
 pub struct SpecializedComputePipelines<S: SpecializedComputePipeline> {
     cache: HashMap<S::Key, CachedComputePipelineId>,
+    /// The [`PipelineCache`] the pipelines were queued in.
+    pipeline_cache: Option<PipelineCacheId>,
 }
 
 impl<S: SpecializedComputePipeline> Default for SpecializedComputePipelines<S> {
     fn default() -> Self {
-        Self { cache: default() }
+        Self {
+            cache: default(),
+            pipeline_cache: None,
+        }
     }
 }
 
@@ -121,6 +136,11 @@ impl<S: SpecializedComputePipeline> SpecializedComputePipelines<S> {
         specialize_pipeline: &S,
         key: S::Key,
     ) -> CachedComputePipelineId {
+        // The pipeline cache is replaced when the render device is recreated.
+        if self.pipeline_cache != Some(cache.id()) {
+            self.cache.clear();
+            self.pipeline_cache = Some(cache.id());
+        }
         *self.cache.entry(key.clone()).or_insert_with(|| {
             let descriptor = specialize_pipeline.specialize(key);
             cache.queue_compute_pipeline(descriptor)
@@ -153,6 +173,8 @@ pub trait SpecializedMeshPipeline {
 pub struct SpecializedMeshPipelines<S: SpecializedMeshPipeline> {
     mesh_layout_cache: HashMap<(MeshVertexBufferLayoutRef, S::Key), CachedRenderPipelineId>,
     vertex_layout_cache: VertexLayoutCache<S>,
+    /// The [`PipelineCache`] the pipelines were queued in.
+    pipeline_cache: Option<PipelineCacheId>,
 }
 
 type VertexLayoutCache<S> = HashMap<
@@ -165,6 +187,7 @@ impl<S: SpecializedMeshPipeline> Default for SpecializedMeshPipelines<S> {
         Self {
             mesh_layout_cache: Default::default(),
             vertex_layout_cache: Default::default(),
+            pipeline_cache: None,
         }
     }
 }
@@ -180,6 +203,12 @@ impl<S: SpecializedMeshPipeline> SpecializedMeshPipelines<S> {
         key: S::Key,
         layout: &MeshVertexBufferLayoutRef,
     ) -> Result<CachedRenderPipelineId, SpecializedMeshPipelineError> {
+        // The pipeline cache is replaced when the render device is recreated.
+        if self.pipeline_cache != Some(cache.id()) {
+            self.mesh_layout_cache.clear();
+            self.vertex_layout_cache.clear();
+            self.pipeline_cache = Some(cache.id());
+        }
         return match self.mesh_layout_cache.entry((layout.clone(), key.clone())) {
             Entry::Occupied(entry) => Ok(*entry.into_mut()),
             Entry::Vacant(entry) => specialize_slow(
@@ -3,7 +3,7 @@ use bevy_material::descriptor::{
     RenderPipelineDescriptor,
 };
 
-use super::{ComputePipeline, PipelineCache, RenderPipeline};
+use super::{ComputePipeline, PipelineCache, PipelineCacheId, RenderPipeline};
 use bevy_ecs::error::BevyError;
 use bevy_log::error;
 use bevy_platform::{
@@ -271,6 +271,8 @@ pub struct Variants<T: Specializable, S: Specializer<T>> {
     base_descriptor: T::Descriptor,
     primary_cache: HashMap<S::Key, T::CachedId>,
     secondary_cache: HashMap<Canonical<S::Key>, T::CachedId>,
+    /// The [`PipelineCache`] the resources were queued in.
+    pipeline_cache: Option<PipelineCacheId>,
 }
 
 impl<T: Specializable, S: Specializer<T>> Variants<T, S> {
@@ -282,6 +284,7 @@ impl<T: Specializable, S: Specializer<T>> Variants<T, S> {
             base_descriptor,
             primary_cache: Default::default(),
             secondary_cache: Default::default(),
+            pipeline_cache: None,
         }
     }
 
@@ -292,6 +295,12 @@ impl<T: Specializable, S: Specializer<T>> Variants<T, S> {
         pipeline_cache: &PipelineCache,
         key: S::Key,
     ) -> Result<T::CachedId, BevyError> {
+        // The pipeline cache is replaced when the render device is recreated.
+        if self.pipeline_cache != Some(pipeline_cache.id()) {
+            self.primary_cache.clear();
+            self.secondary_cache.clear();
+            self.pipeline_cache = Some(pipeline_cache.id());
+        }
         let entry = self.primary_cache.entry(key.clone());
         match entry {
             Entry::Occupied(entry) => Ok(entry.get().clone()),
@@ -22,8 +22,10 @@ use bevy_ecs::{prelude::*, system::SystemState};
 use bevy_log::{debug, info, info_span, warn};
 use bevy_render::camera::ExtractedCamera;
 use bevy_window::RawHandleWrapperHolder;
+use thiserror::Error;
 use wgpu::{
-    Adapter, AdapterInfo, Backends, DeviceType, Instance, Queue, RequestAdapterOptions, Trace,
+    Adapter, AdapterInfo, Backend, Backends, DeviceType, Instance, Queue, RequestAdapterOptions,
+    Trace,
 };
 
 /// Schedule label for the root render graph schedule. This schedule runs once per frame
@@ -134,18 +136,110 @@ pub struct RenderInstance(pub Arc<WgpuWrapper<Instance>>);
 #[derive(Resource, Clone, Deref, DerefMut)]
 pub struct RenderAdapterInfo(pub WgpuWrapper<AdapterInfo>);
 
+/// The features and limits supported by the adapter that the [`RenderDevice`] was created
+/// without, because of the [`WgpuSettingsPriority`], the
+/// [`disabled_features`](WgpuSettings::disabled_features) or the
+/// [`constrained_limits`](WgpuSettings::constrained_limits) of the [`WgpuSettings`].
+///
+/// This resource is in both the main world and the render world. Plugins can use it to scale down
+/// their features to what the device actually allows, e.g. the number of shadow cascades, rather
+/// than failing to create their resources later.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NegotiatedWgpuCaps {
+    /// The features supported by the adapter but not enabled on the device.
+    pub dropped_features: wgpu::Features,
+    /// The limits of the device that are lower than those of the adapter.
+    pub reduced_limits: Vec<ReducedLimit>,
+}
+
+/// A limit of the [`RenderDevice`] that is lower than what the adapter supports, see
+/// [`NegotiatedWgpuCaps`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReducedLimit {
+    /// The name of the field of [`wgpu::Limits`], e.g. `max_texture_dimension_2d`.
+    pub name: &'static str,
+    /// The value supported by the adapter.
+    pub adapter: u64,
+    /// The value of the device.
+    pub device: u64,
+}
+
+impl NegotiatedWgpuCaps {
+    /// Compares the features and limits of `device` to those of the `adapter` it was created
+    /// from.
+    pub fn new(adapter: &RenderAdapter, device: &RenderDevice) -> Self {
+        let mut reduced_limits = Vec::new();
+        adapter.limits().check_limits_with_fail_fn(
+            &device.limits(),
+            false,
+            |name, adapter, device| {
+                reduced_limits.push(ReducedLimit {
+                    name,
+                    adapter,
+                    device,
+                });
+            },
+        );
+        Self {
+            dropped_features: adapter.features() - device.features(),
+            reduced_limits,
+        }
+    }
+
+    /// Returns `true` if the device has all the features and limits of the adapter.
+    pub fn is_empty(&self) -> bool {
+        self.dropped_features.is_empty() && self.reduced_limits.is_empty()
+    }
+
+    /// Returns `true` if any of `features` is supported by the adapter but not enabled on the
+    /// device.
+    pub fn dropped_any(&self, features: wgpu::Features) -> bool {
+        self.dropped_features.intersects(features)
+    }
+
+    /// Returns how the limit named `name` was reduced, if it was.
+    pub fn reduced_limit(&self, name: &str) -> Option<&ReducedLimit> {
+        self.reduced_limits.iter().find(|limit| limit.name == name)
+    }
+}
+
 const GPU_NOT_FOUND_ERROR_MESSAGE: &str = if cfg!(target_os = "linux") {
     "Unable to find a GPU! Make sure you have installed required drivers! For extra information, see: https://github.com/bevyengine/bevy/blob/latest/docs/linux_dependencies.md"
 } else {
     "Unable to find a GPU! Make sure you have installed required drivers!"
 };
 
+/// Lists the adapters available for `backends`, before the renderer is initialized.
+///
+/// Use this to let users choose an adapter, which is then selected with
+/// [`WgpuSettings::adapter_selector`].
+///
+/// ```no_run
+/// # use bevy_render::{renderer::enumerate_adapters, settings::Backends};
+/// for info in bevy_tasks::block_on(enumerate_adapters(Backends::all())) {
+///     println!("{} ({:?}, {:?})", info.name, info.backend, info.device_type);
+/// }
+/// ```
+#[cfg(not(target_family = "wasm"))]
+pub async fn enumerate_adapters(backends: Backends) -> Vec<AdapterInfo> {
+    let instance = Instance::new(&wgpu::InstanceDescriptor {
+        backends,
+        ..wgpu::InstanceDescriptor::from_env_or_default()
+    });
+    instance
+        .enumerate_adapters(backends)
+        .await
+        .iter()
+        .map(Adapter::get_info)
+        .collect()
+}
+
 #[cfg(not(target_family = "wasm"))]
-async fn find_adapter_by_name(
+async fn find_adapter(
     instance: &Instance,
     options: &WgpuSettings,
     compatible_surface: Option<&wgpu::Surface<'_>>,
-    adapter_name: &str,
+    predicate: impl Fn(&AdapterInfo) -> bool,
 ) -> Option<Adapter> {
     for adapter in instance
         .enumerate_adapters(options.backends.expect(
@@ -161,19 +255,55 @@ async fn find_adapter_by_name(
             continue;
         }
 
-        if info
-            .name
-            .to_lowercase()
-            .contains(&adapter_name.to_lowercase())
-        {
+        if predicate(&info) {
             return Some(adapter);
         }
     }
     None
 }
 
+/// An error that occurs when initializing the renderer with [`try_initialize_renderer`].
+#[derive(Error, Debug)]
+pub enum RenderInitializationError {
+    /// No adapter supports the backends, or can present to the primary window.
+    #[error("{message} Backends: {0:?}", message = GPU_NOT_FOUND_ERROR_MESSAGE)]
+    AdapterNotFound(Backends),
+    /// The surface of the primary window couldn't be created.
+    #[error("Failed to create wgpu surface: {0}")]
+    CreateSurface(#[from] wgpu::CreateSurfaceError),
+    /// The adapter couldn't create a device.
+    #[error("Failed to request a device from the {backend} adapter: {error}")]
+    RequestDevice {
+        /// The backend of the adapter.
+        backend: Backend,
+        /// The error returned by the adapter.
+        #[source]
+        error: wgpu::RequestDeviceError,
+    },
+    /// The adapter couldn't create a device with the additional Vulkan features.
+    #[cfg(feature = "raw_vulkan_init")]
+    #[error("Failed to create a raw Vulkan device: {0}")]
+    RawVulkanDevice(#[from] raw_vulkan_init::CreateRawVulkanDeviceError),
+}
+
+impl RenderInitializationError {
+    /// Returns the backends that failed to initialize, out of the `requested` ones.
+    pub fn failed_backends(&self, requested: Backends) -> Backends {
+        match self {
+            Self::RequestDevice { backend, .. } => Backends::from(*backend),
+            #[cfg(feature = "raw_vulkan_init")]
+            Self::RawVulkanDevice(_) => Backends::VULKAN,
+            Self::AdapterNotFound(_) | Self::CreateSurface(_) => requested,
+        }
+    }
+}
+
 /// Initializes the renderer by retrieving and preparing the GPU instance, device and queue
 /// for the specified backend.
+///
+/// # Panics
+///
+/// Panics if the renderer can't be initialized, see [`try_initialize_renderer`].
 pub async fn initialize_renderer(
     backends: Backends,
     primary_window: Option<RawHandleWrapperHolder>,
@@ -181,6 +311,26 @@ pub async fn initialize_renderer(
     #[cfg(feature = "raw_vulkan_init")]
     raw_vulkan_init_settings: raw_vulkan_init::RawVulkanInitSettings,
 ) -> RenderResources {
+    try_initialize_renderer(
+        backends,
+        primary_window,
+        options,
+        #[cfg(feature = "raw_vulkan_init")]
+        raw_vulkan_init_settings,
+    )
+    .await
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Initializes the renderer by retrieving and preparing the GPU instance, device and queue
+/// for the specified backend, returning an error if no adapter or device can be created.
+pub async fn try_initialize_renderer(
+    backends: Backends,
+    primary_window: Option<RawHandleWrapperHolder>,
+    options: &WgpuSettings,
+    #[cfg(feature = "raw_vulkan_init")]
+    raw_vulkan_init_settings: raw_vulkan_init::RawVulkanInitSettings,
+) -> Result<RenderResources, RenderInitializationError> {
     let instance_descriptor = wgpu::InstanceDescriptor {
         backends,
         flags: options.instance_flags,
@@ -211,23 +361,19 @@ pub async fn initialize_renderer(
         &mut additional_vulkan_features,
     );
 
-    let surface = primary_window.and_then(|wrapper| {
-        let maybe_handle = wrapper
-            .0
-            .lock()
-            .expect("Couldn't get the window handle in time for renderer initialization");
-        if let Some(wrapper) = maybe_handle.as_ref() {
-            // SAFETY: Plugins should be set up on the main thread.
-            let handle = unsafe { wrapper.get_handle() };
-            Some(
-                instance
-                    .create_surface(handle)
-                    .expect("Failed to create wgpu surface"),
-            )
-        } else {
-            None
-        }
-    });
+    let surface = primary_window
+        .and_then(|wrapper| {
+            let maybe_handle = wrapper
+                .0
+                .lock()
+                .expect("Couldn't get the window handle in time for renderer initialization");
+            maybe_handle.as_ref().map(|wrapper| {
+                // SAFETY: Plugins should be set up on the main thread.
+                let handle = unsafe { wrapper.get_handle() };
+                instance.create_surface(handle)
+            })
+        })
+        .transpose()?;
 
     let force_fallback_adapter = std::env::var("WGPU_FORCE_FALLBACK_ADAPTER")
         .map_or(options.force_fallback_adapter, |v| {
@@ -246,13 +392,29 @@ pub async fn initialize_renderer(
 
     #[cfg(not(target_family = "wasm"))]
     let mut selected_adapter = if let Some(adapter_name) = desired_adapter_name {
-        find_adapter_by_name(
+        find_adapter(
             &instance,
             options,
             request_adapter_options.compatible_surface,
-            &adapter_name,
+            |info| {
+                info.name
+                    .to_lowercase()
+                    .contains(&adapter_name.to_lowercase())
+            },
         )
         .await
+    } else if let Some(selector) = &options.adapter_selector {
+        let adapter = find_adapter(
+            &instance,
+            options,
+            request_adapter_options.compatible_surface,
+            |info| selector.matches(info),
+        )
+        .await;
+        if adapter.is_none() {
+            warn!("No adapter matches the adapter selector, using the default adapter.");
+        }
+        adapter
     } else {
         None
     };
@@ -260,7 +422,7 @@ pub async fn initialize_renderer(
     let mut selected_adapter = None;
 
     #[cfg(target_family = "wasm")]
-    if desired_adapter_name.is_some() {
+    if desired_adapter_name.is_some() || options.adapter_selector.is_some() {
         warn!("Choosing an adapter is not supported on wasm.");
     }
 
@@ -275,7 +437,7 @@ pub async fn initialize_renderer(
             .ok();
     }
 
-    let adapter = selected_adapter.expect(GPU_NOT_FOUND_ERROR_MESSAGE);
+    let adapter = selected_adapter.ok_or(RenderInitializationError::AdapterNotFound(backends))?;
     let adapter_info = adapter.get_info();
     info!("{:?}", adapter_info);
 
@@ -470,6 +632,19 @@ pub async fn initialize_renderer(
         };
     }
 
+    #[cfg(feature = "wgpu_trace")]
+    let trace = options
+        .trace_path
+        .clone()
+        .map_or(Trace::Off, Trace::Directory);
+    #[cfg(not(feature = "wgpu_trace"))]
+    let trace = {
+        if options.trace_path.is_some() {
+            warn!("A wgpu trace path is set, but traces can't be recorded without the `wgpu_trace` feature");
+        }
+        Trace::Off
+    };
+
     let device_descriptor = wgpu::DeviceDescriptor {
         label: options.device_label.as_ref().map(AsRef::as_ref),
         required_features: features,
@@ -477,12 +652,17 @@ pub async fn initialize_renderer(
         // SAFETY: TODO, see https://github.com/bevyengine/bevy/issues/22082
         experimental_features: unsafe { wgpu::ExperimentalFeatures::enabled() },
         memory_hints: options.memory_hints.clone(),
-        // See https://github.com/gfx-rs/wgpu/issues/5974
-        trace: Trace::Off,
+        trace,
     };
 
     #[cfg(not(feature = "raw_vulkan_init"))]
-    let (device, queue) = adapter.request_device(&device_descriptor).await.unwrap();
+    let (device, queue) = adapter
+        .request_device(&device_descriptor)
+        .await
+        .map_err(|error| RenderInitializationError::RequestDevice {
+            backend: adapter_info.backend,
+            error,
+        })?;
 
     #[cfg(feature = "raw_vulkan_init")]
     let (device, queue) = raw_vulkan_init::create_raw_device(
@@ -491,13 +671,12 @@ pub async fn initialize_renderer(
         &raw_vulkan_init_settings,
         &mut additional_vulkan_features,
     )
-    .await
-    .unwrap();
+    .await?;
 
     debug!("Configured wgpu adapter Limits: {:#?}", device.limits());
     debug!("Configured wgpu adapter Features: {:#?}", device.features());
 
-    RenderResources(
+    Ok(RenderResources(
         RenderDevice::from(device),
         RenderQueue(Arc::new(WgpuWrapper::new(queue))),
         RenderAdapterInfo(WgpuWrapper::new(adapter_info)),
@@ -505,5 +684,5 @@ pub async fn initialize_renderer(
         RenderInstance(Arc::new(WgpuWrapper::new(instance))),
         #[cfg(feature = "raw_vulkan_init")]
         additional_vulkan_features,
-    )
+    ))
 }
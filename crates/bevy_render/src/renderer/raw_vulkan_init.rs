@@ -121,8 +121,9 @@ pub(crate) async fn create_raw_device(
     }
 }
 
+/// An error that occurs when creating a device with [`AdditionalVulkanFeatures`].
 #[derive(Error, Debug)]
-pub(crate) enum CreateRawVulkanDeviceError {
+pub enum CreateRawVulkanDeviceError {
     #[error(transparent)]
     RequestDeviceError(#[from] wgpu::RequestDeviceError),
     #[error(transparent)]
@@ -1,22 +1,28 @@
 use crate::{
-    error_handler::DeviceErrorHandler,
-    render_resource::PipelineCache,
-    renderer::{self, RenderAdapter, RenderAdapterInfo, RenderDevice, RenderInstance, RenderQueue},
+    error_handler::{DeviceErrorHandler, RenderError},
+    render_resource::{PipelineCache, PipelineCacheDir},
+    renderer::{
+        self, NegotiatedWgpuCaps, RenderAdapter, RenderAdapterInfo, RenderDevice, RenderInstance,
+        RenderQueue,
+    },
     FutureRenderResources,
 };
-use alloc::borrow::Cow;
+use alloc::{borrow::Cow, sync::Arc};
 use bevy_ecs::world::World;
 use bevy_image::{CompressedImageFormatSupport, CompressedImageFormats};
-use bevy_window::RawHandleWrapperHolder;
+use bevy_window::{GraphicsBackend, RawHandleWrapperHolder};
+use core::num::NonZero;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
 
+use wgpu::{AdapterInfo, MemoryBudgetThresholds};
 pub use wgpu::{
-    Backends, Dx12Compiler, Features as WgpuFeatures, Gles3MinorVersion, InstanceFlags,
-    Limits as WgpuLimits, MemoryHints, PowerPreference,
+    Backends, DeviceType, Dx12Compiler, DxcShaderModel, Features as WgpuFeatures,
+    Gles3MinorVersion, InstanceFlags, Limits as WgpuLimits, MemoryHints, PowerPreference,
 };
-use wgpu::{DxcShaderModel, MemoryBudgetThresholds};
 
 /// Configures the priority used when automatically configuring the features/limits of `wgpu`.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum WgpuSettingsPriority {
     /// WebGPU default features and limits
     Compatibility,
@@ -51,6 +57,9 @@ pub struct WgpuSettings {
     /// The constraints on limits allowed regardless of what the adapter/backend supports
     pub constrained_limits: Option<WgpuLimits>,
     /// The shader compiler to use for the DX12 backend.
+    ///
+    /// Defaults to DXC if `dxcompiler.dll` is found in the working directory or next to the
+    /// executable, see [`dynamic_dxc_in`], and to FXC otherwise.
     pub dx12_shader_compiler: Dx12Compiler,
     /// Allows you to choose which minor version of GLES3 to use (3.0, 3.1, 3.2, or automatic)
     /// This only applies when using ANGLE and the GL backend.
@@ -65,6 +74,24 @@ pub struct WgpuSettings {
     pub force_fallback_adapter: bool,
     /// The name of the adapter to use.
     pub adapter_name: Option<String>,
+    /// Selects the adapter to use among the available ones, listed by
+    /// [`enumerate_adapters`](renderer::enumerate_adapters).
+    ///
+    /// [`adapter_name`](Self::adapter_name) takes precedence over this. If no adapter matches,
+    /// the adapter is chosen from the [`power_preference`](Self::power_preference).
+    pub adapter_selector: Option<AdapterSelector>,
+    /// The maximum number of frames queued on the GPU for windows that don't set
+    /// [`Window::desired_maximum_frame_latency`](bevy_window::Window::desired_maximum_frame_latency).
+    ///
+    /// Lower values reduce input latency at the cost of throughput. If not provided, this will
+    /// default to 2.
+    pub desired_maximum_frame_latency: Option<NonZero<u32>>,
+    /// The folder to record a wgpu API trace of the render device to, to be replayed with the
+    /// [wgpu player](https://github.com/gfx-rs/wgpu/wiki/Debugging-wgpu-Applications#tracing-infrastructure).
+    ///
+    /// This requires the `wgpu_trace` feature. To only record a few frames while the app runs,
+    /// use [`WgpuTraceCapture`](crate::wgpu_trace::WgpuTraceCapture) instead.
+    pub trace_path: Option<PathBuf>,
 }
 
 impl Default for WgpuSettings {
@@ -81,7 +108,13 @@ impl Default for WgpuSettings {
             Backends::all()
         };
 
-        let backends = Some(Backends::from_env().unwrap_or(default_backends));
+        // A `WGPU_BACKEND` without known backends falls back to the default backends, see
+        // `WgpuSettingsOverrides::from_env`.
+        let backends = Some(
+            Backends::from_env()
+                .filter(|backends| !backends.is_empty())
+                .unwrap_or(default_backends),
+        );
 
         let power_preference =
             PowerPreference::from_env().unwrap_or(PowerPreference::HighPerformance);
@@ -113,17 +146,17 @@ impl Default for WgpuSettings {
         let dx12_shader_compiler =
             Dx12Compiler::from_env().unwrap_or(if cfg!(feature = "statically-linked-dxc") {
                 Dx12Compiler::StaticDxc
+            } else if cfg!(target_os = "windows") {
+                // Look in the working directory, and then next to the executable, which is where
+                // the files of packaged apps are installed.
+                dynamic_dxc_in(".", DxcShaderModel::V6_7)
+                    .or_else(|| {
+                        let exe = std::env::current_exe().ok()?;
+                        dynamic_dxc_in(exe.parent()?, DxcShaderModel::V6_7)
+                    })
+                    .unwrap_or(Dx12Compiler::Fxc)
             } else {
-                let dxc = "dxcompiler.dll";
-
-                if cfg!(target_os = "windows") && std::fs::metadata(dxc).is_ok() {
-                    Dx12Compiler::DynamicDxc {
-                        dxc_path: String::from(dxc),
-                        max_shader_model: DxcShaderModel::V6_7,
-                    }
-                } else {
-                    Dx12Compiler::Fxc
-                }
+                Dx12Compiler::Fxc
             });
 
         let gles3_minor_version = Gles3MinorVersion::from_env().unwrap_or_default();
@@ -146,10 +179,294 @@ impl Default for WgpuSettings {
             instance_memory_budget_thresholds: MemoryBudgetThresholds::default(),
             force_fallback_adapter: false,
             adapter_name: None,
+            adapter_selector: None,
+            desired_maximum_frame_latency: None,
+            trace_path: None,
         }
     }
 }
 
+impl WgpuSettings {
+    /// Applies the overrides of the config file at `path`, if it exists, and then those of the
+    /// environment variables, see [`WgpuSettingsOverrides`].
+    ///
+    /// A config file that can't be read or parsed is skipped with an error.
+    pub fn with_overrides(mut self, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        match WgpuSettingsOverrides::from_file(path) {
+            Ok(Some(overrides)) => overrides.apply(&mut self),
+            Ok(None) => {}
+            Err(error) => {
+                bevy_log::error!("Failed to load the wgpu settings from {path:?}: {error}");
+            }
+        }
+        WgpuSettingsOverrides::from_env().apply(&mut self);
+        self
+    }
+
+    /// Restricts the backends to the one picked in the
+    /// [`GraphicsSettings`](bevy_window::GraphicsSettings), unless it is
+    /// [`GraphicsBackend::Auto`].
+    ///
+    /// The `WGPU_BACKEND` environment variable takes precedence over it.
+    pub fn with_graphics_backend(mut self, backend: GraphicsBackend) -> Self {
+        if let Some(backends) = graphics_backends(backend)
+            && Backends::from_env().is_none()
+        {
+            self.backends = Some(backends);
+        }
+        self
+    }
+}
+
+/// Returns the backends of a [`GraphicsBackend`], or [`None`] for [`GraphicsBackend::Auto`].
+pub(crate) fn graphics_backends(backend: GraphicsBackend) -> Option<Backends> {
+    match backend {
+        GraphicsBackend::Auto => None,
+        GraphicsBackend::Vulkan => Some(Backends::VULKAN),
+        GraphicsBackend::Dx12 => Some(Backends::DX12),
+        GraphicsBackend::Metal => Some(Backends::METAL),
+        GraphicsBackend::Gl => Some(Backends::GL),
+        GraphicsBackend::BrowserWebGpu => Some(Backends::BROWSER_WEBGPU),
+    }
+}
+
+/// Overrides of some of the [`WgpuSettings`], so that the renderer of a shipped app can be
+/// configured without recompiling it, e.g. to work around a driver bug by switching backends.
+///
+/// Overrides are read from a config file of `key = value` lines, where lines starting with `#`
+/// are comments:
+///
+/// ```text
+/// # Use Vulkan, falling back to DX12.
+/// backends = vulkan, dx12
+/// power_preference = low
+/// priority = compatibility
+/// adapter_name = Intel
+/// force_fallback_adapter = false
+/// ```
+///
+/// - `backends` is a list of backends, as in the `WGPU_BACKEND` environment variable.
+/// - `power_preference` is `low`, `high` or `none`, as in the `WGPU_POWER_PREF` environment
+///   variable.
+/// - `priority` is `compatibility`, `functionality` or `webgl2`, as in the `WGPU_SETTINGS_PRIO`
+///   environment variable, and selects the limits requested from the device.
+/// - `adapter_name` is a part of the name of the adapter to use.
+/// - `force_fallback_adapter` is `true` or `false`.
+///
+/// The config file is applied with [`WgpuSettings::with_overrides`], or by setting
+/// [`RenderPlugin::wgpu_settings_file`](crate::RenderPlugin::wgpu_settings_file). The
+/// environment variables take precedence over it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WgpuSettingsOverrides {
+    /// Overrides [`WgpuSettings::backends`].
+    pub backends: Option<Backends>,
+    /// Overrides [`WgpuSettings::power_preference`].
+    pub power_preference: Option<PowerPreference>,
+    /// Overrides [`WgpuSettings::priority`].
+    pub priority: Option<WgpuSettingsPriority>,
+    /// Overrides [`WgpuSettings::adapter_name`].
+    pub adapter_name: Option<String>,
+    /// Overrides [`WgpuSettings::force_fallback_adapter`].
+    pub force_fallback_adapter: Option<bool>,
+}
+
+impl WgpuSettingsOverrides {
+    /// The conventional name of the config file, in the asset base path.
+    pub const DEFAULT_FILE_NAME: &'static str = "wgpu_settings.cfg";
+
+    /// Reads the overrides from the config file at `path`.
+    ///
+    /// Returns [`None`] if the file doesn't exist.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Option<Self>, WgpuSettingsOverridesError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(&contents).map(Some),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Parses the overrides from the contents of a config file.
+    pub fn parse(contents: &str) -> Result<Self, WgpuSettingsOverridesError> {
+        let mut overrides = Self::default();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || WgpuSettingsOverridesError::InvalidLine {
+                line: index + 1,
+                contents: line.to_string(),
+            };
+            let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim();
+            match key.trim() {
+                "backends" => {
+                    // Unknown backends are ignored, so a list without known backends is invalid.
+                    let backends = Backends::from_comma_list(value);
+                    if backends.is_empty() {
+                        return Err(invalid());
+                    }
+                    overrides.backends = Some(backends);
+                }
+                "power_preference" => {
+                    overrides.power_preference = Some(match value.to_lowercase().as_str() {
+                        "low" => PowerPreference::LowPower,
+                        "high" => PowerPreference::HighPerformance,
+                        "none" => PowerPreference::None,
+                        _ => return Err(invalid()),
+                    });
+                }
+                "priority" => {
+                    overrides.priority = Some(parse_settings_priority(value).ok_or_else(invalid)?);
+                }
+                "adapter_name" => overrides.adapter_name = Some(value.to_string()),
+                "force_fallback_adapter" => {
+                    overrides.force_fallback_adapter = Some(value.parse().map_err(|_| invalid())?);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(overrides)
+    }
+
+    /// Reads the overrides from the `WGPU_BACKEND`, `WGPU_POWER_PREF` and `WGPU_SETTINGS_PRIO`
+    /// environment variables.
+    ///
+    /// `WGPU_ADAPTER_NAME` and `WGPU_FORCE_FALLBACK_ADAPTER` are read when the renderer is
+    /// initialized, so they aren't included. A `WGPU_BACKEND` without known backends is ignored
+    /// with a warning.
+    pub fn from_env() -> Self {
+        let backends = Backends::from_env().filter(|backends| {
+            if backends.is_empty() {
+                bevy_log::warn!("Ignoring WGPU_BACKEND, as it doesn't contain any known backend");
+            }
+            !backends.is_empty()
+        });
+        Self {
+            backends,
+            power_preference: PowerPreference::from_env(),
+            priority: settings_priority_from_env(),
+            ..Default::default()
+        }
+    }
+
+    /// Overrides the values of `settings` that are set in `self`.
+    pub fn apply(&self, settings: &mut WgpuSettings) {
+        if let Some(backends) = self.backends {
+            settings.backends = Some(backends);
+        }
+        if let Some(power_preference) = self.power_preference {
+            settings.power_preference = power_preference;
+        }
+        if let Some(priority) = &self.priority {
+            settings.priority = priority.clone();
+            if *priority == WgpuSettingsPriority::WebGL2 {
+                settings.limits = wgpu::Limits::downlevel_webgl2_defaults();
+            }
+        }
+        if let Some(adapter_name) = &self.adapter_name {
+            settings.adapter_name = Some(adapter_name.clone());
+        }
+        if let Some(force_fallback_adapter) = self.force_fallback_adapter {
+            settings.force_fallback_adapter = force_fallback_adapter;
+        }
+    }
+}
+
+/// An error when reading [`WgpuSettingsOverrides`] from a config file.
+#[derive(Error, Debug)]
+pub enum WgpuSettingsOverridesError {
+    /// The file couldn't be read.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A line isn't a known `key = value` pair.
+    #[error("invalid line {line}: `{contents}`")]
+    InvalidLine {
+        /// The number of the line, starting at 1.
+        line: usize,
+        /// The contents of the line.
+        contents: String,
+    },
+}
+
+/// Returns a [`Dx12Compiler::DynamicDxc`] that loads the `dxcompiler.dll` shipped in `dir`, e.g.
+/// in the package of an app, or [`None`] if `dir` doesn't contain it.
+///
+/// `max_shader_model` is the highest shader model the shipped version of DXC supports.
+///
+/// DXC versions before v1.8.2502 also need `dxil.dll` next to `dxcompiler.dll` to validate and
+/// sign the shaders they compile, or the driver rejects them. A warning is logged when it is
+/// missing.
+///
+/// ```
+/// # use bevy_render::settings::{dynamic_dxc_in, Dx12Compiler, DxcShaderModel, WgpuSettings};
+/// let settings = WgpuSettings {
+///     dx12_shader_compiler: dynamic_dxc_in("shaders/dxc", DxcShaderModel::V6_7)
+///         .unwrap_or(Dx12Compiler::Fxc),
+///     ..Default::default()
+/// };
+/// ```
+pub fn dynamic_dxc_in(
+    dir: impl AsRef<Path>,
+    max_shader_model: DxcShaderModel,
+) -> Option<Dx12Compiler> {
+    let dir = dir.as_ref();
+    let dxc_path = dir.join("dxcompiler.dll");
+    if !dxc_path.is_file() {
+        return None;
+    }
+    let Some(dxc_path) = dxc_path.to_str() else {
+        bevy_log::warn!("Ignoring DXC at {dxc_path:?}, as its path isn't valid UTF-8");
+        return None;
+    };
+    if !dir.join("dxil.dll").is_file() {
+        bevy_log::warn!(
+            "Found {dxc_path} without dxil.dll. DXC versions before v1.8.2502 need it to sign shaders."
+        );
+    }
+    Some(Dx12Compiler::DynamicDxc {
+        dxc_path: dxc_path.to_string(),
+        max_shader_model,
+    })
+}
+
+/// Selects the adapter to initialize the renderer with, for [`WgpuSettings::adapter_selector`].
+///
+/// ```
+/// # use bevy_render::settings::{AdapterSelector, DeviceType, WgpuSettings};
+/// // Force the integrated GPU of a laptop.
+/// let settings = WgpuSettings {
+///     adapter_selector: Some(AdapterSelector::device_type(DeviceType::IntegratedGpu)),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone)]
+pub struct AdapterSelector(Arc<dyn Fn(&AdapterInfo) -> bool + Send + Sync>);
+
+impl AdapterSelector {
+    /// Selects the first adapter for which `predicate` returns `true`.
+    pub fn new(predicate: impl Fn(&AdapterInfo) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+
+    /// Selects the first adapter of the given [`DeviceType`].
+    pub fn device_type(device_type: DeviceType) -> Self {
+        Self::new(move |info| info.device_type == device_type)
+    }
+
+    /// Selects the adapter with the given PCI vendor and device ids, which identify it across
+    /// runs, e.g. to restore the choice of the user.
+    pub fn pci_id(vendor: u32, device: u32) -> Self {
+        Self::new(move |info| info.vendor == vendor && info.device == device)
+    }
+
+    /// Returns `true` if the adapter described by `info` is selected.
+    pub fn matches(&self, info: &AdapterInfo) -> bool {
+        (self.0)(info)
+    }
+}
+
 #[derive(Clone)]
 pub struct RenderResources(
     pub RenderDevice,
@@ -178,12 +495,19 @@ impl RenderResources {
 
         let compressed_image_format_support =
             CompressedImageFormatSupport(CompressedImageFormats::from_features(device.features()));
+        let negotiated_caps = NegotiatedWgpuCaps::new(&render_adapter, &device);
+        if !negotiated_caps.is_empty() {
+            bevy_log::debug!(
+                "Device created with less than the adapter supports: {negotiated_caps:?}"
+            );
+        }
 
         main_world.insert_resource(device.clone());
         main_world.insert_resource(queue.clone());
         main_world.insert_resource(adapter_info.clone());
         main_world.insert_resource(render_adapter.clone());
         main_world.insert_resource(compressed_image_format_support);
+        main_world.insert_resource(negotiated_caps.clone());
 
         #[cfg(feature = "raw_vulkan_init")]
         {
@@ -192,17 +516,26 @@ impl RenderResources {
             render_world.insert_resource(additional_vulkan_features);
         }
 
-        render_world.insert_resource(instance);
-        render_world.insert_resource(PipelineCache::new(
+        let device_error_handler = DeviceErrorHandler::new(&device);
+        let mut pipeline_cache = PipelineCache::new(
             device.clone(),
             render_adapter.clone(),
             synchronous_pipeline_compilation,
-        ));
-        render_world.insert_resource(DeviceErrorHandler::new(&device));
+        );
+        pipeline_cache.error_reporter = Some(device_error_handler.reporter());
+        if let Some(dir) = render_world.get_resource::<PipelineCacheDir>() {
+            pipeline_cache.load_persistent_cache(dir, &adapter_info);
+        }
+
+        render_world.insert_resource(instance);
+        render_world.insert_resource(pipeline_cache);
+        render_world.insert_resource(device_error_handler.reporter());
+        render_world.insert_resource(device_error_handler);
         render_world.insert_resource(device);
         render_world.insert_resource(queue);
         render_world.insert_resource(render_adapter);
         render_world.insert_resource(adapter_info);
+        render_world.insert_resource(negotiated_caps);
     }
 }
 
@@ -238,7 +571,8 @@ impl RenderCreation {
     }
 
     /// Creates [`RenderResources`] from this [`RenderCreation`] and an optional primary window
-    /// and writes them into `future_resources`, possibly asynchronously.
+    /// and writes them into `future_resources`, possibly asynchronously. If the renderer can't be
+    /// initialized, the error is written instead.
     ///
     /// Returns true if creation was successful, false otherwise.
     ///
@@ -252,7 +586,7 @@ impl RenderCreation {
     ) -> bool {
         match self {
             RenderCreation::Manual(resources) => {
-                *future_resources.lock().unwrap() = Some(resources.clone());
+                *future_resources.lock().unwrap() = Some(Ok(resources.clone()));
             }
             RenderCreation::Automatic(render_creation) => {
                 let Some(backends) = render_creation.backends else {
@@ -261,14 +595,15 @@ impl RenderCreation {
                 let settings = render_creation.clone();
 
                 let async_renderer = async move {
-                    let render_resources = renderer::initialize_renderer(
+                    let render_resources = renderer::try_initialize_renderer(
                         backends,
                         primary_window,
                         &settings,
                         #[cfg(feature = "raw_vulkan_init")]
                         raw_vulkan_init_settings,
                     )
-                    .await;
+                    .await
+                    .map_err(|error| RenderError::initialization(&error, backends));
 
                     *future_resources.lock().unwrap() = Some(render_resources);
                 };
@@ -307,16 +642,41 @@ impl From<WgpuSettings> for RenderCreation {
 
 /// Get a features/limits priority from the environment variable `WGPU_SETTINGS_PRIO`
 pub fn settings_priority_from_env() -> Option<WgpuSettingsPriority> {
-    Some(
-        match std::env::var("WGPU_SETTINGS_PRIO")
-            .as_deref()
-            .map(str::to_lowercase)
-            .as_deref()
-        {
-            Ok("compatibility") => WgpuSettingsPriority::Compatibility,
-            Ok("functionality") => WgpuSettingsPriority::Functionality,
-            Ok("webgl2") => WgpuSettingsPriority::WebGL2,
-            _ => return None,
-        },
-    )
+    parse_settings_priority(&std::env::var("WGPU_SETTINGS_PRIO").ok()?)
+}
+
+fn parse_settings_priority(value: &str) -> Option<WgpuSettingsPriority> {
+    Some(match value.to_lowercase().as_str() {
+        "compatibility" => WgpuSettingsPriority::Compatibility,
+        "functionality" => WgpuSettingsPriority::Functionality,
+        "webgl2" => WgpuSettingsPriority::WebGL2,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Backends, PowerPreference, WgpuSettingsOverrides, WgpuSettingsPriority};
+
+    #[test]
+    fn parse_overrides() {
+        let overrides = WgpuSettingsOverrides::parse(
+            "# Comment\n\nbackends = vulkan, dx12\npower_preference = Low\npriority = webgl2\n",
+        )
+        .unwrap();
+        assert_eq!(
+            overrides,
+            WgpuSettingsOverrides {
+                backends: Some(Backends::VULKAN | Backends::DX12),
+                power_preference: Some(PowerPreference::LowPower),
+                priority: Some(WgpuSettingsPriority::WebGL2),
+                ..Default::default()
+            }
+        );
+
+        assert!(WgpuSettingsOverrides::parse("backends").is_err());
+        assert!(WgpuSettingsOverrides::parse("backends = vulkn").is_err());
+        assert!(WgpuSettingsOverrides::parse("unknown = 1").is_err());
+        assert!(WgpuSettingsOverrides::parse("force_fallback_adapter = maybe").is_err());
+    }
 }
@@ -0,0 +1,111 @@
+//! Releases GPU memory while the app is suspended.
+
+use crate::{
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    renderer::RenderDevice,
+    texture::{update_texture_cache_system, TextureCache},
+    view::WindowSurfaces,
+    Render, RenderApp, RenderSystems,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_window::AppLifecycle;
+
+/// Releases GPU memory when the app is suspended, as mobile platforms and app stores expect from
+/// apps in the background.
+///
+/// On the last frame before the app is [`Suspended`](AppLifecycle::Suspended), the window surfaces
+/// and their swap chains are dropped, as are the transient textures of the [`TextureCache`]. On
+/// DirectX 12, the device is then trimmed with `IDXGIDevice3::Trim` when the driver supports it, as
+/// the Microsoft Store requires. The surfaces aren't configured while the app is suspended, and are
+/// created again when it resumes.
+#[derive(Default)]
+pub struct RenderSuspendPlugin;
+
+impl Plugin for RenderSuspendPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<AppLifecycle>()
+            .init_resource::<AppSuspended>()
+            .add_plugins(ExtractResourcePlugin::<AppSuspended>::default())
+            .add_systems(PreUpdate, update_app_suspended);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(
+                Render,
+                release_gpu_memory_on_suspend
+                    .in_set(RenderSystems::Cleanup)
+                    .after(update_texture_cache_system),
+            );
+        }
+    }
+}
+
+/// Whether the app is suspended, as reported by the last [`AppLifecycle`] message.
+#[derive(Resource, ExtractResource, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AppSuspended(pub bool);
+
+fn update_app_suspended(
+    mut lifecycle: MessageReader<AppLifecycle>,
+    mut suspended: ResMut<AppSuspended>,
+) {
+    let Some(&lifecycle) = lifecycle.read().last() else {
+        return;
+    };
+    // The app keeps rendering when it will suspend, and the last frame before suspension is
+    // rendered as `Suspended`.
+    suspended.set_if_neq(AppSuspended(lifecycle == AppLifecycle::Suspended));
+}
+
+/// Drops the GPU resources that are created again on demand once the app is suspended.
+pub fn release_gpu_memory_on_suspend(
+    suspended: Res<AppSuspended>,
+    mut window_surfaces: ResMut<WindowSurfaces>,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+) {
+    if !suspended.is_changed() || !suspended.0 {
+        return;
+    }
+    // The surfaces, and their swap chains, are created again on the first frame after resuming.
+    *window_surfaces = WindowSurfaces::default();
+    texture_cache.clear();
+    // Wait for the GPU to finish with the dropped resources, so that they are freed before the
+    // app is suspended.
+    if let Err(error) = render_device.poll(wgpu::PollType::wait_indefinitely()) {
+        bevy_log::warn!("Failed to wait for the GPU before suspending: {error}");
+    }
+    #[cfg(target_os = "windows")]
+    trim_dx12_device(&render_device);
+}
+
+/// Releases the memory the driver allocated on behalf of the app with `IDXGIDevice3::Trim`, if the
+/// render device is a DirectX 12 device that supports it.
+#[cfg(target_os = "windows")]
+fn trim_dx12_device(render_device: &RenderDevice) {
+    use windows::{
+        core::Interface,
+        Win32::Graphics::{Direct3D12::ID3D12Device, Dxgi::IDXGIDevice3},
+    };
+
+    // SAFETY: The raw device is only borrowed while the render device is alive, and isn't used to
+    // modify the state wgpu tracks.
+    let dxgi_device = unsafe {
+        let Some(device) = render_device.wgpu_device().as_hal::<wgpu::hal::api::Dx12>() else {
+            return;
+        };
+        // wgpu may depend on another version of the `windows` crate, but COM interfaces are
+        // transparent wrappers of their interface pointer in every version.
+        let raw = *core::ptr::from_ref(device.raw_device()).cast::<*mut core::ffi::c_void>();
+        ID3D12Device::from_raw_borrowed(&raw).map(ID3D12Device::cast::<IDXGIDevice3>)
+    };
+    match dxgi_device {
+        Some(Ok(dxgi_device)) => {
+            // SAFETY: The GPU finished using the released resources.
+            unsafe { dxgi_device.Trim() };
+        }
+        Some(Err(error)) => {
+            bevy_log::debug!("Not trimming the render device, as it isn't a DXGI device: {error}");
+        }
+        None => {}
+    }
+}
@@ -2,12 +2,21 @@ use crate::{
     render_asset::{AssetExtractionError, PrepareAssetError, RenderAsset},
     render_resource::{DefaultImageSampler, Sampler, Texture, TextureView},
     renderer::{RenderDevice, RenderQueue},
+    Extract,
+};
+use bevy_asset::{meta::AssetHash, AssetEvent, AssetId, AssetServer, RenderAssetUsages};
+use bevy_ecs::{
+    message::MessageReader,
+    resource::Resource,
+    system::{
+        lifetimeless::{SRes, SResMut},
+        Res, ResMut, SystemParamItem,
+    },
 };
-use bevy_asset::{AssetId, RenderAssetUsages};
-use bevy_ecs::system::{lifetimeless::SRes, SystemParamItem};
 use bevy_image::{Image, ImageSampler};
 use bevy_log::warn;
 use bevy_math::{AspectRatio, UVec2};
+use bevy_platform::collections::{HashMap, HashSet};
 use wgpu::{Extent3d, TexelCopyBufferLayout, TextureFormat, TextureUsages};
 use wgpu_types::{TextureDescriptor, TextureViewDescriptor};
 
@@ -29,6 +38,7 @@ impl RenderAsset for GpuImage {
         SRes<RenderDevice>,
         SRes<RenderQueue>,
         SRes<DefaultImageSampler>,
+        SResMut<SharedImageTextures>,
     );
 
     #[inline]
@@ -62,12 +72,17 @@ impl RenderAsset for GpuImage {
     /// Converts the extracted image into a [`GpuImage`].
     fn prepare_asset(
         image: Self::SourceAsset,
-        _: AssetId<Self::SourceAsset>,
-        (render_device, render_queue, default_sampler): &mut SystemParamItem<Self::Param>,
+        asset_id: AssetId<Self::SourceAsset>,
+        (render_device, render_queue, default_sampler, shared_textures): &mut SystemParamItem<
+            Self::Param,
+        >,
         previous_asset: Option<&Self>,
     ) -> Result<Self, PrepareAssetError<Self::SourceAsset>> {
         let had_data = image.data.is_some();
+        // A shared texture is never written to, as the other images still use it.
+        let was_shared = shared_textures.release(asset_id);
         let texture = if let Some(prev) = previous_asset
+            && !was_shared
             && prev.texture_descriptor == image.texture_descriptor
             && (!had_data
                 || prev
@@ -101,12 +116,21 @@ impl RenderAsset for GpuImage {
             // reuse previous texture
             prev.texture.clone()
         } else if let Some(ref data) = image.data {
-            render_device.create_texture_with_data(
-                render_queue,
-                &image.texture_descriptor,
-                image.data_order,
-                data,
-            )
+            let create_texture = || {
+                render_device.create_texture_with_data(
+                    render_queue,
+                    &image.texture_descriptor,
+                    image.data_order,
+                    data,
+                )
+            };
+            // Only the first upload is shared, as a modified image may not match its content hash
+            // anymore.
+            if previous_asset.is_none() {
+                shared_textures.get_or_create(asset_id, &image.texture_descriptor, create_texture)
+            } else {
+                create_texture()
+            }
         } else {
             let new_texture = render_device.create_texture(&image.texture_descriptor);
             if image.copy_on_resize {
@@ -147,6 +171,7 @@ impl RenderAsset for GpuImage {
         };
 
         let texture_view = if let Some(prev) = previous_asset.as_ref()
+            && !was_shared
             && prev.texture_descriptor == image.texture_descriptor
             && prev
                 .texture_descriptor
@@ -178,6 +203,14 @@ impl RenderAsset for GpuImage {
             had_data,
         })
     }
+
+    fn unload_asset(
+        asset_id: AssetId<Self::SourceAsset>,
+        (_, _, _, shared_textures): &mut SystemParamItem<Self::Param>,
+    ) {
+        shared_textures.release(asset_id);
+        shared_textures.content_hashes.remove(&asset_id);
+    }
 }
 
 impl GpuImage {
@@ -212,3 +245,80 @@ impl GpuImage {
             .unwrap_or(self.texture_descriptor.format)
     }
 }
+
+/// The key of a [`SharedImageTextures`] texture: the content hash of the images and their texture
+/// descriptor.
+type SharedImageTextureKey = (
+    AssetHash,
+    TextureDescriptor<Option<&'static str>, &'static [TextureFormat]>,
+);
+
+/// The textures of the images loaded from the same content-addressed processed bytes, which are
+/// uploaded to the GPU once and shared by the [`GpuImage`]s of these images.
+///
+/// The content hashes of the images are extracted by [`extract_image_content_hashes`]. See
+/// [`AssetServer::get_content_hash`].
+#[derive(Resource, Default)]
+pub struct SharedImageTextures {
+    content_hashes: HashMap<AssetId<Image>, AssetHash>,
+    textures: HashMap<SharedImageTextureKey, (Texture, HashSet<AssetId<Image>>)>,
+}
+
+impl SharedImageTextures {
+    /// Returns the shared texture of the image `id`, creating it if no other image with the same
+    /// content hash and `descriptor` has been uploaded. Images without a content hash get a
+    /// texture of their own.
+    fn get_or_create(
+        &mut self,
+        id: AssetId<Image>,
+        descriptor: &TextureDescriptor<Option<&'static str>, &'static [TextureFormat]>,
+        create_texture: impl FnOnce() -> Texture,
+    ) -> Texture {
+        let Some(hash) = self.content_hashes.get(&id) else {
+            return create_texture();
+        };
+        let (texture, users) = self
+            .textures
+            .entry((*hash, descriptor.clone()))
+            .or_insert_with(|| (create_texture(), HashSet::default()));
+        users.insert(id);
+        texture.clone()
+    }
+
+    /// Stops sharing a texture with the image `id`, dropping the texture once no image uses it.
+    ///
+    /// Returns whether the image used a shared texture.
+    fn release(&mut self, id: AssetId<Image>) -> bool {
+        let mut released = false;
+        self.textures.retain(|_, (_, users)| {
+            released |= users.remove(&id);
+            !users.is_empty()
+        });
+        released
+    }
+}
+
+/// Drops the [`SharedImageTextures`] of the lost render device, keeping the content hashes of the
+/// images.
+pub(crate) fn reset_shared_image_textures(mut shared_textures: ResMut<SharedImageTextures>) {
+    shared_textures.textures.clear();
+}
+
+/// Extracts the content hashes of the images added in the main world, so that the images loaded
+/// from the same content-addressed processed bytes share their texture.
+pub fn extract_image_content_hashes(
+    mut shared_textures: ResMut<SharedImageTextures>,
+    mut events: Extract<MessageReader<AssetEvent<Image>>>,
+    asset_server: Extract<Option<Res<AssetServer>>>,
+) {
+    let Some(asset_server) = asset_server.as_deref() else {
+        return;
+    };
+    for event in events.read() {
+        if let AssetEvent::Added { id } = event
+            && let Some(hash) = asset_server.get_content_hash(*id)
+        {
+            shared_textures.content_hashes.insert(*id, hash);
+        }
+    }
+}
@@ -5,7 +5,9 @@ mod texture_attachment;
 mod texture_cache;
 
 pub use crate::render_resource::DefaultImageSampler;
-use bevy_image::{CompressedImageFormatSupport, CompressedImageFormats, ImageLoader, ImagePlugin};
+use bevy_image::{
+    CompressedImageFormatSupport, CompressedImageFormats, ImageBudget, ImageLoader, ImagePlugin,
+};
 pub use fallback_image::*;
 pub use gpu_image::*;
 pub use manual_texture_view::*;
@@ -14,7 +16,8 @@ pub use texture_cache::*;
 
 use crate::{
     extract_resource::ExtractResourcePlugin, render_asset::RenderAssetPlugin,
-    renderer::RenderDevice, Render, RenderApp, RenderSystems,
+    renderer::RenderDevice, reset_render_resource, ExtractSchedule, Render, RenderApp,
+    RenderDeviceReset, RenderSystems,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::AssetApp;
@@ -34,15 +37,36 @@ impl Plugin for TexturePlugin {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<TextureCache>()
+                .init_resource::<SharedImageTextures>()
                 .allow_ambiguous_resource::<TextureCache>()
+                .add_systems(ExtractSchedule, extract_image_content_hashes)
                 .add_systems(
                     Render,
                     update_texture_cache_system.in_set(RenderSystems::Cleanup),
+                )
+                .add_systems(
+                    RenderDeviceReset,
+                    (
+                        reset_render_resource::<TextureCache>,
+                        reset_shared_image_textures,
+                    ),
                 );
         }
     }
 
     fn finish(&self, app: &mut App) {
+        // Limit the image budget to the largest textures supported by the GPU.
+        if let Some(device) = app.world().get_resource::<RenderDevice>() {
+            let max_texture_dimension_2d = device.limits().max_texture_dimension_2d;
+            let budget = app
+                .world()
+                .get_resource::<ImageBudget>()
+                .copied()
+                .unwrap_or_default()
+                .with_max_texture_dimension(max_texture_dimension_2d);
+            app.insert_resource(budget);
+        }
+
         if !ImageLoader::SUPPORTED_FORMATS.is_empty() {
             let supported_compressed_formats = if let Some(resource) =
                 app.world().get_resource::<CompressedImageFormatSupport>()
@@ -54,23 +78,47 @@ impl Plugin for TexturePlugin {
                 CompressedImageFormats::NONE
             };
 
-            app.register_asset_loader(ImageLoader::new(supported_compressed_formats));
+            let budget = app
+                .world()
+                .get_resource::<ImageBudget>()
+                .copied()
+                .unwrap_or_default();
+            app.register_asset_loader(
+                ImageLoader::new(supported_compressed_formats).with_budget(budget),
+            );
         }
         let default_sampler = app.get_added_plugins::<ImagePlugin>()[0]
             .default_sampler
             .clone();
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            let default_sampler = {
+            let sampler = {
                 let device = render_app.world().resource::<RenderDevice>();
                 device.create_sampler(&default_sampler.as_wgpu())
             };
             render_app
-                .insert_resource(DefaultImageSampler(default_sampler))
+                .insert_resource(DefaultImageSampler(sampler))
                 .init_resource::<FallbackImage>()
                 .init_resource::<FallbackImageZero>()
                 .init_resource::<FallbackImageCubemap>()
-                .init_resource::<FallbackImageFormatMsaaCache>();
+                .init_resource::<FallbackImageFormatMsaaCache>()
+                .add_systems(
+                    RenderDeviceReset,
+                    (
+                        move |mut commands: Commands, device: Res<RenderDevice>| {
+                            let sampler = device.create_sampler(&default_sampler.as_wgpu());
+                            commands.insert_resource(DefaultImageSampler(sampler));
+                        },
+                        // The fallback images use the default sampler.
+                        (
+                            reset_render_resource::<FallbackImage>,
+                            reset_render_resource::<FallbackImageZero>,
+                            reset_render_resource::<FallbackImageCubemap>,
+                            reset_render_resource::<FallbackImageFormatMsaaCache>,
+                        ),
+                    )
+                        .chain(),
+                );
         }
     }
 }
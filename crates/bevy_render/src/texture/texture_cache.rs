@@ -88,6 +88,11 @@ impl TextureCache {
         self.textures.is_empty()
     }
 
+    /// Drops all the cached textures, e.g. to release GPU memory when the app is suspended.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+    }
+
     /// Updates the cache and only retains recently used textures.
     pub fn update(&mut self) {
         self.textures.retain(|_, textures| {
@@ -2,7 +2,7 @@ pub mod visibility;
 pub mod window;
 
 use bevy_camera::{
-    primitives::Frustum, CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure,
+    primitives::Frustum, CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure, Hdr,
     MainPassResolutionOverride, NormalizedRenderTarget,
 };
 use bevy_diagnostic::FrameCount;
@@ -16,20 +16,23 @@ use crate::{
     render_asset::RenderAssets,
     render_phase::ViewRangefinder3d,
     render_resource::{DynamicUniformBuffer, ShaderType, Texture, TextureView},
-    renderer::{RenderDevice, RenderQueue},
+    renderer::{RenderAdapter, RenderDevice, RenderQueue},
+    reset_render_resource,
+    settings::WgpuFeatures,
     sync_world::MainEntity,
     texture::{
         CachedTexture, ColorAttachment, DepthAttachment, GpuImage, ManualTextureViews,
         OutputColorAttachment, TextureCache,
     },
-    Render, RenderApp, RenderSystems,
+    Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 use alloc::sync::Arc;
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_color::LinearRgba;
 use bevy_derive::{Deref, DerefMut};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{entity::EntityHashSet, prelude::*};
 use bevy_image::{BevyDefault as _, ToExtents};
+use bevy_log::warn;
 use bevy_math::{mat3, vec2, vec3, Mat3, Mat4, UVec4, Vec2, Vec3, Vec4, Vec4Swizzles};
 use bevy_platform::collections::{hash_map::Entry, HashMap};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -106,7 +109,12 @@ impl Plugin for ViewPlugin {
                 ExtractComponentPlugin::<Msaa>::default(),
                 ExtractComponentPlugin::<OcclusionCulling>::default(),
                 RenderVisibilityRangePlugin,
-            ));
+            ))
+            .init_resource::<MsaaTextureFormats>()
+            .add_systems(
+                PostUpdate,
+                clamp_msaa_to_supported.run_if(resource_exists::<RenderAdapter>),
+            );
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.add_systems(
@@ -122,10 +130,10 @@ impl Plugin for ViewPlugin {
                     prepare_view_attachments
                         .in_set(RenderSystems::PrepareViews)
                         .before(prepare_view_targets)
-                        .after(prepare_windows),
+                        .after(prepare_offscreen_windows),
                     prepare_view_targets
                         .in_set(RenderSystems::PrepareViews)
-                        .after(prepare_windows)
+                        .after(prepare_offscreen_windows)
                         .after(crate::render_asset::prepare_assets::<GpuImage>)
                         .ambiguous_with(crate::camera::sort_cameras), // doesn't use `sorted_camera_index_for_target`
                     prepare_view_uniforms.in_set(RenderSystems::PrepareResources),
@@ -138,7 +146,14 @@ impl Plugin for ViewPlugin {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ViewUniforms>()
-                .init_resource::<ViewTargetAttachments>();
+                .init_resource::<ViewTargetAttachments>()
+                .add_systems(
+                    RenderDeviceReset,
+                    (
+                        reset_render_resource::<ViewUniforms>,
+                        reset_render_resource::<ViewTargetAttachments>,
+                    ),
+                );
         }
     }
 }
@@ -151,6 +166,10 @@ impl Plugin for ViewPlugin {
 /// Some advanced rendering features may require that MSAA is disabled.
 ///
 /// Note that the web currently only supports 1 or 4 samples.
+///
+/// This can be changed at runtime. The pipelines and textures of the camera are then specialized
+/// and created again for the new number of samples. Sample counts the render device doesn't
+/// support are lowered by [`clamp_msaa_to_supported`].
 #[derive(
     Component,
     Default,
@@ -188,6 +207,87 @@ impl Msaa {
             _ => panic!("Unsupported MSAA sample count: {samples}"),
         }
     }
+
+    /// Returns `true` if the render device supports this number of samples for textures of
+    /// `format`.
+    ///
+    /// Only [`Msaa::Off`] and [`Msaa::Sample4`] are guaranteed to be supported, the other sample
+    /// counts also need [`WgpuFeatures::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`].
+    pub fn is_supported(
+        &self,
+        format: TextureFormat,
+        adapter: &RenderAdapter,
+        device: &RenderDevice,
+    ) -> bool {
+        let features = if device
+            .features()
+            .contains(WgpuFeatures::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+        {
+            adapter.get_texture_format_features(format)
+        } else {
+            format.guaranteed_format_features(device.features())
+        };
+        features.flags.sample_count_supported(self.samples())
+    }
+}
+
+/// The formats of the multisampled textures cameras may render to with [`Msaa`], besides their
+/// main and depth textures, which are checked by [`clamp_msaa_to_supported`].
+///
+/// Plugins that render views to other multisampled textures, e.g. prepasses, add their formats.
+/// They are checked for every camera, even the ones that don't use these textures.
+#[derive(Resource, Default, Debug, Clone, Deref, DerefMut)]
+pub struct MsaaTextureFormats(pub Vec<TextureFormat>);
+
+/// Lowers the [`Msaa`] of cameras to the highest number of samples the render device supports for
+/// their main and depth textures, and for the [`MsaaTextureFormats`].
+///
+/// This lets [`Msaa`] be changed at runtime, e.g. from a graphics menu, without creating textures
+/// the device can't render to. The lowered value is written back to the camera, so the menu can
+/// show the setting actually used.
+///
+/// Cameras are only checked when their [`Msaa`] or [`Hdr`] changes, or when the render device or
+/// the [`MsaaTextureFormats`] change.
+pub fn clamp_msaa_to_supported(
+    mut cameras: Query<(Entity, &mut Msaa, Option<Ref<Hdr>>)>,
+    mut removed_hdr: RemovedComponents<Hdr>,
+    msaa_formats: Res<MsaaTextureFormats>,
+    adapter: Res<RenderAdapter>,
+    device: Res<RenderDevice>,
+) {
+    let check_all = adapter.is_changed() || device.is_changed() || msaa_formats.is_changed();
+    let removed_hdr: EntityHashSet = removed_hdr.read().collect();
+    for (entity, mut msaa, hdr) in &mut cameras {
+        let hdr_changed =
+            hdr.as_ref().is_some_and(|hdr| hdr.is_added()) || removed_hdr.contains(&entity);
+        if !check_all && !msaa.is_changed() && !hdr_changed {
+            continue;
+        }
+        let main_texture_format = if hdr.is_some() {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+        // The depth format of the core 2D and 3D pipelines.
+        let formats = [main_texture_format, TextureFormat::Depth32Float];
+        let supported = [Msaa::Sample8, Msaa::Sample4, Msaa::Sample2, Msaa::Off]
+            .into_iter()
+            .filter(|samples| samples <= &*msaa)
+            .find(|samples| {
+                formats
+                    .iter()
+                    .chain(msaa_formats.iter())
+                    .all(|format| samples.is_supported(*format, &adapter, &device))
+            })
+            .unwrap_or(Msaa::Off);
+        if supported != *msaa {
+            warn!(
+                "{:?} isn't supported by the render device for camera {}, using {:?} instead",
+                *msaa, entity, supported
+            );
+            *msaa = supported;
+        }
+    }
 }
 
 /// An identifier for a view that is stable across frames.
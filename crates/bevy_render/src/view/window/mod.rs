@@ -1,55 +1,132 @@
 use crate::renderer::WgpuWrapper;
 use crate::{
-    render_resource::{SurfaceTexture, TextureView},
+    error_handler::{RenderError, RenderErrorCode, RenderErrorReporter},
+    render_resource::{SurfaceTexture, Texture, TextureView},
     renderer::{RenderAdapter, RenderDevice, RenderInstance},
-    Extract, ExtractSchedule, Render, RenderApp, RenderSystems,
+    reset_render_resource,
+    suspend::AppSuspended,
+    Extract, ExtractSchedule, MainWorld, Render, RenderApp, RenderDeviceReset, RenderSystems,
 };
 use bevy_app::{App, Plugin};
 use bevy_ecs::{entity::EntityHashMap, prelude::*};
 use bevy_log::{debug, info, warn};
-use bevy_platform::collections::HashSet;
+use bevy_platform::{collections::HashSet, time::Instant};
 use bevy_utils::default;
 use bevy_window::{
-    CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
+    CompositeAlphaMode, OffscreenWindow, PresentMode, PrimaryWindow, RawHandleWrapper,
+    RequestRedraw, SupportedPresentModes, Window, WindowClosing, WindowDynamicRange,
+    WindowRenderRate,
 };
 use core::{
     num::NonZero,
     ops::{Deref, DerefMut},
+    time::Duration,
 };
 use wgpu::{
-    SurfaceConfiguration, SurfaceTargetUnsafe, TextureFormat, TextureUsages, TextureViewDescriptor,
+    Extent3d, SurfaceConfiguration, SurfaceTargetUnsafe, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsages, TextureViewDescriptor,
 };
 
+pub mod recorder;
 pub mod screenshot;
 
+use recorder::FrameRecorderPlugin;
 use screenshot::ScreenshotPlugin;
 
 pub struct WindowRenderPlugin;
 
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ScreenshotPlugin);
+        app.add_plugins((ScreenshotPlugin, FrameRecorderPlugin))
+            .init_resource::<SurfaceResizeDebounce>();
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ExtractedWindows>()
                 .init_resource::<WindowSurfaces>()
-                .add_systems(ExtractSchedule, extract_windows)
+                .init_resource::<OffscreenWindowTextures>()
+                .init_resource::<DefaultFrameLatency>()
+                .add_systems(
+                    ExtractSchedule,
+                    (
+                        extract_windows,
+                        sync_supported_present_modes.ambiguous_with_all(),
+                        request_redraw_during_resize
+                            .after(extract_windows)
+                            .ambiguous_with_all(),
+                    ),
+                )
                 .add_systems(
                     Render,
                     create_surfaces
                         .run_if(need_surface_configuration)
                         .before(prepare_windows),
                 )
-                .add_systems(Render, prepare_windows.in_set(RenderSystems::PrepareViews));
+                .add_systems(
+                    Render,
+                    (prepare_windows, prepare_offscreen_windows)
+                        .chain()
+                        .in_set(RenderSystems::PrepareViews),
+                )
+                .add_systems(
+                    RenderDeviceReset,
+                    reset_render_resource::<OffscreenWindowTextures>,
+                );
         }
     }
 }
 
+/// Requests the texture format of the surface of a [`Window`], overriding the format chosen from
+/// its [`WindowDynamicRange`], e.g. [`TextureFormat::Rgb10a2Unorm`] for a 10-bit output.
+///
+/// If the surface doesn't support the format, the format chosen from the dynamic range is used
+/// instead. Changing the format recreates the surface of the window.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowSurfaceFormat(pub TextureFormat);
+
+/// Delays reconfiguring the surfaces of resized windows until the resize settles.
+///
+/// Reconfiguring a surface recreates its swap chain, which flickers and can make the surface
+/// lost on some backends like DX12 when done on every frame of a live resize. While a resize is
+/// pending, frames keep rendering at the new size and are stretched onto the previous surface
+/// size.
+///
+/// Set [`delay`](Self::delay) to zero to reconfigure surfaces as soon as windows are resized.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceResizeDebounce {
+    /// How long the size of a window must stay the same before its surface is reconfigured.
+    pub delay: Duration,
+    /// The longest a surface keeps its previous size during a resize, so that the frames aren't
+    /// stretched for the whole duration of a long resize.
+    pub max_delay: Duration,
+}
+
+impl Default for SurfaceResizeDebounce {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(250),
+        }
+    }
+}
+
+/// A window size the surface hasn't been reconfigured to yet, see [`SurfaceResizeDebounce`].
+#[derive(Clone, Copy, Debug)]
+struct PendingResize {
+    width: u32,
+    height: u32,
+    /// When the window first changed size.
+    started: Instant,
+    /// When the window last changed size.
+    changed: Instant,
+}
+
 pub struct ExtractedWindow {
     /// An entity that contains the components in [`Window`].
     pub entity: Entity,
-    pub handle: RawHandleWrapper,
+    /// The handle the surface of the window is created from, or [`None`] for an
+    /// [`OffscreenWindow`], which is rendered to an offscreen texture instead.
+    pub handle: Option<RawHandleWrapper>,
     pub physical_width: u32,
     pub physical_height: u32,
     pub present_mode: PresentMode,
@@ -63,12 +140,29 @@ pub struct ExtractedWindow {
     pub swap_chain_texture_view_format: Option<TextureFormat>,
     pub size_changed: bool,
     pub present_mode_changed: bool,
+    pub desired_maximum_frame_latency_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    pub alpha_mode_changed: bool,
+    /// The surface format requested with [`WindowSurfaceFormat`].
+    pub requested_format: Option<TextureFormat>,
+    /// The requested dynamic range of this window's output. The surface format actually used is
+    /// [`swap_chain_texture_format`](Self::swap_chain_texture_format).
+    pub dynamic_range: WindowDynamicRange,
     /// Whether this window needs an initial buffer commit.
     ///
     /// On Wayland, windows must present at least once before they are shown.
     /// See <https://wayland.app/protocols/xdg-shell#xdg_surface>
     pub needs_initial_present: bool,
+    /// The minimum time between two renders of this window, from its [`WindowRenderRate`].
+    pub render_interval: Option<Duration>,
+    /// Whether this window is rendered this frame. This is false for the frames skipped because
+    /// of [`render_interval`](Self::render_interval), during which no swap chain texture is
+    /// acquired for this window.
+    pub render_this_frame: bool,
+    /// When this window is next due to render, if it has a [`render_interval`](Self::render_interval).
+    next_render: Option<Instant>,
+    /// The size the surface is reconfigured to once the resize settles.
+    pending_resize: Option<PendingResize>,
 }
 
 impl ExtractedWindow {
@@ -84,10 +178,83 @@ impl ExtractedWindow {
         self.swap_chain_texture = Some(SurfaceTexture::from(frame));
     }
 
+    fn maximum_frame_latency(&self, default: DefaultFrameLatency) -> u32 {
+        self.desired_maximum_frame_latency
+            .unwrap_or(default.0)
+            .get()
+    }
+
+    /// Returns `true` if the window was resized but its surface still has the previous size, see
+    /// [`SurfaceResizeDebounce`].
+    pub fn resize_pending(&self) -> bool {
+        self.pending_resize.is_some()
+    }
+
+    /// Debounces a change of the window size, and decides whether the surface is resized this
+    /// frame.
+    fn update_size(
+        &mut self,
+        width: u32,
+        height: u32,
+        debounce: SurfaceResizeDebounce,
+        now: Instant,
+    ) {
+        if width == self.physical_width && height == self.physical_height {
+            self.size_changed = false;
+            self.pending_resize = None;
+            return;
+        }
+        let pending = self.pending_resize.get_or_insert(PendingResize {
+            width,
+            height,
+            started: now,
+            changed: now,
+        });
+        if pending.width != width || pending.height != height {
+            pending.width = width;
+            pending.height = height;
+            pending.changed = now;
+        }
+        self.size_changed = now.saturating_duration_since(pending.changed) >= debounce.delay
+            || now.saturating_duration_since(pending.started) >= debounce.max_delay;
+        if self.size_changed {
+            debug!(
+                "Window size changed from {}x{} to {}x{}",
+                self.physical_width, self.physical_height, width, height
+            );
+            self.physical_width = width;
+            self.physical_height = height;
+            self.pending_resize = None;
+        }
+    }
+
     fn has_swapchain_texture(&self) -> bool {
         self.swap_chain_texture_view.is_some() && self.swap_chain_texture.is_some()
     }
 
+    /// Decides whether this window is rendered this frame, and schedules its next render.
+    fn update_render_this_frame(&mut self, now: Instant) {
+        let Some(interval) = self.render_interval else {
+            self.render_this_frame = true;
+            self.next_render = None;
+            return;
+        };
+        let due = self
+            .next_render
+            .is_none_or(|next_render| next_render <= now);
+        self.render_this_frame = due || self.size_changed || self.needs_initial_present;
+        if self.render_this_frame {
+            // Schedule from the previous deadline so the rate doesn't drift with the frame
+            // time, unless this window is falling behind.
+            let next_render = self
+                .next_render
+                .filter(|_| due)
+                .and_then(|next_render| next_render.checked_add(interval))
+                .filter(|next_render| *next_render > now);
+            self.next_render = next_render.or_else(|| now.checked_add(interval));
+        }
+    }
+
     pub fn present(&mut self) {
         if let Some(surface_texture) = self.swap_chain_texture.take() {
             // TODO(clean): winit docs recommends calling pre_present_notify before this.
@@ -122,11 +289,26 @@ impl DerefMut for ExtractedWindows {
 fn extract_windows(
     mut extracted_windows: ResMut<ExtractedWindows>,
     mut closing: Extract<MessageReader<WindowClosing>>,
-    windows: Extract<Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>)>>,
+    windows: Extract<
+        Query<
+            (
+                Entity,
+                &Window,
+                Option<&RawHandleWrapper>,
+                Option<&PrimaryWindow>,
+                Option<&WindowRenderRate>,
+                Option<&WindowSurfaceFormat>,
+            ),
+            Or<(With<RawHandleWrapper>, With<OffscreenWindow>)>,
+        >,
+    >,
     mut removed: Extract<RemovedComponents<RawHandleWrapper>>,
+    mut removed_offscreen: Extract<RemovedComponents<OffscreenWindow>>,
+    resize_debounce: Extract<Res<SurfaceResizeDebounce>>,
     mut window_surfaces: ResMut<WindowSurfaces>,
 ) {
-    for (entity, window, handle, primary) in windows.iter() {
+    let now = Instant::now();
+    for (entity, window, handle, primary, render_rate, surface_format) in windows.iter() {
         if primary.is_some() {
             extracted_windows.primary = Some(entity);
         }
@@ -138,7 +320,7 @@ fn extract_windows(
 
         let extracted_window = extracted_windows.entry(entity).or_insert(ExtractedWindow {
             entity,
-            handle: handle.clone(),
+            handle: handle.cloned(),
             physical_width: new_width,
             physical_height: new_height,
             present_mode: window.present_mode,
@@ -149,8 +331,16 @@ fn extract_windows(
             swap_chain_texture_format: None,
             swap_chain_texture_view_format: None,
             present_mode_changed: false,
+            desired_maximum_frame_latency_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            alpha_mode_changed: false,
+            requested_format: surface_format.map(|format| format.0),
+            dynamic_range: window.dynamic_range,
             needs_initial_present: true,
+            render_interval: None,
+            render_this_frame: true,
+            next_render: None,
+            pending_resize: None,
         });
 
         if extracted_window.swap_chain_texture.is_none() {
@@ -160,22 +350,13 @@ fn extract_windows(
             // swap chain texture if needed.
             extracted_window.swap_chain_texture_view = None;
         }
-        extracted_window.size_changed = new_width != extracted_window.physical_width
-            || new_height != extracted_window.physical_height;
+        extracted_window.update_size(new_width, new_height, **resize_debounce, now);
         extracted_window.present_mode_changed =
             window.present_mode != extracted_window.present_mode;
-
-        if extracted_window.size_changed {
-            debug!(
-                "Window size changed from {}x{} to {}x{}",
-                extracted_window.physical_width,
-                extracted_window.physical_height,
-                new_width,
-                new_height
-            );
-            extracted_window.physical_width = new_width;
-            extracted_window.physical_height = new_height;
-        }
+        extracted_window.desired_maximum_frame_latency_changed =
+            window.desired_maximum_frame_latency != extracted_window.desired_maximum_frame_latency;
+        extracted_window.alpha_mode_changed =
+            window.composite_alpha_mode != extracted_window.alpha_mode;
 
         if extracted_window.present_mode_changed {
             debug!(
@@ -184,23 +365,93 @@ fn extract_windows(
             );
             extracted_window.present_mode = window.present_mode;
         }
+
+        if extracted_window.desired_maximum_frame_latency_changed {
+            debug!(
+                "Window desired maximum frame latency changed from {:?} to {:?}",
+                extracted_window.desired_maximum_frame_latency,
+                window.desired_maximum_frame_latency
+            );
+            extracted_window.desired_maximum_frame_latency = window.desired_maximum_frame_latency;
+        }
+
+        if extracted_window.alpha_mode_changed {
+            debug!(
+                "Window composite alpha mode changed from {:?} to {:?}",
+                extracted_window.alpha_mode, window.composite_alpha_mode
+            );
+            extracted_window.alpha_mode = window.composite_alpha_mode;
+        }
+
+        let requested_format = surface_format.map(|format| format.0);
+        if requested_format != extracted_window.requested_format {
+            debug!(
+                "Window surface format changed from {:?} to {:?}",
+                extracted_window.requested_format, requested_format
+            );
+            extracted_window.requested_format = requested_format;
+            // The surface format can't be reconfigured, so recreate the surface.
+            drop(extracted_window.swap_chain_texture.take());
+            window_surfaces.remove(&entity);
+        }
+
+        if window.dynamic_range != extracted_window.dynamic_range {
+            debug!(
+                "Window dynamic range changed from {:?} to {:?}",
+                extracted_window.dynamic_range, window.dynamic_range
+            );
+            extracted_window.dynamic_range = window.dynamic_range;
+            // The surface format can't be reconfigured, so recreate the surface.
+            drop(extracted_window.swap_chain_texture.take());
+            window_surfaces.remove(&entity);
+        }
+
+        // Longer intervals could overflow the `Instant` of the next render.
+        extracted_window.render_interval =
+            render_rate.map(|render_rate| render_rate.interval.min(WindowRenderRate::MAX_INTERVAL));
+        extracted_window.update_render_this_frame(now);
+        if !extracted_window.render_this_frame {
+            // Nothing renders to this window this frame, so release the swap chain texture we
+            // may still hold, which would otherwise be presented by the next render.
+            drop(extracted_window.swap_chain_texture.take());
+            #[cfg_attr(
+                target_arch = "wasm32",
+                expect(clippy::drop_non_drop, reason = "texture views are not drop on wasm")
+            )]
+            drop(extracted_window.swap_chain_texture_view.take());
+        }
     }
 
     for closing_window in closing.read() {
         extracted_windows.remove(&closing_window.window);
         window_surfaces.remove(&closing_window.window);
     }
-    for removed_window in removed.read() {
+    for removed_window in removed.read().chain(removed_offscreen.read()) {
         extracted_windows.remove(&removed_window);
         window_surfaces.remove(&removed_window);
     }
 }
 
+/// Keeps the app updating while the surface of a window waits for its resize to settle, so that
+/// the surface is reconfigured even if no other event wakes the app up.
+fn request_redraw_during_resize(
+    mut main_world: ResMut<MainWorld>,
+    extracted_windows: Res<ExtractedWindows>,
+) {
+    if extracted_windows
+        .values()
+        .any(ExtractedWindow::resize_pending)
+    {
+        main_world.write_message(RequestRedraw);
+    }
+}
+
 struct SurfaceData {
     // TODO: what lifetime should this be?
     surface: WgpuWrapper<wgpu::Surface<'static>>,
     configuration: SurfaceConfiguration,
     texture_view_format: Option<TextureFormat>,
+    present_modes: Vec<PresentMode>,
 }
 
 #[derive(Resource, Default)]
@@ -242,6 +493,7 @@ pub fn prepare_windows(
     mut windows: ResMut<ExtractedWindows>,
     mut window_surfaces: ResMut<WindowSurfaces>,
     render_device: Res<RenderDevice>,
+    error_reporter: Res<RenderErrorReporter>,
     #[cfg(target_os = "linux")] render_instance: Res<RenderInstance>,
 ) {
     for window in windows.windows.values_mut() {
@@ -250,8 +502,17 @@ pub fn prepare_windows(
             continue;
         };
 
+        // This window skips this frame because of its `WindowRenderRate`.
+        if !window.render_this_frame {
+            continue;
+        }
+
         // We didn't present the previous frame, so we can keep using our existing swapchain texture.
-        if window.has_swapchain_texture() && !window.size_changed && !window.present_mode_changed {
+        if window.has_swapchain_texture()
+            && !window.size_changed
+            && !window.present_mode_changed
+            && !window.desired_maximum_frame_latency_changed
+        {
             continue;
         }
 
@@ -298,6 +559,15 @@ pub fn prepare_windows(
                 };
                 window.set_swapchain_texture(frame);
             }
+            Err(wgpu::SurfaceError::Lost) => {
+                // The surface is created again on the next frame.
+                window_surfaces.remove(&window.entity);
+                error_reporter.report(RenderError::new(
+                    RenderErrorCode::SurfaceLost,
+                    "The surface of the window was lost",
+                ));
+                continue;
+            }
             #[cfg(target_os = "linux")]
             Err(wgpu::SurfaceError::Timeout) if may_erroneously_timeout() => {
                 bevy_log::trace!(
@@ -313,14 +583,121 @@ pub fn prepare_windows(
     }
 }
 
+/// The textures the [`OffscreenWindow`]s are rendered to, by window entity.
+#[derive(Resource, Default)]
+struct OffscreenWindowTextures(EntityHashMap<Texture>);
+
+/// Provides the [`OffscreenWindow`]s with a texture of their size to render to, in place of the
+/// swap chain texture of a surface.
+///
+/// The texture is kept until the window is resized, and is never presented. Screenshots of the
+/// window copy it like a swap chain texture.
+pub fn prepare_offscreen_windows(
+    mut windows: ResMut<ExtractedWindows>,
+    mut textures: ResMut<OffscreenWindowTextures>,
+    render_device: Res<RenderDevice>,
+) {
+    textures.0.retain(|entity, _| {
+        windows
+            .get(entity)
+            .is_some_and(|window| window.handle.is_none())
+    });
+    for window in windows.windows.values_mut() {
+        if window.handle.is_some() || !window.render_this_frame {
+            continue;
+        }
+        let format = offscreen_window_format(window);
+        let texture = textures
+            .0
+            .entry(window.entity)
+            .or_insert_with(|| create_offscreen_window_texture(&render_device, window, format));
+        if texture.width() != window.physical_width
+            || texture.height() != window.physical_height
+            || texture.format() != format
+        {
+            *texture = create_offscreen_window_texture(&render_device, window, format);
+        }
+        let view_format = format.add_srgb_suffix();
+        window.swap_chain_texture_format = Some(format);
+        window.swap_chain_texture_view_format = Some(view_format);
+        window.swap_chain_texture_view = Some(texture.create_view(&TextureViewDescriptor {
+            format: Some(view_format),
+            ..default()
+        }));
+    }
+}
+
+/// Picks the texture format of an [`OffscreenWindow`] like [`surface_format`] does, as if every
+/// format was supported.
+fn offscreen_window_format(window: &ExtractedWindow) -> TextureFormat {
+    if let Some(format) = window.requested_format {
+        return format;
+    }
+    match window.dynamic_range {
+        WindowDynamicRange::LinearFloat => TextureFormat::Rgba16Float,
+        _ => TextureFormat::Rgba8UnormSrgb,
+    }
+}
+
+fn create_offscreen_window_texture(
+    render_device: &RenderDevice,
+    window: &ExtractedWindow,
+    format: TextureFormat,
+) -> Texture {
+    render_device.create_texture(&TextureDescriptor {
+        label: Some("offscreen_window_texture"),
+        size: Extent3d {
+            width: window.physical_width,
+            height: window.physical_height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format,
+        usage: TextureUsages::RENDER_ATTACHMENT
+            | TextureUsages::COPY_SRC
+            | TextureUsages::TEXTURE_BINDING,
+        view_formats: &[format.add_srgb_suffix()],
+    })
+}
+
+/// Inserts the [`SupportedPresentModes`] of the window surfaces in the main world.
+fn sync_supported_present_modes(
+    mut main_world: ResMut<MainWorld>,
+    window_surfaces: Res<WindowSurfaces>,
+) {
+    for (entity, data) in &window_surfaces.surfaces {
+        let Ok(mut window) = main_world.get_entity_mut(*entity) else {
+            continue;
+        };
+        if window
+            .get::<SupportedPresentModes>()
+            .is_none_or(|supported| supported.0 != data.present_modes)
+        {
+            window.insert(SupportedPresentModes(data.present_modes.clone()));
+        }
+    }
+}
+
 pub fn need_surface_configuration(
     windows: Res<ExtractedWindows>,
     window_surfaces: Res<WindowSurfaces>,
+    suspended: Option<Res<AppSuspended>>,
 ) -> bool {
+    // The surfaces released on suspend are only created again once the app resumes.
+    if suspended.is_some_and(|suspended| suspended.0) {
+        return false;
+    }
     for window in windows.windows.values() {
+        if window.handle.is_none() {
+            continue;
+        }
         if !window_surfaces.configured_windows.contains(&window.entity)
             || window.size_changed
             || window.present_mode_changed
+            || window.desired_maximum_frame_latency_changed
+            || window.alpha_mode_changed
         {
             return true;
         }
@@ -334,6 +711,19 @@ pub fn need_surface_configuration(
 // has to wait for the cpu to finish to start on the next frame.
 const DEFAULT_DESIRED_MAXIMUM_FRAME_LATENCY: u32 = 2;
 
+/// The maximum number of frames queued on the GPU for windows that don't set
+/// [`Window::desired_maximum_frame_latency`].
+///
+/// This is set from [`WgpuSettings::desired_maximum_frame_latency`](crate::settings::WgpuSettings::desired_maximum_frame_latency).
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DefaultFrameLatency(pub NonZero<u32>);
+
+impl Default for DefaultFrameLatency {
+    fn default() -> Self {
+        Self(NonZero::new(DEFAULT_DESIRED_MAXIMUM_FRAME_LATENCY).unwrap())
+    }
+}
+
 /// Creates window surfaces.
 pub fn create_surfaces(
     // By accessing a NonSend resource, we tell the scheduler to put this system on the main thread,
@@ -344,15 +734,19 @@ pub fn create_surfaces(
     render_instance: Res<RenderInstance>,
     render_adapter: Res<RenderAdapter>,
     render_device: Res<RenderDevice>,
+    default_frame_latency: Res<DefaultFrameLatency>,
 ) {
     for window in windows.windows.values_mut() {
+        let Some(handle) = window.handle.clone() else {
+            continue;
+        };
         let data = window_surfaces
             .surfaces
             .entry(window.entity)
             .or_insert_with(|| {
                 let surface_target = SurfaceTargetUnsafe::RawHandle {
-                    raw_display_handle: window.handle.get_display_handle(),
-                    raw_window_handle: window.handle.get_window_handle(),
+                    raw_display_handle: handle.get_display_handle(),
+                    raw_window_handle: handle.get_window_handle(),
                 };
                 // SAFETY: The window handles in ExtractedWindows will always be valid objects to create surfaces on
                 let surface = unsafe {
@@ -364,22 +758,12 @@ pub fn create_surfaces(
                 };
                 let caps = surface.get_capabilities(&render_adapter);
                 let present_mode = present_mode(window, &caps);
+                let alpha_mode = alpha_mode(window, &caps);
                 let formats = caps.formats;
-                // For future HDR output support, we'll need to request a format that supports HDR,
-                // but as of wgpu 0.15 that is not yet supported.
-                // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
-                let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
-                    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
-                    if available_format == TextureFormat::Rgba8UnormSrgb
-                        || available_format == TextureFormat::Bgra8UnormSrgb
-                    {
-                        format = available_format;
-                        break;
-                    }
-                }
+                let format = surface_format(window, &formats);
 
-                let texture_view_format = if !format.is_srgb() {
+                // Formats with an sRGB variant are rendered to through an sRGB view.
+                let texture_view_format = if format.add_srgb_suffix() != format {
                     Some(format.add_srgb_suffix())
                 } else {
                     None
@@ -391,20 +775,8 @@ pub fn create_surfaces(
                     usage: TextureUsages::RENDER_ATTACHMENT,
                     present_mode,
                     desired_maximum_frame_latency: window
-                        .desired_maximum_frame_latency
-                        .map(NonZero::<u32>::get)
-                        .unwrap_or(DEFAULT_DESIRED_MAXIMUM_FRAME_LATENCY),
-                    alpha_mode: match window.alpha_mode {
-                        CompositeAlphaMode::Auto => wgpu::CompositeAlphaMode::Auto,
-                        CompositeAlphaMode::Opaque => wgpu::CompositeAlphaMode::Opaque,
-                        CompositeAlphaMode::PreMultiplied => {
-                            wgpu::CompositeAlphaMode::PreMultiplied
-                        }
-                        CompositeAlphaMode::PostMultiplied => {
-                            wgpu::CompositeAlphaMode::PostMultiplied
-                        }
-                        CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
-                    },
+                        .maximum_frame_latency(*default_frame_latency),
+                    alpha_mode,
                     view_formats: match texture_view_format {
                         Some(format) => vec![format],
                         None => vec![],
@@ -417,10 +789,25 @@ pub fn create_surfaces(
                     surface: WgpuWrapper::new(surface),
                     configuration,
                     texture_view_format,
+                    present_modes: caps
+                        .present_modes
+                        .iter()
+                        .filter_map(|present_mode| match present_mode {
+                            wgpu::PresentMode::Fifo => Some(PresentMode::Fifo),
+                            wgpu::PresentMode::FifoRelaxed => Some(PresentMode::FifoRelaxed),
+                            wgpu::PresentMode::Mailbox => Some(PresentMode::Mailbox),
+                            wgpu::PresentMode::Immediate => Some(PresentMode::Immediate),
+                            _ => None,
+                        })
+                        .collect(),
                 }
             });
 
-        if window.size_changed || window.present_mode_changed {
+        if window.size_changed
+            || window.present_mode_changed
+            || window.desired_maximum_frame_latency_changed
+            || window.alpha_mode_changed
+        {
             // normally this is dropped on present but we double check here to be safe as failure to
             // drop it will cause validation errors in wgpu
             drop(window.swap_chain_texture.take());
@@ -434,6 +821,9 @@ pub fn create_surfaces(
             data.configuration.height = window.physical_height;
             let caps = data.surface.get_capabilities(&render_adapter);
             data.configuration.present_mode = present_mode(window, &caps);
+            data.configuration.alpha_mode = alpha_mode(window, &caps);
+            data.configuration.desired_maximum_frame_latency =
+                window.maximum_frame_latency(*default_frame_latency);
             render_device.configure_surface(&data.surface, &data.configuration);
         }
 
@@ -441,6 +831,59 @@ pub fn create_surfaces(
     }
 }
 
+fn surface_format(window: &ExtractedWindow, formats: &[TextureFormat]) -> TextureFormat {
+    if let Some(format) = window.requested_format {
+        if formats.contains(&format) {
+            return format;
+        }
+        warn!(
+            "Surface format {:?} requested but the surface doesn't support it. Available formats: {:?}",
+            format, formats
+        );
+    }
+    if window.dynamic_range == WindowDynamicRange::LinearFloat {
+        // wgpu configures Rgba16Float surfaces with the extended linear sRGB (scRGB) color space.
+        // The colors are presented as rendered, without an HDR output transform.
+        if formats.contains(&TextureFormat::Rgba16Float) {
+            return TextureFormat::Rgba16Float;
+        }
+        warn!(
+            "WindowDynamicRange::LinearFloat requested but the surface doesn't support Rgba16Float. Falling back to SDR. Available formats: {:?}",
+            formats
+        );
+    }
+    // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
+    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
+    formats
+        .iter()
+        .copied()
+        .find(|format| {
+            *format == TextureFormat::Rgba8UnormSrgb || *format == TextureFormat::Bgra8UnormSrgb
+        })
+        .unwrap_or_else(|| *formats.first().expect("No supported formats for surface"))
+}
+
+fn alpha_mode(
+    window: &ExtractedWindow,
+    caps: &wgpu::SurfaceCapabilities,
+) -> wgpu::CompositeAlphaMode {
+    let alpha_mode = match window.alpha_mode {
+        CompositeAlphaMode::Auto => wgpu::CompositeAlphaMode::Auto,
+        CompositeAlphaMode::Opaque => wgpu::CompositeAlphaMode::Opaque,
+        CompositeAlphaMode::PreMultiplied => wgpu::CompositeAlphaMode::PreMultiplied,
+        CompositeAlphaMode::PostMultiplied => wgpu::CompositeAlphaMode::PostMultiplied,
+        CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
+    };
+    if alpha_mode == wgpu::CompositeAlphaMode::Auto || caps.alpha_modes.contains(&alpha_mode) {
+        return alpha_mode;
+    }
+    warn!(
+        "Composite alpha mode {:?} requested but the surface doesn't support it. Falling back to Auto. Available alpha modes: {:?}",
+        alpha_mode, caps.alpha_modes
+    );
+    wgpu::CompositeAlphaMode::Auto
+}
+
 fn present_mode(
     window: &mut ExtractedWindow,
     caps: &wgpu::SurfaceCapabilities,
@@ -467,6 +910,13 @@ fn present_mode(
             wgpu::PresentMode::Immediate,
             wgpu::PresentMode::Fifo,
         ][..],
+        // Stay uncapped without tearing if tearing isn't allowed, e.g. on DX12 without
+        // `DXGI_FEATURE_PRESENT_ALLOW_TEARING`.
+        wgpu::PresentMode::Immediate => &[
+            wgpu::PresentMode::Immediate,
+            wgpu::PresentMode::Mailbox,
+            wgpu::PresentMode::Fifo,
+        ][..],
         // Always end in FIFO to make sure it's always supported
         x => &[x, wgpu::PresentMode::Fifo][..],
     };
@@ -486,3 +936,117 @@ fn present_mode(
     }
     new_present_mode
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ExtractedWindow, SurfaceResizeDebounce};
+    use bevy_ecs::entity::Entity;
+    use bevy_platform::time::Instant;
+    use bevy_window::{CompositeAlphaMode, PresentMode, WindowDynamicRange};
+    use core::time::Duration;
+
+    fn extracted_window(width: u32, height: u32) -> ExtractedWindow {
+        ExtractedWindow {
+            entity: Entity::PLACEHOLDER,
+            handle: None,
+            physical_width: width,
+            physical_height: height,
+            present_mode: PresentMode::Fifo,
+            desired_maximum_frame_latency: None,
+            swap_chain_texture_view: None,
+            swap_chain_texture: None,
+            swap_chain_texture_format: None,
+            swap_chain_texture_view_format: None,
+            size_changed: false,
+            present_mode_changed: false,
+            desired_maximum_frame_latency_changed: false,
+            alpha_mode: CompositeAlphaMode::Auto,
+            alpha_mode_changed: false,
+            requested_format: None,
+            dynamic_range: WindowDynamicRange::Sdr,
+            needs_initial_present: false,
+            render_interval: None,
+            render_this_frame: true,
+            next_render: None,
+            pending_resize: None,
+        }
+    }
+
+    #[test]
+    fn resize_waits_for_size_to_settle() {
+        let debounce = SurfaceResizeDebounce::default();
+        let mut window = extracted_window(800, 600);
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        window.update_size(800, 600, debounce, start);
+        assert!(!window.size_changed);
+        assert!(!window.resize_pending());
+
+        // The surface keeps its size while the window is being resized.
+        window.update_size(900, 600, debounce, start);
+        assert!(!window.size_changed);
+        assert!(window.resize_pending());
+        window.update_size(1000, 600, debounce, start + ms(30));
+        assert!(!window.size_changed);
+        window.update_size(1000, 600, debounce, start + ms(60));
+        assert!(!window.size_changed);
+        assert_eq!((window.physical_width, window.physical_height), (800, 600));
+
+        // Once the size stays the same for the delay, the surface is resized.
+        window.update_size(1000, 600, debounce, start + ms(80));
+        assert!(window.size_changed);
+        assert!(!window.resize_pending());
+        assert_eq!((window.physical_width, window.physical_height), (1000, 600));
+
+        window.update_size(1000, 600, debounce, start + ms(90));
+        assert!(!window.size_changed);
+    }
+
+    #[test]
+    fn long_resize_is_applied_after_max_delay() {
+        let debounce = SurfaceResizeDebounce::default();
+        let mut window = extracted_window(800, 600);
+        let start = Instant::now();
+
+        let mut width = 800;
+        let mut elapsed = Duration::ZERO;
+        while elapsed < debounce.max_delay {
+            width += 10;
+            window.update_size(width, 600, debounce, start + elapsed);
+            assert!(!window.size_changed);
+            elapsed += Duration::from_millis(20);
+        }
+        width += 10;
+        window.update_size(width, 600, debounce, start + elapsed);
+        assert!(window.size_changed);
+        assert_eq!(window.physical_width, width);
+    }
+
+    #[test]
+    fn resize_back_to_surface_size_is_cancelled() {
+        let debounce = SurfaceResizeDebounce::default();
+        let mut window = extracted_window(800, 600);
+        let start = Instant::now();
+
+        window.update_size(900, 600, debounce, start);
+        assert!(window.resize_pending());
+        window.update_size(800, 600, debounce, start + Duration::from_millis(10));
+        assert!(!window.size_changed);
+        assert!(!window.resize_pending());
+    }
+
+    #[test]
+    fn zero_delay_resizes_immediately() {
+        let debounce = SurfaceResizeDebounce {
+            delay: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut window = extracted_window(800, 600);
+
+        window.update_size(900, 700, debounce, Instant::now());
+        assert!(window.size_changed);
+        assert!(!window.resize_pending());
+        assert_eq!((window.physical_width, window.physical_height), (900, 700));
+    }
+}
@@ -0,0 +1,196 @@
+//! Records the frames of a render target, e.g. for trailers or visual regression tests.
+
+use super::screenshot::{Screenshot, ScreenshotCaptured};
+use alloc::sync::Arc;
+use bevy_app::{App, Last, Plugin};
+use bevy_camera::RenderTarget;
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_log::error;
+use bevy_tasks::AsyncComputeTaskPool;
+use core::{
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::path::PathBuf;
+
+/// Captures the frames of the render targets of [`FrameRecorder`]s.
+pub struct FrameRecorderPlugin;
+
+impl Plugin for FrameRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, record_frames);
+    }
+}
+
+/// Records every frame rendered to [`target`](Self::target) while it is not
+/// [`paused`](Self::paused).
+///
+/// Each frame is captured as a [`Screenshot`], so it is delivered to the output a few frames
+/// later. At most [`max_frames_in_flight`](Self::max_frames_in_flight) frames are captured or
+/// encoded at once, which bounds the memory used by the recording: the transfer buffers of the
+/// screenshots are reused, and frames are skipped while the output can't keep up. A frame
+/// stops being in flight once its [`Screenshot`] entity is despawned, even if it wasn't captured,
+/// e.g. because another screenshot of the same target was taken that frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::view::recorder::{FrameRecorder, FrameRecorderOutput};
+/// fn start_recording(mut commands: Commands) {
+///     commands.spawn(FrameRecorder::primary_window(FrameRecorderOutput::PngSequence(
+///         "recording".into(),
+///     )));
+/// }
+/// ```
+#[derive(Component, Debug)]
+pub struct FrameRecorder {
+    /// The render target to record.
+    pub target: RenderTarget,
+    /// Where the recorded frames go.
+    pub output: FrameRecorderOutput,
+    /// Whether recording is paused.
+    pub paused: bool,
+    /// The number of the next recorded frame.
+    pub next_frame: u32,
+    /// The maximum number of frames being captured or encoded at once. Defaults to
+    /// [`FrameRecorder::DEFAULT_MAX_FRAMES_IN_FLIGHT`].
+    pub max_frames_in_flight: usize,
+    /// The number of frames skipped because [`max_frames_in_flight`](Self::max_frames_in_flight)
+    /// frames were already in flight. Skipped frames don't get a frame number.
+    pub skipped_frames: u32,
+    frames_in_flight: Arc<AtomicUsize>,
+}
+
+impl FrameRecorder {
+    /// The default value of [`FrameRecorder::max_frames_in_flight`].
+    pub const DEFAULT_MAX_FRAMES_IN_FLIGHT: usize = 4;
+
+    /// Records the frames of `target` to `output`.
+    pub fn new(target: RenderTarget, output: FrameRecorderOutput) -> Self {
+        Self {
+            target,
+            output,
+            paused: false,
+            next_frame: 0,
+            max_frames_in_flight: Self::DEFAULT_MAX_FRAMES_IN_FLIGHT,
+            skipped_frames: 0,
+            frames_in_flight: Arc::default(),
+        }
+    }
+
+    /// Records the frames of the primary window to `output`.
+    pub fn primary_window(output: FrameRecorderOutput) -> Self {
+        Self::new(Screenshot::primary_window().0, output)
+    }
+
+    /// Returns the number of frames being captured or encoded.
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames_in_flight.load(Ordering::Acquire)
+    }
+}
+
+/// Where the frames of a [`FrameRecorder`] go.
+#[derive(Clone)]
+pub enum FrameRecorderOutput {
+    /// Encodes the frames to PNG files in this directory, named after their frame number, e.g.
+    /// `frame_00042.png`. Encoding happens on the [`AsyncComputeTaskPool`].
+    PngSequence(PathBuf),
+    /// Calls this function with the number and image of each frame.
+    Callback(Arc<dyn Fn(u32, &Image) + Send + Sync>),
+}
+
+impl FrameRecorderOutput {
+    /// Calls `callback` with the number and image of each frame.
+    pub fn callback(callback: impl Fn(u32, &Image) + Send + Sync + 'static) -> Self {
+        Self::Callback(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for FrameRecorderOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PngSequence(directory) => f.debug_tuple("PngSequence").field(directory).finish(),
+            Self::Callback(_) => f.debug_tuple("Callback").finish_non_exhaustive(),
+        }
+    }
+}
+
+fn record_frames(mut commands: Commands, mut recorders: Query<&mut FrameRecorder>) {
+    for mut recorder in &mut recorders {
+        if recorder.paused {
+            continue;
+        }
+        if recorder.frames_in_flight() >= recorder.max_frames_in_flight {
+            recorder.skipped_frames += 1;
+            continue;
+        }
+        let in_flight = FrameInFlight::new(&recorder.frames_in_flight);
+        let frame = recorder.next_frame;
+        recorder.next_frame += 1;
+        let output = recorder.output.clone();
+        commands
+            .spawn((Screenshot(recorder.target.clone()), in_flight))
+            .observe(
+                move |screenshot_captured: On<ScreenshotCaptured>,
+                      in_flight: Query<&FrameInFlight>| {
+                    match &output {
+                        FrameRecorderOutput::PngSequence(directory) => {
+                            let path = directory.join(format!("frame_{frame:05}.png"));
+                            let image = screenshot_captured.image.clone();
+                            // Keep the frame in flight until it is encoded.
+                            let in_flight = in_flight.get(screenshot_captured.entity).ok().cloned();
+                            AsyncComputeTaskPool::get()
+                                .spawn(async move {
+                                    save_png(image, path);
+                                    drop(in_flight);
+                                })
+                                .detach();
+                        }
+                        FrameRecorderOutput::Callback(callback) => {
+                            callback(frame, &screenshot_captured.image);
+                        }
+                    }
+                },
+            );
+    }
+}
+
+/// Counts a frame of a [`FrameRecorder`] as in flight until the screenshot entity is despawned
+/// and the last clone is dropped, whether the frame was captured or not.
+#[derive(Component, Clone)]
+struct FrameInFlight(Arc<FrameInFlightSlot>);
+
+impl FrameInFlight {
+    fn new(frames_in_flight: &Arc<AtomicUsize>) -> Self {
+        frames_in_flight.fetch_add(1, Ordering::AcqRel);
+        Self(Arc::new(FrameInFlightSlot(frames_in_flight.clone())))
+    }
+}
+
+struct FrameInFlightSlot(Arc<AtomicUsize>);
+
+impl Drop for FrameInFlightSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn save_png(image: Image, path: PathBuf) {
+    let image = match image.try_into_dynamic() {
+        Ok(image) => image.to_rgb8(),
+        Err(e) => {
+            error!("Cannot record frame, screen format cannot be understood: {e}");
+            return;
+        }
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(directory) = path.parent()
+        && let Err(e) = std::fs::create_dir_all(directory)
+    {
+        error!("Cannot record frame, IO error: {e}");
+        return;
+    }
+    if let Err(e) = image.save_with_format(&path, image::ImageFormat::Png) {
+        error!("Cannot record frame to {}: {e}", path.display());
+    }
+}
@@ -7,13 +7,16 @@ use crate::{
         SpecializedRenderPipeline, SpecializedRenderPipelines, Texture, TextureUsages, TextureView,
     },
     renderer::RenderDevice,
+    reset_render_resource,
     texture::{GpuImage, ManualTextureViews, OutputColorAttachment},
     view::{prepare_view_attachments, prepare_view_targets, ViewTargetAttachments, WindowSurfaces},
-    ExtractSchedule, MainWorld, Render, RenderApp, RenderStartup, RenderSystems,
+    ExtractSchedule, MainWorld, Render, RenderApp, RenderDeviceReset, RenderStartup, RenderSystems,
 };
 use alloc::{borrow::Cow, sync::Arc};
 use bevy_app::{First, Plugin, Update};
-use bevy_asset::{embedded_asset, load_embedded_asset, AssetServer, Handle, RenderAssetUsages};
+use bevy_asset::{
+    embedded_asset, load_embedded_asset, AssetPath, AssetServer, Assets, Handle, RenderAssetUsages,
+};
 use bevy_camera::{ManualTextureViewHandle, NormalizedRenderTarget, RenderTarget};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
@@ -28,10 +31,10 @@ use bevy_material::{
         VertexState,
     },
 };
-use bevy_platform::collections::HashSet;
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_reflect::Reflect;
 use bevy_shader::Shader;
-use bevy_tasks::AsyncComputeTaskPool;
+use bevy_tasks::{AsyncComputeTaskPool, IoTaskPool};
 use bevy_utils::default;
 use bevy_window::{PrimaryWindow, WindowRef};
 use core::ops::Deref;
@@ -75,6 +78,9 @@ pub struct ScreenshotCaptured {
 ///       .observe(save_to_disk("screenshot.png"));
 /// }
 /// ```
+///
+/// The captured image can also be stored as an asset with [`save_to_image_asset`], or written
+/// through an asset source with [`save_to_asset_source`].
 #[derive(Component, Deref, DerefMut, Reflect, Debug)]
 #[reflect(Component, Debug)]
 pub struct Screenshot(pub RenderTarget);
@@ -130,68 +136,189 @@ struct RenderScreenshotsPrepared(EntityHashMap<ScreenshotPreparedState>);
 #[derive(Resource, Deref, DerefMut)]
 struct RenderScreenshotsSender(Sender<(Entity, Image)>);
 
+/// The transfer buffers of the screenshots whose image has been read, reused by the screenshots of
+/// the next frame with the same size, e.g. when a
+/// [`FrameRecorder`](super::recorder::FrameRecorder) captures every frame.
+///
+/// Buffers that aren't reused by the next frame are dropped, so the pool only holds as many
+/// buffers as there are screenshots in flight.
+#[derive(Resource)]
+struct ScreenshotBufferPool {
+    free: HashMap<u64, Vec<Buffer>>,
+    returned_sender: async_channel::Sender<Buffer>,
+    returned_receiver: async_channel::Receiver<Buffer>,
+}
+
+impl Default for ScreenshotBufferPool {
+    fn default() -> Self {
+        let (returned_sender, returned_receiver) = async_channel::unbounded();
+        Self {
+            free: HashMap::default(),
+            returned_sender,
+            returned_receiver,
+        }
+    }
+}
+
+impl ScreenshotBufferPool {
+    /// Drops the buffers that weren't reused since the last call, and makes the buffers returned
+    /// since then available.
+    fn recycle(&mut self) {
+        self.free.clear();
+        while let Ok(buffer) = self.returned_receiver.try_recv() {
+            self.free.entry(buffer.size()).or_default().push(buffer);
+        }
+    }
+
+    fn get(&mut self, render_device: &RenderDevice, size: u64) -> Buffer {
+        if let Some(buffer) = self.free.get_mut(&size).and_then(Vec::pop) {
+            return buffer;
+        }
+        render_device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("screenshot-transfer-buffer"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+}
+
 /// Saves the captured screenshot to disk at the provided path.
 pub fn save_to_disk(path: impl AsRef<Path>) -> impl FnMut(On<ScreenshotCaptured>) {
     let path = path.as_ref().to_owned();
     move |screenshot_captured| {
-        let img = screenshot_captured.image.clone();
-        match img.try_into_dynamic() {
-            Ok(dyn_img) => match image::ImageFormat::from_path(&path) {
-                Ok(format) => {
-                    // discard the alpha channel which stores brightness values when HDR is enabled to make sure
-                    // the screenshot looks right
-                    let img = dyn_img.to_rgb8();
-                    #[cfg(not(target_arch = "wasm32"))]
-                    match img.save_with_format(&path, format) {
-                        Ok(_) => info!("Screenshot saved to {}", path.display()),
-                        Err(e) => error!("Cannot save screenshot, IO error: {e}"),
-                    }
+        let Some(bytes) = encode_screenshot(&screenshot_captured.image, &path) else {
+            return;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        match std::fs::write(&path, bytes) {
+            Ok(()) => info!("Screenshot saved to {}", path.display()),
+            Err(e) => error!("Cannot save screenshot, IO error: {e}"),
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let save_screenshot = || {
+                use wasm_bindgen::{JsCast, JsValue};
+
+                let parts = js_sys::Array::of1(&js_sys::Uint8Array::new_from_slice(&bytes).into());
+                let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
+                let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+                let window = web_sys::window().unwrap();
+                let document = window.document().unwrap();
+                let link = document.create_element("a")?;
+                link.set_attribute("href", &url)?;
+                link.set_attribute(
+                    "download",
+                    path.file_name()
+                        .and_then(|filename| filename.to_str())
+                        .ok_or_else(|| JsValue::from_str("Invalid filename"))?,
+                )?;
+                let html_element = link.dyn_into::<web_sys::HtmlElement>()?;
+                html_element.click();
+                web_sys::Url::revoke_object_url(&url)?;
+                Ok::<(), JsValue>(())
+            };
+
+            match (save_screenshot)() {
+                Ok(_) => info!("Screenshot saved to {}", path.display()),
+                Err(e) => error!("Cannot save screenshot, error: {e:?}"),
+            };
+        }
+    }
+}
 
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        let save_screenshot = || {
-                            use image::EncodableLayout;
-                            use wasm_bindgen::{JsCast, JsValue};
-
-                            let mut image_buffer = std::io::Cursor::new(Vec::new());
-                            img.write_to(&mut image_buffer, format)
-                                .map_err(|e| JsValue::from_str(&format!("{e}")))?;
-
-                            let parts = js_sys::Array::of1(
-                                &js_sys::Uint8Array::new_from_slice(
-                                    image_buffer.into_inner().as_bytes(),
-                                )
-                                .into(),
-                            );
-                            let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
-                            let url = web_sys::Url::create_object_url_with_blob(&blob)?;
-                            let window = web_sys::window().unwrap();
-                            let document = window.document().unwrap();
-                            let link = document.create_element("a")?;
-                            link.set_attribute("href", &url)?;
-                            link.set_attribute(
-                                "download",
-                                path.file_name()
-                                    .and_then(|filename| filename.to_str())
-                                    .ok_or_else(|| JsValue::from_str("Invalid filename"))?,
-                            )?;
-                            let html_element = link.dyn_into::<web_sys::HtmlElement>()?;
-                            html_element.click();
-                            web_sys::Url::revoke_object_url(&url)?;
-                            Ok::<(), JsValue>(())
-                        };
-
-                        match (save_screenshot)() {
-                            Ok(_) => info!("Screenshot saved to {}", path.display()),
-                            Err(e) => error!("Cannot save screenshot, error: {e:?}"),
-                        };
+/// Stores the captured screenshot in the [`Assets<Image>`] under `handle`, e.g. to show it in the
+/// UI or send it elsewhere without going through the file system.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::{Assets, Handle};
+/// # use bevy_image::Image;
+/// # use bevy_render::view::screenshot::{save_to_image_asset, Screenshot};
+/// fn capture(mut commands: Commands, images: Res<Assets<Image>>) -> Handle<Image> {
+///     let handle = images.reserve_handle();
+///     commands
+///         .spawn(Screenshot::primary_window())
+///         .observe(save_to_image_asset(handle.clone()));
+///     handle
+/// }
+/// ```
+pub fn save_to_image_asset(
+    handle: Handle<Image>,
+) -> impl FnMut(On<ScreenshotCaptured>, ResMut<Assets<Image>>) {
+    move |screenshot_captured, mut images| {
+        if let Err(e) = images.insert(&handle, screenshot_captured.image.clone()) {
+            error!("Cannot store screenshot: {e}");
+        }
+    }
+}
+
+/// Writes the captured screenshot to `path` with the [`AssetWriter`](bevy_asset::io::AssetWriter)
+/// of its asset source, e.g. when the app can only write to directories exposed as asset sources,
+/// such as the pictures library of a sandboxed app.
+///
+/// The image format is deduced from the extension of the path.
+pub fn save_to_asset_source(
+    path: impl Into<AssetPath<'static>>,
+) -> impl FnMut(On<ScreenshotCaptured>, Res<AssetServer>) {
+    let path = path.into();
+    move |screenshot_captured, asset_server| {
+        let Some(bytes) = encode_screenshot(&screenshot_captured.image, path.path()) else {
+            return;
+        };
+        let asset_server = asset_server.clone();
+        let path = path.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let writer = match asset_server.get_source(path.source().clone()) {
+                    Ok(source) => match source.writer() {
+                        Ok(writer) => writer,
+                        Err(e) => {
+                            error!("Cannot save screenshot: {e}");
+                            return;
+                        }
+                    },
+                    Err(e) => {
+                        error!("Cannot save screenshot: {e}");
+                        return;
                     }
+                };
+                match writer.write_bytes(path.path(), &bytes).await {
+                    Ok(()) => info!("Screenshot saved to {path}"),
+                    Err(e) => error!("Cannot save screenshot, IO error: {e}"),
                 }
-                Err(e) => error!("Cannot save screenshot, requested format not recognized: {e}"),
-            },
-            Err(e) => error!("Cannot save screenshot, screen format cannot be understood: {e}"),
+            })
+            .detach();
+    }
+}
+
+/// Encodes the captured screenshot in the image format deduced from the extension of `path`, or
+/// logs why it can't.
+fn encode_screenshot(image: &Image, path: &Path) -> Option<Vec<u8>> {
+    let img = match image.clone().try_into_dynamic() {
+        // discard the alpha channel which stores brightness values when HDR is enabled to make sure
+        // the screenshot looks right
+        Ok(dyn_img) => dyn_img.to_rgb8(),
+        Err(e) => {
+            error!("Cannot save screenshot, screen format cannot be understood: {e}");
+            return None;
+        }
+    };
+    let format = match image::ImageFormat::from_path(path) {
+        Ok(format) => format,
+        Err(e) => {
+            error!("Cannot save screenshot, requested format not recognized: {e}");
+            return None;
         }
+    };
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    if let Err(e) = img.write_to(&mut bytes, format) {
+        error!("Cannot save screenshot, encoding error: {e}");
+        return None;
     }
+    Some(bytes.into_inner())
 }
 
 fn clear_screenshots(mut commands: Commands, screenshots: Query<Entity, With<Captured>>) {
@@ -265,6 +392,7 @@ fn extract_screenshots(
 fn prepare_screenshots(
     targets: Res<RenderScreenshotTargets>,
     mut prepared: ResMut<RenderScreenshotsPrepared>,
+    mut buffer_pool: ResMut<ScreenshotBufferPool>,
     window_surfaces: Res<WindowSurfaces>,
     render_device: Res<RenderDevice>,
     screenshot_pipeline: Res<ScreenshotToScreenPipeline>,
@@ -275,6 +403,7 @@ fn prepare_screenshots(
     mut view_target_attachments: ResMut<ViewTargetAttachments>,
 ) {
     prepared.clear();
+    buffer_pool.recycle();
     for (entity, target) in targets.iter() {
         match target {
             NormalizedRenderTarget::Window(window) => {
@@ -295,6 +424,7 @@ fn prepare_screenshots(
                     size,
                     view_format,
                     &render_device,
+                    &mut buffer_pool,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
@@ -315,6 +445,7 @@ fn prepare_screenshots(
                     gpu_image.texture_descriptor.size,
                     view_format,
                     &render_device,
+                    &mut buffer_pool,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
@@ -339,6 +470,7 @@ fn prepare_screenshots(
                     size,
                     view_format,
                     &render_device,
+                    &mut buffer_pool,
                     &screenshot_pipeline,
                     &pipeline_cache,
                     &mut pipelines,
@@ -360,6 +492,7 @@ fn prepare_screenshot_state(
     size: Extent3d,
     format: TextureFormat,
     render_device: &RenderDevice,
+    buffer_pool: &mut ScreenshotBufferPool,
     pipeline: &ScreenshotToScreenPipeline,
     pipeline_cache: &PipelineCache,
     pipelines: &mut SpecializedRenderPipelines<ScreenshotToScreenPipeline>,
@@ -377,12 +510,10 @@ fn prepare_screenshot_state(
         view_formats: &[],
     });
     let texture_view = texture.create_view(&Default::default());
-    let buffer = render_device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("screenshot-transfer-buffer"),
-        size: gpu_readback::get_aligned_size(size, format.pixel_size().unwrap_or(0) as u32) as u64,
-        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
-        mapped_at_creation: false,
-    });
+    let buffer = buffer_pool.get(
+        render_device,
+        gpu_readback::get_aligned_size(size, format.pixel_size().unwrap_or(0) as u32) as u64,
+    );
     let bind_group = render_device.create_bind_group(
         "screenshot-to-screen-bind-group",
         &pipeline_cache.get_bind_group_layout(&pipeline.bind_group_layout),
@@ -426,6 +557,7 @@ impl Plugin for ScreenshotPlugin {
             .insert_resource(RenderScreenshotsSender(tx))
             .init_resource::<RenderScreenshotTargets>()
             .init_resource::<RenderScreenshotsPrepared>()
+            .init_resource::<ScreenshotBufferPool>()
             .init_resource::<SpecializedRenderPipelines<ScreenshotToScreenPipeline>>()
             .add_systems(RenderStartup, init_screenshot_to_screen_pipeline)
             .add_systems(ExtractSchedule, extract_screenshots.ambiguous_with_all())
@@ -435,6 +567,10 @@ impl Plugin for ScreenshotPlugin {
                     .after(prepare_view_attachments)
                     .before(prepare_view_targets)
                     .in_set(RenderSystems::PrepareViews),
+            )
+            .add_systems(
+                RenderDeviceReset,
+                reset_render_resource::<ScreenshotBufferPool>,
             );
     }
 }
@@ -631,10 +767,15 @@ pub(crate) fn collect_screenshots(world: &mut World) {
 
     let sender = world.resource::<RenderScreenshotsSender>().deref().clone();
     let prepared = world.resource::<RenderScreenshotsPrepared>();
+    let returned_buffers = world
+        .resource::<ScreenshotBufferPool>()
+        .returned_sender
+        .clone();
 
     for (entity, prepared) in prepared.iter() {
         let entity = *entity;
         let sender = sender.clone();
+        let returned_buffers = returned_buffers.clone();
         let width = prepared.size.width;
         let height = prepared.size.height;
         let texture_format = prepared.texture.format();
@@ -658,6 +799,9 @@ pub(crate) fn collect_screenshots(world: &mut World) {
             // we immediately move the data to CPU memory to avoid holding the mapped view for long
             let mut result = Vec::from(&*data);
             drop(data);
+            buffer.unmap();
+            // The pool may have been reset since, in which case the buffer is dropped.
+            let _ = returned_buffers.try_send(buffer);
 
             if result.len() != ((width * height) as usize * pixel_size) {
                 // Our buffer has been padded because we needed to align to a multiple of 256.
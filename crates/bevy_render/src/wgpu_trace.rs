@@ -0,0 +1,122 @@
+//! Records wgpu API traces of a few frames while the app runs.
+
+use bevy_ecs::{resource::Resource, world::World};
+use std::{fs, path::PathBuf};
+
+use crate::{
+    error_handler::{RenderRecreationSettings, RenderState},
+    insert_future_resources,
+    settings::RenderCreation,
+};
+
+/// Records the next frames of the app to a wgpu API trace, which can be replayed with the
+/// [wgpu player](https://github.com/gfx-rs/wgpu/wiki/Debugging-wgpu-Applications#tracing-infrastructure).
+///
+/// This lets users capture rendering bugs that are hard to reproduce when they happen, e.g. with a
+/// debug key, and send the trace to the developers. This resource is in the main world, and
+/// requires the `wgpu_trace` feature.
+///
+/// wgpu can only record a trace from the creation of a device, so the render device is recreated
+/// with [`WgpuSettings::trace_path`](crate::settings::WgpuSettings::trace_path) set for the
+/// capture, as with [`RenderErrorPolicy::RecreateDevice`](crate::error_handler::RenderErrorPolicy::RecreateDevice),
+/// and recreated again without it once the frames are recorded. The trace then also holds the
+/// creation of all the GPU resources used by these frames.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::wgpu_trace::WgpuTraceCapture;
+/// # #[derive(Message)]
+/// # struct ReportBug;
+/// fn capture_on_bug_report(
+///     mut reports: MessageReader<ReportBug>,
+///     mut capture: ResMut<WgpuTraceCapture>,
+/// ) {
+///     if reports.read().next().is_some() {
+///         capture.capture_frames("wgpu_trace", 10);
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct WgpuTraceCapture {
+    requested: Option<(PathBuf, u32)>,
+    recording: Option<TraceRecording>,
+}
+
+#[derive(Debug)]
+struct TraceRecording {
+    directory: PathBuf,
+    remaining_frames: u32,
+}
+
+impl WgpuTraceCapture {
+    /// Records the next `frames` frames to a trace in `directory`, which is created if it doesn't
+    /// exist. This does nothing if a trace is already being recorded.
+    pub fn capture_frames(&mut self, directory: impl Into<PathBuf>, frames: u32) {
+        if self.recording.is_none() {
+            self.requested = Some((directory.into(), frames.max(1)));
+        }
+    }
+
+    /// Returns `true` if a trace is being recorded, or will be from the next frame.
+    pub fn is_capturing(&self) -> bool {
+        self.requested.is_some() || self.recording.is_some()
+    }
+}
+
+/// Starts, counts down and stops the trace recordings of [`WgpuTraceCapture`], while the renderer
+/// is ready.
+pub(crate) fn update_trace_capture(main_world: &mut World, render_world: &mut World) {
+    let Some(mut capture) = main_world.get_resource_mut::<WgpuTraceCapture>() else {
+        return;
+    };
+    let requested = capture.requested.take();
+    let finished = match &mut capture.recording {
+        Some(recording) => {
+            recording.remaining_frames = recording.remaining_frames.saturating_sub(1);
+            (recording.remaining_frames == 0).then(|| recording.directory.clone())
+        }
+        None => None,
+    };
+
+    if let Some((directory, frames)) = requested {
+        if !cfg!(feature = "wgpu_trace") {
+            bevy_log::warn!("wgpu traces can't be recorded without the `wgpu_trace` feature");
+            return;
+        }
+        if let Err(error) = fs::create_dir_all(&directory) {
+            bevy_log::error!("Failed to create the wgpu trace folder {directory:?}: {error}");
+            return;
+        }
+        let Some(mut settings) = main_world
+            .get_resource::<RenderRecreationSettings>()
+            .and_then(|recreation| recreation.settings.clone())
+        else {
+            bevy_log::error!(
+                "A wgpu trace can't be recorded, as the renderer was created manually"
+            );
+            return;
+        };
+        bevy_log::info!("Recording {frames} frames to a wgpu trace in {directory:?}");
+        settings.trace_path = Some(directory.clone());
+        if insert_future_resources(&RenderCreation::Automatic(settings), main_world) {
+            main_world.resource_mut::<WgpuTraceCapture>().recording = Some(TraceRecording {
+                directory,
+                remaining_frames: frames,
+            });
+            render_world.insert_resource(RenderState::Reinitializing);
+        }
+    } else if let Some(directory) = finished {
+        main_world.resource_mut::<WgpuTraceCapture>().recording = None;
+        bevy_log::info!("Finished recording the wgpu trace in {directory:?}");
+        // Go back to the settings the renderer was created with to stop recording.
+        let Some(settings) = main_world
+            .get_resource::<RenderRecreationSettings>()
+            .and_then(|recreation| recreation.settings.clone())
+        else {
+            return;
+        };
+        if insert_future_resources(&RenderCreation::Automatic(settings), main_world) {
+            render_world.insert_resource(RenderState::Reinitializing);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+#![doc = include_str!("../README.md")]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevy.org/assets/icon.png",
+    html_favicon_url = "https://bevy.org/assets/icon.png"
+)]
+#![no_std]
+
+//! All the random number generators of this crate are derived from the seed of the [`GlobalRng`]
+//! resource. Running the app again with the same seed produces the same random numbers on every
+//! platform, which is required for replays, lockstep networking, or procedural generation that
+//! must match between the server and the clients.
+//!
+//! Drawing from a single generator in parallel systems would make the numbers depend on the order
+//! the systems run in. Instead, each system gets its own stream of random numbers with the
+//! [`SystemRng`] system parameter, and entities can store their own stream in an [`RngStream`]
+//! component.
+//!
+//! ```
+//! use bevy_app::App;
+//! use bevy_ecs::prelude::*;
+//! use bevy_rng::{RngPlugin, SystemRng};
+//! use rand::RngExt;
+//!
+//! #[derive(Component)]
+//! struct Enemy {
+//!     health: u32,
+//! }
+//!
+//! fn spawn_enemies(mut commands: Commands, mut rng: SystemRng) {
+//!     for _ in 0..10 {
+//!         commands.spawn(Enemy {
+//!             health: rng.random_range(50..100),
+//!         });
+//!     }
+//! }
+//!
+//! App::new()
+//!     .add_plugins(RngPlugin::from_seed(42))
+//!     .add_systems(bevy_app::Startup, spawn_enemies)
+//!     .run();
+//! ```
+
+mod stream;
+
+pub use stream::*;
+
+/// The RNG prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{GlobalRng, RngPlugin, RngStream, SystemRng};
+}
+
+use bevy_app::{App, Plugin};
+
+/// Adds the [`GlobalRng`] resource, seeded with [`RngPlugin::seed`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RngPlugin {
+    /// The seed of the [`GlobalRng`], or [`None`] to pick a random seed.
+    ///
+    /// The seed picked at random can be read with [`GlobalRng::seed`], e.g. to record it in a
+    /// replay.
+    pub seed: Option<u64>,
+}
+
+impl RngPlugin {
+    /// Creates a plugin seeding the [`GlobalRng`] with `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let rng = match self.seed {
+            Some(seed) => GlobalRng::from_seed(seed),
+            None => GlobalRng::from_entropy(),
+        };
+        app.insert_resource(rng);
+    }
+}
@@ -0,0 +1,244 @@
+use bevy_ecs::{
+    component::Component,
+    resource::Resource,
+    system::{Local, SystemParam},
+    world::{FromWorld, World},
+};
+use bevy_platform::hash::RandomState;
+use core::{
+    hash::BuildHasher,
+    ops::{Deref, DerefMut},
+};
+use rand::{rngs::ChaCha8Rng, SeedableRng};
+
+/// The stream of the [`GlobalRng`] itself, which is never handed out by [`GlobalRng::fork`].
+const GLOBAL_STREAM: u64 = 0;
+
+/// The seeded random number generator of the app, from which all the other generators are derived.
+///
+/// This is inserted by the [`RngPlugin`](crate::RngPlugin). It can be used directly as a random
+/// number generator, but systems drawing from it can't run in parallel, so systems should rather
+/// use their own [`SystemRng`].
+///
+/// All the generators of this crate use the `ChaCha8` algorithm, which produces the same numbers on
+/// every platform.
+#[derive(Resource, Debug)]
+pub struct GlobalRng {
+    seed: u64,
+    rng: ChaCha8Rng,
+    next_stream: u64,
+}
+
+impl GlobalRng {
+    /// Creates a generator from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: stream_rng(seed, GLOBAL_STREAM),
+            next_stream: GLOBAL_STREAM + 1,
+        }
+    }
+
+    /// Creates a generator from a random seed, which is different every time the app runs.
+    pub fn from_entropy() -> Self {
+        Self::from_seed(RandomState::default().hash_one(GLOBAL_STREAM))
+    }
+
+    /// The seed of this generator, which reproduces all the random numbers of the app when it is
+    /// passed to [`RngPlugin::from_seed`](crate::RngPlugin::from_seed).
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns a new, independent stream of random numbers.
+    ///
+    /// The streams are numbered in the order they are forked, so forking them in a deterministic
+    /// order, e.g. when spawning entities in a single system, gives the same streams every time.
+    pub fn fork(&mut self) -> RngStream {
+        let stream = self.next_stream;
+        self.next_stream += 1;
+        self.stream(stream)
+    }
+
+    /// Returns the stream of random numbers identified by `name`, e.g. a subsystem or a level.
+    ///
+    /// Unlike [`GlobalRng::fork`], this stream doesn't depend on the streams forked before it, so
+    /// it stays the same when the app is changed.
+    pub fn named_stream(&self, name: &str) -> RngStream {
+        // FNV-1a, as the hashers of `core` are not guaranteed to be stable across platforms.
+        let hash = name.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        // Named streams use the upper half of the streams, away from the forked ones.
+        self.stream(hash | 1 << 63)
+    }
+
+    fn stream(&self, stream: u64) -> RngStream {
+        RngStream(stream_rng(self.seed, stream))
+    }
+}
+
+impl Default for GlobalRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+impl Deref for GlobalRng {
+    type Target = ChaCha8Rng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rng
+    }
+}
+
+impl DerefMut for GlobalRng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rng
+    }
+}
+
+fn stream_rng(seed: u64, stream: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(stream);
+    rng
+}
+
+/// A stream of random numbers derived from the [`GlobalRng`], with [`GlobalRng::fork`] or
+/// [`GlobalRng::named_stream`].
+///
+/// This can be stored in a component to give an entity its own stream, so that the numbers drawn
+/// for one entity don't depend on how many were drawn for the others.
+///
+/// Initializing a stream from the world, e.g. as a [`Local`], forks it from the [`GlobalRng`].
+#[derive(Component, Debug, PartialEq, Eq)]
+pub struct RngStream(ChaCha8Rng);
+
+impl FromWorld for RngStream {
+    fn from_world(world: &mut World) -> Self {
+        world.get_resource_or_init::<GlobalRng>().fork()
+    }
+}
+
+impl Deref for RngStream {
+    type Target = ChaCha8Rng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for RngStream {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// A system parameter giving each system its own [`RngStream`], so that systems drawing random
+/// numbers can run in parallel without making the numbers depend on the order they run in.
+///
+/// The stream of a system is forked from the [`GlobalRng`] when the system is initialized. As
+/// systems are initialized in the order they are added to the app, a system keeps the same stream
+/// as long as the systems added before it don't change. Use [`GlobalRng::named_stream`] for
+/// streams that don't depend on the other systems.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_rng::SystemRng;
+/// use rand::RngExt;
+///
+/// fn roll_dice(mut rng: SystemRng) {
+///     let roll = rng.random_range(1..=6);
+///     assert!((1..=6).contains(&roll));
+/// }
+/// # bevy_ecs::system::assert_is_system(roll_dice);
+/// ```
+#[derive(SystemParam)]
+pub struct SystemRng<'s> {
+    stream: Local<'s, RngStream>,
+}
+
+impl Deref for SystemRng<'_> {
+    type Target = RngStream;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stream
+    }
+}
+
+impl DerefMut for SystemRng<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        schedule::{IntoScheduleConfigs, Schedule},
+        system::ResMut,
+    };
+    use rand::Rng;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = GlobalRng::from_seed(7);
+        let mut b = GlobalRng::from_seed(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_eq!(a.fork().next_u64(), b.fork().next_u64());
+        assert_eq!(
+            a.named_stream("loot").next_u64(),
+            b.named_stream("loot").next_u64()
+        );
+        // Streams don't depend on the numbers drawn from the global generator.
+        b.next_u64();
+        assert_eq!(a.fork(), b.fork());
+        assert_ne!(a.fork(), a.fork());
+        assert_ne!(GlobalRng::from_seed(8).next_u64(), a.next_u64());
+    }
+
+    #[test]
+    fn streams_are_stable() {
+        // These must never change, as they would break the replays and saves of apps.
+        let mut rng = GlobalRng::from_seed(42);
+        let mut fork = rng.fork();
+        let mut named = rng.named_stream("loot");
+        assert_eq!(
+            [rng.next_u64(), fork.next_u64(), named.next_u64()],
+            [
+                12578764544318200737,
+                13222472167927179408,
+                920442310115510385
+            ]
+        );
+    }
+
+    #[test]
+    fn systems_get_distinct_streams() {
+        #[derive(Resource, Default)]
+        struct Rolls(u64, u64);
+
+        fn roll_a(mut rng: SystemRng, mut rolls: ResMut<Rolls>) {
+            rolls.0 = rng.next_u64();
+        }
+
+        fn roll_b(mut rng: SystemRng, mut rolls: ResMut<Rolls>) {
+            rolls.1 = rng.next_u64();
+        }
+
+        let roll = || {
+            let mut world = World::new();
+            world.insert_resource(GlobalRng::from_seed(1));
+            world.init_resource::<Rolls>();
+            let mut schedule = Schedule::default();
+            schedule.add_systems((roll_a, roll_b).chain());
+            schedule.run(&mut world);
+            let rolls = world.resource::<Rolls>();
+            (rolls.0, rolls.1)
+        };
+        let (a, b) = roll();
+        assert_ne!(a, b);
+        assert_eq!(roll(), (a, b));
+    }
+}
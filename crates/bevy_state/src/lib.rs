@@ -32,6 +32,9 @@
 //! Bevy also provides functionality for managing the lifetime of entities in the context of game states, using the [`state_scoped`] module.
 //! Specifically, the marker components [`DespawnOnEnter<S>`](crate::state_scoped::DespawnOnEnter) and [`DespawnOnExit<S>`](crate::state_scoped::DespawnOnExit) are provided for despawning entities on state transition.
 //! This, especially in combination with system scheduling, enables a flexible and expressive way to manage spawning and despawning entities.
+//! How these entities are cleaned up can be configured with [`StateScopedCleanup`](crate::state_scoped::StateScopedCleanup),
+//! for example to keep a menu around for quick re-entry. With the `bevy_asset` feature, asset handles can be scoped to states
+//! in the same way using `StateScopedAssets<S>`.
 
 #![cfg_attr(
     any(docsrs, docsrs_dep),
@@ -62,6 +65,9 @@ pub mod state;
 
 /// Provides tools for managing the lifetime of entities based on state transitions.
 pub mod state_scoped;
+#[cfg(feature = "bevy_asset")]
+/// Provides tools for managing the lifetime of asset handles based on state transitions.
+pub mod state_scoped_assets;
 #[cfg(feature = "bevy_app")]
 /// Provides [`App`](bevy_app::App) and [`SubApp`](bevy_app::SubApp) with methods for registering
 /// state-scoped events.
@@ -79,6 +85,10 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{app::AppExtStates, state_scoped_events::StateScopedMessagesAppExt};
 
+    #[cfg(feature = "bevy_asset")]
+    #[doc(hidden)]
+    pub use crate::state_scoped_assets::{StateScopedAssets, StateScopedAssetsAppExt};
+
     #[cfg(feature = "bevy_reflect")]
     #[doc(hidden)]
     pub use crate::reflect::{ReflectFreelyMutableState, ReflectState};
@@ -92,7 +102,7 @@ pub mod prelude {
             OnExit, OnTransition, PreviousState, State, StateSet, StateTransition,
             StateTransitionEvent, States, SubStates, TransitionSchedules,
         },
-        state_scoped::{DespawnOnEnter, DespawnOnExit},
+        state_scoped::{DespawnOnEnter, DespawnOnExit, KeptWarm, StateScopedCleanup},
     };
 }
 
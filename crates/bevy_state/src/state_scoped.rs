@@ -4,6 +4,7 @@ use bevy_ecs::{
     component::Component,
     entity::Entity,
     entity_disabling::Disabled,
+    hierarchy::Children,
     message::MessageReader,
     query::Allow,
     system::{Commands, Query},
@@ -16,6 +17,8 @@ use crate::state::{StateTransitionEvent, States};
 /// Entities marked with this component will be removed
 /// when the world's state of the matching type no longer matches the supplied value.
 ///
+/// How the entity is removed can be configured with the [`StateScopedCleanup`] component.
+///
 /// If you need to disable this behavior, add the attribute `#[states(scoped_entities = false)]` when deriving [`States`].
 ///
 /// ```
@@ -65,14 +68,93 @@ where
     }
 }
 
-/// Despawns entities marked with [`DespawnOnExit<S>`] when their state no
-/// longer matches the world state.
+/// Configures how an entity marked with [`DespawnOnExit<S>`] is cleaned up when its state is exited.
+///
+/// Entities without this component use [`StateScopedCleanup::Immediate`].
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Component)]
+/// struct MainMenuRoot;
+///
+/// fn spawn_main_menu(mut commands: Commands, warm_menus: Query<(), With<MainMenuRoot>>) {
+///     // The menu may still be around from a previous visit.
+///     if !warm_menus.is_empty() {
+///         return;
+///     }
+///     commands.spawn((
+///         DespawnOnExit(GameState::MainMenu),
+///         // Keep the menu around for one transition, so backing out of a level is instant.
+///         StateScopedCleanup::KeepWarm { transitions: 1 },
+///         MainMenuRoot,
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Clone, Default, Debug, PartialEq)
+)]
+pub enum StateScopedCleanup {
+    /// Cleans up as soon as the state is exited.
+    ///
+    /// For entities, this despawns the entity along with all of its descendants.
+    #[default]
+    Immediate,
+    /// Keeps the scoped data around after the state is exited, so that re-entering the state is cheap.
+    ///
+    /// For entities, the entity and its descendants are [`Disabled`] and marked with [`KeptWarm`].
+    /// If the state is entered again within the given number of further `transitions`,
+    /// they are re-enabled. Otherwise, they are despawned.
+    ///
+    /// Note that re-enabling removes [`Disabled`] from the entity and all of its descendants,
+    /// even if some of them were already disabled before the state was exited.
+    KeepWarm {
+        /// The number of state transitions after exiting the state during which the data is kept.
+        transitions: u32,
+    },
+}
+
+/// Marks an entity with [`StateScopedCleanup::KeepWarm`] that was disabled upon exiting its state,
+/// and will be re-enabled if that state is entered again in time.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Clone, Debug, PartialEq)
+)]
+pub struct KeptWarm {
+    /// The number of further state transitions after which the entity is despawned.
+    pub remaining_transitions: u32,
+}
+
+/// Cleans up entities marked with [`DespawnOnExit<S>`] when their state no
+/// longer matches the world state, according to their [`StateScopedCleanup`] policy.
 ///
 /// If the entity has already been despawned no warning will be emitted.
 pub fn despawn_entities_on_exit_state<S: States>(
     mut commands: Commands,
     mut transitions: MessageReader<StateTransitionEvent<S>>,
-    query: Query<(Entity, &DespawnOnExit<S>), Allow<Disabled>>,
+    query: Query<
+        (
+            Entity,
+            &DespawnOnExit<S>,
+            Option<&StateScopedCleanup>,
+            Option<&KeptWarm>,
+        ),
+        Allow<Disabled>,
+    >,
+    children: Query<&Children, Allow<Disabled>>,
 ) {
     // We use the latest event, because state machine internals generate at most 1
     // transition event (per type) each frame. No event means no change happened
@@ -83,12 +165,40 @@ pub fn despawn_entities_on_exit_state<S: States>(
     if transition.entered == transition.exited {
         return;
     }
-    let Some(exited) = &transition.exited else {
-        return;
-    };
-    for (entity, binding) in &query {
-        if binding.0 == *exited {
-            commands.entity(entity).try_despawn();
+    for (entity, binding, cleanup, kept_warm) in &query {
+        if let Some(kept_warm) = kept_warm {
+            if transition.entered.as_ref() == Some(&binding.0) {
+                commands.entity(entity).try_remove::<(Disabled, KeptWarm)>();
+                for descendant in children.iter_descendants(entity) {
+                    commands.entity(descendant).try_remove::<Disabled>();
+                }
+            } else if kept_warm.remaining_transitions <= 1 {
+                commands.entity(entity).try_despawn();
+            } else {
+                commands.entity(entity).try_insert(KeptWarm {
+                    remaining_transitions: kept_warm.remaining_transitions - 1,
+                });
+            }
+            continue;
+        }
+        if transition.exited.as_ref() != Some(&binding.0) {
+            continue;
+        }
+        match cleanup.copied().unwrap_or_default() {
+            StateScopedCleanup::KeepWarm { transitions } if transitions > 0 => {
+                commands.entity(entity).try_insert((
+                    Disabled,
+                    KeptWarm {
+                        remaining_transitions: transitions,
+                    },
+                ));
+                for descendant in children.iter_descendants(entity) {
+                    commands.entity(descendant).try_insert(Disabled);
+                }
+            }
+            _ => {
+                commands.entity(entity).try_despawn();
+            }
         }
     }
 }
@@ -166,6 +276,7 @@ mod tests {
     use super::*;
 
     use bevy_app::App;
+    use bevy_ecs::hierarchy::ChildOf;
 
     use crate::{
         app::{AppExtStates, StatesPlugin},
@@ -231,4 +342,49 @@ mod tests {
             .is_none());
         assert!(app.world().get_entity(entity).is_err());
     }
+
+    #[test]
+    fn keep_warm_on_exit() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, States)]
+        enum State {
+            Menu,
+            Game,
+            Pause,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin);
+        app.insert_state(State::Menu);
+        app.update();
+
+        let entity = app
+            .world_mut()
+            .spawn((
+                DespawnOnExit(State::Menu),
+                StateScopedCleanup::KeepWarm { transitions: 1 },
+            ))
+            .id();
+        let child = app.world_mut().spawn(ChildOf(entity)).id();
+
+        app.world_mut().commands().set_state(State::Game);
+        app.update();
+        assert!(app.world().entity(entity).contains::<Disabled>());
+        assert!(app.world().entity(entity).contains::<KeptWarm>());
+        assert!(app.world().entity(child).contains::<Disabled>());
+
+        // Re-entering the state re-enables the entity and its descendants.
+        app.world_mut().commands().set_state(State::Menu);
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Disabled>());
+        assert!(!app.world().entity(entity).contains::<KeptWarm>());
+        assert!(!app.world().entity(child).contains::<Disabled>());
+
+        // Not re-entering in time despawns the entity and its descendants.
+        app.world_mut().commands().set_state(State::Game);
+        app.update();
+        app.world_mut().commands().set_state(State::Pause);
+        app.update();
+        assert!(app.world().get_entity(entity).is_err());
+        assert!(app.world().get_entity(child).is_err());
+    }
 }
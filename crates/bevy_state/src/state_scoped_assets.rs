@@ -0,0 +1,269 @@
+use alloc::vec::Vec;
+
+use bevy_app::{App, SubApp};
+use bevy_asset::UntypedHandle;
+use bevy_ecs::{
+    message::MessageReader, resource::Resource, schedule::IntoScheduleConfigs, system::ResMut,
+};
+use bevy_platform::collections::HashMap;
+
+use crate::{
+    state::{StateTransition, StateTransitionEvent, StateTransitionSystems, States},
+    state_scoped::StateScopedCleanup,
+};
+
+/// Asset handles kept alive for as long as a state of type `S` is active.
+///
+/// Handles inserted for a state are dropped when that state is exited, which lets the
+/// asset be unloaded once nothing else holds on to it. This can be configured per state
+/// with [`StateScopedAssets::set_cleanup_policy`]: using [`StateScopedCleanup::KeepWarm`]
+/// keeps the handles around for a few more transitions, so quickly returning to the state
+/// (e.g. going from a level back to the main menu) doesn't reload everything.
+///
+/// This resource is added by [`StateScopedAssetsAppExt::init_state_scoped_assets`].
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+/// use bevy_asset::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// fn load_level(
+///     asset_server: Res<AssetServer>,
+///     mut scoped_assets: ResMut<StateScopedAssets<GameState>>,
+/// ) {
+///     let level = asset_server.load_folder("levels/1");
+///     // The level will be released once `GameState::InGame` is exited.
+///     scoped_assets.insert(GameState::InGame, level);
+/// }
+/// ```
+#[derive(Resource)]
+pub struct StateScopedAssets<S: States> {
+    scopes: HashMap<S, AssetScope>,
+}
+
+#[derive(Default)]
+struct AssetScope {
+    handles: Vec<UntypedHandle>,
+    cleanup: StateScopedCleanup,
+    /// The number of further transitions the handles are kept for, if the state was exited.
+    warm_transitions: Option<u32>,
+}
+
+impl<S: States> Default for StateScopedAssets<S> {
+    fn default() -> Self {
+        Self {
+            scopes: HashMap::default(),
+        }
+    }
+}
+
+impl<S: States> StateScopedAssets<S> {
+    /// Keeps `handle` alive until `state` is exited, according to its cleanup policy.
+    pub fn insert(&mut self, state: S, handle: impl Into<UntypedHandle>) {
+        self.scopes
+            .entry(state)
+            .or_default()
+            .handles
+            .push(handle.into());
+    }
+
+    /// Sets how the handles scoped to `state` are cleaned up when it is exited.
+    ///
+    /// The default is [`StateScopedCleanup::Immediate`].
+    pub fn set_cleanup_policy(&mut self, state: S, cleanup: StateScopedCleanup) {
+        self.scopes.entry(state).or_default().cleanup = cleanup;
+    }
+
+    /// Iterates over the handles currently scoped to `state`, including handles that are being kept warm.
+    pub fn handles(&self, state: &S) -> impl Iterator<Item = &UntypedHandle> {
+        self.scopes
+            .get(state)
+            .into_iter()
+            .flat_map(|scope| scope.handles.iter())
+    }
+
+    /// Returns `true` if `state` was exited, but its handles are still being kept warm.
+    pub fn is_warm(&self, state: &S) -> bool {
+        self.scopes
+            .get(state)
+            .is_some_and(|scope| scope.warm_transitions.is_some())
+    }
+
+    /// Immediately drops all handles scoped to `state`, regardless of its cleanup policy.
+    pub fn release(&mut self, state: &S) {
+        if let Some(scope) = self.scopes.get_mut(state) {
+            scope.handles.clear();
+            scope.warm_transitions = None;
+        }
+    }
+
+    fn on_transition(&mut self, exited: Option<&S>, entered: Option<&S>) {
+        for (state, scope) in &mut self.scopes {
+            if Some(state) == entered {
+                scope.warm_transitions = None;
+            } else if Some(state) == exited {
+                match scope.cleanup {
+                    StateScopedCleanup::KeepWarm { transitions } if transitions > 0 => {
+                        scope.warm_transitions = Some(transitions);
+                    }
+                    _ => scope.handles.clear(),
+                }
+            } else if let Some(remaining) = scope.warm_transitions {
+                if remaining <= 1 {
+                    scope.handles.clear();
+                    scope.warm_transitions = None;
+                } else {
+                    scope.warm_transitions = Some(remaining - 1);
+                }
+            }
+        }
+    }
+}
+
+/// Drops the handles in [`StateScopedAssets<S>`] according to each state's cleanup policy.
+pub fn release_state_scoped_assets<S: States>(
+    mut transitions: MessageReader<StateTransitionEvent<S>>,
+    mut scoped_assets: ResMut<StateScopedAssets<S>>,
+) {
+    // We use the latest event, because state machine internals generate at most 1
+    // transition event (per type) each frame.
+    let Some(transition) = transitions.read().last() else {
+        return;
+    };
+    if transition.entered == transition.exited {
+        return;
+    }
+    scoped_assets.on_transition(transition.exited.as_ref(), transition.entered.as_ref());
+}
+
+/// Extension trait for [`App`] adding methods for state-scoped asset handles.
+pub trait StateScopedAssetsAppExt {
+    /// Adds the [`StateScopedAssets<S>`] resource, and releases its handles on transitions of `S`.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// Handles are released in schedule [`StateTransition`] and system set
+    /// `StateTransitionSystems::ExitSchedules`, alongside [`DespawnOnExit`](crate::prelude::DespawnOnExit)
+    /// entity cleanup.
+    fn init_state_scoped_assets<S: States>(&mut self) -> &mut Self;
+}
+
+fn init_state_scoped_assets<S: States>(app: &mut SubApp) {
+    if app.world().contains_resource::<StateScopedAssets<S>>() {
+        return;
+    }
+    app.init_resource::<StateScopedAssets<S>>().add_systems(
+        StateTransition,
+        release_state_scoped_assets::<S>.in_set(StateTransitionSystems::ExitSchedules),
+    );
+}
+
+impl StateScopedAssetsAppExt for App {
+    fn init_state_scoped_assets<S: States>(&mut self) -> &mut Self {
+        init_state_scoped_assets::<S>(self.main_mut());
+        self
+    }
+}
+
+impl StateScopedAssetsAppExt for SubApp {
+    fn init_state_scoped_assets<S: States>(&mut self) -> &mut Self {
+        init_state_scoped_assets::<S>(self);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy_asset::{AssetPlugin, Assets, Handle};
+    use bevy_state_macros::States;
+
+    use crate::{
+        app::{AppExtStates, StatesPlugin},
+        prelude::CommandsStatesExt,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, States)]
+    enum TestState {
+        #[default]
+        Menu,
+        Game,
+        Pause,
+    }
+
+    fn setup() -> (App, Handle<()>) {
+        let mut app = App::new();
+        app.add_plugins((
+            bevy_app::TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            StatesPlugin,
+        ))
+        .init_state::<TestState>()
+        .init_state_scoped_assets::<TestState>();
+        app.update();
+
+        let handle = app.world_mut().resource_mut::<Assets<()>>().add(());
+        app.world_mut()
+            .resource_mut::<StateScopedAssets<TestState>>()
+            .insert(TestState::Menu, handle.clone());
+        (app, handle)
+    }
+
+    fn transition(app: &mut App, state: TestState) {
+        app.world_mut().commands().set_state(state);
+        app.update();
+    }
+
+    #[test]
+    fn release_on_exit() {
+        let (mut app, handle) = setup();
+        let id = handle.id();
+        drop(handle);
+
+        transition(&mut app, TestState::Game);
+        // Allow `Assets` to observe the dropped handle.
+        app.update();
+        assert!(!app.world().resource::<Assets<()>>().contains(id));
+    }
+
+    #[test]
+    fn keep_warm_until_expired() {
+        let (mut app, handle) = setup();
+        let id = handle.id();
+        drop(handle);
+        app.world_mut()
+            .resource_mut::<StateScopedAssets<TestState>>()
+            .set_cleanup_policy(
+                TestState::Menu,
+                StateScopedCleanup::KeepWarm { transitions: 1 },
+            );
+
+        transition(&mut app, TestState::Game);
+        assert!(app
+            .world()
+            .resource::<StateScopedAssets<TestState>>()
+            .is_warm(&TestState::Menu));
+
+        // Re-entering in time restores the scope.
+        transition(&mut app, TestState::Menu);
+        assert!(!app
+            .world()
+            .resource::<StateScopedAssets<TestState>>()
+            .is_warm(&TestState::Menu));
+        assert!(app.world().resource::<Assets<()>>().contains(id));
+
+        // Exiting, then transitioning elsewhere lets the warm handles expire.
+        transition(&mut app, TestState::Game);
+        transition(&mut app, TestState::Pause);
+        app.update();
+        assert!(!app.world().resource::<Assets<()>>().contains(id));
+    }
+}
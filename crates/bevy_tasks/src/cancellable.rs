@@ -0,0 +1,271 @@
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use atomic_waker::AtomicWaker;
+use bevy_platform::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, PoisonError,
+};
+use futures_lite::FutureExt;
+
+use crate::Task;
+
+/// The state shared by a [`CancellableTask`] and the [`TaskContext`] of its future.
+struct TaskState<P> {
+    cancelled: AtomicBool,
+    waker: AtomicWaker,
+    progress: Mutex<Option<P>>,
+}
+
+impl<P> TaskState<P> {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        self.waker.wake();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+/// Resolves once the task is cancelled.
+struct Cancelled<P>(Arc<TaskState<P>>);
+
+impl<P> Future for Cancelled<P> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.waker.register(cx.waker());
+        if self.0.is_cancelled() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Lets the future of a [`CancellableTask`] report its progress, and check if it was cancelled.
+///
+/// See [`TaskPool::spawn_cancellable`](crate::TaskPool::spawn_cancellable).
+pub struct TaskContext<P = ()> {
+    state: Arc<TaskState<P>>,
+}
+
+impl<P> TaskContext<P> {
+    /// Returns `true` if the task was cancelled.
+    ///
+    /// A cancelled task stops running at its next `.await`. Long computations that don't await
+    /// should check this regularly, and return early once the task is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Reports the progress of the task, which replaces the previously reported progress.
+    pub fn report_progress(&self, progress: P) {
+        *self
+            .state
+            .progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(progress);
+    }
+}
+
+impl<P> Clone for TaskContext<P> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<P> fmt::Debug for TaskContext<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskContext")
+            .field("cancelled", &self.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Task`] that can be cancelled, and that reports its progress.
+///
+/// This is spawned with [`TaskPool::spawn_cancellable`](crate::TaskPool::spawn_cancellable).
+/// Like a [`Task`], it is a future that resolves to the output of the task, or to [`None`] if the
+/// task was cancelled before it completed.
+///
+/// Dropping a [`CancellableTask`] cancels it. Storing it in a component thus cancels the task
+/// when the entity is despawned, e.g. when leaving the state the entity was spawned in.
+///
+/// ```
+/// use bevy_tasks::{block_on, AsyncComputeTaskPool, TaskPool};
+///
+/// let pool = AsyncComputeTaskPool::get_or_init(TaskPool::new);
+/// let task = pool.spawn_cancellable(|context| async move {
+///     let mut sum = 0;
+///     for i in 1..=100 {
+///         if context.is_cancelled() {
+///             break;
+///         }
+///         sum += i;
+///         context.report_progress(i as f32 / 100.0);
+///     }
+///     sum
+/// });
+///
+/// // In a system, poll the task with `block_on(poll_once(&mut task))` instead.
+/// assert_eq!(block_on(task), Some(5050));
+/// ```
+#[must_use = "Tasks are canceled when dropped."]
+pub struct CancellableTask<T, P = ()> {
+    task: Task<Option<T>>,
+    state: Arc<TaskState<P>>,
+}
+
+/// The state of a [`CancellableTask`], shared with the [`TaskContext`] of its future.
+pub(crate) struct CancellableState<P>(Arc<TaskState<P>>);
+
+impl<P> CancellableState<P> {
+    /// Creates the state of a new task, and the context to pass to its future.
+    pub(crate) fn new() -> (Self, TaskContext<P>) {
+        let state = Arc::new(TaskState {
+            cancelled: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+            progress: Mutex::new(None),
+        });
+        let context = TaskContext {
+            state: state.clone(),
+        };
+        (Self(state), context)
+    }
+
+    /// Wraps `future` so that it stops running when the task is cancelled.
+    pub(crate) fn wrap<T, Fut: Future<Output = T>>(
+        &self,
+        future: Fut,
+    ) -> impl Future<Output = Option<T>> + use<T, P, Fut> {
+        let cancelled = Cancelled(self.0.clone());
+        async move {
+            // The task output wins if it completes at the same time as it is cancelled.
+            async { Some(future.await) }
+                .or(async {
+                    cancelled.await;
+                    None
+                })
+                .await
+        }
+    }
+}
+
+impl<T, P> CancellableTask<T, P> {
+    pub(crate) fn new(task: Task<Option<T>>, state: CancellableState<P>) -> Self {
+        Self {
+            task,
+            state: state.0,
+        }
+    }
+
+    /// Cancels the task. It stops running at its next `.await`, and resolves to [`None`].
+    ///
+    /// The future of the task can check if it was cancelled with [`TaskContext::is_cancelled`].
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+
+    /// Returns `true` if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_cancelled()
+    }
+
+    /// Returns `true` if the task is finished, either because it completed or because it was
+    /// cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Returns the last progress reported with [`TaskContext::report_progress`], if any.
+    pub fn progress(&self) -> Option<P>
+    where
+        P: Clone,
+    {
+        self.state
+            .progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl<T, P> Future for CancellableTask<T, P> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.task).poll(cx)
+    }
+}
+
+impl<T, P> Drop for CancellableTask<T, P> {
+    fn drop(&mut self) {
+        // Dropping the task already stops polling its future, this also lets a computation that
+        // checks `TaskContext::is_cancelled` stop early.
+        self.state.cancel();
+    }
+}
+
+impl<T, P> fmt::Debug for CancellableTask<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellableTask")
+            .field("task", &self.task)
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{block_on, TaskPool};
+    use futures_lite::future::pending;
+
+    #[test]
+    fn cancel_pending_task() {
+        let pool = TaskPool::new();
+        let task = pool.spawn_cancellable(|context| async move {
+            context.report_progress(0.5);
+            pending::<()>().await;
+        });
+        while task.progress().is_none() {
+            pool.with_local_executor(|executor| executor.try_tick());
+        }
+        assert_eq!(task.progress(), Some(0.5));
+        assert!(!task.is_cancelled());
+
+        task.cancel();
+        pool.with_local_executor(|executor| while executor.try_tick() {});
+        assert_eq!(block_on(task), None);
+    }
+
+    #[test]
+    fn completed_task_resolves_to_output() {
+        let pool = TaskPool::new();
+        let task = pool.spawn_cancellable(|_: TaskContext| async { 42 });
+        pool.with_local_executor(|executor| while executor.try_tick() {});
+        assert_eq!(block_on(task), Some(42));
+    }
+
+    #[test]
+    fn dropping_task_cancels_it() {
+        let pool = TaskPool::new();
+        let mut context = None;
+        let task = pool.spawn_cancellable(|task_context: TaskContext| {
+            context = Some(task_context.clone());
+            pending::<()>()
+        });
+        let context = context.unwrap();
+        assert!(!context.is_cancelled());
+        drop(task);
+        assert!(context.is_cancelled());
+    }
+}
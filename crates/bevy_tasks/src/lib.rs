@@ -63,6 +63,7 @@ use alloc::boxed::Box;
 pub type BoxedFuture<'a, T> = core::pin::Pin<Box<dyn ConditionalSendFuture<Output = T> + 'a>>;
 
 // Modules
+mod cancellable;
 mod executor;
 pub mod futures;
 mod iter;
@@ -77,6 +78,7 @@ cfg::async_executor! {
 }
 
 // Exports
+pub use cancellable::{CancellableTask, TaskContext};
 pub use iter::ParallelIterator;
 pub use slice::{ParallelSlice, ParallelSliceMut};
 pub use task::Task;
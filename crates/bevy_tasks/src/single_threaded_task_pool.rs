@@ -3,7 +3,11 @@ use bevy_platform::sync::Arc;
 use core::{cell::{RefCell, Cell}, future::Future, marker::PhantomData, mem};
 
 use crate::executor::LocalExecutor;
-use crate::{block_on, Task};
+use crate::{
+    block_on,
+    cancellable::{CancellableState, CancellableTask, TaskContext},
+    Task,
+};
 
 crate::cfg::std! {
     if {
@@ -215,6 +219,26 @@ impl TaskPool {
         }}
     }
 
+    /// Spawns a static future that can be cancelled and can report its progress. The future is
+    /// created by `f` from a [`TaskContext`], through which it reports its progress and checks
+    /// whether it was cancelled.
+    ///
+    /// The returned [`CancellableTask`] resolves to the output of the future, or to [`None`] if
+    /// it was cancelled with [`CancellableTask::cancel`] or dropped before completing.
+    pub fn spawn_cancellable<T, P, Fut>(
+        &self,
+        f: impl FnOnce(TaskContext<P>) -> Fut,
+    ) -> CancellableTask<T, P>
+    where
+        Fut: Future<Output = T> + 'static + MaybeSend + MaybeSync,
+        T: 'static + MaybeSend + MaybeSync,
+        P: 'static + MaybeSend + MaybeSync,
+    {
+        let (state, context) = CancellableState::new();
+        let task = self.spawn(state.wrap(f(context)));
+        CancellableTask::new(task, state)
+    }
+
     /// Spawns a static future on the JS event loop. This is exactly the same as [`TaskPool::spawn`].
     pub fn spawn_local<T>(
         &self,
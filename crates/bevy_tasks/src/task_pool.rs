@@ -12,6 +12,7 @@ use futures_lite::FutureExt;
 
 use crate::{
     block_on,
+    cancellable::{CancellableState, CancellableTask, TaskContext},
     thread_executor::{ThreadExecutor, ThreadExecutorTicker},
     Task,
 };
@@ -560,7 +561,27 @@ impl TaskPool {
     where
         T: Send + 'static,
     {
-        Task::new(self.executor.spawn(future))
+        Task::new(self.executor.spawn(in_current_span(future)))
+    }
+
+    /// Spawns a static future that can be cancelled and can report its progress. The future is
+    /// created by `f` from a [`TaskContext`], through which it reports its progress and checks
+    /// whether it was cancelled.
+    ///
+    /// The returned [`CancellableTask`] resolves to the output of the future, or to [`None`] if
+    /// it was cancelled with [`CancellableTask::cancel`] or dropped before completing.
+    pub fn spawn_cancellable<T, P, Fut>(
+        &self,
+        f: impl FnOnce(TaskContext<P>) -> Fut,
+    ) -> CancellableTask<T, P>
+    where
+        Fut: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+        P: Send + 'static,
+    {
+        let (state, context) = CancellableState::new();
+        let task = self.spawn(state.wrap(f(context)));
+        CancellableTask::new(task, state)
     }
 
     /// Spawns a static future on the thread-local async executor for the
@@ -578,7 +599,7 @@ impl TaskPool {
     where
         T: 'static,
     {
-        Task::new(TaskPool::LOCAL_EXECUTOR.with(|executor| executor.spawn(future)))
+        Task::new(TaskPool::LOCAL_EXECUTOR.with(|executor| executor.spawn(in_current_span(future))))
     }
 
     /// Runs a function with the local executor. Typically used to tick
@@ -646,7 +667,7 @@ impl<'scope, 'env, T: Send + 'scope> Scope<'scope, 'env, T> {
     pub fn spawn<Fut: Future<Output = T> + 'scope + Send>(&self, f: Fut) {
         let task = self
             .executor
-            .spawn(AssertUnwindSafe(f).catch_unwind())
+            .spawn(AssertUnwindSafe(in_current_span(f)).catch_unwind())
             .fallible();
         // ConcurrentQueue only errors when closed or full, but we never
         // close and use an unbounded queue, so it is safe to unwrap
@@ -662,7 +683,7 @@ impl<'scope, 'env, T: Send + 'scope> Scope<'scope, 'env, T> {
     pub fn spawn_on_scope<Fut: Future<Output = T> + 'scope + Send>(&self, f: Fut) {
         let task = self
             .scope_executor
-            .spawn(AssertUnwindSafe(f).catch_unwind())
+            .spawn(AssertUnwindSafe(in_current_span(f)).catch_unwind())
             .fallible();
         // ConcurrentQueue only errors when closed or full, but we never
         // close and use an unbounded queue, so it is safe to unwrap
@@ -679,7 +700,7 @@ impl<'scope, 'env, T: Send + 'scope> Scope<'scope, 'env, T> {
     pub fn spawn_on_external<Fut: Future<Output = T> + 'scope + Send>(&self, f: Fut) {
         let task = self
             .external_executor
-            .spawn(AssertUnwindSafe(f).catch_unwind())
+            .spawn(AssertUnwindSafe(in_current_span(f)).catch_unwind())
             .fallible();
         // ConcurrentQueue only errors when closed or full, but we never
         // close and use an unbounded queue, so it is safe to unwrap
@@ -700,12 +721,46 @@ where
     }
 }
 
+/// Runs `future` inside the tracing span that is current when it is spawned, so the work moved to
+/// another thread of the pool still shows up under the span that spawned it.
+#[inline]
+fn in_current_span<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    #[cfg(feature = "trace")]
+    let future = tracing::Instrument::in_current_span(future);
+    future
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use core::sync::atomic::{AtomicBool, AtomicI32, Ordering};
     use std::sync::Barrier;
 
+    #[cfg(feature = "trace")]
+    #[test]
+    fn tasks_run_in_the_span_they_are_spawned_in() {
+        // The spans must be recorded on the threads of the pool too.
+        let _ = tracing::subscriber::set_global_default(tracing_subscriber::registry());
+        let pool = TaskPool::new();
+
+        let span = tracing::info_span!("spawner");
+        let entered = span.enter();
+        let task = pool.spawn(async { tracing::Span::current().id() });
+        assert_eq!(block_on(task), span.id());
+
+        let outputs = pool.scope(|scope| {
+            scope.spawn(async { tracing::Span::current().id() });
+            scope.spawn_on_scope(async { tracing::Span::current().id() });
+        });
+        assert_eq!(outputs, [span.id(), span.id()]);
+
+        // Tasks spawned outside of a span don't run in the span of the thread that polls them.
+        drop(entered);
+        let task = pool.spawn(async { tracing::Span::current().id() });
+        let _entered = span.enter();
+        assert_eq!(block_on(task), None);
+    }
+
     #[test]
     fn test_spawn() {
         let pool = TaskPool::new();
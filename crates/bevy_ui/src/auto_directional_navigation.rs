@@ -194,6 +194,62 @@ impl<'w, 's> AutoDirectionalNavigator<'w, 's> {
         }
     }
 
+    /// Wraps the input focus around to the opposite side of the UI, moving it to the node furthest
+    /// away in the opposite of the given direction, e.g. from the last to the first item of a
+    /// column when navigating [`CompassOctant::South`].
+    ///
+    /// This is meant to be called when [`navigate`](Self::navigate) returns
+    /// [`DirectionalNavigationError::NoNeighborInDirection`]. Manual edges are not considered.
+    pub fn wrap(&mut self, direction: CompassOctant) -> Result<Entity, DirectionalNavigationError> {
+        let Some(current_focus) = self.input_focus() else {
+            return Err(DirectionalNavigationError::NoFocus);
+        };
+        let no_neighbor = DirectionalNavigationError::NoNeighborInDirection {
+            current_focus,
+            direction,
+        };
+        let Some((target_camera, mut origin)) =
+            self.entity_to_camera_and_focusable_area(current_focus)
+        else {
+            return Err(no_neighbor);
+        };
+        let candidates = self.get_navigable_nodes(target_camera);
+        let Some((min, max)) = candidates
+            .iter()
+            .map(|area| {
+                (
+                    area.position - area.size / 2.0,
+                    area.position + area.size / 2.0,
+                )
+            })
+            .reduce(|(min, max), (area_min, area_max)| (min.min(area_min), max.max(area_max)))
+        else {
+            return Err(no_neighbor);
+        };
+
+        // Navigate from beyond the opposite edge of the navigable nodes, without a distance limit.
+        let span = max - min + origin.size;
+        let offset = match direction {
+            CompassOctant::North => Vec2::new(0.0, span.y),
+            CompassOctant::NorthEast => Vec2::new(-span.x, span.y),
+            CompassOctant::East => Vec2::new(-span.x, 0.0),
+            CompassOctant::SouthEast => Vec2::new(-span.x, -span.y),
+            CompassOctant::South => Vec2::new(0.0, -span.y),
+            CompassOctant::SouthWest => Vec2::new(span.x, -span.y),
+            CompassOctant::West => Vec2::new(span.x, 0.0),
+            CompassOctant::NorthWest => Vec2::new(span.x, span.y),
+        };
+        origin.position += offset;
+        let config = AutoNavigationConfig {
+            max_search_distance: None,
+            ..(*self.config).clone()
+        };
+        let new_focus =
+            find_best_candidate(&origin, direction, &candidates, &config).ok_or(no_neighbor)?;
+        self.manual_directional_navigation.focus.set(new_focus);
+        Ok(new_focus)
+    }
+
     /// Returns a vec of [`FocusableArea`] representing nodes that are eligible to be automatically navigated to.
     /// The camera of any navigable nodes will equal the desired `target_camera`.
     fn get_navigable_nodes(&self, target_camera: Entity) -> Vec<FocusableArea> {
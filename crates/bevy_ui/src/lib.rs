@@ -13,6 +13,8 @@
 pub mod auto_directional_navigation;
 pub mod interaction_states;
 pub mod measurement;
+pub mod navigation_input;
+pub mod on_screen_keyboard;
 pub mod update;
 pub mod widget;
 
@@ -33,6 +35,7 @@ mod focus;
 mod geometry;
 mod layout;
 mod stack;
+mod ui_anchor;
 mod ui_node;
 
 pub use focus::*;
@@ -41,6 +44,7 @@ pub use gradients::*;
 pub use interaction_states::{Checkable, Checked, InteractionDisabled, Pressed};
 pub use layout::*;
 pub use measurement::*;
+pub use ui_anchor::*;
 pub use ui_node::*;
 pub use ui_transform::*;
 
@@ -188,6 +192,11 @@ impl Plugin for UiPlugin {
             PostUpdate,
             (
                 propagate_ui_target_cameras.in_set(UiSystems::Prepare),
+                widget::update_input_icons.in_set(UiSystems::Prepare),
+                on_screen_keyboard::update_on_screen_keyboard
+                    .run_if(resource_exists_and_changed::<bevy_input_focus::InputFocus>)
+                    .in_set(UiSystems::Prepare),
+                update_ui_anchors.in_set(UiSystems::Content),
                 ui_layout_system_config,
                 ui_stack_system
                     .in_set(UiSystems::Stack)
@@ -218,6 +227,11 @@ impl Plugin for UiPlugin {
 
         build_text_interop(app);
     }
+
+    #[cfg(feature = "input_icon_atlas")]
+    fn finish(&self, app: &mut App) {
+        widget::insert_bundled_input_icon_atlas(app);
+    }
 }
 
 fn build_text_interop(app: &mut App) {
@@ -0,0 +1,443 @@
+//! Moves the input focus between UI nodes with the arrow keys, the D-pad and the left stick of
+//! gamepads, making menus navigable without a mouse.
+//!
+//! Add the [`DirectionalNavigationInputPlugin`] to move the focus between the nodes with the
+//! [`AutoDirectionalNavigation`](crate::auto_directional_navigation::AutoDirectionalNavigation)
+//! component, using the [`AutoDirectionalNavigator`]. Holding a direction repeats the move, and
+//! [`NavigationWrap`] chooses what happens at the edges of the UI.
+//!
+//! The focused node is activated with Enter or Space on keyboards, and with the
+//! [`South`](bevy_input::gamepad::GamepadButton::South) button of gamepads, e.g. the A button of
+//! Xbox controllers. This is done by the widgets of `bevy_ui_widgets`, from the focused input
+//! dispatched by the [`InputDispatchPlugin`](bevy_input_focus::InputDispatchPlugin). Sliders
+//! aren't adjustable with gamepads yet, as the D-pad and the left stick navigate away from them.
+//!
+//! Navigating sets [`InputFocusVisible`], so that a focus indicator is only shown when using
+//! keyboards or gamepads. Add the [`FocusOutline`] component to draw an [`Outline`] around the
+//! focused node.
+
+use crate::{auto_directional_navigation::AutoDirectionalNavigator, Outline, UiSystems};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::Gamepad,
+    keyboard::{KeyCode, KeyboardInput},
+    ButtonState, InputSystems,
+};
+use bevy_input_focus::{
+    directional_navigation::{DirectionalNavigationError, DirectionalNavigationPlugin},
+    FocusedInput, InputFocus, InputFocusVisible,
+};
+use bevy_math::{CompassOctant, Dir2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::{Real, Time};
+use bevy_window::PrimaryWindow;
+use core::time::Duration;
+
+/// Adds directional navigation of the UI with keyboards and gamepads.
+///
+/// This also adds the [`DirectionalNavigationPlugin`] if it hasn't been added yet. The
+/// [`InputDispatchPlugin`](bevy_input_focus::InputDispatchPlugin) must be added for keyboard
+/// navigation, and the initial [`InputFocus`] must be set for navigation to start.
+#[derive(Default)]
+pub struct DirectionalNavigationInputPlugin;
+
+impl Plugin for DirectionalNavigationInputPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<DirectionalNavigationPlugin>() {
+            app.add_plugins(DirectionalNavigationPlugin);
+        }
+        // Keyboard input is dispatched to the primary window, which may be spawned before or after
+        // this plugin is added.
+        let world = app.world_mut();
+        let windows: Vec<Entity> = world
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .iter(world)
+            .collect();
+        for window in windows {
+            world.entity_mut(window).observe(keyboard_navigation);
+        }
+        app.init_resource::<DirectionalNavigationInput>()
+            .init_resource::<InputFocus>()
+            .init_resource::<InputFocusVisible>()
+            .add_observer(setup_keyboard_navigation)
+            .add_systems(PreUpdate, gamepad_navigation_system.after(InputSystems))
+            .add_systems(PostUpdate, focus_outline_system.in_set(UiSystems::Prepare));
+    }
+}
+
+/// Configures the [`DirectionalNavigationInputPlugin`].
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq, Clone)]
+pub struct DirectionalNavigationInput {
+    /// Whether the arrow keys navigate.
+    ///
+    /// Arrow key presses handled by the focused widget, e.g. a slider, don't navigate. Held keys
+    /// repeat at the rate of the operating system.
+    pub keyboard: bool,
+    /// Whether the D-pad and the left stick of gamepads navigate.
+    pub gamepad: bool,
+    /// How far the left stick must be pushed to navigate, from `0.0` to `1.0`.
+    pub stick_threshold: f32,
+    /// How long a direction must be held on a gamepad before the move repeats.
+    pub repeat_delay: Duration,
+    /// The time between the repeated moves while a direction is held on a gamepad.
+    pub repeat_interval: Duration,
+    /// What happens when there is no node to navigate to.
+    pub wrap: NavigationWrap,
+}
+
+impl Default for DirectionalNavigationInput {
+    fn default() -> Self {
+        Self {
+            keyboard: true,
+            gamepad: true,
+            stick_threshold: 0.5,
+            repeat_delay: Duration::from_millis(500),
+            repeat_interval: Duration::from_millis(125),
+            wrap: NavigationWrap::default(),
+        }
+    }
+}
+
+/// What happens when navigating past the edge of the UI.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash, Clone)]
+pub enum NavigationWrap {
+    /// The focus stays on the node at the edge.
+    #[default]
+    Stop,
+    /// The focus wraps around to the node at the opposite edge, see
+    /// [`AutoDirectionalNavigator::wrap`].
+    Wrap,
+}
+
+/// The [`Outline`] drawn around this node while it has the [`InputFocus`] and
+/// [`InputFocusVisible`] is set.
+///
+/// This takes over the [`Outline`] component of the node, hiding it with [`Color::NONE`] while
+/// the node isn't focused.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+pub struct FocusOutline(pub Outline);
+
+/// The direction held on gamepads, and when its move repeats.
+#[derive(Default)]
+struct HeldDirection {
+    direction: Option<CompassOctant>,
+    next_move: Duration,
+}
+
+fn setup_keyboard_navigation(add: On<Add, PrimaryWindow>, mut commands: Commands) {
+    commands.entity(add.entity).observe(keyboard_navigation);
+}
+
+/// Navigates with the arrow keys that weren't handled by the focused entity.
+fn keyboard_navigation(
+    mut event: On<FocusedInput<KeyboardInput>>,
+    settings: Res<DirectionalNavigationInput>,
+    mut navigator: AutoDirectionalNavigator,
+    mut visible: ResMut<InputFocusVisible>,
+) {
+    let key_event = &event.input;
+    if !settings.keyboard || key_event.state != ButtonState::Pressed {
+        return;
+    }
+    let direction = match key_event.key_code {
+        KeyCode::ArrowUp => CompassOctant::North,
+        KeyCode::ArrowDown => CompassOctant::South,
+        KeyCode::ArrowLeft => CompassOctant::West,
+        KeyCode::ArrowRight => CompassOctant::East,
+        _ => return,
+    };
+    event.propagate(false);
+    navigate(&mut navigator, direction, settings.wrap, &mut visible);
+}
+
+/// Navigates with the D-pad and the left stick of the gamepads, repeating held directions.
+fn gamepad_navigation_system(
+    settings: Res<DirectionalNavigationInput>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time<Real>>,
+    mut held: Local<HeldDirection>,
+    mut navigator: AutoDirectionalNavigator,
+    mut visible: ResMut<InputFocusVisible>,
+) {
+    let direction = settings
+        .gamepad
+        .then(|| {
+            gamepads.iter().find_map(|gamepad| {
+                let dpad = gamepad.dpad();
+                let input = if dpad != Vec2::ZERO {
+                    dpad
+                } else if gamepad.left_stick().length() >= settings.stick_threshold {
+                    gamepad.left_stick()
+                } else {
+                    return None;
+                };
+                Dir2::new(input).ok().map(CompassOctant::from)
+            })
+        })
+        .flatten();
+
+    let now = time.elapsed();
+    match direction {
+        None => {
+            held.direction = None;
+            return;
+        }
+        Some(direction) if held.direction != Some(direction) => {
+            held.direction = Some(direction);
+            held.next_move = now + settings.repeat_delay;
+        }
+        Some(_) if now >= held.next_move => {
+            held.next_move = now + settings.repeat_interval;
+        }
+        Some(_) => return,
+    }
+    if let Some(direction) = held.direction {
+        navigate(&mut navigator, direction, settings.wrap, &mut visible);
+    }
+}
+
+fn navigate(
+    navigator: &mut AutoDirectionalNavigator,
+    direction: CompassOctant,
+    wrap: NavigationWrap,
+    visible: &mut InputFocusVisible,
+) {
+    let result = match navigator.navigate(direction) {
+        Err(DirectionalNavigationError::NoNeighborInDirection { .. })
+            if wrap == NavigationWrap::Wrap =>
+        {
+            navigator.wrap(direction)
+        }
+        result => result,
+    };
+    // Show where the focus is, even at the edge of the UI.
+    if !matches!(result, Err(DirectionalNavigationError::NoFocus)) && !visible.0 {
+        visible.0 = true;
+    }
+}
+
+/// Shows the [`FocusOutline`] of the focused node, and hides the others.
+fn focus_outline_system(
+    mut commands: Commands,
+    focus: Res<InputFocus>,
+    visible: Res<InputFocusVisible>,
+    mut query: Query<(Entity, &FocusOutline, Option<&mut Outline>)>,
+) {
+    for (entity, focus_outline, outline) in &mut query {
+        let shown = visible.0 && focus.0 == Some(entity);
+        let target = Outline {
+            color: if shown {
+                focus_outline.0.color
+            } else {
+                Color::NONE
+            },
+            ..focus_outline.0
+        };
+        match outline {
+            Some(mut outline) => {
+                outline.set_if_neq(target);
+            }
+            None => {
+                commands.entity(entity).insert(target);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auto_directional_navigation::AutoDirectionalNavigation, ComputedNode,
+        ComputedUiTargetCamera, UiGlobalTransform,
+    };
+    use bevy_camera::visibility::InheritedVisibility;
+    use bevy_input::{gamepad::GamepadButton, keyboard::Key, InputPlugin};
+    use bevy_input_focus::InputDispatchPlugin;
+    use bevy_window::Window;
+
+    /// Spawns a primary window and a column of three nodes, focusing the top one.
+    fn setup_app(wrap: NavigationWrap) -> (App, [Entity; 3]) {
+        let mut app = App::new();
+        app.add_plugins((
+            InputPlugin,
+            InputDispatchPlugin,
+            DirectionalNavigationInputPlugin,
+        ))
+        .insert_resource(DirectionalNavigationInput {
+            wrap,
+            ..Default::default()
+        })
+        .init_resource::<Time<Real>>();
+
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        let nodes = spawn_nodes(&mut app);
+        (app, nodes)
+    }
+
+    /// Spawns a column of three nodes, focusing the top one.
+    fn spawn_nodes(app: &mut App) -> [Entity; 3] {
+        let world = app.world_mut();
+        let camera = world.spawn_empty().id();
+        let nodes = [0.0, 100.0, 200.0].map(|y| {
+            world
+                .spawn((
+                    AutoDirectionalNavigation::default(),
+                    ComputedUiTargetCamera { camera },
+                    ComputedNode {
+                        size: Vec2::splat(50.0),
+                        ..ComputedNode::DEFAULT
+                    },
+                    UiGlobalTransform::from_xy(0.0, y),
+                    InheritedVisibility::VISIBLE,
+                ))
+                .id()
+        });
+        world.insert_resource(InputFocus(Some(nodes[0])));
+
+        advance(app, Duration::ZERO);
+        nodes
+    }
+
+    fn advance(app: &mut App, duration: Duration) {
+        app.world_mut()
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(duration);
+        app.update();
+    }
+
+    fn focus(app: &App) -> Option<Entity> {
+        app.world().resource::<InputFocus>().0
+    }
+
+    fn arrow_down(repeat: bool) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::ArrowDown,
+            logical_key: Key::ArrowDown,
+            state: ButtonState::Pressed,
+            text: None,
+            repeat,
+            window: Entity::PLACEHOLDER,
+            timestamp: None,
+        }
+    }
+
+    fn set_dpad(app: &mut App, gamepad: Entity, button: GamepadButton, value: f32) {
+        app.world_mut()
+            .get_mut::<Gamepad>(gamepad)
+            .unwrap()
+            .analog_mut()
+            .set(button, value);
+    }
+
+    #[test]
+    fn keyboard_navigation_with_key_repeat() {
+        let (mut app, nodes) = setup_app(NavigationWrap::Stop);
+        assert!(!app.world().resource::<InputFocusVisible>().0);
+
+        app.world_mut().write_message(arrow_down(false));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[1]));
+        assert!(app.world().resource::<InputFocusVisible>().0);
+
+        // Keys held down repeat at the rate of the operating system.
+        app.world_mut().write_message(arrow_down(true));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[2]));
+
+        // The focus stops at the edge.
+        app.world_mut().write_message(arrow_down(true));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[2]));
+    }
+
+    #[test]
+    fn keyboard_navigation_on_windows_spawned_later() {
+        let (mut app, nodes) = setup_app(NavigationWrap::Stop);
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut().despawn(window);
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+
+        app.world_mut().write_message(arrow_down(false));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[1]));
+    }
+
+    #[test]
+    fn keyboard_navigation_on_windows_spawned_before_the_plugin() {
+        let mut app = App::new();
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.add_plugins((
+            InputPlugin,
+            InputDispatchPlugin,
+            DirectionalNavigationInputPlugin,
+        ))
+        .init_resource::<Time<Real>>();
+        let nodes = spawn_nodes(&mut app);
+
+        // Each arrow key press moves the focus once.
+        app.world_mut().write_message(arrow_down(false));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[1]));
+    }
+
+    #[test]
+    fn navigation_wraps_around() {
+        let (mut app, nodes) = setup_app(NavigationWrap::Wrap);
+
+        app.world_mut().write_message(arrow_down(false));
+        app.world_mut().write_message(arrow_down(false));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[2]));
+
+        app.world_mut().write_message(arrow_down(false));
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[0]));
+
+        app.world_mut().write_message(KeyboardInput {
+            key_code: KeyCode::ArrowUp,
+            logical_key: Key::ArrowUp,
+            ..arrow_down(false)
+        });
+        app.update();
+        assert_eq!(focus(&app), Some(nodes[2]));
+    }
+
+    #[test]
+    fn gamepad_navigation_repeats_held_directions() {
+        let (mut app, nodes) = setup_app(NavigationWrap::Wrap);
+        let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+
+        set_dpad(&mut app, gamepad, GamepadButton::DPadDown, 1.0);
+        advance(&mut app, Duration::ZERO);
+        assert_eq!(focus(&app), Some(nodes[1]));
+        assert!(app.world().resource::<InputFocusVisible>().0);
+
+        // The move repeats after the repeat delay, then at the repeat interval.
+        advance(&mut app, Duration::from_millis(400));
+        assert_eq!(focus(&app), Some(nodes[1]));
+        advance(&mut app, Duration::from_millis(100));
+        assert_eq!(focus(&app), Some(nodes[2]));
+        advance(&mut app, Duration::from_millis(100));
+        assert_eq!(focus(&app), Some(nodes[2]));
+        advance(&mut app, Duration::from_millis(25));
+        assert_eq!(focus(&app), Some(nodes[0]));
+
+        // Releasing the D-pad stops the moves, and pressing it again moves right away.
+        set_dpad(&mut app, gamepad, GamepadButton::DPadDown, 0.0);
+        advance(&mut app, Duration::from_secs(1));
+        assert_eq!(focus(&app), Some(nodes[0]));
+        set_dpad(&mut app, gamepad, GamepadButton::DPadUp, 1.0);
+        advance(&mut app, Duration::from_millis(10));
+        assert_eq!(focus(&app), Some(nodes[2]));
+    }
+}
@@ -0,0 +1,103 @@
+//! Shows the on-screen keyboard of the system while a UI node that takes text input has the
+//! [`InputFocus`], on devices without a physical keyboard.
+//!
+//! Add the [`OnScreenKeyboardTarget`] component to the text fields. The keyboard can cover the
+//! focused field: read the [`OnScreenKeyboardChanged`](bevy_window::OnScreenKeyboardChanged)
+//! messages to scroll it above the part of the window covered by the keyboard.
+
+use crate::ComputedUiTargetCamera;
+use bevy_camera::{NormalizedRenderTarget, RenderTarget};
+use bevy_ecs::prelude::*;
+use bevy_input_focus::InputFocus;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_window::{PrimaryWindow, Window};
+
+/// Marks a UI node that takes text input, so that the on-screen keyboard is shown while it has the
+/// [`InputFocus`].
+///
+/// See [`Window::on_screen_keyboard`] for the supported platforms.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component, Debug, Clone, PartialEq, Default)]
+pub struct OnScreenKeyboardTarget;
+
+/// Shows the on-screen keyboard of the window of the [`OnScreenKeyboardTarget`] with the
+/// [`InputFocus`], and hides it in the other windows.
+///
+/// The keyboard is requested again on each change of the focus, so that focusing a text field
+/// shows it again after the user dismissed it.
+pub fn update_on_screen_keyboard(
+    input_focus: Res<InputFocus>,
+    targets: Query<&ComputedUiTargetCamera, With<OnScreenKeyboardTarget>>,
+    cameras: Query<&RenderTarget>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut windows: Query<(Entity, &mut Window)>,
+) {
+    let focused_window = input_focus
+        .get()
+        .and_then(|focus| targets.get(focus).ok()?.get())
+        .and_then(|camera| cameras.get(camera).ok())
+        .and_then(
+            |render_target| match render_target.normalize(primary_window.single().ok()) {
+                Some(NormalizedRenderTarget::Window(window)) => Some(window.entity()),
+                _ => None,
+            },
+        );
+    for (entity, mut window) in &mut windows {
+        if Some(entity) == focused_window {
+            window.on_screen_keyboard = true;
+        } else {
+            window
+                .map_unchanged(|window| &mut window.on_screen_keyboard)
+                .set_if_neq(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+
+    #[test]
+    fn keyboard_is_shown_in_the_window_of_the_focused_target() {
+        let mut app = App::new();
+        app.init_resource::<InputFocus>().add_systems(
+            Update,
+            update_on_screen_keyboard.run_if(resource_changed::<InputFocus>),
+        );
+        let primary = app
+            .world_mut()
+            .spawn((Window::default(), PrimaryWindow))
+            .id();
+        let other = app.world_mut().spawn(Window::default()).id();
+        let camera = app.world_mut().spawn(RenderTarget::default()).id();
+        let field = app
+            .world_mut()
+            .spawn((OnScreenKeyboardTarget, ComputedUiTargetCamera { camera }))
+            .id();
+        let keyboard_shown = |app: &App, window: Entity| {
+            app.world()
+                .get::<Window>(window)
+                .unwrap()
+                .on_screen_keyboard
+        };
+
+        app.world_mut().resource_mut::<InputFocus>().set(field);
+        app.update();
+        assert!(keyboard_shown(&app, primary));
+        assert!(!keyboard_shown(&app, other));
+
+        // The user dismissed the keyboard, and focuses the field again.
+        app.world_mut()
+            .get_mut::<Window>(primary)
+            .unwrap()
+            .on_screen_keyboard = false;
+        app.world_mut().resource_mut::<InputFocus>().set(field);
+        app.update();
+        assert!(keyboard_shown(&app, primary));
+
+        app.world_mut().resource_mut::<InputFocus>().clear();
+        app.update();
+        assert!(!keyboard_shown(&app, primary));
+    }
+}
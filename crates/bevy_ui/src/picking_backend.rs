@@ -23,12 +23,13 @@
 
 use crate::{clip_check_recursive, prelude::*, ui_transform::UiGlobalTransform, UiStack};
 use bevy_app::prelude::*;
-use bevy_camera::{visibility::InheritedVisibility, Camera, RenderTarget};
+use bevy_camera::{visibility::InheritedVisibility, Camera, CoordinateSpaces, RenderTarget};
 use bevy_ecs::{prelude::*, query::QueryData};
 use bevy_math::Vec2;
 use bevy_platform::collections::HashMap;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_text::{ComputedTextBlock, TextLayoutInfo};
+use bevy_transform::components::GlobalTransform;
 use bevy_window::PrimaryWindow;
 
 use bevy_picking::backend::prelude::*;
@@ -100,7 +101,13 @@ pub struct NodeQuery {
 /// we need for determining picking.
 pub fn ui_picking(
     pointers: Query<(&PointerId, &PointerLocation)>,
-    camera_query: Query<(Entity, &Camera, &RenderTarget, Has<UiPickingCamera>)>,
+    camera_query: Query<(
+        Entity,
+        &Camera,
+        &GlobalTransform,
+        &RenderTarget,
+        Has<UiPickingCamera>,
+    )>,
     primary_window: Query<Entity, With<PrimaryWindow>>,
     settings: Res<UiPickingSettings>,
     ui_stack: Res<UiStack>,
@@ -120,29 +127,27 @@ pub fn ui_picking(
     {
         // This pointer is associated with a render target, which could be used by multiple
         // cameras. We want to ensure we return all cameras with a matching target.
-        for (entity, camera, _, _) in
+        for (entity, camera, camera_transform, _, _) in
             camera_query
                 .iter()
-                .filter(|(_, _, render_target, cam_can_pick)| {
+                .filter(|(_, _, _, render_target, cam_can_pick)| {
                     (!settings.require_markers || *cam_can_pick)
                         && render_target
                             .normalize(primary_window.single().ok())
                             .is_some_and(|target| target == pointer_location.target)
                 })
         {
-            let mut pointer_pos =
-                pointer_location.position * camera.target_scaling_factor().unwrap_or(1.);
-            if let Some(viewport) = camera.physical_viewport_rect() {
-                if !viewport.as_rect().contains(pointer_pos) {
-                    // The pointer is outside the viewport, skip it
-                    continue;
-                }
-                pointer_pos -= viewport.min.as_vec2();
+            let Some(spaces) = CoordinateSpaces::new(camera, camera_transform) else {
+                continue;
+            };
+            if !spaces.contains(pointer_location.position) {
+                // The pointer is outside the viewport, skip it
+                continue;
             }
             pointer_pos_by_camera
                 .entry(entity)
                 .or_default()
-                .insert(pointer_id, pointer_pos);
+                .insert(pointer_id, spaces.logical_to_ui(pointer_location.position));
         }
     }
 
@@ -281,7 +286,7 @@ pub fn ui_picking(
 
         let order = camera_query
             .get(*camera)
-            .map(|(_, cam, _, _)| cam.order)
+            .map(|(_, cam, _, _, _)| cam.order)
             .unwrap_or_default() as f32
             + 0.5; // bevy ui can run on any camera, it's a special case
 
@@ -0,0 +1,362 @@
+use crate::{ComputedNode, ComputedUiTargetCamera, Node, PositionType, UiScale, Val};
+use bevy_camera::{visibility::Visibility, Camera, CoordinateSpaces};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{Vec2, Vec3, Vec4Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::helper::TransformHelper;
+
+/// Keeps a UI node positioned over a world-space entity, such as a nameplate over a character or a
+/// marker over an objective.
+///
+/// Each frame, the [`GlobalTransform`](bevy_transform::components::GlobalTransform) of the
+/// [`target`](Self::target) is projected with the UI's target camera, and the [`Node`] is moved so
+/// that its [`pivot`](Self::pivot) lies on the projected position. The node must be a root node,
+/// as its [`Node::left`] and [`Node::top`] are set relative to the camera's viewport.
+///
+/// The anchor controls the [`Visibility`] of the node: the node is hidden when the target is
+/// behind the camera or doesn't exist, unless [`edge_margin`](Self::edge_margin) is set, in which
+/// case the node is pinned to the closest edge of the viewport instead. The outcome is available in
+/// [`ComputedUiAnchor`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec3;
+/// # use bevy_ui::{widget::Text, UiAnchor};
+/// fn spawn_nameplate(mut commands: Commands, character: Entity) {
+///     commands.spawn((
+///         UiAnchor::new(character).with_offset(Vec3::Y * 2.0),
+///         Text::new("Alice"),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[require(Node, ComputedUiAnchor)]
+pub struct UiAnchor {
+    /// The world-space entity to follow.
+    pub target: Entity,
+    /// An offset added to the position of the target, in world space.
+    pub offset: Vec3,
+    /// The point of the node placed over the target, relative to the size of the node:
+    /// `(0.0, 0.0)` is the top-left corner and `(1.0, 1.0)` the bottom-right corner.
+    pub pivot: Vec2,
+    /// If set, the node is kept inside the viewport, at least this many logical pixels away from
+    /// its edges, including when the target is behind the camera.
+    pub edge_margin: Option<f32>,
+}
+
+impl Default for UiAnchor {
+    fn default() -> Self {
+        Self::new(Entity::PLACEHOLDER)
+    }
+}
+
+impl UiAnchor {
+    /// Creates an anchor centering the node over `target`.
+    pub const fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vec3::ZERO,
+            pivot: Vec2::splat(0.5),
+            edge_margin: None,
+        }
+    }
+
+    /// Returns this anchor with the given world-space [`offset`](Self::offset).
+    pub const fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns this anchor with the given [`pivot`](Self::pivot).
+    pub const fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    /// Returns this anchor keeping the node inside the viewport, `margin` logical pixels away from
+    /// its edges.
+    pub const fn clamped_to_edges(mut self, margin: f32) -> Self {
+        self.edge_margin = Some(margin);
+        self
+    }
+}
+
+/// The outcome of positioning a [`UiAnchor`] node, updated each frame in
+/// [`UiSystems::Content`](crate::UiSystems::Content).
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub struct ComputedUiAnchor {
+    visible: bool,
+    clamped: bool,
+    behind_camera: bool,
+}
+
+impl ComputedUiAnchor {
+    /// Returns `true` if the node is displayed.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Returns `true` if the node was moved to stay inside the viewport, which means the target
+    /// isn't under the node. Markers can use this to display an arrow pointing to the target.
+    pub fn is_clamped(&self) -> bool {
+        self.clamped
+    }
+
+    /// Returns `true` if the target is behind the camera.
+    pub fn is_behind_camera(&self) -> bool {
+        self.behind_camera
+    }
+}
+
+/// Positions [`UiAnchor`] nodes over their target entities.
+pub fn update_ui_anchors(
+    ui_scale: Res<UiScale>,
+    transform_helper: TransformHelper,
+    cameras: Query<&Camera>,
+    mut anchors: Query<(
+        &UiAnchor,
+        &mut Node,
+        &ComputedNode,
+        &ComputedUiTargetCamera,
+        &mut ComputedUiAnchor,
+        &mut Visibility,
+    )>,
+) {
+    for (anchor, mut node, computed_node, target_camera, mut computed_anchor, mut visibility) in
+        &mut anchors
+    {
+        let position = target_camera.get().and_then(|camera_entity| {
+            let camera = cameras.get(camera_entity).ok()?;
+            let camera_transform = transform_helper
+                .compute_global_transform(camera_entity)
+                .ok()?;
+            let target_transform = transform_helper
+                .compute_global_transform(anchor.target)
+                .ok()?;
+            let spaces = CoordinateSpaces::new(camera, &camera_transform)?;
+
+            let world_position = target_transform.translation() + anchor.offset;
+            let view_position = camera_transform
+                .affine()
+                .inverse()
+                .transform_point3(world_position);
+            let clip_position = camera.clip_from_view() * view_position.extend(1.0);
+            // Dividing by the absolute value of `w` keeps the direction of targets behind the
+            // camera, so that they can be pinned to the correct edge.
+            let behind_camera = clip_position.w <= 0.0;
+            let ndc = clip_position.xy() / clip_position.w.abs().max(f32::EPSILON);
+            let ndc = if behind_camera {
+                let extent = ndc.abs().max_element();
+                if extent > 0.0 {
+                    ndc / extent
+                } else {
+                    Vec2::NEG_Y
+                }
+            } else {
+                ndc
+            };
+
+            let viewport_position = spaces.logical_to_viewport(spaces.ndc_to_logical(ndc));
+            let viewport_size = spaces.logical_viewport_rect().size();
+            Some((viewport_position, viewport_size, behind_camera))
+        });
+
+        let Some((viewport_position, viewport_size, behind_camera)) = position else {
+            computed_anchor.set_if_neq(ComputedUiAnchor::default());
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        // `Val::Px` values are scaled by `UiScale`.
+        let size = computed_node.size() * computed_node.inverse_scale_factor();
+        let unclamped = viewport_position / ui_scale.0 - anchor.pivot * size;
+        let top_left = match anchor.edge_margin {
+            Some(margin) => {
+                let min = Vec2::splat(margin);
+                let max = (viewport_size / ui_scale.0 - size - margin).max(min);
+                unclamped.clamp(min, max)
+            }
+            None => unclamped,
+        };
+        let visible = !behind_camera || anchor.edge_margin.is_some();
+
+        computed_anchor.set_if_neq(ComputedUiAnchor {
+            visible,
+            clamped: behind_camera || top_left != unclamped,
+            behind_camera,
+        });
+        visibility.set_if_neq(if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+
+        let left = Val::Px(top_left.x);
+        let top = Val::Px(top_left.y);
+        if node.position_type != PositionType::Absolute || node.left != left || node.top != top {
+            node.position_type = PositionType::Absolute;
+            node.left = left;
+            node.top = top;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{update_ui_anchors, ComputedUiAnchor, UiAnchor};
+    use crate::{ComputedNode, ComputedUiTargetCamera, Node, UiScale, Val};
+    use bevy_camera::{visibility::Visibility, Camera, ComputedCameraValues, RenderTargetInfo};
+    use bevy_ecs::{entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_math::{Mat4, UVec2, Vec2, Vec3};
+    use bevy_transform::components::Transform;
+    use core::f32::consts::FRAC_PI_2;
+
+    /// Spawns a camera at the origin, looking at `-Z`, rendering to an 800x600 target.
+    fn spawn_camera(world: &mut World) -> Entity {
+        world.init_resource::<UiScale>();
+        world
+            .spawn((
+                Camera {
+                    computed: ComputedCameraValues {
+                        clip_from_view: Mat4::perspective_infinite_reverse_rh(
+                            FRAC_PI_2,
+                            800. / 600.,
+                            0.1,
+                        ),
+                        target_info: Some(RenderTargetInfo {
+                            physical_size: UVec2::new(800, 600),
+                            scale_factor: 1.,
+                        }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Transform::default(),
+            ))
+            .id()
+    }
+
+    /// Spawns a 100x20 node anchored to a target at `target_position`, and positions it.
+    fn anchor_node(
+        world: &mut World,
+        camera: Entity,
+        target_position: Vec3,
+        anchor: impl FnOnce(Entity) -> UiAnchor,
+    ) -> Entity {
+        let target = world
+            .spawn(Transform::from_translation(target_position))
+            .id();
+        let node = world
+            .spawn((
+                anchor(target),
+                ComputedNode {
+                    size: Vec2::new(100., 20.),
+                    ..ComputedNode::DEFAULT
+                },
+                ComputedUiTargetCamera { camera },
+            ))
+            .id();
+        world.run_system_once(update_ui_anchors).unwrap();
+        node
+    }
+
+    fn top_left(world: &World, node: Entity) -> (Val, Val) {
+        let node = world.get::<Node>(node).unwrap();
+        (node.left, node.top)
+    }
+
+    #[test]
+    fn node_is_centered_over_target() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world);
+        let node = anchor_node(&mut world, camera, Vec3::new(0., 0., -10.), UiAnchor::new);
+
+        assert_eq!(top_left(&world, node), (Val::Px(350.), Val::Px(290.)));
+        let computed = world.get::<ComputedUiAnchor>(node).unwrap();
+        assert!(computed.is_visible());
+        assert!(!computed.is_clamped());
+        assert!(!computed.is_behind_camera());
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Inherited));
+    }
+
+    #[test]
+    fn node_is_hidden_behind_camera() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world);
+        let node = anchor_node(&mut world, camera, Vec3::new(0., 0., 10.), UiAnchor::new);
+
+        let computed = world.get::<ComputedUiAnchor>(node).unwrap();
+        assert!(!computed.is_visible());
+        assert!(computed.is_behind_camera());
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Hidden));
+    }
+
+    #[test]
+    fn node_is_hidden_without_target() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world);
+        let node = anchor_node(&mut world, camera, Vec3::new(0., 0., -10.), UiAnchor::new);
+        world.get_mut::<UiAnchor>(node).unwrap().target = Entity::PLACEHOLDER;
+        world.run_system_once(update_ui_anchors).unwrap();
+
+        assert_eq!(
+            world.get::<ComputedUiAnchor>(node),
+            Some(&ComputedUiAnchor::default())
+        );
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Hidden));
+    }
+
+    #[test]
+    fn clamped_node_stays_inside_viewport() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world);
+
+        // A target far to the right is pinned to the right edge.
+        let node = anchor_node(&mut world, camera, Vec3::new(100., 0., -10.), |target| {
+            UiAnchor::new(target).clamped_to_edges(10.)
+        });
+        assert_eq!(top_left(&world, node), (Val::Px(690.), Val::Px(290.)));
+        let computed = world.get::<ComputedUiAnchor>(node).unwrap();
+        assert!(computed.is_visible());
+        assert!(computed.is_clamped());
+        assert!(!computed.is_behind_camera());
+
+        // A target right behind the camera is pinned to the bottom edge, and stays visible.
+        let node = anchor_node(&mut world, camera, Vec3::new(0., 0., 10.), |target| {
+            UiAnchor::new(target).clamped_to_edges(10.)
+        });
+        assert_eq!(top_left(&world, node), (Val::Px(350.), Val::Px(570.)));
+        let computed = world.get::<ComputedUiAnchor>(node).unwrap();
+        assert!(computed.is_visible());
+        assert!(computed.is_clamped());
+        assert!(computed.is_behind_camera());
+        assert_eq!(world.get::<Visibility>(node), Some(&Visibility::Inherited));
+    }
+
+    #[test]
+    fn offset_and_pivot_move_the_node() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world);
+        // With a 90° vertical field of view, a point 10 units away and 5 units up is halfway to
+        // the top of the viewport.
+        let node = anchor_node(&mut world, camera, Vec3::new(0., 0., -10.), |target| {
+            UiAnchor::new(target)
+                .with_offset(Vec3::Y * 5.)
+                .with_pivot(Vec2::new(0.5, 1.))
+        });
+
+        let (Val::Px(left), Val::Px(top)) = top_left(&world, node) else {
+            panic!("the anchored node should be positioned in pixels");
+        };
+        assert!((left - 350.).abs() < 1e-3);
+        assert!((top - 130.).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{widget::ImageNode, Node};
+use bevy_asset::Handle;
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    query::Ref,
+    reflect::{ReflectComponent, ReflectResource},
+    resource::Resource,
+    system::{Commands, Query, Res},
+};
+use bevy_image::{Image, TextureAtlas, TextureAtlasLayout};
+use bevy_input::glyph::{ActiveInputDevice, InputGlyph, InputPrompt};
+use bevy_math::UVec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+#[cfg(feature = "input_icon_atlas")]
+use {
+    bevy_app::App,
+    bevy_asset::{Assets, RenderAssetUsages},
+    bevy_ecs::change_detection::Mut,
+    bevy_image::{CompressedImageFormats, ImageSampler, ImageType},
+};
+
+use super::Text;
+
+/// A UI node displaying the glyph of an [`InputPrompt`] for the [`ActiveInputDevice`].
+///
+/// Gamepad buttons are displayed as an [`ImageNode`] using the [`InputIconAtlas`] resource if it
+/// exists, which is the atlas bundled with Bevy with the `input_icon_atlas` feature. Otherwise, and
+/// for keyboard keys, the node displays the [label](InputGlyph::label) of the glyph as [`Text`].
+/// The icon is updated when the player switches to another kind of device.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{gamepad::GamepadButton, glyph::InputPrompt, keyboard::KeyCode};
+/// # use bevy_ui::widget::InputIcon;
+/// fn spawn_back_prompt(mut commands: Commands) {
+///     commands.spawn(InputIcon(InputPrompt::new(KeyCode::Escape, GamepadButton::East)));
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[require(Node)]
+pub struct InputIcon(pub InputPrompt);
+
+/// The texture atlas holding the gamepad glyphs displayed by [`InputIcon`]s.
+///
+/// The atlas layout must match [`InputGlyph::gamepad_atlas_index`]: use
+/// [`InputIconAtlas::grid_layout`] to build it for an image where all glyphs have the same size.
+///
+/// With the `input_icon_atlas` feature, the atlas bundled with Bevy is inserted by the
+/// [`UiPlugin`](crate::UiPlugin), unless the resource already exists. Insert your own atlas to
+/// replace it with glyphs matching the art style of your game.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Debug, Clone)]
+pub struct InputIconAtlas {
+    /// The image holding the glyphs.
+    pub image: Handle<Image>,
+    /// The layout of the glyphs in [`Self::image`].
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl InputIconAtlas {
+    /// The size of the glyphs of the atlas bundled with the `input_icon_atlas` feature, in pixels.
+    pub const BUNDLED_TILE_SIZE: UVec2 = UVec2::splat(64);
+
+    /// Adds the gamepad glyph atlas bundled with Bevy to `images` and `layouts`.
+    ///
+    /// Its glyphs are [`Self::BUNDLED_TILE_SIZE`] pixels, drawn as light labels and symbols on dark
+    /// buttons.
+    #[cfg(feature = "input_icon_atlas")]
+    pub fn bundled(images: &mut Assets<Image>, layouts: &mut Assets<TextureAtlasLayout>) -> Self {
+        let image = Image::from_buffer(
+            include_bytes!("input_icons.png"),
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+            ImageSampler::Default,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .expect("Failed to decode the bundled input icon atlas");
+        Self {
+            image: images.add(image),
+            layout: layouts.add(Self::grid_layout(Self::BUNDLED_TILE_SIZE, None)),
+        }
+    }
+
+    /// Returns the layout of a gamepad glyph atlas made of glyphs of `tile_size` pixels,
+    /// separated by `padding` pixels.
+    pub fn grid_layout(tile_size: UVec2, padding: Option<UVec2>) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(
+            tile_size,
+            InputGlyph::GAMEPAD_ATLAS_COLUMNS,
+            InputGlyph::GAMEPAD_ATLAS_ROWS,
+            padding,
+            None,
+        )
+    }
+}
+
+/// Inserts the [bundled](InputIconAtlas::bundled) [`InputIconAtlas`], unless one already exists
+/// or the image assets aren't set up.
+#[cfg(feature = "input_icon_atlas")]
+pub(crate) fn insert_bundled_input_icon_atlas(app: &mut App) {
+    let world = app.world_mut();
+    if world.contains_resource::<InputIconAtlas>()
+        || !world.contains_resource::<Assets<Image>>()
+        || !world.contains_resource::<Assets<TextureAtlasLayout>>()
+    {
+        return;
+    }
+    let atlas = world.resource_scope(|world, mut images: Mut<Assets<Image>>| {
+        let mut layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+        InputIconAtlas::bundled(&mut images, &mut layouts)
+    });
+    world.insert_resource(atlas);
+}
+
+/// Updates the content of [`InputIcon`] nodes when their prompt, the [`ActiveInputDevice`] or the
+/// [`InputIconAtlas`] changes.
+pub fn update_input_icons(
+    mut commands: Commands,
+    active_device: Res<ActiveInputDevice>,
+    atlas: Option<Res<InputIconAtlas>>,
+    icons: Query<(Entity, Ref<InputIcon>)>,
+) {
+    let atlas_changed = atlas.as_ref().is_some_and(DetectChanges::is_changed);
+    let force_update = active_device.is_changed() || atlas_changed;
+
+    for (entity, icon) in &icons {
+        if !force_update && !icon.is_changed() {
+            continue;
+        }
+
+        let glyph = icon.0.glyph(active_device.glyph_style);
+        let atlas_image = atlas.as_ref().and_then(|atlas| {
+            let index = glyph?.gamepad_atlas_index()?;
+            Some(ImageNode::from_atlas_image(
+                atlas.image.clone(),
+                TextureAtlas {
+                    layout: atlas.layout.clone(),
+                    index,
+                },
+            ))
+        });
+
+        let mut entity = commands.entity(entity);
+        match atlas_image {
+            Some(image) => {
+                entity.remove::<Text>().insert(image);
+            }
+            None => {
+                let label = glyph.map(|glyph| glyph.label()).unwrap_or_default();
+                entity
+                    .remove::<ImageNode>()
+                    .insert(Text::new(label.into_owned()));
+            }
+        }
+    }
+}
@@ -2,12 +2,14 @@
 
 mod button;
 mod image;
+mod input_icon;
 mod label;
 mod text;
 mod viewport;
 
 pub use button::*;
 pub use image::*;
+pub use input_icon::*;
 pub use label::*;
 pub use text::*;
 pub use viewport::*;
@@ -30,7 +30,9 @@ use bevy_render::{
     view::*,
     Extract, ExtractSchedule, Render, RenderSystems,
 };
-use bevy_render::{sync_world::MainEntity, RenderStartup};
+use bevy_render::{
+    reset_render_resource, sync_world::MainEntity, RenderDeviceReset, RenderStartup,
+};
 use bevy_shader::Shader;
 use bevy_sprite::BorderRect;
 use bevy_ui::{
@@ -54,6 +56,7 @@ impl Plugin for GradientPlugin {
                 .init_resource::<GradientMeta>()
                 .init_resource::<SpecializedRenderPipelines<GradientPipeline>>()
                 .add_systems(RenderStartup, init_gradient_pipeline)
+                .add_systems(RenderDeviceReset, reset_render_resource::<GradientMeta>)
                 .add_systems(
                     ExtractSchedule,
                     extract_gradients
@@ -138,7 +141,8 @@ pub fn compute_gradient_line_length(angle: f32, size: Vec2) -> f32 {
 pub struct UiGradientPipelineKey {
     anti_alias: bool,
     color_space: InterpolationColorSpace,
-    pub hdr: bool,
+    /// The format of the texture the UI is drawn into.
+    pub texture_format: TextureFormat,
 }
 
 impl SpecializedRenderPipeline for GradientPipeline {
@@ -207,11 +211,7 @@ impl SpecializedRenderPipeline for GradientPipeline {
                 shader: self.shader.clone(),
                 shader_defs,
                 targets: vec![Some(ColorTargetState {
-                    format: if key.hdr {
-                        ViewTarget::TEXTURE_FORMAT_HDR
-                    } else {
-                        TextureFormat::bevy_default()
-                    },
+                    format: key.texture_format,
                     blend: Some(BlendState::ALPHA_BLENDING),
                     write_mask: ColorWrites::ALL,
                 })],
@@ -585,7 +585,7 @@ pub fn queue_gradient(
     mut pipelines: ResMut<SpecializedRenderPipelines<GradientPipeline>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     mut render_views: Query<(&UiCameraView, Option<&UiAntiAlias>), With<ExtractedView>>,
-    camera_views: Query<&ExtractedView>,
+    camera_views: Query<(&ExtractedView, &UiViewTextureFormat)>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
 ) {
@@ -597,7 +597,7 @@ pub fn queue_gradient(
             continue;
         };
 
-        let Ok(view) = camera_views.get(default_camera_view.0) else {
+        let Ok((view, texture_format)) = camera_views.get(default_camera_view.0) else {
             continue;
         };
 
@@ -612,7 +612,7 @@ pub fn queue_gradient(
             UiGradientPipelineKey {
                 anti_alias: matches!(ui_anti_alias, None | Some(UiAntiAlias::On)),
                 color_space: gradient.color_space,
-                hdr: view.hdr,
+                texture_format: texture_format.0,
             },
         );
 
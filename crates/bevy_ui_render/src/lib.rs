@@ -20,7 +20,7 @@ pub mod ui_texture_slice_pipeline;
 mod debug_overlay;
 
 use bevy_camera::visibility::InheritedVisibility;
-use bevy_camera::{Camera, Camera2d, Camera3d, Hdr, RenderTarget};
+use bevy_camera::{Camera, Camera2d, Camera3d, Hdr, NormalizedRenderTarget, RenderTarget};
 use bevy_reflect::prelude::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_shader::load_shader_library;
@@ -49,10 +49,13 @@ use bevy_render::{
     },
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
+    reset_render_resource,
     sync_world::{MainEntity, RenderEntity, TemporaryRenderEntity},
     texture::GpuImage,
-    view::{ExtractedView, RetainedViewEntity, ViewUniforms},
-    Extract, ExtractSchedule, Render, RenderApp, RenderStartup, RenderSystems,
+    view::{
+        prepare_view_targets, ExtractedView, Msaa, RetainedViewEntity, ViewTarget, ViewUniforms,
+    },
+    Extract, ExtractSchedule, Render, RenderApp, RenderDeviceReset, RenderStartup, RenderSystems,
 };
 use bevy_sprite::BorderRect;
 #[cfg(feature = "bevy_ui_debug")]
@@ -67,9 +70,12 @@ use bevy_text::{
     TextColor, TextLayoutInfo, Underline, UnderlineColor,
 };
 use bevy_transform::components::GlobalTransform;
+use bevy_utils::once;
+use bevy_window::{PrimaryWindow, Window};
 use box_shadow::BoxShadowPlugin;
 use bytemuck::{Pod, Zeroable};
 use core::ops::Range;
+use tracing::warn;
 
 pub use pipeline::*;
 pub use render_pass::*;
@@ -84,6 +90,7 @@ pub mod prelude {
 
     pub use crate::{
         ui_material::*, ui_material_pipeline::UiMaterialPlugin, BoxShadowSamples, UiAntiAlias,
+        UiRenderPath,
     };
 }
 
@@ -186,6 +193,52 @@ impl Default for BoxShadowSamples {
     }
 }
 
+/// Controls where the UI and the 2D content of a window or a camera is drawn.
+///
+/// By default, UI is drawn into the main texture of the camera, after post-processing and before
+/// the main texture is upscaled to the output texture (such as the window's swap chain). On
+/// tile-based and low-end GPUs, drawing UI this way costs an extra load and store of the whole main
+/// texture, which [`UiRenderPath::OutputTexture`] avoids, along with the MSAA resolve of 2D cameras.
+///
+/// Insert it on a [`Window`] to select the path of every camera rendering to the window, or on a
+/// camera to override the path of its window.
+///
+/// The cost of both paths can be compared with
+/// [`RenderDiagnosticsPlugin`](bevy_render::diagnostic::RenderDiagnosticsPlugin): the UI pass is
+/// measured as `render/ui` on the main texture, and as `render/ui_output` on the output texture.
+///
+/// ```
+/// use bevy_camera::prelude::*;
+/// use bevy_ecs::prelude::*;
+/// use bevy_ui_render::prelude::*;
+/// use bevy_window::Window;
+///
+/// fn spawn_camera(mut commands: Commands) {
+///     commands.spawn((Camera2d, UiRenderPath::OutputTexture));
+/// }
+///
+/// fn select_window_render_path(mut commands: Commands, window: Single<Entity, With<Window>>) {
+///     commands.entity(*window).insert(UiRenderPath::OutputTexture);
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Default, Debug, Reflect, Eq, PartialEq)]
+#[reflect(Component, Default, PartialEq, Clone)]
+pub enum UiRenderPath {
+    /// UI is drawn into the main texture of the camera, in its HDR format if the camera uses
+    /// [`Hdr`], before upscaling.
+    #[default]
+    MainTexture,
+    /// UI is drawn directly into the output texture, without MSAA, after upscaling.
+    ///
+    /// [`Camera2d`]s also draw their 2D content without MSAA, whatever their [`Msaa`], so that no
+    /// MSAA texture is resolved in the frame.
+    ///
+    /// The UI doesn't go through the main texture of the camera, so it is never drawn in HDR. The
+    /// UI pipelines are specialized on the format of the output texture, which must be an sRGB or
+    /// float format, or the UI falls back to [`UiRenderPath::MainTexture`].
+    OutputTexture,
+}
+
 #[derive(Default)]
 pub struct UiRenderPlugin;
 
@@ -227,6 +280,13 @@ impl Plugin for UiRenderPlugin {
                     .chain(),
             )
             .add_systems(RenderStartup, init_ui_pipeline)
+            .add_systems(
+                RenderDeviceReset,
+                (
+                    reset_render_resource::<ImageNodeBindGroups>,
+                    reset_render_resource::<UiMeta>,
+                ),
+            )
             .add_systems(
                 ExtractSchedule,
                 (
@@ -245,6 +305,12 @@ impl Plugin for UiRenderPlugin {
             .add_systems(
                 Render,
                 (
+                    disable_2d_msaa_of_output_texture_paths
+                        .in_set(RenderSystems::ManageViews)
+                        .before(prepare_view_targets),
+                    prepare_ui_render_paths
+                        .in_set(RenderSystems::ManageViews)
+                        .after(prepare_view_targets),
                     queue_uinodes.in_set(RenderSystems::Queue),
                     sort_phase_system::<TransparentUi>.in_set(RenderSystems::PhaseSort),
                     prepare_uinodes.in_set(RenderSystems::PrepareBindGroups),
@@ -252,11 +318,17 @@ impl Plugin for UiRenderPlugin {
             )
             .add_systems(
                 Core2d,
-                ui_pass.after(Core2dSystems::PostProcess).before(upscaling),
+                (
+                    ui_pass.after(Core2dSystems::PostProcess).before(upscaling),
+                    ui_output_pass.after(upscaling),
+                ),
             )
             .add_systems(
                 Core3d,
-                ui_pass.after(Core3dSystems::PostProcess).before(upscaling),
+                (
+                    ui_pass.after(Core3dSystems::PostProcess).before(upscaling),
+                    ui_output_pass.after(upscaling),
+                ),
             );
 
         app.add_plugins(UiTextureSlicerPlugin);
@@ -728,6 +800,67 @@ pub struct UiCameraView(pub Entity);
 #[derive(Component)]
 pub struct UiViewTarget(pub Entity);
 
+/// The format of the texture the UI of a view is drawn into, which the UI pipelines are specialized
+/// on.
+///
+/// This lives on the UI view, and is set by [`prepare_ui_render_paths`] from the [`UiRenderPath`]
+/// of the view.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UiViewTextureFormat(pub TextureFormat);
+
+/// Resolves the [`UiRenderPath`] and the [`UiViewTextureFormat`] of the UI views, now that the
+/// textures of their camera are known.
+///
+/// UI drawn into the output texture is never drawn in HDR, and falls back to the main texture if the
+/// UI pipelines don't support the format of the output texture. The UI shaders output linear colors,
+/// so the output texture must be in an sRGB format or a float format.
+pub fn prepare_ui_render_paths(
+    mut ui_views: Query<(
+        &mut ExtractedView,
+        &mut UiRenderPath,
+        &mut UiViewTextureFormat,
+        &UiViewTarget,
+    )>,
+    view_targets: Query<&ViewTarget>,
+) {
+    for (mut ui_view, mut ui_render_path, mut texture_format, ui_view_target) in &mut ui_views {
+        let Ok(view_target) = view_targets.get(ui_view_target.0) else {
+            continue;
+        };
+        if *ui_render_path == UiRenderPath::OutputTexture {
+            let format = view_target.out_texture_view_format();
+            if format.is_srgb() || format == ViewTarget::TEXTURE_FORMAT_HDR {
+                ui_view.hdr = false;
+                texture_format.0 = format;
+                continue;
+            }
+            once!(warn!(
+                "UiRenderPath::OutputTexture is not supported for output textures in the {format:?} format, drawing UI into the main texture instead"
+            ));
+            *ui_render_path = UiRenderPath::MainTexture;
+        }
+        texture_format.0 = view_target.main_texture_format();
+    }
+}
+
+/// Draws the 2D content of [`Camera2d`]s using [`UiRenderPath::OutputTexture`] without MSAA.
+///
+/// This runs before the textures of the views are created, and after the [`Msaa`] of the camera is
+/// extracted, so the [`Msaa`] of the main world is left untouched.
+pub fn disable_2d_msaa_of_output_texture_paths(
+    mut cameras: Query<(&mut Msaa, &UiCameraView), With<Camera2d>>,
+    ui_views: Query<&UiRenderPath>,
+) {
+    for (mut msaa, ui_camera_view) in &mut cameras {
+        if ui_views
+            .get(ui_camera_view.0)
+            .is_ok_and(|path| *path == UiRenderPath::OutputTexture)
+        {
+            *msaa = Msaa::Off;
+        }
+    }
+}
+
 /// Extracts all UI elements associated with a camera into the render world.
 pub fn extract_ui_camera_view(
     mut commands: Commands,
@@ -738,18 +871,33 @@ pub fn extract_ui_camera_view(
                 Entity,
                 RenderEntity,
                 &Camera,
+                &RenderTarget,
                 Has<Hdr>,
                 Option<&UiAntiAlias>,
                 Option<&BoxShadowSamples>,
+                Option<&UiRenderPath>,
             ),
             Or<(With<Camera2d>, With<Camera3d>)>,
         >,
     >,
+    windows: Extract<Query<&UiRenderPath, With<Window>>>,
+    primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
     mut live_entities: Local<HashSet<RetainedViewEntity>>,
 ) {
     live_entities.clear();
 
-    for (main_entity, render_entity, camera, hdr, ui_anti_alias, shadow_samples) in &query {
+    let primary_window = primary_window.single().ok();
+    for (
+        main_entity,
+        render_entity,
+        camera,
+        render_target,
+        hdr,
+        ui_anti_alias,
+        shadow_samples,
+        ui_render_path,
+    ) in &query
+    {
         // ignore inactive cameras
         if !camera.is_active {
             commands
@@ -795,6 +943,21 @@ pub fn extract_ui_camera_view(
                     },
                     // Link to the main camera view.
                     UiViewTarget(render_entity),
+                    // The path of the camera overrides the path of its window.
+                    ui_render_path
+                        .or_else(|| match render_target.normalize(primary_window)? {
+                            NormalizedRenderTarget::Window(window) => {
+                                windows.get(window.entity()).ok()
+                            }
+                            _ => None,
+                        })
+                        .copied()
+                        .unwrap_or_default(),
+                    UiViewTextureFormat(if hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    }),
                     TemporaryRenderEntity,
                 ))
                 .id();
@@ -1355,7 +1518,7 @@ pub fn queue_uinodes(
     mut pipelines: ResMut<SpecializedRenderPipelines<UiPipeline>>,
     mut transparent_render_phases: ResMut<ViewSortedRenderPhases<TransparentUi>>,
     render_views: Query<(&UiCameraView, Option<&UiAntiAlias>), With<ExtractedView>>,
-    camera_views: Query<&ExtractedView>,
+    camera_views: Query<(&ExtractedView, &UiViewTextureFormat)>,
     pipeline_cache: Res<PipelineCache>,
     draw_functions: Res<DrawFunctions<TransparentUi>>,
 ) {
@@ -1369,19 +1532,21 @@ pub fn queue_uinodes(
                 .get(extracted_uinode.extracted_camera_entity)
                 .ok()
                 .and_then(|(default_camera_view, ui_anti_alias)| {
-                    camera_views
-                        .get(default_camera_view.0)
-                        .ok()
-                        .and_then(|view| {
+                    camera_views.get(default_camera_view.0).ok().and_then(
+                        |(view, texture_format)| {
                             transparent_render_phases
                                 .get_mut(&view.retained_view_entity)
-                                .map(|transparent_phase| (view, ui_anti_alias, transparent_phase))
-                        })
+                                .map(|transparent_phase| {
+                                    (texture_format, ui_anti_alias, transparent_phase)
+                                })
+                        },
+                    )
                 });
             current_camera_entity = extracted_uinode.extracted_camera_entity;
         }
 
-        let Some((view, ui_anti_alias, transparent_phase)) = current_phase.as_mut() else {
+        let Some((texture_format, ui_anti_alias, transparent_phase)) = current_phase.as_mut()
+        else {
             continue;
         };
 
@@ -1389,7 +1554,7 @@ pub fn queue_uinodes(
             &pipeline_cache,
             &ui_pipeline,
             UiPipelineKey {
-                hdr: view.hdr,
+                texture_format: texture_format.0,
                 anti_alias: matches!(ui_anti_alias, None | Some(UiAntiAlias::On)),
             },
         );
@@ -1,13 +1,12 @@
 use bevy_asset::{load_embedded_asset, AssetServer, Handle};
 use bevy_ecs::prelude::*;
-use bevy_image::BevyDefault as _;
 use bevy_mesh::VertexBufferLayout;
 use bevy_render::{
     render_resource::{
         binding_types::{sampler, texture_2d, uniform_buffer},
         *,
     },
-    view::{ViewTarget, ViewUniform},
+    view::ViewUniform,
 };
 use bevy_shader::Shader;
 use bevy_utils::default;
@@ -48,7 +47,8 @@ pub fn init_ui_pipeline(mut commands: Commands, asset_server: Res<AssetServer>)
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct UiPipelineKey {
-    pub hdr: bool,
+    /// The format of the texture the UI is drawn into.
+    pub texture_format: TextureFormat,
     pub anti_alias: bool,
 }
 
@@ -94,11 +94,7 @@ impl SpecializedRenderPipeline for UiPipeline {
                 shader: self.shader.clone(),
                 shader_defs,
                 targets: vec![Some(ColorTargetState {
-                    format: if key.hdr {
-                        ViewTarget::TEXTURE_FORMAT_HDR
-                    } else {
-                        TextureFormat::bevy_default()
-                    },
+                    format: key.texture_format,
                     blend: Some(BlendState::ALPHA_BLENDING),
                     write_mask: ColorWrites::ALL,
                 })],
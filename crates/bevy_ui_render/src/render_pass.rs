@@ -1,8 +1,9 @@
 use core::ops::Range;
 
-use super::{ImageNodeBindGroups, UiBatch, UiMeta, UiViewTarget};
+use super::{ImageNodeBindGroups, UiBatch, UiMeta, UiRenderPath, UiViewTarget};
 
 use crate::UiCameraView;
+use bevy_camera::CameraOutputMode;
 use bevy_ecs::{
     entity::EntityHash,
     prelude::*,
@@ -21,21 +22,67 @@ use bevy_render::{
 use indexmap::IndexMap;
 use tracing::error;
 
+/// Draws the UI of views using [`UiRenderPath::MainTexture`] into their main texture.
 pub fn ui_pass(
     world: &World,
     view: ViewQuery<&UiCameraView>,
-    ui_view_query: Query<(&ExtractedView, &UiViewTarget)>,
+    ui_view_query: Query<(&ExtractedView, &UiViewTarget, &UiRenderPath)>,
+    ui_view_target_query: Query<(&ViewTarget, &ExtractedCamera)>,
+    transparent_render_phases: Res<ViewSortedRenderPhases<TransparentUi>>,
+    ctx: RenderContext,
+) {
+    render_ui(
+        world,
+        view.into_inner(),
+        UiRenderPath::MainTexture,
+        ui_view_query,
+        ui_view_target_query,
+        transparent_render_phases,
+        ctx,
+    );
+}
+
+/// Draws the UI of views using [`UiRenderPath::OutputTexture`] into their output texture, after
+/// upscaling.
+pub fn ui_output_pass(
+    world: &World,
+    view: ViewQuery<&UiCameraView>,
+    ui_view_query: Query<(&ExtractedView, &UiViewTarget, &UiRenderPath)>,
+    ui_view_target_query: Query<(&ViewTarget, &ExtractedCamera)>,
+    transparent_render_phases: Res<ViewSortedRenderPhases<TransparentUi>>,
+    ctx: RenderContext,
+) {
+    render_ui(
+        world,
+        view.into_inner(),
+        UiRenderPath::OutputTexture,
+        ui_view_query,
+        ui_view_target_query,
+        transparent_render_phases,
+        ctx,
+    );
+}
+
+fn render_ui(
+    world: &World,
+    ui_camera_view: &UiCameraView,
+    render_path: UiRenderPath,
+    ui_view_query: Query<(&ExtractedView, &UiViewTarget, &UiRenderPath)>,
     ui_view_target_query: Query<(&ViewTarget, &ExtractedCamera)>,
     transparent_render_phases: Res<ViewSortedRenderPhases<TransparentUi>>,
     mut ctx: RenderContext,
 ) {
-    let ui_camera_view = view.into_inner();
     let ui_view_entity = ui_camera_view.0;
 
-    let Ok((extracted_view, ui_view_target)) = ui_view_query.get(ui_view_entity) else {
+    let Ok((extracted_view, ui_view_target, ui_render_path)) = ui_view_query.get(ui_view_entity)
+    else {
         return;
     };
 
+    if *ui_render_path != render_path {
+        return;
+    }
+
     let Ok((target, camera)) = ui_view_target_query.get(ui_view_target.0) else {
         return;
     };
@@ -50,18 +97,29 @@ pub fn ui_pass(
         return;
     }
 
+    let (color_attachment, label) = match render_path {
+        UiRenderPath::MainTexture => (target.get_unsampled_color_attachment(), "ui"),
+        UiRenderPath::OutputTexture => {
+            // Nothing is written to the output texture when the camera skips its output.
+            if matches!(camera.output_mode, CameraOutputMode::Skip) {
+                return;
+            }
+            (target.out_texture_color_attachment(None), "ui_output")
+        }
+    };
+
     let diagnostics = ctx.diagnostic_recorder();
     let diagnostics = diagnostics.as_deref();
 
     let mut render_pass = ctx.begin_tracked_render_pass(RenderPassDescriptor {
-        label: Some("ui"),
-        color_attachments: &[Some(target.get_unsampled_color_attachment())],
+        label: Some(label),
+        color_attachments: &[Some(color_attachment)],
         depth_stencil_attachment: None,
         timestamp_writes: None,
         occlusion_query_set: None,
         multiview_mask: None,
     });
-    let pass_span = diagnostics.pass_span(&mut render_pass, "ui");
+    let pass_span = diagnostics.pass_span(&mut render_pass, label);
 
     if let Some(viewport) = camera.viewport.as_ref() {
         render_pass.set_camera_viewport(viewport);
@@ -5,7 +5,7 @@ use bevy_ecs::{component::Component, reflect::ReflectComponent};
 use bevy_reflect::{prelude::ReflectDefault, Reflect};
 use bevy_render::{
     extract_component::ExtractComponent,
-    render_resource::{AsBindGroup, RenderPipelineDescriptor},
+    render_resource::{AsBindGroup, RenderPipelineDescriptor, TextureFormat},
 };
 use bevy_shader::ShaderRef;
 use derive_more::derive::From;
@@ -126,6 +126,8 @@ pub trait UiMaterial: AsBindGroup + Asset + Clone + Sized {
 
 pub struct UiMaterialKey<M: UiMaterial> {
     pub hdr: bool,
+    /// The format of the texture the UI is drawn into.
+    pub texture_format: TextureFormat,
     pub bind_group_data: M::Data,
 }
 
@@ -136,7 +138,9 @@ where
     M::Data: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.hdr == other.hdr && self.bind_group_data == other.bind_group_data
+        self.hdr == other.hdr
+            && self.texture_format == other.texture_format
+            && self.bind_group_data == other.bind_group_data
     }
 }
 
@@ -147,6 +151,7 @@ where
     fn clone(&self) -> Self {
         Self {
             hdr: self.hdr,
+            texture_format: self.texture_format,
             bind_group_data: self.bind_group_data.clone(),
         }
     }
@@ -158,6 +163,7 @@ where
 {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.hdr.hash(state);
+        self.texture_format.hash(state);
         self.bind_group_data.hash(state);
     }
 }
@@ -9,6 +9,7 @@ use bevy_ecs::{
     query::With,
     system::{Commands, Query},
 };
+use bevy_input::gamepad::{GamepadButton, GamepadButtonChangedEvent};
 use bevy_input::keyboard::{KeyCode, KeyboardInput};
 use bevy_input::ButtonState;
 use bevy_input_focus::FocusedInput;
@@ -45,6 +46,24 @@ fn button_on_key_event(
     }
 }
 
+fn button_on_gamepad_event(
+    mut event: On<FocusedInput<GamepadButtonChangedEvent>>,
+    q_state: Query<Has<InteractionDisabled>, With<Button>>,
+    mut commands: Commands,
+) {
+    if let Ok(disabled) = q_state.get(event.focused_entity)
+        && !disabled
+    {
+        let input_event = &event.input;
+        if input_event.button == GamepadButton::South && input_event.state == ButtonState::Pressed {
+            event.propagate(false);
+            commands.trigger(Activate {
+                entity: event.focused_entity,
+            });
+        }
+    }
+}
+
 fn button_on_pointer_click(
     mut click: On<Pointer<Click>>,
     mut q_state: Query<(Has<Pressed>, Has<InteractionDisabled>), With<Button>>,
@@ -118,6 +137,7 @@ pub struct ButtonPlugin;
 impl Plugin for ButtonPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(button_on_key_event)
+            .add_observer(button_on_gamepad_event)
             .add_observer(button_on_pointer_down)
             .add_observer(button_on_pointer_up)
             .add_observer(button_on_pointer_click)
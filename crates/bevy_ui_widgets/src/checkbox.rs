@@ -9,6 +9,7 @@ use bevy_ecs::{
     observer::On,
     system::{Commands, Query},
 };
+use bevy_input::gamepad::{GamepadButton, GamepadButtonChangedEvent};
 use bevy_input::keyboard::{KeyCode, KeyboardInput};
 use bevy_input::ButtonState;
 use bevy_input_focus::{FocusedInput, InputFocus, InputFocusVisible};
@@ -20,7 +21,8 @@ use bevy_ecs::entity::Entity;
 
 /// Headless widget implementation for checkboxes. The [`Checked`] component represents the current
 /// state of the checkbox. The widget will emit a [`ValueChange<bool>`] event when clicked, or when
-/// the `Enter` or `Space` key is pressed while the checkbox is focused.
+/// the `Enter` or `Space` key, or the `South` gamepad button, is pressed while the checkbox is
+/// focused.
 ///
 /// Add the [`checkbox_self_update`] observer watching the entity with this component to automatically add and remove the [`Checked`] component.
 ///
@@ -53,6 +55,23 @@ fn checkbox_on_key_input(
     }
 }
 
+fn checkbox_on_gamepad_input(
+    mut ev: On<FocusedInput<GamepadButtonChangedEvent>>,
+    q_checkbox: Query<Has<Checked>, (With<Checkbox>, Without<InteractionDisabled>)>,
+    mut commands: Commands,
+) {
+    if let Ok(is_checked) = q_checkbox.get(ev.focused_entity) {
+        let event = &ev.event().input;
+        if event.state == ButtonState::Pressed && event.button == GamepadButton::South {
+            ev.propagate(false);
+            commands.trigger(ValueChange {
+                source: ev.focused_entity,
+                value: !is_checked,
+            });
+        }
+    }
+}
+
 fn checkbox_on_pointer_click(
     mut click: On<Pointer<Click>>,
     q_checkbox: Query<(Has<Checked>, Has<InteractionDisabled>), With<Checkbox>>,
@@ -175,6 +194,7 @@ pub struct CheckboxPlugin;
 impl Plugin for CheckboxPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(checkbox_on_key_input)
+            .add_observer(checkbox_on_gamepad_input)
             .add_observer(checkbox_on_pointer_click)
             .add_observer(checkbox_on_set_checked)
             .add_observer(checkbox_on_toggle_checked);
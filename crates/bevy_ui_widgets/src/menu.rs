@@ -14,6 +14,7 @@ use bevy_ecs::{
     system::{Commands, Query, Res, ResMut},
 };
 use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonChangedEvent},
     keyboard::{KeyCode, KeyboardInput},
     ButtonState,
 };
@@ -372,6 +373,34 @@ fn menu_on_menu_event(
 #[require(AccessibilityNode(accesskit::Node::new(Role::Button)))]
 pub struct MenuButton;
 
+fn menu_item_on_gamepad_event(
+    mut ev: On<FocusedInput<GamepadButtonChangedEvent>>,
+    q_item: Query<Has<InteractionDisabled>, With<MenuItem>>,
+    mut commands: Commands,
+) {
+    if let Ok(disabled) = q_item.get(ev.focused_entity)
+        && !disabled
+    {
+        let event = &ev.event().input;
+        let entity = ev.event().focused_entity;
+        if event.button == GamepadButton::South && event.state == ButtonState::Pressed {
+            ev.propagate(false);
+            // Trigger the action for this menu item.
+            commands.trigger(Activate { entity });
+            // Set the focus to the menu button.
+            commands.trigger(MenuEvent {
+                source: entity,
+                action: MenuAction::FocusRoot,
+            });
+            // Close the stack
+            commands.trigger(MenuEvent {
+                source: entity,
+                action: MenuAction::CloseAll,
+            });
+        }
+    }
+}
+
 fn menubutton_on_key_event(
     mut event: On<FocusedInput<KeyboardInput>>,
     q_state: Query<Has<InteractionDisabled>, With<MenuButton>>,
@@ -394,6 +423,25 @@ fn menubutton_on_key_event(
     }
 }
 
+fn menubutton_on_gamepad_event(
+    mut event: On<FocusedInput<GamepadButtonChangedEvent>>,
+    q_state: Query<Has<InteractionDisabled>, With<MenuButton>>,
+    mut commands: Commands,
+) {
+    if let Ok(disabled) = q_state.get(event.focused_entity)
+        && !disabled
+    {
+        let input_event = &event.input;
+        if input_event.button == GamepadButton::South && input_event.state == ButtonState::Pressed {
+            event.propagate(false);
+            commands.trigger(MenuEvent {
+                action: MenuAction::Toggle,
+                source: event.focused_entity,
+            });
+        }
+    }
+}
+
 fn menubutton_on_pointer_press(
     mut press: On<Pointer<Press>>,
     mut q_state: Query<(Entity, Has<InteractionDisabled>, Has<Pressed>), With<MenuButton>>,
@@ -417,6 +465,7 @@ impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, (menu_acquire_focus, menu_on_lose_focus).chain())
             .add_observer(menu_on_key_event)
+            .add_observer(menu_item_on_gamepad_event)
             .add_observer(menu_on_menu_event)
             .add_observer(menu_item_on_pointer_down)
             .add_observer(menu_item_on_pointer_up)
@@ -424,6 +473,7 @@ impl Plugin for MenuPlugin {
             .add_observer(menu_item_on_pointer_drag_end)
             .add_observer(menu_item_on_pointer_cancel)
             .add_observer(menubutton_on_key_event)
+            .add_observer(menubutton_on_gamepad_event)
             .add_observer(menubutton_on_pointer_press);
     }
 }
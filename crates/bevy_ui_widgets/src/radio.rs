@@ -10,6 +10,7 @@ use bevy_ecs::{
     reflect::ReflectComponent,
     system::{Commands, Query},
 };
+use bevy_input::gamepad::{GamepadButton, GamepadButtonChangedEvent};
 use bevy_input::keyboard::{KeyCode, KeyboardInput};
 use bevy_input::ButtonState;
 use bevy_input_focus::FocusedInput;
@@ -50,7 +51,7 @@ pub struct RadioGroup;
 /// The widget emits a [`ValueChange<bool>`] event with the value `true` whenever it becomes checked,
 /// either through a mouse click or when a [`RadioGroup`] checks the widget.
 /// If the [`RadioButton`] is focusable, it can also be checked using the `Enter` or `Space` keys,
-/// in which case the event will likewise be emitted.
+/// or the `South` gamepad button, in which case the event will likewise be emitted.
 #[derive(Component, Debug)]
 #[require(AccessibilityNode(accesskit::Node::new(Role::RadioButton)), Checkable)]
 #[derive(Reflect)]
@@ -187,6 +188,36 @@ fn radio_button_on_key_input(
     }
 }
 
+fn radio_button_on_gamepad_input(
+    mut ev: On<FocusedInput<GamepadButtonChangedEvent>>,
+    q_radio_button: Query<(Has<InteractionDisabled>, Has<Checked>), With<RadioButton>>,
+    q_group: Query<(), With<RadioGroup>>,
+    q_parents: Query<&ChildOf>,
+    mut commands: Commands,
+) {
+    let Ok((disabled, checked)) = q_radio_button.get(ev.focused_entity) else {
+        // Not a radio button
+        return;
+    };
+
+    let event = &ev.event().input;
+    if event.state == ButtonState::Pressed && event.button == GamepadButton::South {
+        ev.propagate(false);
+
+        // Radio button is disabled or already checked
+        if disabled || checked {
+            return;
+        }
+
+        trigger_radio_button_and_radio_group_value_change(
+            ev.focused_entity,
+            &q_group,
+            &q_parents,
+            &mut commands,
+        );
+    }
+}
+
 fn radio_button_on_click(
     mut ev: On<Pointer<Click>>,
     q_group: Query<(), With<RadioGroup>>,
@@ -247,6 +278,7 @@ impl Plugin for RadioGroupPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(radio_group_on_key_input)
             .add_observer(radio_button_on_click)
-            .add_observer(radio_button_on_key_input);
+            .add_observer(radio_button_on_key_input)
+            .add_observer(radio_button_on_gamepad_input);
     }
 }
@@ -0,0 +1,496 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use bevy_app::{App, First, Last, Plugin};
+use bevy_ecs::{error::BevyError, prelude::*, world::FromWorld};
+use bevy_platform::collections::HashMap;
+
+use crate::{
+    ClosingWindow, CursorOptions, Monitor, PrimaryMonitor, RawHandleWrapper,
+    RawHandleWrapperHolder, Window, WindowClosed, WindowClosing, WindowCreated, WindowEvent,
+};
+
+/// A windowing backend driven by the [`WindowBackendPlugin`].
+///
+/// This is the extension point for alternative windowing backends, such as SDL3, a host-provided
+/// window, or a null backend for tests. The backend only has to talk to the platform: the plugin
+/// keeps the [`Window`] entities and the backend windows in sync, and forwards the events reported
+/// by the backend as messages.
+///
+/// Backends are stored as [`!Send`](Send) data, so they can hold platform handles that must stay on
+/// the main thread.
+///
+/// `bevy_winit` doesn't use this trait: winit owns the event loop, so it is integrated as the
+/// [runner](bevy_app::App::set_runner) of the app instead. Backends implementing this trait are
+/// polled once per frame and work with any runner.
+pub trait WindowBackend: 'static {
+    /// Creates the backend window for the `entity` with a newly added [`Window`] component.
+    ///
+    /// The backend can update `window` to reflect the window that was actually created, for
+    /// example its scale factor. If it returns a [`RawHandleWrapper`], the renderer creates a
+    /// surface for the window, otherwise the window is marked as an [`OffscreenWindow`].
+    fn create_window(
+        &mut self,
+        entity: Entity,
+        window: &mut Window,
+        cursor_options: Option<&CursorOptions>,
+    ) -> Result<Option<RawHandleWrapper>, BevyError>;
+
+    /// Applies the changes made to the [`Window`] component of `entity` by the app.
+    ///
+    /// This is also called after the plugin applies the events reported by the backend to the
+    /// [`Window`], so the backend should ignore changes matching its own state.
+    fn update_window(&mut self, entity: Entity, window: &Window);
+
+    /// Destroys the backend window of `entity`, whose [`Window`] component was removed.
+    fn destroy_window(&mut self, entity: Entity);
+
+    /// Collects the events that happened since the last call into `events`.
+    ///
+    /// This is called once per frame, in [`First`].
+    fn pump_events(&mut self, events: &mut Vec<WindowEvent>);
+
+    /// Returns the monitors currently connected.
+    ///
+    /// Backends without monitor support can return an empty list.
+    fn monitors(&self) -> Vec<BackendMonitor> {
+        Vec::new()
+    }
+}
+
+/// A monitor reported by a [`WindowBackend`].
+#[derive(Debug, Clone)]
+pub struct BackendMonitor {
+    /// An identifier of the monitor, unique and stable for as long as the monitor stays connected.
+    pub id: u64,
+    /// The properties of the monitor.
+    pub monitor: Monitor,
+    /// Whether this is the primary monitor.
+    pub primary: bool,
+}
+
+/// Drives a [`WindowBackend`] of type `B`.
+///
+/// The backend is created with [`FromWorld`] when the plugin is built, unless it was already
+/// inserted with [`App::insert_non_send`], which lets apps configure the backend beforehand.
+///
+/// The plugin:
+/// - creates, updates and destroys backend windows to match the [`Window`] entities, and writes
+///   [`WindowCreated`], [`WindowClosing`] and [`WindowClosed`] messages, in [`Last`]. Windows
+///   created without a [`RawHandleWrapper`] are marked as [`OffscreenWindow`]s.
+/// - pumps the events of the backend in [`First`], applies the resize, scale factor, focus, move
+///   and cursor events to the [`Window`] components, and writes each event both as a
+///   [`WindowEvent`] and as its own message.
+/// - synchronizes [`Monitor`] entities with the monitors reported by the backend.
+///
+/// This plugin requires the [`WindowPlugin`](crate::WindowPlugin), and should not be used
+/// alongside `bevy_winit`.
+pub struct WindowBackendPlugin<B>(PhantomData<fn() -> B>);
+
+impl<B> Default for WindowBackendPlugin<B> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B: WindowBackend + FromWorld> Plugin for WindowBackendPlugin<B> {
+    fn build(&self, app: &mut App) {
+        app.init_non_send::<B>()
+            .init_resource::<BackendMonitors>()
+            .add_systems(
+                First,
+                (pump_window_backend_events::<B>, sync_backend_monitors::<B>).chain(),
+            )
+            .add_systems(
+                Last,
+                (
+                    destroy_backend_windows::<B>,
+                    create_backend_windows::<B>,
+                    update_backend_windows::<B>,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Marks a [`Window`] whose backend window has no [`RawHandleWrapper`], such as the windows of the
+/// [`VirtualWindowBackend`](crate::VirtualWindowBackend).
+///
+/// The renderer can't create a surface for these windows, so it renders them to an offscreen
+/// texture of the size of the window instead. Cameras targeting them render as usual, and their
+/// frames can be read back with screenshots.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct OffscreenWindow;
+
+/// The [`Monitor`] entities spawned for the monitors of the [`WindowBackend`], by id.
+#[derive(Resource, Debug, Default)]
+pub struct BackendMonitors {
+    entities: HashMap<u64, Entity>,
+}
+
+impl BackendMonitors {
+    /// Returns the [`Monitor`] entity of the backend monitor with the given `id`.
+    pub fn get(&self, id: u64) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+}
+
+/// Creates the backend windows of the entities with a newly added [`Window`] component.
+pub fn create_backend_windows<B: WindowBackend>(
+    mut commands: Commands,
+    mut backend: NonSendMut<B>,
+    mut windows: Query<
+        (
+            Entity,
+            &mut Window,
+            Option<&CursorOptions>,
+            Option<&RawHandleWrapperHolder>,
+        ),
+        Added<Window>,
+    >,
+    mut window_created: MessageWriter<WindowCreated>,
+) {
+    for (entity, mut window, cursor_options, handle_holder) in &mut windows {
+        log::info!("Creating new window {} ({})", window.title.as_str(), entity);
+        match backend.create_window(entity, &mut window, cursor_options) {
+            Ok(handle_wrapper) => {
+                if let Some(handle_wrapper) = handle_wrapper {
+                    if let Some(handle_holder) = handle_holder {
+                        *handle_holder.0.lock().unwrap() = Some(handle_wrapper.clone());
+                    }
+                    commands.entity(entity).insert(handle_wrapper);
+                } else {
+                    commands.entity(entity).insert(OffscreenWindow);
+                }
+                window_created.write(WindowCreated { window: entity });
+            }
+            Err(error) => log::error!("Failed to create window {entity}: {error}"),
+        }
+    }
+}
+
+/// Applies the changes made to [`Window`] components to the backend windows.
+pub fn update_backend_windows<B: WindowBackend>(
+    mut backend: NonSendMut<B>,
+    windows: Query<(Entity, Ref<Window>), Changed<Window>>,
+) {
+    for (entity, window) in &windows {
+        // Newly created windows are already up to date.
+        if !window.is_added() {
+            backend.update_window(entity, &window);
+        }
+    }
+}
+
+/// Destroys the backend windows of the entities whose [`Window`] component was removed.
+pub fn destroy_backend_windows<B: WindowBackend>(
+    mut backend: NonSendMut<B>,
+    closing: Query<Entity, With<ClosingWindow>>,
+    mut closed: RemovedComponents<Window>,
+    windows: Query<(), With<Window>>,
+    mut window_closing: MessageWriter<WindowClosing>,
+    mut window_closed: MessageWriter<WindowClosed>,
+) {
+    for window in &closing {
+        window_closing.write(WindowClosing { window });
+    }
+    for window in closed.read() {
+        // The component could have been removed and added again in the same frame.
+        if !windows.contains(window) {
+            log::info!("Closing window {}", window);
+            backend.destroy_window(window);
+            window_closed.write(WindowClosed { window });
+        }
+    }
+}
+
+/// Pumps the events of the [`WindowBackend`], applies them to the [`Window`] components, and
+/// writes them as messages.
+pub fn pump_window_backend_events<B: WindowBackend>(
+    world: &mut World,
+    mut events: Local<Vec<WindowEvent>>,
+) {
+    world.non_send_mut::<B>().pump_events(&mut events);
+    for event in events.drain(..) {
+        apply_window_event(world, &event);
+        write_window_event(world, event);
+    }
+}
+
+/// Keeps the [`Window`] components in sync with the state reported by the backend.
+fn apply_window_event(world: &mut World, event: &WindowEvent) {
+    let entity = match event {
+        WindowEvent::WindowResized(event) => event.window,
+        WindowEvent::WindowBackendScaleFactorChanged(event) => event.window,
+        WindowEvent::WindowFocused(event) => event.window,
+        WindowEvent::WindowMoved(event) => event.window,
+        WindowEvent::CursorMoved(event) => event.window,
+        WindowEvent::CursorLeft(event) => event.window,
+        _ => return,
+    };
+    let Some(mut window) = world.get_mut::<Window>(entity) else {
+        return;
+    };
+    match event {
+        WindowEvent::WindowResized(event) => window.resolution.set(event.width, event.height),
+        WindowEvent::WindowBackendScaleFactorChanged(event) => {
+            // Backends report the physical size with a `WindowResized` event, like winit does.
+            // Scaling the current physical size here would apply the scale factor twice.
+            window
+                .resolution
+                .set_scale_factor(event.scale_factor as f32);
+        }
+        WindowEvent::WindowFocused(event) => window.focused = event.focused,
+        WindowEvent::WindowMoved(event) => window.position.set(event.position),
+        WindowEvent::CursorMoved(event) => window.set_cursor_position(Some(event.position)),
+        WindowEvent::CursorLeft(_) => window.set_cursor_position(None),
+        _ => {}
+    }
+}
+
+/// Writes `event` both as a [`WindowEvent`] and as its own message.
+fn write_window_event(world: &mut World, event: WindowEvent) {
+    match event.clone() {
+        WindowEvent::AppLifecycle(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorEntered(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorLeft(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorMoved(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::FileDragAndDrop(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::Ime(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::RequestRedraw(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowBackendScaleFactorChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowCloseRequested(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowCreated(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowDestroyed(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowFocused(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowMoved(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowOccluded(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::OnScreenKeyboardChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowResized(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowScaleFactorChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowThemeChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseButtonInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseMotion(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseWheel(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::PinchGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::RotationGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::DoubleTapGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::PanGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::TouchInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::KeyboardInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::KeyboardFocusLost(e) => {
+            world.write_message(e);
+        }
+    };
+    world.write_message(event);
+}
+
+/// Synchronizes the [`Monitor`] entities with the monitors reported by the [`WindowBackend`].
+pub fn sync_backend_monitors<B: WindowBackend>(
+    mut commands: Commands,
+    backend: NonSend<B>,
+    mut monitors: ResMut<BackendMonitors>,
+) {
+    let reported = backend.monitors();
+    monitors.entities.retain(|id, entity| {
+        let connected = reported.iter().any(|monitor| monitor.id == *id);
+        if !connected {
+            log::info!("Monitor removed {}", entity);
+            commands.entity(*entity).despawn();
+        }
+        connected
+    });
+    for BackendMonitor {
+        id,
+        monitor,
+        primary,
+    } in reported
+    {
+        if monitors.entities.contains_key(&id) {
+            continue;
+        }
+        let mut entity = commands.spawn(monitor);
+        if primary {
+            entity.insert(PrimaryMonitor);
+        }
+        monitors.entities.insert(id, entity.id());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{WindowBackendScaleFactorChanged, WindowFocused, WindowPlugin, WindowResized};
+    use alloc::vec;
+    use bevy_ecs::message::Messages;
+
+    #[derive(Default)]
+    struct TestBackend {
+        created: Vec<Entity>,
+        updated: Vec<Entity>,
+        destroyed: Vec<Entity>,
+        pending: Vec<WindowEvent>,
+    }
+
+    impl WindowBackend for TestBackend {
+        fn create_window(
+            &mut self,
+            entity: Entity,
+            window: &mut Window,
+            _cursor_options: Option<&CursorOptions>,
+        ) -> Result<Option<RawHandleWrapper>, BevyError> {
+            window.resolution.set_scale_factor(2.0);
+            self.created.push(entity);
+            Ok(None)
+        }
+
+        fn update_window(&mut self, entity: Entity, _window: &Window) {
+            self.updated.push(entity);
+        }
+
+        fn destroy_window(&mut self, entity: Entity) {
+            self.destroyed.push(entity);
+        }
+
+        fn pump_events(&mut self, events: &mut Vec<WindowEvent>) {
+            events.append(&mut self.pending);
+        }
+
+        fn monitors(&self) -> Vec<BackendMonitor> {
+            vec![BackendMonitor {
+                id: 7,
+                monitor: Monitor {
+                    name: None,
+                    physical_height: 1080,
+                    physical_width: 1920,
+                    physical_position: Default::default(),
+                    refresh_rate_millihertz: None,
+                    scale_factor: 1.0,
+                    video_modes: Vec::new(),
+                },
+                primary: true,
+            }]
+        }
+    }
+
+    #[test]
+    fn drives_backend_windows() {
+        let mut app = App::new();
+        app.add_plugins((
+            WindowPlugin {
+                exit_condition: crate::ExitCondition::DontExit,
+                ..Default::default()
+            },
+            WindowBackendPlugin::<TestBackend>::default(),
+        ));
+        app.update();
+
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<Window>>()
+            .single(app.world())
+            .unwrap();
+        assert_eq!(app.world().non_send::<TestBackend>().created, [window]);
+        assert!(app.world().get::<OffscreenWindow>(window).is_some());
+        assert_eq!(app.world().resource::<Messages<WindowCreated>>().len(), 1);
+        assert_eq!(
+            app.world().get::<Window>(window).unwrap().scale_factor(),
+            2.0
+        );
+        let monitor = app.world().resource::<BackendMonitors>().get(7).unwrap();
+        assert!(app.world().get::<PrimaryMonitor>(monitor).is_some());
+
+        app.world_mut().non_send_mut::<TestBackend>().pending = vec![
+            WindowResized {
+                window,
+                width: 640.0,
+                height: 480.0,
+            }
+            .into(),
+            WindowFocused {
+                window,
+                focused: false,
+            }
+            .into(),
+        ];
+        app.update();
+
+        let window_component = app.world().get::<Window>(window).unwrap();
+        assert_eq!(window_component.width(), 640.0);
+        assert!(!window_component.focused);
+        assert_eq!(app.world().resource::<Messages<WindowResized>>().len(), 1);
+        assert_eq!(app.world().resource::<Messages<WindowEvent>>().len(), 2);
+        assert_eq!(app.world().non_send::<TestBackend>().updated, [window]);
+
+        // The backend reports the physical size itself, so it doesn't change with the scale factor.
+        app.world_mut().non_send_mut::<TestBackend>().pending =
+            vec![WindowBackendScaleFactorChanged {
+                window,
+                scale_factor: 1.0,
+            }
+            .into()];
+        app.update();
+        let window_component = app.world().get::<Window>(window).unwrap();
+        assert_eq!(window_component.scale_factor(), 1.0);
+        assert_eq!(window_component.physical_width(), 1280);
+
+        app.world_mut().despawn(window);
+        app.update();
+        assert_eq!(app.world().non_send::<TestBackend>().destroyed, [window]);
+        assert_eq!(app.world().resource::<Messages<WindowClosed>>().len(), 1);
+    }
+}
@@ -6,7 +6,7 @@ use bevy_input::{
     mouse::{MouseButtonInput, MouseMotion, MouseWheel},
     touch::TouchInput,
 };
-use bevy_math::{IVec2, Vec2};
+use bevy_math::{IVec2, Rect, Vec2};
 
 #[cfg(feature = "std")]
 use std::path::PathBuf;
@@ -329,6 +329,30 @@ pub struct WindowOccluded {
     pub occluded: bool,
 }
 
+/// The on-screen keyboard of a window was shown, hidden or moved.
+///
+/// See [`Window::on_screen_keyboard`](crate::Window::on_screen_keyboard) to show it.
+#[derive(Message, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct OnScreenKeyboardChanged {
+    /// Window of the keyboard.
+    pub window: Entity,
+    /// The part of the window covered by the keyboard, in logical pixels from the top-left corner
+    /// of the window, or `None` when it doesn't cover the window.
+    ///
+    /// Scroll the focused text field above this rectangle to keep it in view.
+    pub occluded: Option<Rect>,
+}
+
 /// An event that indicates a window's scale factor has changed.
 #[derive(Message, Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -528,6 +552,8 @@ pub enum WindowEvent {
     WindowMoved(WindowMoved),
     /// A window has started or stopped being occluded.
     WindowOccluded(WindowOccluded),
+    /// The on-screen keyboard of a window was shown, hidden or moved.
+    OnScreenKeyboardChanged(OnScreenKeyboardChanged),
     /// A window's logical size has changed.
     WindowResized(WindowResized),
     /// A window's scale factor has changed.
@@ -646,6 +672,12 @@ impl From<WindowOccluded> for WindowEvent {
     }
 }
 
+impl From<OnScreenKeyboardChanged> for WindowEvent {
+    fn from(e: OnScreenKeyboardChanged) -> Self {
+        Self::OnScreenKeyboardChanged(e)
+    }
+}
+
 impl From<WindowResized> for WindowEvent {
     fn from(e: WindowResized) -> Self {
         Self::WindowResized(e)
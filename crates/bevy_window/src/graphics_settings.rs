@@ -0,0 +1,665 @@
+use alloc::{format, string::String};
+use core::time::Duration;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::UVec2;
+use bevy_platform::time::Instant;
+use log::{info, warn};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::prelude::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{
+    Monitor, MonitorSelection, PresentMode, PrimaryMonitor, PrimaryWindow, SupportedPresentModes,
+    VideoModeSelection, Window, WindowMode,
+};
+
+/// A [`Plugin`] that applies [`GraphicsSettings`] to the primary window transactionally.
+///
+/// New settings are requested with an [`ApplyGraphicsSettings`] message. Once applied, they can
+/// require confirmation: unless a [`ConfirmGraphicsSettings`] message is sent within
+/// [`GraphicsSettingsPlugin::confirmation_timeout`], the previous settings are restored. This is
+/// the usual "Keep these settings? Reverting in 15 seconds" flow of graphics options menus, which
+/// protects players from ending up with a black screen after picking an unsupported mode.
+///
+/// Settings the primary window can't use, like a present mode its surface doesn't support or a
+/// video mode its monitor doesn't have, are not applied and are reverted. Other plugins can
+/// reconfigure themselves by reacting to changes of the [`GraphicsSettings`] resource, and send a
+/// [`GraphicsSettingsFailed`] message if the new settings couldn't be applied, which reverts them.
+///
+/// With the `bevy_state` feature, and if the `StatesPlugin` is added, the
+/// [`GraphicsSettings::quality`] is also exposed as a [`GraphicsQuality`] state. Plugins can
+/// then configure their presets for each tier in its `OnEnter` schedules, or with sub-states of
+/// it, instead of each tracking the resource.
+///
+/// This plugin requires the [`WindowPlugin`](crate::WindowPlugin).
+pub struct GraphicsSettingsPlugin {
+    /// How long newly applied settings wait for a [`ConfirmGraphicsSettings`] message before being
+    /// reverted, if [`ApplyGraphicsSettings::require_confirmation`] is set.
+    ///
+    /// Defaults to 15 seconds.
+    pub confirmation_timeout: Duration,
+}
+
+impl Default for GraphicsSettingsPlugin {
+    fn default() -> Self {
+        Self {
+            confirmation_timeout: Duration::from_secs(15),
+        }
+    }
+}
+
+impl Plugin for GraphicsSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ApplyGraphicsSettings>()
+            .add_message::<ConfirmGraphicsSettings>()
+            .add_message::<RevertGraphicsSettings>()
+            .add_message::<GraphicsSettingsFailed>()
+            .add_message::<GraphicsSettingsEvent>()
+            .insert_resource(GraphicsSettingsTransaction {
+                confirmation_timeout: self.confirmation_timeout,
+                pending: None,
+            })
+            .add_systems(
+                PostUpdate,
+                (
+                    update_graphics_settings_transaction,
+                    apply_graphics_settings_to_primary_window
+                        .run_if(resource_changed::<GraphicsSettings>),
+                )
+                    .chain()
+                    .in_set(GraphicsSettingsSystems),
+            );
+
+        // Settings inserted before this plugin (e.g. loaded from a config file) take precedence
+        // over the primary window's initial configuration.
+        if !app.world().contains_resource::<GraphicsSettings>() {
+            let settings = app
+                .world_mut()
+                .query_filtered::<&Window, With<PrimaryWindow>>()
+                .single(app.world())
+                .map(GraphicsSettings::from_window)
+                .unwrap_or_default();
+            app.insert_resource(settings);
+        }
+    }
+
+    #[cfg(feature = "bevy_state")]
+    fn finish(&self, app: &mut App) {
+        use bevy_state::{app::AppExtStates, state::StateTransition};
+
+        // The states plugin may be added after this one.
+        if app.get_schedule(StateTransition).is_none() {
+            return;
+        }
+        let quality = app.world().resource::<GraphicsSettings>().quality;
+        app.insert_state(quality).add_systems(
+            PostUpdate,
+            update_graphics_quality_state
+                .after(update_graphics_settings_transaction)
+                .in_set(GraphicsSettingsSystems),
+        );
+    }
+}
+
+/// The [`SystemSet`] in [`PostUpdate`] that applies [`GraphicsSettings`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphicsSettingsSystems;
+
+/// The user-facing graphics settings of the application, as found in a graphics options menu.
+///
+/// The window related settings are applied to the [`PrimaryWindow`] by the
+/// [`GraphicsSettingsPlugin`], and the backend is applied by the renderer when it is created.
+///
+/// This resource should not be mutated directly: send an [`ApplyGraphicsSettings`] message instead,
+/// so that the change can be reverted.
+#[derive(Resource, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GraphicsSettings {
+    /// The physical resolution of the primary window.
+    pub resolution: UVec2,
+    /// The mode of the primary window.
+    pub mode: WindowMode,
+    /// The present mode of the primary window, controlling vsync.
+    pub present_mode: PresentMode,
+    /// The overall quality tier of the rendering features.
+    ///
+    /// What each tier means is up to the plugins reacting to it, e.g. through the
+    /// [`GraphicsQuality`] state.
+    pub quality: GraphicsQuality,
+    /// The graphics backend to render with.
+    ///
+    /// The backend is picked when the renderer is created, from the settings inserted before the
+    /// render plugin is added (e.g. loaded from a config file), so changing it only takes effect
+    /// once the application is restarted. See [`GraphicsSettings::requires_restart`].
+    pub backend: GraphicsBackend,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self::from_window(&Window::default())
+    }
+}
+
+impl GraphicsSettings {
+    /// Creates settings matching the current configuration of `window`.
+    pub fn from_window(window: &Window) -> Self {
+        Self {
+            resolution: window.physical_size(),
+            mode: window.mode,
+            present_mode: window.present_mode,
+            quality: GraphicsQuality::default(),
+            backend: GraphicsBackend::default(),
+        }
+    }
+
+    /// Returns `true` if switching from `self` to `other` only takes full effect after restarting
+    /// the application.
+    pub fn requires_restart(&self, other: &GraphicsSettings) -> bool {
+        self.backend != other.backend
+    }
+
+    /// Writes the window related settings to `window`, only touching the fields that differ.
+    pub fn apply_to_window(&self, window: &mut Window) {
+        if window.physical_size() != self.resolution {
+            window
+                .resolution
+                .set_physical_resolution(self.resolution.x, self.resolution.y);
+        }
+        if window.mode != self.mode {
+            window.mode = self.mode;
+        }
+        if window.present_mode != self.present_mode {
+            window.present_mode = self.present_mode;
+        }
+    }
+}
+
+/// The overall quality tier of the rendering features, used by [`GraphicsSettings`].
+///
+/// With the `bevy_state` feature, this is also a state kept in sync with the
+/// [`GraphicsSettings`] by the [`GraphicsSettingsPlugin`], including when they are reverted.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "bevy_state", derive(bevy_state::state::States))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GraphicsQuality {
+    /// The lowest quality, for low-end hardware.
+    Low,
+    /// A balance between quality and performance.
+    Medium,
+    /// High quality.
+    #[default]
+    High,
+    /// The highest quality, for high-end hardware.
+    Ultra,
+}
+
+/// The graphics backend preferred by [`GraphicsSettings`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum GraphicsBackend {
+    /// Let the renderer pick the best backend for the platform.
+    #[default]
+    Auto,
+    /// Vulkan.
+    Vulkan,
+    /// DirectX 12.
+    Dx12,
+    /// Metal.
+    Metal,
+    /// OpenGL, OpenGL ES or WebGL 2.
+    Gl,
+    /// WebGPU in the browser.
+    BrowserWebGpu,
+}
+
+/// Requests new [`GraphicsSettings`] to be applied.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct ApplyGraphicsSettings {
+    /// The settings to apply.
+    pub settings: GraphicsSettings,
+    /// Whether the settings must be confirmed with a [`ConfirmGraphicsSettings`] message before the
+    /// [`GraphicsSettingsPlugin::confirmation_timeout`] elapses, or be reverted.
+    ///
+    /// Settings that don't require confirmation are still reverted if a
+    /// [`GraphicsSettingsFailed`] message is sent in the frame after they were applied.
+    pub require_confirmation: bool,
+}
+
+/// Keeps the [`GraphicsSettings`] that are awaiting confirmation.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConfirmGraphicsSettings;
+
+/// Restores the [`GraphicsSettings`] that were active before the ones awaiting confirmation.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RevertGraphicsSettings;
+
+/// Reports that the current [`GraphicsSettings`] couldn't be applied, reverting them if they are
+/// still awaiting confirmation.
+///
+/// This is sent by the plugins reconfiguring themselves in response to new settings.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct GraphicsSettingsFailed {
+    /// A description of what went wrong.
+    pub reason: String,
+}
+
+/// Reports the progress of [`GraphicsSettings`] transactions, e.g. to show or hide a confirmation
+/// dialog.
+#[derive(Message, Debug, Clone, PartialEq)]
+pub enum GraphicsSettingsEvent {
+    /// New settings were applied.
+    Applied {
+        /// Whether the settings must be confirmed with a [`ConfirmGraphicsSettings`] message.
+        awaiting_confirmation: bool,
+        /// Whether some of the settings only take effect after a restart.
+        /// See [`GraphicsSettings::requires_restart`].
+        requires_restart: bool,
+    },
+    /// The applied settings were kept.
+    Confirmed,
+    /// The applied settings were reverted to the previous ones.
+    Reverted(GraphicsSettingsRevertReason),
+}
+
+/// Why [`GraphicsSettings`] were reverted, see [`GraphicsSettingsEvent::Reverted`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphicsSettingsRevertReason {
+    /// A [`RevertGraphicsSettings`] message was sent.
+    Requested,
+    /// The settings weren't confirmed before [`GraphicsSettingsPlugin::confirmation_timeout`].
+    TimedOut,
+    /// A [`GraphicsSettingsFailed`] message was sent.
+    Failed(String),
+}
+
+/// Tracks the [`GraphicsSettings`] transaction in progress, if any.
+#[derive(Resource, Debug)]
+pub struct GraphicsSettingsTransaction {
+    /// See [`GraphicsSettingsPlugin::confirmation_timeout`].
+    pub confirmation_timeout: Duration,
+    pending: Option<PendingGraphicsSettings>,
+}
+
+#[derive(Debug)]
+struct PendingGraphicsSettings {
+    previous: GraphicsSettings,
+    /// When the settings are reverted, or `None` if they are kept once no failure was reported.
+    deadline: Option<Instant>,
+}
+
+impl GraphicsSettingsTransaction {
+    /// Returns `true` if the current settings are waiting for a [`ConfirmGraphicsSettings`] message.
+    pub fn is_awaiting_confirmation(&self) -> bool {
+        self.pending
+            .as_ref()
+            .is_some_and(|pending| pending.deadline.is_some())
+    }
+
+    /// Returns the time left to confirm the current settings before they are reverted, if they are
+    /// awaiting confirmation.
+    pub fn remaining_confirmation_time(&self) -> Option<Duration> {
+        let deadline = self.pending.as_ref()?.deadline?;
+        Some(deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Returns the settings that are restored if the current ones are reverted.
+    pub fn previous(&self) -> Option<&GraphicsSettings> {
+        self.pending.as_ref().map(|pending| &pending.previous)
+    }
+}
+
+/// Handles the [`ApplyGraphicsSettings`], [`ConfirmGraphicsSettings`], [`RevertGraphicsSettings`]
+/// and [`GraphicsSettingsFailed`] messages, and reverts unconfirmed settings once they time out.
+///
+/// This system is added by the [`GraphicsSettingsPlugin`].
+pub fn update_graphics_settings_transaction(
+    mut settings: ResMut<GraphicsSettings>,
+    mut transaction: ResMut<GraphicsSettingsTransaction>,
+    mut apply: MessageReader<ApplyGraphicsSettings>,
+    mut confirm: MessageReader<ConfirmGraphicsSettings>,
+    mut revert: MessageReader<RevertGraphicsSettings>,
+    mut failed: MessageReader<GraphicsSettingsFailed>,
+    mut events: MessageWriter<GraphicsSettingsEvent>,
+) {
+    let now = Instant::now();
+    let transaction = &mut *transaction;
+
+    // Resolve the pending transaction first, as failures refer to the settings applied previously.
+    if let Some(pending) = transaction.pending.take() {
+        let reason = if let Some(failure) = failed.read().last() {
+            Some(GraphicsSettingsRevertReason::Failed(failure.reason.clone()))
+        } else if revert.read().last().is_some() {
+            Some(GraphicsSettingsRevertReason::Requested)
+        } else if pending.deadline.is_some_and(|deadline| now >= deadline) {
+            Some(GraphicsSettingsRevertReason::TimedOut)
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            warn!("Reverting graphics settings: {reason:?}");
+            *settings = pending.previous;
+            events.write(GraphicsSettingsEvent::Reverted(reason));
+        } else if pending.deadline.is_none() || confirm.read().last().is_some() {
+            events.write(GraphicsSettingsEvent::Confirmed);
+        } else {
+            transaction.pending = Some(pending);
+        }
+    } else {
+        for failure in failed.read() {
+            warn!(
+                "Graphics settings failed to apply, but there are no previous settings to revert to: {}",
+                failure.reason
+            );
+        }
+    }
+    confirm.clear();
+    revert.clear();
+
+    if let Some(request) = apply.read().last() {
+        let previous = match transaction.pending.take() {
+            // Chained changes revert to the last confirmed settings.
+            Some(pending) => pending.previous,
+            None => settings.clone(),
+        };
+        let requires_restart = previous.requires_restart(&request.settings);
+        info!("Applying graphics settings: {:?}", request.settings);
+        *settings = request.settings.clone();
+        transaction.pending = Some(PendingGraphicsSettings {
+            previous,
+            deadline: request
+                .require_confirmation
+                .then(|| now + transaction.confirmation_timeout),
+        });
+        events.write(GraphicsSettingsEvent::Applied {
+            awaiting_confirmation: request.require_confirmation,
+            requires_restart,
+        });
+    }
+}
+
+/// Transitions the [`GraphicsQuality`] state to the quality of the [`GraphicsSettings`].
+///
+/// This system is added by the [`GraphicsSettingsPlugin`] with the `bevy_state` feature.
+#[cfg(feature = "bevy_state")]
+pub fn update_graphics_quality_state(
+    settings: Res<GraphicsSettings>,
+    quality: Res<bevy_state::state::State<GraphicsQuality>>,
+    mut next_quality: ResMut<bevy_state::state::NextState<GraphicsQuality>>,
+) {
+    if settings.is_changed() && *quality.get() != settings.quality {
+        next_quality.set(settings.quality);
+    }
+}
+
+/// Applies the window related [`GraphicsSettings`] to the [`PrimaryWindow`].
+///
+/// Settings the window can't use are not applied, and a [`GraphicsSettingsFailed`] message is
+/// sent instead, which reverts them.
+///
+/// This system is added by the [`GraphicsSettingsPlugin`].
+pub fn apply_graphics_settings_to_primary_window(
+    settings: Res<GraphicsSettings>,
+    mut primary_window: Query<(&mut Window, Option<&SupportedPresentModes>), With<PrimaryWindow>>,
+    monitors: Query<(&Monitor, Has<PrimaryMonitor>)>,
+    mut failed: MessageWriter<GraphicsSettingsFailed>,
+) {
+    let Ok((mut window, supported_present_modes)) = primary_window.single_mut() else {
+        failed.write(GraphicsSettingsFailed {
+            reason: "there is no primary window to apply the graphics settings to".into(),
+        });
+        return;
+    };
+
+    // The supported present modes are only known once the window surface was created.
+    if let Some(supported_present_modes) = supported_present_modes
+        && !supported_present_modes.contains(settings.present_mode)
+    {
+        failed.write(GraphicsSettingsFailed {
+            reason: format!(
+                "the present mode {:?} is not supported by the primary window",
+                settings.present_mode
+            ),
+        });
+        return;
+    }
+
+    if let WindowMode::Fullscreen(selection, VideoModeSelection::Specific(video_mode)) =
+        settings.mode
+    {
+        // The current monitor of the window, and the order of the monitors of the windowing
+        // backend, aren't known here, so the video modes of those monitors can't be checked.
+        let monitor = match selection {
+            MonitorSelection::Current | MonitorSelection::Index(_) => None,
+            MonitorSelection::Primary => monitors.iter().find(|(_, primary)| *primary),
+            MonitorSelection::Entity(entity) => monitors.get(entity).ok(),
+        };
+        if let Some((monitor, _)) = monitor
+            && !monitor.video_modes.contains(&video_mode)
+        {
+            failed.write(GraphicsSettingsFailed {
+                reason: format!("the video mode {video_mode:?} is not supported by the monitor"),
+            });
+            return;
+        }
+    }
+
+    settings.apply_to_window(&mut window);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VideoMode;
+    use alloc::vec;
+
+    fn setup(confirmation_timeout: Duration) -> App {
+        let mut app = App::new();
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        app.add_plugins(GraphicsSettingsPlugin {
+            confirmation_timeout,
+        });
+        app.update();
+        app
+    }
+
+    fn apply(app: &mut App, require_confirmation: bool) -> GraphicsSettings {
+        let settings = GraphicsSettings {
+            resolution: UVec2::new(1920, 1080),
+            present_mode: PresentMode::Immediate,
+            ..app.world().resource::<GraphicsSettings>().clone()
+        };
+        app.world_mut().write_message(ApplyGraphicsSettings {
+            settings: settings.clone(),
+            require_confirmation,
+        });
+        app.update();
+        settings
+    }
+
+    fn primary_window(app: &mut App) -> Window {
+        app.world_mut()
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .single(app.world())
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn confirm_keeps_settings() {
+        let mut app = setup(Duration::from_secs(60));
+        let settings = apply(&mut app, true);
+        assert_eq!(
+            primary_window(&mut app).present_mode,
+            PresentMode::Immediate
+        );
+        assert!(app
+            .world()
+            .resource::<GraphicsSettingsTransaction>()
+            .is_awaiting_confirmation());
+
+        app.world_mut().write_message(ConfirmGraphicsSettings);
+        app.update();
+        assert!(!app
+            .world()
+            .resource::<GraphicsSettingsTransaction>()
+            .is_awaiting_confirmation());
+        assert_eq!(*app.world().resource::<GraphicsSettings>(), settings);
+        assert_eq!(
+            primary_window(&mut app).physical_size(),
+            settings.resolution
+        );
+    }
+
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn quality_state_follows_settings() {
+        use bevy_state::{app::StatesPlugin, state::State};
+
+        let mut app = App::new();
+        app.world_mut().spawn((Window::default(), PrimaryWindow));
+        // The states plugin can be added after the graphics settings plugin.
+        app.add_plugins((GraphicsSettingsPlugin::default(), StatesPlugin));
+        app.finish();
+        app.cleanup();
+        app.update();
+        let quality = |app: &App| *app.world().resource::<State<GraphicsQuality>>().get();
+        assert_eq!(quality(&app), GraphicsQuality::High);
+
+        let settings = GraphicsSettings {
+            quality: GraphicsQuality::Low,
+            ..app.world().resource::<GraphicsSettings>().clone()
+        };
+        app.world_mut().write_message(ApplyGraphicsSettings {
+            settings,
+            require_confirmation: true,
+        });
+        app.update();
+        app.update();
+        assert_eq!(quality(&app), GraphicsQuality::Low);
+
+        app.world_mut().write_message(RevertGraphicsSettings);
+        app.update();
+        app.update();
+        assert_eq!(quality(&app), GraphicsQuality::High);
+    }
+
+    #[test]
+    fn revert_on_timeout() {
+        let mut app = setup(Duration::ZERO);
+        let previous = app.world().resource::<GraphicsSettings>().clone();
+        apply(&mut app, true);
+
+        app.update();
+        assert_eq!(*app.world().resource::<GraphicsSettings>(), previous);
+        assert_eq!(primary_window(&mut app).present_mode, previous.present_mode);
+    }
+
+    #[test]
+    fn revert_on_failure() {
+        let mut app = setup(Duration::from_secs(60));
+        let previous = app.world().resource::<GraphicsSettings>().clone();
+        apply(&mut app, false);
+
+        app.world_mut().write_message(GraphicsSettingsFailed {
+            reason: "unsupported".into(),
+        });
+        app.update();
+        assert_eq!(*app.world().resource::<GraphicsSettings>(), previous);
+    }
+
+    #[test]
+    fn revert_unsupported_present_mode() {
+        let mut app = setup(Duration::from_secs(60));
+        let window = app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .single(app.world())
+            .unwrap();
+        app.world_mut()
+            .entity_mut(window)
+            .insert(SupportedPresentModes(vec![PresentMode::Fifo]));
+        let previous = app.world().resource::<GraphicsSettings>().clone();
+
+        apply(&mut app, false);
+        assert_eq!(primary_window(&mut app).present_mode, previous.present_mode);
+        app.update();
+        assert_eq!(*app.world().resource::<GraphicsSettings>(), previous);
+    }
+
+    #[test]
+    fn revert_unsupported_video_mode() {
+        let mut app = setup(Duration::from_secs(60));
+        let video_mode = VideoMode {
+            physical_size: UVec2::new(1920, 1080),
+            bit_depth: 32,
+            refresh_rate_millihertz: 60_000,
+        };
+        app.world_mut().spawn((
+            Monitor {
+                name: None,
+                physical_height: 1080,
+                physical_width: 1920,
+                physical_position: Default::default(),
+                refresh_rate_millihertz: Some(60_000),
+                scale_factor: 1.0,
+                video_modes: vec![video_mode],
+            },
+            PrimaryMonitor,
+        ));
+        let previous = app.world().resource::<GraphicsSettings>().clone();
+
+        app.world_mut().write_message(ApplyGraphicsSettings {
+            settings: GraphicsSettings {
+                mode: WindowMode::Fullscreen(
+                    MonitorSelection::Primary,
+                    VideoModeSelection::Specific(VideoMode {
+                        refresh_rate_millihertz: 240_000,
+                        ..video_mode
+                    }),
+                ),
+                ..previous.clone()
+            },
+            require_confirmation: false,
+        });
+        app.update();
+        assert_eq!(primary_window(&mut app).mode, WindowMode::Windowed);
+        app.update();
+        assert_eq!(*app.world().resource::<GraphicsSettings>(), previous);
+    }
+}
@@ -17,19 +17,27 @@ extern crate std;
 
 extern crate alloc;
 
+mod backend;
 mod cursor;
 mod event;
+mod graphics_settings;
 mod monitor;
+mod power_saving;
 mod raw_handle;
 mod system;
+mod virtual_backend;
 mod window;
 
 pub use crate::raw_handle::*;
 
+pub use backend::*;
 pub use cursor::*;
 pub use event::*;
+pub use graphics_settings::*;
 pub use monitor::*;
+pub use power_saving::*;
 pub use system::*;
+pub use virtual_backend::*;
 pub use window::*;
 
 /// The windowing prelude.
@@ -38,9 +46,10 @@ pub use window::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MonitorSelection,
-        VideoModeSelection, Window, WindowMoved, WindowPlugin, WindowPosition,
-        WindowResizeConstraints,
+        ApplyGraphicsSettings, ConfirmGraphicsSettings, CursorEntered, CursorLeft, CursorMoved,
+        FileDragAndDrop, GraphicsSettings, GraphicsSettingsPlugin, Ime, MonitorSelection,
+        RevertGraphicsSettings, VideoModeSelection, Window, WindowMoved, WindowPlugin,
+        WindowPosition, WindowResizeConstraints,
     };
 }
 
@@ -117,6 +126,7 @@ impl Plugin for WindowPlugin {
             .add_message::<Ime>()
             .add_message::<WindowFocused>()
             .add_message::<WindowOccluded>()
+            .add_message::<OnScreenKeyboardChanged>()
             .add_message::<WindowScaleFactorChanged>()
             .add_message::<WindowBackendScaleFactorChanged>()
             .add_message::<FileDragAndDrop>()
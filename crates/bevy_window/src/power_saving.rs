@@ -0,0 +1,447 @@
+use core::time::Duration;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_platform::time::Instant;
+use log::debug;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::prelude::ReflectResource,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+use crate::{Window, WindowRenderRate};
+
+/// A [`Plugin`] that lowers the frame rate of the windows when the system saves power, e.g. on
+/// the battery of a laptop or handheld, following the [`PowerSavingPolicy`].
+///
+/// The power state of the system is polled into the [`PowerState`] resource every
+/// [`poll_interval`](Self::poll_interval), on the platforms that report it (currently Windows,
+/// including UWP, and Linux). The app can also update [`PowerState`] itself, e.g. from the
+/// thermal notifications of a platform SDK.
+///
+/// The limits matching the current power state are combined into the [`ActivePowerSaving`]
+/// resource. Its frame rate is applied to every [`Window`] as a [`WindowRenderRate`]. The app can
+/// still change the render rate of a window while it is limited: the new rate is restored once the
+/// power state allows it. The [`resolution_scale`](PowerSavingLimits::resolution_scale) of the
+/// limits is left to the rendering code of the app, e.g. to lower the render resolution of an
+/// upscaler, as Bevy can't render a window below its resolution on its own.
+pub struct PowerSavingPlugin {
+    /// How often the power state of the system is polled.
+    ///
+    /// Defaults to 5 seconds.
+    pub poll_interval: Duration,
+}
+
+impl Default for PowerSavingPlugin {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Plugin for PowerSavingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerState>()
+            .init_resource::<PowerSavingPolicy>()
+            .init_resource::<ActivePowerSaving>()
+            .insert_resource(PowerStatePolling {
+                interval: self.poll_interval,
+                last_poll: None,
+                reported_thermal_throttled: false,
+            })
+            .add_systems(
+                PostUpdate,
+                (
+                    poll_power_state,
+                    update_active_power_saving.run_if(
+                        resource_changed::<PowerState>
+                            .or_else(resource_changed::<PowerSavingPolicy>),
+                    ),
+                    apply_power_saving_render_rate.run_if(
+                        resource_changed::<ActivePowerSaving>
+                            .or_else(any_match_filter::<Added<Window>>)
+                            .or_else(any_match_filter::<Changed<WindowRenderRate>>)
+                            .or_else(any_component_removed::<WindowRenderRate>),
+                    ),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// The power state of the system, used by the [`PowerSavingPlugin`].
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+pub struct PowerState {
+    /// Whether the system runs on battery, instead of being plugged in.
+    pub on_battery: bool,
+    /// The charge of the battery, from `0.0` to `1.0`, if the system has one.
+    pub battery_level: Option<f32>,
+    /// Whether the user enabled the battery saver, or a low power profile.
+    pub battery_saver: bool,
+    /// Whether the system slows down to cool down.
+    ///
+    /// This is reported on Windows and Linux, see
+    /// [`PowerStatus::thermal_throttled`](bevy_platform::power::PowerStatus::thermal_throttled).
+    /// The app can also set it, e.g. from the thermal notifications of a platform SDK, and it
+    /// stays set until the app clears it.
+    pub thermal_throttled: bool,
+}
+
+/// The limits applied by the [`PowerSavingPlugin`] in each power state.
+///
+/// When several states apply, e.g. running on battery with the battery saver on, the strictest
+/// limits of each are used.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+pub struct PowerSavingPolicy {
+    /// The limits when [`PowerState::on_battery`] is set.
+    ///
+    /// Defaults to 60 frames per second.
+    pub on_battery: PowerSavingLimits,
+    /// The limits when [`PowerState::battery_saver`] is set.
+    ///
+    /// Defaults to 30 frames per second, at 75% of the resolution.
+    pub battery_saver: PowerSavingLimits,
+    /// The limits when [`PowerState::thermal_throttled`] is set.
+    ///
+    /// Defaults to 30 frames per second, at 75% of the resolution.
+    pub thermal_throttled: PowerSavingLimits,
+}
+
+impl Default for PowerSavingPolicy {
+    fn default() -> Self {
+        Self {
+            on_battery: PowerSavingLimits {
+                max_frame_rate: Some(60.0),
+                resolution_scale: 1.0,
+            },
+            battery_saver: PowerSavingLimits {
+                max_frame_rate: Some(30.0),
+                resolution_scale: 0.75,
+            },
+            thermal_throttled: PowerSavingLimits {
+                max_frame_rate: Some(30.0),
+                resolution_scale: 0.75,
+            },
+        }
+    }
+}
+
+impl PowerSavingPolicy {
+    /// Returns the limits to apply in the power `state`.
+    pub fn limits(&self, state: &PowerState) -> PowerSavingLimits {
+        [
+            (state.on_battery, self.on_battery),
+            (state.battery_saver, self.battery_saver),
+            (state.thermal_throttled, self.thermal_throttled),
+        ]
+        .into_iter()
+        .filter(|(active, _)| *active)
+        .fold(PowerSavingLimits::NONE, |limits, (_, other)| {
+            limits.min(other)
+        })
+    }
+}
+
+/// Limits on the rendering of the app to save power, see [`PowerSavingPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Default, Clone)
+)]
+pub struct PowerSavingLimits {
+    /// The maximum number of frames per second the windows are rendered at, or `None` to not
+    /// limit it. Rates that aren't positive don't limit it either.
+    pub max_frame_rate: Option<f64>,
+    /// The scale of the render resolution, from `0.0` to `1.0`.
+    ///
+    /// This isn't applied by the [`PowerSavingPlugin`] itself, as how to render at a lower
+    /// resolution depends on the app.
+    pub resolution_scale: f32,
+}
+
+impl Default for PowerSavingLimits {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl PowerSavingLimits {
+    /// No limits.
+    pub const NONE: Self = Self {
+        max_frame_rate: None,
+        resolution_scale: 1.0,
+    };
+
+    /// Returns the maximum frame rate, if it limits the rendering.
+    fn frame_rate_limit(&self) -> Option<f64> {
+        self.max_frame_rate.filter(|rate| *rate > 0.0)
+    }
+
+    /// Returns the strictest limits of `self` and `other`.
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            max_frame_rate: match (self.frame_rate_limit(), other.frame_rate_limit()) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+            resolution_scale: self.resolution_scale.min(other.resolution_scale),
+        }
+    }
+}
+
+/// The [`PowerSavingLimits`] currently applied by the [`PowerSavingPlugin`].
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+pub struct ActivePowerSaving(pub PowerSavingLimits);
+
+#[derive(Resource)]
+struct PowerStatePolling {
+    interval: Duration,
+    last_poll: Option<Instant>,
+    /// The thermal state reported by the last poll, to tell it apart from the one set by the app.
+    reported_thermal_throttled: bool,
+}
+
+/// Keeps the [`WindowRenderRate`] a window had before the [`PowerSavingPlugin`] limited it.
+#[derive(Component)]
+struct PowerSavingRenderRate {
+    previous: Option<WindowRenderRate>,
+    /// The rate applied by the plugin, to detect the app changing the rate of the window.
+    applied: WindowRenderRate,
+}
+
+#[cfg_attr(
+    not(feature = "std"),
+    expect(
+        unused_variables,
+        unused_mut,
+        reason = "the power state is only polled with std"
+    )
+)]
+fn poll_power_state(mut polling: ResMut<PowerStatePolling>, mut state: ResMut<PowerState>) {
+    let now = Instant::now();
+    if polling
+        .last_poll
+        .is_some_and(|last_poll| now.saturating_duration_since(last_poll) < polling.interval)
+    {
+        return;
+    }
+    polling.last_poll = Some(now);
+
+    #[cfg(feature = "std")]
+    if let Some(status) = bevy_platform::power::power_status() {
+        // Keep the thermal state set by the app, but not the one reported by the previous poll.
+        let set_by_app = state.thermal_throttled && !polling.reported_thermal_throttled;
+        polling.reported_thermal_throttled = status.thermal_throttled;
+        state.set_if_neq(PowerState {
+            on_battery: status.on_battery,
+            battery_level: status.battery_level,
+            battery_saver: status.battery_saver,
+            thermal_throttled: set_by_app || status.thermal_throttled,
+        });
+    }
+}
+
+fn update_active_power_saving(
+    state: Res<PowerState>,
+    policy: Res<PowerSavingPolicy>,
+    mut active: ResMut<ActivePowerSaving>,
+) {
+    let limits = policy.limits(&state);
+    if active.0 != limits {
+        debug!("Power saving limits changed to {limits:?} for {:?}", *state);
+        active.0 = limits;
+    }
+}
+
+fn apply_power_saving_render_rate(
+    mut commands: Commands,
+    active: Res<ActivePowerSaving>,
+    windows: Query<
+        (
+            Entity,
+            Option<&WindowRenderRate>,
+            Option<&PowerSavingRenderRate>,
+        ),
+        With<Window>,
+    >,
+) {
+    let limit = active.0.frame_rate_limit().map(WindowRenderRate::from_hz);
+    for (entity, render_rate, saved) in &windows {
+        let mut window = commands.entity(entity);
+        // The rate of the window if it wasn't limited, which is the current one unless the plugin
+        // applied it.
+        let previous = match saved {
+            Some(saved) if render_rate == Some(&saved.applied) => saved.previous,
+            _ => render_rate.copied(),
+        };
+        match limit {
+            Some(limit) => {
+                // Windows already rendered less often keep their own rate.
+                let applied = match previous {
+                    Some(previous) if previous.interval > limit.interval => previous,
+                    _ => limit,
+                };
+                if render_rate != Some(&applied) {
+                    window.insert(applied);
+                }
+                if saved.is_none_or(|saved| saved.previous != previous || saved.applied != applied)
+                {
+                    window.insert(PowerSavingRenderRate { previous, applied });
+                }
+            }
+            None if saved.is_some() => {
+                if previous.as_ref() != render_rate {
+                    match previous {
+                        Some(previous) => window.insert(previous),
+                        None => window.remove::<WindowRenderRate>(),
+                    };
+                }
+                window.remove::<PowerSavingRenderRate>();
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_combine_active_states() {
+        let policy = PowerSavingPolicy::default();
+        assert_eq!(
+            policy.limits(&PowerState::default()),
+            PowerSavingLimits::NONE
+        );
+
+        let limits = policy.limits(&PowerState {
+            on_battery: true,
+            battery_saver: true,
+            ..Default::default()
+        });
+        assert_eq!(limits.max_frame_rate, Some(30.0));
+        assert_eq!(limits.resolution_scale, 0.75);
+    }
+
+    #[test]
+    fn render_rate_is_restored() {
+        let mut app = App::new();
+        app.add_plugins(PowerSavingPlugin {
+            poll_interval: Duration::MAX,
+        });
+        let capped = app.world_mut().spawn(Window::default()).id();
+        let slow = app
+            .world_mut()
+            .spawn((Window::default(), WindowRenderRate::from_hz(10.0)))
+            .id();
+
+        // Poll the power state of the system once, so that it doesn't override the test states.
+        app.update();
+
+        *app.world_mut().resource_mut::<PowerState>() = PowerState {
+            battery_saver: true,
+            ..Default::default()
+        };
+        app.update();
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(capped),
+            Some(&WindowRenderRate::from_hz(30.0))
+        );
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(slow),
+            Some(&WindowRenderRate::from_hz(10.0))
+        );
+
+        *app.world_mut().resource_mut::<PowerState>() = PowerState::default();
+        app.update();
+        assert!(app.world().get::<WindowRenderRate>(capped).is_none());
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(slow),
+            Some(&WindowRenderRate::from_hz(10.0))
+        );
+    }
+
+    #[test]
+    fn render_rate_changed_while_limited_is_restored() {
+        let mut app = App::new();
+        app.add_plugins(PowerSavingPlugin {
+            poll_interval: Duration::MAX,
+        });
+        let window = app.world_mut().spawn(Window::default()).id();
+        app.update();
+
+        *app.world_mut().resource_mut::<PowerState>() = PowerState {
+            battery_saver: true,
+            ..Default::default()
+        };
+        app.update();
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(window),
+            Some(&WindowRenderRate::from_hz(30.0))
+        );
+
+        // The rates set by the app while limited are kept if they are slower than the limit.
+        app.world_mut()
+            .entity_mut(window)
+            .insert(WindowRenderRate::from_hz(20.0));
+        app.update();
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(window),
+            Some(&WindowRenderRate::from_hz(20.0))
+        );
+        app.world_mut()
+            .entity_mut(window)
+            .insert(WindowRenderRate::from_hz(120.0));
+        app.update();
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(window),
+            Some(&WindowRenderRate::from_hz(30.0))
+        );
+
+        // Its rate is restored once the limit is lifted.
+        *app.world_mut().resource_mut::<PowerState>() = PowerState::default();
+        app.update();
+        assert_eq!(
+            app.world().get::<WindowRenderRate>(window),
+            Some(&WindowRenderRate::from_hz(120.0))
+        );
+    }
+
+    #[test]
+    fn non_positive_frame_rates_are_unlimited() {
+        assert_eq!(WindowRenderRate::from_hz(0.0).interval, Duration::ZERO);
+        assert_eq!(WindowRenderRate::from_hz(-1.0).interval, Duration::ZERO);
+        assert_eq!(WindowRenderRate::from_hz(f64::NAN).interval, Duration::ZERO);
+
+        let unlimited = PowerSavingLimits {
+            max_frame_rate: Some(0.0),
+            ..PowerSavingLimits::NONE
+        };
+        let capped = PowerSavingLimits {
+            max_frame_rate: Some(30.0),
+            ..PowerSavingLimits::NONE
+        };
+        assert_eq!(unlimited.min(capped).max_frame_rate, Some(30.0));
+        assert_eq!(unlimited.min(unlimited).max_frame_rate, None);
+    }
+}
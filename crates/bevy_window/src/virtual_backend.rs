@@ -0,0 +1,481 @@
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+
+use bevy_ecs::{entity::Entity, error::BevyError};
+use bevy_math::{IVec2, UVec2, Vec2};
+
+use crate::{
+    BackendMonitor, CursorLeft, CursorMoved, CursorOptions, Monitor, RawHandleWrapper, Window,
+    WindowBackend, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowEvent,
+    WindowFocused, WindowMoved, WindowPosition, WindowResized, WindowScaleFactorChanged,
+};
+
+/// A [`WindowBackend`] without any platform windows, for automated tests.
+///
+/// The windows only exist in memory, and change in response to the events of a scripted timeline,
+/// so window-lifecycle logic such as close vetoes, scale factor changes or platforms supporting a
+/// single window can be covered by `cargo test`.
+///
+/// Virtual windows don't have a [`RawHandleWrapper`], so they are marked as
+/// [`OffscreenWindow`](crate::OffscreenWindow)s and the renderer draws them to offscreen textures
+/// of their size. Cameras targeting them render as with a platform window, even with the headless
+/// renderer of a CI machine, and their frames can be read back with screenshots.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_window::{ScriptedWindowEvent, VirtualWindowBackend, WindowBackendPlugin, WindowPlugin};
+/// let backend = VirtualWindowBackend::default()
+///     .at_frame(1, ScriptedWindowEvent::Resize { width: 640, height: 480 })
+///     .at_frame(2, ScriptedWindowEvent::ScaleFactor(2.0))
+///     .at_frame(3, ScriptedWindowEvent::CloseRequested);
+///
+/// let mut app = App::new();
+/// app.insert_non_send(backend).add_plugins((
+///     WindowPlugin::default(),
+///     WindowBackendPlugin::<VirtualWindowBackend>::default(),
+/// ));
+/// for _ in 0..5 {
+///     app.update();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct VirtualWindowBackend {
+    /// The windows in creation order, with [`None`] for destroyed windows so that creation
+    /// indices stay stable.
+    windows: Vec<(Entity, Option<VirtualWindow>)>,
+    max_windows: Option<usize>,
+    monitors: Vec<BackendMonitor>,
+    script: Vec<ScriptedEntry>,
+    queued: VecDeque<WindowEvent>,
+    frame: u32,
+}
+
+#[derive(Debug)]
+struct ScriptedEntry {
+    frame: u32,
+    window: usize,
+    event: ScriptedWindowEvent,
+}
+
+/// An event of the timeline of a [`VirtualWindowBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptedWindowEvent {
+    /// The window is resized to the given physical size.
+    Resize {
+        /// The new physical width of the window.
+        width: u32,
+        /// The new physical height of the window.
+        height: u32,
+    },
+    /// The scale factor of the window changes, as when it is moved to another monitor.
+    ScaleFactor(f32),
+    /// The window gains or loses focus.
+    Focus(bool),
+    /// The window is moved to the given physical position.
+    Move(IVec2),
+    /// The user asks to close the window, as when pressing its close button.
+    CloseRequested,
+    /// The cursor moves to the given logical position in the window.
+    CursorMoved(Vec2),
+    /// The cursor leaves the window.
+    CursorLeft,
+    /// Any other event, such as an input event, sent as is.
+    ///
+    /// The event should target the [`Window`] entity it is scripted for, as returned by
+    /// [`VirtualWindowBackend::window_entity`].
+    Event(WindowEvent),
+}
+
+/// The state of a window of a [`VirtualWindowBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualWindow {
+    /// The title of the window.
+    pub title: String,
+    /// The physical size of the window.
+    pub physical_size: UVec2,
+    /// The scale factor reported for the window, before any override.
+    pub scale_factor: f32,
+    /// The scale factor override of the window.
+    pub scale_factor_override: Option<f32>,
+    /// The physical position of the window.
+    pub position: IVec2,
+    /// Whether the window is focused.
+    pub focused: bool,
+    /// The number of times the window was updated by the app after its creation.
+    pub updates: usize,
+}
+
+impl VirtualWindow {
+    fn effective_scale_factor(&self) -> f32 {
+        self.scale_factor_override.unwrap_or(self.scale_factor)
+    }
+
+    fn logical_size(&self) -> Vec2 {
+        self.physical_size.as_vec2() / self.effective_scale_factor()
+    }
+}
+
+impl VirtualWindowBackend {
+    /// Schedules `event` for the first window created, on the given `frame`.
+    ///
+    /// Frames are counted from `0`, the first update of the app. Events scheduled before the
+    /// window is created are sent once it exists.
+    pub fn at_frame(self, frame: u32, event: ScriptedWindowEvent) -> Self {
+        self.at_frame_for_window(frame, 0, event)
+    }
+
+    /// Schedules `event` for the window with the given creation index, on the given `frame`.
+    ///
+    /// Windows are indexed in creation order, starting from `0`, including the windows that were
+    /// destroyed since.
+    pub fn at_frame_for_window(
+        mut self,
+        frame: u32,
+        window: usize,
+        event: ScriptedWindowEvent,
+    ) -> Self {
+        self.script.push(ScriptedEntry {
+            frame,
+            window,
+            event,
+        });
+        self
+    }
+
+    /// Limits the number of windows open at once, to simulate platforms that only support a single
+    /// window. Creating more windows fails.
+    pub fn with_max_windows(mut self, max_windows: usize) -> Self {
+        self.max_windows = Some(max_windows);
+        self
+    }
+
+    /// Adds a monitor, which is the primary monitor if it is the first one.
+    pub fn with_monitor(mut self, monitor: Monitor) -> Self {
+        self.monitors.push(BackendMonitor {
+            id: self.monitors.len() as u64,
+            monitor,
+            primary: self.monitors.is_empty(),
+        });
+        self
+    }
+
+    /// Sends `event` on the next frame.
+    pub fn send(&mut self, event: impl Into<WindowEvent>) {
+        self.queued.push_back(event.into());
+    }
+
+    /// Returns the number of frames pumped so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Returns the entity of the window with the given creation index.
+    pub fn window_entity(&self, window: usize) -> Option<Entity> {
+        self.windows.get(window).map(|(entity, _)| *entity)
+    }
+
+    /// Returns the state of the open window of `entity`.
+    pub fn window(&self, entity: Entity) -> Option<&VirtualWindow> {
+        self.windows
+            .iter()
+            .find(|(window, _)| *window == entity)
+            .and_then(|(_, state)| state.as_ref())
+    }
+
+    /// Returns the number of open windows.
+    pub fn open_windows(&self) -> usize {
+        self.windows
+            .iter()
+            .filter(|(_, state)| state.is_some())
+            .count()
+    }
+
+    fn window_mut(&mut self, entity: Entity) -> Option<&mut VirtualWindow> {
+        self.windows
+            .iter_mut()
+            .find(|(window, _)| *window == entity)
+            .and_then(|(_, state)| state.as_mut())
+    }
+
+    fn script_event(
+        state: &mut VirtualWindow,
+        entity: Entity,
+        event: ScriptedWindowEvent,
+        events: &mut Vec<WindowEvent>,
+    ) {
+        match event {
+            ScriptedWindowEvent::Resize { width, height } => {
+                state.physical_size = UVec2::new(width, height);
+                events.push(resized(entity, state));
+            }
+            ScriptedWindowEvent::ScaleFactor(scale_factor) => {
+                let prior_factor = state.scale_factor;
+                state.scale_factor = scale_factor;
+                events.push(
+                    WindowBackendScaleFactorChanged {
+                        window: entity,
+                        scale_factor: scale_factor as f64,
+                    }
+                    .into(),
+                );
+                if state.scale_factor_override.is_none() && scale_factor != prior_factor {
+                    events.push(
+                        WindowScaleFactorChanged {
+                            window: entity,
+                            scale_factor: scale_factor as f64,
+                        }
+                        .into(),
+                    );
+                    events.push(resized(entity, state));
+                }
+            }
+            ScriptedWindowEvent::Focus(focused) => {
+                state.focused = focused;
+                events.push(
+                    WindowFocused {
+                        window: entity,
+                        focused,
+                    }
+                    .into(),
+                );
+            }
+            ScriptedWindowEvent::Move(position) => {
+                state.position = position;
+                events.push(
+                    WindowMoved {
+                        window: entity,
+                        position,
+                    }
+                    .into(),
+                );
+            }
+            ScriptedWindowEvent::CloseRequested => {
+                events.push(WindowCloseRequested { window: entity }.into());
+            }
+            ScriptedWindowEvent::CursorMoved(position) => {
+                events.push(
+                    CursorMoved {
+                        window: entity,
+                        position,
+                        delta: None,
+                    }
+                    .into(),
+                );
+            }
+            ScriptedWindowEvent::CursorLeft => {
+                events.push(CursorLeft { window: entity }.into());
+            }
+            ScriptedWindowEvent::Event(event) => events.push(event),
+        }
+    }
+}
+
+fn resized(entity: Entity, state: &VirtualWindow) -> WindowEvent {
+    let size = state.logical_size();
+    WindowResized {
+        window: entity,
+        width: size.x,
+        height: size.y,
+    }
+    .into()
+}
+
+impl WindowBackend for VirtualWindowBackend {
+    fn create_window(
+        &mut self,
+        entity: Entity,
+        window: &mut Window,
+        _cursor_options: Option<&CursorOptions>,
+    ) -> Result<Option<RawHandleWrapper>, BevyError> {
+        if let Some(max_windows) = self.max_windows
+            && self.open_windows() >= max_windows
+        {
+            return Err(alloc::format!(
+                "the virtual backend only supports {max_windows} window(s)"
+            )
+            .into());
+        }
+        let position = match window.position {
+            WindowPosition::At(position) => position,
+            _ => IVec2::ZERO,
+        };
+        self.windows.push((
+            entity,
+            Some(VirtualWindow {
+                title: window.title.clone(),
+                physical_size: window.physical_size(),
+                scale_factor: window.resolution.base_scale_factor(),
+                scale_factor_override: window.resolution.scale_factor_override(),
+                position,
+                focused: window.focused,
+                updates: 0,
+            }),
+        ));
+        Ok(None)
+    }
+
+    fn update_window(&mut self, entity: Entity, window: &Window) {
+        let Some(state) = self.window_mut(entity) else {
+            return;
+        };
+        state.title.clone_from(&window.title);
+        state.physical_size = window.physical_size();
+        state.scale_factor_override = window.resolution.scale_factor_override();
+        if let WindowPosition::At(position) = window.position {
+            state.position = position;
+        }
+        state.focused = window.focused;
+        state.updates += 1;
+    }
+
+    fn destroy_window(&mut self, entity: Entity) {
+        if let Some((_, state)) = self
+            .windows
+            .iter_mut()
+            .find(|(window, _)| *window == entity)
+        {
+            *state = None;
+        }
+    }
+
+    fn pump_events(&mut self, events: &mut Vec<WindowEvent>) {
+        let frame = self.frame;
+        self.frame += 1;
+
+        let mut index = 0;
+        while index < self.script.len() {
+            let entry = &self.script[index];
+            match self.windows.get_mut(entry.window) {
+                Some((entity, Some(state))) if entry.frame <= frame => {
+                    let entry = self.script.remove(index);
+                    Self::script_event(state, *entity, entry.event, events);
+                }
+                _ => index += 1,
+            }
+        }
+        events.extend(self.queued.drain(..));
+    }
+
+    fn monitors(&self) -> Vec<BackendMonitor> {
+        self.monitors.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExitCondition, WindowBackendPlugin, WindowClosed, WindowPlugin};
+    use bevy_app::App;
+    use bevy_ecs::message::Messages;
+
+    fn run(backend: VirtualWindowBackend, plugin: WindowPlugin, frames: usize) -> App {
+        let mut app = App::new();
+        app.insert_non_send(backend).add_plugins((
+            plugin,
+            WindowBackendPlugin::<VirtualWindowBackend>::default(),
+        ));
+        for _ in 0..frames {
+            app.update();
+        }
+        app
+    }
+
+    fn primary_window(app: &mut App) -> Entity {
+        app.world()
+            .non_send::<VirtualWindowBackend>()
+            .window_entity(0)
+            .unwrap()
+    }
+
+    #[test]
+    fn resize_and_scale_factor_change() {
+        let backend = VirtualWindowBackend::default()
+            .at_frame(
+                1,
+                ScriptedWindowEvent::Resize {
+                    width: 800,
+                    height: 600,
+                },
+            )
+            .at_frame(2, ScriptedWindowEvent::ScaleFactor(2.0));
+        let mut app = run(backend, WindowPlugin::default(), 2);
+        let entity = primary_window(&mut app);
+
+        let window = app.world().get::<Window>(entity).unwrap();
+        assert_eq!(window.physical_size(), UVec2::new(800, 600));
+        assert_eq!(window.size(), Vec2::new(800.0, 600.0));
+
+        app.update();
+        let window = app.world().get::<Window>(entity).unwrap();
+        assert_eq!(window.physical_size(), UVec2::new(800, 600));
+        assert_eq!(window.size(), Vec2::new(400.0, 300.0));
+        assert_eq!(
+            app.world()
+                .resource::<Messages<WindowScaleFactorChanged>>()
+                .len(),
+            1
+        );
+        let state = app
+            .world()
+            .non_send::<VirtualWindowBackend>()
+            .window(entity)
+            .unwrap();
+        assert_eq!(state.scale_factor, 2.0);
+        assert_eq!(state.physical_size, UVec2::new(800, 600));
+    }
+
+    #[test]
+    fn close_request_can_be_vetoed() {
+        let backend =
+            VirtualWindowBackend::default().at_frame(1, ScriptedWindowEvent::CloseRequested);
+        let vetoed = WindowPlugin {
+            close_when_requested: false,
+            exit_condition: ExitCondition::DontExit,
+            ..Default::default()
+        };
+        let mut app = run(backend, vetoed, 4);
+        assert_eq!(
+            app.world()
+                .non_send::<VirtualWindowBackend>()
+                .open_windows(),
+            1
+        );
+        assert!(app
+            .world_mut()
+            .query::<&Window>()
+            .single(app.world())
+            .is_ok());
+
+        let backend =
+            VirtualWindowBackend::default().at_frame(1, ScriptedWindowEvent::CloseRequested);
+        let closing = WindowPlugin {
+            exit_condition: ExitCondition::DontExit,
+            ..Default::default()
+        };
+        let mut app = run(backend, closing, 4);
+        assert_eq!(
+            app.world()
+                .non_send::<VirtualWindowBackend>()
+                .open_windows(),
+            0
+        );
+        assert!(app
+            .world_mut()
+            .query::<&Window>()
+            .iter(app.world())
+            .next()
+            .is_none());
+        assert_eq!(app.world().resource::<Messages<WindowClosed>>().len(), 1);
+    }
+
+    #[test]
+    fn single_window_constraint() {
+        let backend = VirtualWindowBackend::default().with_max_windows(1);
+        let mut app = run(backend, WindowPlugin::default(), 1);
+        let second = app.world_mut().spawn(Window::default()).id();
+        app.update();
+
+        let backend = app.world().non_send::<VirtualWindowBackend>();
+        assert_eq!(backend.open_windows(), 1);
+        assert!(backend.window(second).is_none());
+    }
+}
@@ -1,13 +1,13 @@
 #[cfg(feature = "std")]
 use alloc::format;
-use alloc::{borrow::ToOwned, string::String};
-use core::num::NonZero;
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{num::NonZero, time::Duration};
 
 use bevy_ecs::{
     entity::{ContainsEntity, Entity},
     prelude::Component,
 };
-use bevy_math::{CompassOctant, DVec2, IVec2, UVec2, Vec2};
+use bevy_math::{CompassOctant, DVec2, IVec2, Rect, UVec2, Vec2};
 use bevy_platform::sync::LazyLock;
 use log::warn;
 
@@ -163,6 +163,9 @@ impl ContainsEntity for NormalizedWindowRef {
 #[require(CursorOptions)]
 pub struct Window {
     /// What presentation mode to give the window.
+    ///
+    /// Changing this reconfigures the window's surface, without recreating the window. The modes
+    /// supported by the surface are listed in [`SupportedPresentModes`].
     pub present_mode: PresentMode,
     /// Which fullscreen or windowing mode should be used.
     pub mode: WindowMode,
@@ -188,7 +191,15 @@ pub struct Window {
     /// Notes: Changing this field during runtime will have no effect for now.
     pub name: Option<String>,
     /// How the alpha channel of textures should be handled while compositing.
+    ///
+    /// Each window has its own alpha mode, which can be changed at runtime. If the surface of the
+    /// window doesn't support it, [`CompositeAlphaMode::Auto`] is used instead.
     pub composite_alpha_mode: CompositeAlphaMode,
+    /// The dynamic range of the window's output, which selects a float swap chain that can
+    /// present colors brighter than the SDR white on HDR displays.
+    ///
+    /// Changing this at runtime recreates the window's surface.
+    pub dynamic_range: WindowDynamicRange,
     /// The limits of the window's logical size
     /// (found in its [`resolution`](WindowResolution)) when resizing.
     pub resize_constraints: WindowResizeConstraints,
@@ -283,6 +294,22 @@ pub struct Window {
     ///
     /// - iOS / Android / Web: Unsupported.
     pub ime_position: Vec2,
+    /// Should the on-screen keyboard of the system be shown for this window?
+    ///
+    /// Set this while a text field has the focus on devices without a physical keyboard. The
+    /// [`OnScreenKeyboardChanged`](crate::OnScreenKeyboardChanged) message reports the part of the
+    /// window the keyboard covers, to scroll the text field into view.
+    ///
+    /// The user can also dismiss the keyboard. Where that is reported, this is then reset to
+    /// `false`, so that setting it again shows the keyboard again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`Windows`**: Shows the touch keyboard.
+    /// - **`Android`**: Shows the soft keyboard, through the IME of the window. The part of the
+    ///   window it covers and its dismissal aren't reported.
+    /// - iOS / macOS / Linux / Web: Unsupported.
+    pub on_screen_keyboard: bool,
     /// Sets a specific theme for the window.
     ///
     /// If `None` is provided, the window will use the system theme.
@@ -323,7 +350,11 @@ pub struct Window {
     pub clip_children: bool,
     /// Optional hint given to the rendering API regarding the maximum number of queued frames admissible on the GPU.
     ///
-    /// Given values are usually within the 1-3 range. If not provided, this will default to 2.
+    /// Given values are usually within the 1-3 range. If not provided, this will default to the
+    /// renderer's setting, which is 2 unless configured otherwise. Lower values reduce input latency
+    /// at the cost of throughput.
+    ///
+    /// Changing this reconfigures the window's surface.
     ///
     /// See [`wgpu::SurfaceConfiguration::desired_maximum_frame_latency`].
     ///
@@ -488,9 +519,11 @@ impl Default for Window {
             resolution: Default::default(),
             internal: Default::default(),
             composite_alpha_mode: Default::default(),
+            dynamic_range: Default::default(),
             resize_constraints: Default::default(),
             ime_enabled: Default::default(),
             ime_position: Default::default(),
+            on_screen_keyboard: false,
             resizable: true,
             enabled_buttons: Default::default(),
             decorations: true,
@@ -777,6 +810,24 @@ pub struct CursorOptions {
     ///
     /// - iOS / Android / Web / X11: Unsupported.
     pub hit_test: bool,
+
+    /// A rectangle of the window the cursor is confined to while the window has the focus, in
+    /// logical pixels from the top-left corner of the window. For example, this keeps the cursor
+    /// in the window to scroll the map of a strategy game by pushing it against the edges.
+    ///
+    /// This takes precedence over [`CursorGrabMode::Confined`], and is ignored while the cursor is
+    /// [`CursorGrabMode::Locked`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`Windows`**: The cursor is confined by the system.
+    /// - **`X11`**: The cursor is confined to the window by the system, and moved back into the
+    ///   rectangle when it leaves it, so it can briefly be seen outside of the rectangle.
+    /// - **`macOS`**: The cursor is moved back into the rectangle when it leaves it, so it can
+    ///   briefly be seen outside of it, or even outside of the window when it is moved quickly.
+    /// - **`Wayland`**: The cursor is only confined to the window.
+    /// - **`Web`**, **`iOS`** and **`Android`**: Unsupported.
+    pub clip_rect: Option<Rect>,
 }
 
 impl Default for CursorOptions {
@@ -785,6 +836,7 @@ impl Default for CursorOptions {
             visible: true,
             grab_mode: CursorGrabMode::None,
             hit_test: true,
+            clip_rect: None,
         }
     }
 }
@@ -1199,7 +1251,8 @@ pub enum VideoModeSelection {
 ///
 /// [`AutoVsync`] or [`AutoNoVsync`] will gracefully fallback to [`Fifo`] when unavailable.
 ///
-/// [`Immediate`] or [`Mailbox`] will panic if not supported by the platform.
+/// [`Immediate`] and [`Mailbox`] fall back to each other, then to [`Fifo`], when not supported
+/// by the platform. Use [`SupportedPresentModes`] to check which modes are available.
 ///
 /// [`Fifo`]: PresentMode::Fifo
 /// [`FifoRelaxed`]: PresentMode::FifoRelaxed
@@ -1269,6 +1322,15 @@ pub enum PresentMode {
     ///
     /// Supported on most platforms except older DX12 and Wayland.
     ///
+    /// On DX12, this presents with tearing allowed (`DXGI_PRESENT_ALLOW_TEARING`) on the flip
+    /// model swap chains wgpu creates, and is only supported if the OS and driver allow tearing
+    /// (Windows 10 1809 or later). Frames are only shown as soon as they are presented when the
+    /// window covers the whole screen in [`WindowMode::BorderlessFullscreen`], or when the
+    /// compositor can flip the window directly to the screen. On variable refresh rate displays,
+    /// the display then follows the frame rate without being capped by the refresh rate. This
+    /// applies to both Win32 and UWP apps. Use [`SupportedPresentModes::allows_tearing`] to check
+    /// for support.
+    ///
     /// This is traditionally called "Vsync Off".
     Immediate = 4,
     /// Presentation frames are kept in a single-frame queue. Every vertical blanking period,
@@ -1327,6 +1389,40 @@ pub enum CompositeAlphaMode {
     Inherit = 4,
 }
 
+/// The dynamic range of the output of a [`Window`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Clone, Default)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum WindowDynamicRange {
+    /// Standard dynamic range output, encoded in sRGB.
+    #[default]
+    Sdr,
+    /// A 16-bit float surface, which the compositor reads as linear extended sRGB (scRGB), where
+    /// `1.0` is its SDR white and brighter values can reach the peak brightness of HDR displays.
+    ///
+    /// This only selects the float swap chain: the rendered colors are presented as they are. No HDR
+    /// output transform is applied, so there is no paper white scaling, and tonemapped colors stay
+    /// within the SDR range. HDR10 output is not supported.
+    ///
+    /// Falls back to [`Sdr`](WindowDynamicRange::Sdr) if the surface doesn't support it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **`Windows`**: Supported with DX12 and Vulkan, when HDR is enabled in the display
+    ///   settings.
+    /// - **`macOS`**: Supported with Metal, on displays with extended dynamic range.
+    /// - Other platforms may not support it.
+    LinearFloat,
+}
+
 /// Defines the way a [`Window`] is displayed.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(
@@ -1467,6 +1563,105 @@ impl Default for EnabledButtons {
 #[derive(Component, Default)]
 pub struct ClosingWindow;
 
+/// Limits how often a [`Window`] is rendered and presented.
+///
+/// Add this to secondary windows that don't need to be redrawn every frame, such as tool panels
+/// or debug views. Frames of the app that fall between two renders of the window skip it, and
+/// leave its previous contents on screen. The window is always rendered when it is resized.
+///
+/// Windows without this component are rendered every frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{Window, WindowRenderRate};
+/// fn spawn_inspector(mut commands: Commands) {
+///     commands.spawn((Window::default(), WindowRenderRate::from_hz(10.0)));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowRenderRate {
+    /// The minimum time between two renders of the window.
+    pub interval: Duration,
+}
+
+impl WindowRenderRate {
+    /// The longest [`interval`](Self::interval) between two renders of a window. Longer intervals
+    /// are shortened to this one.
+    pub const MAX_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// Renders the window at most `hz` times per second.
+    ///
+    /// A rate that isn't positive, e.g. `0.0` or NaN, doesn't limit the rendering of the window.
+    /// Rates below one render per [`MAX_INTERVAL`](Self::MAX_INTERVAL) render at that interval.
+    pub fn from_hz(hz: f64) -> Self {
+        let interval = if hz > 0.0 {
+            Duration::try_from_secs_f64(1.0 / hz).map_or(Self::MAX_INTERVAL, |interval| {
+                interval.min(Self::MAX_INTERVAL)
+            })
+        } else {
+            Duration::ZERO
+        };
+        Self { interval }
+    }
+}
+
+/// The [`PresentMode`]s supported by the surface of a [`Window`].
+///
+/// This is inserted on windows by the renderer once their surface is created, and can be used to
+/// only offer the supported modes in a settings menu. [`Window::present_mode`] can then be changed
+/// at any time, without recreating the window.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_window::{PresentMode, PrimaryWindow, SupportedPresentModes, Window};
+/// fn toggle_mailbox(
+///     mut window: Single<(&mut Window, &SupportedPresentModes), With<PrimaryWindow>>,
+/// ) {
+///     let (window, supported) = &mut *window;
+///     if supported.contains(PresentMode::Mailbox) {
+///         window.present_mode = PresentMode::Mailbox;
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, PartialEq, Clone, Default)
+)]
+pub struct SupportedPresentModes(pub Vec<PresentMode>);
+
+impl SupportedPresentModes {
+    /// Returns `true` if `present_mode` can be used for the window.
+    ///
+    /// [`PresentMode::AutoVsync`] and [`PresentMode::AutoNoVsync`] are always supported, as they
+    /// fall back to a supported mode.
+    pub fn contains(&self, present_mode: PresentMode) -> bool {
+        matches!(
+            present_mode,
+            PresentMode::AutoVsync | PresentMode::AutoNoVsync
+        ) || self.0.contains(&present_mode)
+    }
+
+    /// Returns `true` if the window can present with tearing, to render with an uncapped frame
+    /// rate using [`PresentMode::Immediate`].
+    ///
+    /// On DX12, this is `false` if the OS or driver doesn't allow tearing.
+    pub fn allows_tearing(&self) -> bool {
+        self.0.contains(&PresentMode::Immediate)
+    }
+}
+
 /// The edges of a screen. Corresponds to [`winit::platform::ios::ScreenEdge`].
 ///
 /// # Platform-specific
@@ -1496,6 +1691,23 @@ pub enum ScreenEdge {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn tiny_render_rates_are_clamped() {
+        assert_eq!(
+            WindowRenderRate::from_hz(1e-300).interval,
+            WindowRenderRate::MAX_INTERVAL
+        );
+        assert_eq!(
+            WindowRenderRate::from_hz(f64::MIN_POSITIVE).interval,
+            WindowRenderRate::MAX_INTERVAL
+        );
+        assert_eq!(
+            WindowRenderRate::from_hz(4.0).interval,
+            Duration::from_millis(250)
+        );
+    }
 
     // Checks that `Window::physical_cursor_position` returns the cursor position if it is within
     // the bounds of the window.
@@ -1546,4 +1758,14 @@ mod tests {
         window.set_physical_cursor_position(Some(DVec2::new(400., 600.)));
         assert!(window.physical_cursor_position().is_none());
     }
+
+    #[test]
+    fn supported_present_modes_tearing() {
+        let supported = SupportedPresentModes(vec![PresentMode::Fifo, PresentMode::Mailbox]);
+        assert!(!supported.allows_tearing());
+        assert!(supported.contains(PresentMode::AutoNoVsync));
+
+        let supported = SupportedPresentModes(vec![PresentMode::Fifo, PresentMode::Immediate]);
+        assert!(supported.allows_tearing());
+    }
 }
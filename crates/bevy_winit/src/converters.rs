@@ -3,10 +3,11 @@ use bevy_ecs::entity::Entity;
 use bevy_input::{
     keyboard::{KeyCode, KeyboardInput, NativeKeyCode},
     mouse::MouseButton,
-    touch::{ForceTouch, TouchInput, TouchPhase},
+    touch::{ForceTouch, TouchInput, TouchPhase, TouchProperties},
     ButtonState,
 };
 use bevy_math::{CompassOctant, Vec2};
+use bevy_platform::time::Instant;
 use bevy_window::SystemCursorIcon;
 use bevy_window::{EnabledButtons, WindowLevel, WindowTheme};
 use winit::keyboard::{Key, NamedKey, NativeKey};
@@ -26,6 +27,7 @@ pub fn convert_keyboard_input(
         text: keyboard_input.text.clone(),
         repeat: keyboard_input.repeat,
         window,
+        timestamp: Some(Instant::now()),
     }
 }
 
@@ -55,6 +57,18 @@ pub fn convert_touch_input(
     location: winit::dpi::LogicalPosition<f64>,
     window_entity: Entity,
 ) -> TouchInput {
+    let force = touch_input.force.map(|f| match f {
+        winit::event::Force::Calibrated {
+            force,
+            max_possible_force,
+            altitude_angle,
+        } => ForceTouch::Calibrated {
+            force,
+            max_possible_force,
+            altitude_angle,
+        },
+        winit::event::Force::Normalized(x) => ForceTouch::Normalized(x),
+    });
     TouchInput {
         phase: match touch_input.phase {
             winit::event::TouchPhase::Started => TouchPhase::Started,
@@ -64,18 +78,9 @@ pub fn convert_touch_input(
         },
         position: Vec2::new(location.x as f32, location.y as f32),
         window: window_entity,
-        force: touch_input.force.map(|f| match f {
-            winit::event::Force::Calibrated {
-                force,
-                max_possible_force,
-                altitude_angle,
-            } => ForceTouch::Calibrated {
-                force,
-                max_possible_force,
-                altitude_angle,
-            },
-            winit::event::Force::Normalized(x) => ForceTouch::Normalized(x),
-        }),
+        force,
+        // winit only reports the force of touches.
+        properties: TouchProperties::default(),
         id: touch_input.id,
     }
 }
@@ -24,7 +24,10 @@ use bevy_a11y::AccessibilityRequested;
 use bevy_app::{App, Last, Plugin};
 use bevy_ecs::prelude::*;
 use bevy_window::{exit_on_all_closed, CursorOptions, Window, WindowCreated};
-use system::{changed_cursor_options, changed_windows, check_keyboard_focus_lost, despawn_windows};
+use system::{
+    changed_cursor_options, changed_windows, check_keyboard_focus_lost, clip_cursors,
+    despawn_windows,
+};
 pub use system::{create_monitors, create_windows};
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 pub use winit::platform::web::CustomCursorExtWebSys;
@@ -44,6 +47,13 @@ use crate::{
 pub mod accessibility;
 pub mod converters;
 mod cursor;
+mod mouse_motions;
+mod on_screen_keyboard;
+#[cfg(target_os = "windows")]
+mod pen_haptics;
+#[cfg(target_os = "windows")]
+mod pointer_point;
+mod pointer_samples;
 mod state;
 mod system;
 mod winit_config;
@@ -140,12 +150,24 @@ impl Plugin for WinitPlugin {
                     // so we don't need to care about its ordering relative to `changed_windows`
                     changed_windows.ambiguous_with(exit_on_all_closed),
                     changed_cursor_options,
+                    clip_cursors,
                     despawn_windows,
                     check_keyboard_focus_lost,
                 )
                     .chain(),
             );
 
+        #[cfg(target_os = "windows")]
+        app.add_systems(
+            Last,
+            (
+                system::update_keyboard_layout,
+                on_screen_keyboard::report_on_screen_keyboards.after(changed_windows),
+            ),
+        )
+        .init_non_send_resource::<pen_haptics::PenHaptics>()
+        .add_systems(bevy_app::PostUpdate, pen_haptics::play_pen_haptics);
+
         app.add_plugins(AccessKitPlugin);
         app.add_plugins(cursor::WinitCursorPlugin);
 
@@ -0,0 +1,114 @@
+//! Splits the motions of the mouse into [`MouseMotion`] events, following the
+//! [`MouseMotionSettings`].
+use bevy_input::mouse::{MouseMotion, MouseMotionAccumulation, MouseMotionSettings};
+use bevy_math::Vec2;
+use bevy_platform::time::Instant;
+
+/// The motions of the mouse to send, following the [`MouseMotionSettings`] of the last update.
+#[derive(Default)]
+pub(crate) struct MouseMotions {
+    settings: MouseMotionSettings,
+    /// The motion to send at the next update, with [`MouseMotionAccumulation::PerFrame`].
+    accumulated: Option<MouseMotion>,
+}
+
+impl MouseMotions {
+    /// Returns the [`MouseMotionSettings`] of the last update.
+    pub(crate) fn settings(&self) -> MouseMotionSettings {
+        self.settings
+    }
+
+    /// Returns the event to send for a motion of the mouse received at `timestamp`, or
+    /// accumulates it until the next update.
+    pub(crate) fn push(&mut self, delta: Vec2, timestamp: Instant) -> Option<MouseMotion> {
+        let timestamp = Some(timestamp);
+        match self.settings.accumulation {
+            MouseMotionAccumulation::PerEvent => Some(MouseMotion { delta, timestamp }),
+            MouseMotionAccumulation::PerFrame => {
+                let motion = self.accumulated.get_or_insert(MouseMotion {
+                    delta: Vec2::ZERO,
+                    timestamp,
+                });
+                motion.delta += delta;
+                motion.timestamp = timestamp;
+                None
+            }
+        }
+    }
+
+    /// Returns the motion accumulated since the last update, and follows the `settings` of this
+    /// update.
+    pub(crate) fn update(&mut self, settings: MouseMotionSettings) -> Option<MouseMotion> {
+        self.settings = settings;
+        self.accumulated.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MouseMotions;
+    use bevy_input::mouse::{MouseMotion, MouseMotionAccumulation, MouseMotionSettings};
+    use bevy_math::Vec2;
+    use bevy_platform::time::Instant;
+    use core::time::Duration;
+
+    #[test]
+    fn motions_per_event() {
+        let mut motions = MouseMotions::default();
+        let start = Instant::now();
+        let later = start + Duration::from_millis(1);
+
+        assert_eq!(
+            motions.push(Vec2::X, start),
+            Some(MouseMotion {
+                delta: Vec2::X,
+                timestamp: Some(start),
+            })
+        );
+        assert_eq!(
+            motions.push(Vec2::Y, later),
+            Some(MouseMotion {
+                delta: Vec2::Y,
+                timestamp: Some(later),
+            })
+        );
+        assert_eq!(motions.update(MouseMotionSettings::default()), None);
+    }
+
+    #[test]
+    fn motions_per_frame() {
+        let mut motions = MouseMotions::default();
+        let settings = MouseMotionSettings {
+            accumulation: MouseMotionAccumulation::PerFrame,
+            ..Default::default()
+        };
+        // The settings apply from the next update.
+        assert_eq!(motions.update(settings), None);
+
+        let start = Instant::now();
+        let later = start + Duration::from_millis(1);
+        assert_eq!(motions.push(Vec2::X, start), None);
+        assert_eq!(motions.push(Vec2::new(2.0, 3.0), later), None);
+
+        // The motions of the frame are summed, with the timestamp of the last one.
+        assert_eq!(
+            motions.update(settings),
+            Some(MouseMotion {
+                delta: Vec2::new(3.0, 3.0),
+                timestamp: Some(later),
+            })
+        );
+        assert_eq!(motions.update(settings), None);
+
+        // Switching back to events per motion still sends the motion accumulated before.
+        assert_eq!(motions.push(Vec2::X, later), None);
+        assert_eq!(
+            motions.update(MouseMotionSettings::default()),
+            Some(MouseMotion {
+                delta: Vec2::X,
+                timestamp: Some(later),
+            })
+        );
+        assert!(motions.push(Vec2::X, later).is_some());
+    }
+}
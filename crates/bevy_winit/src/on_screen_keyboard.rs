@@ -0,0 +1,100 @@
+//! Shows the on-screen keyboard of the system for [`Window::on_screen_keyboard`], and reports the
+//! part of the windows it covers.
+use bevy_platform::on_screen_keyboard;
+use winit::{
+    raw_window_handle::{HasWindowHandle, RawWindowHandle},
+    window::Window as WinitWindow,
+};
+
+#[cfg(target_os = "windows")]
+use {
+    crate::{system::CachedWindow, WINIT_WINDOWS},
+    bevy_ecs::{
+        entity::{Entity, EntityHashMap},
+        message::MessageWriter,
+        system::{Local, NonSendMarker, Query},
+    },
+    bevy_math::{Rect, Vec2},
+    bevy_window::{OnScreenKeyboardChanged, Window, WindowEvent},
+};
+
+/// Shows or hides the on-screen keyboard of a window.
+pub(crate) fn set_visible(winit_window: &WinitWindow, visible: bool, ime_enabled: bool) {
+    // The soft keyboard of Android is the IME of the window.
+    #[cfg(target_os = "android")]
+    winit_window.set_ime_allowed(visible || ime_enabled);
+    #[cfg(not(target_os = "android"))]
+    let _ = ime_enabled;
+
+    if let Some(handle) = native_handle(winit_window) {
+        on_screen_keyboard::set_visible(handle, visible);
+    }
+}
+
+/// Returns the native handle of a window, used by the on-screen keyboard and keyboard layout APIs
+/// of the platform.
+pub(crate) fn native_handle(winit_window: &WinitWindow) -> Option<isize> {
+    match winit_window.window_handle().ok()?.as_raw() {
+        RawWindowHandle::Win32(handle) => Some(handle.hwnd.get()),
+        _ => None,
+    }
+}
+
+/// Writes an [`OnScreenKeyboardChanged`] when the part of a window covered by its on-screen
+/// keyboard changes, and resets [`Window::on_screen_keyboard`] when the user dismisses it.
+///
+/// The keyboard slides in and out, and can be moved or dismissed by the user, so it is polled
+/// every frame while it is requested or covers the window.
+#[cfg(target_os = "windows")]
+pub(crate) fn report_on_screen_keyboards(
+    mut windows: Query<(Entity, &mut Window, &mut CachedWindow)>,
+    mut occluded: Local<EntityHashMap<Rect>>,
+    mut keyboard_events: MessageWriter<OnScreenKeyboardChanged>,
+    mut window_events: MessageWriter<WindowEvent>,
+    _non_send_marker: NonSendMarker,
+) {
+    occluded.retain(|entity, _| windows.contains(*entity));
+    WINIT_WINDOWS.with_borrow(|winit_windows| {
+        for (entity, mut window, mut cache) in &mut windows {
+            let previous = occluded.get(&entity).copied();
+            if !window.on_screen_keyboard && previous.is_none() {
+                continue;
+            }
+            let Some(winit_window) = winit_windows.get_window(entity) else {
+                continue;
+            };
+            let rect = native_handle(winit_window)
+                .and_then(on_screen_keyboard::occluded_rect)
+                .and_then(|(x, y, width, height)| {
+                    // The keyboard is in logical pixels of the screen.
+                    let origin = winit_window
+                        .inner_position()
+                        .ok()?
+                        .to_logical::<f32>(winit_window.scale_factor());
+                    let min = Vec2::new(x - origin.x, y - origin.y);
+                    let rect = Rect::from_corners(min, min + Vec2::new(width, height))
+                        .intersect(Rect::new(0.0, 0.0, window.width(), window.height()));
+                    (!rect.is_empty()).then_some(rect)
+                });
+            if rect == previous {
+                continue;
+            }
+            // The keyboard was hidden while it was still requested, so the user dismissed it. The
+            // cache is reset too, as the keyboard is already hidden.
+            if previous.is_some() && rect.is_none() && window.on_screen_keyboard {
+                window.on_screen_keyboard = false;
+                cache.on_screen_keyboard = false;
+            }
+            match rect {
+                Some(rect) => occluded.insert(entity, rect),
+                None => occluded.remove(&entity),
+            };
+            let event = OnScreenKeyboardChanged {
+                window: entity,
+                occluded: rect,
+            };
+            keyboard_events.write(event.clone());
+            window_events.write(event.into());
+        }
+    });
+}
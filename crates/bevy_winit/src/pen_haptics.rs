@@ -0,0 +1,142 @@
+//! Haptic feedback of pens, through `Windows.Devices.Haptics`.
+//!
+//! The touch ids of pens are the pointer ids of Windows, which find their [`PenDevice`].
+use bevy_ecs::{message::MessageReader, system::NonSendMut};
+use bevy_input::haptics::{HapticDevice, HapticEffect, HapticRequest};
+use bevy_log::{debug, warn};
+use bevy_platform::{collections::HashMap, time::Instant};
+use core::time::Duration;
+use windows::{
+    Devices::{
+        Haptics::{
+            KnownSimpleHapticsControllerWaveforms, SimpleHapticsController,
+            SimpleHapticsControllerFeedback,
+        },
+        Input::PenDevice,
+    },
+    Foundation::TimeSpan,
+};
+
+/// The smallest change of the intensity of a [`HapticEffect::Ramp`] sent to a pen.
+const RAMP_STEP: f32 = 0.05;
+
+/// The haptic effects playing on pens, by pointer id.
+///
+/// This is a non-send resource, as the `Windows.Devices.Haptics` objects live on the main thread.
+#[derive(Default)]
+pub(crate) struct PenHaptics(HashMap<u32, PenHaptic>);
+
+struct PenHaptic {
+    controller: SimpleHapticsController,
+    effect: HapticEffect,
+    start: Instant,
+    /// The intensity last sent to the pen, for [`HapticEffect::Ramp`].
+    intensity: f32,
+}
+
+/// Plays the [`HapticRequest`]s of pens.
+pub(crate) fn play_pen_haptics(
+    mut requests: MessageReader<HapticRequest>,
+    mut running: NonSendMut<PenHaptics>,
+) {
+    for request in requests.read() {
+        let HapticDevice::Pen(id) = request.device() else {
+            continue;
+        };
+        let Ok(pointer_id) = u32::try_from(id) else {
+            continue;
+        };
+        match *request {
+            HapticRequest::Stop { .. } => {
+                if let Some(haptic) = running.0.remove(&pointer_id) {
+                    let _ = haptic.controller.StopFeedback();
+                }
+            }
+            HapticRequest::Play { effect, .. } => {
+                let Some(controller) = PenDevice::GetFromPointerId(pointer_id)
+                    .and_then(|pen| pen.SimpleHapticsController())
+                    .ok()
+                else {
+                    debug!(
+                        "Tried to play a haptic effect on pen {id}, but it doesn't support haptics"
+                    );
+                    continue;
+                };
+                let haptic = PenHaptic {
+                    controller,
+                    effect,
+                    start: Instant::now(),
+                    intensity: effect.intensity_at(Duration::ZERO),
+                };
+                if let Err(err) = start(&haptic) {
+                    warn!("Failed to play a haptic effect on pen {id}: {err}");
+                    continue;
+                }
+                running.0.insert(pointer_id, haptic);
+            }
+        }
+    }
+
+    // Follow the intensity of the ramps, and forget the effects that are over.
+    running.0.retain(|_, haptic| {
+        let elapsed = haptic.start.elapsed();
+        if elapsed >= haptic.effect.duration() {
+            return false;
+        }
+        if let HapticEffect::Ramp { .. } = haptic.effect {
+            let intensity = haptic.effect.intensity_at(elapsed);
+            if (intensity - haptic.intensity).abs() >= RAMP_STEP {
+                haptic.intensity = intensity;
+                let remaining = haptic.effect.duration() - elapsed;
+                return send_buzz(&haptic.controller, intensity, remaining).is_ok();
+            }
+        }
+        true
+    });
+}
+
+fn start(haptic: &PenHaptic) -> windows::core::Result<()> {
+    let controller = &haptic.controller;
+    match haptic.effect {
+        HapticEffect::Click => {
+            match find_feedback(controller, KnownSimpleHapticsControllerWaveforms::Click()?) {
+                Some(click) => controller.SendHapticFeedback(&click),
+                None => send_buzz(controller, 1.0, HapticEffect::CLICK_DURATION),
+            }
+        }
+        effect => send_buzz(controller, haptic.intensity, effect.duration()),
+    }
+}
+
+/// Plays the continuous buzz waveform of the pen.
+fn send_buzz(
+    controller: &SimpleHapticsController,
+    intensity: f32,
+    duration: Duration,
+) -> windows::core::Result<()> {
+    let Some(buzz) = find_feedback(
+        controller,
+        KnownSimpleHapticsControllerWaveforms::BuzzContinuous()?,
+    ) else {
+        return Ok(());
+    };
+    controller.SendHapticFeedbackForDuration(
+        &buzz,
+        intensity as f64,
+        TimeSpan {
+            // In units of 100 nanoseconds.
+            Duration: (duration.as_nanos() / 100) as i64,
+        },
+    )
+}
+
+fn find_feedback(
+    controller: &SimpleHapticsController,
+    waveform: u16,
+) -> Option<SimpleHapticsControllerFeedback> {
+    controller
+        .SupportedFeedback()
+        .ok()?
+        .into_iter()
+        .find(|feedback| feedback.Waveform().is_ok_and(|w| w == waveform))
+}
@@ -0,0 +1,28 @@
+//! The tilt, twist and contact size of pens and fingers, through the `PointerPoint` of
+//! `Windows.UI.Input`, as winit only reports their force.
+//!
+//! The touch ids of winit are the pointer ids of Windows, which find their [`PointerPoint`].
+use bevy_input::touch::TouchProperties;
+use bevy_math::Vec2;
+use windows::UI::Input::PointerPoint;
+
+/// Returns the current properties of the pointer with the given touch id, or [`None`] if Windows
+/// doesn't know the pointer.
+pub(crate) fn touch_properties(id: u64) -> Option<TouchProperties> {
+    let point = PointerPoint::GetCurrentPoint(u32::try_from(id).ok()?).ok()?;
+    let properties = point.Properties().ok()?;
+    // The tilt and twist of Windows are in degrees, and are zero for devices without them.
+    let tilt = Vec2::new(
+        properties.XTilt().ok()?.to_radians(),
+        properties.YTilt().ok()?.to_radians(),
+    );
+    let twist = properties.Twist().ok()?;
+    // The contact rectangle is in device-independent pixels, which are logical pixels.
+    let contact = properties.ContactRect().ok()?;
+    let contact_size = Vec2::new(contact.Width, contact.Height);
+    Some(TouchProperties {
+        tilt: (tilt != Vec2::ZERO).then_some(tilt),
+        twist: (twist != 0.0).then(|| twist.to_radians()),
+        contact_size: (contact_size.x > 0.0 && contact_size.y > 0.0).then_some(contact_size),
+    })
+}
@@ -0,0 +1,105 @@
+//! Collects the [`PointerSample`]s of the winit pointer events between two updates.
+use bevy_input::pointer_sample::{PointerSample, PointerSampleSource};
+use bevy_platform::{collections::HashMap, time::Instant};
+use core::time::Duration;
+
+#[derive(Default)]
+pub(crate) struct PointerSamples {
+    /// The samples received since the last update.
+    samples: Vec<PointerSample>,
+    /// The position of each pointer at the last update, if known, and its latest sample, which
+    /// give its velocity over the frame.
+    ///
+    /// winit doesn't report when the events happened, and events delivered together are received
+    /// at almost the same time, so the velocity between two consecutive samples isn't reliable.
+    history: HashMap<PointerSampleSource, (Option<PointerSample>, PointerSample)>,
+    /// The pointers that moved since the last update.
+    moved: Vec<PointerSampleSource>,
+}
+
+impl PointerSamples {
+    /// Records a new position of a pointer.
+    pub(crate) fn push(&mut self, sample: PointerSample) {
+        self.history
+            .entry(sample.source)
+            .and_modify(|(_, latest)| *latest = sample)
+            .or_insert((None, sample));
+        if !self.moved.contains(&sample.source) {
+            self.moved.push(sample.source);
+        }
+        self.samples.push(sample);
+    }
+
+    /// Forgets a pointer that was lifted or left the window, so that it isn't predicted.
+    pub(crate) fn end(&mut self, source: PointerSampleSource) {
+        self.history.remove(&source);
+        self.moved.retain(|moved| *moved != source);
+    }
+
+    /// Returns the samples received since the last update, followed by a sample predicted `ahead`
+    /// of each pointer that moved, from its velocity since the last update at `now`.
+    pub(crate) fn drain(&mut self, ahead: Option<Duration>, now: Instant) -> Vec<PointerSample> {
+        let mut samples = core::mem::take(&mut self.samples);
+        if let Some(ahead) = ahead {
+            samples.extend(self.moved.iter().filter_map(|source| {
+                let (frame_start, latest) = self.history.get(source)?;
+                latest.predict(frame_start.as_ref()?, ahead)
+            }));
+        }
+        // The pointers are where they were last seen at this update.
+        for (frame_start, latest) in self.history.values_mut() {
+            *frame_start = Some(PointerSample {
+                timestamp: now.max(latest.timestamp),
+                ..*latest
+            });
+        }
+        self.moved.clear();
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PointerSamples;
+    use bevy_ecs::entity::Entity;
+    use bevy_input::pointer_sample::{PointerSample, PointerSampleSource};
+    use bevy_math::Vec2;
+    use bevy_platform::time::Instant;
+    use core::time::Duration;
+
+    fn sample(x: f32, timestamp: Instant) -> PointerSample {
+        PointerSample {
+            window: Entity::PLACEHOLDER,
+            source: PointerSampleSource::Mouse,
+            position: Vec2::new(x, 0.0),
+            force: None,
+            properties: Default::default(),
+            timestamp,
+            predicted: false,
+        }
+    }
+
+    #[test]
+    fn predicts_from_the_velocity_over_the_frame() {
+        let start = Instant::now();
+        let frame = Duration::from_millis(16);
+        let ahead = Some(frame);
+        let mut samples = PointerSamples::default();
+
+        samples.push(sample(0.0, start));
+        // The velocity of a pointer is only known from its second frame.
+        assert_eq!(samples.drain(ahead, start).len(), 1);
+
+        // Samples delivered in the same batch, at the end of the frame.
+        samples.push(sample(8.0, start + frame));
+        samples.push(sample(16.0, start + frame + Duration::from_nanos(10)));
+        let drained = samples.drain(ahead, start + frame);
+        assert_eq!(drained.len(), 3);
+        let predicted = drained[2];
+        assert!(predicted.predicted);
+        assert!(predicted.position.abs_diff_eq(Vec2::new(32.0, 0.0), 0.01));
+
+        // Pointers that didn't move aren't predicted.
+        assert!(samples.drain(ahead, start + frame * 2).is_empty());
+    }
+}
@@ -10,7 +10,9 @@ use bevy_ecs::{
 };
 use bevy_input::{
     gestures::*,
-    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    mouse::{MouseButtonInput, MouseMotionSettings, MouseScrollUnit, MouseWheel},
+    pointer_sample::{PointerSample, PointerSampleSettings, PointerSampleSource},
+    touch::TouchPhase,
 };
 use bevy_log::{trace, warn};
 use bevy_math::{ivec2, DVec2, Vec2};
@@ -40,6 +42,8 @@ use bevy_window::{CursorOptions, PrimaryWindow, RawHandleWrapper};
 use crate::{
     accessibility::ACCESS_KIT_ADAPTERS,
     converters, create_windows,
+    mouse_motions::MouseMotions,
+    pointer_samples::PointerSamples,
     system::{create_monitors, CachedWindow, WinitWindowPressedKeys},
     AppSendEvent, CreateMonitorParams, CreateWindowParams, RawWinitWindowEvent, UpdateMode,
     WinitSettings, WinitUserEvent, WINIT_WINDOWS,
@@ -77,6 +81,10 @@ pub(crate) struct WinitAppRunnerState {
     bevy_window_events: Vec<bevy_window::WindowEvent>,
     /// Raw Winit window events to send
     raw_winit_events: Vec<RawWinitWindowEvent>,
+    /// Pointer samples to send
+    pointer_samples: PointerSamples,
+    /// Mouse motions to send
+    mouse_motions: MouseMotions,
 
     message_writer_system_state: SystemState<(
         MessageWriter<'static, WindowResized>,
@@ -121,6 +129,8 @@ impl WinitAppRunnerState {
             startup_forced_updates: 5,
             bevy_window_events: Vec::new(),
             raw_winit_events: Vec::new(),
+            pointer_samples: PointerSamples::default(),
+            mouse_motions: MouseMotions::default(),
             message_writer_system_state,
             scheduled_tick_start: None,
         }
@@ -296,9 +306,27 @@ impl ApplicationHandler<WinitUserEvent> for WinitAppRunnerState {
                             (physical_position.as_vec2() - last_pos) / win.resolution.scale_factor()
                         });
 
+                        if !self.mouse_motions.settings().bypass_pointer_acceleration
+                            && let Some(last_position) = last_position
+                            && let Some(motion) = self
+                                .mouse_motions
+                                .push(physical_position.as_vec2() - last_position, Instant::now())
+                        {
+                            self.bevy_window_events.send(motion);
+                        }
+
                         win.set_physical_cursor_position(Some(physical_position));
                         let position =
                             (physical_position / win.resolution.scale_factor() as f64).as_vec2();
+                        self.pointer_samples.push(PointerSample {
+                            window,
+                            source: PointerSampleSource::Mouse,
+                            position,
+                            force: None,
+                            properties: Default::default(),
+                            timestamp: Instant::now(),
+                            predicted: false,
+                        });
                         self.bevy_window_events.send(CursorMoved {
                             window,
                             position,
@@ -310,6 +338,7 @@ impl ApplicationHandler<WinitUserEvent> for WinitAppRunnerState {
                     }
                     WindowEvent::CursorLeft { .. } => {
                         win.set_physical_cursor_position(None);
+                        self.pointer_samples.end(PointerSampleSource::Mouse);
                         self.bevy_window_events.send(CursorLeft { window });
                     }
                     WindowEvent::MouseInput { state, button, .. } => {
@@ -317,6 +346,7 @@ impl ApplicationHandler<WinitUserEvent> for WinitAppRunnerState {
                             button: converters::convert_mouse_button(button),
                             state: converters::convert_element_state(state),
                             window,
+                            timestamp: Some(Instant::now()),
                         });
                     }
                     WindowEvent::PinchGesture { delta, .. } => {
@@ -356,8 +386,26 @@ impl ApplicationHandler<WinitUserEvent> for WinitAppRunnerState {
                         let location = touch
                             .location
                             .to_logical(win.resolution.scale_factor() as f64);
-                        self.bevy_window_events
-                            .send(converters::convert_touch_input(touch, location, window));
+                        #[cfg_attr(not(target_os = "windows"), expect(unused_mut))]
+                        let mut touch = converters::convert_touch_input(touch, location, window);
+                        #[cfg(target_os = "windows")]
+                        if let Some(properties) = crate::pointer_point::touch_properties(touch.id) {
+                            touch.properties = properties;
+                        }
+                        let source = PointerSampleSource::Touch(touch.id);
+                        self.pointer_samples.push(PointerSample {
+                            window,
+                            source,
+                            position: touch.position,
+                            force: touch.force,
+                            properties: touch.properties,
+                            timestamp: Instant::now(),
+                            predicted: false,
+                        });
+                        if matches!(touch.phase, TouchPhase::Ended | TouchPhase::Canceled) {
+                            self.pointer_samples.end(source);
+                        }
+                        self.bevy_window_events.send(touch);
                     }
                     WindowEvent::Focused(focused) => {
                         win.focused = focused;
@@ -451,9 +499,13 @@ impl ApplicationHandler<WinitUserEvent> for WinitAppRunnerState {
     ) {
         self.device_event_received = true;
 
-        if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
-            let delta = Vec2::new(x as f32, y as f32);
-            self.bevy_window_events.send(MouseMotion { delta });
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = event
+            && self.mouse_motions.settings().bypass_pointer_acceleration
+            && let Some(motion) = self
+                .mouse_motions
+                .push(Vec2::new(x as f32, y as f32), Instant::now())
+        {
+            self.bevy_window_events.send(motion);
         }
     }
 
@@ -772,8 +824,21 @@ impl WinitAppRunnerState {
     }
 
     fn forward_bevy_events(&mut self) {
+        let mouse_motion_settings = self
+            .world()
+            .get_resource::<MouseMotionSettings>()
+            .copied()
+            .unwrap_or_default();
+        if let Some(motion) = self.mouse_motions.update(mouse_motion_settings) {
+            self.bevy_window_events.send(motion);
+        }
         let raw_winit_events = self.raw_winit_events.drain(..).collect::<Vec<_>>();
         let window_events = self.bevy_window_events.drain(..).collect::<Vec<_>>();
+        let prediction = self
+            .world()
+            .get_resource::<PointerSampleSettings>()
+            .and_then(|settings| settings.prediction);
+        let pointer_samples = self.pointer_samples.drain(prediction, Instant::now());
         let world = self.world_mut();
 
         if !raw_winit_events.is_empty() {
@@ -782,6 +847,12 @@ impl WinitAppRunnerState {
                 .write_batch(raw_winit_events);
         }
 
+        if !pointer_samples.is_empty()
+            && let Some(mut messages) = world.get_resource_mut::<Messages<PointerSample>>()
+        {
+            messages.write_batch(pointer_samples);
+        }
+
         for window_event in window_events.iter() {
             match window_event.clone() {
                 BevyWindowEvent::AppLifecycle(e) => {
@@ -826,6 +897,9 @@ impl WinitAppRunnerState {
                 BevyWindowEvent::WindowOccluded(e) => {
                     world.write_message(e);
                 }
+                BevyWindowEvent::OnScreenKeyboardChanged(e) => {
+                    world.write_message(e);
+                }
                 BevyWindowEvent::WindowResized(e) => {
                     world.write_message(e);
                 }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
-    entity::Entity,
+    entity::{Entity, EntityHashMap},
     lifecycle::RemovedComponents,
     message::MessageWriter,
     prelude::{Changed, Component},
@@ -11,20 +11,22 @@ use bevy_ecs::{
 };
 use bevy_input::keyboard::{Key, KeyCode, KeyboardFocusLost, KeyboardInput};
 use bevy_window::{
-    ClosingWindow, CursorOptions, Monitor, PrimaryMonitor, RawHandleWrapper, VideoMode, Window,
-    WindowClosed, WindowClosing, WindowCreated, WindowEvent, WindowFocused, WindowMode,
-    WindowResized, WindowWrapper,
+    ClosingWindow, CursorGrabMode, CursorOptions, Monitor, PrimaryMonitor, RawHandleWrapper,
+    VideoMode, Window, WindowClosed, WindowClosing, WindowCreated, WindowEvent, WindowFocused,
+    WindowMode, WindowResized, WindowWrapper,
 };
 use tracing::{error, info, warn};
 
 use winit::{
     dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     event_loop::ActiveEventLoop,
+    window::CursorGrabMode as WinitCursorGrabMode,
 };
 
 use bevy_app::AppExit;
 use bevy_ecs::{prelude::MessageReader, query::With, system::Res};
-use bevy_math::{IVec2, UVec2};
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_platform::time::Instant;
 #[cfg(target_os = "ios")]
 use winit::platform::ios::WindowExtIOS;
 #[cfg(target_arch = "wasm32")]
@@ -99,6 +101,14 @@ pub fn create_windows(
                     }
                 }
 
+                if window.on_screen_keyboard {
+                    crate::on_screen_keyboard::set_visible(
+                        winit_window,
+                        true,
+                        window.ime_enabled,
+                    );
+                }
+
                 #[cfg(target_arch = "wasm32")]
                 {
                     if window.fit_canvas_to_parent {
@@ -166,6 +176,7 @@ pub(crate) fn check_keyboard_focus_lost(
                     repeat: false,
                     window,
                     text: None,
+                    timestamp: Some(Instant::now()),
                 };
                 window_event_writer.write(WindowEvent::KeyboardInput(event.clone()));
                 keyboard_input_writer.write(event);
@@ -174,6 +185,57 @@ pub(crate) fn check_keyboard_focus_lost(
     }
 }
 
+/// Fills the [`KeyboardLayout`](bevy_input::keyboard::KeyboardLayout) from the keyboard layout of
+/// the system, and writes a [`KeyboardLayoutChanged`](bevy_input::keyboard::KeyboardLayoutChanged)
+/// when the user switches layouts.
+///
+/// Keyboard layouts are per thread on Windows, so the layout of the thread owning the windows of
+/// the app is read.
+#[cfg(target_os = "windows")]
+pub(crate) fn update_keyboard_layout(
+    mut current_layout: Local<Option<u64>>,
+    mut layout: bevy_ecs::system::ResMut<bevy_input::keyboard::KeyboardLayout>,
+    mut layout_changed_writer: MessageWriter<bevy_input::keyboard::KeyboardLayoutChanged>,
+    _non_send_marker: NonSendMarker,
+) {
+    use winit::{keyboard::PhysicalKey, platform::scancode::PhysicalKeyExtScancode};
+
+    // All the windows are created by the thread of the event loop.
+    let Some(system_layout) = WINIT_WINDOWS.with_borrow(|winit_windows| {
+        winit_windows
+            .windows
+            .values()
+            .find_map(|window| crate::on_screen_keyboard::native_handle(window))
+            .and_then(bevy_platform::keyboard::keyboard_layout)
+    }) else {
+        return;
+    };
+    if Some(system_layout) == *current_layout {
+        return;
+    }
+    let first_layout = current_layout.is_none();
+    *current_layout = Some(system_layout);
+
+    layout.clear();
+    layout.set_from_system(true);
+    // The extended keys, with the `0xE0` prefix, don't type characters.
+    for scan_code in 1..0x80 {
+        let Some(character) =
+            bevy_platform::keyboard::scan_code_character(system_layout, scan_code)
+        else {
+            continue;
+        };
+        let key_code =
+            crate::converters::convert_physical_key_code(PhysicalKey::from_scancode(scan_code));
+        if !matches!(key_code, KeyCode::Unidentified(_)) {
+            layout.insert(key_code, Key::Character(character.to_string().into()));
+        }
+    }
+    if !first_layout {
+        layout_changed_writer.write(bevy_input::keyboard::KeyboardLayoutChanged);
+    }
+}
+
 /// Synchronize available monitors as reported by [`winit`] with [`Monitor`] entities in the world.
 pub fn create_monitors(
     event_loop: &ActiveEventLoop,
@@ -293,7 +355,7 @@ pub(crate) struct CachedCursorOptions(CursorOptions);
 ///
 /// # Notes
 ///
-/// - [`Window::present_mode`] and [`Window::composite_alpha_mode`] changes are handled by the `bevy_render` crate.
+/// - [`Window::present_mode`], [`Window::composite_alpha_mode`] and [`Window::dynamic_range`] changes are handled by the `bevy_render` crate.
 /// - [`Window::transparent`] cannot be changed after the window is created.
 /// - [`Window::canvas`] cannot be changed after the window is created.
 /// - [`Window::focused`] cannot be manually changed to `false` after the window is created.
@@ -508,6 +570,14 @@ pub(crate) fn changed_windows(
                 winit_window.set_ime_allowed(window.ime_enabled);
             }
 
+            if window.on_screen_keyboard != cache.on_screen_keyboard {
+                crate::on_screen_keyboard::set_visible(
+                    winit_window,
+                    window.on_screen_keyboard,
+                    window.ime_enabled,
+                );
+            }
+
             if window.ime_position != cache.ime_position {
                 winit_window.set_ime_cursor_area(
                     LogicalPosition::new(window.ime_position.x, window.ime_position.y),
@@ -621,6 +691,98 @@ pub(crate) fn changed_cursor_options(
     });
 }
 
+/// Confines the cursor to the [`CursorOptions::clip_rect`] of the focused window.
+///
+/// The clip is applied again every frame, as the system releases it when the window is moved or
+/// another window is activated. Platforms that can't confine the cursor move it back into the
+/// rectangle instead, confining it to the window in between where they can. If the cursor still
+/// left the window, it is moved back to where it last was in the rectangle.
+pub(crate) fn clip_cursors(
+    windows: Query<(Entity, &Window, &CursorOptions)>,
+    mut clipped: Local<bool>,
+    mut emulated: Local<EntityHashMap<Vec2>>,
+    _non_send_marker: NonSendMarker,
+) {
+    WINIT_WINDOWS.with_borrow(|winit_windows| {
+        let mut clip = None;
+        let mut still_emulated = EntityHashMap::default();
+        for (entity, window, cursor_options) in &windows {
+            let Some(rect) = cursor_options.clip_rect else {
+                continue;
+            };
+            if !window.focused || cursor_options.grab_mode == CursorGrabMode::Locked {
+                continue;
+            }
+            let Some(winit_window) = winit_windows.get_window(entity) else {
+                continue;
+            };
+            let size = window.physical_size().as_vec2();
+            let scale_factor = window.resolution.scale_factor();
+            let min = (rect.min * scale_factor).clamp(Vec2::ZERO, size);
+            let max = (rect.max * scale_factor).clamp(min, size);
+
+            if cfg!(target_os = "windows") {
+                let Ok(origin) = winit_window.inner_position() else {
+                    continue;
+                };
+                clip = Some((
+                    origin.x + min.x as i32,
+                    origin.y + min.y as i32,
+                    origin.x + max.x as i32,
+                    origin.y + max.y as i32,
+                ));
+            } else {
+                let position = window.physical_cursor_position();
+                // The cursor isn't in the window when it moved out of it faster than it is
+                // moved back.
+                let Some(target) = position.or_else(|| emulated.get(&entity).copied()) else {
+                    continue;
+                };
+                let clamped = target.clamp(min, (max - Vec2::ONE).max(min));
+                if position != Some(clamped) {
+                    let _ = winit_window.set_cursor_position(PhysicalPosition::new(
+                        clamped.x as f64,
+                        clamped.y as f64,
+                    ));
+                }
+                // macOS can't confine the cursor to the window.
+                if !cfg!(target_os = "macos") {
+                    let _ = winit_window.set_cursor_grab(WinitCursorGrabMode::Confined);
+                }
+                still_emulated.insert(entity, clamped);
+            }
+        }
+
+        // Restore the grab mode of the windows that stopped clipping the cursor.
+        for entity in emulated.keys() {
+            if !still_emulated.contains_key(entity)
+                && let Ok((_, _, cursor_options)) = windows.get(*entity)
+                && let Some(winit_window) = winit_windows.get_window(*entity)
+            {
+                let _ = crate::winit_windows::attempt_grab(winit_window, cursor_options.grab_mode);
+            }
+        }
+        *emulated = still_emulated;
+
+        if clip.is_some() {
+            *clipped = bevy_platform::cursor::clip_cursor(clip);
+        } else if *clipped {
+            bevy_platform::cursor::clip_cursor(None);
+            *clipped = false;
+            // Releasing the clip also releases the windows confining the cursor themselves.
+            for (entity, window, cursor_options) in &windows {
+                if window.focused
+                    && cursor_options.grab_mode == CursorGrabMode::Confined
+                    && let Some(winit_window) = winit_windows.get_window(entity)
+                {
+                    let _ =
+                        crate::winit_windows::attempt_grab(winit_window, cursor_options.grab_mode);
+                }
+            }
+        }
+    });
+}
+
 /// This keeps track of which keys are pressed on each window.
 /// When a window is unfocused, this is used to send key release events for all the currently held keys.
 #[derive(Default, Component)]
@@ -4,7 +4,7 @@ use bevy::{
     input::keyboard::Key,
     prelude::*,
     render::{
-        error_handler::{RenderErrorHandler, RenderErrorPolicy},
+        error_handler::{RenderControl, RenderErrorHandler, RenderErrorPolicy},
         extract_resource::{ExtractResource, ExtractResourcePlugin},
         render_resource::{
             BufferDescriptor, BufferUsages, CommandEncoderDescriptor, ComputePassDescriptor,
@@ -13,6 +13,7 @@ use bevy::{
             TextureFormat, TextureUsages,
         },
         renderer::{RenderDevice, RenderQueue},
+        settings::Backends,
         Render, RenderApp,
     },
 };
@@ -74,7 +75,9 @@ fn setup(
             Press 2 to panic on error.\n\
             Press 3 to signals app exit on error.\n\
             Press 4 to keeps the app alive, but stops rendering further on error.\n\
-            Press 5 to attempt renderer recovery.\n\
+            Press 5 to attempt renderer recovery, up to 3 times.\n\
+            Press 6 to fall back to the next backend on error.\n\
+            Press R to resume rendering after it was stopped.\n\
             ",
         ),
         Node {
@@ -107,6 +110,7 @@ fn input(
     input: Res<ButtonInput<Key>>,
     mut error: ResMut<RenderError>,
     mut handler: ResMut<RenderErrorHandler>,
+    mut control: ResMut<RenderControl>,
 ) {
     *error = RenderError::None;
     if input.just_pressed(Key::Character("o".into())) {
@@ -123,22 +127,38 @@ fn input(
     }
 
     if input.just_pressed(Key::Character("1".into())) {
-        *handler = RenderErrorHandler(|_, _, _| RenderErrorPolicy::Ignore);
+        *handler = RenderErrorHandler::new(|_| RenderErrorPolicy::Ignore);
     }
     if input.just_pressed(Key::Character("2".into())) {
-        *handler = RenderErrorHandler(|error, _, _| panic!("Rendering error {error:?}"));
+        *handler = RenderErrorHandler::new(|context| panic!("Rendering error {:?}", context.error));
     }
     if input.just_pressed(Key::Character("3".into())) {
-        *handler = RenderErrorHandler(|_, main_world, _| {
-            main_world.write_message(AppExit::error());
+        *handler = RenderErrorHandler::new(|context| {
+            context.main_world.write_message(AppExit::error());
             RenderErrorPolicy::StopRendering
         });
     }
     if input.just_pressed(Key::Character("4".into())) {
-        *handler = RenderErrorHandler(|_, _, _| RenderErrorPolicy::StopRendering);
+        *handler = RenderErrorHandler::new(|_| RenderErrorPolicy::StopRendering);
     }
     if input.just_pressed(Key::Character("5".into())) {
-        *handler = RenderErrorHandler(|_, _, _| RenderErrorPolicy::Recover(default()));
+        // Handlers can keep state, here to stop rendering if recovering keeps failing.
+        let mut recoveries = 0;
+        *handler = RenderErrorHandler::new(move |_| {
+            recoveries += 1;
+            if recoveries <= 3 {
+                RenderErrorPolicy::Recover(default())
+            } else {
+                RenderErrorPolicy::StopRendering
+            }
+        });
+    }
+    if input.just_pressed(Key::Character("6".into())) {
+        *handler = RenderErrorHandler::new(|_| RenderErrorPolicy::FallbackBackend(Backends::all()));
+    }
+
+    if input.just_pressed(Key::Character("r".into())) && control.is_stopped() {
+        control.resume();
     }
 }
 
@@ -32,8 +32,7 @@ fn main() {
                 // This is just overriding the default paths to scope this to the correct example folder
                 // You can generally skip this in your own projects
                 file_path: "examples/asset/processing/assets".to_string(),
-                processed_file_path: "examples/asset/processing/imported_assets/Default"
-                    .to_string(),
+                processed_file_path: "examples/asset/processing/imported_assets/Default".into(),
                 ..default()
             }),
             TextPlugin,
@@ -3,6 +3,7 @@
 use bevy::{
     dev_tools::fps_overlay::{FpsOverlayConfig, FpsOverlayPlugin, FrameTimeGraphConfig},
     prelude::*,
+    render::diagnostic::RenderDiagnosticsPlugin,
     text::FontSmoothing,
 };
 
@@ -17,6 +18,8 @@ fn main() {
     App::new()
         .add_plugins((
             DefaultPlugins,
+            // Measures the GPU time of each frame, which the overlay shows next to the FPS
+            RenderDiagnosticsPlugin,
             FpsOverlayPlugin {
                 config: FpsOverlayConfig {
                     text_config: TextFont {
@@ -40,6 +43,8 @@ fn main() {
                         // The target fps
                         target_fps: 144.0,
                     },
+                    // We can also show the GPU time of each frame, when the backend supports it
+                    show_gpu_time: true,
                 },
             },
         ))
@@ -60,7 +65,8 @@ fn setup(mut commands: Commands) {
             "Press 2 to decrease the overlay size.\n",
             "Press 3 to increase the overlay size.\n",
             "Press 4 to toggle the text visibility.\n",
-            "Press 5 to toggle the frame time graph."
+            "Press 5 to toggle the frame time graph.\n",
+            "Press 6 to toggle the GPU time."
         )),
         Node {
             position_type: PositionType::Absolute,
@@ -100,4 +106,7 @@ fn customize_config(input: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<FpsOve
     if input.just_released(KeyCode::Digit5) {
         overlay.frame_time_graph_config.enabled = !overlay.frame_time_graph_config.enabled;
     }
+    if input.just_pressed(KeyCode::Digit6) {
+        overlay.show_gpu_time = !overlay.show_gpu_time;
+    }
 }
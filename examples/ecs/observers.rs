@@ -1,6 +1,7 @@
 //! Demonstrates how to observe events: both component lifecycle events and custom events.
 
 use bevy::{
+    camera::CoordinateSpaces,
     platform::collections::{HashMap, HashSet},
     prelude::*,
 };
@@ -200,10 +201,10 @@ fn handle_click(
     };
 
     let (camera, camera_transform) = *camera;
-    if let Some(pos) = windows
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor).ok())
-        .map(|ray| ray.origin.truncate())
+    if let Some(spaces) = CoordinateSpaces::new(camera, camera_transform)
+        && let Some(pos) = windows
+            .cursor_position()
+            .and_then(|cursor| spaces.logical_to_world_2d(cursor).ok())
         && mouse_button_input.just_pressed(MouseButton::Left)
     {
         commands.trigger(ExplodeMines { pos, radius: 1.0 });
@@ -57,11 +57,23 @@ fn gamepad_system(
                     strong_motor: 0.5,
                     // intensity of high-frequency motor, usually on the right-hand side
                     weak_motor: 0.25,
+                    ..GamepadRumbleIntensity::ZERO
                 },
                 duration: Duration::from_secs(5),
             });
         }
 
+        if gamepad.just_pressed(GamepadButton::RightTrigger2) {
+            info!(
+                "Right trigger: rumble the trigger motors for 1 second, on Windows with impulse triggers"
+            );
+            rumble_requests.write(GamepadRumbleRequest::Add {
+                gamepad: entity,
+                duration: Duration::from_secs(1),
+                intensity: GamepadRumbleIntensity::TRIGGERS_MAX,
+            });
+        }
+
         if gamepad.just_pressed(GamepadButton::Start) {
             info!("Start button: Interrupt the current rumble");
             rumble_requests.write(GamepadRumbleRequest::Stop { gamepad: entity });
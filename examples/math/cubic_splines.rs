@@ -2,6 +2,7 @@
 
 use bevy::{
     app::{App, Startup, Update},
+    camera::CoordinateSpaces,
     color::*,
     ecs::system::Commands,
     gizmos::gizmos::Gizmos,
@@ -337,12 +338,15 @@ fn handle_mouse_press(
                 };
 
                 let (camera, camera_transform) = *camera;
+                let Some(spaces) = CoordinateSpaces::new(camera, camera_transform) else {
+                    continue;
+                };
 
                 // Convert the starting point and end point (current mouse pos) into world coords:
-                let Ok(point) = camera.viewport_to_world_2d(camera_transform, start) else {
+                let Ok(point) = spaces.logical_to_world_2d(start) else {
                     continue;
                 };
-                let Ok(end_point) = camera.viewport_to_world_2d(camera_transform, mouse_pos) else {
+                let Ok(end_point) = spaces.logical_to_world_2d(mouse_pos) else {
                     continue;
                 };
                 let tangent = end_point - point;
@@ -373,13 +377,16 @@ fn draw_edit_move(
     };
 
     let (camera, camera_transform) = *camera;
+    let Some(spaces) = CoordinateSpaces::new(camera, camera_transform) else {
+        return;
+    };
 
-    // Resources store data in viewport coordinates, so we need to convert to world coordinates
-    // to display them:
-    let Ok(start) = camera.viewport_to_world_2d(camera_transform, start) else {
+    // Resources store data in logical window coordinates, so we need to convert to world
+    // coordinates to display them:
+    let Ok(start) = spaces.logical_to_world_2d(start) else {
         return;
     };
-    let Ok(end) = camera.viewport_to_world_2d(camera_transform, mouse_pos) else {
+    let Ok(end) = spaces.logical_to_world_2d(mouse_pos) else {
         return;
     };
 